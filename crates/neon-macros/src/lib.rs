@@ -1,5 +1,7 @@
 //! Procedural macros supporting [Neon](https://docs.rs/neon/latest/neon/)
 
+mod try_from_js;
+
 #[proc_macro_attribute]
 /// Marks a function as the main entry point for initialization in
 /// a Neon module.
@@ -18,17 +20,64 @@
 /// }
 /// ```
 ///
+/// The minimum Node-API version and, optionally, the minimum Node version the addon
+/// requires may be declared as arguments. If the runtime is older, loading the module
+/// throws an `Error` naming the required versions instead of calling the function.
+/// The Node-API version defaults to the highest `napi-*` feature enabled for Neon.
+///
+/// ```ignore
+/// #[neon::main(min_napi = 8, min_node = "18.0")]
+/// fn main(mut cx: ModuleContext) -> NeonResult<()> {
+///     Ok(())
+/// }
+/// ```
+///
+/// `#[neon::main]` runs once in each context that loads the module, e.g., once on the
+/// main thread and again in each worker thread. Global Rust state, such as a logger or
+/// a thread pool, may instead be initialized by a function named with `process_init`,
+/// which is called exactly once per process, before the first call to the main function.
+/// Contexts loading the module concurrently wait for it to complete, so the main
+/// function may always assume it has already run. If it panics, loading the module
+/// throws an `Error` in every context.
+///
+/// ```ignore
+/// fn init_logger() {
+///     env_logger::init();
+/// }
+///
+/// #[neon::main(process_init = "init_logger")]
+/// fn main(mut cx: ModuleContext) -> NeonResult<()> {
+///     log::info!("loaded in a new context");
+///
+///     Ok(())
+/// }
+/// ```
+///
 /// If multiple functions are marked with `#[neon::main]`, there may be a compile error:
 ///
 /// ```sh
 /// error: symbol `napi_register_module_v1` is already defined
 /// ```
 pub fn main(
-    _attr: proc_macro::TokenStream,
+    attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(attr as syn::AttributeArgs);
     let input = syn::parse_macro_input!(item as syn_mid::ItemFn);
 
+    let MainArgs {
+        requirements,
+        process_init,
+    } = match main_args(args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let process_init = match process_init {
+        Some(path) => quote::quote!(Some(#path as fn())),
+        None => quote::quote!(None),
+    };
+
     let attrs = &input.attrs;
     let vis = &input.vis;
     let sig = &input.sig;
@@ -43,7 +92,7 @@ pub fn main(
                 env: *mut std::ffi::c_void,
                 m: *mut std::ffi::c_void,
             ) -> *mut std::ffi::c_void {
-                neon::macro_internal::initialize_module(env, m, #name);
+                neon::macro_internal::initialize_module(env, m, &#requirements, #process_init, #name);
                 m
             }
 
@@ -52,3 +101,120 @@ pub fn main(
     )
     .into()
 }
+
+struct MainArgs {
+    // A `neon::runtime::Requirements` expression
+    requirements: proc_macro2::TokenStream,
+    process_init: Option<syn::Path>,
+}
+
+// Parses the `#[neon::main]` arguments
+fn main_args(args: syn::AttributeArgs) -> syn::Result<MainArgs> {
+    let mut requirements = quote::quote!(neon::runtime::Requirements::new(env!("CARGO_PKG_NAME")));
+    let mut process_init = None;
+
+    for arg in args {
+        let pair = match arg {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(pair)) => pair,
+            arg => return Err(syn::Error::new_spanned(arg, "expected `key = value`")),
+        };
+
+        let lit = &pair.lit;
+
+        requirements = match (pair.path.get_ident(), lit) {
+            (Some(key), syn::Lit::Int(_)) if key == "min_napi" => {
+                quote::quote!(#requirements.napi_version(#lit))
+            }
+            (Some(key), syn::Lit::Str(_)) if key == "min_node" => {
+                quote::quote!(#requirements.node_version(#lit))
+            }
+            (Some(key), syn::Lit::Str(s)) if key == "process_init" => {
+                if process_init.is_some() {
+                    return Err(syn::Error::new_spanned(pair, "duplicate `process_init`"));
+                }
+
+                process_init = Some(s.parse::<syn::Path>()?);
+                requirements
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    pair,
+                    "expected `min_napi = <integer>`, `min_node = \"<version>\"` or `process_init = \"<function>\"`",
+                ))
+            }
+        };
+    }
+
+    Ok(MainArgs {
+        requirements,
+        process_init,
+    })
+}
+
+#[proc_macro_derive(TryFromJs, attributes(neon))]
+/// Derives `TryFromJs` for a struct with named fields, extracting it from a
+/// JavaScript object, e.g., an options object.
+///
+/// Each field is extracted from the property of the same name with its own
+/// `TryFromJs` implementation. A missing or `undefined` property is an error unless
+/// the field is optional, e.g., an `Option`. Every field is checked, and all errors
+/// are thrown together as a single `TypeError` naming the path of each field, e.g.,
+/// `invalid Options: timeout: missing required field; retry.max: must be at most 10`.
+///
+/// ```ignore
+/// use neon::types::extract::TryFromJs;
+///
+/// #[derive(TryFromJs)]
+/// #[neon(deny_unknown_fields, rename_all = "camelCase")]
+/// struct Options {
+///     timeout: f64,
+///     #[neon(default = 3, range(min = 0, max = 10))]
+///     max_retries: f64,
+///     #[neon(default, with = "parse_mode")]
+///     mode: Mode,
+/// }
+///
+/// #[derive(Default)]
+/// enum Mode {
+///     #[default]
+///     Fast,
+///     Safe,
+/// }
+///
+/// fn parse_mode(mode: String) -> Result<Mode, String> {
+///     match mode.as_str() {
+///         "fast" => Ok(Mode::Fast),
+///         "safe" => Ok(Mode::Safe),
+///         _ => Err(format!("expected \"fast\" or \"safe\", found {:?}", mode)),
+///     }
+/// }
+/// ```
+///
+/// Attributes on the struct:
+///
+/// * `#[neon(deny_unknown_fields)]` reports an error for each own enumerable property
+///   that does not match a field.
+/// * `#[neon(rename_all = "camelCase")]` names properties in camel case, e.g.,
+///   `max_retries` is extracted from `maxRetries`.
+///
+/// Attributes on fields:
+///
+/// * `#[neon(default)]` uses `Default::default()` when the property is `undefined`.
+/// * `#[neon(default = <literal>)]` converts the literal to the field type with `From`.
+/// * `#[neon(range(min = <number>, max = <number>))]` checks a numeric value; either
+///   bound may be omitted.
+/// * `#[neon(with = "<function>")]` extracts the value as the argument type of the
+///   function and converts it with the function, which returns a `Result` whose error
+///   is reported with `Display`.
+/// * `#[neon(rename = "<key>")]` names the property.
+///
+/// Nested structs that derive `TryFromJs` report their errors with the path of the
+/// field, e.g., `retry.max`.
+pub fn derive_try_from_js(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+
+    match try_from_js::derive(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
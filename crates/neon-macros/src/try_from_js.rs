@@ -0,0 +1,346 @@
+//! Implementation of `#[derive(TryFromJs)]`
+
+pub(crate) fn derive(input: syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`#[derive(TryFromJs)]` does not support generic structs",
+        ));
+    }
+
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`#[derive(TryFromJs)]` only supports structs with named fields",
+            ))
+        }
+    };
+
+    let container = ContainerArgs::parse(&input.attrs)?;
+    let fields = fields
+        .iter()
+        .map(|field| FieldArgs::parse(field, &container))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let name_str = name.to_string();
+    let idents = fields.iter().map(|field| field.ident).collect::<Vec<_>>();
+    let vars = fields
+        .iter()
+        .map(|field| quote::format_ident!("__neon_field_{}", field.ident))
+        .collect::<Vec<_>>();
+    let extracts = fields.iter().map(FieldArgs::extract);
+
+    let deny_unknown_fields = if container.deny_unknown_fields {
+        let keys = fields.iter().map(|field| &field.key);
+
+        quote::quote!(errors.deny_unknown_fields(cx, object, path, &[#(#keys),*])?;)
+    } else {
+        quote::quote!()
+    };
+
+    Ok(quote::quote!(
+        impl<'cx> neon::types::extract::TryFromJs<'cx> for #name {
+            fn try_from_js<C: neon::context::Context<'cx>>(
+                cx: &mut C,
+                v: neon::handle::Handle<'cx, neon::types::JsValue>,
+            ) -> neon::result::NeonResult<Self> {
+                let mut errors = neon::macro_internal::FieldErrors::default();
+
+                match <Self as neon::types::extract::TryFromJs<'cx>>::try_from_js_field(
+                    cx,
+                    v,
+                    "",
+                    &mut errors,
+                )? {
+                    Some(v) => Ok(v),
+                    None => errors.throw(cx, #name_str),
+                }
+            }
+
+            fn try_from_js_field<C: neon::context::Context<'cx>>(
+                cx: &mut C,
+                v: neon::handle::Handle<'cx, neon::types::JsValue>,
+                path: &str,
+                errors: &mut neon::macro_internal::FieldErrors,
+            ) -> neon::result::NeonResult<Option<Self>> {
+                use neon::object::Object as _;
+
+                let start = errors.len();
+                let object = match errors.object(cx, v, path) {
+                    Some(object) => object,
+                    None => return Ok(None),
+                };
+
+                #(let #vars = #extracts;)*
+
+                #deny_unknown_fields
+
+                match (#(#vars,)*) {
+                    (#(Some(#vars),)*) if errors.len() == start => Ok(Some(Self {
+                        #(#idents: #vars,)*
+                    })),
+                    _ => Ok(None),
+                }
+            }
+        }
+    ))
+}
+
+struct ContainerArgs {
+    deny_unknown_fields: bool,
+    rename_all_camel_case: bool,
+}
+
+impl ContainerArgs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut args = Self {
+            deny_unknown_fields: false,
+            rename_all_camel_case: false,
+        };
+
+        for meta in neon_args(attrs)? {
+            match &meta {
+                syn::Meta::Path(path) if path.is_ident("deny_unknown_fields") => {
+                    args.deny_unknown_fields = true;
+                }
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) if path.is_ident("rename_all") && s.value() == "camelCase" => {
+                    args.rename_all_camel_case = true;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "expected `deny_unknown_fields` or `rename_all = \"camelCase\"`",
+                    ))
+                }
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+struct FieldArgs<'a> {
+    ident: &'a syn::Ident,
+    ty: &'a syn::Type,
+    // Name of the JavaScript property
+    key: String,
+    default: Option<DefaultValue>,
+    range: Option<(Option<syn::Lit>, Option<syn::Lit>)>,
+    with: Option<syn::Path>,
+}
+
+enum DefaultValue {
+    // `#[neon(default)]`
+    Trait,
+    // `#[neon(default = <literal>)]`
+    Value(syn::Lit),
+}
+
+impl<'a> FieldArgs<'a> {
+    fn parse(field: &'a syn::Field, container: &ContainerArgs) -> syn::Result<Self> {
+        // Unwrap safety: only structs with named fields are derived
+        let ident = field.ident.as_ref().unwrap();
+        let name = ident.to_string();
+        let name = name.strip_prefix("r#").unwrap_or(&name);
+        let mut args = Self {
+            ident,
+            ty: &field.ty,
+            key: if container.rename_all_camel_case {
+                camel_case(name)
+            } else {
+                name.to_string()
+            },
+            default: None,
+            range: None,
+            with: None,
+        };
+
+        for meta in neon_args(&field.attrs)? {
+            match &meta {
+                syn::Meta::Path(path) if path.is_ident("default") => {
+                    args.default = Some(DefaultValue::Trait);
+                }
+                syn::Meta::NameValue(pair) if pair.path.is_ident("default") => {
+                    args.default = Some(DefaultValue::Value(pair.lit.clone()));
+                }
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) if path.is_ident("with") => {
+                    args.with = Some(s.parse()?);
+                }
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) if path.is_ident("rename") => {
+                    args.key = s.value();
+                }
+                syn::Meta::List(list) if list.path.is_ident("range") => {
+                    args.range = Some(range_args(list)?);
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "expected `default`, `default = <literal>`, `range(min = <number>, max = <number>)`, `with = \"<function>\"` or `rename = \"<key>\"`",
+                    ))
+                }
+            }
+        }
+
+        Ok(args)
+    }
+
+    // An expression evaluating to `Option<T>`, where `None` indicates that errors were
+    // recorded
+    fn extract(&self) -> proc_macro2::TokenStream {
+        let key = &self.key;
+        let ty = self.ty;
+
+        let mut extract = match &self.with {
+            Some(with) => quote::quote!(
+                neon::macro_internal::extract_field_with(cx, v, &path, errors, #with)?
+            ),
+            None => quote::quote!(
+                neon::macro_internal::extract_field::<C, #ty>(cx, v, &path, errors)?
+            ),
+        };
+
+        if let Some((min, max)) = &self.range {
+            let min = optional_number(min);
+            let max = optional_number(max);
+
+            extract = quote::quote!(
+                #extract.and_then(|v| {
+                    neon::macro_internal::check_range(v, #min, #max, &path, errors)
+                })
+            );
+        }
+
+        let default = match &self.default {
+            Some(DefaultValue::Trait) => Some(quote::quote!(::std::default::Default::default())),
+            Some(DefaultValue::Value(lit)) => {
+                Some(quote::quote!(<#ty as ::std::convert::From<_>>::from(#lit)))
+            }
+            None => None,
+        };
+
+        if let Some(default) = default {
+            extract = quote::quote!(
+                if v.is_a::<neon::types::JsUndefined, _>(cx) {
+                    Some(#default)
+                } else {
+                    #extract
+                }
+            );
+        }
+
+        quote::quote!({
+            let path = neon::macro_internal::field_path(path, #key);
+            let v = object.get_value(cx, #key)?;
+
+            #extract
+        })
+    }
+}
+
+// Arguments of every `#[neon(...)]` attribute
+fn neon_args(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::Meta>> {
+    let mut args = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("neon")) {
+        let list = match attr.parse_meta()? {
+            syn::Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected `#[neon(...)]`")),
+        };
+
+        for arg in list.nested {
+            match arg {
+                syn::NestedMeta::Meta(meta) => args.push(meta),
+                syn::NestedMeta::Lit(lit) => {
+                    return Err(syn::Error::new_spanned(lit, "unexpected literal"))
+                }
+            }
+        }
+    }
+
+    Ok(args)
+}
+
+// Parses `range(min = <number>, max = <number>)`, where either bound may be omitted
+fn range_args(list: &syn::MetaList) -> syn::Result<(Option<syn::Lit>, Option<syn::Lit>)> {
+    let mut min = None;
+    let mut max = None;
+
+    for arg in &list.nested {
+        let (key, lit) = match arg {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(pair))
+                if matches!(pair.lit, syn::Lit::Int(_) | syn::Lit::Float(_)) =>
+            {
+                (&pair.path, &pair.lit)
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    arg,
+                    "expected `min = <number>` or `max = <number>`",
+                ))
+            }
+        };
+
+        if key.is_ident("min") {
+            min = Some(lit.clone());
+        } else if key.is_ident("max") {
+            max = Some(lit.clone());
+        } else {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "expected `min = <number>` or `max = <number>`",
+            ));
+        }
+    }
+
+    if min.is_none() && max.is_none() {
+        return Err(syn::Error::new_spanned(
+            list,
+            "expected `min = <number>` or `max = <number>`",
+        ));
+    }
+
+    Ok((min, max))
+}
+
+fn optional_number(lit: &Option<syn::Lit>) -> proc_macro2::TokenStream {
+    match lit {
+        Some(lit) => quote::quote!(Some(#lit as f64)),
+        None => quote::quote!(None),
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+
+    for c in name.chars() {
+        if c == '_' {
+            upper = !out.is_empty();
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+
+    out
+}
@@ -0,0 +1,133 @@
+//! Logging to the JavaScript console from Rust.
+//!
+//! [`Context::console`] provides access to the global
+//! [`console`](https://nodejs.org/api/console.html) object, which is convenient for
+//! debugging code that runs outside of a JavaScript function call, such as closures
+//! scheduled with [`Channel::send`](crate::event::Channel::send) or finalizers.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! fn report(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+//!     let value = cx.argument::<JsValue>(0)?;
+//!
+//!     cx.console().log([value])?;
+//!     cx.console().warn(["something odd happened", "twice"])?;
+//!
+//!     Ok(cx.undefined())
+//! }
+//! ```
+//!
+//! [`Context::inspect`] formats a value as a string with
+//! [`util.inspect`](https://nodejs.org/api/util.html#utilinspectobject-options).
+
+use crate::{
+    context::Context,
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{extract::TryIntoJs, function::private::ArgsVec, JsFunction, JsValue, Value},
+};
+
+/// A handle to the global `console` object, created with [`Context::console`].
+///
+/// Each method looks up the corresponding function on `console` when it is called,
+/// so that replacements installed from JavaScript, e.g., by a test framework, are
+/// respected. If the function has been replaced with something that cannot be
+/// called, or it throws, the exception is propagated.
+pub struct Console<'cx, C> {
+    cx: &'cx mut C,
+}
+
+impl<'a: 'cx, 'cx, C: Context<'a>> Console<'cx, C> {
+    pub(crate) fn new(cx: &'cx mut C) -> Self {
+        Self { cx }
+    }
+
+    /// Calls `console.log(...args)`.
+    pub fn log<I>(&mut self, args: I) -> NeonResult<()>
+    where
+        I: IntoIterator,
+        I::Item: TryIntoJs<'a>,
+    {
+        self.call("log", args)
+    }
+
+    /// Calls `console.warn(...args)`.
+    pub fn warn<I>(&mut self, args: I) -> NeonResult<()>
+    where
+        I: IntoIterator,
+        I::Item: TryIntoJs<'a>,
+    {
+        self.call("warn", args)
+    }
+
+    /// Calls `console.error(...args)`.
+    pub fn error<I>(&mut self, args: I) -> NeonResult<()>
+    where
+        I: IntoIterator,
+        I::Item: TryIntoJs<'a>,
+    {
+        self.call("error", args)
+    }
+
+    fn call<I>(&mut self, method: &str, args: I) -> NeonResult<()>
+    where
+        I: IntoIterator,
+        I::Item: TryIntoJs<'a>,
+    {
+        let cx = &mut *self.cx;
+        let console = crate::intrinsics::console(cx)?;
+        let f = console.get::<JsFunction, _, _>(cx, method)?;
+        let args = args
+            .into_iter()
+            .map(|arg| Ok(arg.try_into_js(cx)?.upcast()))
+            .collect::<NeonResult<ArgsVec>>()?;
+
+        f.call(cx, console, args.as_slice())?;
+
+        Ok(())
+    }
+}
+
+/// Options for [`Context::inspect`], corresponding to the options of `util.inspect`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InspectOptions {
+    /// The number of times to recurse into nested objects, or `None` to recurse
+    /// without limit. _Default: `Some(2)`_
+    pub depth: Option<u32>,
+    /// Whether to style the output with ANSI color codes. _Default: `false`_
+    pub colors: bool,
+}
+
+impl Default for InspectOptions {
+    fn default() -> Self {
+        Self {
+            depth: Some(2),
+            colors: false,
+        }
+    }
+}
+
+pub(crate) fn inspect<'a, C: Context<'a>, V: Value>(
+    cx: &mut C,
+    value: Handle<V>,
+    options: InspectOptions,
+) -> NeonResult<String> {
+    let inspect = crate::intrinsics::util_inspect(cx)?;
+    let opts = cx.empty_object();
+    let depth = match options.depth {
+        Some(depth) => cx.number(depth).upcast::<JsValue>(),
+        None => cx.number(f64::INFINITY).upcast(),
+    };
+    let colors = cx.boolean(options.colors);
+
+    opts.set(cx, "depth", depth)?;
+    opts.set(cx, "colors", colors)?;
+
+    let undefined = cx.undefined();
+    let s = inspect
+        .call(cx, undefined, [value.upcast(), opts.upcast()])?
+        .to_string(cx)?;
+
+    Ok(s.value(cx))
+}
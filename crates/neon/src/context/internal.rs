@@ -1,9 +1,18 @@
-use std::{cell::RefCell, ffi::c_void, mem::MaybeUninit};
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::c_void,
+    mem::MaybeUninit,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use once_cell::sync::OnceCell;
 
 use crate::{
-    context::ModuleContext,
+    context::{Context, ModuleContext},
     handle::{Handle, Managed},
     result::NeonResult,
+    runtime::Requirements,
     sys::{self, raw},
     types::JsObject,
 };
@@ -50,9 +59,40 @@ pub trait ContextInternal<'a>: Sized {
     fn env(&self) -> Env;
 }
 
+// Result of the `process_init` function of `#[neon::main]`, shared by every context
+// in the process. Initializing blocks concurrent callers until it completes.
+static PROCESS_INIT: OnceCell<Result<(), String>> = OnceCell::new();
+
+fn run_process_init(process_init: fn()) -> Result<(), &'static str> {
+    PROCESS_INIT
+        .get_or_init(|| {
+            catch_unwind(AssertUnwindSafe(process_init)).map_err(|panic| {
+                format!(
+                    "process initialization panicked: {}",
+                    panic_message(&*panic)
+                )
+            })
+        })
+        .as_ref()
+        .map(|_| ())
+        .map_err(String::as_str)
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(msg) = panic.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = panic.downcast_ref::<String>() {
+        msg
+    } else {
+        "Unknown panic"
+    }
+}
+
 pub unsafe fn initialize_module(
     env: *mut c_void,
     exports: *mut c_void,
+    requirements: &Requirements,
+    process_init: Option<fn()>,
     init: fn(ModuleContext) -> NeonResult<()>,
 ) {
     let env = env.cast();
@@ -66,7 +106,17 @@ pub unsafe fn initialize_module(
     let env = Env(env);
     let exports = Handle::new_internal(JsObject::from_raw(env, exports.cast()));
 
-    ModuleContext::with(env, exports, |cx| {
+    ModuleContext::with(env, exports, |mut cx| {
+        // Throws before calling into symbols the runtime may be missing
+        if requirements.check(&mut cx).is_err() {
+            return;
+        }
+
+        if let Some(Err(msg)) = process_init.map(run_process_init) {
+            let _ = cx.throw_error::<_, ()>(msg);
+            return;
+        }
+
         let _ = init(cx);
     });
 }
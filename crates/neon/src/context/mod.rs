@@ -140,14 +140,27 @@
 //! [question-mark]: https://doc.rust-lang.org/edition-guide/rust-2018/error-handling-and-panics/the-question-mark-operator-for-easier-error-handling.html
 
 pub(crate) mod internal;
+mod module;
+
+use std::{
+    convert::Into,
+    fmt,
+    marker::PhantomData,
+    ops::Range,
+    panic::{Location, UnwindSafe},
+    path::PathBuf,
+};
 
-use std::{convert::Into, marker::PhantomData, panic::UnwindSafe};
+use once_cell::unsync::OnceCell;
 
 pub use crate::types::buffer::lock::Lock;
 
+pub use self::module::{ModuleBuilder, Namespace};
+
 use crate::{
+    console::{Console, InspectOptions},
     event::TaskBuilder,
-    handle::{Handle, Managed},
+    handle::{with_function_name, Handle, Managed},
     object::Object,
     result::{JsResult, NeonResult, Throw},
     sys::{
@@ -156,9 +169,13 @@ use crate::{
     },
     types::{
         boxed::{Finalize, JsBox},
+        buffer::TypedArray,
         error::JsError,
+        extract::{TryFromJs, TryIntoJs},
+        function::{ForwardedArgs, FunctionOptions},
         Deferred, JsArray, JsArrayBuffer, JsBoolean, JsBuffer, JsFunction, JsNull, JsNumber,
-        JsObject, JsPromise, JsString, JsUndefined, JsValue, StringResult, Value,
+        JsObject, JsPromise, JsString, JsUndefined, JsUrl, JsValue, NumberResult, StringResult,
+        TryIntoF64, Value,
     },
 };
 
@@ -168,10 +185,19 @@ use self::internal::{ContextInternal, Env};
 use crate::event::Channel;
 
 #[cfg(feature = "napi-5")]
-use crate::types::date::{DateError, JsDate};
+use crate::{
+    error::ThrowError,
+    types::{
+        date::{DateError, JsDate},
+        proxy::ProxyHandler,
+    },
+};
 
 #[cfg(feature = "napi-6")]
-use crate::lifecycle::InstanceData;
+use crate::{
+    lifecycle::InstanceData,
+    types::{JsBigInt, JsRwLock, Shared, SharedHandle},
+};
 
 #[repr(C)]
 pub(crate) struct CallbackInfo<'a> {
@@ -195,21 +221,12 @@ impl CallbackInfo<'_> {
         }
     }
 
-    pub fn len<'b, C: Context<'b>>(&self, cx: &C) -> usize {
-        unsafe { sys::call::len(cx.env().to_raw(), self.info) }
+    fn new_target<'b, C: Context<'b>>(&self, cx: &C) -> Option<raw::Local> {
+        unsafe { sys::call::new_target(cx.env().to_raw(), self.info) }
     }
 
-    pub fn argv<'b, C: Context<'b>>(&self, cx: &mut C) -> sys::call::Arguments {
-        unsafe { sys::call::argv(cx.env().to_raw(), self.info) }
-    }
-
-    pub fn this<'b, C: Context<'b>>(&self, cx: &mut C) -> raw::Local {
-        let env = cx.env();
-        unsafe {
-            let mut local: raw::Local = std::mem::zeroed();
-            sys::call::this(env.to_raw(), self.info, &mut local);
-            local
-        }
+    pub fn data<'b, C: Context<'b>>(&self, cx: &C) -> sys::call::CallbackData {
+        unsafe { sys::call::data(cx.env().to_raw(), self.info) }
     }
 }
 
@@ -281,6 +298,49 @@ pub trait Context<'a>: ContextInternal<'a> {
         Ok(Handle::new_internal(V::from_raw(self.env(), escapee)))
     }
 
+    /// Processes the indices `0..len` in chunks of `chunk_size`, calling `f` with the
+    /// range of each chunk in a new memory management scope.
+    ///
+    /// Handles created while processing a chunk are released before the next chunk
+    /// begins, which bounds the number of live handles when converting large
+    /// collections. Processing stops at the first chunk that throws. A `chunk_size` of
+    /// `0` is treated as `1`.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn squares(mut cx: FunctionContext) -> JsResult<JsArray> {
+    ///     let values = (0..1_000_000u32).map(|i| i as f64 * i as f64).collect::<Vec<_>>();
+    ///     let array = JsArray::new(&mut cx, values.len() as u32);
+    ///
+    ///     cx.chunked(values.len(), 10_000, |cx, range| {
+    ///         for i in range {
+    ///             let n = cx.number(values[i]);
+    ///             array.set(cx, i as u32, n)?;
+    ///         }
+    ///
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     Ok(array)
+    /// }
+    /// ```
+    fn chunked<F>(&mut self, len: usize, chunk_size: usize, mut f: F) -> NeonResult<()>
+    where
+        F: for<'b> FnMut(&mut ExecuteContext<'b>, Range<usize>) -> NeonResult<()>,
+    {
+        let chunk_size = chunk_size.max(1);
+        let mut start = 0;
+
+        while start < len {
+            let end = len.min(start.saturating_add(chunk_size));
+
+            self.execute_scoped(|mut cx| f(&mut cx, start..end))?;
+            start = end;
+        }
+
+        Ok(())
+    }
+
     #[cfg_attr(
         feature = "try-catch-api",
         deprecated = "`try-catch-api` feature has no impact and may be removed"
@@ -296,6 +356,62 @@ pub trait Context<'a>: ContextInternal<'a> {
         }
     }
 
+    /// Returns `true` if a JavaScript exception is pending.
+    ///
+    /// An exception is pending after an operation fails with [`Throw`], until the
+    /// exception is propagated to JavaScript or caught with [`Context::catch_pending`]
+    /// or [`Context::try_catch`]. Most Neon APIs may not be called while an exception
+    /// is pending.
+    fn is_throwing(&self) -> bool {
+        unsafe { sys::error::is_throwing(self.env().to_raw()) }
+    }
+
+    /// Clears the pending JavaScript exception and returns it, or returns `None` if no
+    /// exception is pending.
+    ///
+    /// This is a lower level alternative to [`Context::try_catch`] for recovering from
+    /// an exception after the fact. Once the exception has been caught, the
+    /// [`Throw`] that signaled it must not be propagated; return a value or throw a
+    /// new exception instead.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// // Calls `f`, returning its exception if it throws a string
+    /// fn catch_strings(mut cx: FunctionContext) -> JsResult<JsValue> {
+    ///     let f = cx.argument::<JsFunction>(0)?;
+    ///     let result = cx.try_catch(|cx| {
+    ///         let this = cx.undefined();
+    ///
+    ///         if let Ok(v) = f.call(cx, this, ()) {
+    ///             return Ok(v);
+    ///         }
+    ///
+    ///         let err = cx.catch_pending().expect("exception is pending");
+    ///
+    ///         // Recover from strings; rethrow everything else for `try_catch`
+    ///         if err.is_a::<JsString, _>(cx) {
+    ///             Ok(err)
+    ///         } else {
+    ///             cx.throw(err)
+    ///         }
+    ///     });
+    ///
+    ///     match result {
+    ///         Ok(v) => Ok(v),
+    ///         Err(err) => cx.throw(err),
+    ///     }
+    /// }
+    /// ```
+    fn catch_pending(&mut self) -> Option<Handle<'a, JsValue>> {
+        let mut local: raw::Local = std::ptr::null_mut();
+
+        if unsafe { sys::error::catch_error(self.env().to_raw(), &mut local) } {
+            Some(JsValue::new_internal(local))
+        } else {
+            None
+        }
+    }
+
     /// Convenience method for creating a `JsBoolean` value.
     fn boolean(&mut self, b: bool) -> Handle<'a, JsBoolean> {
         JsBoolean::new(self, b)
@@ -306,6 +422,53 @@ pub trait Context<'a>: ContextInternal<'a> {
         JsNumber::new(self, x.into())
     }
 
+    /// Convenience method for creating a `JsNumber` value from any Rust number,
+    /// including 64-bit and 128-bit integers.
+    ///
+    /// If the value cannot be represented exactly by a JavaScript number, this method
+    /// returns an `Err` value instead of rounding.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn file_size(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    ///     let size: u64 = 4_294_967_296;
+    ///
+    ///     cx.number_checked(size).or_throw(&mut cx)
+    /// }
+    /// ```
+    fn number_checked<T: TryIntoF64>(&mut self, x: T) -> NumberResult<'a, T> {
+        let n = x.try_into_f64()?;
+
+        Ok(JsNumber::new(self, n))
+    }
+
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+    /// Convenience method for converting a `u64` to a `JsNumber` when it is a safe
+    /// integer, i.e., no greater than `Number.MAX_SAFE_INTEGER`, and to a `JsBigInt`
+    /// otherwise.
+    ///
+    /// Larger integers are never converted to a number, even when representable,
+    /// because distinct `u64` values would be indistinguishable in JavaScript.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn next_id(mut cx: FunctionContext) -> JsResult<JsValue> {
+    ///     let id: u64 = 18_446_744_073_709_551_557;
+    ///
+    ///     Ok(cx.int_or_bigint(id))
+    /// }
+    /// ```
+    fn int_or_bigint(&mut self, n: u64) -> Handle<'a, JsValue> {
+        const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+        if n <= MAX_SAFE_INTEGER {
+            JsNumber::new(self, n as f64).upcast()
+        } else {
+            JsBigInt::from_u64(self, n).upcast()
+        }
+    }
+
     /// Convenience method for creating a `JsString` value.
     ///
     /// If the string exceeds the limits of the JS engine, this method panics.
@@ -320,6 +483,30 @@ pub trait Context<'a>: ContextInternal<'a> {
         JsString::try_new(self, s)
     }
 
+    /// Creates a `JsString` containing `data` encoded as padded base64 with the standard
+    /// alphabet, equivalent to `buffer.toString("base64")`.
+    ///
+    /// If the string exceeds the limits of the JS engine, this method panics.
+    ///
+    /// **See also:** [`JsString::decode_base64`]
+    fn string_base64<D: AsRef<[u8]>>(&mut self, data: D) -> Handle<'a, JsString> {
+        let encoded = crate::types::encoding::encode_base64(data.as_ref());
+
+        JsString::from_ascii(self, &encoded)
+    }
+
+    /// Creates a `JsString` containing `data` encoded as lowercase hex, equivalent to
+    /// `buffer.toString("hex")`.
+    ///
+    /// If the string exceeds the limits of the JS engine, this method panics.
+    ///
+    /// **See also:** [`JsString::decode_hex`]
+    fn string_hex<D: AsRef<[u8]>>(&mut self, data: D) -> Handle<'a, JsString> {
+        let encoded = crate::types::encoding::encode_hex(data.as_ref());
+
+        JsString::from_ascii(self, &encoded)
+    }
+
     /// Convenience method for creating a `JsNull` value.
     fn null(&mut self) -> Handle<'a, JsNull> {
         JsNull::new(self)
@@ -340,6 +527,25 @@ pub trait Context<'a>: ContextInternal<'a> {
         JsArray::new(self, 0)
     }
 
+    /// Convenience method for creating a `JsArray` with storage preallocated for
+    /// `capacity` elements, equivalent to the JavaScript expression `new Array(capacity)`.
+    ///
+    /// The array starts with a length of `capacity` and every element is a
+    /// [hole](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Indexed_collections#sparse_arrays),
+    /// so it should be filled by index with [`Object::set`].
+    /// [`JsArray::push`] appends after the holes; use [`Context::empty_array`] to build
+    /// an array with `push` instead.
+    fn array_with_capacity(&mut self, capacity: u32) -> Handle<'a, JsArray> {
+        JsArray::new(self, capacity)
+    }
+
+    /// Convenience method for creating a `JsArray` containing the numbers in `values`.
+    ///
+    /// **See also:** [`JsArray::from_f64_slice`], [`JsArray::to_vec_f64`]
+    fn array_from_f64_slice(&mut self, values: &[f64]) -> JsResult<'a, JsArray> {
+        JsArray::from_f64_slice(self, values)
+    }
+
     /// Convenience method for creating an empty `JsArrayBuffer` value.
     fn array_buffer(&mut self, size: usize) -> JsResult<'a, JsArrayBuffer> {
         JsArrayBuffer::new(self, size)
@@ -356,6 +562,92 @@ pub trait Context<'a>: ContextInternal<'a> {
         JsDate::new(self, value)
     }
 
+    /// Convenience method for parsing a `JsUrl` with `new URL(input, base)`. Throws a
+    /// `TypeError` if `input` is not a valid URL.
+    fn url(&mut self, input: &str, base: Option<&str>) -> JsResult<'a, JsUrl> {
+        JsUrl::new(self, input, base)
+    }
+
+    /// Convenience method for creating a `JsFunction` value from a closure.
+    ///
+    /// The closure may mutate its captured state. As with [`JsFunction::new_mut`],
+    /// calling the function again while it is already running throws an `Error`.
+    ///
+    /// **See also:** [`JsFunction::new`], [`JsFunction::new_mut`].
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    fn function<F, V>(&mut self, f: F) -> JsResult<'a, JsFunction>
+    where
+        F: FnMut(FunctionContext) -> JsResult<V> + 'static,
+        V: Value,
+    {
+        JsFunction::new_mut(self, f)
+    }
+
+    /// Convenience method for parsing a JSON string with [`json::parse`](crate::json::parse).
+    fn json_parse(&mut self, s: &str) -> JsResult<'a, JsValue> {
+        crate::json::parse(self, s)
+    }
+
+    /// Convenience method for serializing a value as JSON with
+    /// [`json::stringify`](crate::json::stringify).
+    fn json_stringify<V: Value>(&mut self, value: Handle<V>) -> NeonResult<String> {
+        crate::json::stringify(self, value)
+    }
+
+    /// Convenience method for serializing a value as indented JSON with
+    /// [`json::stringify_pretty`](crate::json::stringify_pretty).
+    fn json_stringify_pretty<V: Value>(
+        &mut self,
+        value: Handle<V>,
+        indent: u32,
+    ) -> NeonResult<String> {
+        crate::json::stringify_pretty(self, value, indent)
+    }
+
+    /// Convenience method for converting a Rust value to JavaScript with
+    /// [`TryIntoJs`], for generic code that names the context rather than the value.
+    ///
+    /// Like every conversion, this works in any context, e.g., the [`TaskContext`] of a
+    /// [`Channel`](crate::event::Channel) callback. The
+    /// [module docs](crate::types::extract#contexts) list the conversions that fail at
+    /// runtime where JavaScript cannot run.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn sum_in_background(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    ///     let channel = cx.channel();
+    ///
+    ///     std::thread::spawn(move || {
+    ///         let sum = (1..=100).sum::<u32>();
+    ///
+    ///         channel.send(move |mut cx| {
+    ///             let result = cx.try_into_js(("sum", sum))?;
+    ///
+    ///             callback
+    ///                 .into_inner(&mut cx)
+    ///                 .call_with(&cx)
+    ///                 .arg(result)
+    ///                 .exec(&mut cx)
+    ///         });
+    ///     });
+    ///
+    ///     Ok(cx.undefined())
+    /// }
+    /// ```
+    fn try_into_js<T: TryIntoJs<'a>>(&mut self, value: T) -> JsResult<'a, T::Value> {
+        value.try_into_js(self)
+    }
+
+    /// Convenience method for extracting a Rust value from JavaScript with
+    /// [`TryFromJs`].
+    ///
+    /// Throws an exception if the value cannot be converted.
+    fn try_from_js<T: TryFromJs<'a>, V: Value>(&mut self, value: Handle<'a, V>) -> NeonResult<T> {
+        T::try_from_js(self, value.upcast())
+    }
+
     /// Produces a handle to the JavaScript global object.
     fn global(&mut self) -> Handle<'a, JsObject> {
         JsObject::build(|out| unsafe {
@@ -363,6 +655,144 @@ pub trait Context<'a>: ContextInternal<'a> {
         })
     }
 
+    /// Creates a deep copy of a value with the
+    /// [structured clone algorithm](https://developer.mozilla.org/en-US/docs/Web/API/structuredClone),
+    /// equivalent to `structuredClone(value, { transfer })` in JavaScript.
+    ///
+    /// Values in the `transfer` list (e.g. `ArrayBuffer`s) are moved to the clone
+    /// rather than copied, detaching the originals.
+    ///
+    /// Throws a `DataCloneError` if the value cannot be cloned, such as a function or
+    /// a symbol. Throws an `Error` if the runtime does not provide `structuredClone`,
+    /// which was added in Node.js 17.
+    fn structured_clone<V: Value>(
+        &mut self,
+        value: Handle<V>,
+        transfer: Option<&[Handle<JsValue>]>,
+    ) -> JsResult<'a, JsValue> {
+        let global = self.global();
+        let clone = match global
+            .get_value(self, "structuredClone")?
+            .downcast::<JsFunction, _>(self)
+        {
+            Ok(clone) => clone,
+            Err(_) => return self.throw_error("structuredClone is not supported by this runtime"),
+        };
+
+        let mut args = vec![value.upcast::<JsValue>()];
+
+        if let Some(transfer) = transfer {
+            let list = JsArray::new(self, transfer.len() as u32);
+
+            for (i, v) in transfer.iter().enumerate() {
+                list.set(self, i as u32, *v)?;
+            }
+
+            let options = self.empty_object();
+
+            options.set(self, "transfer", list)?;
+            args.push(options.upcast());
+        }
+
+        let undefined = self.undefined();
+
+        clone.call(self, undefined, args)
+    }
+
+    /// Returns a handle to the global `console` object for logging.
+    ///
+    /// **See also:** [`neon::console`](crate::console)
+    fn console<'cx>(&'cx mut self) -> Console<'cx, Self>
+    where
+        'a: 'cx,
+    {
+        Console::new(self)
+    }
+
+    /// Formats a value as a string with
+    /// [`util.inspect`](https://nodejs.org/api/util.html#utilinspectobject-options).
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use neon::console::InspectOptions;
+    ///
+    /// fn describe(mut cx: FunctionContext) -> JsResult<JsString> {
+    ///     let value = cx.argument::<JsValue>(0)?;
+    ///     let options = InspectOptions {
+    ///         depth: None,
+    ///         ..Default::default()
+    ///     };
+    ///     let s = cx.inspect(value, options)?;
+    ///
+    ///     Ok(cx.string(s))
+    /// }
+    /// ```
+    fn inspect<V: Value>(
+        &mut self,
+        value: Handle<V>,
+        options: InspectOptions,
+    ) -> NeonResult<String> {
+        crate::console::inspect(self, value, options)
+    }
+
+    /// Serializes a value with the V8 serializer, equivalent to `v8.serialize(value)`
+    /// in JavaScript.
+    ///
+    /// The serialized data uses the same format as `postMessage` and may be deserialized
+    /// with [`Context::deserialize_from_transfer`] in any JavaScript environment,
+    /// e.g., a worker thread, allowing Rust to move structured data between
+    /// environments without converting it.
+    ///
+    /// Throws the engine's clone error, e.g., `f() {} could not be cloned.`, if the
+    /// value cannot be serialized, such as a function or a symbol.
+    fn serialize_for_transfer<V: Value>(&mut self, value: Handle<V>) -> NeonResult<Vec<u8>> {
+        let v8 = crate::intrinsics::v8_module(self)?;
+        let serialize = v8.get::<JsFunction, _, _>(self, "serialize")?;
+        let data = serialize
+            .call(self, v8, [value.upcast()])?
+            .downcast_or_throw::<JsBuffer, _>(self)?;
+
+        Ok(data.as_slice(self).to_vec())
+    }
+
+    /// Deserializes a value serialized with [`Context::serialize_for_transfer`],
+    /// equivalent to `v8.deserialize(data)` in JavaScript.
+    fn deserialize_from_transfer(&mut self, data: &[u8]) -> JsResult<'a, JsValue> {
+        let v8 = crate::intrinsics::v8_module(self)?;
+        let deserialize = v8.get::<JsFunction, _, _>(self, "deserialize")?;
+        let data = JsBuffer::from_slice(self, data)?;
+
+        deserialize.call(self, v8, [data.upcast()])
+    }
+
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    /// Creates a [`Proxy`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy)
+    /// for `target` with traps implemented by Rust closures, equivalent to
+    /// `new Proxy(target, handler)` in JavaScript.
+    ///
+    /// The proxy has the same type as the target; e.g., a proxy for a function is itself
+    /// callable. Exceptions thrown by a trap propagate to the JavaScript code that
+    /// triggered it.
+    fn proxy<T: Object>(&mut self, target: Handle<T>, handler: ProxyHandler) -> JsResult<'a, T> {
+        crate::types::proxy::new(self, target, handler)
+    }
+
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    /// Creates a revocable [`Proxy`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy)
+    /// for `target`, equivalent to `Proxy.revocable(target, handler)` in JavaScript.
+    ///
+    /// Returns the proxy and a function that revokes it. After the proxy is revoked, any
+    /// operation on it throws a `TypeError`, and the traps may be garbage collected.
+    fn revocable_proxy<T: Object>(
+        &mut self,
+        target: Handle<T>,
+        handler: ProxyHandler,
+    ) -> NeonResult<(Handle<'a, T>, Handle<'a, JsFunction>)> {
+        crate::types::proxy::revocable(self, target, handler)
+    }
+
     /// Throws a JS value.
     fn throw<T: Value, U>(&mut self, v: Handle<T>) -> NeonResult<U> {
         unsafe {
@@ -387,20 +817,93 @@ pub trait Context<'a>: ContextInternal<'a> {
     }
 
     /// Throws a direct instance of the [`Error`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/Error) class.
+    #[cfg_attr(feature = "rust-stack-frames", track_caller)]
     fn throw_error<S: AsRef<str>, T>(&mut self, msg: S) -> NeonResult<T> {
         let err = JsError::error(self, msg)?;
+
+        #[cfg(feature = "rust-stack-frames")]
+        crate::types::error::note_location(
+            self,
+            err,
+            "throw_error",
+            std::panic::Location::caller(),
+        )?;
+
         self.throw(err)
     }
 
     /// Throws an instance of the [`TypeError`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/TypeError) class.
+    #[cfg_attr(feature = "rust-stack-frames", track_caller)]
     fn throw_type_error<S: AsRef<str>, T>(&mut self, msg: S) -> NeonResult<T> {
         let err = JsError::type_error(self, msg)?;
+
+        #[cfg(feature = "rust-stack-frames")]
+        crate::types::error::note_location(
+            self,
+            err,
+            "throw_type_error",
+            std::panic::Location::caller(),
+        )?;
+
         self.throw(err)
     }
 
     /// Throws an instance of the [`RangeError`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/RangeError) class.
+    #[cfg_attr(feature = "rust-stack-frames", track_caller)]
     fn throw_range_error<S: AsRef<str>, T>(&mut self, msg: S) -> NeonResult<T> {
         let err = JsError::range_error(self, msg)?;
+
+        #[cfg(feature = "rust-stack-frames")]
+        crate::types::error::note_location(
+            self,
+            err,
+            "throw_range_error",
+            std::panic::Location::caller(),
+        )?;
+
+        self.throw(err)
+    }
+
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+    /// Throws an instance of the error class [registered](crate::error::register_class)
+    /// as `class`, constructed with `msg`. The own enumerable properties of `props`,
+    /// if any, are copied to the error.
+    ///
+    /// See [`ErrorBuilder`](crate::error::ErrorBuilder) for the behavior when no class
+    /// is registered with the name.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn lookup(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let key = cx.argument::<JsString>(0)?;
+    ///     let props = cx.empty_object();
+    ///
+    ///     props.set(&mut cx, "key", key)?;
+    ///
+    ///     cx.throw_custom("NotFoundError", "key not found", Some(props))
+    /// }
+    /// ```
+    fn throw_custom<S: AsRef<str>, T>(
+        &mut self,
+        class: &str,
+        msg: S,
+        props: Option<Handle<'a, JsObject>>,
+    ) -> NeonResult<T> {
+        let err = crate::error::ErrorBuilder::class(class)
+            .message(msg.as_ref())
+            .build(self)?;
+
+        if let Some(props) = props {
+            let keys = props.get_own_property_names(self)?.to_vec(self)?;
+
+            for key in keys {
+                let value = props.get_value(self, key)?;
+
+                err.set(self, key, value)?;
+            }
+        }
+
         self.throw(err)
     }
 
@@ -424,6 +927,62 @@ pub trait Context<'a>: ContextInternal<'a> {
         JsBox::new(self, v)
     }
 
+    /// Creates a JavaScript iterator backed by a [`JsBox`] that converts the items of
+    /// `iter` as they are requested. See [`BoxedIterator`](crate::types::BoxedIterator).
+    fn boxed_iterator<I>(&mut self, iter: I) -> JsResult<'a, JsObject>
+    where
+        I: Iterator + Send + 'static,
+        I::Item: for<'cx> TryIntoJs<'cx>,
+    {
+        crate::types::BoxedIterator::object(self, iter)
+    }
+
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+    /// Convenience method for wrapping a value in a `JsBox` guarded by a reader-writer
+    /// lock, so that it may be read from other threads. See [`JsRwLock`].
+    fn boxed_rwlock<U: Finalize + Send + Sync + 'static>(
+        &mut self,
+        v: U,
+    ) -> Handle<'a, JsBox<JsRwLock<U>>> {
+        JsRwLock::boxed(self, v)
+    }
+
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+    /// Convenience method for sharing a value between JavaScript objects and Rust.
+    /// Returns a [`SharedHandle`] and a [`JsBox`] that both own the value.
+    ///
+    /// Additional boxes may be created with [`SharedHandle::to_js`]. The value is
+    /// finalized once every box has been garbage collected and every handle has been
+    /// dropped.
+    ///
+    /// ```rust
+    /// # use neon::prelude::*;
+    /// use neon::types::Shared;
+    ///
+    /// struct Pool;
+    ///
+    /// impl Finalize for Pool {}
+    ///
+    /// fn pools(mut cx: FunctionContext) -> JsResult<JsArray> {
+    ///     let (pool, a) = cx.shared(Pool);
+    ///     let b = pool.to_js(&mut cx);
+    ///     let pools = cx.empty_array();
+    ///
+    ///     pools.set(&mut cx, 0, a)?;
+    ///     pools.set(&mut cx, 1, b)?;
+    ///
+    ///     Ok(pools)
+    /// }
+    /// ```
+    fn shared<U: Finalize + Send + Sync + 'static>(
+        &mut self,
+        v: U,
+    ) -> (SharedHandle<U>, Handle<'a, JsBox<Shared<U>>>) {
+        SharedHandle::pair(self, v)
+    }
+
     #[cfg(feature = "napi-4")]
     #[deprecated(since = "0.9.0", note = "Please use the channel() method instead")]
     #[doc(hidden)]
@@ -443,8 +1002,12 @@ pub trait Context<'a>: ContextInternal<'a> {
     #[cfg_attr(docsrs, doc(cfg(feature = "napi-4")))]
     /// Returns an unbounded channel for scheduling events to be executed on the JavaScript thread.
     ///
-    /// When using N-API >= 6,the channel returned by this method is backed by a shared queue.
-    /// To create a channel backed by a _new_ queue see [`Channel`](crate::event::Channel).
+    /// When using N-API >= 6, the channel returned by this method is a clone of a channel
+    /// shared by the module instance, created on first use, so calling it for every object
+    /// is cheap. Each clone is referenced independently: [`Channel::unref`] on one clone
+    /// does not allow the event loop to exit while other clones are referenced. Closures
+    /// sent on any clone share a single queue; use [`Context::new_channel`] for a channel
+    /// with its own queue.
     fn channel(&mut self) -> Channel {
         #[cfg(feature = "napi-6")]
         let channel = InstanceData::channel(self);
@@ -455,6 +1018,78 @@ pub trait Context<'a>: ContextInternal<'a> {
         channel
     }
 
+    #[cfg(feature = "napi-4")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-4")))]
+    /// Returns a new unbounded channel backed by its own queue. Equivalent to
+    /// [`Channel::new`].
+    ///
+    /// Closures sent on a new channel are not ordered with closures sent on other
+    /// channels, and cannot be delayed by them. Each new channel creates a threadsafe
+    /// function; prefer [`Context::channel`] unless the queue must be isolated.
+    fn new_channel(&mut self) -> Channel {
+        Channel::new(self)
+    }
+
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    /// Calls `f` with the error whenever an exception goes uncaught, e.g., one thrown
+    /// by a [`Channel`] closure, until the returned guard is dropped.
+    ///
+    /// The listener is installed for the
+    /// [`uncaughtExceptionMonitor`](https://nodejs.org/api/process.html#event-uncaughtexceptionmonitor)
+    /// event, so it observes errors without changing how the process handles them: by
+    /// default, it still crashes after `f` returns.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use neon::event::ListenerGuard;
+    ///
+    /// fn watch_errors(mut cx: FunctionContext) -> JsResult<JsBox<ListenerGuard>> {
+    ///     let guard = cx.on_uncaught_exception(|mut cx, err| {
+    ///         let message = err.to_string(&mut cx)?.value(&mut cx);
+    ///
+    ///         eprintln!("uncaught exception: {}", message);
+    ///
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     // The listener is removed when the box is garbage collected
+    ///     Ok(cx.boxed(guard))
+    /// }
+    /// ```
+    fn on_uncaught_exception<F>(&mut self, f: F) -> NeonResult<crate::event::ListenerGuard>
+    where
+        F: for<'b> Fn(TaskContext<'b>, Handle<'b, JsValue>) -> NeonResult<()> + 'static,
+    {
+        crate::event::process::on_uncaught_exception(self, f)
+    }
+
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    /// Calls `f` with the reason and the promise whenever a promise is rejected without
+    /// a handler, until the returned guard is dropped.
+    ///
+    /// The listener is installed for the
+    /// [`unhandledRejection`](https://nodejs.org/api/process.html#event-unhandledrejection)
+    /// event, but only observes rejections. Node.js stops raising unhandled rejections
+    /// as uncaught exceptions while any code listens for the event, so if every
+    /// listener was installed by this method, the rejection is handled after calling
+    /// `f` as it would be without a listener: it is raised as an uncaught exception in
+    /// the default `--unhandled-rejections=throw` mode, so the process still crashes,
+    /// while the `warn` and `none` modes are not changed. In `warn-with-error-code`
+    /// mode, a warning is emitted and the exit code is set to 1.
+    fn on_unhandled_rejection<F>(&mut self, f: F) -> NeonResult<crate::event::ListenerGuard>
+    where
+        F: for<'b> Fn(
+                TaskContext<'b>,
+                Handle<'b, JsValue>,
+                Handle<'b, JsPromise>,
+            ) -> NeonResult<()>
+            + 'static,
+    {
+        crate::event::process::on_unhandled_rejection(self, f)
+    }
+
     #[cfg_attr(
         feature = "promise-api",
         deprecated = "`promise-api` feature has no impact and may be removed"
@@ -497,6 +1132,7 @@ pub trait Context<'a>: ContextInternal<'a> {
     ///     Ok(promise)
     /// }
     /// ```
+    #[track_caller]
     fn task<'cx, O, E>(&'cx mut self, execute: E) -> TaskBuilder<Self, E>
     where
         'a: 'cx,
@@ -505,57 +1141,540 @@ pub trait Context<'a>: ContextInternal<'a> {
     {
         TaskBuilder::new(self, execute)
     }
-}
 
-/// An execution context of module initialization.
-pub struct ModuleContext<'a> {
-    env: Env,
-    exports: Handle<'a, JsObject>,
-}
+    /// Returns the path of the file the addon was loaded from, e.g. `/path/to/index.node`.
+    ///
+    /// This is useful for locating data files that are distributed alongside the addon.
+    /// Returns `None` if the runtime does not support Node-API 9 (Node 18.17 and later).
+    fn module_filename(&mut self) -> Option<String> {
+        let url = unsafe { sys::module::file_name(self.env().to_raw()) }?;
 
-impl<'a> UnwindSafe for ModuleContext<'a> {}
+        Some(file_url_to_path(&url))
+    }
 
-impl<'a> ModuleContext<'a> {
-    pub(crate) fn with<T, F: for<'b> FnOnce(ModuleContext<'b>) -> T>(
-        env: Env,
-        exports: Handle<'a, JsObject>,
-        f: F,
-    ) -> T {
-        f(ModuleContext { env, exports })
+    /// Returns the directory containing the file the addon was loaded from.
+    ///
+    /// **See also:** [`Context::module_filename`]
+    fn module_dir(&mut self) -> Option<PathBuf> {
+        let filename = PathBuf::from(self.module_filename()?);
+
+        filename.parent().map(PathBuf::from)
     }
 
-    #[cfg(not(feature = "napi-5"))]
+    /// Loads a JavaScript module, equivalent to `require(specifier)` in a file
+    /// alongside the addon.
+    ///
+    /// Relative specifiers, e.g. `./helpers.js`, are resolved against the directory
+    /// containing the addon (see [`Context::module_dir`]), or against the current
+    /// working directory if the runtime does not report the addon's location.
+    /// Built-in modules, e.g. `node:crypto`, and packages are resolved as usual.
+    ///
+    /// Resolution and evaluation errors are thrown with their original message, e.g.,
+    /// `Cannot find module './helpers.js'`. The exports of the module are downcast to
+    /// `V`, e.g., a [`JsFunction`] for `node:events`, throwing a `TypeError` if they
+    /// are another type; use [`JsValue`] to accept any exports.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn join(mut cx: FunctionContext) -> JsResult<JsString> {
+    ///     let path = cx.require::<JsObject>("node:path")?;
+    ///     let join = path.get::<JsFunction, _, _>(&mut cx, "join")?;
+    ///     let a = cx.string("a");
+    ///     let b = cx.string("b");
+    ///
+    ///     join.call_with(&cx)
+    ///         .this(path)
+    ///         .arg(a)
+    ///         .arg(b)
+    ///         .apply(&mut cx)
+    /// }
+    /// ```
+    fn require<V: Value>(&mut self, specifier: &str) -> JsResult<'a, V> {
+        let require = crate::intrinsics::require_function(self)?;
+        let specifier = self.string(specifier);
+
+        require
+            .call(self, require, [specifier.upcast()])?
+            .downcast_or_throw(self)
+    }
+
+    /// Loads an ECMAScript module, equivalent to `import(specifier)` in JavaScript.
+    ///
+    /// Returns a promise that resolves to the module namespace object. Relative
+    /// specifiers are resolved in the same way as [`Context::require`]. Resolution and
+    /// evaluation errors reject the promise with their original message.
+    fn import(&mut self, specifier: &str) -> JsResult<'a, JsPromise> {
+        let url = match specifier {
+            s if s.starts_with("./") || s.starts_with("../") => {
+                let base = module_base_url(self);
+                let base = self.string(base);
+                let specifier = self.string(specifier);
+                let url = crate::intrinsics::url(self)?
+                    .construct(self, [specifier.upcast(), base.upcast()])?;
+
+                url.get::<JsString, _, _>(self, "href")?
+            }
+            s => self.string(s),
+        };
+
+        let import = import_function(self)?;
+
+        import.call1(self, url)?.downcast_or_throw(self)
+    }
+
+    /// Indicates whether the addon is running on the main thread, as opposed to a
+    /// [worker thread](https://nodejs.org/api/worker_threads.html).
+    ///
+    /// This is the value of `require("worker_threads").isMainThread`, which is read once
+    /// per environment. On versions of Node without `process.getBuiltinModule`, the module
+    /// is loaded with the `require` function of the main module. If `worker_threads`
+    /// cannot be loaded, e.g., because the entry point of the application is an ES module
+    /// on an older version of Node, the addon is assumed to be running on the main thread.
+    fn is_main_thread(&mut self) -> bool {
+        is_main_thread(self)
+    }
+
+    /// Reads an environment variable from the live `process.env` object.
+    ///
+    /// Unlike [`std::env::var`], this observes changes made from JavaScript after
+    /// startup, e.g., `process.env.DEBUG = "1"`. Non-string values are coerced to
+    /// strings, following Node's semantics. Returns `None` if the variable is not set.
+    fn process_env(&mut self, name: &str) -> NeonResult<Option<String>> {
+        let env = crate::intrinsics::process(self)?.get::<JsObject, _, _>(self, "env")?;
+        let value = env.get_value(self, name)?;
+
+        if value.is_a::<JsUndefined, _>(self) {
+            return Ok(None);
+        }
+
+        Ok(Some(value.to_string(self)?.value(self)))
+    }
+
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+    /// Reads all environment variables from the live `process.env` object.
+    ///
+    /// **See also:** [`Context::process_env`]
+    fn process_env_all(&mut self) -> NeonResult<std::collections::HashMap<String, String>> {
+        let env = crate::intrinsics::process(self)?.get::<JsObject, _, _>(self, "env")?;
+        let names = env.get_own_property_names(self)?.to_vec(self)?;
+        let mut vars = std::collections::HashMap::with_capacity(names.len());
+
+        for name in names {
+            let value = env.get_value(self, name)?.to_string(self)?.value(self);
+            let name = name.to_string(self)?.value(self);
+
+            vars.insert(name, value);
+        }
+
+        Ok(vars)
+    }
+
+    /// Returns the id of the current process, equivalent to `process.pid`.
+    fn process_pid(&mut self) -> NeonResult<u32> {
+        let pid = crate::intrinsics::process(self)?.get::<JsNumber, _, _>(self, "pid")?;
+
+        Ok(pid.value(self) as u32)
+    }
+
+    /// Returns the command-line arguments of the current process, equivalent to
+    /// `process.argv`.
+    ///
+    /// The first two elements are the path of the runtime executable and the path of
+    /// the script being executed.
+    fn process_argv(&mut self) -> NeonResult<Vec<String>> {
+        let argv = crate::intrinsics::process(self)?.get::<JsArray, _, _>(self, "argv")?;
+
+        argv.to_vec(self)?
+            .into_iter()
+            .map(|arg| Ok(arg.to_string(self)?.value(self)))
+            .collect()
+    }
+
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+    /// Returns the cache of JavaScript built-ins for the current environment.
+    ///
+    /// **See also:** [`neon::intrinsics`](crate::intrinsics)
+    fn intrinsics(&mut self) -> &'a crate::intrinsics::Intrinsics {
+        crate::intrinsics::Intrinsics::get(self)
+    }
+
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+    /// Returns an identifier for the JavaScript environment the addon is running in.
+    ///
+    /// Each environment that loads the addon, e.g., the main thread and each worker
+    /// thread, has a different identifier, which remains the same for the lifetime of
+    /// the environment and is never reused within the process. This makes it suitable
+    /// as a key for Rust data that is associated with an environment.
+    ///
+    /// _Note_: Since identifiers are assigned lazily, their order may not reflect the
+    /// order in which environments were created.
+    fn env_id(&mut self) -> u64 {
+        InstanceData::id(self).as_u64()
+    }
+
+    /// Returns the current high resolution timestamp, in milliseconds, equivalent to
+    /// `performance.now()`.
+    ///
+    /// **See also:** [`neon::performance`](crate::performance)
+    fn performance_now(&mut self) -> NeonResult<f64> {
+        crate::performance::now(self)
+    }
+
+    /// Returns the time at which the environment started, in milliseconds since the Unix
+    /// epoch, equivalent to `performance.timeOrigin`.
+    ///
+    /// Adding the result of [`Context::performance_now`] gives the current time.
+    fn time_origin(&mut self) -> NeonResult<f64> {
+        crate::performance::time_origin(self)
+    }
+
+    /// Converts an [`Instant`](std::time::Instant) to a timestamp on the same scale as
+    /// `performance.now()`, i.e., milliseconds since [`Context::time_origin`].
+    ///
+    /// This allows timings captured in Rust, for example on a thread that later sends
+    /// them over a [`Channel`](crate::event::Channel), to be compared with
+    /// `performance.now()` marks in JavaScript.
+    ///
+    /// **See also:** [`neon::performance`](crate::performance)
+    fn to_performance_timestamp(&mut self, instant: std::time::Instant) -> NeonResult<f64> {
+        crate::performance::to_timestamp(self, instant)
+    }
+}
+
+// Reads `worker_threads.isMainThread`, which is cached per instance when possible
+fn is_main_thread<'a, C: Context<'a>>(cx: &mut C) -> bool {
+    fn read<'a, C: Context<'a>>(cx: &mut C) -> bool {
+        cx.try_catch(|cx| {
+            let worker_threads =
+                crate::reflect::builtin_module::<JsObject, _>(cx, "worker_threads")?;
+            let is_main_thread = worker_threads.get::<JsBoolean, _, _>(cx, "isMainThread")?;
+
+            Ok(is_main_thread.value(cx))
+        })
+        .unwrap_or(true)
+    }
+
+    #[cfg(feature = "napi-6")]
+    {
+        static IS_MAIN_THREAD: crate::thread::LocalKey<bool> = crate::thread::LocalKey::new();
+
+        if let Some(is_main_thread) = IS_MAIN_THREAD.get(cx) {
+            return *is_main_thread;
+        }
+
+        let is_main_thread = read(cx);
+
+        *IS_MAIN_THREAD.get_or_init(cx, || is_main_thread)
+    }
+
+    #[cfg(not(feature = "napi-6"))]
+    read(cx)
+}
+
+// Returns the URL or path that relative module specifiers are resolved against
+fn module_base_url<'a, C: Context<'a>>(cx: &mut C) -> String {
+    if let Some(url) = unsafe { sys::module::file_name(cx.env().to_raw()) } {
+        return url;
+    }
+
+    // A trailing separator indicates a directory to `createRequire`
+    let mut dir = std::env::current_dir()
+        .map(|dir| dir.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    if !dir.ends_with(std::path::MAIN_SEPARATOR) {
+        dir.push(std::path::MAIN_SEPARATOR);
+    }
+
+    dir
+}
+
+// Creates a `require` function with `module.createRequire`; cached by
+// `intrinsics::require_function`
+pub(crate) fn create_require<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsFunction> {
+    // The `module` module is the `Module` constructor
+    let module = crate::reflect::builtin_module::<JsFunction, _>(cx, "module")?;
+    let create_require = module.get::<JsFunction, _, _>(cx, "createRequire")?;
+    let base = module_base_url(cx);
+    let base = cx.string(base);
+
+    create_require
+        .call(cx, module, [base.upcast()])?
+        .downcast_or_throw(cx)
+}
+
+// Returns a function that calls `import()`. Scripts evaluated by Node-API cannot
+// use dynamic imports, but functions created with the `Function` constructor can.
+fn import_function<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsFunction> {
+    let function = cx.global().get::<JsFunction, _, _>(cx, "Function")?;
+    let param = cx.string("specifier");
+    let body = cx.string("return import(specifier)");
+
+    function
+        .construct(cx, [param.upcast(), body.upcast()])?
+        .downcast_or_throw(cx)
+}
+
+// Converts a `file://` URL to a path, decoding percent-encoded characters. Other
+// strings are returned unchanged.
+fn file_url_to_path(url: &str) -> String {
+    let path = match url.strip_prefix("file://") {
+        Some(path) => path,
+        None => return url.to_string(),
+    };
+
+    // Windows paths are formatted as `file:///C:/path`
+    let path = match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => &path[1..],
+        _ => path,
+    };
+
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = match bytes.get(i..i + 3) {
+            Some([b'%', hi, lo]) => hex(*hi).zip(hex(*lo)).map(|(hi, lo)| hi << 4 | lo),
+            _ => None,
+        };
+
+        match escaped {
+            Some(b) => {
+                decoded.push(b);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// An execution context of module initialization.
+pub struct ModuleContext<'a> {
+    env: Env,
+    exports: Handle<'a, JsObject>,
+    exported: module::Registry,
+}
+
+impl<'a> UnwindSafe for ModuleContext<'a> {}
+
+impl<'a> ModuleContext<'a> {
+    pub(crate) fn with<T, F: for<'b> FnOnce(ModuleContext<'b>) -> T>(
+        env: Env,
+        exports: Handle<'a, JsObject>,
+        f: F,
+    ) -> T {
+        f(ModuleContext {
+            env,
+            exports,
+            exported: Default::default(),
+        })
+    }
+
+    // Records that the call at `location` exports `key`, throwing if it already was
+    pub(crate) fn register_export(
+        &mut self,
+        key: &str,
+        location: &'static Location<'static>,
+    ) -> NeonResult<()> {
+        match self.exported.register(key, location) {
+            Ok(()) => Ok(()),
+            Err(msg) => self.throw_error(msg),
+        }
+    }
+
+    #[cfg(not(feature = "napi-5"))]
     /// Convenience method for exporting a Neon function from a module.
+    ///
+    /// Throws an `Error` if `key` was already exported during the module's
+    /// initialization; use
+    /// [`export_function_overwrite`](ModuleContext::export_function_overwrite) to
+    /// replace an export.
+    #[track_caller]
     pub fn export_function<T: Value>(
         &mut self,
         key: &str,
         f: fn(FunctionContext) -> JsResult<T>,
     ) -> NeonResult<()> {
-        let value = JsFunction::new(self, f)?.upcast::<JsValue>();
+        self.register_export(key, Location::caller())?;
+
+        let options = FunctionOptions::new().name(key);
+        let value = JsFunction::with_options(self, options, f)?.upcast::<JsValue>();
         self.exports.clone().set(self, key, value)?;
         Ok(())
     }
 
     #[cfg(feature = "napi-5")]
     /// Convenience method for exporting a Neon function from a module.
+    ///
+    /// Throws an `Error` naming both calls if `key` was already exported during the
+    /// module's initialization, e.g., by [`export_value`](ModuleContext::export_value)
+    /// or a [`ModuleBuilder`]; use
+    /// [`export_function_overwrite`](ModuleContext::export_function_overwrite) to
+    /// replace an export.
+    ///
+    /// The function may be a closure, e.g., capturing configuration computed when the
+    /// module is loaded. The closure is owned by the exported function and dropped when
+    /// the function is garbage collected, at the latest when the module's environment
+    /// is torn down.
+    ///
+    /// A function that was already created, e.g., with [`JsFunction::bind`], may be
+    /// exported with [`export_value`](ModuleContext::export_value).
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// # fn add(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    /// #     let a = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    /// #     let b = cx.argument::<JsNumber>(1)?.value(&mut cx);
+    /// #     Ok(cx.number(a + b))
+    /// # }
+    /// fn init(mut cx: ModuleContext) -> NeonResult<()> {
+    ///     let prefix = std::env::var("LOG_PREFIX").unwrap_or_default();
+    ///
+    ///     cx.export_function("prefixed", move |mut cx| {
+    ///         let message = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///
+    ///         Ok(cx.string(format!("{}{}", prefix, message)))
+    ///     })?;
+    ///
+    ///     // `increment(n)` is `add(1, n)`
+    ///     let add = JsFunction::new(&mut cx, add)?;
+    ///     let this = cx.undefined();
+    ///     let increment = add.bind(&mut cx, this, (1.0,))?;
+    ///
+    ///     cx.export_value("increment", increment)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[track_caller]
     pub fn export_function<F, V>(&mut self, key: &str, f: F) -> NeonResult<()>
     where
         F: Fn(FunctionContext) -> JsResult<V> + 'static,
         V: Value,
     {
-        let value = JsFunction::new(self, f)?.upcast::<JsValue>();
+        self.register_export(key, Location::caller())?;
+
+        let options = FunctionOptions::new().name(key);
+        let value = JsFunction::with_options(self, options, f)?.upcast::<JsValue>();
         // Note: Cloning `exports` is necessary to avoid holding a shared reference to
         // `self` while attempting to use it mutably in `set`.
         self.exports.clone().set(self, key, value)?;
         Ok(())
     }
 
+    #[cfg(not(feature = "napi-5"))]
+    /// Exports a Neon function like
+    /// [`export_function`](ModuleContext::export_function), replacing any export
+    /// named `key` instead of throwing.
+    #[track_caller]
+    pub fn export_function_overwrite<T: Value>(
+        &mut self,
+        key: &str,
+        f: fn(FunctionContext) -> JsResult<T>,
+    ) -> NeonResult<()> {
+        self.exported.remove(key);
+        self.export_function(key, f)
+    }
+
+    #[cfg(feature = "napi-5")]
+    /// Exports a Neon function like
+    /// [`export_function`](ModuleContext::export_function), replacing any export
+    /// named `key` instead of throwing.
+    #[track_caller]
+    pub fn export_function_overwrite<F, V>(&mut self, key: &str, f: F) -> NeonResult<()>
+    where
+        F: Fn(FunctionContext) -> JsResult<V> + 'static,
+        V: Value,
+    {
+        self.exported.remove(key);
+        self.export_function(key, f)
+    }
+
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    /// Exports a function that reports failures with a Rust [`Result`] instead of
+    /// a [`NeonResult`].
+    ///
+    /// When `f` returns `Err`, the error is thrown with [`ThrowError::throw`]: an error
+    /// converting into an [`ErrorSpec`](crate::types::ErrorSpec) is thrown as specified,
+    /// and a [`neon::error::Error`](crate::error::Error) is thrown with the translation
+    /// registered for its type.
+    /// If a JavaScript exception is already pending, e.g., because `f` ignored a
+    /// [`Throw`](crate::result::Throw), that exception is propagated instead. Panics
+    /// are handled the same as with [`export_function`](ModuleContext::export_function).
+    ///
+    /// ```
+    /// # use neon::{prelude::*, types::ErrorSpec};
+    /// fn parse(mut cx: FunctionContext) -> Result<Handle<JsNumber>, ErrorSpec> {
+    ///     let s = cx
+    ///         .argument::<JsString>(0)
+    ///         .map_err(|_| ErrorSpec::type_error("expected a string"))?
+    ///         .value(&mut cx);
+    ///
+    ///     let n = s.parse::<f64>().map_err(|err| {
+    ///         ErrorSpec::error(err.to_string()).with_code("ERR_PARSE")
+    ///     })?;
+    ///
+    ///     Ok(cx.number(n))
+    /// }
+    ///
+    /// #[neon::main]
+    /// fn main(mut cx: ModuleContext) -> NeonResult<()> {
+    ///     cx.export_result_function("parse", parse)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn export_result_function<F, V, E>(&mut self, key: &str, f: F) -> NeonResult<()>
+    where
+        F: Fn(FunctionContext) -> Result<Handle<V>, E> + 'static,
+        V: Value,
+        E: ThrowError,
+    {
+        self.export_function(key, move |cx| {
+            let env = cx.env();
+
+            f(cx).or_else(|err| {
+                if unsafe { sys::error::is_throwing(env.to_raw()) } {
+                    return Err(Throw::new());
+                }
+
+                TaskContext::with_context(env, |mut cx| err.throw(&mut cx))
+            })
+        })
+    }
+
     /// Exports a JavaScript value from a Neon module.
+    ///
+    /// Throws an `Error` if `key` was already exported during the module's
+    /// initialization.
+    #[track_caller]
     pub fn export_value<T: Value>(&mut self, key: &str, val: Handle<T>) -> NeonResult<()> {
+        self.register_export(key, Location::caller())?;
         self.exports.clone().set(self, key, val)?;
         Ok(())
     }
 
+    /// Returns the names exported so far by this module's initialization, in the order
+    /// they were first exported.
+    ///
+    /// Includes the names exported with [`export_function`](ModuleContext::export_function),
+    /// [`export_value`](ModuleContext::export_value) and a [`ModuleBuilder`], but not
+    /// properties set directly on the [`exports_object`](ModuleContext::exports_object).
+    pub fn exported_names(&self) -> Vec<String> {
+        self.exported.names()
+    }
+
     /// Produces a handle to a module's exports object.
     pub fn exports_object(&mut self) -> JsResult<'a, JsObject> {
         Ok(self.exports)
@@ -604,8 +1723,84 @@ impl<'a> Context<'a> for ComputeContext<'a> {}
 pub struct FunctionContext<'a> {
     env: Env,
     info: &'a CallbackInfo<'a>,
+    name: Option<&'a str>,
+
+    // Lazily fetched on first access, since many functions never read their arguments
+    data: OnceCell<ArgsInfo<'a>>,
+}
+
+/// The arguments and `this`-binding of a function call, read from the engine with a single
+/// call to `napi_get_cb_info` and cached on the [`FunctionContext`].
+///
+/// The argument accessors of `FunctionContext`, e.g., [`FunctionContext::argument`], all
+/// read from the cache, so a function that reads many arguments only queries the engine
+/// once.
+///
+/// ```
+/// # use neon::prelude::*;
+/// fn count_strings(mut cx: FunctionContext) -> JsResult<JsNumber> {
+///     let args = cx.args_info().iter().collect::<Vec<_>>();
+///     let n = args.iter().filter(|v| v.is_a::<JsString, _>(&mut cx)).count();
+///
+///     Ok(cx.number(n as f64))
+/// }
+/// ```
+pub struct ArgsInfo<'a> {
+    env: Env,
+    data: sys::call::CallbackData,
+    _lifetime: PhantomData<&'a ()>,
+}
+
+impl<'a> ArgsInfo<'a> {
+    /// The number of arguments that were passed to the function.
+    pub fn len(&self) -> usize {
+        self.data.argv.len()
+    }
+
+    /// Indicates if no arguments were passed to the function.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Produces the `i`th argument, or `None` if `i` is greater than or equal to `self.len()`.
+    pub fn get(&self, i: usize) -> Option<Handle<'a, JsValue>> {
+        self.data
+            .argv
+            .get(i)
+            .map(|v| Handle::new_internal(JsValue::from_raw(self.env, v)))
+    }
+
+    /// Produces the `this`-binding of the call.
+    pub fn this(&self) -> Handle<'a, JsValue> {
+        JsValue::new_internal(self.data.this)
+    }
+
+    /// Produces an iterator over the arguments, starting at index `from`.
+    fn iter_from(&self, from: usize) -> impl Iterator<Item = Handle<'a, JsValue>> + '_ {
+        let env = self.env;
+
+        self.data
+            .argv
+            .slice_from(from)
+            .iter()
+            .map(move |v| Handle::new_internal(JsValue::from_raw(env, *v)))
+    }
 
-    arguments: Option<sys::call::Arguments>,
+    /// Produces an iterator over the arguments.
+    ///
+    /// The handles are copied out of the cache, so the iterator can be collected and then
+    /// used with the context, as in [`FunctionContext::arguments_iter`].
+    pub fn iter(&self) -> impl Iterator<Item = Handle<'a, JsValue>> + '_ {
+        self.iter_from(0)
+    }
+}
+
+impl fmt::Debug for ArgsInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ArgsInfo")
+            .field("len", &self.len())
+            .finish()
+    }
 }
 
 impl<'a> UnwindSafe for FunctionContext<'a> {}
@@ -619,18 +1814,30 @@ impl<'a> FunctionContext<'a> {
     pub(crate) fn with<U, F: for<'b> FnOnce(FunctionContext<'b>) -> U>(
         env: Env,
         info: &'a CallbackInfo<'a>,
+        name: Option<&'a str>,
         f: F,
     ) -> U {
         f(FunctionContext {
             env,
             info,
-            arguments: None,
+            name,
+            data: OnceCell::new(),
         })
     }
 
+    /// Returns the name of the function being called, if one was given when it was
+    /// created, e.g., the key passed to [`ModuleContext::export_function`] or the name
+    /// set with [`FunctionOptions::name`](crate::types::function::FunctionOptions::name).
+    ///
+    /// The name prefixes the messages of errors thrown when extracting arguments, e.g.,
+    /// `parseConfig: expected string at argument 0, got number`.
+    pub fn function_name(&self) -> Option<&'a str> {
+        self.name
+    }
+
     /// Indicates the number of arguments that were passed to the function.
     pub fn len(&self) -> usize {
-        self.info.len(self)
+        self.args_info().len()
     }
 
     /// Indicates if no arguments were passed to the function.
@@ -640,25 +1847,165 @@ impl<'a> FunctionContext<'a> {
 
     /// Produces the `i`th argument, or `None` if `i` is greater than or equal to `self.len()`.
     pub fn argument_opt(&mut self, i: usize) -> Option<Handle<'a, JsValue>> {
-        let argv = if let Some(argv) = self.arguments.as_ref() {
-            argv
-        } else {
-            let argv = self.info.argv(self);
-            self.arguments.insert(argv)
-        };
+        self.args_info().get(i)
+    }
 
-        argv.get(i)
-            .map(|v| Handle::new_internal(JsValue::from_raw(self.env(), v)))
+    /// Produces the arguments and `this`-binding of the call, fetching them from the
+    /// engine on first use.
+    pub fn args_info(&self) -> &ArgsInfo<'a> {
+        self.data.get_or_init(|| ArgsInfo {
+            env: self.env,
+            data: self.info.data(self),
+            _lifetime: PhantomData,
+        })
     }
 
     /// Produces the `i`th argument and casts it to the type `V`, or throws an exception if `i` is greater than or equal to `self.len()` or cannot be cast to `V`.
     pub fn argument<V: Value>(&mut self, i: usize) -> JsResult<'a, V> {
         match self.argument_opt(i) {
-            Some(v) => v.downcast_or_throw(self),
-            None => self.throw_type_error("not enough arguments"),
+            Some(v) => v.downcast_or_throw_at(self, self.name, Some(i)),
+            None => {
+                let msg = with_function_name(self.name, "not enough arguments".to_string());
+
+                self.throw_type_error(msg)
+            }
+        }
+    }
+
+    /// Produces the `i`th argument and casts it to the type `V`, or returns `default` if
+    /// `i` is greater than or equal to `self.len()` or the argument is `undefined`.
+    ///
+    /// Throws an exception if the argument cannot be cast to `V`.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn repeat(mut cx: FunctionContext) -> JsResult<JsString> {
+    ///     let s = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///     let default = cx.number(1);
+    ///     let n = cx.argument_or(1, default)?.value(&mut cx);
+    ///
+    ///     Ok(cx.string(s.repeat(n as usize)))
+    /// }
+    /// ```
+    pub fn argument_or<V: Value>(&mut self, i: usize, default: Handle<'a, V>) -> JsResult<'a, V> {
+        match self.argument_opt(i) {
+            Some(v) if !v.is_a::<JsUndefined, _>(self) => {
+                v.downcast_or_throw_at(self, self.name, Some(i))
+            }
+            _ => Ok(default),
         }
     }
 
+    /// Extracts the `i`th argument as a Rust value with [`TryFromJs`]. A missing
+    /// argument is extracted as `undefined`.
+    ///
+    /// Throws an exception if the argument cannot be converted.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use neon::types::extract::MaybeUndefined;
+    ///
+    /// fn describe(mut cx: FunctionContext) -> JsResult<JsString> {
+    ///     let description = match cx.extract_argument::<MaybeUndefined<String>>(0)? {
+    ///         MaybeUndefined::Missing => "missing".to_string(),
+    ///         MaybeUndefined::Null => "null".to_string(),
+    ///         MaybeUndefined::Value(s) => s,
+    ///     };
+    ///
+    ///     Ok(cx.string(description))
+    /// }
+    /// ```
+    pub fn extract_argument<T: TryFromJs<'a>>(&mut self, i: usize) -> NeonResult<T> {
+        let v = match self.argument_opt(i) {
+            Some(v) => v,
+            None => self.undefined().upcast(),
+        };
+
+        T::try_from_js(self, v)
+    }
+
+    /// Produces the arguments starting at index `from`, like a JavaScript rest parameter
+    /// (`...args`). Returns an empty `Vec` if `from` is greater than or equal to `self.len()`.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn log(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let level = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///
+    ///     for message in cx.rest_arguments(1) {
+    ///         let message = message.to_string(&mut cx)?.value(&mut cx);
+    ///         println!("[{}] {}", level, message);
+    ///     }
+    ///
+    ///     Ok(cx.undefined())
+    /// }
+    /// ```
+    pub fn rest_arguments(&mut self, from: usize) -> Vec<Handle<'a, JsValue>> {
+        self.args_info().iter_from(from).collect()
+    }
+
+    /// Produces an iterator over all of the arguments passed to the function.
+    ///
+    /// The iterator does not borrow the context, so it may be used while iterating.
+    pub fn arguments_iter(&mut self) -> impl Iterator<Item = Handle<'a, JsValue>> {
+        self.rest_arguments(0).into_iter()
+    }
+
+    /// Captures the arguments, `this`-binding and `new.target` of the call, so that they
+    /// can be forwarded unchanged to another function with
+    /// [`JsFunction::call_with_forwarded`].
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn traced(mut cx: FunctionContext) -> JsResult<JsValue> {
+    ///     let inner: Handle<JsFunction> = cx.this::<JsObject>()?.get(&mut cx, "inner")?;
+    ///     let forwarded = cx.forward_args();
+    ///
+    ///     println!("calling inner with {} arguments", forwarded.len());
+    ///
+    ///     let this = forwarded.this();
+    ///     inner.call_with_forwarded(&mut cx, this, forwarded)
+    /// }
+    /// ```
+    pub fn forward_args(&mut self) -> ForwardedArgs<'a> {
+        let info = self.args_info();
+        let args = info.iter().collect();
+        let this = info.this();
+        let new_target = self.new_target();
+
+        ForwardedArgs::new(this, new_target, args)
+    }
+
+    /// Produces the value of [`new.target`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/new.target),
+    /// or `None` if the function was called without `new`.
+    ///
+    /// When the function is the constructor of a subclass, `new.target` is the subclass
+    /// rather than the function itself.
+    pub fn new_target(&mut self) -> Option<Handle<'a, JsFunction>> {
+        let env = self.env();
+
+        self.info
+            .new_target(self)
+            .map(|v| Handle::new_internal(JsFunction::from_raw(env, v)))
+    }
+
+    /// Indicates whether the function was called with `new`. Equivalent to
+    /// `matches!(cx.kind(), CallKind::Construct)`.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn point(mut cx: FunctionContext) -> JsResult<JsObject> {
+    ///     if !cx.is_construct_call() {
+    ///         return cx.throw_type_error("Class constructor Point cannot be invoked without 'new'");
+    ///     }
+    ///
+    ///     cx.this()
+    /// }
+    /// ```
+    pub fn is_construct_call(&self) -> bool {
+        matches!(self.kind(), CallKind::Construct)
+    }
+
     /// Produces a handle to the `this`-binding and attempts to downcast as a specific type.
     /// Equivalent to calling `cx.this_value().downcast_or_throw(&mut cx)`.
     ///
@@ -669,7 +2016,7 @@ impl<'a> FunctionContext<'a> {
 
     /// Produces a handle to the function's [`this`-binding](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/this#function_context).
     pub fn this_value(&mut self) -> Handle<'a, JsValue> {
-        JsValue::new_internal(self.info.this(self))
+        self.args_info().this()
     }
 }
 
@@ -704,8 +2051,15 @@ impl<'a> ContextInternal<'a> for TaskContext<'a> {
 
 impl<'a> Context<'a> for TaskContext<'a> {}
 
-/// A view of the JS engine in the context of a finalize method on garbage collection
-pub(crate) struct FinalizeContext<'a> {
+/// The context of [`Finalize::finalize_gc`], called while a value is garbage collected.
+///
+/// Calling JavaScript during garbage collection may observe or corrupt the state of
+/// the engine, so a `FinalizeContext` does not implement [`Context`]: it cannot create
+/// values or call functions. It can release a [`Root`](crate::handle::Root) with
+/// [`FinalizeContext::drop_root`] and schedule work that needs a full context with
+/// [`FinalizeContext::defer`]. Values may also be sent to other threads, e.g., with a
+/// [`Channel`].
+pub struct FinalizeContext<'a> {
     env: Env,
     _phantom_inner: PhantomData<&'a ()>,
 }
@@ -717,12 +2071,53 @@ impl<'a> FinalizeContext<'a> {
             _phantom_inner: PhantomData,
         })
     }
-}
 
-impl<'a> ContextInternal<'a> for FinalizeContext<'a> {
-    fn env(&self) -> Env {
-        self.env
+    // Calls `f` with a full context, for finalizers written before `finalize_gc`
+    pub(crate) fn with_context<T, F: for<'b> FnOnce(&mut TaskContext<'b>) -> T>(
+        &mut self,
+        f: F,
+    ) -> T {
+        TaskContext::with_context(self.env, |mut cx| f(&mut cx))
     }
-}
 
-impl<'a> Context<'a> for FinalizeContext<'a> {}
+    /// Releases `root` without returning the referenced object. Equivalent to
+    /// [`Root::drop`](crate::handle::Root::drop).
+    pub fn drop_root<T: Object>(&mut self, root: crate::handle::Root<T>) {
+        self.with_context(|cx| root.drop(cx));
+    }
+
+    /// Schedules `f` to be called with a full context on a later tick of the event
+    /// loop, after garbage collection has finished.
+    ///
+    /// If the JavaScript environment is shutting down, `f` is dropped without being
+    /// called. If `f` throws, the exception is reported as an unhandled rejection, as
+    /// with any other [`Channel`] closure.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use neon::context::FinalizeContext;
+    ///
+    /// struct Connection {
+    ///     on_close: Root<JsFunction>,
+    /// }
+    ///
+    /// impl Finalize for Connection {
+    ///     fn finalize_gc(self, cx: &mut FinalizeContext) {
+    ///         let on_close = self.on_close;
+    ///
+    ///         cx.defer(move |mut cx| on_close.into_inner(&mut cx).call_with(&cx).exec(&mut cx));
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "napi-4")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-4")))]
+    pub fn defer<F>(&mut self, f: F)
+    where
+        F: FnOnce(TaskContext) -> NeonResult<()> + Send + 'static,
+    {
+        let channel = self.with_context(|cx| cx.channel());
+
+        // Pending closures keep the event loop alive after the channel is dropped
+        let _ = channel.try_send(f);
+    }
+}
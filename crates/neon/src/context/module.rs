@@ -0,0 +1,474 @@
+use std::panic::Location;
+
+use crate::{
+    context::{Context, FunctionContext, ModuleContext},
+    handle::{Handle, Managed},
+    result::{JsResult, NeonResult, Throw},
+    sys,
+    types::{JsFunction, JsObject, JsValue, Value},
+};
+
+#[cfg(feature = "napi-5")]
+use crate::object::Object;
+
+type Build<'cx> = Box<dyn FnOnce(&mut ModuleContext<'cx>) -> JsResult<'cx, JsValue> + 'cx>;
+
+#[cfg(feature = "napi-5")]
+type Init = Box<dyn for<'b> FnOnce(&mut FunctionContext<'b>) -> JsResult<'b, JsValue>>;
+
+/// Builds a module's exports, including nested namespaces, in a single pass.
+///
+/// Exports are added with the same methods as a [`Namespace`] and are defined on
+/// `module.exports` by [`ModuleBuilder::finish`]. Each export is enumerable and
+/// writable by default; the attributes of the most recently added export may be
+/// changed with [`Namespace::enumerable`] and [`Namespace::read_only`].
+///
+/// Like [`ModuleContext::export_function`], [`ModuleBuilder::finish`] returns an error
+/// if a name is exported twice, either in the same namespace or by another call during
+/// the module's initialization.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::context::ModuleBuilder;
+///
+/// fn alloc(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+///     let len = cx.argument::<JsNumber>(0)?.value(&mut cx);
+///
+///     cx.buffer(len as usize)
+/// }
+///
+/// #[neon::main]
+/// fn main(mut cx: ModuleContext) -> NeonResult<()> {
+///     let version = cx.string(env!("CARGO_PKG_VERSION"));
+///
+///     ModuleBuilder::new(&mut cx)
+///         .namespace("buffers", |ns| {
+///             ns.function("alloc", alloc);
+///         })
+///         .value("version", version)
+///         .read_only()
+///         .finish()
+/// }
+/// ```
+pub struct ModuleBuilder<'a, 'cx> {
+    cx: &'a mut ModuleContext<'cx>,
+    exports: Namespace<'cx>,
+}
+
+impl<'a, 'cx> ModuleBuilder<'a, 'cx> {
+    /// Creates a builder for the exports of the module being initialized.
+    pub fn new(cx: &'a mut ModuleContext<'cx>) -> Self {
+        Self {
+            cx,
+            exports: Namespace::new(),
+        }
+    }
+
+    /// Defines every export on `module.exports`.
+    pub fn finish(&mut self) -> NeonResult<()> {
+        let exports = self.cx.exports_object()?;
+        let entries = std::mem::take(&mut self.exports.entries);
+
+        // Duplicates are found before defining any export
+        for entry in &entries {
+            if let Kind::Namespace(namespace) = &entry.kind {
+                if let Err(msg) = check_duplicates(&entry.name, namespace) {
+                    return self.cx.throw_error(msg);
+                }
+            }
+        }
+
+        for entry in &entries {
+            self.cx.register_export(&entry.name, entry.location)?;
+        }
+
+        define_entries(self.cx, exports, entries)
+    }
+}
+
+// The `Namespace` builder methods are repeated so that calls can be chained with `finish`
+impl<'a, 'cx> ModuleBuilder<'a, 'cx> {
+    #[cfg(not(feature = "napi-5"))]
+    /// See [`Namespace::function`].
+    #[track_caller]
+    pub fn function<V: Value>(
+        &mut self,
+        name: &str,
+        f: fn(FunctionContext) -> JsResult<V>,
+    ) -> &mut Self {
+        self.exports.function(name, f);
+        self
+    }
+
+    #[cfg(feature = "napi-5")]
+    /// See [`Namespace::function`].
+    #[track_caller]
+    pub fn function<F, V>(&mut self, name: &str, f: F) -> &mut Self
+    where
+        F: Fn(FunctionContext) -> JsResult<V> + 'static,
+        V: Value,
+    {
+        self.exports.function(name, f);
+        self
+    }
+
+    /// See [`Namespace::value`].
+    #[track_caller]
+    pub fn value<V: Value>(&mut self, name: &str, value: Handle<'cx, V>) -> &mut Self {
+        self.exports.value(name, value);
+        self
+    }
+
+    /// See [`Namespace::namespace`].
+    #[track_caller]
+    pub fn namespace<F>(&mut self, name: &str, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Namespace<'cx>),
+    {
+        self.exports.namespace(name, f);
+        self
+    }
+
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    /// See [`Namespace::lazy`].
+    #[track_caller]
+    pub fn lazy<F, V>(&mut self, name: &str, init: F) -> &mut Self
+    where
+        F: for<'b> FnOnce(&mut FunctionContext<'b>) -> JsResult<'b, V> + 'static,
+        V: Value,
+    {
+        self.exports.lazy(name, init);
+        self
+    }
+
+    /// See [`Namespace::enumerable`].
+    pub fn enumerable(&mut self, enumerable: bool) -> &mut Self {
+        self.exports.enumerable(enumerable);
+        self
+    }
+
+    /// See [`Namespace::read_only`].
+    pub fn read_only(&mut self) -> &mut Self {
+        self.exports.read_only();
+        self
+    }
+}
+
+/// A set of exports, defined as properties of an object by [`ModuleBuilder::finish`].
+pub struct Namespace<'cx> {
+    entries: Vec<Entry<'cx>>,
+}
+
+struct Entry<'cx> {
+    name: String,
+    // Location of the call that added the export
+    location: &'static Location<'static>,
+    kind: Kind<'cx>,
+    enumerable: bool,
+    read_only: bool,
+}
+
+enum Kind<'cx> {
+    Value(Build<'cx>),
+    Namespace(Namespace<'cx>),
+    #[cfg(feature = "napi-5")]
+    Lazy(Init),
+}
+
+impl<'cx> Namespace<'cx> {
+    fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    #[track_caller]
+    fn push(&mut self, name: &str, kind: Kind<'cx>) -> &mut Self {
+        self.entries.push(Entry {
+            name: name.to_string(),
+            location: Location::caller(),
+            kind,
+            enumerable: true,
+            read_only: false,
+        });
+
+        self
+    }
+
+    #[cfg(not(feature = "napi-5"))]
+    /// Exports a Neon function as `name`.
+    #[track_caller]
+    pub fn function<V: Value>(
+        &mut self,
+        name: &str,
+        f: fn(FunctionContext) -> JsResult<V>,
+    ) -> &mut Self {
+        self.push(
+            name,
+            Kind::Value(Box::new(move |cx| Ok(JsFunction::new(cx, f)?.upcast()))),
+        )
+    }
+
+    #[cfg(feature = "napi-5")]
+    /// Exports a Neon function as `name`.
+    #[track_caller]
+    pub fn function<F, V>(&mut self, name: &str, f: F) -> &mut Self
+    where
+        F: Fn(FunctionContext) -> JsResult<V> + 'static,
+        V: Value,
+    {
+        self.push(
+            name,
+            Kind::Value(Box::new(move |cx| Ok(JsFunction::new(cx, f)?.upcast()))),
+        )
+    }
+
+    /// Exports `value` as `name`.
+    #[track_caller]
+    pub fn value<V: Value>(&mut self, name: &str, value: Handle<'cx, V>) -> &mut Self {
+        self.push(name, Kind::Value(Box::new(move |_| Ok(value.upcast()))))
+    }
+
+    /// Exports a nested object as `name`, containing the exports added by `f`.
+    #[track_caller]
+    pub fn namespace<F>(&mut self, name: &str, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Namespace<'cx>),
+    {
+        let mut namespace = Namespace::new();
+
+        f(&mut namespace);
+        self.push(name, Kind::Namespace(namespace))
+    }
+
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    /// Exports a value as `name` that is created by `init` when the property is first
+    /// read.
+    ///
+    /// The export is defined with a getter which replaces itself with the value
+    /// returned by `init`, defined on the object the property is read from. If `init`
+    /// throws, the exception is thrown to the reader and later reads throw an `Error`.
+    #[track_caller]
+    pub fn lazy<F, V>(&mut self, name: &str, init: F) -> &mut Self
+    where
+        F: for<'b> FnOnce(&mut FunctionContext<'b>) -> JsResult<'b, V> + 'static,
+        V: Value,
+    {
+        let init: Init = Box::new(move |cx| Ok(init(cx)?.upcast()));
+
+        self.push(name, Kind::Lazy(init))
+    }
+
+    /// Sets whether the most recently added export is enumerable, e.g., listed by
+    /// `Object.keys`. _Default: `true`_
+    pub fn enumerable(&mut self, enumerable: bool) -> &mut Self {
+        if let Some(entry) = self.entries.last_mut() {
+            entry.enumerable = enumerable;
+        }
+
+        self
+    }
+
+    /// Makes the most recently added export read-only; it cannot be reassigned or
+    /// deleted. A read-only namespace is also frozen.
+    pub fn read_only(&mut self) -> &mut Self {
+        if let Some(entry) = self.entries.last_mut() {
+            entry.read_only = true;
+        }
+
+        self
+    }
+}
+
+// Names set on an object by Neon, with the location of the call that set each
+#[derive(Default)]
+pub(crate) struct Registry(Vec<(String, &'static Location<'static>)>);
+
+impl Registry {
+    // Records `name`, failing with a message naming both calls if it is already set
+    pub(crate) fn register(
+        &mut self,
+        name: &str,
+        location: &'static Location<'static>,
+    ) -> Result<(), String> {
+        match self.0.iter().find(|(other, _)| other == name) {
+            Some((_, first)) => Err(format!(
+                "`{}` is exported more than once, at {} and at {}; use \
+                 `ModuleContext::export_function_overwrite` to replace an export",
+                name, first, location,
+            )),
+            None => {
+                self.0.push((name.to_string(), location));
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) {
+        self.0.retain(|(other, _)| other != name);
+    }
+
+    pub(crate) fn names(&self) -> Vec<String> {
+        self.0.iter().map(|(name, _)| name.clone()).collect()
+    }
+}
+
+// Checks that every name in `namespace`, and in its nested namespaces, is unique
+fn check_duplicates(path: &str, namespace: &Namespace) -> Result<(), String> {
+    let mut registry = Registry::default();
+
+    for entry in &namespace.entries {
+        let name = format!("{}.{}", path, entry.name);
+
+        registry.register(&name, entry.location)?;
+
+        if let Kind::Namespace(namespace) = &entry.kind {
+            check_duplicates(&name, namespace)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn define_entries<'cx>(
+    cx: &mut ModuleContext<'cx>,
+    target: Handle<'cx, JsObject>,
+    entries: Vec<Entry<'cx>>,
+) -> NeonResult<()> {
+    for entry in entries {
+        let value = match entry.kind {
+            Kind::Value(build) => build(cx)?,
+            Kind::Namespace(namespace) => {
+                let object = cx.empty_object();
+
+                define_entries(cx, object, namespace.entries)?;
+
+                if entry.read_only {
+                    freeze(cx, object)?;
+                }
+
+                object.upcast()
+            }
+            #[cfg(feature = "napi-5")]
+            Kind::Lazy(init) => {
+                define_lazy(
+                    cx,
+                    target,
+                    entry.name,
+                    init,
+                    entry.enumerable,
+                    entry.read_only,
+                )?;
+                continue;
+            }
+        };
+
+        define_value(
+            cx,
+            target,
+            &entry.name,
+            value,
+            entry.enumerable,
+            entry.read_only,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn define_value<'a, C: Context<'a>>(
+    cx: &mut C,
+    target: Handle<JsObject>,
+    name: &str,
+    value: Handle<JsValue>,
+    enumerable: bool,
+    read_only: bool,
+) -> NeonResult<()> {
+    let mut attributes = sys::PropertyAttributes::DEFAULT;
+
+    if enumerable {
+        attributes = attributes | sys::PropertyAttributes::ENUMERABLE;
+    }
+
+    if !read_only {
+        attributes =
+            attributes | sys::PropertyAttributes::WRITABLE | sys::PropertyAttributes::CONFIGURABLE;
+    }
+
+    let env = cx.env().to_raw();
+    let key = cx.string(name);
+    let defined = unsafe {
+        sys::object::define_value(
+            env,
+            target.to_raw(),
+            key.to_raw(),
+            value.to_raw(),
+            attributes,
+        )
+    };
+
+    if !defined {
+        return Err(Throw::new());
+    }
+
+    Ok(())
+}
+
+// Calls `Object.freeze(object)`
+fn freeze<'a, C: Context<'a>>(cx: &mut C, object: Handle<JsObject>) -> NeonResult<()> {
+    let global = cx.global();
+    let freeze = crate::intrinsics::object_freeze(cx)?;
+
+    freeze.exec(cx, global, [object.upcast()])
+}
+
+#[cfg(feature = "napi-5")]
+// Defines a configurable getter with `Object.defineProperty` that replaces itself with
+// the initialized value
+fn define_lazy<'a, C: Context<'a>>(
+    cx: &mut C,
+    target: Handle<JsObject>,
+    name: String,
+    init: Init,
+    enumerable: bool,
+    read_only: bool,
+) -> NeonResult<()> {
+    // The getter is owned by `target`, so it finds `target` as its receiver instead of
+    // holding a `Root` of it, which would keep both alive for the life of the module
+    let mut init = Some(init);
+    let key = cx.string(&name);
+    let get = JsFunction::new_mut(cx, move |mut cx| {
+        let target = cx.this::<JsObject>()?;
+        let init = match init.take() {
+            Some(init) => init,
+            None => return cx.throw_error(format!("lazy export `{}` failed to initialize", name)),
+        };
+
+        let value = init(&mut cx)?;
+
+        define_value(&mut cx, target, &name, value, enumerable, read_only)?;
+
+        Ok(value)
+    })?;
+
+    let descriptor = cx.empty_object();
+    let enumerable = cx.boolean(enumerable);
+    let configurable = cx.boolean(true);
+
+    descriptor.set(cx, "get", get)?;
+    descriptor.set(cx, "enumerable", enumerable)?;
+    descriptor.set(cx, "configurable", configurable)?;
+
+    let global = cx.global();
+    let define_property = crate::intrinsics::object_define_property(cx)?;
+
+    define_property.exec(
+        cx,
+        global,
+        [target.upcast(), key.upcast(), descriptor.upcast()],
+    )
+}
@@ -0,0 +1,336 @@
+//! Tools for diagnosing the performance of a Neon module.
+//!
+//! A [`LoopMonitor`] measures how long closures scheduled on the JavaScript thread
+//! wait before they run. High latency indicates that the event loop is saturated,
+//! e.g., by long-running synchronous work or a flood of [`Channel`] sends.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use neon::diagnostics::LoopMonitor;
+//!
+//! fn start_monitor(mut cx: FunctionContext) -> JsResult<JsBox<LoopMonitor>> {
+//!     let monitor = LoopMonitor::new(&mut cx);
+//!
+//!     Ok(cx.boxed(monitor))
+//! }
+//!
+//! fn p99_ms(mut cx: FunctionContext) -> JsResult<JsNumber> {
+//!     let monitor = cx.argument::<JsBox<LoopMonitor>>(0)?;
+//!     let stats = monitor.snapshot();
+//!
+//!     Ok(cx.number(stats.p99.as_secs_f64() * 1000.0))
+//! }
+//! ```
+
+use std::{
+    convert::TryFrom,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{context::Context, event::Channel, types::Finalize};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Periodically measures the latency of the JavaScript event loop.
+///
+/// A background thread sends a timestamped no-op closure through a dedicated
+/// [`Channel`] once per interval and records the time until the closure runs on the
+/// JavaScript thread. Only one probe is in flight at a time, so a blocked event loop
+/// is measured by a single long sample rather than a burst of queued ones.
+///
+/// The channel is unreferenced and does not keep the process alive. The background
+/// thread is stopped when the monitor is dropped. Statistics may be read from any
+/// thread with [`LoopMonitor::snapshot`].
+pub struct LoopMonitor {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+struct Shared {
+    histogram: Histogram,
+    in_flight: AtomicBool,
+    stopped: Mutex<bool>,
+    wake: Condvar,
+}
+
+impl LoopMonitor {
+    /// Starts monitoring the event loop, sampling every 100 milliseconds.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C) -> Self {
+        Self::with_interval(cx, DEFAULT_INTERVAL)
+    }
+
+    /// Starts monitoring the event loop, sampling once per `interval`.
+    pub fn with_interval<'a, C: Context<'a>>(cx: &mut C, interval: Duration) -> Self {
+        let mut channel = cx.channel();
+
+        channel.unref(cx);
+
+        let shared = Arc::new(Shared {
+            histogram: Histogram::new(),
+            in_flight: AtomicBool::new(false),
+            stopped: Mutex::new(false),
+            wake: Condvar::new(),
+        });
+
+        let thread = {
+            let shared = Arc::clone(&shared);
+
+            thread::Builder::new()
+                .name("neon-loop-monitor".to_string())
+                .spawn(move || Shared::run(shared, channel, interval))
+                .expect("failed to spawn loop monitor thread")
+        };
+
+        Self {
+            shared,
+            thread: Some(thread),
+        }
+    }
+
+    /// Returns the latency statistics recorded so far.
+    pub fn snapshot(&self) -> LatencyStats {
+        self.shared.histogram.snapshot()
+    }
+
+    /// Discards the latency statistics recorded so far.
+    pub fn reset(&self) {
+        self.shared.histogram.reset();
+    }
+}
+
+impl Shared {
+    fn run(shared: Arc<Self>, channel: Channel, interval: Duration) {
+        let mut stopped = shared.stopped.lock().unwrap();
+
+        loop {
+            stopped = shared.wake.wait_timeout(stopped, interval).unwrap().0;
+
+            if *stopped {
+                return;
+            }
+
+            // Skip the sample if the previous probe has not run yet
+            if shared.in_flight.swap(true, Ordering::AcqRel) {
+                continue;
+            }
+
+            let probe = Arc::clone(&shared);
+            let sent = Instant::now();
+            let result = channel.try_send(move |_| {
+                probe.histogram.record(sent.elapsed());
+                probe.in_flight.store(false, Ordering::Release);
+
+                Ok(())
+            });
+
+            // The JavaScript environment is shutting down
+            if result.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+impl Drop for LoopMonitor {
+    fn drop(&mut self) {
+        *self.shared.stopped.lock().unwrap() = true;
+        self.shared.wake.notify_one();
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Finalize for LoopMonitor {}
+
+impl fmt::Debug for LoopMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoopMonitor")
+            .field("stats", &self.snapshot())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+/// Event loop latency statistics, returned by [`LoopMonitor::snapshot`].
+///
+/// Percentiles are approximate, with a relative error of at most 1/16. The maximum
+/// is exact. All values are zero if no samples have been recorded.
+pub struct LatencyStats {
+    /// Median latency.
+    pub p50: Duration,
+    /// 95th percentile latency.
+    pub p95: Duration,
+    /// 99th percentile latency.
+    pub p99: Duration,
+    /// Largest latency recorded.
+    pub max: Duration,
+    /// Number of samples recorded.
+    pub count: u64,
+}
+
+// Number of sub-buckets per power of two, as a power of two
+const PRECISION: u32 = 4;
+const SUB_BUCKETS: u64 = 1 << PRECISION;
+
+// Values below `SUB_BUCKETS` each have their own bucket, followed by `SUB_BUCKETS`
+// buckets for each remaining power of two
+const BUCKETS: usize = (SUB_BUCKETS * (u64::BITS - PRECISION + 1) as u64) as usize;
+
+// A lock-free log-linear histogram of latencies in microseconds
+struct Histogram {
+    buckets: Box<[AtomicU64]>,
+    max: AtomicU64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            buckets: (0..BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+            max: AtomicU64::new(0),
+        }
+    }
+
+    fn bucket(value: u64) -> usize {
+        if value < SUB_BUCKETS {
+            return value as usize;
+        }
+
+        let exp = u64::BITS - 1 - value.leading_zeros();
+        let sub = (value >> (exp - PRECISION)) & (SUB_BUCKETS - 1);
+
+        (SUB_BUCKETS * (exp - PRECISION + 1) as u64 + sub) as usize
+    }
+
+    // Largest value that falls in `bucket`
+    fn upper_bound(bucket: usize) -> u64 {
+        let bucket = bucket as u64;
+
+        if bucket < SUB_BUCKETS {
+            return bucket;
+        }
+
+        let exp = (bucket / SUB_BUCKETS) as u32 + PRECISION - 1;
+        let sub = bucket % SUB_BUCKETS;
+        let lower = (SUB_BUCKETS + sub) << (exp - PRECISION);
+
+        lower + ((1 << (exp - PRECISION)) - 1)
+    }
+
+    fn record(&self, latency: Duration) {
+        let value = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+
+        self.buckets[Self::bucket(value)].fetch_add(1, Ordering::Relaxed);
+        self.max.fetch_max(value, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        for bucket in self.buckets.iter() {
+            bucket.store(0, Ordering::Relaxed);
+        }
+
+        self.max.store(0, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> LatencyStats {
+        // Buckets are read individually, so the snapshot may be slightly inconsistent
+        // with concurrent recording, but the percentiles are always well defined
+        let counts = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect::<Vec<_>>();
+
+        let count = counts.iter().sum::<u64>();
+        let max = self.max.load(Ordering::Relaxed);
+
+        let percentile = |q: f64| {
+            if count == 0 {
+                return Duration::ZERO;
+            }
+
+            let rank = ((q * count as f64).ceil() as u64).max(1);
+            let mut seen = 0;
+
+            for (bucket, n) in counts.iter().enumerate() {
+                seen += n;
+
+                if seen >= rank {
+                    return Duration::from_micros(Self::upper_bound(bucket).min(max));
+                }
+            }
+
+            Duration::from_micros(max)
+        };
+
+        LatencyStats {
+            p50: percentile(0.50),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+            max: Duration::from_micros(max),
+            count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Histogram, BUCKETS};
+
+    #[test]
+    fn test_buckets_contain_values() {
+        let values = (0..4096)
+            .chain((12..64).flat_map(|exp| [1u64 << exp, (1 << exp) + 1, (1 << exp) - 1]))
+            .chain([u64::MAX]);
+
+        for value in values {
+            let bucket = Histogram::bucket(value);
+
+            assert!(bucket < BUCKETS, "{} is out of range", value);
+            assert!(Histogram::upper_bound(bucket) >= value);
+
+            if bucket > 0 {
+                assert!(Histogram::upper_bound(bucket - 1) < value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_snapshot_percentiles() {
+        let histogram = Histogram::new();
+
+        assert_eq!(histogram.snapshot().count, 0);
+        assert_eq!(histogram.snapshot().max, Duration::ZERO);
+
+        for ms in 1..=100 {
+            histogram.record(Duration::from_millis(ms));
+        }
+
+        let stats = histogram.snapshot();
+        let near = |actual: Duration, expected: u64| {
+            let expected = Duration::from_millis(expected);
+
+            actual >= expected && actual <= expected + expected / 16
+        };
+
+        assert_eq!(stats.count, 100);
+        assert_eq!(stats.max, Duration::from_millis(100));
+        assert!(near(stats.p50, 50), "{:?}", stats);
+        assert!(near(stats.p95, 95), "{:?}", stats);
+        assert!(near(stats.p99, 99), "{:?}", stats);
+
+        histogram.reset();
+
+        assert_eq!(histogram.snapshot().count, 0);
+    }
+}
@@ -0,0 +1,410 @@
+//! Translation of Rust errors into JavaScript errors.
+//!
+//! Rust errors are usually thrown as an `Error` with the message of the Rust error. A
+//! translation [registered](register) for a Rust error type replaces that `Error`
+//! with any JavaScript object, e.g., an instance of a custom class with a `code`
+//! property. Translations are looked up by the type of the error, so the same class
+//! is thrown no matter which function surfaces the error:
+//!
+//! * [`ResultExt::or_throw`] on a `Result<T, neon::error::Error>`
+//! * a task or promise resolved with a `Result<T, neon::error::Error>`, converted with
+//!   [`TryIntoJs`], e.g., [`TaskBuilder::into_promise`](crate::event::TaskBuilder::into_promise)
+//! * a function exported with
+//!   [`ModuleContext::export_result_function`](crate::context::ModuleContext::export_result_function)
+//! * [`throw`], for errors that are not wrapped in an [`Error`]
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use std::fmt;
+//!
+//! use neon::error::{self, Error};
+//!
+//! #[derive(Debug)]
+//! struct DatabaseError(String);
+//!
+//! impl fmt::Display for DatabaseError {
+//!     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+//!         write!(f, "database error: {}", self.0)
+//!     }
+//! }
+//!
+//! impl std::error::Error for DatabaseError {}
+//!
+//! fn query(_sql: &str) -> Result<f64, Error> {
+//!     Err(DatabaseError("no such table".to_string()).into())
+//! }
+//!
+//! fn count(mut cx: FunctionContext) -> JsResult<JsNumber> {
+//!     let n = query("SELECT COUNT(*) FROM users").or_throw(&mut cx)?;
+//!
+//!     Ok(cx.number(n))
+//! }
+//!
+//! #[neon::main]
+//! fn main(mut cx: ModuleContext) -> NeonResult<()> {
+//!     error::register::<DatabaseError, _>(|cx, err| {
+//!         let js_err = cx.error(err.to_string())?;
+//!         let code = cx.string("EDB");
+//!
+//!         js_err.set(cx, "code", code)?;
+//!
+//!         Ok(js_err.upcast())
+//!     });
+//!
+//!     cx.export_function("count", count)?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Translations are shared by every JavaScript environment in the process, including
+//! worker threads. A translation that needs values from a particular environment,
+//! e.g., a class defined by the addon, should look them up in the environment of its
+//! context, e.g., with a [`LocalKey`](crate::thread::LocalKey), rather than capture a
+//! [`Root`](crate::handle::Root).
+//!
+//! # Custom error classes
+//!
+//! With the `napi-6` feature, a class defined in JavaScript, e.g., by the application
+//! loading the addon, may be [registered](register_class) by name in each environment.
+//! An [`ErrorBuilder`] or [`Context::throw_custom`] then creates errors with the
+//! registered constructor, so that `instanceof` checks in JavaScript succeed.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use neon::error::{self, ErrorBuilder};
+//!
+//! // Called by the application with `class AppError extends Error {}`
+//! fn init(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+//!     let class = cx.argument::<JsFunction>(0)?;
+//!
+//!     error::register_class(&mut cx, "AppError", class);
+//!
+//!     Ok(cx.undefined())
+//! }
+//!
+//! fn open(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+//!     let path = cx.argument::<JsString>(0)?.value(&mut cx);
+//!
+//!     ErrorBuilder::class("AppError")
+//!         .message(format!("cannot open {}", path))
+//!         .code("ENOENT")
+//!         .throw(&mut cx)
+//! }
+//! ```
+
+use std::{
+    any::{Any, TypeId},
+    error::Error as StdError,
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+use crate::{
+    context::{Context, TaskContext},
+    result::{JsResult, NeonResult, ResultExt},
+    types::{extract::TryIntoJs, ErrorSpec, JsObject},
+};
+
+#[cfg(feature = "napi-6")]
+use {
+    crate::{
+        handle::{Handle, Root},
+        object::Object,
+        thread::LocalKey,
+        types::{JsError, JsFunction},
+    },
+    std::sync::{Mutex, MutexGuard},
+};
+
+type Translate = dyn for<'a> Fn(&mut TaskContext<'a>, &dyn Any) -> JsResult<'a, JsObject>
+    + Send
+    + Sync
+    + 'static;
+
+static TRANSLATIONS: RwLock<Vec<(TypeId, Arc<Translate>)>> = RwLock::new(Vec::new());
+
+/// Registers the translation of errors of type `E` into JavaScript values, replacing
+/// any translation registered for `E` before.
+///
+/// Translations may be registered at any time; errors thrown before registration are
+/// thrown as an `Error`. `translate` may throw, in which case the exception is thrown
+/// instead of the translated error.
+pub fn register<E, F>(translate: F)
+where
+    E: Any,
+    F: for<'a> Fn(&mut TaskContext<'a>, &E) -> JsResult<'a, JsObject> + Send + Sync + 'static,
+{
+    let translate: Arc<Translate> = Arc::new(move |cx, err| match err.downcast_ref::<E>() {
+        Some(err) => translate(cx, err),
+        None => unreachable!("translations are only called with errors of their type"),
+    });
+
+    let mut translations = TRANSLATIONS.write().unwrap_or_else(|err| err.into_inner());
+
+    match translations
+        .iter_mut()
+        .find(|(id, _)| *id == TypeId::of::<E>())
+    {
+        Some((_, other)) => *other = translate,
+        None => translations.push((TypeId::of::<E>(), translate)),
+    }
+}
+
+/// Throws `err` as the JavaScript value created by the translation registered for its
+/// type, or as an `Error` with its message if there is none.
+pub fn throw<'a, C, E, T>(cx: &mut C, err: &E) -> NeonResult<T>
+where
+    C: Context<'a>,
+    E: Any + fmt::Display,
+{
+    throw_translated(cx, err, err)
+}
+
+fn throw_translated<'a, C, T>(
+    cx: &mut C,
+    err: &dyn Any,
+    message: &dyn fmt::Display,
+) -> NeonResult<T>
+where
+    C: Context<'a>,
+{
+    let translate = TRANSLATIONS
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .find(|(id, _)| *id == err.type_id())
+        .map(|(_, translate)| Arc::clone(translate));
+
+    // The lock is released before calling the translation, which may register another
+    let translate = match translate {
+        Some(translate) => translate,
+        None => return cx.throw_error(message.to_string()),
+    };
+
+    TaskContext::with_context(cx.env(), |mut cx| {
+        let err = translate(&mut cx, err)?;
+
+        cx.throw(err)
+    })
+}
+
+// A Rust error that can be recovered as `Any` to find its translation
+trait Source: StdError + Send + Sync + 'static {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<E: StdError + Send + Sync + 'static> Source for E {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Any Rust error, thrown with the translation [registered](register) for its type.
+///
+/// Any type implementing [`std::error::Error`] converts into an `Error`, e.g., with the
+/// `?` operator. Like `Box<dyn std::error::Error>`, `Error` does not implement
+/// `std::error::Error` itself.
+pub struct Error(Box<dyn Source>);
+
+impl Error {
+    /// Returns `true` if the wrapped error is of type `E`.
+    pub fn is<E: StdError + 'static>(&self) -> bool {
+        self.0.as_any().is::<E>()
+    }
+
+    /// Returns a reference to the wrapped error if it is of type `E`.
+    pub fn downcast_ref<E: StdError + 'static>(&self) -> Option<&E> {
+        self.0.as_any().downcast_ref()
+    }
+
+    /// Throws the error as the JavaScript value created by the translation registered
+    /// for the type of the wrapped error, or as an `Error` with its message if there is
+    /// none.
+    pub fn throw<'a, C: Context<'a>, T>(&self, cx: &mut C) -> NeonResult<T> {
+        throw_translated(cx, self.0.as_any(), &self.0)
+    }
+}
+
+impl<E: StdError + Send + Sync + 'static> From<E> for Error {
+    fn from(err: E) -> Self {
+        Self(Box::new(err))
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<T> ResultExt<T> for Result<T, Error> {
+    fn or_throw<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<T> {
+        self.or_else(|err| err.throw(cx))
+    }
+}
+
+/// Converts the `Ok` value, or throws the `Err` with its translation.
+impl<'cx, T: TryIntoJs<'cx>> TryIntoJs<'cx> for Result<T, Error> {
+    type Value = T::Value;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, Self::Value> {
+        self.or_throw(cx)?.try_into_js(cx)
+    }
+}
+
+/// An error returned by a function exported with
+/// [`ModuleContext::export_result_function`](crate::context::ModuleContext::export_result_function).
+///
+/// Implemented for any type that converts into an [`ErrorSpec`], and for [`Error`].
+pub trait ThrowError {
+    /// Throws the error.
+    fn throw<'a, C: Context<'a>, T>(self, cx: &mut C) -> NeonResult<T>;
+}
+
+impl<E: Into<ErrorSpec>> ThrowError for E {
+    fn throw<'a, C: Context<'a>, T>(self, cx: &mut C) -> NeonResult<T> {
+        ErrorSpec::throw(&self.into(), cx)
+    }
+}
+
+impl ThrowError for Error {
+    fn throw<'a, C: Context<'a>, T>(self, cx: &mut C) -> NeonResult<T> {
+        Error::throw(&self, cx)
+    }
+}
+
+#[cfg(feature = "napi-6")]
+// Constructors registered with `register_class` in an environment. Only accessed from
+// the JavaScript thread of the environment, but `LocalKey` requires `Sync`.
+type Classes = Mutex<Vec<(String, Root<JsFunction>)>>;
+
+#[cfg(feature = "napi-6")]
+fn classes<'a, C: Context<'a>>(cx: &mut C) -> MutexGuard<'a, Vec<(String, Root<JsFunction>)>> {
+    static CLASSES: LocalKey<Classes> = LocalKey::new();
+
+    CLASSES
+        .get_or_init_default(cx)
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+/// Registers `constructor` as the error class named `name` in the environment of `cx`,
+/// replacing any class registered with the same name before.
+///
+/// Classes are registered separately in each environment: a worker thread must
+/// register its own classes, since a constructor cannot be used in another
+/// environment.
+pub fn register_class<'a, C: Context<'a>>(cx: &mut C, name: &str, constructor: Handle<JsFunction>) {
+    let root = constructor.root(cx);
+    let other = {
+        let mut classes = classes(cx);
+
+        match classes.iter_mut().find(|(other, _)| other == name) {
+            Some((_, other)) => Some(std::mem::replace(other, root)),
+            None => {
+                classes.push((name.to_string(), root));
+                None
+            }
+        }
+    };
+
+    if let Some(other) = other {
+        other.drop(cx);
+    }
+}
+
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+/// A builder for an error created with a constructor [registered](register_class) by
+/// name.
+///
+/// The constructor is called with the message as its only argument, like
+/// `new AppError(message)`. If no class is registered with the name in the environment,
+/// an `Error` is created instead, with a `warning` property naming the missing class.
+///
+/// See the [module documentation](self#custom-error-classes) for an example.
+#[derive(Clone, Debug)]
+pub struct ErrorBuilder {
+    class: String,
+    message: String,
+    code: Option<String>,
+}
+
+#[cfg(feature = "napi-6")]
+impl ErrorBuilder {
+    /// Creates a builder for an error of the class registered as `name`.
+    pub fn class<S: Into<String>>(name: S) -> Self {
+        Self {
+            class: name.into(),
+            message: String::new(),
+            code: None,
+        }
+    }
+
+    /// Sets the message passed to the constructor. _Default: empty_
+    pub fn message<S: Into<String>>(mut self, message: S) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets the `code` property of the error.
+    pub fn code<S: Into<String>>(mut self, code: S) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Creates the error, throwing if the constructor throws.
+    pub fn build<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsObject> {
+        // The lock is released before calling the constructor, which may register
+        // another class
+        let constructor = classes(cx)
+            .iter()
+            .find(|(name, _)| *name == self.class)
+            .map(|(_, constructor)| constructor.to_inner(cx));
+
+        let err = match constructor {
+            Some(constructor) => {
+                let message = cx.string(&self.message);
+
+                constructor
+                    .construct_with(cx)
+                    .arg(message)
+                    .apply::<JsObject, _>(cx)?
+            }
+            None => {
+                let err = JsError::error(cx, &self.message)?;
+                let warning = cx.string(format!(
+                    "error class `{}` is not registered in this environment",
+                    self.class
+                ));
+
+                err.set(cx, "warning", warning)?;
+                err.upcast()
+            }
+        };
+
+        if let Some(code) = &self.code {
+            let code = cx.string(code);
+
+            err.set(cx, "code", code)?;
+        }
+
+        Ok(err)
+    }
+
+    /// Creates and throws the error.
+    pub fn throw<'a, C: Context<'a>, T>(&self, cx: &mut C) -> NeonResult<T> {
+        let err = self.build(cx)?;
+
+        cx.throw(err)
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    context::Context,
+    handle::{Handle, Managed, Root},
+    object::Object,
+    result::{JsResult, NeonResult},
+    sys::{self, async_context, raw},
+    types::{JsFunction, JsObject, JsValue},
+    types_impl::{build, prepare_call},
+};
+
+#[cfg(feature = "napi-6")]
+use {
+    crate::lifecycle::{DropData, DropQueue, InstanceData},
+    std::sync::Arc,
+};
+
+/// An asynchronous resource for calling JavaScript from Rust with the correct
+/// [async context](https://nodejs.org/api/async_context.html).
+///
+/// Node tracks which asynchronous operation caused each callback, e.g., for
+/// [`AsyncLocalStorage`](https://nodejs.org/api/async_context.html#class-asynclocalstorage),
+/// async stack traces and [`async_hooks`](https://nodejs.org/api/async_hooks.html).
+/// Calling a function with [`JsFunction::call`] from a [`Channel`](crate::event::Channel)
+/// closure or a [`TaskBuilder`](crate::event::TaskBuilder) completion does not tell Node
+/// which operation the call belongs to. An `AsyncResource` represents that operation:
+/// it is created, emitting an `init` hook, in the context that started the operation,
+/// and each call made through [`AsyncResource::make_callback`] runs in that context,
+/// emitting `before` and `after` hooks.
+///
+/// This is the equivalent of the JavaScript
+/// [`AsyncResource`](https://nodejs.org/api/async_context.html#class-asyncresource) class.
+///
+/// An `AsyncResource` may be sent across threads, but may only be used on the
+/// JavaScript thread that created it. It should be disposed with
+/// [`AsyncResource::destroy`], which emits a `destroy` hook. If it is dropped instead:
+/// * N-API < 6, Neon will `panic` to notify of the leak
+/// * N-API >= 6, Neon will destroy it from a global queue at a runtime cost
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::event::AsyncResource;
+///
+/// fn read_file(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let path = cx.argument::<JsString>(0)?.value(&mut cx);
+///     let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+///     let resource = AsyncResource::new(&mut cx, "ReadFile")?;
+///     let channel = cx.channel();
+///
+///     std::thread::spawn(move || {
+///         let contents = std::fs::read_to_string(path).unwrap_or_default();
+///
+///         channel.send(move |mut cx| {
+///             let callback = callback.into_inner(&mut cx);
+///             let this = cx.global();
+///             let contents = cx.string(contents);
+///
+///             resource.make_callback(&mut cx, this, callback, [contents.upcast()])?;
+///             resource.destroy(&mut cx);
+///
+///             Ok(())
+///         });
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// ```
+pub struct AsyncResource {
+    // `Option` is used to skip `Drop` when `AsyncResource::destroy` is used.
+    // It will *always* be `Some` when a user is interacting with `AsyncResource`.
+    context: Option<NapiAsyncContext>,
+    resource: Option<Root<JsObject>>,
+    #[cfg(feature = "napi-6")]
+    drop_queue: Arc<DropQueue>,
+}
+
+impl std::fmt::Debug for AsyncResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("AsyncResource")
+    }
+}
+
+impl AsyncResource {
+    /// Creates an async resource of type `name`, e.g., `"ReadFile"`, in the current
+    /// async context.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, name: &str) -> NeonResult<Self> {
+        let resource = cx.empty_object();
+
+        Self::with_resource(cx, name, resource)
+    }
+
+    /// Creates an async resource of type `name` associated with `resource`, in the
+    /// current async context.
+    ///
+    /// `resource` is passed to the `init` async hook and is returned by
+    /// [`executionAsyncResource()`](https://nodejs.org/api/async_hooks.html#async_hooksexecutionasyncresource)
+    /// while a callback is running.
+    pub fn with_resource<'a, C: Context<'a>>(
+        cx: &mut C,
+        name: &str,
+        resource: Handle<JsObject>,
+    ) -> NeonResult<Self> {
+        let name = cx.string(name);
+        let env = cx.env().to_raw();
+        let context = unsafe { async_context::init(env, resource.to_raw(), name.to_raw()) };
+
+        Ok(Self {
+            context: Some(NapiAsyncContext(context)),
+            resource: Some(resource.root(cx)),
+            #[cfg(feature = "napi-6")]
+            drop_queue: InstanceData::drop_queue(cx),
+        })
+    }
+
+    /// Calls `function` in the async context of the resource, with `this` and `args`.
+    ///
+    /// Unlike [`JsFunction::call`], `this` must be an object; functions that do not
+    /// use `this` may be called with the global object from [`Context::global`].
+    ///
+    /// If the JavaScript stack is empty, e.g., in a [`Channel`](crate::event::Channel)
+    /// closure, the microtask queue and `process.nextTick` callbacks are run after the
+    /// call, as they would be after any other callback from the event loop.
+    pub fn make_callback<'a, 'b, C: Context<'a>, T, AS>(
+        &self,
+        cx: &mut C,
+        this: Handle<'b, T>,
+        function: Handle<'b, JsFunction>,
+        args: AS,
+    ) -> JsResult<'a, JsValue>
+    where
+        T: Object,
+        AS: AsRef<[Handle<'b, JsValue>]>,
+    {
+        let (argc, argv) = unsafe { prepare_call(cx, args.as_ref()) }?;
+        let env = cx.env().to_raw();
+        let context = self.as_raw();
+
+        build(cx.env(), |out| unsafe {
+            async_context::make_callback(
+                out,
+                env,
+                context,
+                this.to_raw(),
+                function.to_raw(),
+                argc as usize,
+                argv,
+            )
+        })
+    }
+
+    #[cfg(feature = "napi-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-3")))]
+    /// Runs `f` in the async context of the resource, so that every call into
+    /// JavaScript it makes, e.g. with [`JsFunction::call`] or by settling a promise,
+    /// is attributed to the resource.
+    ///
+    /// As with [`AsyncResource::make_callback`], the microtask queue is run afterwards
+    /// if the JavaScript stack is empty.
+    pub fn in_scope<'a, C: Context<'a>, T>(&self, cx: &mut C, f: impl FnOnce(&mut C) -> T) -> T {
+        struct Scope {
+            env: raw::Env,
+            scope: sys::CallbackScope,
+        }
+
+        impl Drop for Scope {
+            fn drop(&mut self) {
+                unsafe { async_context::close_scope(self.env, self.scope) };
+            }
+        }
+
+        let env = cx.env().to_raw();
+        let resource = self.resource.as_ref().unwrap().to_inner(cx);
+        let scope = unsafe { async_context::open_scope(env, resource.to_raw(), self.as_raw()) };
+        let _scope = Scope { env, scope };
+
+        f(cx)
+    }
+
+    /// Destroys the resource, emitting a `destroy` async hook.
+    pub fn destroy<'a, C: Context<'a>>(mut self, cx: &mut C) {
+        let env = cx.env().to_raw();
+
+        if let Some(resource) = self.resource.take() {
+            resource.drop(cx);
+        }
+
+        if let Some(context) = self.context.take() {
+            unsafe { context.destroy(env) };
+        }
+    }
+
+    fn as_raw(&self) -> sys::AsyncContext {
+        // `unwrap` will not fail because the `context` is only `None` after `destroy`
+        self.context.as_ref().unwrap().0
+    }
+}
+
+impl crate::types::boxed::Finalize for AsyncResource {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        self.destroy(cx);
+    }
+}
+
+impl Drop for AsyncResource {
+    #[cfg(not(feature = "napi-6"))]
+    fn drop(&mut self) {
+        // If `None`, the resource has already been destroyed
+        if self.context.is_none() {
+            return;
+        }
+
+        // Destructors are called during stack unwinding, prevent a double
+        // panic and instead prefer to leak.
+        if std::thread::panicking() {
+            eprintln!("Warning: neon::event::AsyncResource leaked during a panic");
+            return;
+        }
+
+        // Only panic if the event loop is still running
+        if let Ok(true) = crate::context::internal::IS_RUNNING.try_with(|v| *v.borrow()) {
+            panic!("Must call `destroy` on `neon::event::AsyncResource`");
+        }
+    }
+
+    #[cfg(feature = "napi-6")]
+    fn drop(&mut self) {
+        // If `None`, the resource has already been destroyed
+        if let Some(context) = self.context.take() {
+            self.drop_queue.push(DropData::AsyncContext(context));
+        }
+    }
+}
+
+#[repr(transparent)]
+pub(crate) struct NapiAsyncContext(sys::AsyncContext);
+
+// # Safety
+// An async context may be sent to other threads, but it is only used on the
+// JavaScript thread that created it, which is ensured by requiring a `Context`.
+unsafe impl Send for NapiAsyncContext {}
+
+unsafe impl Sync for NapiAsyncContext {}
+
+impl NapiAsyncContext {
+    /// # Safety
+    /// Must only be used from the same module context that created the async context
+    pub(crate) unsafe fn destroy(self, env: raw::Env) {
+        async_context::destroy(env, self.0);
+    }
+}
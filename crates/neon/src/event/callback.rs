@@ -0,0 +1,321 @@
+use std::{error, fmt, marker::PhantomData, sync::Arc};
+
+use crate::{
+    context::{Context, TaskContext},
+    event::{Channel, JoinError, JoinHandle, SendError},
+    handle::{Handle, Root},
+    object::Object,
+    result::{JsResult, NeonResult, ResultExt},
+    types::{extract::TryFromJs, function::TryIntoArguments, JsFunction, JsValue},
+};
+
+/// A JavaScript function that can be called from any thread.
+///
+/// A `Callback` holds a reference to a function and a [`Channel`] to the JavaScript
+/// thread that owns it. Each call schedules the function to be called on that thread
+/// with `this` set to `undefined`. `Args` is the type of the arguments passed by
+/// [`Callback::call`], e.g., a tuple of Rust values that implement
+/// [`TryIntoJs`](crate::types::extract::TryIntoJs); arguments may also be created on
+/// the JavaScript thread with [`Callback::call_with`].
+///
+/// Cloning a `Callback` creates a new handle to the same function. The function is
+/// released when the last clone is dropped, on any thread.
+///
+/// Calls made after the JavaScript environment has shut down fail with a
+/// [`SendError`] instead of calling the function.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::event::Callback;
+///
+/// fn download(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let on_progress = cx.argument::<JsFunction>(0)?;
+///     let on_progress = Callback::<(f64,)>::new(&mut cx, on_progress);
+///
+///     std::thread::spawn(move || {
+///         for percent in 0..=100 {
+///             // Wait for JavaScript to acknowledge each update
+///             let ack: bool = on_progress.call_blocking((percent as f64,)).unwrap();
+///
+///             if !ack {
+///                 break;
+///             }
+///         }
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// ```
+pub struct Callback<Args = ()> {
+    state: Arc<CallbackState>,
+    _args: PhantomData<fn(Args)>,
+}
+
+impl<Args> Clone for Callback<Args> {
+    fn clone(&self) -> Self {
+        Self {
+            state: Arc::clone(&self.state),
+            _args: PhantomData,
+        }
+    }
+}
+
+impl<Args> fmt::Debug for Callback<Args> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Callback")
+    }
+}
+
+impl<Args> Callback<Args> {
+    /// Creates a `Callback` for `callback`, using a new [`Channel`].
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, callback: Handle<JsFunction>) -> Self {
+        let channel = cx.channel();
+
+        Self::with_channel(cx, callback, channel)
+    }
+
+    /// Creates a `Callback` for `callback`, scheduling calls on `channel`.
+    ///
+    /// Use an unreferenced channel to allow the Node event loop to exit while the
+    /// `Callback` exists.
+    pub fn with_channel<'a, C: Context<'a>>(
+        cx: &mut C,
+        callback: Handle<JsFunction>,
+        channel: Channel,
+    ) -> Self {
+        callback.root(cx).into_callback(channel)
+    }
+
+    /// Schedules a call with the arguments created by `f` on the JavaScript thread.
+    /// The return value of the function is extracted as an `R` and returned from
+    /// [`JoinHandle::join`].
+    ///
+    /// If `f` or the function throws, or the return value cannot be extracted, the
+    /// exception is reported as an unhandled rejection and joining returns a
+    /// [`JoinError`].
+    pub fn call_with<R, F>(&self, f: F) -> Result<JoinHandle<R>, SendError>
+    where
+        R: for<'cx> TryFromJs<'cx> + Send + 'static,
+        F: for<'a> FnOnce(&mut TaskContext<'a>) -> NeonResult<Vec<Handle<'a, JsValue>>>
+            + Send
+            + 'static,
+    {
+        self.send(move |cx, callback| {
+            let args = f(cx)?;
+            let this = cx.undefined();
+
+            callback.call(cx, this, args)
+        })
+    }
+
+    fn send<R, F>(&self, f: F) -> Result<JoinHandle<R>, SendError>
+    where
+        R: for<'cx> TryFromJs<'cx> + Send + 'static,
+        F: for<'a> FnOnce(&mut TaskContext<'a>, Handle<'a, JsFunction>) -> JsResult<'a, JsValue>
+            + Send
+            + 'static,
+    {
+        let state = Arc::clone(&self.state);
+
+        self.state.channel.try_send(move |mut cx| {
+            // `unwrap` will not fail because `callback` is only `None` during `Drop`
+            let callback = state.callback.as_ref().unwrap().to_inner(&mut cx);
+            let v = f(&mut cx, callback)?;
+
+            R::try_from_js(&mut cx, v)
+        })
+    }
+}
+
+impl<Args> Callback<Args>
+where
+    Args: for<'cx> TryIntoArguments<'cx> + Send + 'static,
+{
+    /// Schedules a call with `args`, converted to JavaScript values on the JavaScript
+    /// thread. The return value of the function is extracted as an `R` and returned
+    /// from [`JoinHandle::join`].
+    ///
+    /// If the function throws, or the return value cannot be extracted, the exception
+    /// is reported as an unhandled rejection and joining returns a [`JoinError`].
+    pub fn call<R>(&self, args: Args) -> Result<JoinHandle<R>, SendError>
+    where
+        R: for<'cx> TryFromJs<'cx> + Send + 'static,
+    {
+        self.send(move |cx, callback| {
+            let this = cx.undefined();
+
+            callback.call(cx, this, args)
+        })
+    }
+
+    /// Calls the function with `args` and blocks the current thread until it returns,
+    /// extracting the return value as an `R`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called within an asynchronous execution context. It
+    /// must not be called on the JavaScript thread, which would deadlock.
+    pub fn call_blocking<R>(&self, args: Args) -> Result<R, CallbackError>
+    where
+        R: for<'cx> TryFromJs<'cx> + Send + 'static,
+    {
+        Ok(self.call(args)?.join()?)
+    }
+
+    /// Schedules a call with `args`, discarding the return value.
+    ///
+    /// If the function throws, the exception is reported as an unhandled rejection.
+    pub fn call_and_forget(&self, args: Args) -> Result<(), SendError> {
+        self.send::<Ignored, _>(move |cx, callback| {
+            let this = cx.undefined();
+
+            callback.call(cx, this, args)
+        })?;
+
+        Ok(())
+    }
+}
+
+impl Root<JsFunction> {
+    /// Schedules a call of the function on the JavaScript thread of `channel`, with
+    /// `this` set to `undefined` and the arguments created by `f` on that thread.
+    ///
+    /// The `Root` is consumed, so the function is called exactly once; convert it with
+    /// [`Root::into_callback`] to call it many times. If `f` or the function throws, the
+    /// exception is handled like any other exception thrown by a closure sent on
+    /// `channel`, and joining returns a [`JoinError`].
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn compute(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    ///     let channel = cx.channel();
+    ///
+    ///     std::thread::spawn(move || {
+    ///         let result = 6.0 * 7.0;
+    ///
+    ///         callback.call_threaded(&channel, move |cx| Ok(vec![cx.number(result).upcast()]));
+    ///     });
+    ///
+    ///     Ok(cx.undefined())
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the call could not be scheduled, like [`Channel::send`].
+    #[cfg_attr(any(feature = "tracing", feature = "rust-stack-frames"), track_caller)]
+    pub fn call_threaded<F>(self, channel: &Channel, f: F) -> JoinHandle<()>
+    where
+        F: for<'a> FnOnce(&mut TaskContext<'a>) -> NeonResult<Vec<Handle<'a, JsValue>>>
+            + Send
+            + 'static,
+    {
+        channel.send(move |mut cx| {
+            let callback = self.into_inner(&mut cx);
+            let args = f(&mut cx)?;
+            let this = cx.undefined();
+
+            callback.exec(&mut cx, this, args)
+        })
+    }
+
+    /// Converts the `Root` into a [`Callback`] that calls the function on the
+    /// JavaScript thread of `channel`, and that may be cloned and called from any
+    /// thread.
+    pub fn into_callback<Args>(self, channel: Channel) -> Callback<Args> {
+        Callback {
+            state: Arc::new(CallbackState {
+                callback: Some(self),
+                channel,
+            }),
+            _args: PhantomData,
+        }
+    }
+}
+
+// Return value of a call made with `Callback::call_and_forget`
+struct Ignored;
+
+impl<'cx> TryFromJs<'cx> for Ignored {
+    fn try_from_js<C: Context<'cx>>(_cx: &mut C, _v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        Ok(Ignored)
+    }
+}
+
+struct CallbackState {
+    // `Option` so that the `Root` can be moved to the JavaScript thread on `Drop`.
+    // It will *always* be `Some` until then.
+    callback: Option<Root<JsFunction>>,
+    channel: Channel,
+}
+
+impl Drop for CallbackState {
+    fn drop(&mut self) {
+        if let Some(callback) = self.callback.take() {
+            // `Channel::try_send` will only fail if the environment has shutdown.
+            // In that case, the teardown will perform clean-up.
+            let _ = self.channel.try_send(move |mut cx| {
+                callback.drop(&mut cx);
+                Ok(())
+            });
+        }
+    }
+}
+
+/// Error returned by [`Callback::call_blocking`] indicating that the call could not be
+/// scheduled, or that the function threw an exception.
+pub struct CallbackError(CallbackErrorType);
+
+enum CallbackErrorType {
+    Send(SendError),
+    Join(JoinError),
+}
+
+impl CallbackError {
+    /// Indicates whether the call could not be scheduled because the JavaScript
+    /// environment has shut down.
+    pub fn is_closed(&self) -> bool {
+        matches!(self.0, CallbackErrorType::Send(_))
+    }
+}
+
+impl fmt::Display for CallbackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            CallbackErrorType::Send(_) => f.write_str("Callback could not be scheduled"),
+            CallbackErrorType::Join(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl fmt::Debug for CallbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            CallbackErrorType::Send(err) => f.debug_tuple("Send").field(err).finish(),
+            CallbackErrorType::Join(err) => f.debug_tuple("Join").field(err).finish(),
+        }
+    }
+}
+
+impl error::Error for CallbackError {}
+
+impl From<SendError> for CallbackError {
+    fn from(err: SendError) -> Self {
+        Self(CallbackErrorType::Send(err))
+    }
+}
+
+impl From<JoinError> for CallbackError {
+    fn from(err: JoinError) -> Self {
+        Self(CallbackErrorType::Join(err))
+    }
+}
+
+impl<T> ResultExt<T> for Result<T, CallbackError> {
+    fn or_throw<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<T> {
+        self.or_else(|err| cx.throw_error(err.to_string()))
+    }
+}
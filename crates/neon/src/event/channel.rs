@@ -1,15 +1,22 @@
 use std::{
     error, fmt, mem,
     sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Weak,
     },
 };
 
 use crate::{
-    context::{Context, TaskContext},
-    result::{NeonResult, ResultExt, Throw},
-    sys::{raw::Env, tsfn::ThreadsafeFunction},
+    context::{internal::ContextInternal, Context, TaskContext},
+    event::trace,
+    handle::Root,
+    object::Object,
+    result::{JsResult, NeonResult, ResultExt, Throw},
+    sys::{
+        self,
+        raw::Env,
+        tsfn::{Item, ThreadsafeFunction},
+    },
 };
 
 #[cfg(feature = "futures")]
@@ -37,20 +44,56 @@ mod oneshot {
         }
     }
 
-    pub(super) fn channel<T>() -> (mpsc::SyncSender<T>, Receiver<T>) {
+    pub(super) struct Sender<T>(mpsc::SyncSender<T>);
+
+    impl<T> Sender<T> {
+        // Returns the value if the receiver was dropped
+        pub(super) fn send(self, t: T) -> Result<(), T> {
+            self.0.send(t).map_err(|err| err.0)
+        }
+    }
+
+    pub(super) fn channel<T>() -> (Sender<T>, Receiver<T>) {
         let (tx, rx) = mpsc::sync_channel(1);
 
-        (tx, Receiver(rx))
+        (Sender(tx), Receiver(rx))
     }
 }
 
-type Callback = Box<dyn FnOnce(Env) + Send + 'static>;
+struct Callback {
+    f: Box<dyn FnOnce(Env) + Send + 'static>,
+}
 
 /// Channel for scheduling Rust closures to execute on the JavaScript main thread.
 ///
 /// Cloning a `Channel` will create a new channel that shares a backing queue for
 /// events.
 ///
+/// # Ordering
+///
+/// Closures scheduled on the same queue execute in the order they were scheduled.
+/// This includes closures sent with [`Channel::send`] and [`Channel::try_send`], as
+/// well as promises settled with
+/// [`Deferred::settle_with`](crate::types::Deferred::settle_with), on the `Channel`
+/// or any of its clones. Closures scheduled concurrently from different threads are
+/// ordered by when each call to `send` acquired the queue.
+///
+/// For example, a closure sent to report progress, followed by `settle_with` to
+/// resolve a promise, always executes before the promise is resolved and before any
+/// of its `then` handlers run.
+///
+/// # Cancellation
+///
+/// Dropping the [`JoinHandle`] returned by `send` detaches the closure: it still
+/// executes and its result is discarded. Use [`JoinHandle::abort`] to remove a
+/// closure from the queue before it executes.
+///
+/// There is no ordering between separate queues, e.g., two channels created with
+/// [`Channel::new`] or [`Context::new_channel`](crate::context::Context::new_channel).
+/// With the `napi-6` feature, [`Context::channel`](crate::context::Context::channel)
+/// returns a clone of a channel shared by the whole module instance; otherwise it
+/// creates a new queue on each call. Clone a single `Channel` when ordering matters.
+///
 /// # Example
 ///
 /// The following example spawns a standard Rust thread to complete a computation
@@ -147,42 +190,201 @@ impl Channel {
     }
 
     /// Schedules a closure to execute on the JavaScript thread that created this Channel
-    /// Panics if there is a libuv error
+    ///
+    /// If the environment is shutting down, the closure is dropped without executing
+    /// and joining returns a [`JoinError`] for which [`JoinError::is_closed`] is
+    /// `true`. This makes it safe to send from a [`Drop`] or
+    /// [`Finalize`](crate::types::Finalize) implementation that may run during
+    /// teardown. Use [`Channel::try_send`] to handle the failure when sending.
+    #[cfg_attr(any(feature = "tracing", feature = "rust-stack-frames"), track_caller)]
     pub fn send<T, F>(&self, f: F) -> JoinHandle<T>
     where
         T: Send + 'static,
         F: FnOnce(TaskContext) -> NeonResult<T> + Send + 'static,
     {
-        self.try_send(f).unwrap()
+        self.try_send(f).unwrap_or_else(|_| JoinHandle::closed())
     }
 
     /// Schedules a closure to execute on the JavaScript thread that created this Channel
     /// Returns an `Error` if the task could not be scheduled.
     ///
     /// See [`SendError`] for additional details on failure causes.
+    #[cfg_attr(any(feature = "tracing", feature = "rust-stack-frames"), track_caller)]
     pub fn try_send<T, F>(&self, f: F) -> Result<JoinHandle<T>, SendError>
     where
         T: Send + 'static,
         F: FnOnce(TaskContext) -> NeonResult<T> + Send + 'static,
     {
+        self.schedule(trace::Caller::new(), move |cx, tx| {
+            // Error can be ignored; it only means the user didn't join
+            let _ = tx.send(f(cx).map_err(Into::into));
+        })
+    }
+
+    /// Schedules a closure that computes a Rust value on the JavaScript thread that
+    /// created this Channel. The value is returned to the sending thread from
+    /// [`JoinHandle::join`].
+    ///
+    /// This is the same as [`Channel::send`]. The value must be `Send` and `'static`,
+    /// so it cannot borrow from the context; in particular, a [`Handle`] cannot be
+    /// returned. Use [`Channel::send_value`] to return a JavaScript object instead.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// # fn example(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    /// let channel = cx.channel();
+    ///
+    /// std::thread::spawn(move || {
+    ///     let title = channel
+    ///         .send_map(|mut cx| {
+    ///             let process: Handle<JsObject> = cx.global().get(&mut cx, "process")?;
+    ///             let title: Handle<JsString> = process.get(&mut cx, "title")?;
+    ///
+    ///             Ok(title.value(&mut cx))
+    ///         })
+    ///         .join();
+    /// });
+    /// # Ok(cx.undefined())
+    /// # }
+    /// ```
+    ///
+    /// Like [`Channel::send`], the closure is dropped if the environment is shutting
+    /// down.
+    ///
+    /// [`Handle`]: crate::handle::Handle
+    #[cfg_attr(any(feature = "tracing", feature = "rust-stack-frames"), track_caller)]
+    pub fn send_map<T, F>(&self, f: F) -> JoinHandle<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(TaskContext) -> NeonResult<T> + Send + 'static,
+    {
+        self.send(f)
+    }
+
+    /// Schedules a closure that creates a JavaScript object on the JavaScript thread
+    /// that created this Channel. The object is rooted, and the [`Root`] is returned
+    /// to the sending thread from [`JoinHandle::join`].
+    ///
+    /// If the `JoinHandle` is dropped without joining, the root is released
+    /// immediately on the JavaScript thread.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// # fn example(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    /// let channel = cx.channel();
+    ///
+    /// std::thread::spawn(move || {
+    ///     let config = channel
+    ///         .send_value(|mut cx| Ok(cx.empty_object()))
+    ///         .join()
+    ///         .unwrap();
+    ///
+    ///     // Later, back on the JavaScript thread
+    ///     channel.send(move |mut cx| {
+    ///         let config = config.into_inner(&mut cx);
+    ///         let enabled = cx.boolean(true);
+    ///
+    ///         config.set(&mut cx, "enabled", enabled)?;
+    ///
+    ///         Ok(())
+    ///     });
+    /// });
+    /// # Ok(cx.undefined())
+    /// # }
+    /// ```
+    ///
+    /// Like [`Channel::send`], the closure is dropped if the environment is shutting
+    /// down.
+    #[cfg_attr(any(feature = "tracing", feature = "rust-stack-frames"), track_caller)]
+    pub fn send_value<V, F>(&self, f: F) -> JoinHandle<Root<V>>
+    where
+        V: Object,
+        F: FnOnce(TaskContext) -> JsResult<V> + Send + 'static,
+    {
+        self.try_send_value(f)
+            .unwrap_or_else(|_| JoinHandle::closed())
+    }
+
+    /// Schedules a closure that creates a JavaScript object on the JavaScript thread
+    /// that created this Channel, returning a [`Root`] to the object.
+    /// Returns an `Error` if the task could not be scheduled.
+    ///
+    /// See [`Channel::send_value`] for usage and [`SendError`] for additional details
+    /// on failure causes.
+    #[cfg_attr(any(feature = "tracing", feature = "rust-stack-frames"), track_caller)]
+    pub fn try_send_value<V, F>(&self, f: F) -> Result<JoinHandle<Root<V>>, SendError>
+    where
+        V: Object,
+        F: FnOnce(TaskContext) -> JsResult<V> + Send + 'static,
+    {
+        self.schedule(trace::Caller::new(), move |cx, tx| {
+            let env = cx.env();
+            let result = f(cx)
+                .map(|v| TaskContext::with_context(env, |mut cx| v.root(&mut cx)))
+                .map_err(Into::into);
+
+            // The `JoinHandle` was dropped; release the root while a context is
+            // available instead of deferring to the drop queue
+            if let Err(Ok(root)) = tx.send(result) {
+                TaskContext::with_context(env, |mut cx| root.drop(&mut cx));
+            }
+        })
+    }
+
+    // Schedules `f` to be called on the JavaScript thread with a sender for the
+    // result of the `JoinHandle`
+    fn schedule<T, F>(&self, caller: trace::Caller, f: F) -> Result<JoinHandle<T>, SendError>
+    where
+        T: Send + 'static,
+        F: FnOnce(TaskContext, oneshot::Sender<Result<T, SendThrow>>) + Send + 'static,
+    {
+        // Waiting for the closure while holding a guard may deadlock
+        #[cfg(all(debug_assertions, feature = "napi-6"))]
+        crate::types::shared::assert_no_guards(self.state.tsfn.owner_env());
+
         let (tx, rx) = oneshot::channel();
-        let callback = Box::new(move |env| {
+        let f = Box::new(move |env| {
             let env = unsafe { mem::transmute(env) };
+            let _span = trace::channel_callback(caller);
 
             // Note: It is sufficient to use `TaskContext`'s `InheritedHandleScope` because
             // N-API creates a `HandleScope` before calling the callback.
-            TaskContext::with_context(env, move |cx| {
-                // Error can be ignored; it only means the user didn't join
-                let _ = tx.send(f(cx).map_err(Into::into));
+            TaskContext::with_context(env, move |cx| f(cx, tx));
+
+            #[cfg(feature = "rust-stack-frames")]
+            TaskContext::with_context(env, |mut cx| {
+                crate::types::error::note_pending(&mut cx, "channel", caller.location());
             });
         });
 
-        self.state
+        let item = self
+            .state
             .tsfn
-            .call(callback, None)
+            .call(Callback { f }, None)
             .map_err(|_| SendError)?;
 
-        Ok(JoinHandle { rx })
+        trace::channel_send(caller);
+
+        Ok(JoinHandle {
+            rx,
+            channel: Arc::downgrade(&self.state),
+            item: Some(item),
+            aborted: AtomicBool::new(false),
+            closed: false,
+        })
+    }
+
+    // Returns the environment of the channel if called on its JavaScript thread where
+    // a closure may execute immediately: no closures are queued ahead of it and no
+    // exception is pending.
+    pub(crate) fn idle_env(&self) -> Option<crate::context::internal::Env> {
+        let env = self.state.tsfn.idle_env()?;
+
+        if unsafe { sys::error::is_throwing(env) } {
+            return None;
+        }
+
+        Some(env.into())
     }
 
     /// Returns a boolean indicating if this `Channel` will prevent the Node event
@@ -237,6 +439,14 @@ impl Drop for Channel {
             return;
         }
 
+        // On the JavaScript thread with no pending closures, the tsfn can be
+        // `unref`'d immediately instead of queueing a closure, so that dropping a
+        // clone does not prevent `Deferred::try_settle_now` from settling immediately
+        if let Some(env) = self.state.tsfn.idle_env() {
+            TaskContext::with_context(env.into(), |mut cx| self.state.unref(&mut cx));
+            return;
+        }
+
         // The ChannelState is dropped on a worker thread. We have to `unref`
         // the tsfn on the UV thread after all pending closures. Note that in
         // the most of scenarios the optimization in N-API layer would coalesce
@@ -259,21 +469,111 @@ impl Drop for Channel {
 
 /// An owned permission to join on the result of a closure sent to the JavaScript main
 /// thread with [`Channel::send`].
+///
+/// Dropping a `JoinHandle` detaches the closure; it still executes and its result is
+/// discarded.
 pub struct JoinHandle<T> {
     // `Err` is always `Throw`, but `Throw` cannot be sent across threads
     rx: oneshot::Receiver<Result<T, SendThrow>>,
+    // Does not keep the queue alive; once the channel is dropped, closures can no
+    // longer be aborted
+    channel: Weak<ChannelState>,
+    // `None` if the closure was never scheduled
+    item: Option<Item<Callback>>,
+    aborted: AtomicBool,
+    // The closure could not be scheduled because the environment is shutting down
+    closed: bool,
 }
 
 impl<T> JoinHandle<T> {
+    // A handle to a closure that was dropped without being scheduled
+    fn closed() -> Self {
+        let (_, rx) = oneshot::channel();
+
+        Self {
+            rx,
+            channel: Weak::new(),
+            item: None,
+            aborted: AtomicBool::new(false),
+            closed: true,
+        }
+    }
+
     /// Waits for the associated closure to finish executing
     ///
-    /// If the closure panics or throws an exception, `Err` is returned
+    /// If the closure panics or throws an exception, or was aborted, `Err` is returned
     ///
     /// # Panics
     ///
     /// This function panics if called within an asynchronous execution context.
     pub fn join(self) -> Result<T, JoinError> {
-        Ok(self.rx.blocking_recv()??)
+        let result = self.rx.blocking_recv();
+
+        Ok(Self::check_aborted(&self.aborted, self.closed, result)??)
+    }
+
+    /// Removes the closure from the queue if it has not started executing, returning
+    /// `true` if it was removed. Once removed, the closure never executes and joining
+    /// returns a [`JoinError`] for which [`JoinError::is_aborted`] is `true`.
+    ///
+    /// Returns `false` if the closure is executing or has executed, or if every
+    /// [`Channel`] sharing its queue has been dropped.
+    ///
+    /// The closure is dropped on the calling thread. If it is not the JavaScript
+    /// thread, a captured [`Root`] or [`Deferred`](crate::types::Deferred) is released
+    /// by the drop queue with the `napi-6` feature, and leaked without it, like when
+    /// a closure is dropped because the environment exits.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// # fn example(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    /// let channel = cx.channel();
+    ///
+    /// std::thread::spawn(move || {
+    ///     let handle = channel.send(|mut cx| {
+    ///         cx.console().log(["not needed"])?;
+    ///
+    ///         Ok(())
+    ///     });
+    ///
+    ///     if !handle.abort() {
+    ///         // Too late; the closure already started
+    ///         let _ = handle.join();
+    ///     }
+    /// });
+    /// # Ok(cx.undefined())
+    /// # }
+    /// ```
+    pub fn abort(&self) -> bool {
+        let (state, item) = match (self.channel.upgrade(), &self.item) {
+            (Some(state), Some(item)) => (state, item),
+            _ => return false,
+        };
+
+        let callback = match state.tsfn.remove(item) {
+            Some(callback) => callback,
+            None => return false,
+        };
+
+        self.aborted.store(true, Ordering::Release);
+        drop(callback);
+
+        true
+    }
+
+    // A dropped sender means the closure panicked, unless it was aborted or never
+    // scheduled
+    fn check_aborted<U>(
+        aborted: &AtomicBool,
+        closed: bool,
+        result: Result<U, oneshot::error::RecvError>,
+    ) -> Result<U, JoinError> {
+        match result {
+            Ok(value) => Ok(value),
+            Err(_) if closed => Err(JoinError(JoinErrorType::Closed)),
+            Err(_) if aborted.load(Ordering::Acquire) => Err(JoinError(JoinErrorType::Aborted)),
+            Err(err) => Err(err.into()),
+        }
     }
 }
 
@@ -285,7 +585,9 @@ impl<T> Future for JoinHandle<T> {
     fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<Self::Output> {
         match Pin::new(&mut self.rx).poll(cx) {
             Poll::Ready(result) => {
-                // Flatten `Result<Result<T, SendThrow>, RecvError>` by mapping to
+                let result = Self::check_aborted(&self.aborted, self.closed, result);
+
+                // Flatten `Result<Result<T, SendThrow>, JoinError>` by mapping to
                 // `Result<T, JoinError>`. This can be simplified by replacing the
                 // closure with a try-block after stabilization.
                 // https://doc.rust-lang.org/beta/unstable-book/language-features/try-blocks.html
@@ -299,21 +601,37 @@ impl<T> Future for JoinHandle<T> {
 }
 
 #[derive(Debug)]
-/// Error returned by [`JoinHandle::join`] indicating the associated closure panicked
-/// or threw an exception.
+/// Error returned by [`JoinHandle::join`] indicating the associated closure panicked,
+/// threw an exception, was aborted, or could not be scheduled.
 pub struct JoinError(JoinErrorType);
 
 #[derive(Debug)]
 enum JoinErrorType {
     Panic,
     Throw,
+    Aborted,
+    Closed,
 }
 
 impl JoinError {
+    /// Indicates whether the closure was removed with [`JoinHandle::abort`] before it
+    /// executed.
+    pub fn is_aborted(&self) -> bool {
+        matches!(self.0, JoinErrorType::Aborted)
+    }
+
+    /// Indicates whether the closure was dropped by [`Channel::send`] without being
+    /// scheduled, because the JavaScript environment is shutting down.
+    pub fn is_closed(&self) -> bool {
+        matches!(self.0, JoinErrorType::Closed)
+    }
+
     fn as_str(&self) -> &str {
         match &self.0 {
             JoinErrorType::Panic => "Closure panicked before returning",
             JoinErrorType::Throw => "Closure threw an exception",
+            JoinErrorType::Aborted => "Closure was aborted before executing",
+            JoinErrorType::Closed => "Closure was dropped because the environment is shutting down",
         }
     }
 }
@@ -419,7 +737,7 @@ impl ChannelState {
     // Monomorphized trampoline funciton for calling the user provided closure
     fn callback(env: Option<Env>, callback: Callback) {
         if let Some(env) = env {
-            callback(env);
+            (callback.f)(env);
         } else {
             crate::context::internal::IS_RUNNING.with(|v| {
                 *v.borrow_mut() = false;
@@ -0,0 +1,253 @@
+use std::{
+    collections::VecDeque,
+    mem,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    context::{Context, TaskContext},
+    event::Channel,
+    handle::{Handle, Root},
+    object::Object,
+    result::{JsResult, NeonResult},
+    types::{extract::TryIntoJs, JsFunction, JsObject, JsValue},
+};
+
+type Payload = Box<dyn for<'a> FnOnce(&mut TaskContext<'a>) -> JsResult<'a, JsValue> + Send>;
+
+struct Emission {
+    event: String,
+    payload: Payload,
+}
+
+/// Emits events on a JavaScript
+/// [`EventEmitter`](https://nodejs.org/api/events.html#class-eventemitter) from any
+/// thread.
+///
+/// An `Emitter` holds a reference to an object with an `emit` method and a [`Channel`]
+/// to the JavaScript thread that owns it. Each call to [`Emitter::emit`] schedules a
+/// call to `target.emit(event, payload)` on that thread.
+///
+/// Cloning an `Emitter` creates a new handle to the same target, sharing a queue
+/// of pending events; events emitted through any clone are delivered in order.
+///
+/// Events emitted after the JavaScript environment has shut down are dropped. If a
+/// listener throws, the exception is reported as an unhandled rejection, as with
+/// any other [`Channel`] closure, and the remaining events are still delivered.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::event::Emitter;
+///
+/// fn download(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let emitter = cx.argument::<JsObject>(0)?;
+///     let emitter = Emitter::new(&mut cx, emitter)?;
+///
+///     std::thread::spawn(move || {
+///         for percent in 0..=100 {
+///             emitter.emit("progress", percent as f64);
+///         }
+///
+///         emitter.emit("done", ());
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Emitter {
+    state: Arc<EmitterState>,
+    batch: bool,
+}
+
+impl std::fmt::Debug for Emitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Emitter")
+    }
+}
+
+impl Emitter {
+    /// Creates an `Emitter` for `target`, using a new [`Channel`].
+    ///
+    /// Throws a `TypeError` if `target` does not have an `emit` method.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, target: Handle<JsObject>) -> NeonResult<Self> {
+        let channel = cx.channel();
+
+        Self::with_channel(cx, target, channel)
+    }
+
+    /// Creates an `Emitter` for `target`, scheduling events on `channel`.
+    ///
+    /// Use an unreferenced channel to allow the Node event loop to exit while the
+    /// `Emitter` exists.
+    ///
+    /// Throws a `TypeError` if `target` does not have an `emit` method.
+    pub fn with_channel<'a, C: Context<'a>>(
+        cx: &mut C,
+        target: Handle<JsObject>,
+        channel: Channel,
+    ) -> NeonResult<Self> {
+        target.get::<JsFunction, _, _>(cx, "emit")?;
+
+        Ok(Self {
+            state: Arc::new(EmitterState {
+                target: Some(target.root(cx)),
+                channel,
+                queue: Mutex::new(Queue::default()),
+            }),
+            batch: false,
+        })
+    }
+
+    /// Deliver events emitted through this handle in batches, one batch per turn of
+    /// the event loop, instead of scheduling each event separately. Batching reduces
+    /// overhead when events are emitted rapidly. _Off by default_
+    ///
+    /// Clones of this `Emitter` inherit the setting.
+    pub fn batch(&mut self, batch: bool) -> &mut Self {
+        self.batch = batch;
+        self
+    }
+
+    /// Emits `event` with `payload`, converted to a JavaScript value on the JavaScript
+    /// thread.
+    pub fn emit<E, P>(&self, event: E, payload: P)
+    where
+        E: Into<String>,
+        P: for<'cx> TryIntoJs<'cx> + Send + 'static,
+    {
+        self.emit_with(event, move |cx| Ok(payload.try_into_js(cx)?.upcast()));
+    }
+
+    /// Emits `event` with the payload created by `f` on the JavaScript thread.
+    ///
+    /// If `f` throws, the event is not emitted and the exception is reported as an
+    /// unhandled rejection.
+    pub fn emit_with<E, F>(&self, event: E, f: F)
+    where
+        E: Into<String>,
+        F: for<'a> FnOnce(&mut TaskContext<'a>) -> JsResult<'a, JsValue> + Send + 'static,
+    {
+        let emission = Emission {
+            event: event.into(),
+            payload: Box::new(f),
+        };
+
+        {
+            let mut queue = self.state.queue.lock().unwrap();
+
+            queue.events.push_back(emission);
+
+            if self.batch {
+                // A batch is already waiting to be delivered
+                if queue.scheduled {
+                    return;
+                }
+
+                queue.scheduled = true;
+            }
+        }
+
+        EmitterState::schedule(&self.state, self.batch);
+    }
+}
+
+#[derive(Default)]
+struct Queue {
+    events: VecDeque<Emission>,
+    // A batch has been scheduled and has not yet started delivering
+    scheduled: bool,
+}
+
+struct EmitterState {
+    // `Option` so that the `Root` can be moved to the JavaScript thread on `Drop`.
+    // It will *always* be `Some` until then.
+    target: Option<Root<JsObject>>,
+    channel: Channel,
+    queue: Mutex<Queue>,
+}
+
+impl EmitterState {
+    // Schedule delivery of a single event, or of every queued event if `batch`
+    fn schedule(state: &Arc<Self>, batch: bool) {
+        let state_ = Arc::clone(state);
+        let sent = state
+            .channel
+            .try_send(move |mut cx| state_.deliver(&mut cx, batch));
+
+        // The environment has shut down; drop pending events outside the lock
+        if sent.is_err() {
+            let events = mem::take(&mut state.queue.lock().unwrap().events);
+
+            drop(events);
+        }
+    }
+
+    fn deliver(self: &Arc<Self>, cx: &mut TaskContext, batch: bool) -> NeonResult<()> {
+        if !batch {
+            let emission = self.queue.lock().unwrap().events.pop_front();
+
+            return match emission {
+                Some(emission) => self.emit(cx, emission),
+                // Delivered by an earlier batch
+                None => Ok(()),
+            };
+        }
+
+        let mut events = {
+            let mut queue = self.queue.lock().unwrap();
+
+            queue.scheduled = false;
+            mem::take(&mut queue.events)
+        };
+
+        while let Some(emission) = events.pop_front() {
+            if let Err(err) = self.emit(cx, emission) {
+                // Re-queue the rest of the batch to be delivered after the exception
+                // has been reported
+                let mut queue = self.queue.lock().unwrap();
+                let reschedule = !queue.scheduled && !events.is_empty();
+
+                while let Some(emission) = events.pop_back() {
+                    queue.events.push_front(emission);
+                }
+
+                queue.scheduled |= reschedule;
+                drop(queue);
+
+                if reschedule {
+                    Self::schedule(self, true);
+                }
+
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn emit(&self, cx: &mut TaskContext, emission: Emission) -> NeonResult<()> {
+        // `unwrap` will not fail because `target` is only `None` during `Drop`
+        let target = self.target.as_ref().unwrap().to_inner(cx);
+        let emit = target.get::<JsFunction, _, _>(cx, "emit")?;
+        let event = cx.string(emission.event);
+        let payload = (emission.payload)(cx)?;
+
+        emit.exec(cx, target, [event.upcast(), payload])
+    }
+}
+
+impl Drop for EmitterState {
+    fn drop(&mut self) {
+        if let Some(target) = self.target.take() {
+            // `Channel::try_send` will only fail if the environment has shutdown.
+            // In that case, the teardown will perform clean-up.
+            let _ = self.channel.try_send(move |mut cx| {
+                target.drop(&mut cx);
+                Ok(())
+            });
+        }
+    }
+}
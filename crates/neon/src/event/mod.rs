@@ -105,6 +105,26 @@
 //! }
 //! ```
 //!
+//! ## Tracing
+//!
+//! With the `tracing` feature enabled, [`Channel`] and [`TaskBuilder`] report
+//! [`tracing`](https://docs.rs/tracing) spans and events with the `neon::event` target
+//! at the `TRACE` level. Their names and fields are stable:
+//!
+//! | Name                    | Kind  | Recorded                                             |
+//! |-------------------------|-------|------------------------------------------------------|
+//! | `neon.channel.send`     | event | A closure was queued with `Channel::send`            |
+//! | `neon.channel.callback` | span  | The closure ran on the JavaScript thread             |
+//! | `neon.task.execute`     | span  | The `execute` callback ran on the Node worker pool   |
+//! | `neon.task.complete`    | span  | The `complete` callback ran on the JavaScript thread |
+//!
+//! Each has `caller.file`, `caller.line` and `caller.column` fields with the location of
+//! the call to `Channel::send` or `Channel::try_send`, or to `TaskBuilder::and_then`
+//! or `TaskBuilder::promise`. Spans also record a `duration_us` field, the time spent
+//! in the span in microseconds, when they close.
+//!
+//! Without the feature, no instrumentation is compiled.
+//!
 //! ## See also
 //!
 //! 1. Panu Pitkamaki. [Event loop from 10,000ft][event-loop].
@@ -118,17 +138,47 @@
 //! [psd-crate]: https://crates.io/crates/psd
 //! [psd-file]: https://www.adobe.com/devnet-apps/photoshop/fileformatashtml/
 
+mod async_resource;
+#[cfg(feature = "napi-4")]
+mod callback;
 #[cfg(feature = "napi-4")]
 mod channel;
+#[cfg(feature = "napi-4")]
+mod emitter;
+#[cfg(feature = "napi-5")]
+pub(crate) mod process;
+#[cfg(feature = "napi-4")]
+mod threadsafe_function;
 
 mod task;
+#[cfg(feature = "napi-4")]
+mod task_scope;
+mod trace;
 
-pub use self::task::TaskBuilder;
+pub use self::{async_resource::AsyncResource, task::TaskBuilder};
 
+#[cfg(feature = "napi-6")]
+pub(crate) use self::async_resource::NapiAsyncContext;
+
+#[cfg(feature = "napi-4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-4")))]
+pub use self::callback::{Callback, CallbackError};
 #[cfg(all(feature = "napi-5", feature = "futures"))]
 pub(crate) use self::channel::SendThrow;
 #[cfg(feature = "napi-4")]
 pub use self::channel::{Channel, JoinError, JoinHandle, SendError};
+#[cfg(feature = "napi-4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-4")))]
+pub use self::emitter::Emitter;
+#[cfg(feature = "napi-5")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+pub use self::process::ListenerGuard;
+#[cfg(feature = "napi-4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-4")))]
+pub use self::task_scope::TaskScope;
+#[cfg(feature = "napi-4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-4")))]
+pub use self::threadsafe_function::{CallError, ThreadsafeFunction};
 
 #[cfg(feature = "napi-4")]
 #[deprecated(since = "0.9.0", note = "Please use the Channel type instead")]
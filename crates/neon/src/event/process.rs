@@ -0,0 +1,325 @@
+use std::{
+    ptr,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{
+    context::{
+        internal::{ContextInternal, Env},
+        Context, FunctionContext, TaskContext,
+    },
+    event::Channel,
+    handle::{Handle, Managed, Root},
+    object::Object,
+    result::{JsResult, NeonResult},
+    sys::no_panic::FailureBoundary,
+    types::{Finalize, JsArray, JsError, JsFunction, JsNumber, JsPromise, JsString, JsValue},
+};
+
+const BOUNDARY: FailureBoundary<'static> = FailureBoundary {
+    action: "executing a `neon::event::ListenerGuard` callback",
+    detail: None,
+};
+
+/// A listener on a process-wide error event, created with
+/// [`Context::on_uncaught_exception`] or [`Context::on_unhandled_rejection`].
+///
+/// The listener is removed when the guard is dropped. Since a listener may only be
+/// removed on the JavaScript thread, dropping the guard schedules the removal with a
+/// [`Channel`], which makes it safe to drop from any thread; use
+/// [`ListenerGuard::remove`] to remove the listener immediately instead.
+///
+/// If the callback panics or throws, the failure is reported as an unhandled
+/// rejection, as with other callbacks scheduled by Neon, which is handled according
+/// to the `--unhandled-rejections` mode of the process. Failures reported this way
+/// are not passed to any `ListenerGuard` callback, so a failing callback cannot
+/// trigger itself.
+pub struct ListenerGuard {
+    // `None` after the listener has been removed by `ListenerGuard::remove`
+    inner: Option<Inner>,
+}
+
+struct Inner {
+    event: &'static str,
+    listener: Root<JsFunction>,
+    channel: Channel,
+}
+
+impl ListenerGuard {
+    fn new<'a, C: Context<'a>>(
+        cx: &mut C,
+        event: &'static str,
+        listener: Handle<JsFunction>,
+    ) -> NeonResult<Self> {
+        let process = crate::intrinsics::process(cx)?;
+        let on = process.get::<JsFunction, _, _>(cx, "on")?;
+        let name = cx.string(event);
+
+        on.exec(cx, process, [name.upcast(), listener.upcast()])?;
+
+        let mut channel = cx.channel();
+
+        // Listening for errors should not keep the event loop alive
+        channel.unref(cx);
+
+        Ok(Self {
+            inner: Some(Inner {
+                event,
+                listener: listener.root(cx),
+                channel,
+            }),
+        })
+    }
+
+    /// Removes the listener immediately.
+    pub fn remove<'a, C: Context<'a>>(mut self, cx: &mut C) -> NeonResult<()> {
+        // `unwrap` will not fail because `inner` is only `None` after `remove`
+        let Inner {
+            event, listener, ..
+        } = self.inner.take().unwrap();
+
+        remove_listener(cx, event, listener)
+    }
+}
+
+impl Finalize for ListenerGuard {}
+
+impl Drop for ListenerGuard {
+    fn drop(&mut self) {
+        let Inner {
+            event,
+            listener,
+            channel,
+        } = match self.inner.take() {
+            Some(inner) => inner,
+            None => return,
+        };
+
+        // `Channel::try_send` will only fail if the environment has shutdown,
+        // in which case the listener is gone as well.
+        let _ = channel.try_send(move |mut cx| remove_listener(&mut cx, event, listener));
+    }
+}
+
+fn remove_listener<'a, C: Context<'a>>(
+    cx: &mut C,
+    event: &'static str,
+    listener: Root<JsFunction>,
+) -> NeonResult<()> {
+    let listener = listener.into_inner(cx);
+    let process = crate::intrinsics::process(cx)?;
+    let remove = process.get::<JsFunction, _, _>(cx, "removeListener")?;
+    let name = cx.string(event);
+
+    remove.exec(cx, process, [name.upcast(), listener.upcast()])
+}
+
+pub(crate) fn on_uncaught_exception<'a, C, F>(cx: &mut C, f: F) -> NeonResult<ListenerGuard>
+where
+    C: Context<'a>,
+    F: for<'b> Fn(TaskContext<'b>, Handle<'b, JsValue>) -> NeonResult<()> + 'static,
+{
+    // Monitoring does not change the default behavior of crashing the process
+    let listener = listener(cx, None, move |cx, error, _origin| f(cx, error))?;
+
+    ListenerGuard::new(cx, "uncaughtExceptionMonitor", listener)
+}
+
+pub(crate) fn on_unhandled_rejection<'a, C, F>(cx: &mut C, f: F) -> NeonResult<ListenerGuard>
+where
+    C: Context<'a>,
+    F: for<'b> Fn(TaskContext<'b>, Handle<'b, JsValue>, Handle<'b, JsPromise>) -> NeonResult<()>
+        + 'static,
+{
+    static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let listener = listener(cx, Some(id), move |mut cx, reason, promise| {
+        let promise = promise.downcast_or_throw(&mut cx)?;
+
+        f(cx, reason, promise)
+    })?;
+    let key = monitor_key(cx)?;
+    let id = cx.number(id);
+
+    listener.set(cx, key, id)?;
+
+    ListenerGuard::new(cx, "unhandledRejection", listener)
+}
+
+// Creates a listener that calls `f` with its first two arguments inside of a
+// `FailureBoundary`. A listener for `unhandledRejection` is given the `id` of its
+// guard, to raise the rejection when no other code is listening.
+fn listener<'a, C, F>(cx: &mut C, id: Option<u32>, f: F) -> JsResult<'a, JsFunction>
+where
+    C: Context<'a>,
+    F: for<'b> Fn(TaskContext<'b>, Handle<'b, JsValue>, Handle<'b, JsValue>) -> NeonResult<()>
+        + 'static,
+{
+    JsFunction::new(cx, move |mut cx| {
+        let undefined = cx.undefined();
+        let first = cx.argument_opt(0).unwrap_or_else(|| undefined.upcast());
+        let second = cx.argument_opt(1).unwrap_or_else(|| undefined.upcast());
+
+        // Ignore failures of this or any other guard's callback
+        if is_callback_failure(&mut cx, first) {
+            return reraise_unobserved(&mut cx, id, first);
+        }
+
+        let reason = first;
+        let (first, second) = (first.to_raw(), second.to_raw());
+
+        unsafe {
+            BOUNDARY.catch_failure(cx.env().to_raw(), None, |env| {
+                if let Some(env) = env {
+                    let env = Env::from(env);
+
+                    TaskContext::with_context(env, |cx| {
+                        let first = Handle::new_internal(JsValue::from_raw(env, first));
+                        let second = Handle::new_internal(JsValue::from_raw(env, second));
+
+                        // An error is left pending for the boundary to catch
+                        let _ = f(cx, first, second);
+                    });
+                }
+
+                ptr::null_mut()
+            });
+        }
+
+        reraise_unobserved(&mut cx, id, reason)
+    })
+}
+
+// The key of the `id` of a guard on its `unhandledRejection` listener
+fn monitor_key<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsValue> {
+    let name = cx.string("neon.unhandledRejectionMonitor");
+
+    crate::intrinsics::symbol_for(cx)?
+        .call_with(cx)
+        .arg(name)
+        .apply(cx)
+}
+
+// Node.js handles an unhandled rejection according to the `--unhandled-rejections`
+// mode when nothing listens for `unhandledRejection`. Since guards only observe
+// rejections, the listener of the most recent guard does the same if every listener
+// belongs to a guard.
+fn reraise_unobserved<'a>(
+    cx: &mut FunctionContext<'a>,
+    id: Option<u32>,
+    reason: Handle<'a, JsValue>,
+) -> JsResult<'a, JsValue> {
+    let undefined = cx.undefined().upcast();
+    let id = match id {
+        Some(id) => id as f64,
+        None => return Ok(undefined),
+    };
+
+    let process = crate::intrinsics::process(cx)?;
+    let name = cx.string("unhandledRejection");
+    let listeners = process
+        .get::<JsFunction, _, _>(cx, "listeners")?
+        .call_with(cx)
+        .this(process)
+        .arg(name)
+        .apply::<JsArray, _>(cx)?
+        .to_vec(cx)?;
+
+    let key = monitor_key(cx)?;
+    let mut last = None;
+
+    for listener in listeners {
+        let listener = match listener.downcast::<JsFunction, _>(cx) {
+            Ok(listener) => listener,
+            Err(_) => return Ok(undefined),
+        };
+
+        match listener.get_value(cx, key)?.downcast::<JsNumber, _>(cx) {
+            Ok(id) => last = Some(id.value(cx)),
+            Err(_) => return Ok(undefined),
+        }
+    }
+
+    if last != Some(id) {
+        return Ok(undefined);
+    }
+
+    match unhandled_rejections_mode(cx)?.as_deref() {
+        // Rejections are only reported if nothing listens in these modes; `strict`
+        // raises them as uncaught exceptions before emitting the event
+        Some("warn") | Some("none") | Some("strict") => Ok(undefined),
+        Some("warn-with-error-code") => {
+            let warning = cx.string("Unhandled promise rejection");
+            let name = cx.string("UnhandledPromiseRejectionWarning");
+            let exit_code = cx.number(1);
+
+            process
+                .get::<JsFunction, _, _>(cx, "emitWarning")?
+                .call_with(cx)
+                .this(process)
+                .arg(warning)
+                .arg(name)
+                .exec(cx)?;
+
+            process.set(cx, "exitCode", exit_code)?;
+
+            Ok(undefined)
+        }
+        // The default `throw` mode
+        _ => cx.throw(reason),
+    }
+}
+
+// Returns the value of the last `--unhandled-rejections` option, if any, from
+// `NODE_OPTIONS` or the command line, which takes precedence
+fn unhandled_rejections_mode<'a, C: Context<'a>>(cx: &mut C) -> NeonResult<Option<String>> {
+    const OPTION: &str = "--unhandled-rejections";
+
+    let mut args = std::env::var("NODE_OPTIONS")
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let process = crate::intrinsics::process(cx)?;
+    let exec_argv = process.get::<JsArray, _, _>(cx, "execArgv")?.to_vec(cx)?;
+
+    for arg in exec_argv {
+        if let Ok(arg) = arg.downcast::<JsString, _>(cx) {
+            args.push(arg.value(cx));
+        }
+    }
+
+    let mut mode = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == OPTION {
+            mode = args.next();
+        } else if let Some(value) = arg.strip_prefix(OPTION).and_then(|s| s.strip_prefix('=')) {
+            mode = Some(value.to_string());
+        }
+    }
+
+    Ok(mode)
+}
+
+// Checks if `value` is an error created by `BOUNDARY`
+fn is_callback_failure<'a, C: Context<'a>>(cx: &mut C, value: Handle<JsValue>) -> bool {
+    let error = match value.downcast::<JsError, _>(cx) {
+        Ok(error) => error,
+        Err(_) => return false,
+    };
+
+    let message = cx
+        .try_catch(|cx| error.get_value(cx, "message"))
+        .ok()
+        .and_then(|message| message.downcast::<JsString, _>(cx).ok())
+        .map(|message| message.value(cx));
+
+    matches!(
+        message,
+        Some(message) if [BOUNDARY.both(), BOUNDARY.exception(), BOUNDARY.panic()].contains(&message)
+    )
+}
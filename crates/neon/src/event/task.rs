@@ -1,11 +1,16 @@
-use std::{panic::resume_unwind, thread};
+use std::{
+    fmt,
+    panic::{resume_unwind, Location},
+    thread,
+};
 
 use crate::{
     context::{internal::Env, Context, TaskContext},
+    event::trace,
     handle::Handle,
     result::{JsResult, NeonResult},
-    sys::{async_work, raw},
-    types::{Deferred, JsPromise, Value},
+    sys::{async_work, no_panic::FailureBoundary, raw},
+    types::{extract::TryIntoJs, Deferred, JsPromise, JsValue, Value},
 };
 
 #[cfg_attr(
@@ -26,9 +31,18 @@ use crate::{
 ///     Ok(promise)
 /// }
 /// ```
+///
+/// ## Failures
+///
+/// A panic in `execute`, or a panic or uncaught exception in `complete`, is reported
+/// with an error whose message includes the location of the call to
+/// [`Context::task`], along with the [`name`](TaskBuilder::name) of the task if it
+/// has one. If the task settles a promise, the promise is rejected with the error;
+/// otherwise, it is emitted as an `unhandledRejection`.
 pub struct TaskBuilder<'cx, C, E> {
     cx: &'cx mut C,
     execute: E,
+    label: Label,
 }
 
 impl<'a: 'cx, 'cx, C, O, E> TaskBuilder<'cx, C, E>
@@ -39,21 +53,51 @@ where
 {
     /// Construct a new task builder from an `execute` callback that can be
     /// scheduled to execute on the Node worker pool
+    #[track_caller]
     pub fn new(cx: &'cx mut C, execute: E) -> Self {
-        Self { cx, execute }
+        Self {
+            cx,
+            execute,
+            label: Label {
+                name: None,
+                location: Location::caller(),
+            },
+        }
+    }
+
+    /// Names the task in the messages of errors reported for its failures.
+    ///
+    /// The worker pool threads are shared by all tasks and are not renamed.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn decode(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    ///     let promise = cx
+    ///         .task(|| "decoded".to_string())
+    ///         .name("decode-image")
+    ///         .promise(|mut cx, image| Ok(cx.string(image)));
+    ///
+    ///     Ok(promise)
+    /// }
+    /// ```
+    pub fn name(mut self, name: &str) -> Self {
+        self.label.name = Some(name.into());
+        self
     }
 
     /// Schedules a task to execute on the Node worker pool, executing the
     /// `complete` callback on the JavaScript main thread with the result
     /// of the `execute` callback
+    #[cfg_attr(any(feature = "tracing", feature = "rust-stack-frames"), track_caller)]
     pub fn and_then<F>(self, complete: F)
     where
         F: FnOnce(TaskContext, O) -> NeonResult<()> + Send + 'static,
     {
         let env = self.cx.env();
+        let caller = trace::Caller::new();
         let execute = self.execute;
 
-        schedule(env, execute, complete);
+        schedule(env, caller, execute, complete, self.label);
     }
 
     /// Schedules a task to execute on the Node worker pool and returns a
@@ -62,46 +106,139 @@ where
     /// The `complete` callback will execute on the JavaScript main thread and
     /// is passed the return value from `execute`. If the `complete` callback
     /// throws, the promise will be rejected with the exception
+    ///
+    /// If `complete` returns a [`JsPromise`], or any other thenable, the returned
+    /// promise adopts its state: it is resolved or rejected once the returned promise
+    /// is, which allows chaining JavaScript async work after the task.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn lookup(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    ///     let key = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///     let fetch = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    ///
+    ///     let promise = cx
+    ///         .task(move || key.trim().to_lowercase())
+    ///         .promise(move |mut cx, key| {
+    ///             let fetch = fetch.into_inner(&mut cx);
+    ///             let key = cx.string(key);
+    ///
+    ///             // Resolves with the result of `await fetch(key)`
+    ///             fetch.call_with(&cx).arg(key).apply::<JsPromise, _>(&mut cx)
+    ///         });
+    ///
+    ///     Ok(promise)
+    /// }
+    /// ```
+    #[cfg_attr(any(feature = "tracing", feature = "rust-stack-frames"), track_caller)]
     pub fn promise<V, F>(self, complete: F) -> Handle<'a, JsPromise>
     where
         V: Value,
         F: FnOnce(TaskContext, O) -> JsResult<V> + Send + 'static,
     {
         let env = self.cx.env();
+        let caller = trace::Caller::new();
         let (deferred, promise) = JsPromise::new(self.cx);
         let execute = self.execute;
 
-        schedule_promise(env, execute, complete, deferred);
+        schedule_promise(env, caller, execute, complete, deferred, self.label);
 
         promise
     }
 }
 
+impl<'a: 'cx, 'cx, C, O, E> TaskBuilder<'cx, C, E>
+where
+    C: Context<'a>,
+    O: for<'b> TryIntoJs<'b> + Send + 'static,
+    E: FnOnce() -> O + Send + 'static,
+{
+    /// Schedules a task to execute on the Node worker pool and returns a
+    /// promise that is resolved with the return value from `execute`, converted
+    /// with [`TryIntoJs`].
+    ///
+    /// Equivalent to `promise(|mut cx, value| value.try_into_js(&mut cx))`. Tuples
+    /// resolve to arrays, and [`Named`](crate::types::extract::Named) tuples to
+    /// objects:
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn checksum(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    ///     let data = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///
+    ///     // Resolves to `[length, sum]`
+    ///     let promise = cx
+    ///         .task(move || {
+    ///             let sum = data.bytes().fold(0u32, |sum, b| sum.wrapping_add(b as u32));
+    ///
+    ///             (data.len() as u32, sum)
+    ///         })
+    ///         .into_promise();
+    ///
+    ///     Ok(promise)
+    /// }
+    /// ```
+    #[cfg_attr(any(feature = "tracing", feature = "rust-stack-frames"), track_caller)]
+    pub fn into_promise(self) -> Handle<'a, JsPromise> {
+        self.promise(|mut cx, value| Ok(value.try_into_js(&mut cx)?.upcast::<JsValue>()))
+    }
+}
+
+// Identifies a task in the messages of errors reported for its failures
+struct Label {
+    name: Option<Box<str>>,
+    location: &'static Location<'static>,
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(name) = &self.name {
+            write!(f, "{:?}, ", name)?;
+        }
+
+        write!(f, "scheduled at {}", self.location)
+    }
+}
+
 // Schedule a task to execute on the Node worker pool
-fn schedule<I, O, D>(env: Env, input: I, data: D)
+fn schedule<I, O, D>(env: Env, caller: trace::Caller, input: I, data: D, label: Label)
 where
     I: FnOnce() -> O + Send + 'static,
     O: Send + 'static,
     D: FnOnce(TaskContext, O) -> NeonResult<()> + Send + 'static,
 {
     unsafe {
-        async_work::schedule(env.to_raw(), input, execute::<I, O>, complete::<O, D>, data);
+        async_work::schedule(
+            env.to_raw(),
+            (input, caller),
+            execute::<I, O>,
+            complete::<O, D>,
+            (data, caller),
+            label,
+        );
     }
 }
 
-fn execute<I, O>(input: I) -> O
+fn execute<I, O>((input, caller): (I, trace::Caller)) -> O
 where
     I: FnOnce() -> O + Send + 'static,
     O: Send + 'static,
 {
+    let _span = trace::task_execute(caller);
+
     input()
 }
 
-fn complete<O, D>(env: raw::Env, output: thread::Result<O>, callback: D)
-where
+fn complete<O, D>(
+    env: raw::Env,
+    output: thread::Result<O>,
+    (callback, caller): (D, trace::Caller),
+    _label: &Label,
+) where
     O: Send + 'static,
     D: FnOnce(TaskContext, O) -> NeonResult<()> + Send + 'static,
 {
+    let _span = trace::task_complete(caller);
     let output = output.unwrap_or_else(|panic| {
         // If a panic was caught while executing the task on the Node Worker
         // pool, resume panicking on the main JavaScript thread
@@ -111,11 +248,22 @@ where
     TaskContext::with_context(env.into(), move |cx| {
         let _ = callback(cx, output);
     });
+
+    #[cfg(feature = "rust-stack-frames")]
+    TaskContext::with_context(env.into(), move |mut cx| {
+        crate::types::error::note_pending(&mut cx, "task", caller.location());
+    });
 }
 
 // Schedule a task to execute on the Node worker pool and settle a `Promise` with the result
-fn schedule_promise<I, O, D, V>(env: Env, input: I, complete: D, deferred: Deferred)
-where
+fn schedule_promise<I, O, D, V>(
+    env: Env,
+    caller: trace::Caller,
+    input: I,
+    complete: D,
+    deferred: Deferred,
+    label: Label,
+) where
     I: FnOnce() -> O + Send + 'static,
     O: Send + 'static,
     D: FnOnce(TaskContext, O) -> JsResult<V> + Send + 'static,
@@ -124,10 +272,11 @@ where
     unsafe {
         async_work::schedule(
             env.to_raw(),
-            input,
+            (input, caller),
             execute::<I, O>,
             complete_promise::<O, D, V>,
-            (complete, deferred),
+            (complete, deferred, caller),
+            label,
         );
     }
 }
@@ -135,19 +284,33 @@ where
 fn complete_promise<O, D, V>(
     env: raw::Env,
     output: thread::Result<O>,
-    (complete, deferred): (D, Deferred),
+    (complete, deferred, caller): (D, Deferred, trace::Caller),
+    label: &Label,
 ) where
     O: Send + 'static,
     D: FnOnce(TaskContext, O) -> JsResult<V> + Send + 'static,
     V: Value,
 {
+    let _span = trace::task_complete(caller);
     let env = env.into();
+    let boundary = FailureBoundary {
+        action: async_work::ACTION,
+        detail: Some(label),
+    };
 
     TaskContext::with_context(env, move |cx| {
-        deferred.try_catch_settle(cx, move |cx| {
+        deferred.try_catch_settle_within(&boundary, cx, move |cx| {
             let output = output.unwrap_or_else(|panic| resume_unwind(panic));
+            let result = complete(cx, output);
+
+            #[cfg(feature = "rust-stack-frames")]
+            if result.is_err() {
+                TaskContext::with_context(env, |mut cx| {
+                    crate::types::error::note_pending(&mut cx, "task", caller.location());
+                });
+            }
 
-            complete(cx, output)
+            result
         })
     });
 }
@@ -0,0 +1,290 @@
+use std::{
+    any::Any,
+    fmt, mem,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::{
+    context::{internal::ContextInternal, Context, TaskContext},
+    event::Channel,
+    handle::Root,
+    object::Object,
+    result::{JsResult, NeonResult},
+    types::{Deferred, JsArray, JsValue, Value},
+};
+
+type Complete<T> =
+    Box<dyn for<'a> FnOnce(TaskContext<'a>, Vec<T>) -> JsResult<'a, JsValue> + Send + 'static>;
+
+/// Settles a promise once a group of closures, running on other threads or on the
+/// JavaScript thread, have all completed.
+///
+/// Each closure started with [`TaskScope::spawn`] or [`TaskScope::send`] is a
+/// _ticket_ that produces a `T`. Once every ticket has completed, and the scope and
+/// all of its clones have been dropped, the `complete` closure passed to
+/// [`TaskScope::new`] is called on the JavaScript thread with the results, in the
+/// order the tickets were started, and the promise is settled with its return value.
+///
+/// # Failures
+///
+/// If a ticket panics, or a closure passed to [`TaskScope::send`] throws, the promise
+/// is rejected with the first failure instead of calling `complete`. Tickets that
+/// have not started when a ticket fails are cancelled and never called, and
+/// [`TaskScope::is_failed`] allows running tickets to stop early. The promise is still
+/// only settled after every running ticket has finished.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::event::TaskScope;
+///
+/// fn count_words(mut cx: FunctionContext) -> JsResult<JsPromise> {
+///     let texts = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+///     let channel = cx.channel();
+///     let (deferred, promise) = cx.promise();
+///
+///     // Resolves with the total once every text has been counted
+///     let scope = TaskScope::new(channel, deferred, |mut cx, counts: Vec<usize>| {
+///         Ok(cx.number(counts.into_iter().sum::<usize>() as f64))
+///     });
+///
+///     for text in texts {
+///         let text = text.downcast_or_throw::<JsString, _>(&mut cx)?.value(&mut cx);
+///
+///         scope.spawn(move || text.split_whitespace().count());
+///     }
+///
+///     Ok(promise)
+/// }
+/// ```
+pub struct TaskScope<T: Send + 'static> {
+    state: Arc<State<T>>,
+}
+
+impl<T: Send + 'static> TaskScope<T> {
+    /// Creates a scope that settles the promise of `deferred` with the value returned
+    /// by `complete`, called on the JavaScript thread of `channel`.
+    pub fn new<V, F>(channel: Channel, deferred: Deferred, complete: F) -> Self
+    where
+        V: Value,
+        F: FnOnce(TaskContext, Vec<T>) -> JsResult<V> + Send + 'static,
+    {
+        let complete: Complete<T> =
+            Box::new(move |cx, results| Ok(complete(cx, results)?.upcast()));
+
+        Self {
+            state: Arc::new(State {
+                channel,
+                failed: AtomicBool::new(false),
+                inner: Mutex::new(Inner {
+                    results: Vec::new(),
+                    failure: None,
+                    settle: Some((deferred, complete)),
+                }),
+            }),
+        }
+    }
+
+    /// Starts a ticket that calls `f` on a new thread.
+    ///
+    /// `f` is not called if a ticket has already failed.
+    pub fn spawn<F>(&self, f: F)
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let ticket = match self.ticket() {
+            Some(ticket) => ticket,
+            None => return,
+        };
+
+        std::thread::spawn(move || {
+            if ticket.is_cancelled() {
+                return;
+            }
+
+            match catch_unwind(AssertUnwindSafe(f)) {
+                Ok(result) => ticket.complete(result),
+                Err(panic) => drop(ticket.fail(Failure::Panic(panic))),
+            }
+        });
+    }
+
+    /// Starts a ticket that calls `f` on the JavaScript thread.
+    ///
+    /// `f` is not called if a ticket has already failed when it would run.
+    pub fn send<F>(&self, f: F)
+    where
+        F: FnOnce(TaskContext) -> NeonResult<T> + Send + 'static,
+    {
+        let ticket = match self.ticket() {
+            Some(ticket) => ticket,
+            None => return,
+        };
+
+        // If the environment has shut down, the ticket is dropped and the promise can
+        // no longer be settled
+        let _ = self.state.channel.try_send(move |cx| {
+            if ticket.is_cancelled() {
+                return Ok(());
+            }
+
+            let env = cx.env();
+            let result = catch_unwind(AssertUnwindSafe(move || f(cx)));
+
+            TaskContext::with_context(env, move |mut cx| {
+                let failure = match result {
+                    Ok(Ok(result)) => {
+                        ticket.complete(result);
+                        return Ok(());
+                    }
+                    Ok(Err(_)) => {
+                        // Unwrap safety: `Throw` is only returned with a pending exception
+                        let exception = cx.catch_pending().unwrap();
+                        let holder = cx.empty_array();
+
+                        holder.set(&mut cx, 0, exception)?;
+                        Failure::Throw(holder.root(&mut cx))
+                    }
+                    Err(panic) => Failure::Panic(panic),
+                };
+
+                if let Some(Failure::Throw(holder)) = ticket.fail(failure) {
+                    holder.drop(&mut cx);
+                }
+
+                Ok(())
+            })
+        });
+    }
+
+    /// Indicates whether a ticket has failed, in which case the promise will be
+    /// rejected.
+    pub fn is_failed(&self) -> bool {
+        self.state.failed.load(Ordering::Acquire)
+    }
+
+    fn ticket(&self) -> Option<Ticket<T>> {
+        if self.is_failed() {
+            return None;
+        }
+
+        let mut inner = self.state.lock();
+        let index = inner.results.len();
+
+        inner.results.push(None);
+
+        Some(Ticket {
+            state: Arc::clone(&self.state),
+            index,
+        })
+    }
+}
+
+impl<T: Send + 'static> Clone for TaskScope<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<T: Send + 'static> fmt::Debug for TaskScope<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskScope")
+            .field("failed", &self.is_failed())
+            .finish()
+    }
+}
+
+struct State<T: Send + 'static> {
+    channel: Channel,
+    failed: AtomicBool,
+    inner: Mutex<Inner<T>>,
+}
+
+struct Inner<T> {
+    // Results of the tickets, in the order they were started
+    results: Vec<Option<T>>,
+    // The first failure of a ticket
+    failure: Option<Failure>,
+    // `Option` so that the promise can be settled on `Drop`
+    settle: Option<(Deferred, Complete<T>)>,
+}
+
+enum Failure {
+    Panic(Box<dyn Any + Send + 'static>),
+    // An array containing the exception, since exceptions may be any value
+    Throw(Root<JsArray>),
+}
+
+impl<T: Send + 'static> State<T> {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner<T>> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+// Settles the promise once the scope and every ticket have been dropped
+impl<T: Send + 'static> Drop for State<T> {
+    fn drop(&mut self) {
+        let inner = self.inner.get_mut().unwrap_or_else(|err| err.into_inner());
+        let (deferred, complete) = match inner.settle.take() {
+            Some(settle) => settle,
+            None => return,
+        };
+
+        let results = mem::take(&mut inner.results);
+        let failure = inner.failure.take();
+
+        let _ = deferred.try_settle_with(&self.channel, move |mut cx| match failure {
+            Some(Failure::Panic(panic)) => resume_unwind(panic),
+            Some(Failure::Throw(holder)) => {
+                let exception = holder.into_inner(&mut cx).get_value(&mut cx, 0)?;
+
+                cx.throw(exception)
+            }
+            None => {
+                // Unwrap safety: Without a failure, no ticket was cancelled, and every
+                // ticket completed before dropping its reference to the state
+                let results = results.into_iter().map(Option::unwrap).collect();
+
+                complete(cx, results)
+            }
+        });
+    }
+}
+
+// A started closure of a `TaskScope`
+struct Ticket<T: Send + 'static> {
+    state: Arc<State<T>>,
+    index: usize,
+}
+
+impl<T: Send + 'static> Ticket<T> {
+    fn is_cancelled(&self) -> bool {
+        self.state.failed.load(Ordering::Acquire)
+    }
+
+    fn complete(self, result: T) {
+        self.state.lock().results[self.index] = Some(result);
+    }
+
+    // Records the failure if it is the first, otherwise returning it
+    fn fail(self, failure: Failure) -> Option<Failure> {
+        let mut inner = self.state.lock();
+
+        self.state.failed.store(true, Ordering::Release);
+
+        if inner.failure.is_some() {
+            return Some(failure);
+        }
+
+        inner.failure = Some(failure);
+
+        None
+    }
+}
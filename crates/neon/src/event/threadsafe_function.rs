@@ -0,0 +1,194 @@
+use std::{error, fmt, mem, sync::Arc};
+
+use crate::{
+    context::{internal::Env, Context, TaskContext},
+    handle::{Handle, Managed},
+    result::NeonResult,
+    sys::{tsfn::FunctionTsfn, Status, ThreadsafeFunctionCallMode},
+    types::JsFunction,
+};
+
+/// A JavaScript function that can be called with messages from any thread.
+///
+/// A `ThreadsafeFunction` wraps a JavaScript function and a Rust _translation_
+/// callback. The callback is registered once, when the `ThreadsafeFunction` is created,
+/// and is called on the JavaScript thread with the function and each message passed to
+/// [`ThreadsafeFunction::call`].
+///
+/// Unlike [`Channel::send`](crate::event::Channel::send), which boxes a new closure
+/// for every call, calling a `ThreadsafeFunction` only enqueues the message. This makes
+/// it a good fit for a fixed callback that is called very frequently with a small
+/// payload. Messages are delivered in the order they are sent from each thread.
+///
+/// A `ThreadsafeFunction` can be cloned cheaply; clones share the same queue and
+/// JavaScript function. The function is released once every clone has been dropped.
+///
+/// Exceptions thrown and panics in the translation callback are treated the same way
+/// as in a `Channel::send` closure: they are reported as uncaught exceptions.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::event::ThreadsafeFunction;
+///
+/// fn count(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+///     let callback = cx.argument::<JsFunction>(1)?;
+///     let tsfn = ThreadsafeFunction::new(&mut cx, callback, 0, |cx, callback, i: u32| {
+///         let this = cx.undefined();
+///         let i = cx.number(i);
+///
+///         callback.call(cx, this, [i.upcast()])?;
+///
+///         Ok(())
+///     })?;
+///
+///     std::thread::spawn(move || {
+///         for i in 0..n {
+///             tsfn.call(i).ok();
+///         }
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// ```
+pub struct ThreadsafeFunction<T> {
+    tsfn: Arc<FunctionTsfn<T>>,
+}
+
+impl<T> fmt::Debug for ThreadsafeFunction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ThreadsafeFunction")
+    }
+}
+
+impl<T: Send + 'static> ThreadsafeFunction<T> {
+    /// Creates a threadsafe function that passes each message to `f` along with
+    /// `callback`.
+    ///
+    /// If `max_queue_size` is `0`, the queue is unbounded. Otherwise, calls block or
+    /// fail while the queue is full, depending on whether [`ThreadsafeFunction::call`]
+    /// or [`ThreadsafeFunction::try_call`] is used.
+    pub fn new<'a, C, F>(
+        cx: &mut C,
+        callback: Handle<JsFunction>,
+        max_queue_size: usize,
+        f: F,
+    ) -> NeonResult<Self>
+    where
+        C: Context<'a>,
+        F: Fn(&mut TaskContext, Handle<JsFunction>, T) -> NeonResult<()> + 'static,
+    {
+        let env = cx.env().to_raw();
+        let translate = move |env, callback, data| {
+            let env: Env = unsafe { mem::transmute(env) };
+            let f = &f;
+
+            // Note: It is sufficient to use `TaskContext`'s `InheritedHandleScope` because
+            // N-API creates a `HandleScope` before calling the callback.
+            TaskContext::with_context(env, move |mut cx| {
+                let callback = Handle::new_internal(JsFunction::from_raw(env, callback));
+
+                // Exceptions are left pending to be reported as uncaught
+                let _ = f(&mut cx, callback, data);
+            });
+        };
+
+        let tsfn = unsafe { FunctionTsfn::new(env, callback.to_raw(), max_queue_size, translate) };
+
+        Ok(Self {
+            tsfn: Arc::new(tsfn),
+        })
+    }
+
+    /// Enqueues a message, blocking while the queue is full.
+    ///
+    /// Calling this from the JavaScript thread with a full, bounded queue would
+    /// deadlock; use [`ThreadsafeFunction::try_call`] instead.
+    pub fn call(&self, msg: T) -> Result<(), CallError<T>> {
+        self.call_with_mode(msg, ThreadsafeFunctionCallMode::Blocking)
+    }
+
+    /// Enqueues a message without blocking, failing if the queue is full.
+    pub fn try_call(&self, msg: T) -> Result<(), CallError<T>> {
+        self.call_with_mode(msg, ThreadsafeFunctionCallMode::NonBlocking)
+    }
+
+    fn call_with_mode(&self, msg: T, mode: ThreadsafeFunctionCallMode) -> Result<(), CallError<T>> {
+        self.tsfn.call(msg, mode).map_err(|err| CallError {
+            msg: err.data,
+            is_full: err.status == Status::QueueFull,
+        })
+    }
+
+    /// Closes the threadsafe function for every clone. Messages that have not been
+    /// delivered yet are dropped and later calls fail.
+    pub fn abort(&self) {
+        self.tsfn.abort();
+    }
+
+    /// Allow the Node event loop to exit while this `ThreadsafeFunction` exists.
+    /// _Idempotent_
+    pub fn unref<'a, C: Context<'a>>(&self, cx: &mut C) -> &Self {
+        unsafe { self.tsfn.unref(cx.env().to_raw()) };
+        self
+    }
+
+    /// Prevent the Node event loop from exiting while this `ThreadsafeFunction` exists. (Default)
+    /// _Idempotent_
+    pub fn reference<'a, C: Context<'a>>(&self, cx: &mut C) -> &Self {
+        unsafe { self.tsfn.reference(cx.env().to_raw()) };
+        self
+    }
+}
+
+impl<T> Clone for ThreadsafeFunction<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tsfn: Arc::clone(&self.tsfn),
+        }
+    }
+}
+
+/// Error indicating that a message could not be sent to a [`ThreadsafeFunction`].
+///
+/// The message can be recovered with [`CallError::into_inner`].
+pub struct CallError<T> {
+    msg: T,
+    is_full: bool,
+}
+
+impl<T> CallError<T> {
+    /// Indicates whether the call failed because the queue was full. Otherwise, the
+    /// threadsafe function has been aborted or the JavaScript environment is shutting
+    /// down.
+    pub fn is_full(&self) -> bool {
+        self.is_full
+    }
+
+    /// Returns the message that could not be sent.
+    pub fn into_inner(self) -> T {
+        self.msg
+    }
+}
+
+impl<T> fmt::Display for CallError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_full {
+            f.write_str("ThreadsafeFunction queue is full")
+        } else {
+            f.write_str("ThreadsafeFunction is closing")
+        }
+    }
+}
+
+impl<T> fmt::Debug for CallError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CallError")
+            .field("is_full", &self.is_full)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> error::Error for CallError<T> {}
@@ -0,0 +1,152 @@
+// Instrumentation of `Channel` and `TaskBuilder` with the `tracing` feature
+//
+// The names and fields of spans and events are documented in the `event` module and
+// should be treated as a stable interface. Without the feature, every function is an
+// empty stub and `Caller` is zero sized, unless the `rust-stack-frames` feature
+// needs its location.
+
+#[cfg(feature = "tracing")]
+pub(crate) use self::enabled::*;
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) use self::disabled::*;
+
+#[cfg(feature = "tracing")]
+mod enabled {
+    use std::{panic::Location, time::Instant};
+
+    use tracing::{field, span::EnteredSpan};
+
+    pub(crate) const TARGET: &str = "neon::event";
+
+    #[derive(Clone, Copy)]
+    // The location in user code that scheduled work
+    pub(crate) struct Caller(&'static Location<'static>);
+
+    impl Caller {
+        #[track_caller]
+        pub(crate) fn new() -> Self {
+            Self(Location::caller())
+        }
+
+        #[cfg(feature = "rust-stack-frames")]
+        pub(crate) fn location(self) -> &'static Location<'static> {
+            self.0
+        }
+    }
+
+    // An entered span that records its duration when it is dropped
+    pub(crate) struct Timed {
+        span: EnteredSpan,
+        start: Instant,
+    }
+
+    impl Drop for Timed {
+        fn drop(&mut self) {
+            let duration_us = self.start.elapsed().as_micros() as u64;
+
+            self.span.record("duration_us", duration_us);
+        }
+    }
+
+    fn timed(span: tracing::Span) -> Timed {
+        Timed {
+            span: span.entered(),
+            start: Instant::now(),
+        }
+    }
+
+    #[cfg(feature = "napi-4")]
+    pub(crate) fn channel_send(caller: Caller) {
+        tracing::event!(
+            name: "neon.channel.send",
+            target: TARGET,
+            tracing::Level::TRACE,
+            caller.file = caller.0.file(),
+            caller.line = caller.0.line(),
+            caller.column = caller.0.column(),
+            "neon.channel.send"
+        );
+    }
+
+    #[cfg(feature = "napi-4")]
+    pub(crate) fn channel_callback(caller: Caller) -> Timed {
+        timed(tracing::trace_span!(
+            target: TARGET,
+            "neon.channel.callback",
+            caller.file = caller.0.file(),
+            caller.line = caller.0.line(),
+            caller.column = caller.0.column(),
+            duration_us = field::Empty,
+        ))
+    }
+
+    pub(crate) fn task_execute(caller: Caller) -> Timed {
+        timed(tracing::trace_span!(
+            target: TARGET,
+            "neon.task.execute",
+            caller.file = caller.0.file(),
+            caller.line = caller.0.line(),
+            caller.column = caller.0.column(),
+            duration_us = field::Empty,
+        ))
+    }
+
+    pub(crate) fn task_complete(caller: Caller) -> Timed {
+        timed(tracing::trace_span!(
+            target: TARGET,
+            "neon.task.complete",
+            caller.file = caller.0.file(),
+            caller.line = caller.0.line(),
+            caller.column = caller.0.column(),
+            duration_us = field::Empty,
+        ))
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod disabled {
+    #[cfg(feature = "rust-stack-frames")]
+    use std::panic::Location;
+
+    #[derive(Clone, Copy)]
+    pub(crate) struct Caller(#[cfg(feature = "rust-stack-frames")] &'static Location<'static>);
+
+    impl Caller {
+        #[inline(always)]
+        #[cfg_attr(feature = "rust-stack-frames", track_caller)]
+        pub(crate) fn new() -> Self {
+            Self(
+                #[cfg(feature = "rust-stack-frames")]
+                Location::caller(),
+            )
+        }
+
+        #[cfg(feature = "rust-stack-frames")]
+        pub(crate) fn location(self) -> &'static Location<'static> {
+            self.0
+        }
+    }
+
+    pub(crate) struct Timed;
+
+    #[cfg(feature = "napi-4")]
+    #[inline(always)]
+    pub(crate) fn channel_send(_caller: Caller) {}
+
+    #[cfg(feature = "napi-4")]
+    #[inline(always)]
+    pub(crate) fn channel_callback(_caller: Caller) -> Timed {
+        Timed
+    }
+
+    #[inline(always)]
+    pub(crate) fn task_execute(_caller: Caller) -> Timed {
+        Timed
+    }
+
+    #[inline(always)]
+    pub(crate) fn task_complete(_caller: Caller) -> Timed {
+        Timed
+    }
+}
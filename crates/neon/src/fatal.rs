@@ -0,0 +1,65 @@
+use std::{
+    os::raw::c_char,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// A function called with the source location and message of a fatal error, as
+/// nul-terminated UTF-8 strings, immediately before the process is aborted.
+pub type FatalHook = extern "C" fn(location: *const c_char, message: *const c_char);
+
+/// Registers a hook that is called when Neon encounters an unrecoverable error and
+/// aborts the process with `napi_fatal_error`, e.g., when a promise cannot be
+/// resolved. This is useful for recording the error in telemetry or a log file
+/// before the process exits.
+///
+/// Only one hook may be registered. Returns `false`, without replacing the hook, if
+/// one has already been registered.
+///
+/// # Writing a hook
+///
+/// The hook is a C ABI function pointer, rather than a closure, because it may be
+/// called in states where unwinding or re-entering the JavaScript engine is undefined
+/// behavior. Panicking in the hook aborts the process immediately. The hook may be
+/// called from any thread and while locks inside Neon or Node.js are held; it should
+/// do as little as possible, e.g., a single `write` to an already open file, and
+/// must not call into JavaScript. Treat it with the same care as a signal handler.
+///
+/// ```
+/// use std::{ffi::CStr, io::Write, os::raw::c_char};
+///
+/// extern "C" fn log_fatal(location: *const c_char, message: *const c_char) {
+///     let (location, message) = unsafe { (CStr::from_ptr(location), CStr::from_ptr(message)) };
+///     let _ = writeln!(std::io::stderr(), "fatal: {:?} at {:?}", message, location);
+/// }
+///
+/// neon::set_fatal_hook(log_fatal);
+/// ```
+pub fn set_fatal_hook(hook: FatalHook) -> bool {
+    HOOK.compare_exchange(
+        ptr::null_mut(),
+        hook as *mut (),
+        Ordering::AcqRel,
+        Ordering::Acquire,
+    )
+    .is_ok()
+}
+
+// Calls the registered hook, if any. Both strings must be nul-terminated.
+pub(crate) fn call_hook(location: &[u8], message: &[u8]) {
+    debug_assert_eq!(location.last(), Some(&0));
+    debug_assert_eq!(message.last(), Some(&0));
+
+    let hook = HOOK.load(Ordering::Acquire);
+
+    if hook.is_null() {
+        return;
+    }
+
+    // Safety: `HOOK` is only ever set from a valid `FatalHook`
+    let hook = unsafe { std::mem::transmute::<*mut (), FatalHook>(hook) };
+
+    hook(location.as_ptr().cast(), message.as_ptr().cast());
+}
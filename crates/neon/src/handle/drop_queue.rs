@@ -0,0 +1,80 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static POLICY: AtomicU8 = AtomicU8::new(DropQueuePolicy::Leak as u8);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+/// Behavior of the global drop queue when a value cannot be released because its
+/// JavaScript environment is shutting down.
+///
+/// A [`Root`](super::Root), [`Deferred`](crate::types::Deferred), or
+/// [`AsyncResource`](crate::event::AsyncResource) that is dropped without a
+/// [`Context`](crate::context::Context) is released on the JavaScript thread that
+/// created it. Once the environment begins tearing down, e.g., when a worker thread is
+/// terminated, the value can no longer be released. The underlying JavaScript value is
+/// destroyed along with the environment, so the reference is leaked instead.
+///
+/// Values stored in a [`LocalKey`](crate::thread::LocalKey) are released when the
+/// environment begins tearing down, so they are never reported as leaked.
+///
+/// The policy applies to every environment in the process and may be changed with
+/// [`set_drop_queue_policy`].
+pub enum DropQueuePolicy {
+    /// Leak the reference without reporting it. This is the default.
+    Leak = 0,
+    /// Leak the reference and print a warning to stderr.
+    Log = 1,
+    /// Abort the process with a fatal error. Useful for detecting values that outlive
+    /// their environment in tests.
+    FatalError = 2,
+}
+
+/// Sets the behavior of the global drop queue when a value is dropped while its
+/// JavaScript environment is shutting down.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::handle::{set_drop_queue_policy, DropQueuePolicy};
+///
+/// #[neon::main]
+/// fn main(mut cx: ModuleContext) -> NeonResult<()> {
+///     if cfg!(debug_assertions) {
+///         set_drop_queue_policy(DropQueuePolicy::Log);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub fn set_drop_queue_policy(policy: DropQueuePolicy) {
+    POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+fn policy() -> DropQueuePolicy {
+    match POLICY.load(Ordering::Relaxed) {
+        1 => DropQueuePolicy::Log,
+        2 => DropQueuePolicy::FatalError,
+        _ => DropQueuePolicy::Leak,
+    }
+}
+
+// Applies the policy to a value of type `name` that could not be released. May be
+// called from any thread.
+pub(crate) fn leaked(name: &str) {
+    match policy() {
+        DropQueuePolicy::Leak => {}
+        DropQueuePolicy::Log => {
+            eprintln!(
+                "Warning: {} leaked because the JavaScript environment is shutting down",
+                name
+            );
+        }
+        DropQueuePolicy::FatalError => {
+            let message = format!(
+                "{} dropped while the JavaScript environment is shutting down",
+                name
+            );
+
+            unsafe { crate::sys::error::fatal_error(&message) }
+        }
+    }
+}
@@ -48,7 +48,10 @@
 
 pub(crate) mod internal;
 
+#[cfg(feature = "napi-6")]
+pub(crate) mod drop_queue;
 pub(crate) mod root;
+pub(crate) mod root_set;
 
 use std::{
     error::Error,
@@ -58,14 +61,25 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-pub use self::root::Root;
+pub use self::{
+    root::Root,
+    root_set::{RootKey, RootSet},
+};
+
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub use self::{
+    drop_queue::{set_drop_queue_policy, DropQueuePolicy},
+    root::GuardedRoot,
+};
 
 use crate::{
     context::{internal::Env, Context},
     handle::internal::{SuperType, TransparentNoCopyWrapper},
-    result::{JsResult, ResultExt},
+    object::Object,
+    result::{JsResult, NeonResult, ResultExt},
     sys::{self, raw},
-    types::Value,
+    types::{JsFunction, JsObject, JsString, JsValue, Value, ValueVisitor},
 };
 
 /// The trait of data owned by the JavaScript engine and that can only be accessed via handles.
@@ -185,10 +199,47 @@ impl<'a, T: Value> Handle<'a, T> {
     }
 
     /// Attempts to downcast a handle to another type, raising a JavaScript `TypeError`
-    /// exception on failure. This method is a convenient shorthand, equivalent to
-    /// `self.downcast::<U>().or_throw::<C>(cx)`.
+    /// exception on failure.
+    ///
+    /// The message names the expected type and describes the actual value, including
+    /// the name of its constructor if it is an object, e.g.,
+    /// `expected function, got object (instance of Map)`.
     pub fn downcast_or_throw<'b, U: Value, C: Context<'b>>(&self, cx: &mut C) -> JsResult<'a, U> {
-        self.downcast(cx).or_throw(cx)
+        self.downcast_or_throw_at(cx, None, None)
+    }
+
+    // Like `downcast_or_throw`, but names the function and the position of the argument
+    // in the message
+    pub(crate) fn downcast_or_throw_at<'b, U: Value, C: Context<'b>>(
+        &self,
+        cx: &mut C,
+        function: Option<&str>,
+        argument: Option<usize>,
+    ) -> JsResult<'a, U> {
+        if let Ok(v) = self.downcast(cx) {
+            return Ok(v);
+        }
+
+        let actual = describe_value(cx, self.upcast());
+        let msg = match argument {
+            Some(i) => format!("expected {} at argument {}, got {}", U::name(), i, actual),
+            None => format!("expected {}, got {}", U::name(), actual),
+        };
+
+        cx.throw_type_error(with_function_name(function, msg))
+    }
+
+    /// Calls the method of `visitor` for the type of this value.
+    ///
+    /// This checks the type once, instead of once per type as a chain of
+    /// [`Handle::is_a`] or [`Handle::downcast`] calls does; see [`ValueVisitor`] for an
+    /// example.
+    pub fn visit<C: Context<'a>, V: ValueVisitor<'a>>(
+        &self,
+        cx: &mut C,
+        visitor: &mut V,
+    ) -> NeonResult<V::Output> {
+        crate::types_impl::visitor::visit(cx, self.to_raw(), visitor)
     }
 
     pub fn strict_equals<'b, U: Value, C: Context<'b>>(
@@ -200,6 +251,47 @@ impl<'a, T: Value> Handle<'a, T> {
     }
 }
 
+// Prefixes an error message with the name of the function that raised it, if known
+pub(crate) fn with_function_name(function: Option<&str>, msg: String) -> String {
+    match function {
+        Some(name) if !name.is_empty() => format!("{}: {}", name, msg),
+        _ => msg,
+    }
+}
+
+// Describes a value for an error message: its `typeof`, and for objects, the name of
+// its constructor if one can be found without throwing
+pub(crate) fn describe_value<'a, C: Context<'a>>(cx: &mut C, v: Handle<JsValue>) -> String {
+    let type_name = unsafe { sys::tag::type_name(cx.env().to_raw(), v.to_raw()) };
+
+    let obj = match v.downcast::<JsObject, _>(cx) {
+        Ok(obj) if type_name == "object" => obj,
+        _ => return type_name.to_string(),
+    };
+
+    let constructor = cx.try_catch(|cx| {
+        let constructor = obj.get_value(cx, "constructor")?;
+
+        match constructor.downcast::<JsFunction, _>(cx) {
+            Ok(f) => Ok(f.get_value(cx, "name")?.downcast::<JsString, _>(cx).ok()),
+            Err(_) => Ok(None),
+        }
+    });
+
+    match constructor {
+        Ok(Some(name)) => {
+            let name = name.value(cx);
+
+            if name.is_empty() {
+                type_name.to_string()
+            } else {
+                format!("{} (instance of {})", type_name, name)
+            }
+        }
+        _ => type_name.to_string(),
+    }
+}
+
 impl<'a, T: Managed> Deref for Handle<'a, T> {
     type Target = T;
     fn deref(&self) -> &T {
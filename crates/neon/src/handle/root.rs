@@ -11,8 +11,9 @@ use crate::{
 #[cfg(feature = "napi-6")]
 use {
     crate::{
-        lifecycle::{DropData, InstanceData, InstanceId},
-        sys::tsfn::ThreadsafeFunction,
+        context::{internal::ContextInternal, TaskContext},
+        lifecycle::{DropData, DropQueue, InstanceData, InstanceId},
+        result::NeonResult,
     },
     std::sync::Arc,
 };
@@ -55,7 +56,7 @@ pub struct Root<T> {
     internal: Option<NapiRef>,
     instance_id: InstanceId,
     #[cfg(feature = "napi-6")]
-    drop_queue: Arc<ThreadsafeFunction<DropData>>,
+    drop_queue: Arc<DropQueue>,
     _phantom: PhantomData<T>,
 }
 
@@ -140,7 +141,7 @@ impl<T: Object> Root<T> {
         let env = cx.env().to_raw();
 
         unsafe {
-            self.into_napi_ref(cx).unref(env);
+            self.into_internal(cx).unref(env);
         }
     }
 
@@ -152,7 +153,7 @@ impl<T: Object> Root<T> {
     /// one in which the handle was created.
     pub fn into_inner<'a, C: Context<'a>>(self, cx: &mut C) -> Handle<'a, T> {
         let env = cx.env();
-        let internal = self.into_napi_ref(cx);
+        let internal = self.into_internal(cx);
         let local = unsafe { reference::get(env.to_raw(), internal.0.cast()) };
 
         unsafe {
@@ -177,6 +178,72 @@ impl<T: Object> Root<T> {
         Handle::new_internal(T::from_raw(env, local))
     }
 
+    /// Consumes the `Root`, returning the underlying `napi_ref` for use with another
+    /// Node-API library.
+    ///
+    /// Clones of a `Root` share a single `napi_ref`, with one count of the reference per
+    /// clone, so the returned pointer may be equal to a pointer returned from a clone of
+    /// the same `Root`. Ownership of one count is transferred to the caller, who is
+    /// responsible for eventually releasing it, either by converting it back with
+    /// [`Root::from_napi_ref`] or by calling `napi_reference_unref`. Only when
+    /// `napi_reference_unref` returns a count of `0` may the caller call
+    /// `napi_delete_reference`; deleting it earlier invalidates the reference for every
+    /// other owner.
+    ///
+    /// The returned count is not tracked by Neon and is never released by the global
+    /// drop queue; if it is never released, the object is leaked.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is called from a different JavaScript thread than the
+    /// one in which the handle was created.
+    pub fn into_napi_ref<'a, C: Context<'a>>(self, cx: &mut C) -> *mut c_void {
+        self.into_internal(cx).0
+    }
+
+    /// Creates a `Root` from a `napi_ref` that was returned by [`Root::into_napi_ref`]
+    /// or created by another Node-API library.
+    ///
+    /// The `Root` takes ownership of one count of the reference and releases it when it
+    /// is consumed or dropped, as if it had been created with [`Root::new`].
+    ///
+    /// # Safety
+    ///
+    /// * `raw` must be a valid `napi_ref` created in the same JavaScript environment as
+    ///   `cx`, with a reference count of at least one that is owned by the caller.
+    /// * The referenced value must be an object of type `T`.
+    /// * Ownership of that count is transferred; it must not be released elsewhere.
+    ///   For example, calling `from_napi_ref` twice with a pointer from a single call to
+    ///   `into_napi_ref` releases the reference twice, which is undefined behavior.
+    pub unsafe fn from_napi_ref<'a, C: Context<'a>>(cx: &mut C, raw: *mut c_void) -> Self {
+        Self {
+            internal: Some(NapiRef(raw)),
+            instance_id: instance_id(cx),
+            #[cfg(feature = "napi-6")]
+            drop_queue: InstanceData::drop_queue(cx),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Attaches a cancellation closure that is called if the `Root` is dropped
+    /// without being consumed, e.g., a callback that was registered but never called
+    /// because the closure holding it was discarded.
+    ///
+    /// See [`GuardedRoot`] for details.
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+    pub fn on_unconsumed<F>(self, cancel: F) -> GuardedRoot<T>
+    where
+        F: for<'cx> FnOnce(&mut TaskContext<'cx>, Handle<'cx, T>) -> NeonResult<()>
+            + Send
+            + 'static,
+    {
+        GuardedRoot {
+            root: Some(self),
+            cancel: Some(Box::new(cancel)),
+        }
+    }
+
     fn as_napi_ref<'a, C: Context<'a>>(&self, cx: &mut C) -> &NapiRef {
         if self.instance_id != instance_id(cx) {
             panic!("Attempted to dereference a `neon::handle::Root` from the wrong module ");
@@ -189,7 +256,7 @@ impl<T: Object> Root<T> {
             .unwrap()
     }
 
-    fn into_napi_ref<'a, C: Context<'a>>(mut self, cx: &mut C) -> NapiRef {
+    fn into_internal<'a, C: Context<'a>>(mut self, cx: &mut C) -> NapiRef {
         let reference = self.as_napi_ref(cx).clone();
         // This uses `as_napi_ref` instead of `Option::take` for the instance id safety check
         self.internal = None;
@@ -230,7 +297,163 @@ impl<T> Drop for Root<T> {
     fn drop(&mut self) {
         // If `None`, the `NapiRef` has already been manually dropped
         if let Some(internal) = self.internal.take() {
-            let _ = self.drop_queue.call(DropData::Ref(internal), None);
+            self.drop_queue.push(DropData::Ref(internal));
         }
     }
 }
+
+#[cfg(feature = "napi-6")]
+type Cancel<T> =
+    Box<dyn for<'cx> FnOnce(&mut TaskContext<'cx>, Handle<'cx, T>) -> NeonResult<()> + Send>;
+
+/// A [`Root`] that calls a cancellation closure if it is dropped without being
+/// consumed. Created with [`Root::on_unconsumed`].
+///
+/// This helps uphold an invariant like "every registered callback is eventually
+/// called or explicitly cancelled", even when the Rust value holding the callback is
+/// discarded, e.g., a closure queued on a [`Channel`](crate::event::Channel) that
+/// never runs.
+///
+/// The root is _consumed_ by [`GuardedRoot::into_inner`], [`GuardedRoot::cancel`] or
+/// [`GuardedRoot::into_root`]. If it is instead dropped, the cancellation is queued
+/// on the global drop queue and runs on the JavaScript thread that created the root,
+/// with the referenced object. If the cancellation throws, the exception is reported
+/// as an uncaught exception.
+///
+/// If the JavaScript environment is shutting down, JavaScript can no longer be called
+/// and the cancellation is leaked without running, following the
+/// [`DropQueuePolicy`](crate::handle::DropQueuePolicy).
+///
+/// ```
+/// # use neon::prelude::*;
+/// fn on_ready(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let callback = cx
+///         .argument::<JsFunction>(0)?
+///         .root(&mut cx)
+///         .on_unconsumed(|cx, callback| {
+///             let err = cx.error("aborted")?;
+///
+///             callback.call1(cx, err)?;
+///
+///             Ok(())
+///         });
+///
+///     let channel = cx.channel();
+///
+///     std::thread::spawn(move || {
+///         // If this closure is dropped without running, the callback is called
+///         // with an "aborted" error instead
+///         channel.send(move |mut cx| {
+///             let callback = callback.into_inner(&mut cx);
+///
+///             callback.call0(&mut cx)?;
+///
+///             Ok(())
+///         });
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// ```
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub struct GuardedRoot<T: Object> {
+    // Both are `Some` until the `GuardedRoot` is consumed
+    root: Option<Root<T>>,
+    cancel: Option<Cancel<T>>,
+}
+
+#[cfg(feature = "napi-6")]
+impl<T: Object> std::fmt::Debug for GuardedRoot<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GuardedRoot<{}>", std::any::type_name::<T>())
+    }
+}
+
+#[cfg(feature = "napi-6")]
+impl<T: Object> GuardedRoot<T> {
+    /// Return the referenced JavaScript object without calling the cancellation.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is called from a different JavaScript thread than the
+    /// one in which the handle was created.
+    pub fn into_inner<'a, C: Context<'a>>(mut self, cx: &mut C) -> Handle<'a, T> {
+        self.disarm().into_inner(cx)
+    }
+
+    /// Access the inner JavaScript object without consuming the `GuardedRoot`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is called from a different JavaScript thread than the
+    /// one in which the handle was created.
+    pub fn to_inner<'a, C: Context<'a>>(&self, cx: &mut C) -> Handle<'a, T> {
+        self.root().to_inner(cx)
+    }
+
+    /// Removes the cancellation, returning the plain [`Root`].
+    pub fn into_root(mut self) -> Root<T> {
+        self.disarm()
+    }
+
+    /// Immediately calls the cancellation with the referenced object and releases the
+    /// root.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is called from a different JavaScript thread than the
+    /// one in which the handle was created.
+    pub fn cancel<'a, C: Context<'a>>(mut self, cx: &mut C) -> NeonResult<()> {
+        // `unwrap` will not panic because `cancel` is `Some` until consumed
+        let cancel = self.cancel.take().unwrap();
+        let value = self.disarm().into_inner(cx).to_raw();
+
+        TaskContext::with_context(cx.env(), move |mut cx| {
+            let value = Handle::new_internal(T::from_raw(cx.env(), value));
+
+            cancel(&mut cx, value)
+        })
+    }
+
+    fn root(&self) -> &Root<T> {
+        // `unwrap` will not panic because `root` is `Some` until consumed
+        self.root.as_ref().unwrap()
+    }
+
+    fn disarm(&mut self) -> Root<T> {
+        self.cancel = None;
+        self.root.take().unwrap()
+    }
+}
+
+#[cfg(feature = "napi-6")]
+impl<T: Object> Drop for GuardedRoot<T> {
+    fn drop(&mut self) {
+        let (mut root, cancel) = match (self.root.take(), self.cancel.take()) {
+            (Some(root), Some(cancel)) => (root, cancel),
+            _ => return,
+        };
+
+        // Take the reference so that dropping `root` does not also queue it. If the
+        // cancellation is leaked, the reference is leaked with it.
+        let internal = match root.internal.take() {
+            Some(internal) => internal,
+            None => return,
+        };
+
+        root.drop_queue
+            .push(DropData::Cancel(Box::new(move |env| unsafe {
+                let local = reference::get(env, internal.0.cast());
+
+                internal.unref(env);
+
+                TaskContext::with_context(env.into(), move |mut cx| {
+                    let value = Handle::new_internal(T::from_raw(cx.env(), local));
+
+                    // A thrown exception is left pending and reported as uncaught
+                    let _ = cancel(&mut cx, value);
+                });
+            })));
+    }
+}
@@ -0,0 +1,204 @@
+use std::convert::TryFrom;
+
+use crate::{
+    context::Context,
+    handle::{Handle, Root},
+    object::Object,
+    result::NeonResult,
+    types::{boxed::Finalize, JsArray, JsValue, Value},
+};
+
+/// A key identifying a value in a [`RootSet`].
+///
+/// Keys remain valid when other values are removed from the set. Once the value
+/// is removed, its key never refers to another value, even if the space is reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RootKey {
+    index: u32,
+    generation: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Slot {
+    generation: u32,
+    occupied: bool,
+}
+
+/// A collection of JavaScript values that are kept alive by a single persistent
+/// reference.
+///
+/// Each [`Root`] creates a persistent reference that the garbage collector must
+/// visit. When retaining a large number of values, e.g., a cache with one object
+/// per entry, a `RootSet` stores the values in a single JavaScript array referenced
+/// by one `Root`, and identifies them with a [`RootKey`].
+///
+/// Like `Root`, a `RootSet` may be sent across threads, but the values may only be
+/// accessed on the JavaScript thread that created it.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::handle::RootSet;
+///
+/// fn first_of_many(mut cx: FunctionContext) -> JsResult<JsValue> {
+///     let mut set = RootSet::new(&mut cx);
+///     let mut keys = Vec::new();
+///
+///     for i in 0..1000 {
+///         let obj = cx.empty_object();
+///         let n = cx.number(i);
+///
+///         obj.set(&mut cx, "n", n)?;
+///         keys.push(set.insert(&mut cx, obj)?);
+///     }
+///
+///     let first = set.get(&mut cx, keys[0])?;
+///
+///     set.drop(&mut cx);
+///
+///     Ok(first.unwrap_or_else(|| cx.undefined().upcast()))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RootSet {
+    values: Root<JsArray>,
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl RootSet {
+    /// Creates an empty `RootSet`.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C) -> Self {
+        let values = cx.empty_array().root(cx);
+
+        Self {
+            values,
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Adds a value to the set, returning the key used to access it.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is called from a different JavaScript thread than the
+    /// one in which the set was created, or if the set holds more than `u32::MAX`
+    /// values.
+    pub fn insert<'a, C: Context<'a>, V: Value>(
+        &mut self,
+        cx: &mut C,
+        value: Handle<V>,
+    ) -> NeonResult<RootKey> {
+        let index = match self.free.last() {
+            Some(&index) => index,
+            None => u32::try_from(self.slots.len()).expect("RootSet is full"),
+        };
+
+        self.values.to_inner(cx).set(cx, index, value)?;
+
+        let slot = match self.free.pop() {
+            Some(_) => &mut self.slots[index as usize],
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    occupied: false,
+                });
+
+                self.slots.last_mut().unwrap()
+            }
+        };
+
+        slot.occupied = true;
+        self.len += 1;
+
+        Ok(RootKey {
+            index,
+            generation: slot.generation,
+        })
+    }
+
+    /// Returns the value for `key`, or `None` if it was removed.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is called from a different JavaScript thread than the
+    /// one in which the set was created.
+    pub fn get<'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        key: RootKey,
+    ) -> NeonResult<Option<Handle<'a, JsValue>>> {
+        if !self.contains(key) {
+            return Ok(None);
+        }
+
+        self.values.to_inner(cx).get_value(cx, key.index).map(Some)
+    }
+
+    /// Removes the value for `key` from the set, returning it, or `None` if it was
+    /// already removed.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is called from a different JavaScript thread than the
+    /// one in which the set was created.
+    pub fn remove<'a, C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        key: RootKey,
+    ) -> NeonResult<Option<Handle<'a, JsValue>>> {
+        if !self.contains(key) {
+            return Ok(None);
+        }
+
+        let values = self.values.to_inner(cx);
+        let value = values.get_value(cx, key.index)?;
+        let undefined = cx.undefined();
+
+        // Overwrite instead of deleting to keep the array dense
+        values.set(cx, key.index, undefined)?;
+
+        let slot = &mut self.slots[key.index as usize];
+
+        slot.occupied = false;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(key.index);
+        self.len -= 1;
+
+        Ok(Some(value))
+    }
+
+    /// Returns `true` if the set holds a value for `key`.
+    pub fn contains(&self, key: RootKey) -> bool {
+        self.slots
+            .get(key.index as usize)
+            .map(|slot| slot.occupied && slot.generation == key.generation)
+            .unwrap_or(false)
+    }
+
+    /// Returns the number of values in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the set holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Releases every value in the set, deleting its single persistent reference.
+    ///
+    /// As with [`Root::drop`], the caller _should_ release the set with this
+    /// method rather than letting it go out of scope.
+    pub fn drop<'a, C: Context<'a>>(self, cx: &mut C) {
+        self.values.drop(cx);
+    }
+}
+
+impl Finalize for RootSet {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        self.drop(cx);
+    }
+}
@@ -0,0 +1,253 @@
+//! Cached references to JavaScript built-ins.
+//!
+//! Looking up a built-in such as `Object.freeze` requires a property lookup on the
+//! global object for each segment of the path. [`Context::intrinsics`] provides an
+//! [`Intrinsics`] cache, unique to each JavaScript environment, that performs each
+//! lookup once and keeps a persistent reference to the result.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! fn frozen(mut cx: FunctionContext) -> JsResult<JsObject> {
+//!     let obj = cx.empty_object();
+//!     let freeze = cx.intrinsics().object_freeze(&mut cx)?;
+//!
+//!     freeze.call_with(&cx).arg(obj).exec(&mut cx)?;
+//!
+//!     Ok(obj)
+//! }
+//! ```
+//!
+//! Built-ins are looked up lazily, the first time they are accessed in an
+//! environment. Later changes to the global object, e.g., replacing
+//! `globalThis.Promise`, are not observed by the cache. References are released
+//! as soon as the environment begins tearing down, e.g., when a worker thread exits,
+//! and since each worker thread has its own cache, references are never shared
+//! across environments.
+
+use crate::{
+    context::Context,
+    object::Object,
+    result::JsResult,
+    types::{JsFunction, JsObject, JsValue, Value},
+};
+
+#[cfg(feature = "napi-6")]
+use {
+    crate::{handle::Root, thread::LocalKey},
+    once_cell::sync::OnceCell,
+};
+
+// Looks up a value by walking a path of properties, starting from the global object
+fn lookup<'a, C: Context<'a>, V: Value>(cx: &mut C, path: &[&str]) -> JsResult<'a, V> {
+    let mut value = cx.global().upcast::<JsValue>();
+
+    for key in path {
+        value = value.coerce_to_object(cx)?.get_value(cx, *key)?;
+    }
+
+    value.downcast_or_throw(cx)
+}
+
+// Returns the cached value of `cell`, initializing it with `init`
+#[cfg(feature = "napi-6")]
+fn cached<'a, C, V, F>(cx: &mut C, cell: &OnceCell<Root<V>>, init: F) -> JsResult<'a, V>
+where
+    C: Context<'a>,
+    V: Object,
+    F: FnOnce(&mut C) -> JsResult<'a, V>,
+{
+    if let Some(value) = cell.get() {
+        return Ok(value.to_inner(cx));
+    }
+
+    let value = init(cx)?;
+
+    // The initializer may have re-entrantly initialized the cell; keep the first
+    let _ = cell.set(value.root(cx));
+
+    Ok(value)
+}
+
+macro_rules! intrinsics {
+    (
+        functions { $($(#[$fattr:meta])* $fname:ident = [$($fkey:literal),+];)* }
+        objects { $($(#[$oattr:meta])* $oname:ident = [$($okey:literal),+];)* }
+        symbols { $($(#[$sattr:meta])* $sname:ident = [$($skey:literal),+];)* }
+        loaded { $($(#[$lattr:meta])* $lname:ident: $lty:ty = $load:path;)* }
+    ) => {
+        #[cfg(feature = "napi-6")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+        /// Persistent references to JavaScript built-ins for a single environment.
+        ///
+        /// Obtained with [`Context::intrinsics`].
+        #[derive(Default)]
+        pub struct Intrinsics {
+            $($fname: OnceCell<Root<JsFunction>>,)*
+            $($oname: OnceCell<Root<JsObject>>,)*
+            // Symbols are primitives that cannot be referenced directly; each is kept
+            // in a single element array
+            $($sname: OnceCell<Root<JsObject>>,)*
+            $($lname: OnceCell<Root<$lty>>,)*
+        }
+
+        #[cfg(feature = "napi-6")]
+        impl Intrinsics {
+            pub(crate) fn get<'a, C: Context<'a>>(cx: &mut C) -> &'a Self {
+                static INTRINSICS: LocalKey<Intrinsics> = LocalKey::new();
+
+                INTRINSICS.get_or_init_default(cx)
+            }
+
+            $(
+                $(#[$fattr])*
+                pub fn $fname<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsFunction> {
+                    cached(cx, &self.$fname, |cx| lookup(cx, &[$($fkey),+]))
+                }
+            )*
+
+            $(
+                $(#[$oattr])*
+                pub fn $oname<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsObject> {
+                    cached(cx, &self.$oname, |cx| lookup(cx, &[$($okey),+]))
+                }
+            )*
+
+            $(
+                $(#[$sattr])*
+                pub fn $sname<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsValue> {
+                    let holder = cached(cx, &self.$sname, |cx| {
+                        let symbol = lookup::<_, JsValue>(cx, &[$($skey),+])?;
+                        let holder = cx.empty_array();
+
+                        holder.set(cx, 0, symbol)?;
+
+                        Ok(holder.upcast())
+                    })?;
+
+                    holder.get_value(cx, 0)
+                }
+            )*
+
+            $(
+                $(#[$lattr])*
+                pub(crate) fn $lname<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, $lty> {
+                    cached(cx, &self.$lname, $load)
+                }
+            )*
+        }
+
+        // Crate internal accessors that use the cache when it is available
+        $(
+            #[allow(dead_code)]
+            pub(crate) fn $fname<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsFunction> {
+                #[cfg(feature = "napi-6")]
+                return Intrinsics::get(cx).$fname(cx);
+
+                #[cfg(not(feature = "napi-6"))]
+                lookup(cx, &[$($fkey),+])
+            }
+        )*
+
+        $(
+            #[allow(dead_code)]
+            pub(crate) fn $oname<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsObject> {
+                #[cfg(feature = "napi-6")]
+                return Intrinsics::get(cx).$oname(cx);
+
+                #[cfg(not(feature = "napi-6"))]
+                lookup(cx, &[$($okey),+])
+            }
+        )*
+
+        $(
+            #[allow(dead_code)]
+            pub(crate) fn $sname<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsValue> {
+                #[cfg(feature = "napi-6")]
+                return Intrinsics::get(cx).$sname(cx);
+
+                #[cfg(not(feature = "napi-6"))]
+                lookup(cx, &[$($skey),+])
+            }
+        )*
+
+        $(
+            #[allow(dead_code)]
+            pub(crate) fn $lname<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, $lty> {
+                #[cfg(feature = "napi-6")]
+                return Intrinsics::get(cx).$lname(cx);
+
+                #[cfg(not(feature = "napi-6"))]
+                $load(cx)
+            }
+        )*
+    };
+}
+
+intrinsics! {
+    functions {
+        /// `Object`
+        object = ["Object"];
+        /// `Object.keys`
+        object_keys = ["Object", "keys"];
+        /// `Error`
+        error = ["Error"];
+        /// `Error.captureStackTrace`
+        error_capture_stack_trace = ["Error", "captureStackTrace"];
+        /// `Object.freeze`
+        object_freeze = ["Object", "freeze"];
+        /// `Object.defineProperty`
+        object_define_property = ["Object", "defineProperty"];
+        /// `Function.prototype.bind`
+        function_bind = ["Function", "prototype", "bind"];
+        /// `Array`
+        array = ["Array"];
+        /// `Promise`
+        promise = ["Promise"];
+        /// `Proxy`
+        proxy = ["Proxy"];
+        /// `Symbol.for`
+        symbol_for = ["Symbol", "for"];
+        /// `URL`
+        url = ["URL"];
+        /// `Atomics.notify`
+        atomics_notify = ["Atomics", "notify"];
+        /// `JSON.parse`
+        json_parse = ["JSON", "parse"];
+        /// `JSON.stringify`
+        json_stringify = ["JSON", "stringify"];
+        /// `String.prototype.concat`
+        string_concat = ["String", "prototype", "concat"];
+    }
+    objects {
+        /// `console`
+        console = ["console"];
+        /// `process`
+        process = ["process"];
+        /// `performance`
+        performance = ["performance"];
+    }
+    symbols {
+        /// `Symbol.iterator`
+        symbol_iterator = ["Symbol", "iterator"];
+        /// `Symbol.asyncIterator`
+        symbol_async_iterator = ["Symbol", "asyncIterator"];
+    }
+    loaded {
+        /// The `v8` module
+        v8_module: JsObject = load_v8_module;
+        /// `util.inspect`
+        util_inspect: JsFunction = load_util_inspect;
+        /// A `require` function that resolves modules relative to the addon
+        require_function: JsFunction = crate::context::create_require;
+    }
+}
+
+fn load_v8_module<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsObject> {
+    crate::reflect::builtin_module(cx, "v8")
+}
+
+fn load_util_inspect<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsFunction> {
+    let util = crate::reflect::builtin_module::<JsObject, _>(cx, "util")?;
+
+    util.get(cx, "inspect")
+}
@@ -0,0 +1,103 @@
+//! Parsing and serializing JSON with the JavaScript
+//! [`JSON`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/JSON)
+//! object.
+//!
+//! Passing large values across the boundary as JSON strings is often faster than
+//! building up an object graph one property at a time:
+//!
+//! ```
+//! # use neon::prelude::*;
+//! fn default_config(mut cx: FunctionContext) -> JsResult<JsValue> {
+//!     let config = r#"{ "retries": 3, "verbose": false }"#;
+//!
+//!     neon::json::parse(&mut cx, config)
+//! }
+//! ```
+//!
+//! Exceptions thrown by `JSON.parse` and `JSON.stringify` (e.g., for invalid syntax or
+//! circular structures) are propagated as a [`Throw`](crate::result::Throw).
+//!
+//! When using N-API >= 6, the `JSON.parse` and `JSON.stringify` functions are looked up
+//! once per module instance and cached in its [`Intrinsics`](crate::intrinsics::Intrinsics).
+
+use crate::{
+    context::Context,
+    handle::Handle,
+    intrinsics,
+    result::{JsResult, NeonResult},
+    types::{JsString, JsValue, Value, ValueVisitor},
+};
+
+/// Parses a JSON string into a JavaScript value, equivalent to `JSON.parse(s)`.
+///
+/// Throws a `SyntaxError` if `s` is not valid JSON.
+pub fn parse<'cx, C: Context<'cx>>(cx: &mut C, s: &str) -> JsResult<'cx, JsValue> {
+    let s = cx.string(s);
+
+    intrinsics::json_parse(cx)?.call_with(cx).arg(s).apply(cx)
+}
+
+/// Serializes a JavaScript value as a JSON string, equivalent to `JSON.stringify(value)`.
+///
+/// Throws a `TypeError` if the value cannot be serialized, e.g., because it contains
+/// a circular reference or it is `undefined`.
+pub fn stringify<'cx, C: Context<'cx>, V: Value>(
+    cx: &mut C,
+    value: Handle<V>,
+) -> NeonResult<String> {
+    let value = value.upcast();
+
+    stringify_internal(cx, &[value])
+}
+
+/// Serializes a JavaScript value as a JSON string indented by `indent` spaces per level,
+/// equivalent to `JSON.stringify(value, null, indent)`.
+///
+/// Throws a `TypeError` if the value cannot be serialized.
+pub fn stringify_pretty<'cx, C: Context<'cx>, V: Value>(
+    cx: &mut C,
+    value: Handle<V>,
+    indent: u32,
+) -> NeonResult<String> {
+    let value = value.upcast();
+    let replacer = cx.null().upcast();
+    let indent = cx.number(indent).upcast();
+
+    stringify_internal(cx, &[value, replacer, indent])
+}
+
+fn stringify_internal<'cx, C: Context<'cx>>(
+    cx: &mut C,
+    args: &[Handle<JsValue>],
+) -> NeonResult<String> {
+    let this = cx.undefined();
+    let json = intrinsics::json_stringify(cx)?.call(cx, this, args)?;
+
+    match json.visit(cx, &mut JsonString)? {
+        Some(json) => Ok(json),
+        None => cx.throw_type_error("value cannot be serialized as JSON"),
+    }
+}
+
+// `JSON.stringify` returns `undefined` for values that cannot be serialized
+struct JsonString;
+
+impl<'cx> ValueVisitor<'cx> for JsonString {
+    type Output = Option<String>;
+
+    fn visit_string<C: Context<'cx>>(
+        &mut self,
+        cx: &mut C,
+        s: Handle<'cx, JsString>,
+    ) -> NeonResult<Self::Output> {
+        Ok(Some(s.value(cx)))
+    }
+
+    fn visit_other<C: Context<'cx>>(
+        &mut self,
+        _cx: &mut C,
+        _value: Handle<'cx, JsValue>,
+    ) -> NeonResult<Self::Output> {
+        Ok(None)
+    }
+}
@@ -78,15 +78,33 @@
 //! [supported]: https://github.com/neon-bindings/neon#platform-support
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+pub mod console;
 pub mod context;
+#[cfg(feature = "napi-4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-4")))]
+pub mod diagnostics;
+pub mod error;
 pub mod event;
+mod fatal;
 pub mod handle;
+pub mod intrinsics;
+pub mod json;
 pub mod meta;
 pub mod object;
+pub mod performance;
 pub mod prelude;
 pub mod reflect;
+pub mod registry;
 pub mod result;
+pub mod runtime;
+pub mod schema;
+#[cfg(feature = "napi-5")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+pub mod stream;
 mod sys;
+#[cfg(all(feature = "test-support", feature = "napi-6"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "test-support", feature = "napi-6"))))]
+pub mod test;
 #[cfg(feature = "napi-6")]
 pub mod thread;
 // To use the #[aquamarine] attribute on the top-level neon::types module docs, we have to
@@ -94,9 +112,14 @@ pub mod thread;
 // See: https://github.com/mersinvald/aquamarine/issues/5#issuecomment-1168816499
 mod types_docs;
 mod types_impl;
+#[cfg(feature = "napi-5")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+pub mod worker;
 
 pub use types_docs::exports as types;
 
+pub use fatal::{set_fatal_hook, FatalHook};
+
 #[doc(hidden)]
 pub mod macro_internal;
 
@@ -10,17 +10,19 @@
 
 use std::{
     any::Any,
+    cell::Cell,
     marker::PhantomData,
+    ptr,
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
 };
 
 use crate::{
     context::Context,
-    event::Channel,
-    handle::root::NapiRef,
+    event::{Channel, NapiAsyncContext},
+    handle::{drop_queue, root::NapiRef},
     sys::{lifecycle, raw::Env, tsfn::ThreadsafeFunction},
     types::promise::NodeApiDeferred,
 };
@@ -39,6 +41,10 @@ impl InstanceId {
 
         Self(NEXT_ID.fetch_add(1, Ordering::SeqCst))
     }
+
+    pub(crate) fn as_u64(self) -> u64 {
+        self.0
+    }
 }
 
 /// `InstanceData` holds Neon data associated with a particular instance of a
@@ -54,10 +60,11 @@ pub(crate) struct InstanceData {
     /// could be replaced with a leaked `&'static ThreadsafeFunction<NapiRef>`. However,
     /// given the cost of FFI, this optimization is omitted until the cost of an
     /// `Arc` is demonstrated as significant.
-    drop_queue: Arc<ThreadsafeFunction<DropData>>,
+    drop_queue: Arc<DropQueue>,
 
-    /// Shared `Channel` that is cloned to be returned by the `cx.channel()` method
-    shared_channel: Channel,
+    /// Shared `Channel` that is cloned to be returned by the `cx.channel()` method.
+    /// Created on first use.
+    shared_channel: Option<Channel>,
 
     /// Table of user-defined instance-local cells.
     locals: LocalTable,
@@ -213,20 +220,117 @@ impl<'cx, 'a, C: Context<'cx>> Drop for TryInitTransaction<'cx, 'a, C> {
 pub(crate) enum DropData {
     Deferred(NodeApiDeferred),
     Ref(NapiRef),
+    AsyncContext(NapiAsyncContext),
+    // Finalizes the value of the last `SharedHandle` to be dropped
+    Shared(Box<dyn FnOnce(Env) + Send>),
+    // Runs the cancellation of a `GuardedRoot` dropped without being consumed
+    Cancel(Box<dyn FnOnce(Env) + Send>),
 }
 
 impl DropData {
     /// Drop a value on the main thread
     fn drop(env: Option<Env>, data: Self) {
-        if let Some(env) = env {
-            unsafe {
-                match data {
-                    DropData::Deferred(data) => data.leaked(env),
-                    DropData::Ref(data) => data.unref(env),
-                }
+        let env = match env {
+            Some(env) => env,
+            // The queue was finalized before the value could be released
+            None => return data.leak(),
+        };
+
+        unsafe {
+            match data {
+                DropData::Deferred(data) => data.leaked(env),
+                DropData::Ref(data) => data.unref(env),
+                DropData::AsyncContext(data) => data.destroy(env),
+                DropData::Shared(finalize) => finalize(env),
+                DropData::Cancel(cancel) => cancel(env),
             }
         }
     }
+
+    /// Name of the public type that owned the value
+    fn name(&self) -> &'static str {
+        match self {
+            DropData::Deferred(_) => "neon::types::Deferred",
+            DropData::Ref(_) => "neon::handle::Root",
+            DropData::AsyncContext(_) => "neon::event::AsyncResource",
+            DropData::Shared(_) => "neon::types::SharedHandle",
+            DropData::Cancel(_) => "neon::handle::GuardedRoot",
+        }
+    }
+
+    /// Leak a value that can no longer be released, applying the `DropQueuePolicy`
+    fn leak(self) {
+        drop_queue::leaked(self.name());
+    }
+}
+
+thread_local! {
+    // The drop queue and environment of the `InstanceData` being released on this
+    // thread by `InstanceData::release`
+    static RELEASING: Cell<Option<(*const DropQueue, Env)>> = const { Cell::new(None) };
+}
+
+/// Releases values in the same JavaScript environment that created them
+///
+/// Once the environment starts tearing down, the threadsafe function can no longer
+/// run callbacks. Values are leaked instead of being queued, since they are about to
+/// be destroyed with the environment anyway.
+pub(crate) struct DropQueue {
+    tsfn: ThreadsafeFunction<DropData>,
+    is_closing: Arc<AtomicBool>,
+}
+
+impl DropQueue {
+    fn new(env: Env) -> Self {
+        let tsfn = unsafe {
+            let tsfn = ThreadsafeFunction::new(env, DropData::drop);
+            tsfn.unref(env);
+            tsfn
+        };
+
+        let is_closing = Arc::new(AtomicBool::new(false));
+
+        // Cleanup hooks run in reverse order of registration, so registering the
+        // hook after creating the threadsafe function marks the queue as closing
+        // before the threadsafe function is finalized
+        unsafe {
+            let is_closing = Arc::clone(&is_closing);
+
+            lifecycle::add_cleanup_hook(env, move || {
+                is_closing.store(true, Ordering::Release);
+            });
+        }
+
+        Self { tsfn, is_closing }
+    }
+
+    /// Queue a value to be dropped on the JavaScript thread. May be called from any
+    /// thread.
+    pub(crate) fn push(&self, data: DropData) {
+        // Values owned by `InstanceData` are released immediately during teardown
+        if let Some(env) = self.releasing_env() {
+            return DropData::drop(Some(env), data);
+        }
+
+        if self.is_closing.load(Ordering::Acquire) {
+            return data.leak();
+        }
+
+        let name = data.name();
+
+        // The threadsafe function is finalized; the value was dropped without
+        // being released
+        if self.tsfn.call(data, None).is_err() {
+            drop_queue::leaked(name);
+        }
+    }
+
+    fn releasing_env(&self) -> Option<Env> {
+        match RELEASING.with(Cell::get) {
+            Some((queue, env)) if ptr::eq(queue, self) => Some(env),
+            _ => None,
+        }
+    }
 }
 
 impl InstanceData {
@@ -244,39 +348,63 @@ impl InstanceData {
             return data;
         }
 
-        let drop_queue = unsafe {
-            let queue = ThreadsafeFunction::new(env, DropData::drop);
-            queue.unref(env);
-            queue
-        };
+        let drop_queue = DropQueue::new(env);
 
-        let shared_channel = {
-            let mut channel = Channel::new(cx);
-            channel.unref(cx);
-            channel
-        };
+        // Instance data is dropped after cleanup hooks, when the drop queue is already
+        // closing. Registering this hook after creating the queue releases the values
+        // that it owns before the queue closes, instead of reporting them as leaked.
+        unsafe {
+            lifecycle::add_cleanup_hook(env, move || InstanceData::release(env));
+        }
 
         let data = InstanceData {
             id: InstanceId::next(),
             drop_queue: Arc::new(drop_queue),
-            shared_channel,
+            shared_channel: None,
             locals: LocalTable::default(),
         };
 
         unsafe { &mut *lifecycle::set_instance_data(env, data) }
     }
 
+    // Drops the instance-local cells and the shared channel while the environment is
+    // torn down, releasing any values that they hold immediately
+    unsafe fn release(env: Env) {
+        let data = match lifecycle::get_instance_data::<InstanceData>(env).as_mut() {
+            Some(data) => data,
+            None => return,
+        };
+
+        let locals = std::mem::take(&mut data.locals);
+        let shared_channel = data.shared_channel.take();
+        let drop_queue = Arc::clone(&data.drop_queue);
+
+        RELEASING.with(|releasing| releasing.set(Some((Arc::as_ptr(&drop_queue), env))));
+
+        drop(locals);
+        drop(shared_channel);
+
+        RELEASING.with(|releasing| releasing.set(None));
+    }
+
     /// Helper to return a reference to the `drop_queue` field of `InstanceData`
-    pub(crate) fn drop_queue<'cx, C: Context<'cx>>(
-        cx: &mut C,
-    ) -> Arc<ThreadsafeFunction<DropData>> {
+    pub(crate) fn drop_queue<'cx, C: Context<'cx>>(cx: &mut C) -> Arc<DropQueue> {
         Arc::clone(&InstanceData::get(cx).drop_queue)
     }
 
     /// Clones the shared channel and references it since new channels should start
     /// referenced, but the shared channel is unreferenced.
     pub(crate) fn channel<'cx, C: Context<'cx>>(cx: &mut C) -> Channel {
-        let mut channel = InstanceData::get(cx).shared_channel.clone();
+        let mut channel = match &InstanceData::get(cx).shared_channel {
+            Some(channel) => channel.clone(),
+            None => {
+                let mut channel = Channel::new(cx);
+
+                channel.unref(cx);
+                InstanceData::get(cx).shared_channel.insert(channel).clone()
+            }
+        };
+
         channel.reference(cx);
         channel
     }
@@ -1,3 +1,7 @@
 //! Internals needed by macros. These have to be exported for the macros to work
 
 pub use crate::context::internal::initialize_module;
+
+pub use crate::types::extract::{
+    check_range, extract_field, extract_field_with, field_path, FieldErrors, InRange,
+};
@@ -0,0 +1,62 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{context::Context, types::boxed::Finalize};
+
+// A `FinalizerGuard` may be sent to another thread, so the finalizer it shares must be `Send`
+type Finalizer = Box<dyn FnOnce() + Send + 'static>;
+
+/// A handle to a finalizer added with [`Object::add_finalizer`](super::Object::add_finalizer)
+/// that can be used to cancel it before the object is garbage collected.
+///
+/// Dropping the guard does _not_ cancel the finalizer. A `FinalizerGuard` may be sent
+/// across threads, but may only be cancelled on the JavaScript thread that added it.
+pub struct FinalizerGuard {
+    finalizer: Arc<Mutex<Option<Finalizer>>>,
+}
+
+impl std::fmt::Debug for FinalizerGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("FinalizerGuard")
+    }
+}
+
+impl FinalizerGuard {
+    pub(super) fn new(finalizer: Finalizer) -> (Self, impl FnOnce() + 'static) {
+        let finalizer = Arc::new(Mutex::new(Some(finalizer)));
+        let pending = Arc::clone(&finalizer);
+
+        let finalize = move || {
+            // The lock is released before calling the finalizer
+            let f = pending.lock().unwrap().take();
+
+            if let Some(f) = f {
+                f();
+            }
+        };
+
+        (Self { finalizer }, finalize)
+    }
+
+    /// Cancels the finalizer, dropping its data without calling it.
+    ///
+    /// Returns `false` if the finalizer has already run.
+    ///
+    /// Cancelling does not unregister the finalizer from the JavaScript engine;
+    /// Node-API only documents deleting the finalizer's reference from within the
+    /// finalizer itself, and older versions of Node may still call a finalizer whose
+    /// reference was deleted. Instead, the data and callback are dropped immediately and
+    /// a small, empty placeholder is released when the object is garbage collected.
+    pub fn cancel<'a, C: Context<'a>>(self, _cx: &mut C) -> bool {
+        let f = self.finalizer.lock().unwrap().take();
+
+        f.is_some()
+    }
+
+    /// Indicates whether the finalizer is still waiting for the object to be
+    /// garbage collected.
+    pub fn is_pending(&self) -> bool {
+        self.finalizer.lock().unwrap().is_some()
+    }
+}
+
+impl Finalize for FinalizerGuard {}
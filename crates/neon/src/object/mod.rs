@@ -8,11 +8,12 @@
 //! ## Property Keys
 //!
 //! Object properties are accessed by a _property key_, which in JavaScript
-//! can be a string or [symbol][symbol]. (Neon does not yet have support for
-//! symbols.) For convenience, the [`PropertyKey`](PropertyKey) trait allows
-//! Neon programs to use various Rust string types, as well as numeric types,
-//! as keys when accessing object properties, converting the keys to strings
-//! as necessary:
+//! can be a string or [symbol][symbol]. For convenience, the
+//! [`PropertyKey`](PropertyKey) trait allows Neon programs to use Rust strings,
+//! [`JsString`](crate::types::JsString) and [`JsSymbol`](crate::types::JsSymbol)
+//! handles, as well as numeric types, as keys when accessing object properties.
+//! Numeric keys access elements directly where possible and are converted to
+//! strings otherwise:
 //!
 //! ```
 //! # use neon::prelude::*;
@@ -32,19 +33,45 @@
 //! [hierarchy]: crate::types#the-javascript-type-hierarchy
 //! [symbol]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Symbol
 
+use std::convert::TryFrom;
+
 use crate::{
     context::Context,
     handle::{Handle, Root},
     result::{NeonResult, Throw},
     sys::{self, raw},
-    types::{build, function::CallOptions, utf8::Utf8, JsFunction, JsUndefined, JsValue, Value},
+    types::{
+        build, extract::TryFromJs, function::CallOptions, utf8::Utf8, JsFunction, JsString,
+        JsUndefined, JsValue, Value,
+    },
 };
 
+#[cfg(feature = "napi-5")]
+use crate::context::TaskContext;
+
 #[cfg(feature = "napi-6")]
 use crate::{result::JsResult, types::JsArray};
 
+#[cfg(feature = "napi-5")]
+mod finalizer;
+
+#[cfg(feature = "napi-5")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+pub use self::finalizer::FinalizerGuard;
+
 /// A property key in a JavaScript object.
+///
+/// Keys may be strings ([`&str`](str) or [`Handle<JsString>`](crate::types::JsString)),
+/// [symbols](crate::types::JsSymbol), or integers (`u32`, `usize`, or `i32`). Integer
+/// keys that fit in a `u32` access elements directly, without converting the key to
+/// a string.
 pub trait PropertyKey {
+    /// Gets the property named by this key from `obj`, writing it to `out`. Returns
+    /// `false` if the property couldn't be retrieved.
+    ///
+    /// # Safety
+    ///
+    /// `obj` must be a valid object in the environment of `cx`.
     unsafe fn get_from<'c, C: Context<'c>>(
         self,
         cx: &mut C,
@@ -52,6 +79,12 @@ pub trait PropertyKey {
         obj: raw::Local,
     ) -> bool;
 
+    /// Sets the property named by this key on `obj` to `val`. Returns `false` if the
+    /// property couldn't be set.
+    ///
+    /// # Safety
+    ///
+    /// `obj` and `val` must be valid values in the environment of `cx`.
     unsafe fn set_from<'c, C: Context<'c>>(
         self,
         cx: &mut C,
@@ -59,6 +92,46 @@ pub trait PropertyKey {
         obj: raw::Local,
         val: raw::Local,
     ) -> bool;
+
+    /// Checks whether `obj` has the property named by this key, writing the result
+    /// to `out`. Returns `false` if the check failed.
+    ///
+    /// # Safety
+    ///
+    /// `obj` must be a valid object in the environment of `cx`.
+    unsafe fn has_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool;
+
+    /// Deletes the property named by this key from `obj`, writing whether it was
+    /// deleted to `out`. Returns `false` if the deletion failed.
+    ///
+    /// # Safety
+    ///
+    /// `obj` must be a valid object in the environment of `cx`.
+    unsafe fn delete_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool;
+
+    /// Defines a data property named by this key on `obj`. Returns `false` if the
+    /// property couldn't be defined.
+    ///
+    /// # Safety
+    ///
+    /// `obj` and `val` must be valid values in the environment of `cx`.
+    unsafe fn define_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        obj: raw::Local,
+        val: raw::Local,
+        attributes: PropertyAttributes,
+    ) -> bool;
 }
 
 impl PropertyKey for u32 {
@@ -80,6 +153,198 @@ impl PropertyKey for u32 {
     ) -> bool {
         sys::object::set_index(out, cx.env().to_raw(), obj, self, val)
     }
+
+    unsafe fn has_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool {
+        sys::object::has_index(out, cx.env().to_raw(), obj, self)
+    }
+
+    unsafe fn delete_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool {
+        sys::object::delete_index(out, cx.env().to_raw(), obj, self)
+    }
+
+    unsafe fn define_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        obj: raw::Local,
+        val: raw::Local,
+        attributes: PropertyAttributes,
+    ) -> bool {
+        // Node-API has no element equivalent of `napi_define_properties`, which only
+        // accepts string and symbol keys
+        cx.string(self.to_string())
+            .define_from(cx, obj, val, attributes)
+    }
+}
+
+// The largest index that can be represented exactly by a JavaScript number
+const MAX_SAFE_INDEX: usize = (1 << 53) - 1;
+
+// Converts an index that does not fit in a `u32` to a string key, throwing a
+// `RangeError` if it can't be represented exactly by a JavaScript number
+fn large_index<'c, C: Context<'c>>(cx: &mut C, index: usize) -> Option<Handle<'c, JsString>> {
+    if index > MAX_SAFE_INDEX {
+        let _ = cx.throw_range_error::<_, ()>(format!(
+            "property index {} is greater than Number.MAX_SAFE_INTEGER",
+            index
+        ));
+
+        return None;
+    }
+
+    Some(cx.string(index.to_string()))
+}
+
+impl PropertyKey for usize {
+    unsafe fn get_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut raw::Local,
+        obj: raw::Local,
+    ) -> bool {
+        match u32::try_from(self) {
+            Ok(index) => index.get_from(cx, out, obj),
+            Err(_) => match large_index(cx, self) {
+                Some(key) => key.get_from(cx, out, obj),
+                None => false,
+            },
+        }
+    }
+
+    unsafe fn set_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+        val: raw::Local,
+    ) -> bool {
+        match u32::try_from(self) {
+            Ok(index) => index.set_from(cx, out, obj, val),
+            Err(_) => match large_index(cx, self) {
+                Some(key) => key.set_from(cx, out, obj, val),
+                None => false,
+            },
+        }
+    }
+
+    unsafe fn has_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool {
+        match u32::try_from(self) {
+            Ok(index) => index.has_from(cx, out, obj),
+            Err(_) => match large_index(cx, self) {
+                Some(key) => key.has_from(cx, out, obj),
+                None => false,
+            },
+        }
+    }
+
+    unsafe fn delete_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool {
+        match u32::try_from(self) {
+            Ok(index) => index.delete_from(cx, out, obj),
+            Err(_) => match large_index(cx, self) {
+                Some(key) => key.delete_from(cx, out, obj),
+                None => false,
+            },
+        }
+    }
+
+    unsafe fn define_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        obj: raw::Local,
+        val: raw::Local,
+        attributes: PropertyAttributes,
+    ) -> bool {
+        match u32::try_from(self) {
+            Ok(index) => index.define_from(cx, obj, val, attributes),
+            Err(_) => match large_index(cx, self) {
+                Some(key) => key.define_from(cx, obj, val, attributes),
+                None => false,
+            },
+        }
+    }
+}
+
+// Integer literals default to `i32` when the key type is otherwise ambiguous. Negative
+// keys are not indices and are converted to strings, e.g., `-1` names the property "-1".
+impl PropertyKey for i32 {
+    unsafe fn get_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut raw::Local,
+        obj: raw::Local,
+    ) -> bool {
+        match u32::try_from(self) {
+            Ok(index) => index.get_from(cx, out, obj),
+            Err(_) => cx.string(self.to_string()).get_from(cx, out, obj),
+        }
+    }
+
+    unsafe fn set_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+        val: raw::Local,
+    ) -> bool {
+        match u32::try_from(self) {
+            Ok(index) => index.set_from(cx, out, obj, val),
+            Err(_) => cx.string(self.to_string()).set_from(cx, out, obj, val),
+        }
+    }
+
+    unsafe fn has_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool {
+        match u32::try_from(self) {
+            Ok(index) => index.has_from(cx, out, obj),
+            Err(_) => cx.string(self.to_string()).has_from(cx, out, obj),
+        }
+    }
+
+    unsafe fn delete_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool {
+        match u32::try_from(self) {
+            Ok(index) => index.delete_from(cx, out, obj),
+            Err(_) => cx.string(self.to_string()).delete_from(cx, out, obj),
+        }
+    }
+
+    unsafe fn define_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        obj: raw::Local,
+        val: raw::Local,
+        attributes: PropertyAttributes,
+    ) -> bool {
+        cx.string(self.to_string())
+            .define_from(cx, obj, val, attributes)
+    }
 }
 
 impl<'a, K: Value> PropertyKey for Handle<'a, K> {
@@ -105,6 +370,40 @@ impl<'a, K: Value> PropertyKey for Handle<'a, K> {
 
         sys::object::set(out, env, obj, self.to_raw(), val)
     }
+
+    unsafe fn has_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool {
+        let env = cx.env().to_raw();
+
+        sys::object::has(out, env, obj, self.to_raw())
+    }
+
+    unsafe fn delete_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool {
+        let env = cx.env().to_raw();
+
+        sys::object::delete(out, env, obj, self.to_raw())
+    }
+
+    unsafe fn define_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        obj: raw::Local,
+        val: raw::Local,
+        attributes: PropertyAttributes,
+    ) -> bool {
+        let env = cx.env().to_raw();
+
+        sys::object::define_value(env, obj, self.to_raw(), val, attributes.to_raw())
+    }
 }
 
 impl<'a> PropertyKey for &'a str {
@@ -132,6 +431,67 @@ impl<'a> PropertyKey for &'a str {
 
         sys::object::set_string(env, out, obj, ptr, len, val)
     }
+
+    unsafe fn has_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool {
+        cx.string(self).has_from(cx, out, obj)
+    }
+
+    unsafe fn delete_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        out: &mut bool,
+        obj: raw::Local,
+    ) -> bool {
+        cx.string(self).delete_from(cx, out, obj)
+    }
+
+    unsafe fn define_from<'c, C: Context<'c>>(
+        self,
+        cx: &mut C,
+        obj: raw::Local,
+        val: raw::Local,
+        attributes: PropertyAttributes,
+    ) -> bool {
+        cx.string(self).define_from(cx, obj, val, attributes)
+    }
+}
+
+/// The attributes of a data property defined with [`Object::define_property`].
+///
+/// As with `Object.defineProperty`, all attributes default to `false`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PropertyAttributes {
+    /// The value may be changed with an assignment.
+    pub writable: bool,
+    /// The property shows up during enumeration, e.g., in `Object.keys`.
+    pub enumerable: bool,
+    /// The property may be deleted and its attributes may be changed.
+    pub configurable: bool,
+}
+
+impl PropertyAttributes {
+    fn to_raw(self) -> sys::PropertyAttributes {
+        let mut attributes = sys::PropertyAttributes::DEFAULT;
+
+        if self.writable {
+            attributes = attributes | sys::PropertyAttributes::WRITABLE;
+        }
+
+        if self.enumerable {
+            attributes = attributes | sys::PropertyAttributes::ENUMERABLE;
+        }
+
+        if self.configurable {
+            attributes = attributes | sys::PropertyAttributes::CONFIGURABLE;
+        }
+
+        attributes
+    }
 }
 
 /// The trait of all object types.
@@ -178,6 +538,20 @@ pub trait Object: Value {
         self.get_value(cx, key)?.downcast_or_throw(cx)
     }
 
+    /// Gets a property from a JavaScript object and extracts it as a Rust value with
+    /// [`TryFromJs`]. A missing property is extracted as `undefined`.
+    ///
+    /// Throws an exception if the value cannot be converted.
+    fn extract_property<'a, T: TryFromJs<'a>, C: Context<'a>, K: PropertyKey>(
+        &self,
+        cx: &mut C,
+        key: K,
+    ) -> NeonResult<T> {
+        let v = self.get_value(cx, key)?;
+
+        T::try_from_js(cx, v)
+    }
+
     #[cfg(feature = "napi-6")]
     #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
     fn get_own_property_names<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsArray> {
@@ -228,6 +602,69 @@ pub trait Object: Value {
         }
     }
 
+    /// Checks whether the object has a property, equivalent to the JavaScript `in`
+    /// operator. Properties inherited from the prototype chain are included.
+    fn has<'a, C: Context<'a>, K: PropertyKey>(&self, cx: &mut C, key: K) -> NeonResult<bool> {
+        let mut result = false;
+        if unsafe { key.has_from(cx, &mut result, self.to_raw()) } {
+            Ok(result)
+        } else {
+            Err(Throw::new())
+        }
+    }
+
+    /// Deletes a property, equivalent to the JavaScript `delete` operator. Returns
+    /// `false` if the property is not configurable.
+    fn delete<'a, C: Context<'a>, K: PropertyKey>(&self, cx: &mut C, key: K) -> NeonResult<bool> {
+        let mut result = false;
+        if unsafe { key.delete_from(cx, &mut result, self.to_raw()) } {
+            Ok(result)
+        } else {
+            Err(Throw::new())
+        }
+    }
+
+    /// Defines a data property with the given attributes, equivalent to
+    /// `Object.defineProperty(obj, key, { value, ...attributes })`.
+    ///
+    /// Throws a `TypeError` if an existing property can't be redefined.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use neon::object::PropertyAttributes;
+    ///
+    /// fn define_constant(mut cx: FunctionContext) -> JsResult<JsObject> {
+    ///     let obj = cx.empty_object();
+    ///     let value = cx.number(42);
+    ///     let attributes = PropertyAttributes {
+    ///         enumerable: true,
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     obj.define_property(&mut cx, "answer", value, attributes)?;
+    ///
+    ///     Ok(obj)
+    /// }
+    /// ```
+    fn define_property<'a, C: Context<'a>, K: PropertyKey, W: Value>(
+        &self,
+        cx: &mut C,
+        key: K,
+        val: Handle<W>,
+        attributes: PropertyAttributes,
+    ) -> NeonResult<()> {
+        if unsafe { key.define_from(cx, self.to_raw(), val.to_raw(), attributes) } {
+            return Ok(());
+        }
+
+        // Node-API reports a rejected definition without throwing
+        if unsafe { sys::error::is_throwing(cx.env().to_raw()) } {
+            Err(Throw::new())
+        } else {
+            cx.throw_type_error("cannot redefine property")
+        }
+    }
+
     fn root<'a, C: Context<'a>>(&self, cx: &mut C) -> Root<Self> {
         Root::new(cx, self)
     }
@@ -241,4 +678,78 @@ pub trait Object: Value {
         options.this(JsValue::new_internal(self.to_raw()));
         Ok(options)
     }
+
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    /// Adds a finalizer that calls `f` with `data` when the object is garbage collected,
+    /// or when the JavaScript environment shuts down if the object is still alive.
+    ///
+    /// Finalizers may be added to any object, including objects that were not created
+    /// by Neon, and an object may have any number of finalizers. The returned
+    /// [`FinalizerGuard`] can be used to cancel the finalizer.
+    ///
+    /// `f` is called while the garbage collector is running and must not call into
+    /// JavaScript; use [`Object::add_finalizer_with_context`] if it needs to. Since the
+    /// guard may be sent to and dropped on another thread, `data` and `f` must be `Send`.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use std::sync::{
+    ///     atomic::{AtomicUsize, Ordering},
+    ///     Arc,
+    /// };
+    ///
+    /// // Counts the live objects passed to `track`
+    /// fn track(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let object = cx.argument::<JsObject>(0)?;
+    ///     let live = cx.argument::<JsBox<Arc<AtomicUsize>>>(1)?;
+    ///     let live = Arc::clone(&live);
+    ///
+    ///     live.fetch_add(1, Ordering::Relaxed);
+    ///     object.add_finalizer(&mut cx, live, |live| {
+    ///         live.fetch_sub(1, Ordering::Relaxed);
+    ///     });
+    ///
+    ///     Ok(cx.undefined())
+    /// }
+    /// ```
+    fn add_finalizer<'a, C, T, F>(&self, cx: &mut C, data: T, f: F) -> FinalizerGuard
+    where
+        C: Context<'a>,
+        T: Send + 'static,
+        F: FnOnce(T) + Send + 'static,
+    {
+        let (guard, finalize) = FinalizerGuard::new(Box::new(move || f(data)));
+
+        unsafe { sys::object::add_finalizer(cx.env().to_raw(), self.to_raw(), finalize) };
+
+        guard
+    }
+
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    /// Adds a finalizer that calls `f` with `data` on the JavaScript thread, on a later
+    /// tick of the event loop, after the object is garbage collected.
+    ///
+    /// Unlike [`Object::add_finalizer`], `f` is passed a [`TaskContext`] and may call
+    /// into JavaScript. It is scheduled with an unreferenced [`Context::channel`], so a
+    /// pending finalizer does not keep the process alive; if the object is still alive
+    /// when the JavaScript environment shuts down, `data` is dropped without calling
+    /// `f`. Exceptions thrown by `f` are reported as uncaught exceptions.
+    fn add_finalizer_with_context<'a, C, T, F>(&self, cx: &mut C, data: T, f: F) -> FinalizerGuard
+    where
+        C: Context<'a>,
+        T: Send + 'static,
+        F: FnOnce(TaskContext, T) -> NeonResult<()> + Send + 'static,
+    {
+        // A pending finalizer must not keep the event loop alive
+        let mut channel = cx.channel();
+
+        channel.unref(cx);
+
+        self.add_finalizer(cx, data, move |data| {
+            // Sending fails if the environment is shutting down
+            let _ = channel.try_send(move |cx| f(cx, data));
+        })
+    }
 }
@@ -0,0 +1,183 @@
+//! Interoperating with the JavaScript
+//! [`performance`](https://developer.mozilla.org/en-US/docs/Web/API/Performance) timeline.
+//!
+//! Timestamps from `performance.now()` are measured in milliseconds from the
+//! [time origin](https://developer.mozilla.org/en-US/docs/Web/API/Performance/timeOrigin)
+//! of the environment, while a Rust [`Instant`] is measured from an unspecified point
+//! of a monotonic clock. [`Context::to_performance_timestamp`] converts an `Instant`
+//! to the same scale as `performance.now()`, so that timings recorded in Rust, e.g.,
+//! on another thread, can be compared with marks recorded in JavaScript.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use std::time::Instant;
+//!
+//! fn measure(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+//!     let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+//!     let channel = cx.channel();
+//!
+//!     std::thread::spawn(move || {
+//!         let start = Instant::now();
+//!
+//!         // Do some work
+//!
+//!         let end = Instant::now();
+//!
+//!         channel.send(move |mut cx| {
+//!             let start = cx.to_performance_timestamp(start)?;
+//!             let start = cx.number(start);
+//!             let end = cx.to_performance_timestamp(end)?;
+//!             let end = cx.number(end);
+//!
+//!             callback
+//!                 .into_inner(&mut cx)
+//!                 .call_with(&cx)
+//!                 .arg(start)
+//!                 .arg(end)
+//!                 .exec(&mut cx)
+//!         });
+//!     });
+//!
+//!     Ok(cx.undefined())
+//! }
+//! ```
+//!
+//! The conversion is calibrated by sampling both clocks together. When using
+//! N-API >= 6, the calibration and the `performance` object are cached for each
+//! environment, and the calibration is repeated lazily once it is older than the
+//! [calibration interval](set_calibration_interval), to correct for drift between the
+//! clocks. Otherwise, both clocks are sampled for each conversion.
+
+use std::time::{Duration, Instant};
+
+use crate::{
+    context::Context,
+    object::Object,
+    result::NeonResult,
+    types::{JsFunction, JsNumber},
+};
+
+#[cfg(feature = "napi-6")]
+use {crate::thread::LocalKey, std::sync::Mutex};
+
+/// Default age after which a calibration is repeated.
+pub const DEFAULT_CALIBRATION_INTERVAL: Duration = Duration::from_secs(60);
+
+// A pair of samples of the Rust and JavaScript clocks taken at the same moment
+#[derive(Clone, Copy)]
+struct Calibration {
+    instant: Instant,
+    timestamp: f64,
+}
+
+impl Calibration {
+    fn sample<'a, C: Context<'a>>(cx: &mut C) -> NeonResult<Self> {
+        let before = Instant::now();
+        let timestamp = now(cx)?;
+        let after = Instant::now();
+
+        Ok(Self {
+            // Assume `performance.now()` was sampled halfway through the call
+            instant: before + (after - before) / 2,
+            timestamp,
+        })
+    }
+
+    fn to_timestamp(self, instant: Instant) -> f64 {
+        if instant >= self.instant {
+            self.timestamp + millis(instant - self.instant)
+        } else {
+            self.timestamp - millis(self.instant - instant)
+        }
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+// The calibration of the clocks in an environment. The `performance` object is cached
+// by `intrinsics::performance`.
+#[cfg(feature = "napi-6")]
+struct Performance {
+    // Only accessed from the JavaScript thread of the environment, but `LocalKey`
+    // requires `Sync`
+    calibration: Mutex<Option<Calibration>>,
+    interval: Mutex<Duration>,
+}
+
+#[cfg(feature = "napi-6")]
+impl Performance {
+    fn get<'a, C: Context<'a>>(cx: &mut C) -> &'a Self {
+        static PERFORMANCE: LocalKey<Performance> = LocalKey::new();
+
+        PERFORMANCE.get_or_init(cx, || Performance {
+            calibration: Mutex::new(None),
+            interval: Mutex::new(DEFAULT_CALIBRATION_INTERVAL),
+        })
+    }
+}
+
+#[cfg(feature = "napi-6")]
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+pub(crate) fn now<'a, C: Context<'a>>(cx: &mut C) -> NeonResult<f64> {
+    let performance = crate::intrinsics::performance(cx)?;
+    let now = performance.get::<JsFunction, _, _>(cx, "now")?;
+    let timestamp = now
+        .call_with(cx)
+        .this(performance)
+        .apply::<JsNumber, _>(cx)?;
+
+    Ok(timestamp.value(cx))
+}
+
+pub(crate) fn time_origin<'a, C: Context<'a>>(cx: &mut C) -> NeonResult<f64> {
+    let performance = crate::intrinsics::performance(cx)?;
+    let origin = performance.get::<JsNumber, _, _>(cx, "timeOrigin")?;
+
+    Ok(origin.value(cx))
+}
+
+pub(crate) fn to_timestamp<'a, C: Context<'a>>(cx: &mut C, instant: Instant) -> NeonResult<f64> {
+    #[cfg(feature = "napi-6")]
+    {
+        let performance = Performance::get(cx);
+        let interval = *lock(&performance.interval);
+        let cached = *lock(&performance.calibration);
+        let calibration = match cached {
+            Some(calibration) if calibration.instant.elapsed() < interval => calibration,
+            _ => {
+                // The lock is not held while calling `performance.now()`, which may
+                // re-entrantly convert another timestamp
+                let calibration = Calibration::sample(cx)?;
+
+                *lock(&performance.calibration) = Some(calibration);
+                calibration
+            }
+        };
+
+        Ok(calibration.to_timestamp(instant))
+    }
+
+    #[cfg(not(feature = "napi-6"))]
+    Ok(Calibration::sample(cx)?.to_timestamp(instant))
+}
+
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+/// Sets the age after which [`Context::to_performance_timestamp`] repeats the
+/// calibration of the Rust and JavaScript clocks in the current environment.
+///
+/// Defaults to [`DEFAULT_CALIBRATION_INTERVAL`]. A zero interval calibrates on every
+/// conversion.
+pub fn set_calibration_interval<'a, C: Context<'a>>(
+    cx: &mut C,
+    interval: Duration,
+) -> NeonResult<()> {
+    *lock(&Performance::get(cx).interval) = interval;
+
+    Ok(())
+}
@@ -13,7 +13,7 @@ pub use crate::{
         boxed::{Finalize, JsBox},
         JsArray, JsArrayBuffer, JsBigInt64Array, JsBigUint64Array, JsBoolean, JsBuffer, JsError,
         JsFloat32Array, JsFloat64Array, JsFunction, JsInt16Array, JsInt32Array, JsInt8Array,
-        JsNull, JsNumber, JsObject, JsPromise, JsString, JsTypedArray, JsUint16Array,
+        JsNull, JsNumber, JsObject, JsPromise, JsString, JsSymbol, JsTypedArray, JsUint16Array,
         JsUint32Array, JsUint8Array, JsUndefined, JsValue, Value,
     },
 };
@@ -3,8 +3,9 @@
 use crate::{
     context::Context,
     handle::{Handle, Managed},
-    result::JsResult,
-    types::{build, JsString, JsValue},
+    object::Object,
+    result::{JsResult, NeonResult},
+    types::{build, JsFunction, JsObject, JsString, JsValue, Value},
 };
 
 pub fn eval<'a, 'b, C: Context<'a>>(
@@ -16,3 +17,63 @@ pub fn eval<'a, 'b, C: Context<'a>>(
         crate::sys::string::run_script(out, env, script.to_raw())
     })
 }
+
+// Loads a built-in module with `process.getBuiltinModule`, which was added in Node 20.16
+// and 22.3. Older runtimes fall back to `require`; see `fallback_require`.
+pub(crate) fn builtin_module<'a, V: Value, C: Context<'a>>(
+    cx: &mut C,
+    name: &str,
+) -> JsResult<'a, V> {
+    let process = cx.global().get_value(cx, "process")?;
+    let loader = match process.downcast::<JsObject, _>(cx) {
+        Ok(process) => match process
+            .get_value(cx, "getBuiltinModule")?
+            .downcast::<JsFunction, _>(cx)
+        {
+            Ok(f) => Some((process, f)),
+            Err(_) => fallback_require(cx, process)?,
+        },
+        Err(_) => None,
+    };
+
+    let (this, load) = match loader {
+        Some(loader) => loader,
+        None => {
+            return cx.throw_error(format!(
+                "the `{}` module is not available in this runtime",
+                name
+            ))
+        }
+    };
+
+    let name = cx.string(name);
+
+    load.call(cx, this, [name.upcast()])?.downcast_or_throw(cx)
+}
+
+// Returns the `require` function of the main module, i.e., `process.mainModule`, or a
+// global `require`, such as in the REPL. Neither exists when the entry point of the
+// application is an ES module.
+fn fallback_require<'a, C: Context<'a>>(
+    cx: &mut C,
+    process: Handle<'a, JsObject>,
+) -> NeonResult<Option<(Handle<'a, JsObject>, Handle<'a, JsFunction>)>> {
+    if let Ok(main) = process
+        .get_value(cx, "mainModule")?
+        .downcast::<JsObject, _>(cx)
+    {
+        if let Ok(require) = main.get_value(cx, "require")?.downcast::<JsFunction, _>(cx) {
+            return Ok(Some((main, require)));
+        }
+    }
+
+    let global = cx.global();
+
+    match global
+        .get_value(cx, "require")?
+        .downcast::<JsFunction, _>(cx)
+    {
+        Ok(require) => Ok(Some((global, require))),
+        Err(_) => Ok(None),
+    }
+}
@@ -0,0 +1,534 @@
+//! Registry of exported function signatures for generating TypeScript declarations.
+//!
+//! Signatures are described with the Rust types of their parameters and return
+//! values, either as type parameters, e.g., [`Function::param_of`], or written as
+//! they appear in source, e.g., `"Handle<JsTypedArray<f64>>"`. [`Function::export`]
+//! exports the function from the module under the name it is declared with.
+//! [`type_declarations`] maps these types to TypeScript and produces the contents of
+//! a `.d.ts` file for every registered function and interface.
+//!
+//! | Rust | TypeScript |
+//! |------|------------|
+//! | numeric primitives, `JsNumber` | `number` |
+//! | `String`, `&str`, `char`, `JsString` | `string` |
+//! | `bool`, `JsBoolean` | `boolean` |
+//! | `JsBuffer` | `Buffer` |
+//! | `JsArrayBuffer` | `ArrayBuffer` |
+//! | `JsTypedArray<f64>`, `JsFloat64Array`, etc. | `Float64Array`, etc. |
+//! | `Vec<T>`, `&[T]` | `T[]` |
+//! | `(A, B)` | `[A, B]` |
+//! | `Option<T>` | `T \| undefined` |
+//! | `Result<T, E>`, `JsResult<T>`, `NeonResult<T>` | `T` |
+//! | `JsPromise`, `impl Future<Output = T>` | `Promise<unknown>`, `Promise<T>` |
+//! | `()`, `JsUndefined` | `void` when returned, otherwise `undefined` |
+//! | a registered [`Interface`] | the interface |
+//!
+//! Types that cannot be mapped, including structs that have not been registered as
+//! an [`Interface`], are declared as `unknown`.
+//!
+//! # Example
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use neon::registry::{self, Function, Interface};
+//!
+//! # fn sum(mut cx: FunctionContext) -> JsResult<JsPromise> { todo!() }
+//! # fn origin(mut cx: FunctionContext) -> JsResult<JsObject> { todo!() }
+//! #[neon::main]
+//! fn main(mut cx: ModuleContext) -> NeonResult<()> {
+//!     Interface::new("Point")
+//!         .field_of::<f64>("x")
+//!         .field_of::<f64>("y")
+//!         .field_of::<Option<String>>("label")
+//!         .register();
+//!
+//!     Function::new("sum")
+//!         .param_of::<Handle<JsFloat64Array>>("nums")
+//!         .returns_of::<f64>()
+//!         .asynchronous()
+//!         .export(&mut cx, sum)?;
+//!
+//!     Function::new("origin").returns("Point").export(&mut cx, origin)?;
+//!
+//!     // export function sum(nums: Float64Array): Promise<number>;
+//!     let declarations = cx.string(registry::type_declarations());
+//!
+//!     cx.export_value("typeDeclarations", declarations)?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::{any, fmt::Write as _, fs, io, path::Path, sync::Mutex};
+
+use crate::{
+    context::{FunctionContext, ModuleContext},
+    result::{JsResult, NeonResult},
+    types::Value,
+};
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    interfaces: Vec::new(),
+    functions: Vec::new(),
+});
+
+struct Registry {
+    interfaces: Vec<Interface>,
+    functions: Vec<Function>,
+}
+
+/// The signature of an exported function.
+#[derive(Clone, Debug)]
+pub struct Function {
+    name: String,
+    params: Vec<(String, String)>,
+    returns: Option<String>,
+    asynchronous: bool,
+}
+
+impl Function {
+    /// Describes a function exported as `name`, taking no parameters and returning
+    /// `undefined`.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        Self {
+            name: name.into(),
+            params: Vec::new(),
+            returns: None,
+            asynchronous: false,
+        }
+    }
+
+    /// Adds a parameter named `name` of the Rust type `ty`.
+    pub fn param<N: Into<String>, T: Into<String>>(mut self, name: N, ty: T) -> Self {
+        self.params.push((name.into(), ty.into()));
+        self
+    }
+
+    /// Adds a parameter named `name` of type `T`.
+    pub fn param_of<T: ?Sized>(self, name: impl Into<String>) -> Self {
+        self.param(name, any::type_name::<T>())
+    }
+
+    /// Sets the Rust return type of the function. _Default: `()`, or the type of value
+    /// returned by the function passed to [`Function::export`]_
+    pub fn returns<T: Into<String>>(mut self, ty: T) -> Self {
+        self.returns = Some(ty.into());
+        self
+    }
+
+    /// Sets the return type of the function to `T`.
+    pub fn returns_of<T: ?Sized>(self) -> Self {
+        self.returns(any::type_name::<T>())
+    }
+
+    /// Declares that the function returns a promise resolving to its return type.
+    pub fn asynchronous(mut self) -> Self {
+        self.asynchronous = true;
+        self
+    }
+
+    /// Adds the signature to the registry, replacing any function registered with
+    /// the same name.
+    pub fn register(self) {
+        let mut registry = REGISTRY.lock().unwrap_or_else(|err| err.into_inner());
+
+        replace_or_push(&mut registry.functions, self, |f| &f.name);
+    }
+
+    #[cfg(not(feature = "napi-5"))]
+    /// Exports `f` from the module with [`ModuleContext::export_function`], named as
+    /// the function is described, and adds the signature to the registry.
+    #[track_caller]
+    pub fn export<V: Value>(
+        self,
+        cx: &mut ModuleContext,
+        f: fn(FunctionContext) -> JsResult<V>,
+    ) -> NeonResult<()> {
+        cx.export_function(&self.name, f)?;
+        self.returning::<V>().register();
+
+        Ok(())
+    }
+
+    #[cfg(feature = "napi-5")]
+    /// Exports `f` from the module with [`ModuleContext::export_function`], named as
+    /// the function is described, and adds the signature to the registry.
+    ///
+    /// If a return type was not set, the function is declared as returning the type of
+    /// value returned by `f`, e.g., `string` for a [`JsString`](crate::types::JsString).
+    #[track_caller]
+    pub fn export<F, V>(self, cx: &mut ModuleContext, f: F) -> NeonResult<()>
+    where
+        F: Fn(FunctionContext) -> JsResult<V> + 'static,
+        V: Value,
+    {
+        cx.export_function(&self.name, f)?;
+        self.returning::<V>().register();
+
+        Ok(())
+    }
+
+    // Defaults the return type to `V`
+    fn returning<V: Value>(mut self) -> Self {
+        if self.returns.is_none() {
+            self.returns = Some(any::type_name::<V>().to_string());
+        }
+
+        self
+    }
+}
+
+/// The shape of an object, e.g., a struct serialized with `serde`, declared as a
+/// TypeScript interface.
+#[derive(Clone, Debug)]
+pub struct Interface {
+    name: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Interface {
+    /// Describes an interface named `name`. Parameters and return types naming the
+    /// interface, e.g., `"Vec<Point>"`, are declared with it.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        Self {
+            name: name.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Adds a field named `name` of the Rust type `ty`. Fields of type `Option<T>` are
+    /// declared as optional.
+    pub fn field<N: Into<String>, T: Into<String>>(mut self, name: N, ty: T) -> Self {
+        self.fields.push((name.into(), ty.into()));
+        self
+    }
+
+    /// Adds a field named `name` of type `T`.
+    pub fn field_of<T: ?Sized>(self, name: impl Into<String>) -> Self {
+        self.field(name, any::type_name::<T>())
+    }
+
+    /// Adds the interface to the registry, replacing any interface registered with
+    /// the same name.
+    pub fn register(self) {
+        let mut registry = REGISTRY.lock().unwrap_or_else(|err| err.into_inner());
+
+        replace_or_push(&mut registry.interfaces, self, |i| &i.name);
+    }
+}
+
+fn replace_or_push<T>(items: &mut Vec<T>, item: T, name: impl Fn(&T) -> &String) {
+    match items.iter_mut().find(|other| name(other) == name(&item)) {
+        Some(other) => *other = item,
+        None => items.push(item),
+    }
+}
+
+/// Returns TypeScript declarations for every registered interface and function.
+pub fn type_declarations() -> String {
+    let registry = REGISTRY.lock().unwrap_or_else(|err| err.into_inner());
+    let mapper = Mapper {
+        interfaces: &registry.interfaces,
+    };
+
+    let mut out = String::new();
+
+    for interface in &registry.interfaces {
+        let _ = writeln!(out, "export interface {} {{", interface.name);
+
+        for (name, ty) in &interface.fields {
+            match option_inner(ty) {
+                Some(ty) => {
+                    let _ = writeln!(out, "  {}?: {};", name, mapper.map(ty));
+                }
+                None => {
+                    let _ = writeln!(out, "  {}: {};", name, mapper.map(ty));
+                }
+            }
+        }
+
+        out.push_str("}\n\n");
+    }
+
+    for function in &registry.functions {
+        let params = function
+            .params
+            .iter()
+            .map(|(name, ty)| format!("{}: {}", name, mapper.map(ty)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let returns = mapper.map_return(function.returns.as_deref().unwrap_or("()"));
+        let returns = if function.asynchronous {
+            format!("Promise<{}>", returns)
+        } else {
+            returns
+        };
+
+        let _ = writeln!(
+            out,
+            "export function {}({}): {};",
+            function.name, params, returns
+        );
+    }
+
+    out
+}
+
+/// Writes [`type_declarations`] to the file at `path`, e.g., from a build script or
+/// a test.
+pub fn write_type_declarations<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    fs::write(path, type_declarations())
+}
+
+// Returns `T` if `ty` is `Option<T>`
+fn option_inner(ty: &str) -> Option<&str> {
+    match parse(ty) {
+        Type::Path("Option", args) if args.len() == 1 => Some(args[0]),
+        _ => None,
+    }
+}
+
+struct Mapper<'a> {
+    interfaces: &'a [Interface],
+}
+
+impl Mapper<'_> {
+    fn map_return(&self, ty: &str) -> String {
+        match self.map(ty).as_str() {
+            "undefined" => String::from("void"),
+            ty => String::from(ty),
+        }
+    }
+
+    fn map(&self, ty: &str) -> String {
+        let (name, args) = match parse(ty) {
+            Type::Tuple(items) if items.is_empty() => return String::from("undefined"),
+            Type::Tuple(items) => {
+                let items = items.iter().map(|ty| self.map(ty)).collect::<Vec<_>>();
+
+                return format!("[{}]", items.join(", "));
+            }
+            Type::Slice(item) => return self.array(item),
+            Type::Path(name, args) => (name, args),
+        };
+
+        let arg = |i: usize| self.map(args[i]);
+
+        let ty = match (name, args.as_slice()) {
+            (
+                "f64" | "f32" | "i64" | "i32" | "i16" | "i8" | "isize" | "u64" | "u32" | "u16"
+                | "u8" | "usize" | "JsNumber",
+                _,
+            ) => "number",
+            ("String" | "str" | "char" | "JsString", _) => "string",
+            ("bool" | "JsBoolean", _) => "boolean",
+            ("JsUndefined", _) => "undefined",
+            ("JsNull", _) => "null",
+            ("JsBuffer", _) => "Buffer",
+            ("JsArrayBuffer", _) => "ArrayBuffer",
+            ("JsUint8Array", _) => "Uint8Array",
+            ("JsInt8Array", _) => "Int8Array",
+            ("JsUint16Array", _) => "Uint16Array",
+            ("JsInt16Array", _) => "Int16Array",
+            ("JsUint32Array", _) => "Uint32Array",
+            ("JsInt32Array", _) => "Int32Array",
+            ("JsFloat32Array", _) => "Float32Array",
+            ("JsFloat64Array", _) => "Float64Array",
+            ("JsBigUint64Array", _) => "BigUint64Array",
+            ("JsBigInt64Array", _) => "BigInt64Array",
+            ("JsTypedArray", [item]) => typed_array(item),
+            ("JsBigInt", _) => "bigint",
+            ("JsDate", _) => "Date",
+            ("JsError", _) => "Error",
+            ("JsFunction", _) => "Function",
+            ("JsObject", _) => "object",
+            ("JsArray", _) => "unknown[]",
+            ("JsPromise", _) => "Promise<unknown>",
+            ("Handle" | "Root" | "Box" | "Rc" | "Arc", [_]) => return arg(0),
+            ("Result" | "JsResult" | "NeonResult", [_, ..]) => return arg(0),
+            ("Option", [_]) => return format!("{} | undefined", arg(0)),
+            ("Vec" | "VecDeque", [item]) => return self.array(item),
+            ("HashMap" | "BTreeMap", [_, _]) => {
+                return format!("Record<{}, {}>", arg(0), arg(1));
+            }
+            ("Future", [_]) => return format!("Promise<{}>", arg(0)),
+            (_, _) if self.interfaces.iter().any(|i| i.name == name) => name,
+            _ => "unknown",
+        };
+
+        String::from(ty)
+    }
+
+    fn array(&self, item: &str) -> String {
+        let item = self.map(item);
+
+        if item.contains(" | ") {
+            format!("({})[]", item)
+        } else {
+            format!("{}[]", item)
+        }
+    }
+}
+
+fn typed_array(item: &str) -> &'static str {
+    match item {
+        "u8" => "Uint8Array",
+        "i8" => "Int8Array",
+        "u16" => "Uint16Array",
+        "i16" => "Int16Array",
+        "u32" => "Uint32Array",
+        "i32" => "Int32Array",
+        "f32" => "Float32Array",
+        "f64" => "Float64Array",
+        "u64" => "BigUint64Array",
+        "i64" => "BigInt64Array",
+        _ => "unknown",
+    }
+}
+
+enum Type<'a> {
+    // `Name<Args>`, with the path, lifetimes and associated type names removed
+    Path(&'a str, Vec<&'a str>),
+    Tuple(Vec<&'a str>),
+    Slice(&'a str),
+}
+
+fn parse(ty: &str) -> Type<'_> {
+    let ty = strip_reference(ty.trim());
+
+    if let Some(inner) = ty.strip_prefix('(').and_then(|ty| ty.strip_suffix(')')) {
+        let items = split_args(inner);
+
+        // Parenthesized type, e.g., `(T)`
+        return match items.as_slice() {
+            [item] if !inner.trim_end().ends_with(',') => parse(item),
+            _ => Type::Tuple(items),
+        };
+    }
+
+    if let Some(inner) = ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')) {
+        // `[T]` or `[T; N]`
+        let item = inner.split(';').next().unwrap_or("").trim();
+
+        return Type::Slice(item);
+    }
+
+    let ty = ty.strip_prefix("impl ").unwrap_or(ty).trim();
+    let (path, args) = match ty.find('<') {
+        Some(i) if ty.ends_with('>') => (&ty[..i], split_args(&ty[(i + 1)..(ty.len() - 1)])),
+        _ => (ty, Vec::new()),
+    };
+
+    let name = path.rsplit("::").next().unwrap_or(path).trim();
+    let args = args
+        .into_iter()
+        .filter(|arg| !arg.starts_with('\''))
+        .map(|arg| match arg.split_once('=') {
+            // Associated type, e.g., `Output = T`
+            Some((_, ty)) => ty.trim(),
+            None => arg,
+        })
+        .collect();
+
+    Type::Path(name, args)
+}
+
+// Removes leading `&`, `&mut` and `&'a`
+fn strip_reference(mut ty: &str) -> &str {
+    while let Some(rest) = ty.strip_prefix('&') {
+        ty = rest.trim_start();
+
+        if ty.starts_with('\'') {
+            ty = ty.split_once(' ').map(|(_, ty)| ty).unwrap_or("");
+        }
+
+        ty = ty.strip_prefix("mut ").unwrap_or(ty).trim_start();
+    }
+
+    ty
+}
+
+// Splits a comma separated list at the top level of nesting
+fn split_args(list: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in list.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                args.push(list[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    args.push(list[start..].trim());
+    args.retain(|arg| !arg.is_empty());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::type_name;
+
+    use super::{Interface, Mapper};
+    use crate::{
+        handle::Handle,
+        types::{JsFloat64Array, JsString},
+    };
+
+    fn map(ty: &str) -> String {
+        let interfaces = [Interface::new("Point")];
+        let mapper = Mapper {
+            interfaces: &interfaces,
+        };
+
+        mapper.map(ty)
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(map("f64"), "number");
+        assert_eq!(map("&'a str"), "string");
+        assert_eq!(map("bool"), "boolean");
+        assert_eq!(map("()"), "undefined");
+    }
+
+    #[test]
+    fn test_handles() {
+        assert_eq!(map("Handle<'a, JsNumber>"), "number");
+        assert_eq!(map("JsResult<'_, JsBuffer>"), "Buffer");
+        assert_eq!(map("Handle<JsTypedArray<f32>>"), "Float32Array");
+        assert_eq!(map("neon::types::JsFloat64Array"), "Float64Array");
+    }
+
+    #[test]
+    fn test_containers() {
+        assert_eq!(map("Option<String>"), "string | undefined");
+        assert_eq!(map("Vec<Option<u32>>"), "(number | undefined)[]");
+        assert_eq!(map("&mut [u8]"), "number[]");
+        assert_eq!(map("(f64, String)"), "[number, string]");
+        assert_eq!(map("Result<Vec<Point>, String>"), "Point[]");
+        assert_eq!(map("HashMap<String, bool>"), "Record<string, boolean>");
+        assert_eq!(map("impl Future<Output = f64>"), "Promise<number>");
+    }
+
+    #[test]
+    fn test_type_names() {
+        assert_eq!(map(type_name::<Handle<JsFloat64Array>>()), "Float64Array");
+        assert_eq!(map(type_name::<Option<String>>()), "string | undefined");
+        assert_eq!(map(type_name::<Vec<(u8, &str)>>()), "[number, string][]");
+        assert_eq!(map(type_name::<JsString>()), "string");
+    }
+
+    #[test]
+    fn test_unknown() {
+        assert_eq!(map("Unregistered"), "unknown");
+        assert_eq!(map("Vec<Unregistered>"), "unknown[]");
+    }
+}
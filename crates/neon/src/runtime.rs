@@ -0,0 +1,292 @@
+//! Detection of the capabilities of the JavaScript runtime that loaded the addon.
+//!
+//! The `napi-*` feature flags select the minimum Node-API version an addon is built
+//! against. An addon may still run on newer runtimes, or on runtimes that only
+//! partially implement Node-API, such as Electron with the V8 memory cage enabled or
+//! alternative runtimes. [`features`] probes the running process so that code can
+//! branch on what is actually available.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! fn freeze(mut cx: FunctionContext) -> JsResult<JsObject> {
+//!     let object = cx.argument::<JsObject>(0)?;
+//!
+//!     if neon::runtime::features(&mut cx).object_freeze {
+//!         // Use `Object.freeze` from JavaScript on runtimes without `napi_object_freeze`
+//!     }
+//!
+//!     Ok(object)
+//! }
+//! ```
+//!
+//! [`Requirements`] checks the runtime against the versions an addon requires when
+//! it is loaded, throwing an error that tells users which version of Node to
+//! install.
+
+use once_cell::sync::OnceCell;
+
+use crate::{
+    context::Context,
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    sys,
+    types::{JsObject, JsString},
+};
+
+static FEATURES: OnceCell<Features> = OnceCell::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+/// Capabilities of the JavaScript runtime, as returned by [`features`].
+///
+/// Each flag indicates that the runtime provides an API, independently of the
+/// `napi-*` feature flags Neon was built with. Neon APIs still require the
+/// corresponding feature flag to be enabled.
+pub struct Features {
+    /// The Node-API version reported by the runtime.
+    pub napi_version: u32,
+    /// The runtime allows creating buffers backed by memory owned by Rust, e.g. with
+    /// [`JsBuffer::external`](crate::types::JsBuffer::external). This is `false` in
+    /// environments that enable the V8 memory cage, such as Electron 21 and later.
+    pub external_buffers: bool,
+    /// Threadsafe functions are available (Node-API 4).
+    pub threadsafe_functions: bool,
+    /// `Date` objects can be created and read (Node-API 5).
+    pub date: bool,
+    /// `BigInt` values can be created and read (Node-API 6).
+    pub bigint: bool,
+    /// Per-instance data is available (Node-API 6).
+    pub instance_data: bool,
+    /// `ArrayBuffer`s can be detached (Node-API 7).
+    pub detach_arraybuffer: bool,
+    /// Objects can be tagged with a type tag (Node-API 8).
+    pub type_tags: bool,
+    /// Objects can be frozen and sealed (Node-API 8).
+    pub object_freeze: bool,
+}
+
+/// Returns the capabilities of the JavaScript runtime.
+///
+/// The runtime is probed the first time this is called; later calls return the
+/// cached result.
+pub fn features<'a, C: Context<'a>>(cx: &mut C) -> Features {
+    *FEATURES.get_or_init(|| Features::probe(cx))
+}
+
+impl Features {
+    fn probe<'a, C: Context<'a>>(cx: &mut C) -> Self {
+        Self {
+            napi_version: sys::version(),
+            external_buffers: external_buffers_allowed(cx),
+            threadsafe_functions: sys::has_symbol("napi_create_threadsafe_function"),
+            date: sys::has_symbol("napi_create_date"),
+            bigint: sys::has_symbol("napi_create_bigint_int64"),
+            instance_data: sys::has_symbol("napi_set_instance_data"),
+            detach_arraybuffer: sys::has_symbol("napi_detach_arraybuffer"),
+            type_tags: sys::has_symbol("napi_type_tag_object"),
+            object_freeze: sys::has_symbol("napi_object_freeze"),
+        }
+    }
+}
+
+// Creating an external buffer to find out whether they are allowed could terminate the
+// process, so the runtime is identified instead. Electron enables the V8 memory cage
+// starting with version 21.
+fn external_buffers_allowed<'a, C: Context<'a>>(cx: &mut C) -> bool {
+    let electron = cx
+        .try_catch(|cx| {
+            let process: Option<Handle<JsObject>> = cx.global().get_opt(cx, "process")?;
+            let versions: Option<Handle<JsObject>> = match process {
+                Some(process) => process.get_opt(cx, "versions")?,
+                None => None,
+            };
+
+            match versions {
+                Some(versions) => versions.get_opt::<JsString, _, _>(cx, "electron"),
+                None => Ok(None),
+            }
+        })
+        .ok()
+        .flatten()
+        .map(|version| version.value(cx));
+
+    match electron {
+        Some(version) => compare_versions(&version, "21") == std::cmp::Ordering::Less,
+        None => true,
+    }
+}
+
+/// The minimum versions of Node and Node-API required by an addon.
+///
+/// Requirements are usually declared with the `#[neon::main]` attribute and checked
+/// before the addon is initialized. An addon that is loaded by an older runtime throws
+/// an `Error` from `require` instead of crashing:
+///
+/// ```
+/// # use neon::prelude::*;
+/// // Error: my-addon requires Node >= 18.0 (N-API 8); you are running Node 14.17.0 (N-API 7)
+/// #[neon::main(min_napi = 8, min_node = "18.0")]
+/// fn main(mut cx: ModuleContext) -> NeonResult<()> {
+///     Ok(())
+/// }
+/// ```
+///
+/// Requirements may also be checked explicitly:
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::runtime::Requirements;
+///
+/// #[neon::main]
+/// fn main(mut cx: ModuleContext) -> NeonResult<()> {
+///     Requirements::new(env!("CARGO_PKG_NAME"))
+///         .node_version("18.0")
+///         .check(&mut cx)?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Requirements {
+    name: String,
+    napi_version: u32,
+    node_version: Option<String>,
+}
+
+impl Requirements {
+    /// Creates the requirements of the addon `name`, initially the highest Node-API
+    /// version enabled by Neon's feature flags.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        Self {
+            name: name.into(),
+            napi_version: sys::BUILD_VERSION,
+            node_version: None,
+        }
+    }
+
+    /// Requires Node-API `version` or later. Versions lower than the one enabled by
+    /// Neon's feature flags are ignored.
+    pub fn napi_version(mut self, version: u32) -> Self {
+        self.napi_version = self.napi_version.max(version);
+        self
+    }
+
+    /// Requires Node `version` or later, e.g., `"18.0"`.
+    pub fn node_version<V: Into<String>>(mut self, version: V) -> Self {
+        self.node_version = Some(version.into());
+        self
+    }
+
+    /// Throws an `Error` if the runtime does not meet the requirements.
+    pub fn check<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<()> {
+        let node_version = cx
+            .global()
+            .get::<JsObject, _, _>(cx, "process")?
+            .get::<JsObject, _, _>(cx, "versions")?
+            .get::<JsString, _, _>(cx, "node")?
+            .value(cx);
+
+        match self.error(sys::version(), &node_version) {
+            Some(msg) => cx.throw_error(msg),
+            None => Ok(()),
+        }
+    }
+
+    // Returns the error message if the reported versions do not meet the requirements
+    fn error(&self, napi_version: u32, node_version: &str) -> Option<String> {
+        let node_too_old = match &self.node_version {
+            Some(min) => compare_versions(node_version, min) == std::cmp::Ordering::Less,
+            None => false,
+        };
+
+        if napi_version >= self.napi_version && !node_too_old {
+            return None;
+        }
+
+        let required = match &self.node_version {
+            Some(min) => format!("Node >= {} (N-API {})", min, self.napi_version),
+            None => format!("N-API {}", self.napi_version),
+        };
+
+        Some(format!(
+            "{} requires {}; you are running Node {} (N-API {})",
+            self.name, required, node_version, napi_version
+        ))
+    }
+}
+
+// Compares dotted version numbers, treating missing components as `0`
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    fn parse(version: &str) -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split('.')
+            .map(|n| n.parse().unwrap_or(0))
+            .collect()
+    }
+
+    let (a, b) = (parse(a), parse(b));
+    let len = a.len().max(b.len());
+
+    (0..len)
+        .map(|i| {
+            let a = a.get(i).copied().unwrap_or(0);
+            let b = b.get(i).copied().unwrap_or(0);
+
+            a.cmp(&b)
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Requirements;
+
+    fn requirements() -> Requirements {
+        Requirements {
+            name: String::from("my-addon"),
+            napi_version: 8,
+            node_version: Some(String::from("18.0")),
+        }
+    }
+
+    #[test]
+    fn test_requirements_met() {
+        assert_eq!(requirements().error(8, "18.0.0"), None);
+        assert_eq!(requirements().error(9, "20.11.1"), None);
+    }
+
+    #[test]
+    fn test_napi_version_too_low() {
+        assert_eq!(
+            requirements().error(7, "18.0.0").as_deref(),
+            Some("my-addon requires Node >= 18.0 (N-API 8); you are running Node 18.0.0 (N-API 7)"),
+        );
+    }
+
+    #[test]
+    fn test_node_version_too_low() {
+        assert_eq!(
+            requirements().error(8, "14.17.0").as_deref(),
+            Some(
+                "my-addon requires Node >= 18.0 (N-API 8); you are running Node 14.17.0 (N-API 8)"
+            ),
+        );
+    }
+
+    #[test]
+    fn test_without_node_version() {
+        let requirements = Requirements {
+            node_version: None,
+            ..requirements()
+        };
+
+        assert_eq!(requirements.error(8, "14.17.0"), None);
+        assert_eq!(
+            requirements.error(6, "14.17.0").as_deref(),
+            Some("my-addon requires N-API 8; you are running Node 14.17.0 (N-API 6)"),
+        );
+    }
+}
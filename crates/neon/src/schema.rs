@@ -0,0 +1,396 @@
+//! Validation of JavaScript objects against a declared set of fields.
+//!
+//! A [`Schema`] describes the fields expected on an object, such as an options
+//! argument, and the [`Kind`] of value each field holds. [`Schema::extract`] checks
+//! every field and either returns the [`ExtractedValues`] or throws a single
+//! `TypeError` describing _all_ of the invalid fields, rather than only the first.
+//!
+//! Schemas do not depend on a [`Context`] and may be built once and cached, e.g., in
+//! a `static`.
+//!
+//! # Example
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use std::sync::OnceLock;
+//!
+//! use neon::schema::{Kind, Schema};
+//!
+//! fn options_schema() -> &'static Schema {
+//!     static SCHEMA: OnceLock<Schema> = OnceLock::new();
+//!
+//!     SCHEMA.get_or_init(|| {
+//!         Schema::object()
+//!             .field("retries", Kind::Number.optional())
+//!             .field("mode", Kind::StringEnum(&["fast", "safe"]))
+//!     })
+//! }
+//!
+//! fn connect(mut cx: FunctionContext) -> JsResult<JsString> {
+//!     let options = cx.argument::<JsValue>(0)?;
+//!
+//!     // connect({ retries: "3", mode: "slow" })
+//!     // TypeError: `retries`: expected number or undefined, found string;
+//!     //            `mode`: expected one of "fast", "safe", found "slow"
+//!     let options = options_schema().extract(&mut cx, options)?;
+//!     let retries = options.number("retries").unwrap_or(3.0);
+//!     let mode = options.string("mode").unwrap_or_default();
+//!
+//!     Ok(cx.string(format!("{} ({} retries)", mode, retries)))
+//! }
+//! ```
+
+use std::fmt::{self, Write as _};
+
+use crate::{
+    context::Context,
+    handle::{Handle, Managed},
+    object::Object,
+    result::NeonResult,
+    sys,
+    types::{JsArray, JsFunction, JsObject, JsString, JsValue, Value, ValueVisitor},
+};
+
+/// The kind of value expected for a field of a [`Schema`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// Any value other than `null` or `undefined`.
+    Any,
+    /// A boolean.
+    Boolean,
+    /// A number.
+    Number,
+    /// A string.
+    String,
+    /// A string equal to one of the listed values.
+    StringEnum(&'static [&'static str]),
+    /// An object, including arrays and functions.
+    Object,
+    /// An array.
+    Array,
+    /// A function.
+    Function,
+    /// A field that may be missing, `null` or `undefined`, or else holds a value of the
+    /// inner kind. Usually created with [`Kind::optional`].
+    Optional(Box<Kind>),
+}
+
+impl Kind {
+    /// Makes the field optional.
+    pub fn optional(self) -> Self {
+        match self {
+            Kind::Optional(_) => self,
+            kind => Kind::Optional(Box::new(kind)),
+        }
+    }
+
+    // Strips `Optional`, returning the kind of present values
+    fn required(&self) -> &Kind {
+        match self {
+            Kind::Optional(kind) => kind.required(),
+            kind => kind,
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Any => f.write_str("a value"),
+            Kind::Boolean => f.write_str("boolean"),
+            Kind::Number => f.write_str("number"),
+            Kind::String => f.write_str("string"),
+            Kind::StringEnum(variants) => {
+                f.write_str("one of ")?;
+
+                for (i, variant) in variants.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    write!(f, "{:?}", variant)?;
+                }
+
+                Ok(())
+            }
+            Kind::Object => f.write_str("object"),
+            Kind::Array => f.write_str("array"),
+            Kind::Function => f.write_str("function"),
+            Kind::Optional(kind) => write!(f, "{} or undefined", kind),
+        }
+    }
+}
+
+/// A description of the fields of an object.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    fields: Vec<(String, Kind)>,
+}
+
+impl Schema {
+    /// Creates an empty schema for an object. Properties not declared with
+    /// [`Schema::field`] are ignored.
+    pub fn object() -> Self {
+        Self::default()
+    }
+
+    /// Declares a field named `name` holding a value of the given kind, replacing
+    /// any field previously declared with the same name.
+    pub fn field<N: Into<String>>(mut self, name: N, kind: Kind) -> Self {
+        let name = name.into();
+
+        match self.fields.iter_mut().find(|(n, _)| *n == name) {
+            Some(field) => field.1 = kind,
+            None => self.fields.push((name, kind)),
+        }
+
+        self
+    }
+
+    /// Reads and validates every field of `v`.
+    ///
+    /// Throws a `TypeError` if `v` is not an object, or if any field is missing or
+    /// holds a value of the wrong kind. The error lists every invalid field.
+    pub fn extract<'cx, C: Context<'cx>, V: Value>(
+        &self,
+        cx: &mut C,
+        v: Handle<'cx, V>,
+    ) -> NeonResult<ExtractedValues<'cx>> {
+        let obj = match v.downcast::<JsObject, _>(cx) {
+            Ok(obj) => obj,
+            Err(_) => {
+                let actual = type_name(cx, v.upcast());
+
+                return cx.throw_type_error(format!("expected object, found {}", actual));
+            }
+        };
+
+        let mut values = Vec::with_capacity(self.fields.len());
+        let mut violations = String::new();
+
+        for (name, kind) in &self.fields {
+            let value = obj.get_value(cx, name.as_str())?;
+
+            match read(cx, kind, value)? {
+                Ok(data) => values.push((name.clone(), value, data)),
+                Err(actual) => {
+                    if !violations.is_empty() {
+                        violations.push_str("; ");
+                    }
+
+                    let _ = write!(
+                        violations,
+                        "`{}`: expected {}, found {}",
+                        name, kind, actual
+                    );
+                }
+            }
+        }
+
+        if !violations.is_empty() {
+            return cx.throw_type_error(violations);
+        }
+
+        Ok(ExtractedValues { values })
+    }
+}
+
+/// The fields of an object that was validated by [`Schema::extract`].
+///
+/// Values are read when the object is extracted; later changes to the object are not
+/// reflected. Accessors return `None` for optional fields that were missing, `null`
+/// or `undefined`, and always return `Some` for required fields.
+///
+/// # Panics
+///
+/// Accessors panic if `name` was not declared in the schema, or was declared with a
+/// kind that cannot be read by the accessor. These are programming errors in the
+/// addon and do not depend on the JavaScript input.
+#[derive(Debug)]
+pub struct ExtractedValues<'cx> {
+    values: Vec<(String, Handle<'cx, JsValue>, Data)>,
+}
+
+#[derive(Debug)]
+enum Data {
+    Missing,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    Value,
+}
+
+impl<'cx> ExtractedValues<'cx> {
+    /// Returns the value of a field declared as [`Kind::Boolean`].
+    pub fn boolean(&self, name: &str) -> Option<bool> {
+        match self.data(name) {
+            Data::Missing => None,
+            Data::Boolean(b) => Some(*b),
+            _ => wrong_kind(name, "boolean"),
+        }
+    }
+
+    /// Returns the value of a field declared as [`Kind::Number`].
+    pub fn number(&self, name: &str) -> Option<f64> {
+        match self.data(name) {
+            Data::Missing => None,
+            Data::Number(n) => Some(*n),
+            _ => wrong_kind(name, "number"),
+        }
+    }
+
+    /// Returns the value of a field declared as [`Kind::String`] or
+    /// [`Kind::StringEnum`].
+    pub fn string(&self, name: &str) -> Option<&str> {
+        match self.data(name) {
+            Data::Missing => None,
+            Data::String(s) => Some(s),
+            _ => wrong_kind(name, "string"),
+        }
+    }
+
+    /// Returns the value of a field of any kind.
+    pub fn value(&self, name: &str) -> Option<Handle<'cx, JsValue>> {
+        let (_, value, data) = self.field(name);
+
+        match data {
+            Data::Missing => None,
+            _ => Some(*value),
+        }
+    }
+
+    fn field(&self, name: &str) -> &(String, Handle<'cx, JsValue>, Data) {
+        self.values
+            .iter()
+            .find(|(n, _, _)| n == name)
+            .unwrap_or_else(|| panic!("field `{}` is not declared in the schema", name))
+    }
+
+    fn data(&self, name: &str) -> &Data {
+        &self.field(name).2
+    }
+}
+
+fn wrong_kind(name: &str, accessor: &str) -> ! {
+    panic!("field `{}` was not declared as a {}", name, accessor)
+}
+
+// Reads a value of `kind`, returning a description of the actual value on failure
+fn read<'cx, C: Context<'cx>>(
+    cx: &mut C,
+    kind: &Kind,
+    v: Handle<'cx, JsValue>,
+) -> NeonResult<Result<Data, String>> {
+    v.visit(cx, &mut Reader(kind))
+}
+
+// Visits a value with a single type check instead of a downcast per kind
+struct Reader<'k>(&'k Kind);
+
+impl Reader<'_> {
+    // `null` and `undefined` are only accepted by optional kinds
+    fn missing(&self, actual: &str) -> NeonResult<Result<Data, String>> {
+        Ok(match self.0 {
+            Kind::Optional(_) => Ok(Data::Missing),
+            _ => Err(actual.to_string()),
+        })
+    }
+
+    // Accepts a value with `Kind::Any` or one of the `accepted` kinds
+    fn value(&self, accepted: &[Kind], actual: &str) -> NeonResult<Result<Data, String>> {
+        let kind = self.0.required();
+
+        Ok(if *kind == Kind::Any || accepted.contains(kind) {
+            Ok(Data::Value)
+        } else {
+            Err(actual.to_string())
+        })
+    }
+}
+
+impl<'cx> ValueVisitor<'cx> for Reader<'_> {
+    type Output = Result<Data, String>;
+
+    fn visit_undefined<C: Context<'cx>>(&mut self, _cx: &mut C) -> NeonResult<Self::Output> {
+        self.missing("undefined")
+    }
+
+    fn visit_null<C: Context<'cx>>(&mut self, _cx: &mut C) -> NeonResult<Self::Output> {
+        self.missing("null")
+    }
+
+    fn visit_boolean<C: Context<'cx>>(&mut self, _cx: &mut C, b: bool) -> NeonResult<Self::Output> {
+        match self.0.required() {
+            Kind::Boolean => Ok(Ok(Data::Boolean(b))),
+            _ => self.value(&[], "boolean"),
+        }
+    }
+
+    fn visit_number<C: Context<'cx>>(&mut self, _cx: &mut C, n: f64) -> NeonResult<Self::Output> {
+        match self.0.required() {
+            Kind::Number => Ok(Ok(Data::Number(n))),
+            _ => self.value(&[], "number"),
+        }
+    }
+
+    fn visit_string<C: Context<'cx>>(
+        &mut self,
+        cx: &mut C,
+        s: Handle<'cx, JsString>,
+    ) -> NeonResult<Self::Output> {
+        match self.0.required() {
+            Kind::String => Ok(Ok(Data::String(s.value(cx)))),
+            Kind::StringEnum(variants) => {
+                let s = s.value(cx);
+
+                Ok(if variants.contains(&s.as_str()) {
+                    Ok(Data::String(s))
+                } else {
+                    Err(format!("{:?}", s))
+                })
+            }
+            _ => self.value(&[], "string"),
+        }
+    }
+
+    fn visit_array<C: Context<'cx>>(
+        &mut self,
+        _cx: &mut C,
+        _array: Handle<'cx, JsArray>,
+    ) -> NeonResult<Self::Output> {
+        self.value(&[Kind::Object, Kind::Array], "object")
+    }
+
+    fn visit_function<C: Context<'cx>>(
+        &mut self,
+        _cx: &mut C,
+        _f: Handle<'cx, JsFunction>,
+    ) -> NeonResult<Self::Output> {
+        self.value(&[Kind::Function], "function")
+    }
+
+    fn visit_object<C: Context<'cx>>(
+        &mut self,
+        _cx: &mut C,
+        _obj: Handle<'cx, JsObject>,
+    ) -> NeonResult<Self::Output> {
+        self.value(&[Kind::Object], "object")
+    }
+
+    // Symbols, `BigInt` values and boxed values
+    fn visit_other<C: Context<'cx>>(
+        &mut self,
+        cx: &mut C,
+        v: Handle<'cx, JsValue>,
+    ) -> NeonResult<Self::Output> {
+        let actual = type_name(cx, v);
+
+        self.value(&[], actual)
+    }
+}
+
+fn type_name<'cx, C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> &'static str {
+    unsafe { sys::tag::type_name(cx.env().to_raw(), v.to_raw()) }
+}
@@ -0,0 +1,396 @@
+//! Bridges between Rust and Node [streams](https://nodejs.org/api/stream.html).
+//!
+//! Streams created by this module are instances of the classes from the `stream`
+//! module of the current runtime, and can be used anywhere a JavaScript stream is
+//! expected, e.g., with `pipe` or `stream.pipeline`.
+
+use std::{cell::RefCell, fmt, rc::Rc};
+
+use crate::{
+    context::{Context, FunctionContext},
+    event::Channel,
+    handle::{Handle, Root},
+    object::Object,
+    reflect,
+    result::{JsResult, NeonResult},
+    types::{
+        buffer::TypedArray, JsBoolean, JsBuffer, JsFunction, JsNull, JsObject, JsUndefined,
+        JsValue, Value,
+    },
+};
+
+/// Creates a [`Readable`](https://nodejs.org/api/stream.html#class-streamreadable)
+/// stream pulling its data from Rust.
+///
+/// `pull` is called on the JavaScript thread with the number of bytes the stream would
+/// like to read, and returns the next chunk, or `None` at the end of the data. Chunks
+/// may be of any size. `pull` is called repeatedly until the stream's internal buffer
+/// is full, and again once it has been drained by a consumer, so data is only generated
+/// as fast as it is read.
+///
+/// If `pull` returns an error, the stream is destroyed with an `Error` with its message.
+/// `pull` is dropped after the end of the data or an error.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// fn count_to(mut cx: FunctionContext) -> JsResult<JsObject> {
+///     let end = cx.argument::<JsNumber>(0)?.value(&mut cx) as u64;
+///     let mut n = 0;
+///
+///     neon::stream::readable(&mut cx, move |_size| {
+///         if n >= end {
+///             return Ok::<_, String>(None);
+///         }
+///
+///         n += 1;
+///
+///         Ok(Some(format!("{}\n", n).into_bytes()))
+///     })
+/// }
+/// ```
+pub fn readable<'a, C, F, E>(cx: &mut C, pull: F) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+    F: FnMut(usize) -> Result<Option<Vec<u8>>, E> + 'static,
+    E: fmt::Display,
+{
+    let mut pull = Some(pull);
+    let read = JsFunction::new_mut(cx, move |mut cx| {
+        let size = cx
+            .argument::<JsValue>(0)?
+            .coerce_to_number(&mut cx)?
+            .value(&mut cx);
+        let size = if size.is_finite() && size > 0.0 {
+            size as usize
+        } else {
+            0
+        };
+
+        let this = cx.this::<JsObject>()?;
+        let push = this.get::<JsFunction, _, _>(&mut cx, "push")?;
+
+        while let Some(f) = pull.as_mut() {
+            let chunk = match f(size) {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => {
+                    pull = None;
+
+                    let eof = cx.null();
+
+                    push.exec(&mut cx, this, [eof.upcast()])?;
+                    break;
+                }
+                Err(err) => {
+                    pull = None;
+
+                    let destroy = this.get::<JsFunction, _, _>(&mut cx, "destroy")?;
+                    let err = cx.error(err.to_string())?;
+
+                    destroy.exec(&mut cx, this, [err.upcast()])?;
+                    break;
+                }
+            };
+
+            let is_empty = chunk.is_empty();
+            let chunk = JsBuffer::from_slice(&mut cx, &chunk)?;
+            let more = push
+                .call(&mut cx, this, [chunk.upcast()])?
+                .downcast_or_throw::<JsBoolean, _>(&mut cx)?
+                .value(&mut cx);
+
+            // Wait to be called again when the stream is ready for more data. Empty
+            // chunks are not added to the buffer, so they never fill it.
+            if !more || is_empty {
+                break;
+            }
+        }
+
+        Ok(cx.undefined())
+    })?;
+
+    let options = cx.empty_object();
+
+    options.set(cx, "read", read)?;
+
+    construct(cx, "Readable", options)
+}
+
+/// Options for creating a [`Writable`](https://nodejs.org/api/stream.html#class-streamwritable)
+/// stream with [`writable`] or [`writable_with_ack`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WritableOptions {
+    high_water_mark: Option<usize>,
+}
+
+impl WritableOptions {
+    /// Creates options with the stream defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of bytes that may be buffered, waiting to be written, before
+    /// `write` returns `false` and producers should wait for a `drain` event.
+    pub fn high_water_mark(mut self, high_water_mark: usize) -> Self {
+        self.high_water_mark = Some(high_water_mark);
+        self
+    }
+}
+
+/// Creates a [`Writable`](https://nodejs.org/api/stream.html#class-streamwritable)
+/// stream passing each chunk written to it to Rust.
+///
+/// `on_chunk` is called on the JavaScript thread with each chunk, in order. The next
+/// chunk is not written until `on_chunk` returns; if it returns an error, the stream
+/// is destroyed with an `Error` with its message.
+///
+/// `on_finish` is called once, with `Ok` after the stream has ended and every chunk
+/// has been written, or with an error message if the stream was destroyed first.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::stream::WritableOptions;
+///
+/// // Returns a stream that writes to standard output
+/// fn stdout(mut cx: FunctionContext) -> JsResult<JsObject> {
+///     use std::io::Write;
+///
+///     neon::stream::writable(
+///         &mut cx,
+///         WritableOptions::new(),
+///         |chunk| std::io::stdout().write_all(chunk),
+///         |_cx, _result| Ok(()),
+///     )
+/// }
+/// ```
+pub fn writable<'a, C, W, F, E>(
+    cx: &mut C,
+    options: WritableOptions,
+    mut on_chunk: W,
+    on_finish: F,
+) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+    W: FnMut(&[u8]) -> Result<(), E> + 'static,
+    F: for<'b> FnOnce(&mut FunctionContext<'b>, Result<(), String>) -> NeonResult<()> + 'static,
+    E: fmt::Display,
+{
+    new_writable(
+        cx,
+        options,
+        move |cx, chunk, callback| {
+            let result = on_chunk(chunk.as_slice(cx)).map_err(|err| err.to_string());
+
+            complete_write(cx, callback, result)
+        },
+        on_finish,
+    )
+}
+
+/// Creates a [`Writable`](https://nodejs.org/api/stream.html#class-streamwritable)
+/// stream passing each chunk written to it to Rust, to be acknowledged asynchronously.
+///
+/// `on_chunk` is called on the JavaScript thread with a copy of each chunk and a
+/// [`WriteAck`], which may be sent to another thread, e.g., with a
+/// [`std::sync::mpsc::Sender`]. The next chunk is not written until the ack has been
+/// completed with [`WriteAck::ack`], so a slow consumer applies backpressure to
+/// the producer.
+///
+/// `on_finish` is called once, with `Ok` after the stream has ended and every chunk
+/// has been acknowledged, or with an error message if the stream was destroyed first.
+pub fn writable_with_ack<'a, C, W, F>(
+    cx: &mut C,
+    options: WritableOptions,
+    mut on_chunk: W,
+    on_finish: F,
+) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+    W: FnMut(Vec<u8>, WriteAck) + 'static,
+    F: for<'b> FnOnce(&mut FunctionContext<'b>, Result<(), String>) -> NeonResult<()> + 'static,
+{
+    let mut channel = cx.channel();
+
+    // Only outstanding acks keep the event loop alive
+    channel.unref(cx);
+
+    new_writable(
+        cx,
+        options,
+        move |cx, chunk, callback| {
+            let data = chunk.as_slice(cx).to_vec();
+            let mut channel = channel.clone();
+
+            channel.reference(cx);
+            on_chunk(
+                data,
+                WriteAck {
+                    state: Some((callback.root(cx), channel)),
+                },
+            );
+
+            Ok(())
+        },
+        on_finish,
+    )
+}
+
+/// Acknowledges that a chunk passed to the `on_chunk` callback of
+/// [`writable_with_ack`] has been written.
+///
+/// A `WriteAck` may be sent across threads. Dropping it without calling
+/// [`WriteAck::ack`] fails the write, destroying the stream.
+pub struct WriteAck {
+    // `Option` so that the ack is only completed once
+    state: Option<(Root<JsFunction>, Channel)>,
+}
+
+impl fmt::Debug for WriteAck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("WriteAck")
+    }
+}
+
+impl WriteAck {
+    /// Completes the write, allowing the next chunk to be written. If `result` is an
+    /// error, the stream is destroyed with an `Error` with its message.
+    pub fn ack<E: fmt::Display>(mut self, result: Result<(), E>) {
+        self.complete(result.map_err(|err| err.to_string()));
+    }
+
+    fn complete(&mut self, result: Result<(), String>) {
+        if let Some((callback, channel)) = self.state.take() {
+            // `Channel::try_send` will only fail if the environment has shutdown.
+            // In that case, the teardown will perform clean-up.
+            let _ = channel.try_send(move |mut cx| {
+                let callback = callback.into_inner(&mut cx);
+
+                complete_write(&mut cx, callback, result)
+            });
+        }
+    }
+}
+
+impl Drop for WriteAck {
+    fn drop(&mut self) {
+        self.complete(Err("chunk was dropped without being acknowledged".into()));
+    }
+}
+
+// Calls the callback passed to `Writable#_write`
+fn complete_write<'a, C: Context<'a>>(
+    cx: &mut C,
+    callback: Handle<JsFunction>,
+    result: Result<(), String>,
+) -> NeonResult<()> {
+    match result {
+        Ok(()) => callback.call0(cx)?,
+        Err(msg) => {
+            let err = cx.error(msg)?;
+
+            callback.call1(cx, err)?
+        }
+    };
+
+    Ok(())
+}
+
+fn new_writable<'a, C, W, F>(
+    cx: &mut C,
+    options: WritableOptions,
+    mut write: W,
+    on_finish: F,
+) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+    W: for<'b> FnMut(
+            &mut FunctionContext<'b>,
+            Handle<'b, JsBuffer>,
+            Handle<'b, JsFunction>,
+        ) -> NeonResult<()>
+        + 'static,
+    F: for<'b> FnOnce(&mut FunctionContext<'b>, Result<(), String>) -> NeonResult<()> + 'static,
+{
+    // Shared by `final` and `destroy`, whichever is called first
+    let on_finish = Rc::new(RefCell::new(Some(on_finish)));
+
+    let write = JsFunction::new_mut(cx, move |mut cx| {
+        let chunk = cx.argument::<JsBuffer>(0)?;
+        let callback = cx.argument::<JsFunction>(2)?;
+
+        write(&mut cx, chunk, callback)?;
+
+        Ok(cx.undefined())
+    })?;
+
+    let final_ = {
+        let on_finish = on_finish.clone();
+
+        JsFunction::new(cx, move |mut cx| {
+            let callback = cx.argument::<JsFunction>(0)?;
+            let f = on_finish.borrow_mut().take();
+
+            if let Some(f) = f {
+                f(&mut cx, Ok(()))?;
+            }
+
+            complete_write(&mut cx, callback, Ok(()))?;
+
+            Ok(cx.undefined())
+        })?
+    };
+
+    let destroy = JsFunction::new(cx, move |mut cx| {
+        let err = cx.argument::<JsValue>(0)?;
+        let callback = cx.argument::<JsFunction>(1)?;
+        let f = on_finish.borrow_mut().take();
+
+        if let Some(f) = f {
+            let msg = if let Ok(err) = err.downcast::<JsObject, _>(&mut cx) {
+                err.get_value(&mut cx, "message")?
+                    .coerce_to_string(&mut cx)?
+                    .value(&mut cx)
+            } else if err.is_a::<JsUndefined, _>(&mut cx) || err.is_a::<JsNull, _>(&mut cx) {
+                String::from("stream was destroyed before finishing")
+            } else {
+                err.coerce_to_string(&mut cx)?.value(&mut cx)
+            };
+
+            f(&mut cx, Err(msg))?;
+        }
+
+        callback.call1(&mut cx, err)?;
+
+        Ok(cx.undefined())
+    })?;
+
+    let js_options = cx.empty_object();
+
+    js_options.set(cx, "write", write)?;
+    js_options.set(cx, "final", final_)?;
+    js_options.set(cx, "destroy", destroy)?;
+
+    if let Some(high_water_mark) = options.high_water_mark {
+        let high_water_mark = cx.number(high_water_mark as f64);
+
+        js_options.set(cx, "highWaterMark", high_water_mark)?;
+    }
+
+    construct(cx, "Writable", js_options)
+}
+
+// Constructs a class from the `stream` module of the current runtime
+fn construct<'a, C: Context<'a>>(
+    cx: &mut C,
+    class: &str,
+    options: Handle<JsObject>,
+) -> JsResult<'a, JsObject> {
+    // The `stream` module is the legacy `Stream` class
+    let stream = reflect::builtin_module::<JsFunction, _>(cx, "stream")?;
+    let class = stream.get::<JsFunction, _, _>(cx, class)?;
+
+    class.construct(cx, [options.upcast::<JsValue>()])
+}
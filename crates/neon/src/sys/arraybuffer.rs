@@ -21,7 +21,9 @@ pub unsafe fn new(env: Env, len: usize) -> Result<Local, napi::Status> {
 }
 
 #[cfg(feature = "external-buffers")]
-pub unsafe fn new_external<T>(env: Env, data: T) -> Local
+/// Creates a buffer backed by `data`, returning `data` if the runtime does not allow
+/// external buffers (e.g., Electron with the V8 memory cage)
+pub unsafe fn new_external<T>(env: Env, data: T) -> Result<Local, T>
 where
     T: AsMut<[u8]> + Send,
 {
@@ -29,21 +31,26 @@ where
     let mut data = Box::new(data);
     let buf = data.as_mut().as_mut();
     let length = buf.len();
+    let ptr = buf.as_mut_ptr();
     let mut result = MaybeUninit::uninit();
-
-    assert_eq!(
-        napi::create_external_arraybuffer(
-            env,
-            buf.as_mut_ptr() as *mut _,
-            length,
-            Some(drop_external::<T>),
-            Box::into_raw(data) as *mut _,
-            result.as_mut_ptr(),
-        ),
-        napi::Status::Ok,
+    let data = Box::into_raw(data);
+    let status = napi::create_external_arraybuffer(
+        env,
+        ptr as *mut _,
+        length,
+        Some(drop_external::<T>),
+        data as *mut _,
+        result.as_mut_ptr(),
     );
 
-    result.assume_init()
+    // The finalizer is not called when external buffers are disallowed; reclaim `data`
+    if status == napi::Status::NoExternalBuffersAllowed {
+        return Err(*Box::from_raw(data));
+    }
+
+    assert_eq!(status, napi::Status::Ok);
+
+    Ok(result.assume_init())
 }
 
 #[cfg(feature = "external-buffers")]
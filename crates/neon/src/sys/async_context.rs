@@ -0,0 +1,96 @@
+//! Rust wrappers for Node-API custom asynchronous operations
+//!
+//! See: [Custom asynchronous operations](https://nodejs.org/api/n-api.html#custom-asynchronous-operations)
+
+use std::{ffi::c_void, mem::MaybeUninit};
+
+use super::{
+    bindings as napi,
+    raw::{Env, Local},
+};
+
+/// Create an async context for `resource`, emitting an `init` async hook
+///
+/// # Safety
+/// * `env` is a valid `napi_env` for the current thread
+/// * `resource` is an object and `name` is a string
+pub unsafe fn init(env: Env, resource: Local, name: Local) -> napi::AsyncContext {
+    let mut context = MaybeUninit::uninit();
+
+    assert_eq!(
+        napi::async_init(env, resource, name, context.as_mut_ptr()),
+        napi::Status::Ok,
+    );
+
+    context.assume_init()
+}
+
+/// Destroy an async context, emitting a `destroy` async hook
+///
+/// # Safety
+/// * `env` is a valid `napi_env` for the current thread
+/// * `context` was created by `init` in the same module instance and has not been destroyed
+pub unsafe fn destroy(env: Env, context: napi::AsyncContext) {
+    assert_eq!(napi::async_destroy(env, context), napi::Status::Ok);
+}
+
+/// Call a function in the async context, emitting `before` and `after` async hooks
+/// and running the microtask queue if the JavaScript stack is empty
+///
+/// Returns `false` if the function threw
+///
+/// # Safety
+/// * `env` is a valid `napi_env` for the current thread
+/// * `argv` points to `argc` valid values
+pub unsafe fn make_callback(
+    out: &mut Local,
+    env: Env,
+    context: napi::AsyncContext,
+    this: Local,
+    fun: Local,
+    argc: usize,
+    argv: *const c_void,
+) -> bool {
+    let status = napi::make_callback(
+        env,
+        context,
+        this,
+        fun,
+        argc,
+        argv as *const _,
+        out as *mut _,
+    );
+
+    status == napi::Status::Ok
+}
+
+#[cfg(feature = "napi-3")]
+/// Open a callback scope for calling into JavaScript in the async context
+///
+/// # Safety
+/// * `env` is a valid `napi_env` for the current thread
+/// * The scope must be closed with `close_scope` before the enclosing handle scope
+pub unsafe fn open_scope(
+    env: Env,
+    resource: Local,
+    context: napi::AsyncContext,
+) -> napi::CallbackScope {
+    let mut scope = MaybeUninit::uninit();
+
+    assert_eq!(
+        napi::open_callback_scope(env, resource, context, scope.as_mut_ptr()),
+        napi::Status::Ok,
+    );
+
+    scope.assume_init()
+}
+
+#[cfg(feature = "napi-3")]
+/// Close a callback scope opened with `open_scope`
+///
+/// # Safety
+/// * `env` is a valid `napi_env` for the current thread
+/// * `scope` is the most recently opened callback scope
+pub unsafe fn close_scope(env: Env, scope: napi::CallbackScope) {
+    assert_eq!(napi::close_callback_scope(env, scope), napi::Status::Ok);
+}
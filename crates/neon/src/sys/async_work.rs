@@ -9,44 +9,46 @@
 
 use std::{
     ffi::c_void,
-    mem,
+    fmt, mem,
     panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
     ptr, thread,
 };
 
 use super::{bindings as napi, no_panic::FailureBoundary, raw::Env};
 
-const BOUNDARY: FailureBoundary = FailureBoundary {
-    both: "A panic and exception occurred while executing a `neon::event::TaskBuilder` task",
-    exception: "An exception occurred while executing a `neon::event::TaskBuilder` task",
-    panic: "A panic occurred while executing a `neon::event::TaskBuilder` task",
-};
+pub const ACTION: &str = "executing a `neon::event::TaskBuilder` task";
 
 type Execute<I, O> = fn(input: I) -> O;
-type Complete<O, D> = fn(env: Env, output: thread::Result<O>, data: D);
+type Complete<O, D, L> = fn(env: Env, output: thread::Result<O>, data: D, label: &L);
 
 /// Schedule work to execute on the libuv thread pool
 ///
+/// `label` identifies the work in the messages of failures in `complete` and is
+/// passed to `complete`.
+///
 /// # Safety
 /// * `env` must be a valid `napi_env` for the current thread
 /// * The `thread::Result::Err` must only be used for resuming unwind if
 ///   `execute` is not unwind safe
-pub unsafe fn schedule<I, O, D>(
+pub unsafe fn schedule<I, O, D, L>(
     env: Env,
     input: I,
     execute: Execute<I, O>,
-    complete: Complete<O, D>,
+    complete: Complete<O, D, L>,
     data: D,
+    label: L,
 ) where
     I: Send + 'static,
     O: Send + 'static,
     D: Send + 'static,
+    L: fmt::Display + Send + 'static,
 {
     let mut data = Box::new(Data {
         state: State::Input(input),
         execute,
         complete,
         data,
+        label,
         // Work is initialized as a null pointer, but set by `create_async_work`
         // `data` must not be used until this value has been set.
         work: ptr::null_mut(),
@@ -61,8 +63,8 @@ pub unsafe fn schedule<I, O, D>(
             env,
             ptr::null_mut(),
             super::string(env, "neon_async_work"),
-            Some(call_execute::<I, O, D>),
-            Some(call_complete::<I, O, D>),
+            Some(call_execute::<I, O, D, L>),
+            Some(call_complete::<I, O, D, L>),
             Box::into_raw(data).cast(),
             work,
         ),
@@ -81,11 +83,12 @@ pub unsafe fn schedule<I, O, D>(
 }
 
 /// A pointer to data is passed to the `execute` and `complete` callbacks
-struct Data<I, O, D> {
+struct Data<I, O, D, L> {
     state: State<I, O>,
     execute: Execute<I, O>,
-    complete: Complete<O, D>,
+    complete: Complete<O, D, L>,
     data: D,
+    label: L,
     work: napi::AsyncWork,
 }
 
@@ -121,9 +124,9 @@ impl<I, O> State<I, O> {
 ///
 /// # Safety
 /// * `Env` should not be used because it could attempt to call JavaScript
-/// * `data` is expected to be a pointer to `Data<I, O, D>`
-unsafe extern "C" fn call_execute<I, O, D>(_: Env, data: *mut c_void) {
-    let data = &mut *data.cast::<Data<I, O, D>>();
+/// * `data` is expected to be a pointer to `Data<I, O, D, L>`
+unsafe extern "C" fn call_execute<I, O, D, L>(_: Env, data: *mut c_void) {
+    let data = &mut *data.cast::<Data<I, O, D, L>>();
 
     // This is unwind safe because unwinding will resume on the other side
     let output = catch_unwind(AssertUnwindSafe(|| {
@@ -140,19 +143,29 @@ unsafe extern "C" fn call_execute<I, O, D>(_: Env, data: *mut c_void) {
 /// Callback executed on the JavaScript main thread
 ///
 /// # Safety
-/// * `data` is expected to be a pointer to `Data<I, O, D>`
-unsafe extern "C" fn call_complete<I, O, D>(env: Env, status: napi::Status, data: *mut c_void) {
+/// * `data` is expected to be a pointer to `Data<I, O, D, L>`
+unsafe extern "C" fn call_complete<I, O, D, L>(env: Env, status: napi::Status, data: *mut c_void)
+where
+    L: fmt::Display,
+{
     let Data {
         state,
         complete,
         data,
+        label,
         work,
         ..
-    } = *Box::<Data<I, O, D>>::from_raw(data.cast());
+    } = *Box::<Data<I, O, D, L>>::from_raw(data.cast());
 
     napi::delete_async_work(env, work);
 
-    BOUNDARY.catch_failure(env, None, move |env| {
+    let label = &label;
+    let boundary = FailureBoundary {
+        action: ACTION,
+        detail: Some(label),
+    };
+
+    boundary.catch_failure(env, None, move |env| {
         // `unwrap` is okay because `call_complete` should be called exactly once
         // if and only if `call_execute` has completed successfully
         let output = state.into_output().unwrap();
@@ -170,7 +183,7 @@ unsafe extern "C" fn call_complete<I, O, D>(env: Env, status: napi::Status, data
         };
 
         match status {
-            napi::Status::Ok => complete(env, output, data),
+            napi::Status::Ok => complete(env, output, data, label),
             napi::Status::Cancelled => {}
             _ => assert_eq!(status, napi::Status::Ok),
         }
@@ -16,6 +16,8 @@ mod napi1 {
 
             fn create_double(env: Env, value: f64, result: *mut Value) -> Status;
 
+            fn create_symbol(env: Env, description: Value, result: *mut Value) -> Status;
+
             fn create_object(env: Env, result: *mut Value) -> Status;
 
             fn get_value_bool(env: Env, value: Value, result: *mut bool) -> Status;
@@ -30,6 +32,12 @@ mod napi1 {
 
             fn coerce_to_string(env: Env, value: Value, result: *mut Value) -> Status;
 
+            fn coerce_to_number(env: Env, value: Value, result: *mut Value) -> Status;
+
+            fn coerce_to_bool(env: Env, value: Value, result: *mut Value) -> Status;
+
+            fn coerce_to_object(env: Env, value: Value, result: *mut Value) -> Status;
+
             fn throw(env: Env, error: Value) -> Status;
 
             fn create_error(env: Env, code: Value, msg: Value, result: *mut Value) -> Status;
@@ -57,6 +65,9 @@ mod napi1 {
             fn is_array(env: Env, value: Value, result: *mut bool) -> Status;
             fn is_promise(env: Env, value: Value, result: *mut bool) -> Status;
 
+            fn instanceof(env: Env, object: Value, constructor: Value, result: *mut bool)
+                -> Status;
+
             fn get_value_string_utf8(
                 env: Env,
                 value: Value,
@@ -87,6 +98,20 @@ mod napi1 {
                 result: *mut Value,
             ) -> Status;
 
+            fn create_string_latin1(
+                env: Env,
+                str: *const c_char,
+                length: usize,
+                result: *mut Value,
+            ) -> Status;
+
+            fn create_string_utf16(
+                env: Env,
+                str: *const u16,
+                length: usize,
+                result: *mut Value,
+            ) -> Status;
+
             fn create_arraybuffer(
                 env: Env,
                 byte_length: usize,
@@ -181,10 +206,25 @@ mod napi1 {
 
             fn get_property(env: Env, object: Value, key: Value, result: *mut Value) -> Status;
 
+            fn has_property(env: Env, object: Value, key: Value, result: *mut bool) -> Status;
+
+            fn delete_property(env: Env, object: Value, key: Value, result: *mut bool) -> Status;
+
+            fn define_properties(
+                env: Env,
+                object: Value,
+                property_count: usize,
+                properties: *const PropertyDescriptor,
+            ) -> Status;
+
             fn set_element(env: Env, object: Value, index: u32, value: Value) -> Status;
 
             fn get_element(env: Env, object: Value, index: u32, result: *mut Value) -> Status;
 
+            fn has_element(env: Env, object: Value, index: u32, result: *mut bool) -> Status;
+
+            fn delete_element(env: Env, object: Value, index: u32, result: *mut bool) -> Status;
+
             fn escape_handle(
                 env: Env,
                 scope: EscapableHandleScope,
@@ -219,7 +259,7 @@ mod napi1 {
                 result: *mut Value,
             ) -> Status;
 
-            #[cfg(feature = "external-buffers")]
+            #[cfg(any(feature = "external-buffers", feature = "bytes"))]
             fn create_external_buffer(
                 env: Env,
                 length: usize,
@@ -247,6 +287,25 @@ mod napi1 {
             fn resolve_deferred(env: Env, deferred: Deferred, resolution: Value) -> Status;
             fn reject_deferred(env: Env, deferred: Deferred, rejection: Value) -> Status;
 
+            fn async_init(
+                env: Env,
+                async_resource: Value,
+                async_resource_name: Value,
+                result: *mut AsyncContext,
+            ) -> Status;
+
+            fn async_destroy(env: Env, async_context: AsyncContext) -> Status;
+
+            fn make_callback(
+                env: Env,
+                async_context: AsyncContext,
+                recv: Value,
+                func: Value,
+                argc: usize,
+                argv: *const Value,
+                result: *mut Value,
+            ) -> Status;
+
             fn fatal_error(
                 location: *const c_char,
                 location_len: usize,
@@ -257,6 +316,24 @@ mod napi1 {
     );
 }
 
+#[cfg(feature = "napi-3")]
+mod napi3 {
+    use super::super::types::*;
+
+    generate!(
+        extern "C" {
+            fn open_callback_scope(
+                env: Env,
+                resource_object: Value,
+                context: AsyncContext,
+                result: *mut CallbackScope,
+            ) -> Status;
+
+            fn close_callback_scope(env: Env, scope: CallbackScope) -> Status;
+        }
+    );
+}
+
 #[cfg(feature = "napi-4")]
 mod napi4 {
     use super::super::types::*;
@@ -346,6 +423,8 @@ mod napi6 {
 
             fn get_instance_data(env: Env, data: *mut *mut c_void) -> Status;
 
+            fn add_env_cleanup_hook(env: Env, fun: CleanupHook, arg: *mut c_void) -> Status;
+
             fn create_bigint_int64(env: Env, value: i64, result: *mut Value) -> Status;
 
             fn create_bigint_uint64(env: Env, value: u64, result: *mut Value) -> Status;
@@ -403,6 +482,8 @@ mod napi8 {
 }
 
 pub(crate) use napi1::*;
+#[cfg(feature = "napi-3")]
+pub(crate) use napi3::*;
 #[cfg(feature = "napi-4")]
 pub(crate) use napi4::*;
 #[cfg(feature = "napi-5")]
@@ -412,7 +493,14 @@ pub(crate) use napi6::*;
 #[cfg(feature = "napi-8")]
 pub(crate) use napi8::*;
 
-use super::{Env, Status};
+use std::{
+    os::raw::{c_char, c_void},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use once_cell::sync::OnceCell;
+
+use super::{Env, Finalize, Status, Value};
 
 // This symbol is loaded separately because it is a prerequisite
 unsafe fn get_version(host: &libloading::Library, env: Env) -> Result<u32, libloading::Error> {
@@ -424,18 +512,157 @@ unsafe fn get_version(host: &libloading::Library, env: Env) -> Result<u32, liblo
     Ok(version)
 }
 
-pub(super) unsafe fn load(env: Env) -> Result<(), libloading::Error> {
+fn host() -> Result<libloading::Library, libloading::Error> {
     #[cfg(not(windows))]
     let host = libloading::os::unix::Library::this().into();
     #[cfg(windows)]
     let host = libloading::os::windows::Library::this()?.into();
 
+    Ok(host)
+}
+
+/// Node-API version reported by the host process when the bindings were loaded
+static VERSION: AtomicU32 = AtomicU32::new(0);
+
+/// Highest Node-API version enabled by feature flags
+pub(crate) const BUILD_VERSION: u32 = if cfg!(feature = "napi-8") {
+    8
+} else if cfg!(feature = "napi-7") {
+    7
+} else if cfg!(feature = "napi-6") {
+    6
+} else if cfg!(feature = "napi-5") {
+    5
+} else if cfg!(feature = "napi-4") {
+    4
+} else if cfg!(feature = "napi-3") {
+    3
+} else if cfg!(feature = "napi-2") {
+    2
+} else {
+    1
+};
+
+/// Returns the Node-API version reported by the host process, or `0` if the bindings
+/// have not been loaded
+pub(crate) fn version() -> u32 {
+    VERSION.load(Ordering::Relaxed)
+}
+
+/// Checks whether the host process exports a Node-API symbol, e.g. `napi_object_freeze`
+pub(crate) fn has_symbol(name: &str) -> bool {
+    match host() {
+        Ok(host) => unsafe { host.get::<*const c_void>(name.as_bytes()).is_ok() },
+        Err(_) => false,
+    }
+}
+
+type GetModuleFileName = unsafe extern "C" fn(env: Env, result: *mut *const c_char) -> Status;
+
+static GET_MODULE_FILE_NAME: OnceCell<Option<GetModuleFileName>> = OnceCell::new();
+
+/// Calls `node_api_get_module_file_name` (Node-API 9), returning `None` if the runtime
+/// does not provide it
+///
+/// This symbol is loaded separately because it is newer than any supported Node-API
+/// version feature and must be handled gracefully when missing.
+pub(crate) unsafe fn get_module_file_name(env: Env, result: *mut *const c_char) -> Option<Status> {
+    let f = GET_MODULE_FILE_NAME.get_or_init(|| {
+        if version() < 9 {
+            return None;
+        }
+
+        let host = host().ok()?;
+        let f = host
+            .get::<GetModuleFileName>(b"node_api_get_module_file_name")
+            .ok()?;
+
+        Some(*f)
+    });
+
+    f.map(|f| f(env, result))
+}
+
+type CreateExternalString<T> = unsafe extern "C" fn(
+    env: Env,
+    str: *mut T,
+    length: usize,
+    finalize_cb: Finalize,
+    finalize_hint: *mut c_void,
+    result: *mut Value,
+    copied: *mut bool,
+) -> Status;
+
+static CREATE_EXTERNAL_STRING_LATIN1: OnceCell<Option<CreateExternalString<c_char>>> =
+    OnceCell::new();
+
+static CREATE_EXTERNAL_STRING_UTF16: OnceCell<Option<CreateExternalString<u16>>> = OnceCell::new();
+
+unsafe fn load_optional<T: Copy>(name: &[u8]) -> Option<T> {
+    let host = host().ok()?;
+    let f = host.get::<T>(name).ok()?;
+
+    Some(*f)
+}
+
+/// Calls `node_api_create_external_string_latin1` (Node-API 10), returning `None` if
+/// the runtime does not provide it
+///
+/// Like `node_api_get_module_file_name`, this symbol is newer than any supported
+/// Node-API version feature. Runtimes export it before reporting Node-API 10, so only
+/// its presence is checked.
+pub(crate) unsafe fn create_external_string_latin1(
+    env: Env,
+    str: *mut c_char,
+    length: usize,
+    finalize_cb: Finalize,
+    finalize_hint: *mut c_void,
+    result: *mut Value,
+    copied: *mut bool,
+) -> Option<Status> {
+    let f = CREATE_EXTERNAL_STRING_LATIN1
+        .get_or_init(|| load_optional(b"node_api_create_external_string_latin1"));
+
+    f.map(|f| f(env, str, length, finalize_cb, finalize_hint, result, copied))
+}
+
+/// Calls `node_api_create_external_string_utf16` (Node-API 10), returning `None` if
+/// the runtime does not provide it
+pub(crate) unsafe fn create_external_string_utf16(
+    env: Env,
+    str: *mut u16,
+    length: usize,
+    finalize_cb: Finalize,
+    finalize_hint: *mut c_void,
+    result: *mut Value,
+    copied: *mut bool,
+) -> Option<Status> {
+    let f = CREATE_EXTERNAL_STRING_UTF16
+        .get_or_init(|| load_optional(b"node_api_create_external_string_utf16"));
+
+    f.map(|f| f(env, str, length, finalize_cb, finalize_hint, result, copied))
+}
+
+pub(super) unsafe fn load(env: Env) -> Result<(), libloading::Error> {
+    let host = host()?;
+
     // This never fail since `get_version` is in N-API Version 1 and the module will fail
     // with `Error: Module did not self-register` if N-API does not exist.
     let version = get_version(&host, env).expect("Failed to find N-API version");
 
+    VERSION.store(version, Ordering::Relaxed);
+
     napi1::load(&host, version, 1);
 
+    // Only load Node-API 1 on runtimes older than the build requires, so that module
+    // initialization can throw an error instead of crashing the process
+    if version < BUILD_VERSION {
+        return Ok(());
+    }
+
+    #[cfg(feature = "napi-3")]
+    napi3::load(&host, version, 3);
+
     #[cfg(feature = "napi-4")]
     napi4::load(&host, version, 4);
 
@@ -63,6 +63,9 @@ pub(crate) type Callback = Option<unsafe extern "C" fn(env: Env, info: CallbackI
 pub(crate) type Finalize =
     Option<unsafe extern "C" fn(env: Env, finalize_data: *mut c_void, finalize_hint: *mut c_void)>;
 
+#[cfg(feature = "napi-6")]
+pub(crate) type CleanupHook = Option<unsafe extern "C" fn(arg: *mut c_void)>;
+
 #[cfg(feature = "napi-4")]
 pub type ThreadsafeFunctionCallJs = Option<
     unsafe extern "C" fn(env: Env, js_callback: Value, context: *mut c_void, data: *mut c_void),
@@ -94,6 +97,8 @@ pub enum Status {
     ArraybufferExpected = 19,
     DetachableArraybufferExpected = 20,
     WouldDeadlock = 21,
+    NoExternalBuffersAllowed = 22,
+    CannotRunJs = 23,
 }
 
 #[allow(dead_code)]
@@ -206,6 +211,39 @@ impl std::ops::BitAndAssign for KeyFilter {
     }
 }
 
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct PropertyAttributes(pub ::std::os::raw::c_uint);
+
+#[allow(dead_code)]
+impl PropertyAttributes {
+    pub(crate) const DEFAULT: PropertyAttributes = PropertyAttributes(0);
+    pub(crate) const WRITABLE: PropertyAttributes = PropertyAttributes(1);
+    pub(crate) const ENUMERABLE: PropertyAttributes = PropertyAttributes(2);
+    pub(crate) const CONFIGURABLE: PropertyAttributes = PropertyAttributes(4);
+}
+
+impl std::ops::BitOr<PropertyAttributes> for PropertyAttributes {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        PropertyAttributes(self.0 | other.0)
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct PropertyDescriptor {
+    pub(crate) utf8name: *const ::std::os::raw::c_char,
+    pub(crate) name: Value,
+    pub(crate) method: Callback,
+    pub(crate) getter: Callback,
+    pub(crate) setter: Callback,
+    pub(crate) value: Value,
+    pub(crate) attributes: PropertyAttributes,
+    pub(crate) data: *mut c_void,
+}
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct AsyncWork__ {
@@ -227,6 +265,24 @@ pub struct Deferred__ {
 
 pub type Deferred = *mut Deferred__;
 
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct AsyncContext__ {
+    _unused: [u8; 0],
+}
+
+pub type AsyncContext = *mut AsyncContext__;
+
+#[cfg(feature = "napi-3")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct CallbackScope__ {
+    _unused: [u8; 0],
+}
+
+#[cfg(feature = "napi-3")]
+pub type CallbackScope = *mut CallbackScope__;
+
 #[cfg(feature = "napi-8")]
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
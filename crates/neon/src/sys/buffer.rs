@@ -1,4 +1,4 @@
-#[cfg(feature = "external-buffers")]
+#[cfg(any(feature = "external-buffers", feature = "bytes"))]
 use std::os::raw::c_void;
 use std::{mem::MaybeUninit, slice};
 
@@ -29,8 +29,10 @@ pub unsafe fn uninitialized(env: Env, len: usize) -> Result<(Local, *mut u8), na
     Ok((buf.assume_init(), bytes.assume_init().cast()))
 }
 
-#[cfg(feature = "external-buffers")]
-pub unsafe fn new_external<T>(env: Env, data: T) -> Local
+#[cfg(any(feature = "external-buffers", feature = "bytes"))]
+/// Creates a buffer backed by `data`, returning `data` if the runtime does not allow
+/// external buffers (e.g., Electron with the V8 memory cage)
+pub unsafe fn new_external<T>(env: Env, data: T) -> Result<Local, T>
 where
     T: AsMut<[u8]> + Send,
 {
@@ -38,24 +40,29 @@ where
     let mut data = Box::new(data);
     let buf = data.as_mut().as_mut();
     let length = buf.len();
+    let ptr = buf.as_mut_ptr();
     let mut result = MaybeUninit::uninit();
-
-    assert_eq!(
-        napi::create_external_buffer(
-            env,
-            length,
-            buf.as_mut_ptr() as *mut _,
-            Some(drop_external::<T>),
-            Box::into_raw(data) as *mut _,
-            result.as_mut_ptr(),
-        ),
-        napi::Status::Ok,
+    let data = Box::into_raw(data);
+    let status = napi::create_external_buffer(
+        env,
+        length,
+        ptr as *mut _,
+        Some(drop_external::<T>),
+        data as *mut _,
+        result.as_mut_ptr(),
     );
 
-    result.assume_init()
+    // The finalizer is not called when external buffers are disallowed; reclaim `data`
+    if status == napi::Status::NoExternalBuffersAllowed {
+        return Err(*Box::from_raw(data));
+    }
+
+    assert_eq!(status, napi::Status::Ok);
+
+    Ok(result.assume_init())
 }
 
-#[cfg(feature = "external-buffers")]
+#[cfg(any(feature = "external-buffers", feature = "bytes"))]
 unsafe extern "C" fn drop_external<T>(_env: Env, _data: *mut c_void, hint: *mut c_void) {
     drop(Box::<T>::from_raw(hint as *mut _));
 }
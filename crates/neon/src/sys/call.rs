@@ -14,7 +14,7 @@ use super::{
 //   with `undefined`.
 // * If the number is too small, getting arguments frequently takes two tries
 //   and requires heap allocation.
-const ARGV_SIZE: usize = 4;
+const ARGV_SIZE: usize = 8;
 
 #[repr(transparent)]
 /// List of JavaScript arguments to a function
@@ -28,9 +28,32 @@ impl Arguments {
     pub fn get(&self, i: usize) -> Option<Local> {
         self.0.get(i).cloned()
     }
+
+    #[inline]
+    /// Get the number of arguments
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    /// Get the arguments starting at a specific position
+    pub fn slice_from(&self, i: usize) -> &[Local] {
+        self.0.get(i..).unwrap_or_default()
+    }
+}
+
+/// The `this` binding and arguments of a function call
+pub struct CallbackData {
+    pub this: Local,
+    pub argv: Arguments,
 }
 
 pub unsafe fn is_construct(env: Env, info: FunctionCallbackInfo) -> bool {
+    new_target(env, info).is_some()
+}
+
+/// Returns `new.target` for a call, or `None` if the function was called without `new`
+pub unsafe fn new_target(env: Env, info: FunctionCallbackInfo) -> Option<Local> {
     let mut target: MaybeUninit<Local> = MaybeUninit::zeroed();
 
     let status = napi::get_new_target(env, info, target.as_mut_ptr());
@@ -43,75 +66,128 @@ pub unsafe fn is_construct(env: Env, info: FunctionCallbackInfo) -> bool {
     // By the get_new_target contract, target will either be NULL if the current
     // function was called without `new`, or a valid napi_value handle if the current
     // function was called with `new`.
-    !target.is_null()
+    if target.is_null() {
+        None
+    } else {
+        Some(target)
+    }
 }
 
-pub unsafe fn this(env: Env, info: FunctionCallbackInfo, out: &mut Local) {
-    let status = napi::get_cb_info(env, info, null_mut(), null_mut(), out as *mut _, null_mut());
-    assert_eq!(status, napi::Status::Ok);
-}
+/// Returns the function arguments and `this` binding for a call, fetched with a single
+/// call to `napi_get_cb_info` in the common case
+pub unsafe fn data(env: Env, info: FunctionCallbackInfo) -> CallbackData {
+    let mut this = MaybeUninit::<Local>::uninit();
+    let mut this_ptr = this.as_mut_ptr();
 
-/// Gets the number of arguments passed to the function.
-// TODO: Remove this when `FunctionContext` is refactored to get call info upfront.
-pub unsafe fn len(env: Env, info: FunctionCallbackInfo) -> usize {
-    let mut argc = 0usize;
-    let status = napi::get_cb_info(
-        env,
-        info,
-        &mut argc as *mut _,
-        null_mut(),
-        null_mut(),
-        null_mut(),
-    );
-    assert_eq!(status, napi::Status::Ok);
-    argc
+    let argv = arguments(|argc, argv| {
+        assert_eq!(
+            napi::get_cb_info(env, info, argc, argv, this_ptr, null_mut()),
+            napi::Status::Ok,
+        );
+
+        // `this` only needs to be read by the first call
+        this_ptr = null_mut();
+    });
+
+    CallbackData {
+        this: this.assume_init(),
+        argv,
+    }
 }
 
-/// Returns the function arguments for a call
-pub unsafe fn argv(env: Env, info: FunctionCallbackInfo) -> Arguments {
+// Collects arguments with `get`, which has the contract of `napi_get_cb_info`: it is
+// passed the capacity of `argv` in `argc` and sets it to the number of arguments,
+// writing as many as fit and filling any remaining capacity with `undefined`.
+//
+// Kept separate from the call to Node-API so that it can be tested without a runtime.
+unsafe fn arguments(mut get: impl FnMut(*mut usize, *mut Local)) -> Arguments {
     // Allocate space on the stack for up to `ARGV_SIZE` values
     let mut argv = MaybeUninit::<[Local; ARGV_SIZE]>::uninit();
 
-    // Starts as the size allocated; after `get_cb_info` it is the number of arguments
+    // Starts as the size allocated; after `get` it is the number of arguments
     let mut argc = ARGV_SIZE;
 
-    assert_eq!(
-        napi::get_cb_info(
-            env,
-            info,
-            &mut argc as *mut _,
-            argv.as_mut_ptr().cast(),
-            null_mut(),
-            null_mut(),
-        ),
-        napi::Status::Ok,
-    );
+    get(&mut argc, argv.as_mut_ptr().cast());
 
     // We did not allocate enough space; allocate on the heap and try again
-    let argv = if argc > ARGV_SIZE {
+    if argc > ARGV_SIZE {
         // We know exactly how much space to reserve
         let mut argv = Vec::with_capacity(argc);
 
-        assert_eq!(
-            napi::get_cb_info(
-                env,
-                info,
-                &mut argc as *mut _,
-                argv.as_mut_ptr(),
-                null_mut(),
-                null_mut(),
-            ),
-            napi::Status::Ok,
-        );
+        get(&mut argc, argv.as_mut_ptr());
 
         // Set the size of `argv` to the number of initialized elements
         argv.set_len(argc);
-        SmallVec::from_vec(argv)
 
-        // There were `ARGV_SIZE` or fewer arguments, use the stack allocated space
-    } else {
-        SmallVec::from_buf_and_len(argv.assume_init(), argc)
-    };
+        return Arguments(SmallVec::from_vec(argv));
+    }
+
+    // There were `ARGV_SIZE` or fewer arguments, use the stack allocated space
+    Arguments(SmallVec::from_buf_and_len(argv.assume_init(), argc))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::null_mut;
+
+    use super::{arguments, Arguments, Local, ARGV_SIZE};
+
+    // Locals are never dereferenced, so any distinct pointer values will do
+    fn local(n: usize) -> Local {
+        n as Local
+    }
+
+    // Follows the `napi_get_cb_info` contract for a call with `n` arguments, with
+    // a null pointer standing in for `undefined`, returning the capacities requested
+    unsafe fn call(n: usize) -> (Arguments, Vec<usize>) {
+        let mut capacities = Vec::new();
+
+        let argv = arguments(|argc, argv| {
+            let capacity = *argc;
+
+            for i in 0..capacity {
+                let value = if i < n { local(i + 1) } else { null_mut() };
 
-    Arguments(argv)
+                argv.add(i).write(value);
+            }
+
+            capacities.push(capacity);
+            *argc = n;
+        });
+
+        (argv, capacities)
+    }
+
+    fn assert_args(argv: &Arguments, n: usize) {
+        assert_eq!(argv.len(), n);
+        assert_eq!(argv.get(n), None);
+        assert!(argv.slice_from(n + 1).is_empty());
+
+        for i in 0..n {
+            assert_eq!(argv.get(i), Some(local(i + 1)));
+            assert_eq!(argv.slice_from(i).len(), n - i);
+        }
+    }
+
+    #[test]
+    fn test_arguments_inline() {
+        for n in [0, 1, 2, ARGV_SIZE] {
+            let (argv, capacities) = unsafe { call(n) };
+
+            assert!(!argv.0.spilled());
+            assert_eq!(capacities, [ARGV_SIZE]);
+            assert_args(&argv, n);
+        }
+    }
+
+    #[test]
+    fn test_arguments_spilled() {
+        for n in [ARGV_SIZE + 1, 3 * ARGV_SIZE] {
+            let (argv, capacities) = unsafe { call(n) };
+
+            assert!(argv.0.spilled());
+            assert_eq!(capacities, [ARGV_SIZE, n]);
+            assert_args(&argv, n);
+        }
+    }
 }
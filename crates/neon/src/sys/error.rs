@@ -91,9 +91,11 @@ pub unsafe fn throw_error_from_utf8(env: Env, msg: *const u8, len: i32) {
 }
 
 #[track_caller]
-pub(super) unsafe fn fatal_error(message: &str) -> ! {
+pub(crate) unsafe fn fatal_error(message: &str) -> ! {
     let location = Location::caller().to_string();
 
+    crate::fatal::call_hook(&nul_terminated(&location), &nul_terminated(message));
+
     napi::fatal_error(
         location.as_ptr().cast(),
         location.len(),
@@ -103,3 +105,14 @@ pub(super) unsafe fn fatal_error(message: &str) -> ! {
 
     unreachable!("Expected napi_fatal_error to exit the process")
 }
+
+// Copies `s` up to the first nul byte, if any, and appends a nul terminator
+fn nul_terminated(s: &str) -> Vec<u8> {
+    let bytes = s.as_bytes();
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    let mut buf = Vec::with_capacity(len + 1);
+
+    buf.extend_from_slice(&bytes[..len]);
+    buf.push(0);
+    buf
+}
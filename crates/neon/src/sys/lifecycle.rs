@@ -44,3 +44,21 @@ pub unsafe fn get_instance_data<T: Send + 'static>(env: Env) -> *mut T {
 unsafe extern "C" fn drop_box<T>(_env: Env, data: *mut c_void, _hint: *mut c_void) {
     drop(Box::<T>::from_raw(data.cast()));
 }
+
+/// Calls `f` when the environment is torn down, before threadsafe functions created
+/// earlier are finalized. Hooks run in reverse order of registration.
+///
+/// # Safety
+/// `env` must point to a valid `napi_env` for this thread
+pub unsafe fn add_cleanup_hook<F: FnOnce() + 'static>(env: Env, f: F) {
+    let data = Box::into_raw(Box::new(f));
+
+    assert_eq!(
+        napi::add_env_cleanup_hook(env, Some(call_box::<F>), data.cast()),
+        napi::Status::Ok,
+    );
+}
+
+unsafe extern "C" fn call_box<F: FnOnce()>(data: *mut c_void) {
+    Box::<F>::from_raw(data.cast())();
+}
@@ -4,6 +4,7 @@ pub(crate) use bindings::*;
 
 pub mod array;
 pub mod arraybuffer;
+pub mod async_context;
 pub mod async_work;
 pub mod buffer;
 pub mod call;
@@ -12,6 +13,7 @@ pub mod error;
 pub mod external;
 pub mod fun;
 pub mod mem;
+pub mod module;
 pub mod no_panic;
 pub mod object;
 pub mod primitive;
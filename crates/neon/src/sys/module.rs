@@ -0,0 +1,21 @@
+//! Information about the loaded addon module
+
+use std::{ffi::CStr, ptr};
+
+use super::{bindings as napi, raw::Env};
+
+/// Returns the URL of the file the addon was loaded from, e.g. `file:///path/to/index.node`,
+/// or `None` if the runtime does not support Node-API 9
+///
+/// # Safety
+/// * `env` is a valid `napi_env` for the current thread
+pub unsafe fn file_name(env: Env) -> Option<String> {
+    let mut result = ptr::null();
+    let status = napi::get_module_file_name(env, &mut result)?;
+
+    if status != napi::Status::Ok || result.is_null() {
+        return None;
+    }
+
+    Some(CStr::from_ptr(result).to_string_lossy().into_owned())
+}
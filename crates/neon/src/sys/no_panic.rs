@@ -11,6 +11,7 @@
 use std::{
     any::Any,
     ffi::c_void,
+    fmt,
     mem::MaybeUninit,
     panic::{catch_unwind, AssertUnwindSafe},
     ptr,
@@ -38,13 +39,35 @@ const UNKNOWN_PANIC_MESSAGE: &str = "Unknown panic";
 /// This process will be aborted if any step unrecoverably fails. For example,
 /// if a `napi::Env` is unavailable, it is impossible to reject a `Promise` or
 /// emit a fatal exception.
-pub struct FailureBoundary {
-    pub both: &'static str,
-    pub exception: &'static str,
-    pub panic: &'static str,
+///
+/// Messages are formatted only when a failure occurs, from the `action` that was
+/// executing and an optional `detail` identifying the particular callback, e.g.,
+/// "A panic occurred while executing a task (scheduled at src/lib.rs:4:5)".
+pub struct FailureBoundary<'a> {
+    pub action: &'static str,
+    pub detail: Option<&'a dyn fmt::Display>,
 }
 
-impl FailureBoundary {
+impl FailureBoundary<'_> {
+    pub fn both(&self) -> String {
+        self.message("A panic and exception")
+    }
+
+    pub fn exception(&self) -> String {
+        self.message("An exception")
+    }
+
+    pub fn panic(&self) -> String {
+        self.message("A panic")
+    }
+
+    fn message(&self, failure: &str) -> String {
+        match self.detail {
+            Some(detail) => format!("{} occurred while {} ({})", failure, self.action, detail),
+            None => format!("{} occurred while {}", failure, self.action),
+        }
+    }
+
     #[track_caller]
     pub unsafe fn catch_failure<F>(&self, env: Env, deferred: Option<napi::Deferred>, f: F)
     where
@@ -66,7 +89,7 @@ impl FailureBoundary {
             if let Err(panic) = panic {
                 let msg = panic_msg(&panic).unwrap_or(UNKNOWN_PANIC_MESSAGE);
 
-                fatal_error(msg);
+                fatal_error(&format!("{}: {}", self.panic(), msg));
             }
 
             // If we don't have an `Env`, we can't catch an exception, nothing more to try
@@ -79,7 +102,7 @@ impl FailureBoundary {
         // Create an error message or return if there wasn't a panic or exception
         let msg = match (exception, panic.as_ref()) {
             // Exception and a panic
-            (Some(_), Err(_)) => self.both,
+            (Some(_), Err(_)) => self.both(),
 
             // Exception, but not a panic
             (Some(err), Ok(_)) => {
@@ -90,11 +113,11 @@ impl FailureBoundary {
                     return;
                 }
 
-                self.exception
+                self.exception()
             }
 
             // Panic, but not an exception
-            (None, Err(_)) => self.panic,
+            (None, Err(_)) => self.panic(),
 
             // No errors occurred! We're done!
             (None, Ok(value)) => {
@@ -108,14 +131,14 @@ impl FailureBoundary {
 
         // Reject the promise
         if let Some(deferred) = deferred {
-            let error = create_error(env, msg, exception, panic.err());
+            let error = create_error(env, &msg, exception, panic.err());
 
             reject_deferred(env, deferred, error);
 
             return;
         }
 
-        let error = create_error(env, msg, exception, panic.err());
+        let error = create_error(env, &msg, exception, panic.err());
 
         // Trigger a fatal exception
         fatal_exception(env, error);
@@ -251,6 +274,9 @@ unsafe fn panic_msg(panic: &Panic) -> Option<&str> {
     }
 }
 
+// Creates an external with the same representation as a `JsBox`, a `Box<Box<dyn Any>>`,
+// so that the payload may be recovered with `JsBox<T>` or `neon::types::panic::downcast_panic`.
+// This cannot use `external::create` because it asserts instead of aborting on failure.
 unsafe fn external_from_panic(env: Env, panic: Panic) -> Local {
     let fail = || fatal_error("Failed to create a neon::types::JsBox from a panic");
     let mut result = MaybeUninit::uninit();
@@ -1,4 +1,4 @@
-use std::mem::MaybeUninit;
+use std::{mem::MaybeUninit, ptr};
 
 use super::{
     bindings as napi,
@@ -69,6 +69,18 @@ pub unsafe fn set_index(out: &mut bool, env: Env, object: Local, index: u32, val
     *out
 }
 
+/// Mutates `out` to indicate whether `object` has an element at `index`. Returns `false` if
+/// the check failed.
+pub unsafe fn has_index(out: &mut bool, env: Env, object: Local, index: u32) -> bool {
+    napi::has_element(env, object, index, out as *mut _) == napi::Status::Ok
+}
+
+/// Deletes the element at `index` from `object`, mutating `out` to indicate whether the
+/// deletion succeeded. Returns `false` if the deletion threw.
+pub unsafe fn delete_index(out: &mut bool, env: Env, object: Local, index: u32) -> bool {
+    napi::delete_element(env, object, index, out as *mut _) == napi::Status::Ok
+}
+
 /// Mutate the `out` argument to refer to the value at a named `key` in the given `object`. Returns `false` if the value couldn't be retrieved.
 pub unsafe fn get_string(
     env: Env,
@@ -148,3 +160,70 @@ pub unsafe fn set(out: &mut bool, env: Env, object: Local, key: Local, val: Loca
 
     *out
 }
+
+/// Mutates `out` to indicate whether `object` has a property named by the `key` value.
+/// Returns `false` if the check failed.
+pub unsafe fn has(out: &mut bool, env: Env, object: Local, key: Local) -> bool {
+    napi::has_property(env, object, key, out as *mut _) == napi::Status::Ok
+}
+
+/// Deletes the property named by the `key` value from `object`, mutating `out` to indicate
+/// whether the deletion succeeded. Returns `false` if the deletion threw.
+pub unsafe fn delete(out: &mut bool, env: Env, object: Local, key: Local) -> bool {
+    napi::delete_property(env, object, key, out as *mut _) == napi::Status::Ok
+}
+
+/// Defines a data property named by the `key` value on `object` with the given attributes.
+/// Returns `true` if the property was defined.
+pub unsafe fn define_value(
+    env: Env,
+    object: Local,
+    key: Local,
+    value: Local,
+    attributes: napi::PropertyAttributes,
+) -> bool {
+    let descriptor = napi::PropertyDescriptor {
+        utf8name: ptr::null(),
+        name: key,
+        method: None,
+        getter: None,
+        setter: None,
+        value,
+        attributes,
+        data: ptr::null_mut(),
+    };
+
+    napi::define_properties(env, object, 1, &descriptor) == napi::Status::Ok
+}
+
+/// Registers `finalize` to be called when `object` is garbage collected.
+///
+/// The closure is called from the finalizer and must not call into JavaScript.
+#[cfg(feature = "napi-5")]
+pub unsafe fn add_finalizer<F>(env: Env, object: Local, finalize: F)
+where
+    F: FnOnce() + 'static,
+{
+    unsafe extern "C" fn finalize_boxed<F: FnOnce()>(
+        _env: Env,
+        _finalize_data: *mut std::os::raw::c_void,
+        finalize_hint: *mut std::os::raw::c_void,
+    ) {
+        Box::from_raw(finalize_hint.cast::<F>())();
+    }
+
+    let data = Box::into_raw(Box::new(finalize));
+    let status = napi::add_finalizer(
+        env,
+        object,
+        ptr::null_mut(),
+        Some(finalize_boxed::<F>),
+        data.cast(),
+        ptr::null_mut(),
+    );
+
+    // If adding the finalizer fails the closure will leak, but it would
+    // be unsafe to drop it because there's no guarantee V8 won't use the
+    // pointer.
+    assert_eq!(status, napi::Status::Ok);
+}
@@ -29,6 +29,16 @@ pub unsafe fn boolean_value(env: Env, p: Local) -> bool {
     value
 }
 
+/// Gets the boolean value of a `Local`, or `None` if it is not a boolean.
+pub unsafe fn try_boolean_value(env: Env, p: Local) -> Option<bool> {
+    let mut value = false;
+
+    match napi::get_value_bool(env, p, &mut value as *mut bool) {
+        napi::Status::Ok => Some(value),
+        _ => None,
+    }
+}
+
 /// Mutates the `out` argument provided to refer to a newly created `Local` containing a
 /// JavaScript number.
 pub unsafe fn number(out: &mut Local, env: Env, v: f64) {
@@ -45,3 +55,20 @@ pub unsafe fn number_value(env: Env, p: Local) -> f64 {
     );
     value
 }
+
+/// Gets the value of a `Local` containing a JavaScript number, or `None` if it is not a
+/// number. Unlike `number_value`, this requires only a single call into the engine.
+pub unsafe fn try_number_value(env: Env, p: Local) -> Option<f64> {
+    let mut value = 0.0;
+
+    match napi::get_value_double(env, p, &mut value as *mut f64) {
+        napi::Status::Ok => Some(value),
+        _ => None,
+    }
+}
+
+/// Mutates the `out` argument provided to refer to a newly created JavaScript symbol. The
+/// `description` may be null. Returns `false` if the symbol couldn't be created.
+pub unsafe fn symbol(out: &mut Local, env: Env, description: Local) -> bool {
+    napi::create_symbol(env, description, out as *mut Local) == napi::Status::Ok
+}
@@ -1,4 +1,4 @@
-use std::{mem::MaybeUninit, ptr};
+use std::{ffi::c_void, mem::MaybeUninit, ptr};
 
 use super::{
     bindings as napi,
@@ -11,6 +11,79 @@ pub unsafe fn new(out: &mut Local, env: Env, data: *const u8, len: i32) -> bool
     status == napi::Status::Ok
 }
 
+pub unsafe fn new_latin1(out: &mut Local, env: Env, data: *const u8, len: usize) -> bool {
+    let status = napi::create_string_latin1(env, data as *const _, len, out);
+
+    status == napi::Status::Ok
+}
+
+pub unsafe fn new_utf16(out: &mut Local, env: Env, data: *const u16, len: usize) -> bool {
+    let status = napi::create_string_utf16(env, data, len, out);
+
+    status == napi::Status::Ok
+}
+
+/// Creates a string backed by `data` without copying, if the runtime supports external
+/// strings. Returns `Err(data)` if it does not, or it failed to create the string.
+///
+/// The engine may still copy the data, e.g., short strings, in which case `data` is
+/// dropped immediately.
+pub unsafe fn new_external_latin1(out: &mut Local, env: Env, data: Vec<u8>) -> Result<(), Vec<u8>> {
+    // Safety: Boxing could move the data; must box before grabbing a raw pointer
+    let mut data = Box::new(data);
+    let ptr = data.as_mut_ptr();
+    let len = data.len();
+    let data = Box::into_raw(data);
+    let mut copied = false;
+    let status = napi::create_external_string_latin1(
+        env,
+        ptr.cast(),
+        len,
+        Some(drop_external::<Vec<u8>>),
+        data.cast(),
+        out,
+        &mut copied,
+    );
+
+    // The finalizer is only called if the string was created
+    match status {
+        Some(napi::Status::Ok) => Ok(()),
+        _ => Err(*Box::from_raw(data)),
+    }
+}
+
+/// Creates a string backed by `data` without copying, if the runtime supports external
+/// strings. Returns `Err(data)` if it does not, or it failed to create the string.
+pub unsafe fn new_external_utf16(
+    out: &mut Local,
+    env: Env,
+    data: Vec<u16>,
+) -> Result<(), Vec<u16>> {
+    let mut data = Box::new(data);
+    let ptr = data.as_mut_ptr();
+    let len = data.len();
+    let data = Box::into_raw(data);
+    let mut copied = false;
+    let status = napi::create_external_string_utf16(
+        env,
+        ptr,
+        len,
+        Some(drop_external::<Vec<u16>>),
+        data.cast(),
+        out,
+        &mut copied,
+    );
+
+    match status {
+        Some(napi::Status::Ok) => Ok(()),
+        _ => Err(*Box::from_raw(data)),
+    }
+}
+
+unsafe extern "C" fn drop_external<T>(_env: Env, _data: *mut c_void, hint: *mut c_void) {
+    drop(Box::<T>::from_raw(hint.cast()));
+}
+
 pub unsafe fn utf8_len(env: Env, value: Local) -> usize {
     let mut len = MaybeUninit::uninit();
     let status = napi::get_value_string_utf8(env, value, ptr::null_mut(), 0, len.as_mut_ptr());
@@ -3,14 +3,35 @@ use super::{
     raw::{Env, Local},
 };
 
-/// Return true if an `napi_value` `val` has the expected value type.
-unsafe fn is_type(env: Env, val: Local, expect: napi::ValueType) -> bool {
+/// Return the value type of an `napi_value` `val`.
+pub unsafe fn value_type(env: Env, val: Local) -> napi::ValueType {
     let mut actual = napi::ValueType::Undefined;
     assert_eq!(
         napi::typeof_value(env, val, &mut actual as *mut _),
         napi::Status::Ok
     );
-    actual == expect
+    actual
+}
+
+/// Return true if an `napi_value` `val` has the expected value type.
+unsafe fn is_type(env: Env, val: Local, expect: napi::ValueType) -> bool {
+    value_type(env, val) == expect
+}
+
+/// Returns the name of the type of `val` for use in error messages. This is the same
+/// as the JavaScript `typeof` operator, except `null` is reported as `"null"`.
+pub unsafe fn type_name(env: Env, val: Local) -> &'static str {
+    match value_type(env, val) {
+        napi::ValueType::Undefined => "undefined",
+        napi::ValueType::Null => "null",
+        napi::ValueType::Boolean => "boolean",
+        napi::ValueType::Number => "number",
+        napi::ValueType::String => "string",
+        napi::ValueType::Symbol => "symbol",
+        napi::ValueType::Object | napi::ValueType::External => "object",
+        napi::ValueType::Function => "function",
+        napi::ValueType::BigInt => "bigint",
+    }
 }
 
 pub unsafe fn is_undefined(env: Env, val: Local) -> bool {
@@ -36,6 +57,11 @@ pub unsafe fn is_string(env: Env, val: Local) -> bool {
     is_type(env, val, napi::ValueType::String)
 }
 
+/// Is `val` a JavaScript symbol?
+pub unsafe fn is_symbol(env: Env, val: Local) -> bool {
+    is_type(env, val, napi::ValueType::Symbol)
+}
+
 pub unsafe fn is_object(env: Env, val: Local) -> bool {
     is_type(env, val, napi::ValueType::Object)
 }
@@ -115,6 +141,70 @@ pub unsafe fn is_promise(env: Env, val: Local) -> bool {
     result
 }
 
+/// Is `val` an instance of the constructor named `name` on the global object, e.g.,
+/// `URL`? Returns `false` if the constructor cannot be found or `instanceof` throws.
+/// An exception that was already pending is left pending.
+pub unsafe fn is_instance_of_global(env: Env, val: Local, name: &str) -> bool {
+    if !is_object(env, val) {
+        return false;
+    }
+
+    // Every call below would fail without running any JavaScript, and the pending
+    // exception must not be mistaken for one that it threw
+    let mut pending = false;
+
+    napi::is_exception_pending(env, &mut pending as *mut _);
+
+    if pending {
+        return false;
+    }
+
+    let mut global = std::ptr::null_mut();
+    let mut key = std::ptr::null_mut();
+    let mut constructor = std::ptr::null_mut();
+    let mut result = false;
+
+    super::scope::get_global(env, &mut global);
+
+    if napi::create_string_utf8(env, name.as_ptr().cast(), name.len(), &mut key as *mut _)
+        != napi::Status::Ok
+    {
+        return false;
+    }
+
+    // A getter for the constructor may throw
+    if napi::get_property(env, global, key, &mut constructor as *mut _) != napi::Status::Ok {
+        clear_thrown(env);
+        return false;
+    }
+
+    if !is_function(env, constructor) {
+        return false;
+    }
+
+    // `Symbol.hasInstance` may throw
+    if napi::instanceof(env, val, constructor, &mut result as *mut _) != napi::Status::Ok {
+        clear_thrown(env);
+        return false;
+    }
+
+    result
+}
+
+// Clears the exception thrown by a failed call, if any. Some calls, e.g., `instanceof`,
+// fail with `GenericFailure` instead of `PendingException` when JavaScript throws.
+unsafe fn clear_thrown(env: Env) {
+    let mut pending = false;
+
+    napi::is_exception_pending(env, &mut pending as *mut _);
+
+    if pending {
+        let mut exception = std::ptr::null_mut();
+
+        napi::get_and_clear_last_exception(env, &mut exception as *mut _);
+    }
+}
+
 #[cfg(feature = "napi-8")]
 pub unsafe fn type_tag_object(env: Env, object: Local, tag: &super::TypeTag) {
     assert_eq!(
@@ -1,18 +1,22 @@
 //! Idiomatic Rust wrappers for N-API threadsafe functions
 
 use std::{
+    cell::UnsafeCell,
     ffi::c_void,
     mem::MaybeUninit,
     ptr,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread::{self, ThreadId},
 };
 
 use super::{bindings as napi, no_panic::FailureBoundary, raw::Env};
 
-const BOUNDARY: FailureBoundary = FailureBoundary {
-    both: "A panic and exception occurred while executing a `neon::event::Channel::send` callback",
-    exception: "An exception occurred while executing a `neon::event::Channel::send` callback",
-    panic: "A panic occurred while executing a `neon::event::Channel::send` callback",
+const BOUNDARY: FailureBoundary<'static> = FailureBoundary {
+    action: "executing a `neon::event::Channel::send` callback",
+    detail: None,
 };
 
 #[derive(Debug)]
@@ -22,19 +26,67 @@ unsafe impl Send for Tsfn {}
 
 unsafe impl Sync for Tsfn {}
 
-#[derive(Debug)]
+// The environment and thread that created a threadsafe function. The `Env` is only
+// used on `thread`.
+struct Owner {
+    env: Env,
+    thread: ThreadId,
+}
+
+unsafe impl Send for Owner {}
+
+unsafe impl Sync for Owner {}
+
 /// Threadsafe Function encapsulate a Rust function pointer and N-API threadsafe
 /// function for scheduling tasks to execute on a JavaScript thread.
+///
+/// Each item is passed to the N-API threadsafe function in a shared [`Item`], so that
+/// it may be removed until it starts executing. Each invocation on the JavaScript
+/// thread executes its own item in the callback scope opened by Node for that call,
+/// so async hooks and the microtask queue run between items.
 pub struct ThreadsafeFunction<T> {
     tsfn: Tsfn,
     is_finalized: Arc<Mutex<bool>>,
     callback: fn(Option<Env>, T),
+    // Number of items that were called and have neither started executing nor been
+    // removed. Shared with the callbacks as the `context`; the reference is released
+    // in `finalize`.
+    pending: Arc<AtomicUsize>,
+    owner: Owner,
 }
 
-#[derive(Debug)]
-struct Callback<T> {
+/// An item scheduled with [`ThreadsafeFunction::call`]. It may be removed with
+/// [`ThreadsafeFunction::remove`] until it starts executing.
+pub struct Item<T>(Arc<Slot<T>>);
+
+struct Slot<T> {
     callback: fn(Option<Env>, T),
-    data: T,
+    // Set by whichever of the callback and `remove` takes the data first
+    is_taken: AtomicBool,
+    data: UnsafeCell<MaybeUninit<T>>,
+}
+
+// The data is only accessed by the single caller that sets `is_taken`
+unsafe impl<T: Send> Send for Slot<T> {}
+
+unsafe impl<T: Send> Sync for Slot<T> {}
+
+impl<T> Slot<T> {
+    fn take(&self) -> Option<T> {
+        if self.is_taken.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+
+        Some(unsafe { (*self.data.get()).assume_init_read() })
+    }
+}
+
+impl<T> Drop for Slot<T> {
+    fn drop(&mut self) {
+        if !*self.is_taken.get_mut() {
+            unsafe { self.data.get_mut().assume_init_drop() };
+        }
+    }
 }
 
 /// Error returned when scheduling a threadsafe function with some data
@@ -44,18 +96,9 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
     /// Creates a new unbounded N-API Threadsafe Function
     /// Safety: `Env` must be valid for the current thread
     pub unsafe fn new(env: Env, callback: fn(Option<Env>, T)) -> Self {
-        Self::with_capacity(env, 0, callback)
-    }
-
-    /// Creates a bounded N-API Threadsafe Function
-    /// Safety: `Env` must be valid for the current thread
-    pub unsafe fn with_capacity(
-        env: Env,
-        max_queue_size: usize,
-        callback: fn(Option<Env>, T),
-    ) -> Self {
         let mut result = MaybeUninit::uninit();
         let is_finalized = Arc::new(Mutex::new(false));
+        let pending = Arc::new(AtomicUsize::new(0));
 
         assert_eq!(
             napi::create_threadsafe_function(
@@ -63,13 +106,13 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
                 std::ptr::null_mut(),
                 std::ptr::null_mut(),
                 super::string(env, "neon threadsafe function"),
-                max_queue_size,
+                0,
                 // Always set the reference count to 1. Prefer using
                 // Rust `Arc` to maintain the struct.
                 1,
                 Arc::into_raw(is_finalized.clone()) as *mut _,
                 Some(Self::finalize),
-                std::ptr::null_mut(),
+                Arc::into_raw(pending.clone()) as *mut _,
                 Some(Self::callback),
                 result.as_mut_ptr(),
             ),
@@ -80,54 +123,108 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
             tsfn: Tsfn(result.assume_init()),
             is_finalized,
             callback,
+            pending,
+            owner: Owner {
+                env,
+                thread: thread::current().id(),
+            },
         }
     }
 
+    /// Returns the `Env` that created the threadsafe function. It may only be used to
+    /// identify the environment off of its JavaScript thread.
+    #[cfg(all(debug_assertions, feature = "napi-6"))]
+    pub fn owner_env(&self) -> Env {
+        self.owner.env
+    }
+
+    /// Returns the `Env` that created the threadsafe function if called on its
+    /// JavaScript thread while no items are pending, i.e., when work may execute
+    /// immediately without overtaking pending items
+    pub fn idle_env(&self) -> Option<Env> {
+        if thread::current().id() != self.owner.thread {
+            return None;
+        }
+
+        if *self.is_finalized.lock().unwrap() {
+            return None;
+        }
+
+        if self.pending.load(Ordering::Acquire) != 0 {
+            return None;
+        }
+
+        Some(self.owner.env)
+    }
+
     /// Schedule a threadsafe function to be executed with some data
     pub fn call(
         &self,
         data: T,
         is_blocking: Option<napi::ThreadsafeFunctionCallMode>,
-    ) -> Result<(), CallError> {
+    ) -> Result<Item<T>, CallError> {
         let is_blocking = is_blocking.unwrap_or(napi::ThreadsafeFunctionCallMode::Blocking);
-
-        let callback = Box::into_raw(Box::new(Callback {
+        let item = Arc::new(Slot {
             callback: self.callback,
-            data,
-        }));
+            is_taken: AtomicBool::new(false),
+            data: UnsafeCell::new(MaybeUninit::new(data)),
+        });
 
         // Hold the lock before entering `call_threadsafe_function` so that
-        // `finalize_cb` would never complete.
+        // `finalize_cb` would never complete. The queue is unbounded, so the call
+        // never waits for the JavaScript thread.
         let mut is_finalized = self.is_finalized.lock().unwrap();
 
-        let status = {
-            if *is_finalized {
-                napi::Status::Closing
-            } else {
-                unsafe {
-                    napi::call_threadsafe_function(self.tsfn.0, callback as *mut _, is_blocking)
-                }
-            }
+        if *is_finalized {
+            return Err(CallError);
+        }
+
+        let data = Arc::into_raw(item.clone());
+
+        self.pending.fetch_add(1, Ordering::AcqRel);
+
+        let status = unsafe {
+            napi::call_threadsafe_function(self.tsfn.0, data as *mut c_void, is_blocking)
         };
 
         if status == napi::Status::Ok {
-            Ok(())
-        } else {
-            // Prevent further calls to `call_threadsafe_function`
-            if status == napi::Status::Closing {
-                *is_finalized = true;
-            }
+            return Ok(Item(item));
+        }
 
-            // If the call failed, the callback won't execute
-            let _ = unsafe { Box::from_raw(callback) };
+        self.pending.fetch_sub(1, Ordering::AcqRel);
 
-            Err(CallError)
+        // Prevent further calls to `call_threadsafe_function`
+        if status == napi::Status::Closing {
+            *is_finalized = true;
         }
+
+        drop(is_finalized);
+
+        // If the call failed, the callback won't execute
+        drop(unsafe { Arc::from_raw(data) });
+        drop(item);
+
+        Err(CallError)
+    }
+
+    /// Removes the item if it has not started executing
+    pub fn remove(&self, item: &Item<T>) -> Option<T> {
+        let data = item.0.take()?;
+
+        self.pending.fetch_sub(1, Ordering::AcqRel);
+
+        Some(data)
     }
 
     /// References a threadsafe function to prevent exiting the event loop until it has been dropped. (Default)
     /// Safety: `Env` must be valid for the current thread
     pub unsafe fn reference(&self, env: Env) {
+        // The threadsafe function was freed during teardown, e.g., when a channel is
+        // created or dropped by a finalizer
+        if *self.is_finalized.lock().unwrap() {
+            return;
+        }
+
         assert_eq!(
             napi::ref_threadsafe_function(env, self.tsfn.0),
             napi::Status::Ok,
@@ -137,6 +234,10 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
     /// Unreferences a threadsafe function to allow exiting the event loop before it has been dropped.
     /// Safety: `Env` must be valid for the current thread
     pub unsafe fn unref(&self, env: Env) {
+        if *self.is_finalized.lock().unwrap() {
+            return;
+        }
+
         assert_eq!(
             napi::unref_threadsafe_function(env, self.tsfn.0),
             napi::Status::Ok,
@@ -144,14 +245,19 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
     }
 
     // Provides a C ABI wrapper for a napi callback notifying us about tsfn
-    // being finalized.
-    unsafe extern "C" fn finalize(_env: Env, data: *mut c_void, _hint: *mut c_void) {
+    // being finalized. Node.js calls `callback` without an `Env` for each item that
+    // was never executed afterwards.
+    unsafe extern "C" fn finalize(_env: Env, data: *mut c_void, hint: *mut c_void) {
         let is_finalized = Arc::from_raw(data as *mut Mutex<bool>);
 
         *is_finalized.lock().unwrap() = true;
+
+        drop(Arc::from_raw(hint as *const AtomicUsize));
     }
 
     // Provides a C ABI wrapper for invoking the user supplied function pointer
+    // with the item. N-API opens a `HandleScope` before calling the callback.
+    //
     // On panic or exception, creates a fatal exception of the form:
     // Error(msg: string) {
     //     // Exception thrown
@@ -165,13 +271,28 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
     unsafe extern "C" fn callback(
         env: Env,
         _js_callback: napi::Value,
-        _context: *mut c_void,
+        context: *mut c_void,
         data: *mut c_void,
     ) {
-        let Callback { callback, data } = *Box::from_raw(data as *mut Callback<T>);
+        let item = Arc::from_raw(data as *const Slot<T>);
+
+        // The item was removed
+        let data = match item.take() {
+            Some(data) => data,
+            None => return,
+        };
+
+        // The threadsafe function is being torn down and `context` was released
+        // by `finalize`
+        if env.is_null() {
+            (item.callback)(None, data);
+            return;
+        }
+
+        (*(context as *const AtomicUsize)).fetch_sub(1, Ordering::AcqRel);
 
         BOUNDARY.catch_failure(env, None, move |env| {
-            callback(env, data);
+            (item.callback)(env, data);
             ptr::null_mut()
         });
     }
@@ -194,3 +315,251 @@ impl<T> Drop for ThreadsafeFunction<T> {
         };
     }
 }
+
+const FUNCTION_BOUNDARY: FailureBoundary<'static> = FailureBoundary {
+    action: "executing a `neon::event::ThreadsafeFunction` callback",
+    detail: None,
+};
+
+/// Threadsafe Function encapsulating a JavaScript function and a Rust callback
+/// that is registered once and called with each message on the JavaScript thread.
+pub struct FunctionTsfn<T> {
+    tsfn: Tsfn,
+    state: Arc<FunctionState>,
+    _data: std::marker::PhantomData<fn(T)>,
+}
+
+// Tracks whether the N-API threadsafe function may still be used and how many threads
+// are currently inside an N-API call with it. The lock is never held across a call,
+// since a call may block on a full queue until the JavaScript thread drains it or
+// aborts. Instead, `finalize_cb` waits for the calls in flight before completing.
+#[derive(Default)]
+struct FunctionState {
+    inner: Mutex<FunctionStateInner>,
+    idle: Condvar,
+}
+
+#[derive(Default)]
+struct FunctionStateInner {
+    is_finalized: bool,
+    in_flight: usize,
+}
+
+impl FunctionState {
+    // Starts an N-API call, returning `false` if the threadsafe function was finalized.
+    // If `finalize` is set, later calls fail without entering N-API.
+    fn enter(&self, finalize: bool) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.is_finalized {
+            return false;
+        }
+
+        inner.is_finalized = finalize;
+        inner.in_flight += 1;
+
+        true
+    }
+
+    // Completes an N-API call started with `enter`
+    fn exit(&self, finalize: bool) {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.is_finalized |= finalize;
+        inner.in_flight -= 1;
+
+        if inner.in_flight == 0 {
+            self.idle.notify_all();
+        }
+    }
+
+    fn is_finalized(&self) -> bool {
+        self.inner.lock().unwrap().is_finalized
+    }
+
+    // Marks the threadsafe function as finalized and waits for calls in flight to
+    // return. Node.js wakes calls blocked on a full queue before finalizing.
+    fn finalize(&self) {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.is_finalized = true;
+
+        while inner.in_flight > 0 {
+            inner = self.idle.wait(inner).unwrap();
+        }
+    }
+}
+
+/// Error returned when scheduling a threadsafe function fails, returning the data
+pub struct FunctionCallError<T> {
+    pub data: T,
+    pub status: napi::Status,
+}
+
+impl<T: Send + 'static> FunctionTsfn<T> {
+    /// Creates an N-API Threadsafe Function calling `callback` with the JavaScript
+    /// function `func` and each message. A `max_queue_size` of `0` is unbounded.
+    /// Safety: `Env` must be valid for the current thread and `func` must be a function
+    pub unsafe fn new<F>(env: Env, func: napi::Value, max_queue_size: usize, callback: F) -> Self
+    where
+        F: Fn(Env, napi::Value, T) + 'static,
+    {
+        let mut result = MaybeUninit::uninit();
+        let state = Arc::new(FunctionState::default());
+        let callback = Box::into_raw(Box::new(callback));
+
+        assert_eq!(
+            napi::create_threadsafe_function(
+                env,
+                func,
+                std::ptr::null_mut(),
+                super::string(env, "neon threadsafe function"),
+                max_queue_size,
+                // Always set the reference count to 1. Prefer using
+                // Rust `Arc` to maintain the struct.
+                1,
+                Arc::into_raw(state.clone()) as *mut _,
+                Some(Self::finalize::<F>),
+                callback.cast(),
+                Some(Self::callback::<F>),
+                result.as_mut_ptr(),
+            ),
+            napi::Status::Ok,
+        );
+
+        Self {
+            tsfn: Tsfn(result.assume_init()),
+            state,
+            _data: std::marker::PhantomData,
+        }
+    }
+
+    /// Schedule the callback to be executed with `data`
+    pub fn call(
+        &self,
+        data: T,
+        mode: napi::ThreadsafeFunctionCallMode,
+    ) -> Result<(), FunctionCallError<T>> {
+        if !self.state.enter(false) {
+            return Err(FunctionCallError {
+                data,
+                status: napi::Status::Closing,
+            });
+        }
+
+        let data = Box::into_raw(Box::new(data));
+        let status = unsafe { napi::call_threadsafe_function(self.tsfn.0, data.cast(), mode) };
+
+        // Prevent further calls to `call_threadsafe_function`
+        self.state.exit(status == napi::Status::Closing);
+
+        if status == napi::Status::Ok {
+            return Ok(());
+        }
+
+        // If the call failed, the callback won't execute
+        let data = unsafe { *Box::from_raw(data) };
+
+        Err(FunctionCallError { data, status })
+    }
+
+    /// Releases the threadsafe function immediately. Pending messages are dropped
+    /// without being passed to the callback and further calls fail.
+    pub fn abort(&self) {
+        if !self.state.enter(true) {
+            return;
+        }
+
+        unsafe {
+            napi::release_threadsafe_function(
+                self.tsfn.0,
+                napi::ThreadsafeFunctionReleaseMode::Abort,
+            );
+        }
+
+        self.state.exit(true);
+    }
+
+    /// References a threadsafe function to prevent exiting the event loop until it has been dropped. (Default)
+    /// Safety: `Env` must be valid for the current thread
+    pub unsafe fn reference(&self, env: Env) {
+        // `finalize_cb` also runs on the JavaScript thread, so the threadsafe function
+        // cannot be finalized before the call returns
+        if self.state.is_finalized() {
+            return;
+        }
+
+        assert_eq!(
+            napi::ref_threadsafe_function(env, self.tsfn.0),
+            napi::Status::Ok,
+        );
+    }
+
+    /// Unreferences a threadsafe function to allow exiting the event loop before it has been dropped.
+    /// Safety: `Env` must be valid for the current thread
+    pub unsafe fn unref(&self, env: Env) {
+        if self.state.is_finalized() {
+            return;
+        }
+
+        assert_eq!(
+            napi::unref_threadsafe_function(env, self.tsfn.0),
+            napi::Status::Ok,
+        );
+    }
+
+    // Provides a C ABI wrapper for a napi callback notifying us about tsfn
+    // being finalized. Also drops the Rust callback.
+    unsafe extern "C" fn finalize<F>(_env: Env, data: *mut c_void, hint: *mut c_void) {
+        let state = Arc::from_raw(data as *const FunctionState);
+
+        state.finalize();
+
+        drop(Box::from_raw(hint.cast::<F>()));
+    }
+
+    // Provides a C ABI wrapper for invoking the user supplied callback. If the
+    // threadsafe function is being torn down, `env` is null and the data is dropped.
+    unsafe extern "C" fn callback<F>(
+        env: Env,
+        js_callback: napi::Value,
+        context: *mut c_void,
+        data: *mut c_void,
+    ) where
+        F: Fn(Env, napi::Value, T) + 'static,
+    {
+        let data = *Box::from_raw(data.cast::<T>());
+
+        if env.is_null() {
+            return;
+        }
+
+        let callback = &*context.cast::<F>();
+
+        FUNCTION_BOUNDARY.catch_failure(env, None, move |env| {
+            if let Some(env) = env {
+                callback(env, js_callback, data);
+            }
+
+            ptr::null_mut()
+        });
+    }
+}
+
+impl<T> Drop for FunctionTsfn<T> {
+    fn drop(&mut self) {
+        // tsfn was already finalized or aborted
+        if !self.state.enter(true) {
+            return;
+        }
+
+        unsafe {
+            napi::release_threadsafe_function(
+                self.tsfn.0,
+                napi::ThreadsafeFunctionReleaseMode::Release,
+            );
+        };
+
+        self.state.exit(true);
+    }
+}
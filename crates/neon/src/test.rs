@@ -0,0 +1,469 @@
+//! Helpers for testing an addon from Rust code that runs inside a JavaScript
+//! environment.
+//!
+//! Tests of an addon usually run in a JavaScript test framework that loads the addon
+//! and calls its exports. Many behaviors, such as the conversion of Rust values or the
+//! exceptions thrown for invalid input, are easier to check from Rust, in a test
+//! function exported by the addon and called from the JavaScript test suite:
+//!
+//! * [`assert_js_eq`] compares a JavaScript value to a [`serde_json::Value`].
+//! * [`expect_throw`] runs a closure that is expected to throw and returns the
+//!   [`CaughtException`].
+//! * [`record_calls`] creates a JavaScript function that records its calls, to check
+//!   how code calls a callback.
+//!
+//! Failed assertions panic, and the panic is thrown as an exception from the exported
+//! test function, failing the JavaScript test that called it.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use neon::{
+//!     test::{assert_js_eq, expect_throw, record_calls},
+//!     types::extract::TryIntoJs,
+//! };
+//! use serde_json::json;
+//!
+//! fn test_point(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+//!     let point = (1.0, 2.0).try_into_js(&mut cx)?;
+//!
+//!     assert_js_eq(&mut cx, point, &json!([1.0, 2.0]))?;
+//!
+//!     let err = expect_throw(&mut cx, |cx| cx.throw_range_error::<_, ()>("out of range"));
+//!
+//!     assert!(err.is_a::<JsError, _>(&mut cx));
+//!     assert_eq!(err.message(&mut cx)?, "out of range");
+//!
+//!     let (callback, calls) = record_calls(&mut cx)?;
+//!
+//!     callback.call_with(&cx).arg(point).exec(&mut cx)?;
+//!
+//!     assert_eq!(calls.len(), 1);
+//!     let args = calls.args(&mut cx, 0);
+//!
+//!     assert_js_eq(&mut cx, args, &json!([[1.0, 2.0]]))?;
+//!
+//!     Ok(cx.undefined())
+//! }
+//! ```
+
+use std::{cell::RefCell, fmt::Write, rc::Rc};
+
+use serde_json::Value as Json;
+
+use crate::{
+    context::Context,
+    handle::{Handle, Root},
+    object::Object,
+    result::NeonResult,
+    types::{
+        JsArray, JsBigInt, JsFunction, JsObject, JsString, JsSymbol, JsUndefined, JsValue, Value,
+        ValueVisitor,
+    },
+};
+
+/// Compares a JavaScript value to the value of a JSON literal, returning a description
+/// of the first difference, if any.
+///
+/// Values are compared the way `JSON.stringify` would represent them:
+///
+/// * `null`, booleans, numbers and strings are compared by value.
+/// * Arrays are compared element by element.
+/// * Other objects are compared by their own enumerable string keys, ignoring
+///   properties whose value is `undefined`. The order of the keys is not compared.
+///
+/// Any other value, such as `undefined` or a function, never matches. Throws if reading
+/// a property throws.
+///
+/// The difference describes its location with a path, e.g., `$.points[1]`.
+pub fn diff<'a, C: Context<'a>, V: Value>(
+    cx: &mut C,
+    value: Handle<'a, V>,
+    expected: &Json,
+) -> NeonResult<Option<String>> {
+    let mut path = String::from("$");
+
+    value.visit(
+        cx,
+        &mut Diff {
+            expected,
+            path: &mut path,
+        },
+    )
+}
+
+/// Asserts that a JavaScript value matches the value of a JSON literal, as compared by
+/// [`diff`].
+///
+/// # Panics
+///
+/// Panics with the first difference if the values do not match.
+pub fn assert_js_eq<'a, C: Context<'a>, V: Value>(
+    cx: &mut C,
+    value: Handle<'a, V>,
+    expected: &Json,
+) -> NeonResult<()> {
+    if let Some(diff) = diff(cx, value, expected)? {
+        panic!("JavaScript value does not match: {}", diff);
+    }
+
+    Ok(())
+}
+
+/// Calls `f`, which is expected to throw, and returns the caught exception.
+///
+/// # Panics
+///
+/// Panics if `f` returns without throwing.
+pub fn expect_throw<'a, C, T, F>(cx: &mut C, f: F) -> CaughtException<'a>
+where
+    C: Context<'a>,
+    F: FnOnce(&mut C) -> NeonResult<T>,
+{
+    match cx.try_catch(f) {
+        Ok(_) => panic!("expected an exception to be thrown"),
+        Err(exception) => CaughtException { exception },
+    }
+}
+
+/// An exception caught by [`expect_throw`].
+#[derive(Debug)]
+pub struct CaughtException<'a> {
+    exception: Handle<'a, JsValue>,
+}
+
+impl<'a> CaughtException<'a> {
+    /// The thrown value.
+    pub fn value(&self) -> Handle<'a, JsValue> {
+        self.exception
+    }
+
+    /// Indicates whether the thrown value is of type `V`, e.g., [`JsError`](crate::types::JsError).
+    pub fn is_a<V: Value, C: Context<'a>>(&self, cx: &mut C) -> bool {
+        self.exception.is_a::<V, _>(cx)
+    }
+
+    /// The `message` property of a thrown object, or the thrown value converted to a
+    /// string if it is not an object.
+    pub fn message<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        let message = match self.exception.downcast::<JsObject, _>(cx) {
+            Ok(obj) => obj.get_value(cx, "message")?,
+            Err(_) => self.exception,
+        };
+
+        Ok(message.to_string(cx)?.value(cx))
+    }
+}
+
+/// Creates a JavaScript function that records each of its calls and returns
+/// `undefined`.
+///
+/// The receiver and the arguments of each call are kept alive until they are cleared
+/// with [`CallRecorder::clear`], or the recorder and the function are both dropped.
+pub fn record_calls<'a, C: Context<'a>>(
+    cx: &mut C,
+) -> NeonResult<(Handle<'a, JsFunction>, CallRecorder)> {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let recorder = CallRecorder {
+        calls: calls.clone(),
+    };
+
+    let f = JsFunction::new(cx, move |mut cx| {
+        // The receiver is stored at index `0`, followed by the arguments
+        let len = cx.len();
+        let call = JsArray::new(&mut cx, len as u32 + 1);
+        let this = cx.this_value();
+
+        call.set(&mut cx, 0, this)?;
+
+        for i in 0..len {
+            let arg = cx.argument::<JsValue>(i)?;
+
+            call.set(&mut cx, i as u32 + 1, arg)?;
+        }
+
+        let call = call.root(&mut cx);
+
+        calls.borrow_mut().push(call);
+
+        Ok(cx.undefined())
+    })?;
+
+    Ok((f, recorder))
+}
+
+/// The calls recorded by a function created with [`record_calls`], in the order they
+/// were made.
+///
+/// # Panics
+///
+/// Methods that take the index of a call panic if there is no such call.
+#[derive(Clone)]
+pub struct CallRecorder {
+    calls: Rc<RefCell<Vec<Root<JsArray>>>>,
+}
+
+impl CallRecorder {
+    /// The number of recorded calls.
+    pub fn len(&self) -> usize {
+        self.calls.borrow().len()
+    }
+
+    /// Indicates whether no calls have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The arguments of call `i`, as a new array.
+    pub fn args<'a, C: Context<'a>>(&self, cx: &mut C, i: usize) -> Handle<'a, JsArray> {
+        let call = self.call(cx, i);
+        let len = call.len(cx);
+        let args = JsArray::new(cx, len - 1);
+
+        for j in 1..len {
+            // Reading and writing the indexes of a new array does not throw
+            if let Ok(arg) = call.get_value(cx, j) {
+                let _ = args.set(cx, j - 1, arg);
+            }
+        }
+
+        args
+    }
+
+    /// Argument `j` of call `i`, or `undefined` if fewer arguments were passed.
+    pub fn arg<'a, C: Context<'a>>(&self, cx: &mut C, i: usize, j: usize) -> Handle<'a, JsValue> {
+        let call = self.call(cx, i);
+
+        if j + 1 >= call.len(cx) as usize {
+            return cx.undefined().upcast();
+        }
+
+        call.get_value(cx, j as u32 + 1)
+            .unwrap_or_else(|_| cx.undefined().upcast())
+    }
+
+    /// The receiver (`this`) of call `i`.
+    pub fn this<'a, C: Context<'a>>(&self, cx: &mut C, i: usize) -> Handle<'a, JsValue> {
+        let call = self.call(cx, i);
+
+        call.get_value(cx, 0)
+            .unwrap_or_else(|_| cx.undefined().upcast())
+    }
+
+    /// Removes the recorded calls, releasing their values.
+    pub fn clear<'a, C: Context<'a>>(&self, cx: &mut C) {
+        let calls = std::mem::take(&mut *self.calls.borrow_mut());
+
+        for call in calls {
+            call.drop(cx);
+        }
+    }
+
+    fn call<'a, C: Context<'a>>(&self, cx: &mut C, i: usize) -> Handle<'a, JsArray> {
+        let calls = self.calls.borrow();
+        let len = calls.len();
+
+        match calls.get(i) {
+            Some(call) => call.to_inner(cx),
+            None => panic!("call {} was not recorded; {} calls were recorded", i, len),
+        }
+    }
+}
+
+impl std::fmt::Debug for CallRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallRecorder")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+// Compares a visited value to `expected`, describing the first difference
+struct Diff<'j, 'p> {
+    expected: &'j Json,
+    path: &'p mut String,
+}
+
+impl Diff<'_, '_> {
+    fn mismatch(&self, actual: impl std::fmt::Display) -> Option<String> {
+        Some(format!(
+            "{}: expected {}, found {}",
+            self.path, self.expected, actual
+        ))
+    }
+}
+
+impl<'a> ValueVisitor<'a> for Diff<'_, '_> {
+    type Output = Option<String>;
+
+    fn visit_undefined<C: Context<'a>>(&mut self, _cx: &mut C) -> NeonResult<Option<String>> {
+        Ok(self.mismatch("undefined"))
+    }
+
+    fn visit_null<C: Context<'a>>(&mut self, _cx: &mut C) -> NeonResult<Option<String>> {
+        match self.expected {
+            Json::Null => Ok(None),
+            _ => Ok(self.mismatch("null")),
+        }
+    }
+
+    fn visit_boolean<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        b: bool,
+    ) -> NeonResult<Option<String>> {
+        match self.expected {
+            Json::Bool(expected) if *expected == b => Ok(None),
+            _ => Ok(self.mismatch(b)),
+        }
+    }
+
+    fn visit_number<C: Context<'a>>(&mut self, _cx: &mut C, n: f64) -> NeonResult<Option<String>> {
+        match self.expected {
+            Json::Number(expected) if expected.as_f64() == Some(n) => Ok(None),
+            _ => Ok(self.mismatch(n)),
+        }
+    }
+
+    fn visit_string<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        s: Handle<'a, JsString>,
+    ) -> NeonResult<Option<String>> {
+        let s = s.value(cx);
+
+        match self.expected {
+            Json::String(expected) if *expected == s => Ok(None),
+            _ => Ok(self.mismatch(Json::String(s))),
+        }
+    }
+
+    fn visit_symbol<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        _s: Handle<'a, JsSymbol>,
+    ) -> NeonResult<Option<String>> {
+        Ok(self.mismatch("a symbol"))
+    }
+
+    fn visit_bigint<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        _n: Handle<'a, JsBigInt>,
+    ) -> NeonResult<Option<String>> {
+        Ok(self.mismatch("a bigint"))
+    }
+
+    fn visit_array<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        array: Handle<'a, JsArray>,
+    ) -> NeonResult<Option<String>> {
+        match self.expected {
+            Json::Array(expected) => diff_array(cx, array, expected, self.path),
+            _ => {
+                let len = array.len(cx);
+
+                Ok(self.mismatch(format!("an array of length {}", len)))
+            }
+        }
+    }
+
+    fn visit_function<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        _f: Handle<'a, JsFunction>,
+    ) -> NeonResult<Option<String>> {
+        Ok(self.mismatch("a function"))
+    }
+
+    fn visit_object<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        obj: Handle<'a, JsObject>,
+    ) -> NeonResult<Option<String>> {
+        match self.expected {
+            Json::Object(expected) => diff_object(cx, obj, expected, self.path),
+            _ => Ok(self.mismatch("an object")),
+        }
+    }
+
+    fn visit_other<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        _value: Handle<'a, JsValue>,
+    ) -> NeonResult<Option<String>> {
+        Ok(self.mismatch("an external"))
+    }
+}
+
+fn diff_array<'a, C: Context<'a>>(
+    cx: &mut C,
+    value: Handle<'a, JsArray>,
+    expected: &[Json],
+    path: &mut String,
+) -> NeonResult<Option<String>> {
+    let len = value.len(cx) as usize;
+
+    if len != expected.len() {
+        return Ok(Some(format!(
+            "{}: expected an array of length {}, found length {}",
+            path,
+            expected.len(),
+            len
+        )));
+    }
+
+    for (i, expected) in expected.iter().enumerate() {
+        let element = value.get_value(cx, i as u32)?;
+        let parent = path.len();
+
+        let _ = write!(path, "[{}]", i);
+
+        if let Some(diff) = element.visit(cx, &mut Diff { expected, path })? {
+            return Ok(Some(diff));
+        }
+
+        path.truncate(parent);
+    }
+
+    Ok(None)
+}
+
+fn diff_object<'a, C: Context<'a>>(
+    cx: &mut C,
+    value: Handle<'a, JsObject>,
+    expected: &serde_json::Map<String, Json>,
+    path: &mut String,
+) -> NeonResult<Option<String>> {
+    let keys = value.get_own_property_names(cx)?;
+    let len = keys.len(cx);
+
+    for i in 0..len {
+        let key = keys.get_value(cx, i)?.to_string(cx)?.value(cx);
+
+        if expected.contains_key(&key)
+            || value
+                .get_value(cx, key.as_str())?
+                .is_a::<JsUndefined, _>(cx)
+        {
+            continue;
+        }
+
+        return Ok(Some(format!("{}: unexpected property `{}`", path, key)));
+    }
+
+    for (key, expected) in expected {
+        let property = value.get_value(cx, key.as_str())?;
+        let parent = path.len();
+
+        let _ = write!(path, ".{}", key);
+
+        if let Some(diff) = property.visit(cx, &mut Diff { expected, path })? {
+            return Ok(Some(diff));
+        }
+
+        path.truncate(parent);
+    }
+
+    Ok(None)
+}
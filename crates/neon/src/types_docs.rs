@@ -69,11 +69,13 @@
 ///     JsBoolean(JsBoolean)
 ///     JsNumber(JsNumber)
 ///     JsString(JsString)
+///     JsSymbol(JsSymbol)
 ///     JsNull(JsNull)
 ///     JsUndefined(JsUndefined)
 ///     click JsBoolean "./struct.JsBoolean.html" "JsBoolean"
 ///     click JsNumber "./struct.JsNumber.html" "JsNumber"
 ///     click JsString "./struct.JsString.html" "JsString"
+///     click JsSymbol "./struct.JsSymbol.html" "JsSymbol"
 ///     click JsNull "./struct.JsNull.html" "JsNull"
 ///     click JsUndefined "./struct.JsUndefined.html" "JsUndefined"
 /// end
@@ -85,7 +87,8 @@
 ///
 /// The primitive types are the built-in JavaScript datatypes that are not object
 /// types: [`JsBoolean`](crate::types::JsBoolean), [`JsNumber`](crate::types::JsNumber),
-/// [`JsString`](crate::types::JsString), [`JsNull`](crate::types::JsNull), and
+/// [`JsString`](crate::types::JsString), [`JsSymbol`](crate::types::JsSymbol),
+/// [`JsNull`](crate::types::JsNull), and
 /// [`JsUndefined`](crate::types::JsUndefined).
 ///
 /// #### Object Types
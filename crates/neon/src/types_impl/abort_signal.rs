@@ -0,0 +1,231 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    context::{internal::ContextInternal, Context, TaskContext},
+    event::Channel,
+    handle::{Handle, Root},
+    object::Object,
+    result::{JsResult, NeonResult},
+    types::{JsBoolean, JsFunction, JsObject, JsValue},
+};
+
+type Callback =
+    Box<dyn for<'a> FnOnce(TaskContext<'a>, Handle<'a, JsValue>) -> NeonResult<()> + Send>;
+
+/// A Rust-side token observing a JavaScript
+/// [`AbortSignal`](https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal).
+///
+/// An `AbortSignal` may be cloned and sent across threads, e.g., to check
+/// [`AbortSignal::is_aborted`] periodically from a long running computation. An
+/// `abort` listener is added to the signal when the token is created and is removed
+/// once every clone of the token has been dropped. Callbacks added with
+/// [`AbortSignal::register`] that have not been called by then are dropped.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::AbortSignal;
+///
+/// fn count_until_aborted(mut cx: FunctionContext) -> JsResult<JsPromise> {
+///     let signal = cx.argument::<JsObject>(0)?;
+///     let signal = AbortSignal::from_object(&mut cx, signal)?;
+///     let (deferred, promise) = cx.promise();
+///     let channel = cx.channel();
+///
+///     std::thread::spawn(move || {
+///         let mut n = 0;
+///
+///         while !signal.is_aborted() {
+///             n += 1;
+///         }
+///
+///         deferred.settle_with(&channel, move |mut cx| Ok(cx.number(n)));
+///     });
+///
+///     Ok(promise)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct AbortSignal {
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for AbortSignal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AbortSignal")
+            .field("aborted", &self.is_aborted())
+            .finish()
+    }
+}
+
+impl AbortSignal {
+    /// Creates a token observing `signal`.
+    ///
+    /// Throws a `TypeError` if `signal` is not an `AbortSignal`.
+    pub fn from_object<'a, C: Context<'a>>(
+        cx: &mut C,
+        signal: Handle<JsObject>,
+    ) -> NeonResult<Self> {
+        let aborted = signal.get::<JsBoolean, _, _>(cx, "aborted")?.value(cx);
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                aborted,
+                callbacks: Vec::new(),
+            }),
+        });
+
+        let listener = if aborted {
+            None
+        } else {
+            let listener = Self::listener(cx, Arc::clone(&shared))?;
+            let add = signal.get::<JsFunction, _, _>(cx, "addEventListener")?;
+            let event = cx.string("abort");
+            let options = cx.empty_object();
+            let once = cx.boolean(true);
+
+            options.set(cx, "once", once)?;
+            add.exec(
+                cx,
+                signal,
+                [event.upcast(), listener.upcast(), options.upcast()],
+            )?;
+
+            Some(listener.root(cx))
+        };
+
+        let mut channel = cx.channel();
+
+        // Observing a signal should not keep the event loop alive
+        channel.unref(cx);
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                shared,
+                signal: Some(signal.root(cx)),
+                listener,
+                channel,
+            }),
+        })
+    }
+
+    /// Indicates whether the signal has been aborted.
+    pub fn is_aborted(&self) -> bool {
+        self.inner.shared.state.lock().unwrap().aborted
+    }
+
+    /// Returns the reason the signal was aborted with, or `undefined` if it has not
+    /// been aborted.
+    pub fn reason<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsValue> {
+        // `unwrap` will not fail because `signal` is only `None` during `Drop`
+        let signal = self.inner.signal.as_ref().unwrap().to_inner(cx);
+
+        signal.get_value(cx, "reason")
+    }
+
+    /// Registers `f` to be called on the JavaScript thread with the abort reason when
+    /// the signal is aborted.
+    ///
+    /// If the signal has already been aborted, `f` is scheduled to be called
+    /// immediately. `register` may be called from any thread. If `f` throws, the
+    /// exception is reported asynchronously and callbacks registered after `f` are
+    /// not called.
+    pub fn register<F>(&self, f: F)
+    where
+        F: for<'a> FnOnce(TaskContext<'a>, Handle<'a, JsValue>) -> NeonResult<()> + Send + 'static,
+    {
+        let f: Callback = Box::new(f);
+
+        {
+            let mut state = self.inner.shared.state.lock().unwrap();
+
+            if !state.aborted {
+                state.callbacks.push(f);
+                return;
+            }
+        }
+
+        let signal = self.clone();
+
+        // `Channel::try_send` will only fail if the environment has shutdown,
+        // in which case the signal can no longer be observed.
+        let _ = self.inner.channel.try_send(move |mut cx| {
+            let reason = signal.reason(&mut cx)?;
+
+            f(cx, reason)
+        });
+    }
+
+    // Creates the `abort` event listener. The listener must not reference the
+    // `Inner`, otherwise it would never be dropped.
+    fn listener<'a, C: Context<'a>>(cx: &mut C, shared: Arc<Shared>) -> JsResult<'a, JsFunction> {
+        JsFunction::new(cx, move |mut cx| {
+            let callbacks = {
+                let mut state = shared.state.lock().unwrap();
+
+                state.aborted = true;
+                std::mem::take(&mut state.callbacks)
+            };
+
+            let signal = cx.this::<JsObject>()?;
+            let env = cx.env();
+
+            for f in callbacks {
+                TaskContext::with_context(env, move |mut cx| {
+                    let reason = signal.get_value(&mut cx, "reason")?;
+
+                    f(cx, reason)
+                })?;
+            }
+
+            Ok(cx.undefined())
+        })
+    }
+}
+
+struct Shared {
+    state: Mutex<State>,
+}
+
+struct State {
+    aborted: bool,
+    callbacks: Vec<Callback>,
+}
+
+struct Inner {
+    shared: Arc<Shared>,
+    // `Option` so that the `Root`s can be moved to the JavaScript thread on `Drop`.
+    // `signal` will *always* be `Some` until then.
+    signal: Option<Root<JsObject>>,
+    listener: Option<Root<JsFunction>>,
+    channel: Channel,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        let signal = self.signal.take();
+        let listener = self.listener.take();
+
+        // `Channel::try_send` will only fail if the environment has shutdown.
+        // In that case, the teardown will perform clean-up.
+        let _ = self.channel.try_send(move |mut cx| {
+            let signal = match signal {
+                Some(signal) => signal.into_inner(&mut cx),
+                None => return Ok(()),
+            };
+
+            if let Some(listener) = listener {
+                let listener = listener.into_inner(&mut cx);
+                let remove = signal.get::<JsFunction, _, _>(&mut cx, "removeEventListener")?;
+                let event = cx.string("abort");
+
+                remove.exec(&mut cx, signal, [event.upcast(), listener.upcast()])?;
+            }
+
+            Ok(())
+        });
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     any::{self, Any},
+    cell::{RefCell, RefMut},
     ops::Deref,
 };
 
@@ -253,13 +254,13 @@ impl<T: Finalize + Send + 'static> JsBox<T> {
     {
         // This function will execute immediately before the `JsBox` is garbage collected.
         // It unwraps the `napi_external`, downcasts the `BoxAny` and moves the type
-        // out of the `Box`. Lastly, it calls the trait method `Finalize::fianlize` of the
+        // out of the `Box`. Lastly, it calls the trait method `Finalize::finalize_gc` of the
         // contained value `T`.
         fn finalizer<U: Finalize + 'static>(env: raw::Env, data: BoxAny) {
             let data = *data.downcast::<U>().unwrap();
             let env = unsafe { std::mem::transmute(env) };
 
-            FinalizeContext::with(env, move |mut cx| data.finalize(&mut cx));
+            FinalizeContext::with(env, move |mut cx| data.finalize_gc(&mut cx));
         }
 
         let v = Box::new(value) as BoxAny;
@@ -281,9 +282,105 @@ impl<T: Send + 'static> Deref for JsBox<T> {
     }
 }
 
+impl<T: Send + 'static> JsBox<RefCell<T>> {
+    /// Calls `f` with exclusive access to the state in the `RefCell`, releasing the
+    /// borrow whenever `f` uses the [`Context`].
+    ///
+    /// Holding a `RefMut` while calling JavaScript panics if the JavaScript calls
+    /// back into a method that borrows the same box. Instead, `f` is passed a
+    /// [`ReentrantState`]: [`ReentrantState::state`] borrows the state and
+    /// [`ReentrantState::cx`] releases the borrow before returning the context. Since
+    /// both take `&mut self`, the state cannot be borrowed while calling JavaScript.
+    /// Changes made by re-entrant calls are visible the next time the state is
+    /// borrowed.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use std::cell::RefCell;
+    ///
+    /// struct Greeter {
+    ///     greeting: String,
+    ///     count: u32,
+    /// }
+    ///
+    /// impl Finalize for Greeter {}
+    ///
+    /// // `greeter.greet(name, callback)`
+    /// fn greet(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    ///     let greeter = cx.this::<JsBox<RefCell<Greeter>>>()?;
+    ///     let name = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///     let callback = cx.argument::<JsFunction>(1)?;
+    ///
+    ///     greeter.with_state_reentrant(&mut cx, |s| {
+    ///         let message = format!("{}, {}!", s.state().greeting, name);
+    ///
+    ///         s.state().count += 1;
+    ///
+    ///         // The callback may call `greet` again
+    ///         let cx = s.cx();
+    ///         let message = cx.string(message);
+    ///
+    ///         callback.call_with(cx).arg(message).exec(cx)?;
+    ///
+    ///         let count = s.state().count;
+    ///
+    ///         Ok(s.cx().number(count))
+    ///     })
+    /// }
+    /// ```
+    pub fn with_state_reentrant<'a, 's, C, F, R>(&'s self, cx: &mut C, f: F) -> R
+    where
+        C: Context<'a>,
+        F: FnOnce(&mut ReentrantState<'s, '_, C, T>) -> R,
+    {
+        f(&mut ReentrantState {
+            cell: self,
+            cx,
+            borrow: None,
+        })
+    }
+}
+
+/// Access to the state of a `JsBox<RefCell<T>>` that is safe to hold across calls
+/// into JavaScript. See [`JsBox::with_state_reentrant`].
+pub struct ReentrantState<'s, 'c, C, T> {
+    cell: &'s RefCell<T>,
+    cx: &'c mut C,
+    borrow: Option<RefMut<'s, T>>,
+}
+
+impl<'s, 'c, C, T> ReentrantState<'s, 'c, C, T> {
+    /// Mutably borrows the state, re-acquiring the borrow if it was released.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state is borrowed outside of this `ReentrantState`, e.g., by
+    /// a caller that borrowed the `RefCell` directly while calling JavaScript.
+    pub fn state(&mut self) -> &mut T {
+        let cell = self.cell;
+
+        self.borrow.get_or_insert_with(|| cell.borrow_mut())
+    }
+
+    /// Releases the borrow of the state, if any, and returns the context for
+    /// calling JavaScript or creating values.
+    pub fn cx(&mut self) -> &mut C {
+        self.borrow = None;
+        self.cx
+    }
+}
+
+impl<'s, 'c, C, T> std::fmt::Debug for ReentrantState<'s, 'c, C, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReentrantState")
+            .field("borrowed", &self.borrow.is_some())
+            .finish()
+    }
+}
+
 /// A trait for finalizing values owned by the main JavaScript thread.
 ///
-/// [`Finalize::finalize`] is executed on the main JavaScript thread
+/// [`Finalize::finalize_gc`] is executed on the main JavaScript thread
 /// immediately before garbage collection.
 ///
 /// Values contained by a `JsBox` must implement `Finalize`.
@@ -299,32 +396,46 @@ impl<T: Send + 'static> Deref for JsBox<T> {
 /// impl Finalize for Point {}
 /// ```
 ///
-/// A `finalize` method may be specified for performing clean-up operations before dropping
-/// the contained value.
+/// A `finalize_gc` method may be specified for performing clean-up operations before
+/// dropping the contained value. JavaScript cannot be called during garbage collection;
+/// instead, [`FinalizeContext::defer`] schedules a closure with a full context.
 ///
 /// ```rust
 /// # use neon::prelude::*;
+/// use neon::context::FinalizeContext;
+///
 /// struct Point(f64, f64);
 ///
 /// impl Finalize for Point {
-///     fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
-///         let global = cx.global();
-///         let emit: Handle<JsFunction> = global
-///             .get(cx, "emit")
-///             .unwrap();
-///
-///         let args = vec![
-///             cx.string("gc_point").upcast::<JsValue>(),
-///             cx.number(self.0).upcast(),
-///             cx.number(self.1).upcast(),
-///         ];
-///
-///         emit.call(cx, global, args).unwrap();
+///     fn finalize_gc(self, cx: &mut FinalizeContext) {
+///         cx.defer(move |mut cx| {
+///             let global = cx.global();
+///             let emit: Handle<JsFunction> = global.get(&mut cx, "emit")?;
+///
+///             emit.call_with(&cx)
+///                 .arg(cx.string("gc_point"))
+///                 .arg(cx.number(self.0))
+///                 .arg(cx.number(self.1))
+///                 .exec(&mut cx)
+///         });
 ///     }
 /// }
 /// ```
 pub trait Finalize: Sized {
+    /// Finalizes the value with a full context.
+    ///
+    /// Called by the default implementation of [`Finalize::finalize_gc`]. Calling
+    /// JavaScript during garbage collection is unreliable; prefer implementing
+    /// `finalize_gc`.
     fn finalize<'a, C: Context<'a>>(self, _: &mut C) {}
+
+    /// Finalizes the value while it is garbage collected, with a [`FinalizeContext`]
+    /// that only permits operations that are safe during garbage collection.
+    ///
+    /// The default implementation calls [`Finalize::finalize`].
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        cx.with_context(|cx| self.finalize(cx));
+    }
 }
 
 // Primitives
@@ -373,6 +484,12 @@ macro_rules! finalize_tuple_impls {
                 let ($($name,)+) = self;
                 ($($name.finalize(cx),)+);
             }
+
+            fn finalize_gc(self, cx: &mut FinalizeContext) {
+                #![allow(non_snake_case)]
+                let ($($name,)+) = self;
+                ($($name.finalize_gc(cx),)+);
+            }
         }
     };
 }
@@ -395,6 +512,12 @@ impl<T: Finalize> Finalize for Vec<T> {
             item.finalize(cx);
         }
     }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        for item in self {
+            item.finalize_gc(cx);
+        }
+    }
 }
 
 // Smart pointers and other wrappers
@@ -403,6 +526,10 @@ impl<T: Finalize> Finalize for std::boxed::Box<T> {
     fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
         (*self).finalize(cx);
     }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        (*self).finalize_gc(cx);
+    }
 }
 
 impl<T: Finalize> Finalize for Option<T> {
@@ -411,6 +538,12 @@ impl<T: Finalize> Finalize for Option<T> {
             v.finalize(cx);
         }
     }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        if let Some(v) = self {
+            v.finalize_gc(cx);
+        }
+    }
 }
 
 impl<T: Finalize> Finalize for std::rc::Rc<T> {
@@ -419,6 +552,12 @@ impl<T: Finalize> Finalize for std::rc::Rc<T> {
             v.finalize(cx);
         }
     }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        if let Ok(v) = std::rc::Rc::try_unwrap(self) {
+            v.finalize_gc(cx);
+        }
+    }
 }
 
 impl<T: Finalize> Finalize for std::sync::Arc<T> {
@@ -427,6 +566,12 @@ impl<T: Finalize> Finalize for std::sync::Arc<T> {
             v.finalize(cx);
         }
     }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        if let Ok(v) = std::sync::Arc::try_unwrap(self) {
+            v.finalize_gc(cx);
+        }
+    }
 }
 
 impl<T: Finalize> Finalize for std::sync::Mutex<T> {
@@ -435,6 +580,12 @@ impl<T: Finalize> Finalize for std::sync::Mutex<T> {
             v.finalize(cx);
         }
     }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        if let Ok(v) = self.into_inner() {
+            v.finalize_gc(cx);
+        }
+    }
 }
 
 impl<T: Finalize> Finalize for std::sync::RwLock<T> {
@@ -443,16 +594,30 @@ impl<T: Finalize> Finalize for std::sync::RwLock<T> {
             v.finalize(cx);
         }
     }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        if let Ok(v) = self.into_inner() {
+            v.finalize_gc(cx);
+        }
+    }
 }
 
 impl<T: Finalize> Finalize for std::cell::Cell<T> {
     fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
         self.into_inner().finalize(cx);
     }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        self.into_inner().finalize_gc(cx);
+    }
 }
 
 impl<T: Finalize> Finalize for std::cell::RefCell<T> {
     fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
         self.into_inner().finalize(cx);
     }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        self.into_inner().finalize_gc(cx);
+    }
 }
@@ -0,0 +1,238 @@
+use std::cell::RefCell;
+
+use crate::{
+    context::{Context, FunctionContext},
+    handle::{Handle, Root},
+    object::Object,
+    result::JsResult,
+    types::{
+        extract::TryIntoJs, Finalize, JsArray, JsBox, JsFunction, JsNumber, JsObject, JsValue,
+    },
+};
+
+/// The contents of a [`JsBox`] backing a JavaScript iterator that pulls its values from
+/// a Rust [`Iterator`], created with [`Context::boxed_iterator`].
+///
+/// The JavaScript object follows the
+/// [iterator protocol](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Iteration_protocols),
+/// so it can be used with `for...of` and spread syntax, and has additional methods:
+///
+/// * `next()` converts the next item with [`TryIntoJs`] and returns
+///   `{ value, done: false }`, or `{ value: undefined, done: true }` once the Rust
+///   iterator is exhausted.
+/// * `nextChunk(n)` returns an array of up to `n` converted items, amortizing the cost
+///   of crossing into Rust over many items. The array is empty once the iterator is
+///   exhausted.
+/// * `return()` closes the iterator early, as `break` in a `for...of` loop does.
+///
+/// The Rust iterator is dropped as soon as it is exhausted, fails, or is closed, and
+/// otherwise when the JavaScript object is garbage collected, releasing any resources
+/// it holds.
+///
+/// # Errors
+///
+/// If converting an item throws, e.g., for an item that is an `Err` of a
+/// `Result<T, neon::error::Error>`, the iterator is dropped and the exception is stored.
+/// Every later call to `next` or `nextChunk` throws the same exception. If `nextChunk`
+/// already converted some items when an item fails, it returns them and the exception
+/// is thrown by the following call.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use std::collections::BTreeMap;
+///
+/// fn scan(mut cx: FunctionContext) -> JsResult<JsObject> {
+///     let prefix = cx.argument::<JsString>(0)?.value(&mut cx);
+///     let db = (0..10_000)
+///         .map(|i| (format!("key:{:05}", i), i as f64))
+///         .collect::<BTreeMap<_, _>>();
+///
+///     // Values are only converted as JavaScript asks for them
+///     let iter = db
+///         .into_iter()
+///         .skip_while(move |(key, _)| !key.starts_with(&prefix))
+///         .map(|(_, value)| value);
+///
+///     cx.boxed_iterator(iter)
+/// }
+/// ```
+///
+/// ```js
+/// const it = addon.scan("key:09");
+///
+/// const first = it.next().value;
+/// const chunk = it.nextChunk(100);
+///
+/// for (const value of it) {
+///     // ...
+/// }
+/// ```
+pub struct BoxedIterator<I> {
+    state: RefCell<State<I>>,
+}
+
+enum State<I> {
+    Active(I),
+    Done,
+    // An array containing the exception, since exceptions may be any value
+    Failed(Root<JsArray>),
+}
+
+impl<I> BoxedIterator<I>
+where
+    I: Iterator + Send + 'static,
+    I::Item: for<'cx> TryIntoJs<'cx>,
+{
+    pub(crate) fn object<'a, C: Context<'a>>(cx: &mut C, iter: I) -> JsResult<'a, JsObject> {
+        let boxed = cx.boxed(BoxedIterator {
+            state: RefCell::new(State::Active(iter)),
+        });
+
+        let obj = cx.empty_object();
+
+        // Methods are bound to the box, instead of capturing it, so that the object
+        // and the box can be garbage collected together
+        let next = JsFunction::new(cx, Self::next)?.bind(cx, boxed, ())?;
+        let next_chunk = JsFunction::new(cx, Self::next_chunk)?.bind(cx, boxed, ())?;
+        let close = JsFunction::new(cx, Self::close)?.bind(cx, boxed, ())?;
+        let iterator = JsFunction::new(cx, |mut cx| Ok(cx.this_value()))?;
+        let symbol = crate::intrinsics::symbol_iterator(cx)?;
+
+        obj.set(cx, "next", next)?;
+        obj.set(cx, "nextChunk", next_chunk)?;
+        obj.set(cx, "return", close)?;
+        obj.set(cx, symbol, iterator)?;
+
+        Ok(obj)
+    }
+
+    fn next(mut cx: FunctionContext) -> JsResult<JsObject> {
+        let this = cx.this::<JsBox<Self>>()?;
+        let value = match this.step(&mut cx) {
+            Ok(value) => value,
+            Err(exception) => return cx.throw(exception),
+        };
+        let done = value.is_none();
+        let value = value.unwrap_or_else(|| cx.undefined().upcast());
+
+        iter_result(&mut cx, value, done)
+    }
+
+    fn next_chunk(mut cx: FunctionContext) -> JsResult<JsArray> {
+        let this = cx.this::<JsBox<Self>>()?;
+        let n = cx.argument::<JsNumber>(0)?.value(&mut cx);
+
+        if n.is_nan() || n < 0.0 {
+            return cx.throw_range_error("chunk size must be a non-negative number");
+        }
+
+        let chunk = cx.empty_array();
+        let mut len = 0;
+
+        while f64::from(len) < n {
+            let value = match this.step(&mut cx) {
+                Ok(Some(value)) => value,
+                Ok(None) => break,
+                // The exception is thrown by the next call instead
+                Err(_) if len > 0 => break,
+                Err(exception) => return cx.throw(exception),
+            };
+
+            chunk.set(&mut cx, len, value)?;
+            len += 1;
+        }
+
+        Ok(chunk)
+    }
+
+    fn close(mut cx: FunctionContext) -> JsResult<JsObject> {
+        let this = cx.this::<JsBox<Self>>()?;
+        let mut state = this.state.borrow_mut();
+
+        // A failed iterator keeps throwing
+        if let State::Active(_) = *state {
+            *state = State::Done;
+        }
+
+        drop(state);
+
+        let value = cx.undefined();
+
+        iter_result(&mut cx, value.upcast(), true)
+    }
+
+    // Converts the next item, or returns `None` if the iterator is done. Returns the
+    // exception without throwing it if the iterator has failed.
+    fn step<'a>(
+        &self,
+        cx: &mut FunctionContext<'a>,
+    ) -> Result<Option<Handle<'a, JsValue>>, Handle<'a, JsValue>> {
+        let mut state = self.state.borrow_mut();
+        let item = match &mut *state {
+            State::Active(iter) => iter.next(),
+            State::Done => return Ok(None),
+            State::Failed(holder) => {
+                let holder = holder.to_inner(cx);
+
+                return Err(match cx.try_catch(|cx| holder.get_value(cx, 0)) {
+                    Ok(exception) => exception,
+                    Err(exception) => exception,
+                });
+            }
+        };
+
+        let item = match item {
+            Some(item) => item,
+            None => {
+                *state = State::Done;
+                return Ok(None);
+            }
+        };
+
+        // Converting may call into JavaScript, which may call the iterator again
+        drop(state);
+
+        match cx.try_catch(|cx| Ok(item.try_into_js(cx)?.upcast::<JsValue>())) {
+            Ok(value) => Ok(Some(value)),
+            Err(exception) => {
+                let holder = cx.empty_array();
+
+                // Setting an index of a new array does not throw
+                let _ = holder.set(cx, 0, exception);
+
+                let holder = holder.root(cx);
+                let other = std::mem::replace(&mut *self.state.borrow_mut(), State::Failed(holder));
+
+                if let State::Failed(other) = other {
+                    other.drop(cx);
+                }
+
+                Err(exception)
+            }
+        }
+    }
+}
+
+impl<I> Finalize for BoxedIterator<I> {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        if let State::Failed(holder) = self.state.into_inner() {
+            holder.drop(cx);
+        }
+    }
+}
+
+fn iter_result<'a>(
+    cx: &mut FunctionContext<'a>,
+    value: Handle<'a, JsValue>,
+    done: bool,
+) -> JsResult<'a, JsObject> {
+    let result = cx.empty_object();
+    let done = cx.boolean(done);
+
+    result.set(cx, "value", value)?;
+    result.set(cx, "done", done)?;
+
+    Ok(result)
+}
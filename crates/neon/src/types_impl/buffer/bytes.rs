@@ -0,0 +1,159 @@
+//! Conversions between [`JsBuffer`] and [`bytes::Bytes`]
+
+use ::bytes::Bytes;
+
+use crate::{
+    context::Context,
+    handle::{Handle, Managed},
+    result::{JsResult, NeonResult},
+    sys,
+    types::{
+        buffer::TypedArray,
+        extract::{TryFromJs, TryIntoJs},
+        JsBuffer, JsValue,
+    },
+};
+
+#[cfg(feature = "napi-6")]
+use crate::{handle::Root, types_impl::buffer::debug};
+
+impl JsBuffer {
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+    /// Constructs a `JsBuffer` from [`Bytes`], sharing its memory if possible.
+    ///
+    /// If `bytes` is the only reference to its memory, the buffer is backed by that
+    /// memory, which is dropped when the buffer is garbage collected. Otherwise, since
+    /// JavaScript may write to the buffer, the bytes are copied. They are also copied in
+    /// environments that [disallow external buffers](crate::runtime::Features::external_buffers),
+    /// such as Electron.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use bytes::{BufMut, BytesMut};
+    ///
+    /// fn frame(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    ///     let payload = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///     let mut frame = BytesMut::with_capacity(4 + payload.len());
+    ///
+    ///     frame.put_u32(payload.len() as u32);
+    ///     frame.put_slice(payload.as_bytes());
+    ///
+    ///     JsBuffer::from_bytes(&mut cx, frame.freeze())
+    /// }
+    /// ```
+    pub fn from_bytes<'a, C: Context<'a>>(cx: &mut C, bytes: Bytes) -> JsResult<'a, Self> {
+        let data = match bytes.try_into_mut() {
+            Ok(data) if !data.is_empty() && crate::runtime::features(cx).external_buffers => data,
+            Ok(data) => return Self::from_slice(cx, &data),
+            Err(bytes) => return Self::from_slice(cx, &bytes),
+        };
+
+        let env = cx.env();
+
+        match unsafe { sys::buffer::new_external(env.to_raw(), data) } {
+            Ok(buf) => Ok(Handle::new_internal(Self::from_raw(env, buf))),
+            Err(data) => Self::from_slice(cx, &data),
+        }
+    }
+
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "bytes", feature = "napi-6"))))]
+    /// Creates [`Bytes`] that share the memory of the buffer, without copying.
+    ///
+    /// The `Bytes`, and every clone or slice of it, keep the buffer from being garbage
+    /// collected. The buffer may be collected after the last of them is dropped, on
+    /// any thread.
+    ///
+    /// Extracting `Bytes` with [`TryFromJs`] copies the buffer instead, which is always
+    /// safe.
+    ///
+    /// # Safety
+    ///
+    /// `Bytes` are immutable and may be read from any thread, but keeping the buffer
+    /// alive does not stop JavaScript from using it. Until the `Bytes` and every clone
+    /// or slice of it are dropped:
+    ///
+    /// * JavaScript must not write to the buffer, or to any other view of the same
+    ///   memory. Writes would race with reads of the `Bytes`.
+    /// * The `ArrayBuffer` of the buffer must not be detached, e.g., by
+    ///   [transferring](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/ArrayBuffer/transfer)
+    ///   it or posting it to a worker, which frees the memory.
+    ///
+    /// Small buffers, such as those created with `Buffer.from`, may share an
+    /// `ArrayBuffer` with other, unrelated buffers.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// // The buffer must not be modified or detached until the promise settles
+    /// fn checksum(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    ///     let buf = cx.argument::<JsBuffer>(0)?;
+    ///
+    ///     // Safety: `checksum` documents that the buffer must not be used until the
+    ///     // promise settles, when the task and `data` have been dropped
+    ///     let data = unsafe { buf.to_bytes(&mut cx) };
+    ///
+    ///     let promise = cx
+    ///         .task(move || data.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)))
+    ///         .promise(|mut cx, sum| Ok(cx.number(sum)));
+    ///
+    ///     Ok(promise)
+    /// }
+    /// ```
+    pub unsafe fn to_bytes<'a, C: Context<'a>>(&self, cx: &mut C) -> Bytes {
+        let env = cx.env().to_raw();
+        let data = unsafe { sys::buffer::as_mut_slice(env, self.to_raw()) };
+
+        debug::check_slice(env, data, false);
+
+        if data.is_empty() {
+            return Bytes::new();
+        }
+
+        Bytes::from_owner(BufferOwner {
+            data: data.as_ptr(),
+            len: data.len(),
+            _buffer: Root::new(cx, self),
+        })
+    }
+}
+
+#[cfg(feature = "napi-6")]
+// Keeps the memory of a buffer alive while any `Bytes` created from it exist
+struct BufferOwner {
+    data: *const u8,
+    len: usize,
+    _buffer: Root<JsBuffer>,
+}
+
+#[cfg(feature = "napi-6")]
+// Safety: The memory is kept alive by the `Root`, which can be dropped on any thread
+unsafe impl Send for BufferOwner {}
+
+#[cfg(feature = "napi-6")]
+impl AsRef<[u8]> for BufferOwner {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.data, self.len) }
+    }
+}
+
+/// Converts with [`JsBuffer::from_bytes`].
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl<'cx> TryIntoJs<'cx> for Bytes {
+    type Value = JsBuffer;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsBuffer> {
+        JsBuffer::from_bytes(cx, self)
+    }
+}
+
+/// Extracts a copy of a `Buffer`; throws a `TypeError` for any other value.
+///
+/// See [`JsBuffer::to_bytes`] to share the memory of the buffer instead.
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl<'cx> TryFromJs<'cx> for Bytes {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        let buf = v.downcast_or_throw::<JsBuffer, _>(cx)?;
+
+        Ok(Bytes::copy_from_slice(buf.as_slice(cx)))
+    }
+}
@@ -0,0 +1,275 @@
+// Tracking of active buffer borrows across locks for the `debug-borrows` feature
+//
+// `Ledger` only checks borrows made with a single `Lock`. With the `debug-borrows`
+// feature enabled in a debug build, every borrow made with `try_borrow` or
+// `try_borrow_mut` is also recorded in a per-environment registry, along with the
+// location where it was created. New borrows, including `as_slice` and `as_mut_slice`,
+// are checked against the registry and the registry is checked before calling into
+// JavaScript, panicking on misuse.
+//
+// Without the feature, or in release builds, every function is an empty stub and
+// `Tracked` is zero-sized.
+
+use crate::sys::raw;
+
+#[cfg(all(feature = "debug-borrows", debug_assertions))]
+use super::lock::Ledger;
+
+#[cfg(all(feature = "debug-borrows", debug_assertions))]
+pub(crate) use self::enabled::*;
+
+#[cfg(not(all(feature = "debug-borrows", debug_assertions)))]
+pub(crate) use self::disabled::*;
+
+#[cfg(all(feature = "debug-borrows", debug_assertions))]
+mod enabled {
+    use std::{cell::RefCell, ops::Range, panic::Location};
+
+    use super::{raw, Ledger};
+
+    struct Entry {
+        id: u64,
+        env: raw::Env,
+        range: Range<*const u8>,
+        mutable: bool,
+        location: &'static Location<'static>,
+    }
+
+    #[derive(Default)]
+    struct Registry {
+        next_id: u64,
+        entries: Vec<Entry>,
+    }
+
+    thread_local! {
+        // JavaScript environments are bound to a single thread, so a thread local
+        // registry, partitioned by environment, is sufficient
+        static REGISTRY: RefCell<Registry> = RefCell::default();
+    }
+
+    #[derive(Debug)]
+    // An active borrow, removed from the registry when dropped
+    pub(crate) struct Tracked {
+        id: Option<u64>,
+    }
+
+    impl Tracked {
+        pub(crate) fn none() -> Self {
+            Self { id: None }
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            let id = match self.id {
+                Some(id) => id,
+                None => return,
+            };
+
+            // The registry may already be destroyed if the thread is exiting
+            let _ = REGISTRY.try_with(|registry| {
+                registry.borrow_mut().entries.retain(|entry| entry.id != id);
+            });
+        }
+    }
+
+    fn kind(mutable: bool) -> &'static str {
+        if mutable {
+            "mutable"
+        } else {
+            "immutable"
+        }
+    }
+
+    fn is_disjoint(a: &Range<*const u8>, b: &Range<*const u8>) -> bool {
+        b.start >= a.end || a.start >= b.end
+    }
+
+    // Panics if a statically checked borrow of `data` conflicts with an active borrow.
+    // The end of a static borrow cannot be observed, so it is not recorded.
+    #[track_caller]
+    pub(crate) fn check_slice<T>(env: raw::Env, data: &[T], mutable: bool) {
+        check(
+            env,
+            &Ledger::slice_to_range(data),
+            mutable,
+            Location::caller(),
+        );
+    }
+
+    // Panics if a new borrow of `range` conflicts with an active borrow
+    fn check(
+        env: raw::Env,
+        range: &Range<*const u8>,
+        mutable: bool,
+        location: &'static Location<'static>,
+    ) {
+        REGISTRY.with(|registry| {
+            let registry = registry.borrow();
+            let conflict = registry.entries.iter().find(|entry| {
+                entry.env == env && (entry.mutable || mutable) && !is_disjoint(&entry.range, range)
+            });
+
+            if let Some(entry) = conflict {
+                panic!(
+                    "{} buffer borrow at {} overlaps with an active {} borrow created at {}",
+                    kind(mutable),
+                    location,
+                    kind(entry.mutable),
+                    entry.location,
+                );
+            }
+        })
+    }
+
+    // Checks and records a new borrow of `range`
+    pub(crate) fn track(
+        env: raw::Env,
+        range: Range<*const u8>,
+        mutable: bool,
+        location: &'static Location<'static>,
+    ) -> Tracked {
+        check(env, &range, mutable, location);
+
+        REGISTRY.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            let id = registry.next_id;
+
+            registry.next_id += 1;
+            registry.entries.push(Entry {
+                id,
+                env,
+                range,
+                mutable,
+                location,
+            });
+
+            Tracked { id: Some(id) }
+        })
+    }
+
+    // Panics if any borrow is active in `env` before calling into JavaScript
+    pub(crate) fn assert_no_borrows(env: raw::Env, location: &'static Location<'static>) {
+        REGISTRY.with(|registry| {
+            let registry = registry.borrow();
+
+            if let Some(entry) = registry.entries.iter().find(|entry| entry.env == env) {
+                panic!(
+                    "call into JavaScript at {} while a {} buffer borrow created at {} is active",
+                    location,
+                    kind(entry.mutable),
+                    entry.location,
+                );
+            }
+        })
+    }
+}
+
+#[cfg(not(all(feature = "debug-borrows", debug_assertions)))]
+mod disabled {
+    use std::panic::Location;
+
+    use super::raw;
+
+    #[derive(Debug)]
+    pub(crate) struct Tracked;
+
+    impl Tracked {
+        #[inline(always)]
+        pub(crate) fn none() -> Self {
+            Self
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn check_slice<T>(_env: raw::Env, _data: &[T], _mutable: bool) {}
+
+    #[inline(always)]
+    pub(crate) fn assert_no_borrows(_env: raw::Env, _location: &'static Location<'static>) {}
+}
+
+#[cfg(all(test, feature = "debug-borrows", debug_assertions))]
+mod tests {
+    use std::{cell::RefCell, error::Error, ptr::NonNull, slice};
+
+    use super::{assert_no_borrows, check_slice, raw, Ledger};
+
+    fn env(id: usize) -> raw::Env {
+        NonNull::<u8>::dangling().as_ptr().wrapping_add(id).cast()
+    }
+
+    fn ledger(id: usize) -> RefCell<Ledger> {
+        RefCell::new(Ledger {
+            env: Some(env(id)),
+            ..Default::default()
+        })
+    }
+
+    // Super unsafe, but we only use it for testing
+    fn unsafe_aliased_slice<T>(data: &mut [T]) -> &'static mut [T] {
+        unsafe { slice::from_raw_parts_mut(data.as_mut_ptr(), data.len()) }
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps with an active mutable borrow created at")]
+    fn test_overlapping_borrows_across_ledgers() {
+        let (a, b) = (ledger(1), ledger(1));
+        let mut data = vec![0u8; 16];
+        let x = unsafe_aliased_slice(&mut data[0..8]);
+        let y = unsafe_aliased_slice(&mut data[4..12]);
+
+        let _x = Ledger::try_borrow_mut(&a, x).unwrap();
+        let _y = Ledger::try_borrow(&b, y);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps with an active immutable borrow created at")]
+    fn test_static_borrow_overlaps_tracked_borrow() {
+        let a = ledger(2);
+        let mut data = vec![0u8; 16];
+        let x = unsafe_aliased_slice(&mut data[..]);
+
+        let _x = Ledger::try_borrow(&a, &x[2..4]).unwrap();
+
+        check_slice(env(2), x, true);
+    }
+
+    #[test]
+    fn test_borrows_are_released() -> Result<(), Box<dyn Error>> {
+        let (a, b) = (ledger(3), ledger(3));
+        let mut data = vec![0u8; 16];
+        let x = unsafe_aliased_slice(&mut data[..]);
+        let y = unsafe_aliased_slice(&mut data[..]);
+
+        drop(Ledger::try_borrow_mut(&a, x)?);
+
+        let _y = Ledger::try_borrow_mut(&b, y)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_borrows_in_other_environments() -> Result<(), Box<dyn Error>> {
+        let (a, b) = (ledger(4), ledger(5));
+        let mut data = vec![0u8; 16];
+        let x = unsafe_aliased_slice(&mut data[..]);
+        let y = unsafe_aliased_slice(&mut data[..]);
+
+        let _x = Ledger::try_borrow_mut(&a, x)?;
+        let _y = Ledger::try_borrow_mut(&b, y)?;
+
+        assert_no_borrows(env(6), std::panic::Location::caller());
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "while a mutable buffer borrow created at")]
+    fn test_call_with_active_borrow() {
+        let a = ledger(7);
+        let mut data = vec![0u8; 16];
+        let _x = Ledger::try_borrow_mut(&a, &mut data[..]).unwrap();
+
+        assert_no_borrows(env(7), std::panic::Location::caller());
+    }
+}
@@ -0,0 +1,176 @@
+use crate::handle::{Handle, Managed};
+
+/// Controls what happens when an external buffer is created in an environment that
+/// [disallows external buffers](https://www.electronjs.org/blog/v8-memory-cage), such
+/// as Electron 21 and later.
+///
+/// Used with [`JsBuffer::external_with_policy`](crate::types::JsBuffer::external_with_policy)
+/// and [`JsArrayBuffer::external_with_policy`](crate::types::JsArrayBuffer::external_with_policy).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalPolicy {
+    /// Throw an `Error` if the data cannot be externalized.
+    Require,
+    /// Copy the data into a new buffer if it cannot be externalized. The original
+    /// data is dropped as soon as it has been copied. This is the policy used by
+    /// `external`.
+    PreferCopyFallback,
+    /// Always copy the data into a new buffer, without attempting to externalize it.
+    AlwaysCopy,
+}
+
+/// A buffer created from data allocated by Rust, indicating whether the data was
+/// externalized or copied.
+#[derive(Debug)]
+pub enum External<'a, T: Managed> {
+    /// The buffer is backed by the original data, which is dropped when the buffer
+    /// is garbage collected.
+    Externalized(Handle<'a, T>),
+    /// The data was copied into a buffer allocated by JavaScript and has been dropped.
+    Copied(Handle<'a, T>),
+}
+
+impl<'a, T: Managed> External<'a, T> {
+    /// Indicates whether the buffer is backed by the original data.
+    pub fn is_externalized(&self) -> bool {
+        matches!(self, External::Externalized(_))
+    }
+
+    /// Returns the buffer.
+    pub fn into_inner(self) -> Handle<'a, T> {
+        match self {
+            External::Externalized(v) | External::Copied(v) => v,
+        }
+    }
+}
+
+/// Outcome of creating an external buffer with a policy
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Outcome<R> {
+    Externalized(R),
+    Copied(R),
+    Disallowed,
+}
+
+/// Applies `policy`, attempting to externalize `data` with `externalize` if allowed
+/// and copying it with `copy` otherwise. `externalize` returns the data if the runtime
+/// disallows external buffers. The data is dropped immediately after being copied.
+pub(crate) fn with_policy<T, R, E>(
+    policy: ExternalPolicy,
+    allowed: bool,
+    data: T,
+    externalize: impl FnOnce(T) -> Result<R, T>,
+    copy: impl FnOnce(&mut T) -> Result<R, E>,
+) -> Result<Outcome<R>, E> {
+    let mut data = match policy {
+        ExternalPolicy::AlwaysCopy => data,
+        ExternalPolicy::Require | ExternalPolicy::PreferCopyFallback if allowed => {
+            match externalize(data) {
+                Ok(v) => return Ok(Outcome::Externalized(v)),
+                Err(data) => data,
+            }
+        }
+        _ => data,
+    };
+
+    if policy == ExternalPolicy::Require {
+        return Ok(Outcome::Disallowed);
+    }
+
+    let copied = copy(&mut data)?;
+
+    drop(data);
+
+    Ok(Outcome::Copied(copied))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+
+    // Data that records when it is dropped
+    struct Data(Rc<Cell<bool>>);
+
+    impl Drop for Data {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    fn run(
+        policy: ExternalPolicy,
+        allowed: bool,
+        status_ok: bool,
+    ) -> (Outcome<&'static str>, bool) {
+        let dropped = Rc::new(Cell::new(false));
+        let data = Data(dropped.clone());
+        let outcome = with_policy::<_, _, ()>(
+            policy,
+            allowed,
+            data,
+            // Fakes `napi_no_external_buffers_allowed` unless `status_ok`
+            |data| {
+                if status_ok {
+                    std::mem::forget(data);
+                    Ok("external")
+                } else {
+                    Err(data)
+                }
+            },
+            |data| {
+                assert!(!data.0.get());
+                Ok("copy")
+            },
+        )
+        .unwrap();
+
+        (outcome, dropped.get())
+    }
+
+    #[test]
+    fn externalizes_when_allowed() {
+        for policy in [ExternalPolicy::Require, ExternalPolicy::PreferCopyFallback] {
+            assert_eq!(
+                run(policy, true, true),
+                (Outcome::Externalized("external"), false)
+            );
+        }
+    }
+
+    #[test]
+    fn copies_on_disallowed_status() {
+        assert_eq!(
+            run(ExternalPolicy::PreferCopyFallback, true, false),
+            (Outcome::Copied("copy"), true)
+        );
+    }
+
+    #[test]
+    fn copies_without_attempting_when_not_allowed() {
+        assert_eq!(
+            run(ExternalPolicy::PreferCopyFallback, false, true),
+            (Outcome::Copied("copy"), true)
+        );
+    }
+
+    #[test]
+    fn always_copies() {
+        assert_eq!(
+            run(ExternalPolicy::AlwaysCopy, true, true),
+            (Outcome::Copied("copy"), true)
+        );
+    }
+
+    #[test]
+    fn require_fails_on_disallowed_status() {
+        assert_eq!(
+            run(ExternalPolicy::Require, true, false),
+            (Outcome::Disallowed, true)
+        );
+        assert_eq!(
+            run(ExternalPolicy::Require, false, true),
+            (Outcome::Disallowed, true)
+        );
+    }
+}
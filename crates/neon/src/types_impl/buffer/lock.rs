@@ -1,8 +1,8 @@
-use std::{cell::RefCell, ops::Range};
+use std::{cell::RefCell, ops::Range, panic::Location};
 
 use crate::{
     context::Context,
-    types::buffer::{BorrowError, Ref, RefMut},
+    types::buffer::{debug, BorrowError, Ref, RefMut},
 };
 
 #[derive(Debug)]
@@ -23,9 +23,18 @@ where
 {
     /// Constructs a new [`Lock`] and locks the VM. See also [`Context::lock`].
     pub fn new(cx: &'cx mut C) -> Lock<'cx, C> {
+        #[cfg(all(feature = "debug-borrows", debug_assertions))]
+        let ledger = Ledger {
+            env: Some(crate::context::internal::ContextInternal::env(cx).to_raw()),
+            ..Default::default()
+        };
+
+        #[cfg(not(all(feature = "debug-borrows", debug_assertions)))]
+        let ledger = Ledger::default();
+
         Lock {
             cx,
-            ledger: Default::default(),
+            ledger: RefCell::new(ledger),
         }
     }
 }
@@ -40,6 +49,10 @@ pub(super) struct Ledger {
 
     // Immutable borrows. May overlap or contain duplicates.
     pub(super) shared: Vec<Range<*const u8>>,
+
+    // Environment of the lock, used to check borrows across locks
+    #[cfg(all(feature = "debug-borrows", debug_assertions))]
+    pub(super) env: Option<crate::sys::raw::Env>,
 }
 
 impl Ledger {
@@ -54,24 +67,54 @@ impl Ledger {
 
     // Dynamically check a slice conforms to borrow rules before returning by
     // using interior mutability of the ledger.
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     pub(super) fn try_borrow<'a, T>(
         ledger: &'a RefCell<Self>,
         data: &'a [T],
     ) -> Result<Ref<'a, T>, BorrowError> {
         ledger.borrow_mut().try_add_borrow(data)?;
 
-        Ok(Ref { ledger, data })
+        let tracked = ledger.borrow().track(data, false, Location::caller());
+
+        Ok(Ref {
+            ledger,
+            data,
+            _tracked: tracked,
+        })
     }
 
     // Dynamically check a mutable slice conforms to borrow rules before returning by
     // using interior mutability of the ledger.
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     pub(super) fn try_borrow_mut<'a, T>(
         ledger: &'a RefCell<Self>,
         data: &'a mut [T],
     ) -> Result<RefMut<'a, T>, BorrowError> {
         ledger.borrow_mut().try_add_borrow_mut(data)?;
 
-        Ok(RefMut { ledger, data })
+        let tracked = ledger.borrow().track(data, true, Location::caller());
+
+        Ok(RefMut {
+            ledger,
+            data,
+            _tracked: tracked,
+        })
+    }
+
+    // Record the borrow in the per-environment registry of the `debug-borrows` feature
+    #[allow(unused_variables)]
+    fn track<T>(
+        &self,
+        data: &[T],
+        mutable: bool,
+        location: &'static Location<'static>,
+    ) -> debug::Tracked {
+        #[cfg(all(feature = "debug-borrows", debug_assertions))]
+        if let Some(env) = self.env {
+            return debug::track(env, Self::slice_to_range(data), mutable, location);
+        }
+
+        debug::Tracked::none()
     }
 
     // Try to add an immutable borrow to the ledger
@@ -18,17 +18,41 @@ use crate::{
     },
 };
 
+#[cfg(feature = "bytes")]
+mod bytes;
+pub(crate) mod debug;
+#[cfg(feature = "external-buffers")]
+pub(crate) mod external;
 pub(crate) mod lock;
+#[cfg(feature = "napi-6")]
+mod pool;
+mod text;
 pub(super) mod types;
 
+pub use text::{decode, decode_lossy, encode, Encoding};
 pub use types::Binary;
 
+#[cfg(feature = "external-buffers")]
+#[cfg_attr(docsrs, doc(cfg(feature = "external-buffers")))]
+pub use external::{External, ExternalPolicy};
+
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub use pool::BufferPool;
+
 /// A trait allowing Rust to borrow binary data from the memory buffer of JavaScript
 /// [typed arrays][typed-arrays].
 ///
 /// This trait provides both statically and dynamically checked borrowing. As usual
 /// in Rust, mutable borrows are guaranteed not to overlap with other borrows.
 ///
+/// Enable the `debug-borrows` feature to additionally check borrows across locks in
+/// debug builds. Borrows made with [`TypedArray::try_borrow`] and
+/// [`TypedArray::try_borrow_mut`] are recorded with their location, and Neon panics,
+/// naming both locations, if a later borrow overlaps an active mutable borrow or if
+/// JavaScript is called while a borrow is active. The feature has no effect in release
+/// builds.
+///
 /// # Example
 ///
 /// ```
@@ -54,6 +78,7 @@ pub trait TypedArray: Value {
     ///
     /// This may not be used if a mutable borrow is in scope. For the dynamically
     /// checked variant see [`TypedArray::try_borrow`].
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     fn as_slice<'cx, 'a, C>(&self, cx: &'a C) -> &'a [Self::Item]
     where
         C: Context<'cx>;
@@ -62,6 +87,7 @@ pub trait TypedArray: Value {
     ///
     /// This may not be used if any other borrow is in scope. For the dynamically
     /// checked variant see [`TypedArray::try_borrow_mut`].
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     fn as_mut_slice<'cx, 'a, C>(&mut self, cx: &'a mut C) -> &'a mut [Self::Item]
     where
         C: Context<'cx>;
@@ -72,6 +98,7 @@ pub trait TypedArray: Value {
     /// The borrow lasts until [`Ref`] exits scope.
     ///
     /// This is the dynamically checked version of [`TypedArray::as_slice`].
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     fn try_borrow<'cx, 'a, C>(&self, lock: &'a Lock<C>) -> Result<Ref<'a, Self::Item>, BorrowError>
     where
         C: Context<'cx>;
@@ -82,6 +109,7 @@ pub trait TypedArray: Value {
     /// The borrow lasts until [`RefMut`] exits scope.
     ///
     /// This is the dynamically checked version of [`TypedArray::as_mut_slice`].
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     fn try_borrow_mut<'cx, 'a, C>(
         &mut self,
         lock: &'a Lock<C>,
@@ -105,6 +133,7 @@ pub trait TypedArray: Value {
 pub struct Ref<'a, T> {
     data: &'a [T],
     ledger: &'a RefCell<Ledger>,
+    _tracked: debug::Tracked,
 }
 
 #[derive(Debug)]
@@ -112,6 +141,7 @@ pub struct Ref<'a, T> {
 pub struct RefMut<'a, T> {
     data: &'a mut [T],
     ledger: &'a RefCell<Ledger>,
+    _tracked: debug::Tracked,
 }
 
 impl<'a, T> Deref for Ref<'a, T> {
@@ -0,0 +1,222 @@
+use std::{
+    cell::{Cell, RefCell},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use crate::{
+    context::Context,
+    handle::{Managed, Root},
+    result::{JsResult, NeonResult},
+    sys,
+    types::{boxed::Finalize, buffer::TypedArray, JsArrayBuffer, JsUint8Array},
+};
+
+const DEFAULT_MAX_SLABS: usize = 16;
+
+/// A pool for allocating many small byte arrays out of a few larger `ArrayBuffer`s.
+///
+/// Allocating a separate buffer for each small chunk of binary data has a fixed
+/// cost on both the Rust and the JavaScript side. A `BufferPool` instead allocates
+/// _slabs_ of `slab_size` bytes and hands out [`JsUint8Array`] views over consecutive
+/// regions of a slab. A slab is recycled once every view over it has been garbage
+/// collected. Since finalizers may be deferred, slabs are usually only recycled after
+/// returning to the event loop.
+///
+/// Allocations that are larger than a slab, or that are made while every slab
+/// is in use and the pool is at its maximum number of slabs, fall back to allocating
+/// a new, unpooled array.
+///
+/// # Aliasing
+///
+/// Views allocated from a pool never overlap each other, and their contents are
+/// always zeroed on allocation. However, the `ArrayBuffer` underlying a view (i.e.,
+/// `view.buffer` in JavaScript) is the whole slab; it is shared with other views
+/// and is not tracked by the pool. Code that retains `view.buffer`, or creates other
+/// views over it, may observe the contents of unrelated allocations and may see them
+/// change after the slab is recycled, as with Node's
+/// [`Buffer.allocUnsafe`](https://nodejs.org/api/buffer.html#static-method-bufferallocunsafesize).
+/// A pool should not be used for sensitive data or for arrays handed to code that
+/// uses `view.buffer`.
+///
+/// A pool is typically stored in a [`JsBox`](crate::types::JsBox) or a
+/// [`LocalKey`](crate::thread::LocalKey) so that it can be reused across calls.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::buffer::BufferPool;
+///
+/// fn chunks(mut cx: FunctionContext) -> JsResult<JsArray> {
+///     let pool = BufferPool::new(&mut cx, 64 * 1024)?;
+///     let array = cx.empty_array();
+///
+///     for _ in 0..100 {
+///         let chunk = pool.alloc(&mut cx, 1024)?;
+///
+///         array.push(&mut cx, chunk)?;
+///     }
+///
+///     Ok(array)
+/// }
+/// ```
+pub struct BufferPool {
+    slab_size: usize,
+    max_slabs: usize,
+    slabs: RefCell<Vec<Slab>>,
+    current: Cell<usize>,
+}
+
+struct Slab {
+    buffer: Root<JsArrayBuffer>,
+    offset: usize,
+    // Number of views over the slab that have not been garbage collected
+    live: Arc<AtomicUsize>,
+}
+
+impl BufferPool {
+    /// Creates a pool of slabs of `slab_size` bytes, with at most 16 slabs.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, slab_size: usize) -> NeonResult<Self> {
+        Self::with_max_slabs(cx, slab_size, DEFAULT_MAX_SLABS)
+    }
+
+    /// Creates a pool of slabs of `slab_size` bytes, with at most `max_slabs` slabs.
+    ///
+    /// The first slab is allocated immediately.
+    pub fn with_max_slabs<'a, C: Context<'a>>(
+        cx: &mut C,
+        slab_size: usize,
+        max_slabs: usize,
+    ) -> NeonResult<Self> {
+        let mut slabs = Vec::with_capacity(max_slabs);
+
+        if max_slabs > 0 && slab_size > 0 {
+            slabs.push(Slab::new(cx, slab_size)?);
+        }
+
+        Ok(Self {
+            slab_size,
+            max_slabs,
+            slabs: RefCell::new(slabs),
+            current: Cell::new(0),
+        })
+    }
+
+    /// Allocates a zeroed array of `len` bytes from the pool.
+    ///
+    /// If the allocation cannot be served from a slab, a new unpooled array is returned.
+    pub fn alloc<'a, C: Context<'a>>(&self, cx: &mut C, len: usize) -> JsResult<'a, JsUint8Array> {
+        if len == 0 || len > self.slab_size {
+            return JsUint8Array::new(cx, len);
+        }
+
+        let mut slabs = self.slabs.borrow_mut();
+        let slab = match self.find_slab(cx, &mut slabs, len)? {
+            Some(slab) => slab,
+            None => return JsUint8Array::new(cx, len),
+        };
+
+        let buffer = slab.buffer.to_inner(cx);
+        let view = buffer.region(slab.offset, len).to_typed_array(cx)?;
+        let live = slab.live.clone();
+
+        slab.offset += len;
+        live.fetch_add(1, Ordering::Relaxed);
+
+        unsafe {
+            sys::object::add_finalizer(cx.env().to_raw(), view.to_raw(), move || {
+                live.fetch_sub(1, Ordering::Relaxed);
+            });
+        }
+
+        Ok(view)
+    }
+
+    /// Returns the size of each slab in bytes.
+    pub fn slab_size(&self) -> usize {
+        self.slab_size
+    }
+
+    /// Returns the number of slabs currently allocated by the pool.
+    pub fn slab_count(&self) -> usize {
+        self.slabs.borrow().len()
+    }
+
+    // Finds a slab with room for `len` bytes, preferring the current slab, then
+    // recycling an unused slab and finally allocating a new slab.
+    //
+    // JavaScript can detach a slab through any view over it, e.g., with
+    // `view.buffer.transfer()`, leaving it with a length of zero. Detached slabs
+    // are discarded when they are found.
+    fn find_slab<'s, 'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        slabs: &'s mut Vec<Slab>,
+        len: usize,
+    ) -> NeonResult<Option<&'s mut Slab>> {
+        let current = self.current.get();
+
+        if let Some(slab) = slabs.get(current) {
+            if !slab.is_attached(cx, self.slab_size) {
+                slabs.swap_remove(current).buffer.drop(cx);
+            } else if slab.offset + len <= self.slab_size {
+                return Ok(slabs.get_mut(current));
+            }
+        }
+
+        while let Some(i) = slabs
+            .iter()
+            .position(|slab| slab.live.load(Ordering::Relaxed) == 0)
+        {
+            if !slabs[i].is_attached(cx, self.slab_size) {
+                slabs.swap_remove(i).buffer.drop(cx);
+                continue;
+            }
+
+            let slab = &mut slabs[i];
+            let mut buffer = slab.buffer.to_inner(cx);
+
+            buffer.as_mut_slice(cx)[..slab.offset].fill(0);
+            slab.offset = 0;
+            self.current.set(i);
+
+            return Ok(Some(slab));
+        }
+
+        if slabs.len() < self.max_slabs {
+            slabs.push(Slab::new(cx, self.slab_size)?);
+            self.current.set(slabs.len() - 1);
+
+            return Ok(slabs.last_mut());
+        }
+
+        Ok(None)
+    }
+}
+
+impl Slab {
+    fn new<'a, C: Context<'a>>(cx: &mut C, size: usize) -> NeonResult<Self> {
+        let buffer = JsArrayBuffer::new(cx, size)?;
+
+        Ok(Self {
+            buffer: Root::new(cx, &*buffer),
+            offset: 0,
+            live: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    fn is_attached<'a, C: Context<'a>>(&self, cx: &mut C, size: usize) -> bool {
+        self.buffer.to_inner(cx).size(cx) == size
+    }
+}
+
+impl Finalize for BufferPool {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        for slab in self.slabs.into_inner() {
+            slab.buffer.drop(cx);
+        }
+    }
+}
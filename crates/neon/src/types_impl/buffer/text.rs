@@ -0,0 +1,291 @@
+//! Conversions between strings and bytes in a text encoding, similar to the
+//! JavaScript `TextEncoder` and `TextDecoder` classes.
+
+use std::{char::REPLACEMENT_CHARACTER, convert::TryFrom, fmt};
+
+use crate::{
+    context::Context,
+    result::{JsResult, NeonResult},
+    types::JsTypedArray,
+};
+
+/// A text encoding for use with [`decode`], [`decode_lossy`] and [`encode`].
+///
+/// `Latin1` is the ISO-8859-1 encoding used by Node's `"latin1"` `Buffer` encoding,
+/// where each byte is the Unicode code point of the same value. Note that the WHATWG
+/// Encoding Standard instead maps the `"latin1"` label to `windows-1252`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// UTF-8
+    Utf8,
+    /// UTF-16 in little endian byte order
+    Utf16Le,
+    /// ISO-8859-1
+    Latin1,
+    #[cfg(feature = "encoding_rs")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "encoding_rs")))]
+    /// An encoding of the [WHATWG Encoding Standard](https://encoding.spec.whatwg.org/),
+    /// e.g., `windows-1252` or `Shift_JIS`.
+    Whatwg(&'static encoding_rs::Encoding),
+}
+
+impl Encoding {
+    #[cfg(feature = "encoding_rs")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "encoding_rs")))]
+    /// Looks up an encoding by one of its
+    /// [WHATWG labels](https://encoding.spec.whatwg.org/#names-and-labels), as with
+    /// `new TextDecoder(label)`. Labels are case-insensitive and surrounding whitespace
+    /// is ignored. Returns `None` for unknown labels.
+    pub fn for_label(label: &str) -> Option<Self> {
+        let encoding = encoding_rs::Encoding::for_label(label.as_bytes())?;
+
+        Some(if encoding == encoding_rs::UTF_8 {
+            Encoding::Utf8
+        } else if encoding == encoding_rs::UTF_16LE {
+            Encoding::Utf16Le
+        } else {
+            Encoding::Whatwg(encoding)
+        })
+    }
+
+    /// The name of the encoding, e.g., `utf-8`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "utf-8",
+            Encoding::Utf16Le => "utf-16le",
+            Encoding::Latin1 => "latin1",
+            #[cfg(feature = "encoding_rs")]
+            Encoding::Whatwg(encoding) => encoding.name(),
+        }
+    }
+}
+
+// Error produced by a failed text conversion
+#[derive(Debug, PartialEq, Eq)]
+enum Error {
+    // An invalid byte sequence in the input of `decode`
+    InvalidSequence {
+        encoding: &'static str,
+        offset: usize,
+    },
+    // A character that cannot be represented in the output of `encode`
+    Unmappable {
+        encoding: &'static str,
+        offset: usize,
+        c: char,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidSequence { encoding, offset } => {
+                write!(f, "invalid {} sequence at byte offset {}", encoding, offset)
+            }
+            Error::Unmappable {
+                encoding,
+                offset,
+                c,
+            } => write!(
+                f,
+                "character {:?} at byte offset {} cannot be encoded as {}",
+                c, offset, encoding
+            ),
+        }
+    }
+}
+
+/// Decodes `bytes` as text in `encoding`, with the same result as a `TextDecoder`
+/// created with `{ fatal: true, ignoreBOM: true }`.
+///
+/// Throws a `TypeError` naming the byte offset of the first invalid sequence. A byte
+/// order mark is not removed.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::buffer::{self, Encoding, TypedArray};
+///
+/// fn decode_utf16(mut cx: FunctionContext) -> JsResult<JsString> {
+///     let data = cx.argument::<JsBuffer>(0)?;
+///     // Copied, since the slice borrows `cx`, which is needed to throw
+///     let bytes = data.as_slice(&cx).to_vec();
+///     let s = buffer::decode(&mut cx, &bytes, Encoding::Utf16Le)?;
+///
+///     Ok(cx.string(s))
+/// }
+/// ```
+pub fn decode<'a, C: Context<'a>>(
+    cx: &mut C,
+    bytes: &[u8],
+    encoding: Encoding,
+) -> NeonResult<String> {
+    decode_strict(bytes, encoding).or_else(|err| cx.throw_type_error(err.to_string()))
+}
+
+/// Decodes `bytes` as text in `encoding`, replacing invalid sequences with U+FFFD,
+/// with the same result as a `TextDecoder` created with `{ ignoreBOM: true }`.
+pub fn decode_lossy(bytes: &[u8], encoding: Encoding) -> String {
+    match encoding {
+        Encoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        Encoding::Utf16Le => {
+            let mut s = char::decode_utf16(utf16_units(bytes))
+                .map(|c| c.unwrap_or(REPLACEMENT_CHARACTER))
+                .collect::<String>();
+
+            if bytes.len() % 2 == 1 {
+                s.push(REPLACEMENT_CHARACTER);
+            }
+
+            s
+        }
+        Encoding::Latin1 => decode_latin1(bytes),
+        #[cfg(feature = "encoding_rs")]
+        Encoding::Whatwg(encoding) => encoding.decode_without_bom_handling(bytes).0.into_owned(),
+    }
+}
+
+/// Encodes `s` as bytes in `encoding`.
+///
+/// Throws a `TypeError` naming the byte offset in `s` of the first character that
+/// cannot be represented in `encoding`, e.g., a character above U+00FF in `Latin1`.
+/// UTF-8 and UTF-16 can represent every string. Following the WHATWG Encoding
+/// Standard, encodings that cannot be encoded to, such as `replacement`, produce UTF-8.
+pub fn encode<'a, C: Context<'a>>(
+    cx: &mut C,
+    s: &str,
+    encoding: Encoding,
+) -> JsResult<'a, JsTypedArray<u8>> {
+    let bytes = match encode_strict(s, encoding) {
+        Ok(bytes) => bytes,
+        Err(err) => return cx.throw_type_error(err.to_string()),
+    };
+
+    JsTypedArray::from_slice(cx, &bytes)
+}
+
+fn utf16_units(bytes: &[u8]) -> impl Iterator<Item = u16> + '_ {
+    bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+}
+
+fn decode_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| char::from(b)).collect()
+}
+
+fn decode_strict(bytes: &[u8], encoding: Encoding) -> Result<String, Error> {
+    let invalid = |offset| Error::InvalidSequence {
+        encoding: encoding.name(),
+        offset,
+    };
+
+    match encoding {
+        Encoding::Utf8 => match std::str::from_utf8(bytes) {
+            Ok(s) => Ok(s.to_owned()),
+            Err(err) => Err(invalid(err.valid_up_to())),
+        },
+        Encoding::Utf16Le => {
+            let mut s = String::with_capacity(bytes.len() / 2);
+            let mut offset = 0;
+
+            for c in char::decode_utf16(utf16_units(bytes)) {
+                let c = c.map_err(|_| invalid(offset))?;
+
+                s.push(c);
+                offset += c.len_utf16() * 2;
+            }
+
+            // A trailing byte that is not part of a code unit
+            if bytes.len() % 2 == 1 {
+                return Err(invalid(bytes.len() - 1));
+            }
+
+            Ok(s)
+        }
+        Encoding::Latin1 => Ok(decode_latin1(bytes)),
+        #[cfg(feature = "encoding_rs")]
+        Encoding::Whatwg(whatwg) => decode_whatwg(whatwg, bytes).map_err(invalid),
+    }
+}
+
+// Decodes without replacement, returning the offset of the first malformed sequence
+#[cfg(feature = "encoding_rs")]
+fn decode_whatwg(encoding: &'static encoding_rs::Encoding, bytes: &[u8]) -> Result<String, usize> {
+    use encoding_rs::DecoderResult;
+
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let capacity = decoder
+        .max_utf8_buffer_length_without_replacement(bytes.len())
+        .unwrap_or(bytes.len());
+    let mut s = String::with_capacity(capacity);
+    let mut read = 0;
+
+    loop {
+        let (result, n) =
+            decoder.decode_to_string_without_replacement(&bytes[read..], &mut s, true);
+
+        read += n;
+
+        match result {
+            DecoderResult::InputEmpty => return Ok(s),
+            DecoderResult::OutputFull => s.reserve(bytes.len() - read + 4),
+            // The malformed sequence ends before the `extra` bytes that were read past it
+            DecoderResult::Malformed(len, extra) => {
+                return Err(read - extra as usize - len as usize)
+            }
+        }
+    }
+}
+
+fn encode_strict(s: &str, encoding: Encoding) -> Result<Vec<u8>, Error> {
+    let unmappable = |offset, c| Error::Unmappable {
+        encoding: encoding.name(),
+        offset,
+        c,
+    };
+
+    match encoding {
+        Encoding::Utf8 => Ok(s.as_bytes().to_vec()),
+        Encoding::Utf16Le => Ok(s.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+        Encoding::Latin1 => s
+            .char_indices()
+            .map(|(offset, c)| u8::try_from(c).map_err(|_| unmappable(offset, c)))
+            .collect(),
+        #[cfg(feature = "encoding_rs")]
+        Encoding::Whatwg(whatwg) => {
+            use encoding_rs::EncoderResult;
+
+            // The WHATWG encoder for UTF-16BE produces UTF-8; encode it directly instead
+            if whatwg == encoding_rs::UTF_16BE {
+                return Ok(s.encode_utf16().flat_map(u16::to_be_bytes).collect());
+            }
+
+            let mut encoder = whatwg.new_encoder();
+            let capacity = encoder
+                .max_buffer_length_from_utf8_without_replacement(s.len())
+                .unwrap_or(s.len());
+            let mut bytes = Vec::with_capacity(capacity);
+            let mut read = 0;
+
+            loop {
+                let (result, n) = encoder.encode_from_utf8_to_vec_without_replacement(
+                    &s[read..],
+                    &mut bytes,
+                    true,
+                );
+
+                read += n;
+
+                match result {
+                    EncoderResult::InputEmpty => return Ok(bytes),
+                    EncoderResult::OutputFull => bytes.reserve(s.len() - read + 8),
+                    // `read` includes the unmappable character
+                    EncoderResult::Unmappable(c) => return Err(unmappable(read - c.len_utf8(), c)),
+                }
+            }
+        }
+    }
+}
@@ -1,13 +1,14 @@
-use std::{marker::PhantomData, slice};
+use std::{io, marker::PhantomData, slice};
 
 use crate::{
     context::{internal::Env, Context},
     handle::{internal::TransparentNoCopyWrapper, Handle, Managed},
     object::Object,
-    result::{JsResult, Throw},
+    result::{JsResult, NeonResult, Throw},
     sys::{self, raw, TypedArrayType},
     types_impl::{
         buffer::{
+            debug,
             lock::{Ledger, Lock},
             private::{self, JsTypedArrayInner},
             BorrowError, Ref, RefMut, Region, TypedArray,
@@ -17,6 +18,9 @@ use crate::{
     },
 };
 
+#[cfg(feature = "external-buffers")]
+use crate::types_impl::buffer::external::{self, External, ExternalPolicy, Outcome};
+
 #[cfg(feature = "doc-comment")]
 use doc_comment::doc_comment;
 
@@ -75,6 +79,55 @@ impl JsBuffer {
         <JsBuffer as TypedArray>::from_slice(cx, slice)
     }
 
+    /// Constructs a `JsBuffer` of `len` bytes read from `reader`.
+    ///
+    /// The bytes are read directly into the new buffer. An [`io::Error`] is thrown as an
+    /// `Error` with the same message, and so is `reader` ending before `len` bytes; the
+    /// buffer is never truncated. Reads are retried if they are
+    /// [interrupted](io::ErrorKind::Interrupted). Bytes after the first `len` are left
+    /// unread.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use std::{fs::File, io::Read};
+    ///
+    /// fn read_header(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    ///     let path = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///     let file = File::open(path).or_else(|err| cx.throw_error(err.to_string()))?;
+    ///
+    ///     JsBuffer::from_reader(&mut cx, file, 512)
+    /// }
+    /// ```
+    pub fn from_reader<'cx, C, R>(cx: &mut C, reader: R, len: usize) -> JsResult<'cx, Self>
+    where
+        C: Context<'cx>,
+        R: io::Read,
+    {
+        let buf = Self::new(cx, len)?;
+
+        fill_from_reader(cx, buf, reader)
+    }
+
+    /// Constructs a `JsBuffer` from the bytes of an iterator.
+    ///
+    /// If the [size hint](Iterator::size_hint) of the iterator is exact, the bytes are
+    /// written directly into a buffer of that size. Otherwise, they are first collected
+    /// into a `Vec`.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn squares(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    ///     JsBuffer::from_iter(&mut cx, (0..16u8).map(|i| i * i))
+    /// }
+    /// ```
+    pub fn from_iter<'cx, C, I>(cx: &mut C, iter: I) -> JsResult<'cx, Self>
+    where
+        C: Context<'cx>,
+        I: IntoIterator<Item = u8>,
+    {
+        fill_from_iter(cx, iter, Self::new)
+    }
+
     /// Constructs a new `Buffer` object with uninitialized memory
     pub unsafe fn uninitialized<'a, C: Context<'a>>(cx: &mut C, len: usize) -> JsResult<'a, Self> {
         let result = sys::buffer::uninitialized(cx.env().to_raw(), len);
@@ -98,6 +151,12 @@ impl JsBuffer {
     /// [runtime function](https://nodejs.org/api/n-api.html#napi_create_external_buffer)
     /// used by this method results in an immediate termination of the Node VM.
     ///
+    /// Neon avoids the call in environments that
+    /// [`neon::runtime::features`](crate::runtime::features) reports as disallowing
+    /// external buffers and in runtimes that fail the call with an error, copying `data`
+    /// into a new buffer instead. The detection is based on the runtime version and may
+    /// not recognize every such environment.
+    ///
     /// As a result, this API is disabled by default. If you are confident that your code will
     /// only be used in environments that disable sandboxed pointers, you can make use of this
     /// method by enabling the **`external-buffers`** feature flag.
@@ -106,10 +165,46 @@ impl JsBuffer {
         C: Context<'a>,
         T: AsMut<[u8]> + Send + 'static,
     {
-        let env = cx.env().to_raw();
-        let value = unsafe { sys::buffer::new_external(env, data) };
+        Self::external_with_policy(cx, data, ExternalPolicy::PreferCopyFallback)
+            .expect("failed to allocate buffer")
+            .into_inner()
+    }
 
-        Handle::new_internal(Self(value))
+    #[cfg(feature = "external-buffers")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "external-buffers")))]
+    /// Construct a new `Buffer` from bytes allocated by Rust, with `policy` controlling
+    /// what happens in environments that disallow external buffers.
+    ///
+    /// The result indicates whether `data` was externalized or copied. With
+    /// [`ExternalPolicy::Require`], an `Error` is thrown if it cannot be externalized.
+    pub fn external_with_policy<'a, C, T>(
+        cx: &mut C,
+        data: T,
+        policy: ExternalPolicy,
+    ) -> NeonResult<External<'a, Self>>
+    where
+        C: Context<'a>,
+        T: AsMut<[u8]> + Send + 'static,
+    {
+        let env = cx.env().to_raw();
+        let allowed = crate::runtime::features(cx).external_buffers;
+        let outcome = external::with_policy(
+            policy,
+            allowed,
+            data,
+            |data| unsafe { sys::buffer::new_external(env, data) },
+            |data| Self::from_slice(cx, data.as_mut()).map(|buf| buf.to_raw()),
+        )?;
+
+        match outcome {
+            Outcome::Externalized(value) => {
+                Ok(External::Externalized(Handle::new_internal(Self(value))))
+            }
+            Outcome::Copied(value) => Ok(External::Copied(Handle::new_internal(Self(value)))),
+            Outcome::Disallowed => {
+                cx.throw_error("external buffers are not allowed in this environment")
+            }
+        }
     }
 }
 
@@ -160,7 +255,12 @@ impl TypedArray for JsBuffer {
         // associated with a `Context` and the value will not be garbage collected while that
         // `Context` is in scope. This means that the referenced data is valid *at least* as long
         // as `Context`, even if the `Handle` is dropped.
-        unsafe { sys::buffer::as_mut_slice(cx.env().to_raw(), self.to_raw()) }
+        let env = cx.env().to_raw();
+        let data = unsafe { sys::buffer::as_mut_slice(env, self.to_raw()) };
+
+        debug::check_slice(env, data, false);
+
+        data
     }
 
     fn as_mut_slice<'cx, 'a, C>(&mut self, cx: &'a mut C) -> &'a mut [Self::Item]
@@ -169,7 +269,12 @@ impl TypedArray for JsBuffer {
     {
         // # Safety
         // See `as_slice`
-        unsafe { sys::buffer::as_mut_slice(cx.env().to_raw(), self.to_raw()) }
+        let env = cx.env().to_raw();
+        let data = unsafe { sys::buffer::as_mut_slice(env, self.to_raw()) };
+
+        debug::check_slice(env, data, true);
+
+        data
     }
 
     fn try_borrow<'cx, 'a, C>(&self, lock: &'a Lock<C>) -> Result<Ref<'a, Self::Item>, BorrowError>
@@ -260,6 +365,55 @@ impl JsArrayBuffer {
         <JsArrayBuffer as TypedArray>::from_slice(cx, slice)
     }
 
+    /// Constructs a `JsArrayBuffer` of `len` bytes read from `reader`.
+    ///
+    /// The bytes are read directly into the new buffer. An [`io::Error`] is thrown as an
+    /// `Error` with the same message, and so is `reader` ending before `len` bytes; the
+    /// buffer is never truncated. Reads are retried if they are
+    /// [interrupted](io::ErrorKind::Interrupted). Bytes after the first `len` are left
+    /// unread.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use std::{fs::File, io::Read};
+    ///
+    /// fn read_header(mut cx: FunctionContext) -> JsResult<JsArrayBuffer> {
+    ///     let path = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///     let file = File::open(path).or_else(|err| cx.throw_error(err.to_string()))?;
+    ///
+    ///     JsArrayBuffer::from_reader(&mut cx, file, 512)
+    /// }
+    /// ```
+    pub fn from_reader<'cx, C, R>(cx: &mut C, reader: R, len: usize) -> JsResult<'cx, Self>
+    where
+        C: Context<'cx>,
+        R: io::Read,
+    {
+        let buf = Self::new(cx, len)?;
+
+        fill_from_reader(cx, buf, reader)
+    }
+
+    /// Constructs a `JsArrayBuffer` from the bytes of an iterator.
+    ///
+    /// If the [size hint](Iterator::size_hint) of the iterator is exact, the bytes are
+    /// written directly into a buffer of that size. Otherwise, they are first collected
+    /// into a `Vec`.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn squares(mut cx: FunctionContext) -> JsResult<JsArrayBuffer> {
+    ///     JsArrayBuffer::from_iter(&mut cx, (0..16u8).map(|i| i * i))
+    /// }
+    /// ```
+    pub fn from_iter<'cx, C, I>(cx: &mut C, iter: I) -> JsResult<'cx, Self>
+    where
+        C: Context<'cx>,
+        I: IntoIterator<Item = u8>,
+    {
+        fill_from_iter(cx, iter, Self::new)
+    }
+
     #[cfg(feature = "external-buffers")]
     #[cfg_attr(docsrs, doc(cfg(feature = "external-buffers")))]
     /// Construct a new `JsArrayBuffer` from bytes allocated by Rust.
@@ -272,6 +426,12 @@ impl JsArrayBuffer {
     /// [runtime function](https://nodejs.org/api/n-api.html#napi_create_external_arraybuffer)
     /// used by this method results in an immediate termination of the Node VM.
     ///
+    /// Neon avoids the call in environments that
+    /// [`neon::runtime::features`](crate::runtime::features) reports as disallowing
+    /// external buffers and in runtimes that fail the call with an error, copying `data`
+    /// into a new buffer instead. The detection is based on the runtime version and may
+    /// not recognize every such environment.
+    ///
     /// As a result, this API is disabled by default. If you are confident that your code will
     /// only be used in environments that disable sandboxed pointers, you can make use of this
     /// method by enabling the **`external-buffers`** feature flag.
@@ -280,10 +440,46 @@ impl JsArrayBuffer {
         C: Context<'a>,
         T: AsMut<[u8]> + Send + 'static,
     {
-        let env = cx.env().to_raw();
-        let value = unsafe { sys::arraybuffer::new_external(env, data) };
+        Self::external_with_policy(cx, data, ExternalPolicy::PreferCopyFallback)
+            .expect("failed to allocate buffer")
+            .into_inner()
+    }
 
-        Handle::new_internal(Self(value))
+    #[cfg(feature = "external-buffers")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "external-buffers")))]
+    /// Construct a new `JsArrayBuffer` from bytes allocated by Rust, with `policy` controlling
+    /// what happens in environments that disallow external buffers.
+    ///
+    /// The result indicates whether `data` was externalized or copied. With
+    /// [`ExternalPolicy::Require`], an `Error` is thrown if it cannot be externalized.
+    pub fn external_with_policy<'a, C, T>(
+        cx: &mut C,
+        data: T,
+        policy: ExternalPolicy,
+    ) -> NeonResult<External<'a, Self>>
+    where
+        C: Context<'a>,
+        T: AsMut<[u8]> + Send + 'static,
+    {
+        let env = cx.env().to_raw();
+        let allowed = crate::runtime::features(cx).external_buffers;
+        let outcome = external::with_policy(
+            policy,
+            allowed,
+            data,
+            |data| unsafe { sys::arraybuffer::new_external(env, data) },
+            |data| Self::from_slice(cx, data.as_mut()).map(|buf| buf.to_raw()),
+        )?;
+
+        match outcome {
+            Outcome::Externalized(value) => {
+                Ok(External::Externalized(Handle::new_internal(Self(value))))
+            }
+            Outcome::Copied(value) => Ok(External::Copied(Handle::new_internal(Self(value)))),
+            Outcome::Disallowed => {
+                cx.throw_error("external buffers are not allowed in this environment")
+            }
+        }
     }
 
     /// Returns a region of this buffer.
@@ -371,14 +567,24 @@ impl TypedArray for JsArrayBuffer {
     where
         C: Context<'cx>,
     {
-        unsafe { sys::arraybuffer::as_mut_slice(cx.env().to_raw(), self.to_raw()) }
+        let env = cx.env().to_raw();
+        let data = unsafe { sys::arraybuffer::as_mut_slice(env, self.to_raw()) };
+
+        debug::check_slice(env, data, false);
+
+        data
     }
 
     fn as_mut_slice<'cx, 'a, C>(&mut self, cx: &'a mut C) -> &'a mut [Self::Item]
     where
         C: Context<'cx>,
     {
-        unsafe { sys::arraybuffer::as_mut_slice(cx.env().to_raw(), self.to_raw()) }
+        let env = cx.env().to_raw();
+        let data = unsafe { sys::arraybuffer::as_mut_slice(env, self.to_raw()) };
+
+        debug::check_slice(env, data, true);
+
+        data
     }
 
     fn try_borrow<'cx, 'a, C>(&self, lock: &'a Lock<C>) -> Result<Ref<'a, Self::Item>, BorrowError>
@@ -420,6 +626,90 @@ impl TypedArray for JsArrayBuffer {
     }
 }
 
+// Reads exactly the length of `buf` from `reader` into `buf`
+fn fill_from_reader<'cx, C, T, R>(
+    cx: &mut C,
+    mut buf: Handle<'cx, T>,
+    mut reader: R,
+) -> JsResult<'cx, T>
+where
+    C: Context<'cx>,
+    T: TypedArray<Item = u8>,
+    R: io::Read,
+{
+    let result = {
+        let data = buf.as_mut_slice(cx);
+        let len = data.len();
+        let mut filled = 0;
+
+        loop {
+            if filled == len {
+                break Ok(());
+            }
+
+            match reader.read(&mut data[filled..]) {
+                Ok(0) => break Err(format!("reader ended after {} of {} bytes", filled, len)),
+                Ok(n) => filled += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => break Err(err.to_string()),
+            }
+        }
+    };
+
+    match result {
+        Ok(()) => Ok(buf),
+        Err(msg) => cx.throw_error(msg),
+    }
+}
+
+// Creates a buffer with `new` and fills it from `iter`, writing in place when the size
+// of the iterator is known
+fn fill_from_iter<'cx, C, T, I>(
+    cx: &mut C,
+    iter: I,
+    new: fn(&mut C, usize) -> JsResult<'cx, T>,
+) -> JsResult<'cx, T>
+where
+    C: Context<'cx>,
+    T: TypedArray<Item = u8>,
+    I: IntoIterator<Item = u8>,
+{
+    let mut iter = iter.into_iter();
+
+    let len = match iter.size_hint() {
+        (lower, Some(upper)) if lower == upper => lower,
+        _ => return T::from_slice(cx, &iter.collect::<Vec<_>>()),
+    };
+
+    let mut buf = new(cx, len)?;
+    let data = buf.as_mut_slice(cx);
+    let mut filled = 0;
+
+    for (slot, b) in data.iter_mut().zip(&mut iter) {
+        *slot = b;
+        filled += 1;
+    }
+
+    // The size hint is not trusted; fall back to copying if it was wrong
+    if filled < len {
+        let prefix = data[..filled].to_vec();
+
+        return T::from_slice(cx, &prefix);
+    }
+
+    match iter.next() {
+        None => Ok(buf),
+        Some(b) => {
+            let mut bytes = data.to_vec();
+
+            bytes.push(b);
+            bytes.extend(iter);
+
+            T::from_slice(cx, &bytes)
+        }
+    }
+}
+
 /// A marker trait for all possible element types of binary buffers.
 ///
 /// This trait can only be implemented within the Neon library.
@@ -565,26 +855,32 @@ where
     where
         C: Context<'cx>,
     {
-        unsafe {
-            let env = cx.env().to_raw();
-            let value = self.to_raw();
-            let info = sys::typedarray::info(env, value);
+        let env = cx.env().to_raw();
+        let data = unsafe {
+            let info = sys::typedarray::info(env, self.to_raw());
 
             slice::from_raw_parts(info.data.cast(), info.length)
-        }
+        };
+
+        debug::check_slice(env, data, false);
+
+        data
     }
 
     fn as_mut_slice<'cx, 'a, C>(&mut self, cx: &'a mut C) -> &'a mut [Self::Item]
     where
         C: Context<'cx>,
     {
-        unsafe {
-            let env = cx.env().to_raw();
-            let value = self.to_raw();
-            let info = sys::typedarray::info(env, value);
+        let env = cx.env().to_raw();
+        let data = unsafe {
+            let info = sys::typedarray::info(env, self.to_raw());
 
             slice::from_raw_parts_mut(info.data.cast(), info.length)
-        }
+        };
+
+        debug::check_slice(env, data, true);
+
+        data
     }
 
     fn try_borrow<'cx, 'b, C>(
@@ -787,6 +1083,120 @@ impl<T: Binary> JsTypedArray<T> {
         let info = unsafe { sys::typedarray::info(cx.env().to_raw(), self.to_raw()) };
         info.length
     }
+
+    /// Reads the element at `index`, or returns `None` if `index` is out of bounds.
+    ///
+    /// Unlike [`as_slice`](TypedArray::as_slice), the backing memory is only borrowed
+    /// for the duration of the call. Bounds are checked against the current length on
+    /// each call, so calling into JavaScript between accesses is allowed, even if it
+    /// detaches the buffer; a detached typed array has a length of zero.
+    ///
+    /// Each call asks the engine for the current location and length of the data,
+    /// which costs far more than indexing a slice. Prefer a slice for many accesses
+    /// without JavaScript running in between.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn first_or_zero(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    ///     let arr = cx.argument::<JsFloat64Array>(0)?;
+    ///     let first = arr.try_get(&cx, 0).unwrap_or(0.0);
+    ///
+    ///     Ok(cx.number(first))
+    /// }
+    /// ```
+    pub fn try_get<'cx, C>(&self, cx: &C, index: usize) -> Option<T>
+    where
+        C: Context<'cx>,
+    {
+        let env = cx.env().to_raw();
+        let info = unsafe { sys::typedarray::info(env, self.to_raw()) };
+
+        if index >= info.length {
+            return None;
+        }
+
+        // Safety: `index` was checked against the current length of the typed array
+        let ptr = unsafe { info.data.cast::<T>().add(index) };
+
+        debug::check_slice(env, unsafe { slice::from_raw_parts(ptr, 1) }, false);
+
+        Some(unsafe { ptr.read_unaligned() })
+    }
+
+    /// Reads the element at `index`.
+    ///
+    /// Throws a `RangeError` if `index` is out of bounds. See
+    /// [`try_get`](JsTypedArray::try_get).
+    ///
+    /// This method takes precedence over [`Object::get`]; properties of a typed array
+    /// can still be read with `Object::get(&arr, cx, key)`.
+    pub fn get<'cx, C>(&self, cx: &mut C, index: usize) -> NeonResult<T>
+    where
+        C: Context<'cx>,
+    {
+        match self.try_get(cx, index) {
+            Some(value) => Ok(value),
+            None => {
+                let len = self.len(cx);
+
+                cx.throw_range_error(out_of_bounds(index, len))
+            }
+        }
+    }
+
+    /// Writes `value` to the element at `index`.
+    ///
+    /// Like [`try_get`](JsTypedArray::try_get), the backing memory is only borrowed for
+    /// the duration of the call and bounds are checked against the current length.
+    /// Throws a `RangeError` if `index` is out of bounds.
+    ///
+    /// Exclusive access to the memory is guaranteed by `cx`, which cannot be borrowed
+    /// while a slice of any typed array is.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn scatter(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let arr = cx.argument::<JsUint32Array>(0)?;
+    ///     let f = cx.argument::<JsFunction>(1)?;
+    ///
+    ///     for i in [3, 1, 4] {
+    ///         // The callback may run arbitrary code, including detaching the buffer
+    ///         let value = f.call_with(&cx).arg(cx.number(i)).apply::<JsNumber, _>(&mut cx)?;
+    ///         let value = value.value(&mut cx) as u32;
+    ///
+    ///         arr.set(&mut cx, i as usize, value)?;
+    ///     }
+    ///
+    ///     Ok(cx.undefined())
+    /// }
+    /// ```
+    pub fn set<'cx, C>(&self, cx: &mut C, index: usize, value: T) -> NeonResult<()>
+    where
+        C: Context<'cx>,
+    {
+        let env = cx.env().to_raw();
+        let info = unsafe { sys::typedarray::info(env, self.to_raw()) };
+
+        if index >= info.length {
+            return cx.throw_range_error(out_of_bounds(index, info.length));
+        }
+
+        // Safety: `index` was checked against the current length of the typed array
+        let ptr = unsafe { info.data.cast::<T>().add(index) };
+
+        debug::check_slice(env, unsafe { slice::from_raw_parts(ptr, 1) }, true);
+
+        unsafe { ptr.write_unaligned(value) };
+
+        Ok(())
+    }
+}
+
+fn out_of_bounds(index: usize, len: usize) -> String {
+    format!(
+        "index {} is out of bounds for typed array of length {}",
+        index, len
+    )
 }
 
 macro_rules! impl_typed_array {
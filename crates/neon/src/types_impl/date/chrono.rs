@@ -0,0 +1,117 @@
+//! Conversions between `JsDate` and the date types of the `chrono` crate.
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use super::{DateError, DateInput, JsDate};
+
+use crate::{
+    context::Context,
+    handle::Handle,
+    result::{JsResult, NeonResult, ResultExt},
+    types::{
+        extract::{Iso8601, TryFromJs, TryIntoJs},
+        JsValue,
+    },
+};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl JsDate {
+    /// Creates a new `Date` from a [`DateTime<Utc>`](chrono::DateTime).
+    ///
+    /// JavaScript dates have millisecond precision; any sub-millisecond part of
+    /// `date_time` is truncated towards the past. The range of `DateTime` is within the
+    /// range of valid JavaScript `Date` values, so this does not fail in practice, but
+    /// it returns a `Result` for consistency with [`JsDate::new`].
+    pub fn from_date_time<'a, C: Context<'a>>(
+        cx: &mut C,
+        date_time: &DateTime<Utc>,
+    ) -> Result<Handle<'a, JsDate>, DateError> {
+        // Lossless, since the milliseconds of any `DateTime` fit in the 53 bits of an `f64`
+        JsDate::new(cx, date_time.timestamp_millis() as f64)
+    }
+
+    /// Creates a new `Date` at midnight UTC of a [`NaiveDate`](chrono::NaiveDate).
+    pub fn from_naive_date<'a, C: Context<'a>>(
+        cx: &mut C,
+        date: NaiveDate,
+    ) -> Result<Handle<'a, JsDate>, DateError> {
+        JsDate::from_date_time(cx, &date.and_time(Default::default()).and_utc())
+    }
+}
+
+fn date_time_from_millis<'a, C: Context<'a>>(cx: &mut C, millis: f64) -> NeonResult<DateTime<Utc>> {
+    match DateTime::from_timestamp_millis(millis as i64) {
+        Some(date_time) => Ok(date_time),
+        None => cx.throw_range_error("Date is outside the range of chrono::DateTime"),
+    }
+}
+
+fn date_time_from_input<'a, C: Context<'a>>(
+    cx: &mut C,
+    input: DateInput,
+) -> NeonResult<DateTime<Utc>> {
+    match input {
+        DateInput::Millis(millis) => date_time_from_millis(cx, millis),
+        DateInput::String(s) => match s.parse::<DateTime<Utc>>() {
+            Ok(date_time) => Ok(date_time),
+            Err(err) => cx.throw_type_error(format!("Invalid ISO 8601 date {:?}: {}", s, err)),
+        },
+    }
+}
+
+impl<'cx> TryIntoJs<'cx> for DateTime<Utc> {
+    type Value = JsDate;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsDate> {
+        JsDate::from_date_time(cx, &self).or_throw(cx)
+    }
+}
+
+/// Extracts a `Date`; throws a `RangeError` for an invalid `Date` or one outside the
+/// range of `DateTime`.
+impl<'cx> TryFromJs<'cx> for DateTime<Utc> {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        let input = DateInput::extract(cx, v, false)?;
+
+        date_time_from_input(cx, input)
+    }
+}
+
+/// Extracts a `Date` or an RFC 3339 string, e.g., `2020-01-02T03:04:05.678Z`.
+impl<'cx> TryFromJs<'cx> for Iso8601<DateTime<Utc>> {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        let input = DateInput::extract(cx, v, true)?;
+
+        date_time_from_input(cx, input).map(Iso8601)
+    }
+}
+
+impl<'cx> TryIntoJs<'cx> for NaiveDate {
+    type Value = JsDate;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsDate> {
+        JsDate::from_naive_date(cx, self).or_throw(cx)
+    }
+}
+
+/// Extracts the UTC calendar date of a `Date`, discarding the time of day.
+impl<'cx> TryFromJs<'cx> for NaiveDate {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        DateTime::<Utc>::try_from_js(cx, v).map(|date_time| date_time.date_naive())
+    }
+}
+
+/// Extracts the UTC calendar date of a `Date` or a string of the form `2020-01-02`.
+impl<'cx> TryFromJs<'cx> for Iso8601<NaiveDate> {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        match DateInput::extract(cx, v, true)? {
+            DateInput::Millis(millis) => {
+                date_time_from_millis(cx, millis).map(|date_time| Iso8601(date_time.date_naive()))
+            }
+            DateInput::String(s) => match s.parse::<NaiveDate>() {
+                Ok(date) => Ok(Iso8601(date)),
+                Err(err) => cx.throw_type_error(format!("Invalid ISO 8601 date {:?}: {}", s, err)),
+            },
+        }
+    }
+}
@@ -1,3 +1,8 @@
+#[cfg(feature = "chrono")]
+mod chrono;
+#[cfg(feature = "time")]
+mod time;
+
 use std::{
     error::Error,
     fmt::{self, Debug},
@@ -180,3 +185,35 @@ impl ValueInternal for JsDate {
 }
 
 impl Object for JsDate {}
+
+// The millisecond time value of a `Date` or, if allowed, an unparsed string
+#[cfg(any(feature = "chrono", feature = "time"))]
+enum DateInput {
+    Millis(f64),
+    String(String),
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+impl DateInput {
+    // Reads a valid `Date`, and a string instead when `allow_string` is set
+    fn extract<'a, C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, super::JsValue>,
+        allow_string: bool,
+    ) -> crate::result::NeonResult<Self> {
+        if allow_string {
+            if let Ok(s) = v.downcast::<super::JsString, _>(cx) {
+                return Ok(DateInput::String(s.value(cx)));
+            }
+        }
+
+        let date = v.downcast_or_throw::<JsDate, _>(cx)?;
+        let millis = date.value(cx);
+
+        if millis.is_nan() {
+            return cx.throw_range_error("Invalid Date");
+        }
+
+        Ok(DateInput::Millis(millis))
+    }
+}
@@ -0,0 +1,79 @@
+//! Conversions between `JsDate` and the date types of the `time` crate.
+
+use time::{format_description::well_known, OffsetDateTime};
+
+use super::{DateError, DateInput, JsDate};
+
+use crate::{
+    context::Context,
+    handle::Handle,
+    result::{JsResult, NeonResult, ResultExt},
+    types::{
+        extract::{Iso8601, TryFromJs, TryIntoJs},
+        JsValue,
+    },
+};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl JsDate {
+    /// Creates a new `Date` from an [`OffsetDateTime`](time::OffsetDateTime).
+    ///
+    /// JavaScript dates have millisecond precision; any sub-millisecond part of
+    /// `date_time` is truncated towards the past. It errors when `date_time` is outside
+    /// the range of valid JavaScript `Date` values.
+    pub fn from_offset_date_time<'a, C: Context<'a>>(
+        cx: &mut C,
+        date_time: OffsetDateTime,
+    ) -> Result<Handle<'a, JsDate>, DateError> {
+        let millis = date_time.unix_timestamp_nanos().div_euclid(1_000_000);
+
+        // Lossless, since any value that does not fit in an `f64` is out of range
+        JsDate::new(cx, millis as f64)
+    }
+}
+
+fn offset_date_time_from_input<'a, C: Context<'a>>(
+    cx: &mut C,
+    input: DateInput,
+) -> NeonResult<OffsetDateTime> {
+    match input {
+        DateInput::Millis(millis) => {
+            match OffsetDateTime::from_unix_timestamp_nanos(millis as i128 * 1_000_000) {
+                Ok(date_time) => Ok(date_time),
+                Err(_) => cx.throw_range_error("Date is outside the range of time::OffsetDateTime"),
+            }
+        }
+        DateInput::String(s) => match OffsetDateTime::parse(&s, &well_known::Iso8601::DEFAULT) {
+            Ok(date_time) => Ok(date_time),
+            Err(err) => cx.throw_type_error(format!("Invalid ISO 8601 date {:?}: {}", s, err)),
+        },
+    }
+}
+
+impl<'cx> TryIntoJs<'cx> for OffsetDateTime {
+    type Value = JsDate;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsDate> {
+        JsDate::from_offset_date_time(cx, self).or_throw(cx)
+    }
+}
+
+/// Extracts a `Date` as an `OffsetDateTime` in UTC; throws a `RangeError` for an
+/// invalid `Date` or one outside the range of `OffsetDateTime`.
+impl<'cx> TryFromJs<'cx> for OffsetDateTime {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        let input = DateInput::extract(cx, v, false)?;
+
+        offset_date_time_from_input(cx, input)
+    }
+}
+
+/// Extracts a `Date` or an ISO 8601 string with an offset, e.g.,
+/// `2020-01-02T03:04:05.678+01:00`. The offset of a string is preserved.
+impl<'cx> TryFromJs<'cx> for Iso8601<OffsetDateTime> {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        let input = DateInput::extract(cx, v, true)?;
+
+        offset_date_time_from_input(cx, input).map(Iso8601)
+    }
+}
@@ -0,0 +1,232 @@
+//! Base64 and hex codecs used by [`Context::string_base64`](crate::context::Context::string_base64),
+//! [`Context::string_hex`](crate::context::Context::string_hex) and the `JsString` decoding methods.
+//!
+//! Encoders produce ASCII, which can be passed directly to the engine as Latin-1. Decoders
+//! operate on UTF-16 code units so that the offset of an invalid character matches its
+//! index in the JavaScript string.
+
+use std::fmt;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+const INVALID: u8 = 0xff;
+
+const BASE64_DECODE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+
+    while i < 64 {
+        table[BASE64_ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum DecodeError {
+    InvalidCharacter {
+        encoding: &'static str,
+        offset: usize,
+    },
+    InvalidLength {
+        encoding: &'static str,
+        offset: usize,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::InvalidCharacter { encoding, offset } => {
+                write!(f, "invalid {} character at offset {}", encoding, offset)
+            }
+            DecodeError::InvalidLength { encoding, offset } => {
+                write!(
+                    f,
+                    "unexpected end of {} string at offset {}",
+                    encoding, offset
+                )
+            }
+        }
+    }
+}
+
+/// Encodes `data` as padded base64 with the standard alphabet
+pub(crate) fn encode_base64(data: &[u8]) -> Vec<u8> {
+    let mut chunks = data.chunks_exact(3);
+    let groups = chunks.len() + usize::from(!chunks.remainder().is_empty());
+    let mut out = Vec::with_capacity(groups * 4);
+
+    for chunk in &mut chunks {
+        let n = (chunk[0] as u32) << 16 | (chunk[1] as u32) << 8 | chunk[2] as u32;
+
+        out.extend_from_slice(&[
+            BASE64_ALPHABET[(n >> 18) as usize & 0x3f],
+            BASE64_ALPHABET[(n >> 12) as usize & 0x3f],
+            BASE64_ALPHABET[(n >> 6) as usize & 0x3f],
+            BASE64_ALPHABET[n as usize & 0x3f],
+        ]);
+    }
+
+    match *chunks.remainder() {
+        [a] => {
+            let n = (a as u32) << 16;
+
+            out.extend_from_slice(&[
+                BASE64_ALPHABET[(n >> 18) as usize & 0x3f],
+                BASE64_ALPHABET[(n >> 12) as usize & 0x3f],
+                b'=',
+                b'=',
+            ]);
+        }
+        [a, b] => {
+            let n = (a as u32) << 16 | (b as u32) << 8;
+
+            out.extend_from_slice(&[
+                BASE64_ALPHABET[(n >> 18) as usize & 0x3f],
+                BASE64_ALPHABET[(n >> 12) as usize & 0x3f],
+                BASE64_ALPHABET[(n >> 6) as usize & 0x3f],
+                b'=',
+            ]);
+        }
+        _ => {}
+    }
+
+    out
+}
+
+/// Encodes `data` as lowercase hex
+pub(crate) fn encode_hex(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() * 2);
+
+    for byte in data {
+        out.push(HEX_ALPHABET[(byte >> 4) as usize]);
+        out.push(HEX_ALPHABET[(byte & 0xf) as usize]);
+    }
+
+    out
+}
+
+/// Decodes standard base64, with or without padding.
+///
+/// Whitespace and characters outside of the standard alphabet are rejected. As with
+/// Node's `Buffer`, unused bits in the final character are ignored.
+pub(crate) fn decode_base64(data: &[u16]) -> Result<Vec<u8>, DecodeError> {
+    const ENCODING: &str = "base64";
+
+    let invalid = |offset| DecodeError::InvalidCharacter {
+        encoding: ENCODING,
+        offset,
+    };
+
+    // Strip up to two padding characters, which may only appear at the end
+    let mut len = data.len();
+
+    while len > 0 && data.len() - len < 2 && data[len - 1] == b'=' as u16 {
+        len -= 1;
+    }
+
+    let padded = len != data.len();
+    let padded_remainder = data.len() % 4;
+
+    if padded && padded_remainder != 0 {
+        return Err(DecodeError::InvalidLength {
+            encoding: ENCODING,
+            offset: data.len(),
+        });
+    }
+
+    if len % 4 == 1 {
+        return Err(DecodeError::InvalidLength {
+            encoding: ENCODING,
+            offset: len,
+        });
+    }
+
+    let mut out = Vec::with_capacity(len / 4 * 3 + 2);
+    let mut chunks = data[..len].chunks_exact(4);
+    let mut offset = 0;
+
+    let sextet = |offset: usize, c: u16| -> Result<u32, DecodeError> {
+        if c > 0xff {
+            return Err(invalid(offset));
+        }
+
+        match BASE64_DECODE[c as usize] {
+            INVALID => Err(invalid(offset)),
+            n => Ok(n as u32),
+        }
+    };
+
+    for chunk in &mut chunks {
+        let n = sextet(offset, chunk[0])? << 18
+            | sextet(offset + 1, chunk[1])? << 12
+            | sextet(offset + 2, chunk[2])? << 6
+            | sextet(offset + 3, chunk[3])?;
+
+        out.extend_from_slice(&[(n >> 16) as u8, (n >> 8) as u8, n as u8]);
+        offset += 4;
+    }
+
+    match *chunks.remainder() {
+        [a, b] => {
+            let n = sextet(offset, a)? << 18 | sextet(offset + 1, b)? << 12;
+
+            out.push((n >> 16) as u8);
+        }
+        [a, b, c] => {
+            let n = sextet(offset, a)? << 18
+                | sextet(offset + 1, b)? << 12
+                | sextet(offset + 2, c)? << 6;
+
+            out.extend_from_slice(&[(n >> 16) as u8, (n >> 8) as u8]);
+        }
+        _ => {}
+    }
+
+    Ok(out)
+}
+
+/// Decodes hex, accepting both lowercase and uppercase digits
+pub(crate) fn decode_hex(data: &[u16]) -> Result<Vec<u8>, DecodeError> {
+    const ENCODING: &str = "hex";
+
+    fn nibble(offset: usize, c: u16) -> Result<u8, DecodeError> {
+        match c {
+            0x30..=0x39 => Ok((c - 0x30) as u8),
+            0x41..=0x46 => Ok((c - 0x41 + 10) as u8),
+            0x61..=0x66 => Ok((c - 0x61 + 10) as u8),
+            _ => Err(DecodeError::InvalidCharacter {
+                encoding: ENCODING,
+                offset,
+            }),
+        }
+    }
+
+    let mut out = Vec::with_capacity(data.len() / 2);
+    let mut pairs = data.chunks_exact(2);
+
+    for (i, pair) in (&mut pairs).enumerate() {
+        let offset = i * 2;
+
+        out.push(nibble(offset, pair[0])? << 4 | nibble(offset + 1, pair[1])?);
+    }
+
+    if let [c] = *pairs.remainder() {
+        let offset = data.len() - 1;
+
+        // Report an invalid character before reporting the odd length
+        nibble(offset, c)?;
+
+        return Err(DecodeError::InvalidLength {
+            encoding: ENCODING,
+            offset: data.len(),
+        });
+    }
+
+    Ok(out)
+}
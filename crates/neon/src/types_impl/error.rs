@@ -2,13 +2,16 @@
 
 use std::panic::{catch_unwind, UnwindSafe};
 
+#[cfg(feature = "rust-stack-frames")]
+use std::panic::Location;
+
 use crate::{
     context::{internal::Env, Context},
     handle::{internal::TransparentNoCopyWrapper, Handle, Managed},
     object::Object,
-    result::{NeonResult, Throw},
+    result::{JsResult, NeonResult, Throw},
     sys::{self, raw},
-    types::{build, private::ValueInternal, utf8::Utf8, Value},
+    types::{build, private::ValueInternal, utf8::Utf8, JsNumber, JsString, Value},
 };
 
 /// The type of JavaScript
@@ -113,6 +116,291 @@ impl JsError {
             true
         })
     }
+
+    /// Replaces the `stack` of the error with the current JavaScript stack, omitting
+    /// the innermost `skip_frames` frames.
+    ///
+    /// An error created in Rust has the stack of the JavaScript code that called into
+    /// Rust, which may point at glue code, e.g., a wrapper that validates arguments.
+    /// Skipping the frames of the wrapper makes the stack point at its caller.
+    /// Re-capturing uses
+    /// [`Error.captureStackTrace`](https://nodejs.org/api/errors.html#errorcapturestacktracetargetobject-constructoropt),
+    /// so the error keeps up to `Error.stackTraceLimit` frames after skipping. Outside
+    /// of a call from JavaScript, e.g., in a [`Channel`](crate::event::Channel)
+    /// callback, the stack has no frames.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// // Called from a JavaScript wrapper; the stack starts at the wrapper's caller
+    /// fn parse(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let err = cx.error("unexpected token")?;
+    ///
+    ///     err.capture_stack_above(&mut cx, 1)?;
+    ///     cx.throw(err)
+    /// }
+    /// ```
+    pub fn capture_stack_above<'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        skip_frames: usize,
+    ) -> NeonResult<()> {
+        let capture = crate::intrinsics::error_capture_stack_trace(cx)?;
+        let error = crate::intrinsics::error(cx)?;
+        let limit = error.get_value(cx, "stackTraceLimit")?;
+
+        // Capture the skipped frames in addition to the usual number of frames
+        if let Ok(n) = limit.downcast::<JsNumber, _>(cx) {
+            let n = n.value(cx) + skip_frames as f64;
+            let n = cx.number(n);
+
+            error.set(cx, "stackTraceLimit", n)?;
+        }
+
+        let this = Handle::new_internal(JsError(self.0));
+        let result = capture.exec(cx, error, (this,));
+
+        error.set(cx, "stackTraceLimit", limit)?;
+        result?;
+
+        if skip_frames > 0 {
+            if let Some(stack) = self.stack(cx)? {
+                let stack = cx.string(skip_stack_frames(&stack, skip_frames));
+
+                self.set(cx, "stack", stack)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Inserts a line `    at rust: {note}` into the `stack` of the error, as its
+    /// innermost frame.
+    ///
+    /// This helps to locate errors that are created in Rust, which otherwise point at
+    /// the JavaScript code that called into Rust.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn parse(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let err = cx.error("unexpected token")?;
+    ///
+    ///     // `    at rust: my_crate::parser::parse (parser.rs:123)`
+    ///     err.prepend_stack_note(&mut cx, "my_crate::parser::parse (parser.rs:123)")?;
+    ///     cx.throw(err)
+    /// }
+    /// ```
+    ///
+    /// With the `rust-stack-frames` feature, a note with the location of the Rust code
+    /// is added automatically to errors thrown with [`Context::throw_error`],
+    /// [`Context::throw_type_error`] and [`Context::throw_range_error`], and to errors
+    /// thrown by task and [`Channel`](crate::event::Channel) callbacks, with the
+    /// location that scheduled the callback.
+    pub fn prepend_stack_note<'a, C: Context<'a>>(&self, cx: &mut C, note: &str) -> NeonResult<()> {
+        let frame = format!("{}{}", RUST_FRAME, note);
+        let stack = match self.stack(cx)? {
+            Some(stack) => stack,
+            None => self.to_string(cx)?.value(cx),
+        };
+
+        let stack = cx.string(insert_stack_frame(&stack, &frame));
+
+        self.set(cx, "stack", stack)?;
+
+        Ok(())
+    }
+
+    fn stack<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Option<String>> {
+        let stack = self.get_value(cx, "stack")?;
+
+        Ok(stack
+            .downcast::<JsString, _>(cx)
+            .ok()
+            .map(|stack| stack.value(cx)))
+    }
+}
+
+const STACK_FRAME: &str = "    at ";
+
+const RUST_FRAME: &str = "    at rust: ";
+
+// Inserts `frame` before the first frame of `stack`, or appends it if there are none
+fn insert_stack_frame(stack: &str, frame: &str) -> String {
+    let mut lines = stack.lines().collect::<Vec<_>>();
+    let first = lines
+        .iter()
+        .position(|line| line.starts_with(STACK_FRAME))
+        .unwrap_or(lines.len());
+
+    lines.insert(first, frame);
+    lines.join("\n")
+}
+
+// Removes the first `n` frames of `stack`, keeping the message
+fn skip_stack_frames(stack: &str, n: usize) -> String {
+    let mut skipped = 0;
+
+    stack
+        .lines()
+        .filter(|line| {
+            if skipped < n && line.starts_with(STACK_FRAME) {
+                skipped += 1;
+                false
+            } else {
+                true
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Notes the location of Rust code on the pending exception, if it is an error
+#[cfg(feature = "rust-stack-frames")]
+pub(crate) fn note_pending<'a, C: Context<'a>>(cx: &mut C, label: &str, location: &Location) {
+    let exception = match cx.catch_pending() {
+        Some(exception) => exception,
+        None => return,
+    };
+
+    if let Ok(err) = exception.downcast::<JsError, _>(cx) {
+        if note_location(cx, err, label, location).is_err() {
+            // Keep the original exception if the note cannot be added
+            cx.catch_pending();
+        }
+    }
+
+    let _ = cx.throw::<_, ()>(exception);
+}
+
+#[cfg(feature = "rust-stack-frames")]
+pub(crate) fn note_location<'a, C: Context<'a>>(
+    cx: &mut C,
+    err: Handle<JsError>,
+    label: &str,
+    location: &Location,
+) -> NeonResult<()> {
+    // Leave a second throw while already throwing to fail as it would without the note
+    if cx.is_throwing() {
+        return Ok(());
+    }
+
+    err.prepend_stack_note(cx, &format!("{} ({})", label, location))
+}
+
+/// The class of JavaScript error constructed from an [`ErrorSpec`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorClass {
+    #[default]
+    /// [`Error`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/Error)
+    Error,
+    /// [`TypeError`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/TypeError)
+    TypeError,
+    /// [`RangeError`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/RangeError)
+    RangeError,
+}
+
+/// A description of a JavaScript error that can be created without a [`Context`].
+///
+/// An `ErrorSpec` allows Rust code that does not have access to a `Context` to
+/// describe the error that should be thrown. Rust error types can implement
+/// `Into<ErrorSpec>` to be used with
+/// [`ModuleContext::export_result_function`](crate::context::ModuleContext::export_result_function).
+///
+/// # Example
+///
+/// ```
+/// # use neon::types::ErrorSpec;
+/// enum ParseError {
+///     Empty,
+///     TooLong(usize),
+/// }
+///
+/// impl From<ParseError> for ErrorSpec {
+///     fn from(err: ParseError) -> Self {
+///         match err {
+///             ParseError::Empty => ErrorSpec::type_error("input is empty").with_code("ERR_EMPTY"),
+///             ParseError::TooLong(n) => {
+///                 ErrorSpec::range_error(format!("input is {} bytes too long", n))
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorSpec {
+    /// The `message` of the error.
+    pub message: String,
+    /// The constructor used to create the error. _Default: [`ErrorClass::Error`]_
+    pub class: ErrorClass,
+    /// The value of the `code` property of the error, if any. _Default: `None`_
+    pub code: Option<String>,
+}
+
+impl ErrorSpec {
+    /// Describes a direct instance of the `Error` class.
+    pub fn error<S: Into<String>>(message: S) -> Self {
+        Self::new(ErrorClass::Error, message)
+    }
+
+    /// Describes an instance of the `TypeError` class.
+    pub fn type_error<S: Into<String>>(message: S) -> Self {
+        Self::new(ErrorClass::TypeError, message)
+    }
+
+    /// Describes an instance of the `RangeError` class.
+    pub fn range_error<S: Into<String>>(message: S) -> Self {
+        Self::new(ErrorClass::RangeError, message)
+    }
+
+    fn new<S: Into<String>>(class: ErrorClass, message: S) -> Self {
+        Self {
+            message: message.into(),
+            class,
+            code: None,
+        }
+    }
+
+    /// Sets the `code` property of the error.
+    pub fn with_code<S: Into<String>>(mut self, code: S) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Creates the described error.
+    pub fn to_error<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsError> {
+        let err = match self.class {
+            ErrorClass::Error => JsError::error(cx, &self.message)?,
+            ErrorClass::TypeError => JsError::type_error(cx, &self.message)?,
+            ErrorClass::RangeError => JsError::range_error(cx, &self.message)?,
+        };
+
+        if let Some(code) = &self.code {
+            let code = cx.string(code);
+
+            err.set(cx, "code", code)?;
+        }
+
+        Ok(err)
+    }
+
+    /// Creates and throws the described error.
+    pub fn throw<'a, C: Context<'a>, T>(&self, cx: &mut C) -> NeonResult<T> {
+        let err = self.to_error(cx)?;
+
+        cx.throw(err)
+    }
+}
+
+impl From<String> for ErrorSpec {
+    fn from(message: String) -> Self {
+        Self::error(message)
+    }
+}
+
+impl From<&str> for ErrorSpec {
+    fn from(message: &str) -> Self {
+        Self::error(message)
+    }
 }
 
 pub(crate) fn convert_panics<T, F: UnwindSafe + FnOnce() -> NeonResult<T>>(
@@ -0,0 +1,895 @@
+//! Traits for converting between Rust values and JavaScript values.
+//!
+//! [`TryIntoJs`] is implemented for handles and for common Rust types, so that APIs
+//! that produce JavaScript values on behalf of the caller, such as
+//! [`Emitter::emit`](crate::event::Emitter::emit), can accept plain Rust data.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use neon::types::extract::TryIntoJs;
+//!
+//! fn greeting(mut cx: FunctionContext) -> JsResult<JsString> {
+//!     String::from("hello").try_into_js(&mut cx)
+//! }
+//! ```
+//!
+//! [`TryFromJs`] is the inverse, extracting a Rust value from a JavaScript value and
+//! throwing an exception if the value has the wrong type.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use neon::types::extract::TryFromJs;
+//!
+//! fn shout(mut cx: FunctionContext) -> JsResult<JsString> {
+//!     let arg = cx.argument::<JsValue>(0)?;
+//!     let s = String::try_from_js(&mut cx, arg)?;
+//!
+//!     Ok(cx.string(s.to_uppercase()))
+//! }
+//! ```
+//!
+//! ## Optional values
+//!
+//! `Option<T>` converts `None` to `null` and accepts either `null` or `undefined`
+//! when extracted. Wrap the value in [`OrUndefined`] to convert `None` to `undefined`
+//! instead. Since conversions are ordinary method calls, the same types can be
+//! returned from exported functions and
+//! [`TaskBuilder::promise`](crate::event::TaskBuilder::promise) callbacks:
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use neon::types::extract::{OrUndefined, TryIntoJs};
+//!
+//! fn find_index(mut cx: FunctionContext) -> JsResult<JsValue> {
+//!     let needle = cx.argument::<JsString>(0)?.value(&mut cx);
+//!     let index = ["a", "b", "c"].iter().position(|s| *s == needle);
+//!
+//!     // `null` when not found
+//!     index.map(|i| i as u32).try_into_js(&mut cx)
+//! }
+//!
+//! fn find_index_or_undefined(mut cx: FunctionContext) -> JsResult<JsValue> {
+//!     let needle = cx.argument::<JsString>(0)?.value(&mut cx);
+//!     let index = ["a", "b", "c"].iter().position(|s| *s == needle);
+//!
+//!     // `undefined` when not found
+//!     OrUndefined(index.map(|i| i as u32)).try_into_js(&mut cx)
+//! }
+//! ```
+//!
+//! Use [`MaybeUndefined`] to tell `null` apart from `undefined` or a missing value,
+//! e.g., to let `{ timeout: null }` disable a default that `{}` keeps.
+//!
+//! Nested options, e.g., `Option<Option<T>>`, cannot be converted because `None` and
+//! `Some(None)` would produce the same JavaScript value. Using one is a compile
+//! time error:
+//!
+//! ```compile_fail
+//! # use neon::prelude::*;
+//! use neon::types::extract::TryIntoJs;
+//!
+//! fn nested(mut cx: FunctionContext) -> JsResult<JsValue> {
+//!     Some(None::<f64>).try_into_js(&mut cx)
+//! }
+//! ```
+//!
+//! ## Tuples
+//!
+//! Tuples of up to 8 values convert to arrays, so a function or task can return
+//! several values for JavaScript to destructure, e.g., `const [written, checksum] = ...`.
+//! Wrap a tuple in [`Named`] to convert it to an object with the given keys instead.
+//!
+//! ## Objects
+//!
+//! Structs with named fields may [derive](macro@TryFromJs) `TryFromJs` to be
+//! extracted from an object, e.g., an options object. Attributes provide defaults,
+//! validation and renaming. Every field is checked before throwing, so a single
+//! `TypeError` lists all invalid fields.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use neon::types::extract::TryFromJs;
+//!
+//! #[derive(TryFromJs)]
+//! #[neon(deny_unknown_fields, rename_all = "camelCase")]
+//! struct Options {
+//!     timeout: Option<f64>,
+//!     #[neon(default = 3, range(min = 0, max = 10))]
+//!     max_retries: f64,
+//! }
+//!
+//! fn fetch(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+//!     // Throws, e.g., `invalid Options: maxRetries: must be between 0 and 10`
+//!     let options = cx.extract_argument::<Options>(0)?;
+//!     # let _ = (options.timeout, options.max_retries);
+//!
+//!     Ok(cx.undefined())
+//! }
+//! ```
+//!
+//! ## Numbers
+//!
+//! Integers wider than 32 bits, e.g., `u64` and `usize`, are converted only if they
+//! can be represented exactly by a JavaScript number, and throw a `RangeError`
+//! otherwise. Wrap the value in [`Lossy`] to round to the nearest number instead, or
+//! use [`Context::int_or_bigint`](crate::context::Context::int_or_bigint) to fall
+//! back to a `BigInt`.
+//!
+//! ## Contexts
+//!
+//! Conversions are generic over [`Context`], so the same implementations work in an
+//! exported function, in the [`TaskContext`](crate::context::TaskContext) of a
+//! [`Channel`](crate::event::Channel) or task callback, and in
+//! [`Finalize::finalize`](crate::types::Finalize::finalize). Generic code can also
+//! call [`Context::try_into_js`] and [`Context::try_from_js`].
+//!
+//! Some conversions call into JavaScript: tuples and [`Named`] set elements and
+//! properties, and URLs are constructed with the `URL` class. While an environment
+//! is torn down, e.g., when finalizers run as a worker thread exits, JavaScript cannot
+//! run and these conversions return `Err(Throw)` without throwing an exception.
+//! Numbers, booleans, strings and options never fail this way.
+
+use std::fmt;
+
+use crate::{
+    context::Context,
+    handle::Handle,
+    object::Object,
+    result::{JsResult, NeonResult, ResultExt},
+    sys,
+    types::{
+        JsArray, JsBoolean, JsNull, JsNumber, JsObject, JsString, JsUndefined, JsValue, Value,
+    },
+};
+
+/// Convert a Rust value into a JavaScript value.
+pub trait TryIntoJs<'cx> {
+    /// The type of JavaScript value that is created
+    type Value: Value;
+
+    // Used to reject nested options at compile time
+    #[doc(hidden)]
+    const IS_OPTION: bool = false;
+
+    /// Create a JavaScript value from `self`.
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, Self::Value>;
+}
+
+impl<'cx, V: Value> TryIntoJs<'cx> for Handle<'cx, V> {
+    type Value = V;
+
+    fn try_into_js<C: Context<'cx>>(self, _cx: &mut C) -> JsResult<'cx, V> {
+        Ok(self)
+    }
+}
+
+macro_rules! impl_number {
+    ($($ty:ty),*) => {
+        $(
+            impl<'cx> TryIntoJs<'cx> for $ty {
+                type Value = JsNumber;
+
+                fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsNumber> {
+                    Ok(cx.number(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_number!(f64, f32, i32, u32, i16, u16, i8, u8);
+
+macro_rules! impl_number_checked {
+    ($($ty:ty),*) => {
+        $(
+            /// Throws a `RangeError` if the value cannot be represented exactly.
+            impl<'cx> TryIntoJs<'cx> for $ty {
+                type Value = JsNumber;
+
+                fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsNumber> {
+                    cx.number_checked(self).or_throw(cx)
+                }
+            }
+
+            impl<'cx> TryIntoJs<'cx> for Lossy<$ty> {
+                type Value = JsNumber;
+
+                fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsNumber> {
+                    Ok(cx.number(self.0 as f64))
+                }
+            }
+        )*
+    };
+}
+
+impl_number_checked!(i64, u64, isize, usize, i128, u128);
+
+/// An integer that is rounded to the nearest JavaScript number when converted,
+/// rather than throwing if it cannot be represented exactly.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::extract::{Lossy, TryIntoJs};
+///
+/// fn elapsed_nanos(mut cx: FunctionContext) -> JsResult<JsNumber> {
+///     let nanos: u128 = 1_700_000_000_123_456_789;
+///
+///     Lossy(nanos).try_into_js(&mut cx)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lossy<T>(pub T);
+
+impl<'cx> TryIntoJs<'cx> for bool {
+    type Value = JsBoolean;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsBoolean> {
+        Ok(cx.boolean(self))
+    }
+}
+
+impl<'cx> TryIntoJs<'cx> for String {
+    type Value = JsString;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsString> {
+        Ok(cx.string(self))
+    }
+}
+
+impl<'cx> TryIntoJs<'cx> for &str {
+    type Value = JsString;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsString> {
+        Ok(cx.string(self))
+    }
+}
+
+impl<'cx> TryIntoJs<'cx> for () {
+    type Value = JsUndefined;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsUndefined> {
+        Ok(cx.undefined())
+    }
+}
+
+/// Tuples of values convert to a named object instead of an array, with a key for
+/// each element in order.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::extract::{Named, TryIntoJs};
+///
+/// fn stats(mut cx: FunctionContext) -> JsResult<JsObject> {
+///     let (bytes_written, checksum) = (1024, 0xcafe);
+///
+///     // `{ bytesWritten: 1024, checksum: 51966 }`
+///     Named(["bytesWritten", "checksum"], (bytes_written, checksum)).try_into_js(&mut cx)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Named<T, const N: usize>(pub [&'static str; N], pub T);
+
+// Tuples convert to arrays, or objects when wrapped in `Named`
+macro_rules! impl_tuple {
+    {
+        [ $(($tprefix:ident, $vprefix:ident), )* ];
+        [];
+    } => {};
+
+    {
+        [ $(($tprefix:ident, $vprefix:ident), )* ];
+        [ ($tname1:ident, $vname1:ident), $(($tnames:ident, $vnames:ident), )* ];
+    } => {
+        impl<'cx, $($tprefix: TryIntoJs<'cx>, )* $tname1: TryIntoJs<'cx>> TryIntoJs<'cx> for ($($tprefix, )* $tname1, ) {
+            type Value = JsArray;
+
+            fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsArray> {
+                let ($($vprefix, )* $vname1, ) = self;
+                let values: [Handle<JsValue>; 1 $(+ impl_tuple!(@one $tprefix))*] =
+                    [$($vprefix.try_into_js(cx)?.upcast(), )* $vname1.try_into_js(cx)?.upcast()];
+                let array = JsArray::new(cx, values.len() as u32);
+
+                array.write_from(cx, 0, &values)?;
+
+                Ok(array)
+            }
+        }
+
+        impl<'cx, $($tprefix: TryIntoJs<'cx>, )* $tname1: TryIntoJs<'cx>> TryIntoJs<'cx>
+            for Named<($($tprefix, )* $tname1, ), { 1 $(+ impl_tuple!(@one $tprefix))* }>
+        {
+            type Value = JsObject;
+
+            fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsObject> {
+                let Named(names, ($($vprefix, )* $vname1, )) = self;
+                let values: [Handle<JsValue>; 1 $(+ impl_tuple!(@one $tprefix))*] =
+                    [$($vprefix.try_into_js(cx)?.upcast(), )* $vname1.try_into_js(cx)?.upcast()];
+                let object = cx.empty_object();
+
+                for (name, value) in names.iter().zip(values.iter()) {
+                    object.set(cx, *name, *value)?;
+                }
+
+                Ok(object)
+            }
+        }
+
+        impl_tuple! {
+            [ $(($tprefix, $vprefix), )* ($tname1, $vname1), ];
+            [ $(($tnames, $vnames), )* ];
+        }
+    };
+
+    (@one $t:ident) => { 1 };
+}
+
+impl_tuple! {
+    [];
+    [
+        (V1, v1),
+        (V2, v2),
+        (V3, v3),
+        (V4, v4),
+        (V5, v5),
+        (V6, v6),
+        (V7, v7),
+        (V8, v8),
+    ];
+}
+
+const NESTED_OPTION: &str =
+    "nested options cannot be converted to or from JavaScript; `None` and `Some(None)` are indistinguishable";
+
+/// Converts `None` to `null`.
+impl<'cx, T: TryIntoJs<'cx>> TryIntoJs<'cx> for Option<T> {
+    type Value = JsValue;
+
+    const IS_OPTION: bool = true;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsValue> {
+        const { assert!(!T::IS_OPTION, "{}", NESTED_OPTION) };
+
+        match self {
+            Some(v) => Ok(v.try_into_js(cx)?.upcast()),
+            None => Ok(cx.null().upcast()),
+        }
+    }
+}
+
+/// An optional value that converts `None` to `undefined`, rather than `null`.
+///
+/// Extracting an `OrUndefined` accepts either `null` or `undefined` as `None`, the
+/// same as `Option`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrUndefined<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for OrUndefined<T> {
+    fn from(v: Option<T>) -> Self {
+        Self(v)
+    }
+}
+
+impl<T> From<OrUndefined<T>> for Option<T> {
+    fn from(v: OrUndefined<T>) -> Self {
+        v.0
+    }
+}
+
+impl<'cx, T: TryIntoJs<'cx>> TryIntoJs<'cx> for OrUndefined<T> {
+    type Value = JsValue;
+
+    const IS_OPTION: bool = true;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsValue> {
+        const { assert!(!T::IS_OPTION, "{}", NESTED_OPTION) };
+
+        match self.0 {
+            Some(v) => Ok(v.try_into_js(cx)?.upcast()),
+            None => Ok(cx.undefined().upcast()),
+        }
+    }
+}
+
+pub use neon_macros::TryFromJs;
+
+/// Extract a Rust value from a JavaScript value.
+///
+/// Structs may [derive](macro@TryFromJs) `TryFromJs` to be extracted from objects.
+pub trait TryFromJs<'cx>: Sized {
+    // Used to reject nested options at compile time
+    #[doc(hidden)]
+    const IS_OPTION: bool = false;
+
+    /// Extract a value of this type from `v`, throwing if `v` cannot be converted.
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self>;
+
+    // Extracts the field of a `#[derive(TryFromJs)]` struct at `path`, recording any
+    // errors instead of throwing. Returns `Ok(None)` if errors were recorded.
+    #[doc(hidden)]
+    fn try_from_js_field<C: Context<'cx>>(
+        cx: &mut C,
+        v: Handle<'cx, JsValue>,
+        path: &str,
+        errors: &mut FieldErrors,
+    ) -> NeonResult<Option<Self>> {
+        let result = Self::try_from_js(cx, v);
+
+        errors.catch(cx, path, result)
+    }
+}
+
+impl<'cx, V: Value> TryFromJs<'cx> for Handle<'cx, V> {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        v.downcast_or_throw(cx)
+    }
+}
+
+impl<'cx> TryFromJs<'cx> for f64 {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        Ok(v.downcast_or_throw::<JsNumber, _>(cx)?.value(cx))
+    }
+}
+
+impl<'cx> TryFromJs<'cx> for bool {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        Ok(v.downcast_or_throw::<JsBoolean, _>(cx)?.value(cx))
+    }
+}
+
+impl<'cx> TryFromJs<'cx> for String {
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        Ok(v.downcast_or_throw::<JsString, _>(cx)?.value(cx))
+    }
+}
+
+/// Extracts `null` or `undefined` as `None`.
+impl<'cx, T: TryFromJs<'cx>> TryFromJs<'cx> for Option<T> {
+    const IS_OPTION: bool = true;
+
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        const { assert!(!T::IS_OPTION, "{}", NESTED_OPTION) };
+
+        if v.is_a::<JsNull, _>(cx) || v.is_a::<JsUndefined, _>(cx) {
+            return Ok(None);
+        }
+
+        T::try_from_js(cx, v).map(Some)
+    }
+
+    fn try_from_js_field<C: Context<'cx>>(
+        cx: &mut C,
+        v: Handle<'cx, JsValue>,
+        path: &str,
+        errors: &mut FieldErrors,
+    ) -> NeonResult<Option<Self>> {
+        if v.is_a::<JsNull, _>(cx) || v.is_a::<JsUndefined, _>(cx) {
+            return Ok(Some(None));
+        }
+
+        Ok(T::try_from_js_field(cx, v, path, errors)?.map(Some))
+    }
+}
+
+impl<'cx, T: TryFromJs<'cx>> TryFromJs<'cx> for OrUndefined<T> {
+    const IS_OPTION: bool = true;
+
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        Option::try_from_js(cx, v).map(OrUndefined)
+    }
+
+    fn try_from_js_field<C: Context<'cx>>(
+        cx: &mut C,
+        v: Handle<'cx, JsValue>,
+        path: &str,
+        errors: &mut FieldErrors,
+    ) -> NeonResult<Option<Self>> {
+        Ok(Option::try_from_js_field(cx, v, path, errors)?.map(OrUndefined))
+    }
+}
+
+/// An optional value that distinguishes `undefined` from `null`.
+///
+/// JavaScript APIs often treat a missing value, e.g., an omitted argument or option,
+/// differently from one that is explicitly `null`. Extracting an `Option` collapses
+/// both to `None`; `MaybeUndefined` keeps them apart. A missing argument or property
+/// extracts as [`MaybeUndefined::Missing`], the same as `undefined`.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::extract::MaybeUndefined;
+///
+/// // `{}` uses the default timeout, `{ timeout: null }` disables it
+/// fn timeout(mut cx: FunctionContext) -> JsResult<JsValue> {
+///     let options = cx.argument::<JsObject>(0)?;
+///     let timeout = options
+///         .extract_property::<MaybeUndefined<f64>, _, _>(&mut cx, "timeout")?
+///         .with_default(30_000.0);
+///
+///     match timeout {
+///         Some(ms) => Ok(cx.number(ms).upcast()),
+///         None => Ok(cx.null().upcast()),
+///     }
+/// }
+/// ```
+///
+/// When converted to JavaScript, `Missing` becomes `undefined` and `Null` becomes `null`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MaybeUndefined<T> {
+    /// The value was `undefined` or not provided.
+    #[default]
+    Missing,
+    /// The value was `null`.
+    Null,
+    /// The value was present.
+    Value(T),
+}
+
+impl<T> MaybeUndefined<T> {
+    /// Returns `true` if the value was `undefined` or not provided.
+    pub fn is_missing(&self) -> bool {
+        matches!(self, MaybeUndefined::Missing)
+    }
+
+    /// Returns `true` if the value was `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, MaybeUndefined::Null)
+    }
+
+    /// Converts to an `Option`, collapsing `Missing` and `Null` to `None`.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            MaybeUndefined::Value(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Replaces a missing value with `default`, while `null` remains `None`.
+    pub fn with_default(self, default: T) -> Option<T> {
+        self.with_default_else(|| default)
+    }
+
+    /// Replaces a missing value with the result of `f`, while `null` remains `None`.
+    pub fn with_default_else<F: FnOnce() -> T>(self, f: F) -> Option<T> {
+        match self {
+            MaybeUndefined::Missing => Some(f()),
+            MaybeUndefined::Null => None,
+            MaybeUndefined::Value(v) => Some(v),
+        }
+    }
+}
+
+impl<T> From<MaybeUndefined<T>> for Option<T> {
+    fn from(v: MaybeUndefined<T>) -> Self {
+        v.into_option()
+    }
+}
+
+impl<'cx, T: TryIntoJs<'cx>> TryIntoJs<'cx> for MaybeUndefined<T> {
+    type Value = JsValue;
+
+    const IS_OPTION: bool = true;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsValue> {
+        const { assert!(!T::IS_OPTION, "{}", NESTED_OPTION) };
+
+        match self {
+            MaybeUndefined::Missing => Ok(cx.undefined().upcast()),
+            MaybeUndefined::Null => Ok(cx.null().upcast()),
+            MaybeUndefined::Value(v) => Ok(v.try_into_js(cx)?.upcast()),
+        }
+    }
+}
+
+impl<'cx, T: TryFromJs<'cx>> TryFromJs<'cx> for MaybeUndefined<T> {
+    const IS_OPTION: bool = true;
+
+    fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+        const { assert!(!T::IS_OPTION, "{}", NESTED_OPTION) };
+
+        if v.is_a::<JsUndefined, _>(cx) {
+            return Ok(MaybeUndefined::Missing);
+        }
+
+        if v.is_a::<JsNull, _>(cx) {
+            return Ok(MaybeUndefined::Null);
+        }
+
+        T::try_from_js(cx, v).map(MaybeUndefined::Value)
+    }
+
+    fn try_from_js_field<C: Context<'cx>>(
+        cx: &mut C,
+        v: Handle<'cx, JsValue>,
+        path: &str,
+        errors: &mut FieldErrors,
+    ) -> NeonResult<Option<Self>> {
+        if v.is_a::<JsUndefined, _>(cx) {
+            return Ok(Some(MaybeUndefined::Missing));
+        }
+
+        if v.is_a::<JsNull, _>(cx) {
+            return Ok(Some(MaybeUndefined::Null));
+        }
+
+        Ok(T::try_from_js_field(cx, v, path, errors)?.map(MaybeUndefined::Value))
+    }
+}
+
+#[cfg(all(feature = "napi-5", any(feature = "chrono", feature = "time")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "napi-5", any(feature = "chrono", feature = "time"))))
+)]
+/// Extracts a date from either a JavaScript `Date` or an
+/// [ISO 8601](https://en.wikipedia.org/wiki/ISO_8601) string.
+///
+/// Date types such as `chrono::DateTime<Utc>` only accept `Date` objects when
+/// extracted directly. Wrapping the type in `Iso8601` opts in to parsing strings as
+/// well, e.g., dates that were serialized to JSON.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use chrono::{DateTime, Utc};
+/// use neon::types::extract::{Iso8601, TryFromJs};
+///
+/// fn year(mut cx: FunctionContext) -> JsResult<JsNumber> {
+///     use chrono::Datelike;
+///
+///     let arg = cx.argument::<JsValue>(0)?;
+///     let Iso8601(date) = Iso8601::<DateTime<Utc>>::try_from_js(&mut cx, arg)?;
+///
+///     Ok(cx.number(date.year()))
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Iso8601<T>(pub T);
+
+// Support for `#[derive(TryFromJs)]`. Each field of a derived struct is extracted even
+// after another fails, and the errors are thrown together as a single `TypeError`.
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct FieldErrors {
+    // Path of the field and a description of the error
+    errors: Vec<(String, String)>,
+}
+
+#[doc(hidden)]
+impl FieldErrors {
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn push(&mut self, path: &str, message: impl fmt::Display) {
+        self.errors.push((path.to_string(), message.to_string()));
+    }
+
+    // Records the message of the exception thrown while producing `result`
+    pub fn catch<'cx, C: Context<'cx>, T>(
+        &mut self,
+        cx: &mut C,
+        path: &str,
+        result: NeonResult<T>,
+    ) -> NeonResult<Option<T>> {
+        let throw = match result {
+            Ok(v) => return Ok(Some(v)),
+            Err(throw) => throw,
+        };
+
+        // `Err(Throw)` without an exception, e.g., while the environment is torn down
+        if !unsafe { sys::error::is_throwing(cx.env().to_raw()) } {
+            return Err(throw);
+        }
+
+        let err = match cx.try_catch(|_| Err::<(), _>(throw)) {
+            Err(err) => err,
+            Ok(()) => unreachable!(),
+        };
+
+        let message = match err.downcast::<JsObject, _>(cx) {
+            Ok(err) => err.get_value(cx, "message")?,
+            Err(_) => err,
+        };
+
+        let message = message.to_string(cx)?.value(cx);
+
+        self.push(path, message);
+
+        Ok(None)
+    }
+
+    // Returns `v` as an object, or records an error if it is not one
+    pub fn object<'cx, C: Context<'cx>>(
+        &mut self,
+        cx: &mut C,
+        v: Handle<'cx, JsValue>,
+        path: &str,
+    ) -> Option<Handle<'cx, JsObject>> {
+        match v.downcast::<JsObject, _>(cx) {
+            Ok(object) => Some(object),
+            Err(_) => {
+                self.push(path, "expected an object");
+                None
+            }
+        }
+    }
+
+    // Records an error for each own property of `object` that is not in `keys`. Without
+    // N-API 6, only enumerable properties are found, with `Object.keys`.
+    pub fn deny_unknown_fields<'cx, C: Context<'cx>>(
+        &mut self,
+        cx: &mut C,
+        object: Handle<'cx, JsObject>,
+        path: &str,
+        keys: &[&str],
+    ) -> NeonResult<()> {
+        #[cfg(feature = "napi-6")]
+        let object_keys = object.get_own_property_names(cx)?.to_vec(cx)?;
+
+        #[cfg(not(feature = "napi-6"))]
+        let object_keys = crate::intrinsics::object_keys(cx)?
+            .call_with(cx)
+            .arg(object)
+            .apply::<JsArray, _>(cx)?
+            .to_vec(cx)?;
+
+        for key in object_keys {
+            let key = key.to_string(cx)?.value(cx);
+
+            if !keys.contains(&key.as_str()) {
+                self.push(&field_path(path, &key), "unknown field");
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn throw<'cx, C: Context<'cx>, T>(self, cx: &mut C, name: &str) -> NeonResult<T> {
+        let errors = self
+            .errors
+            .iter()
+            .map(|(path, message)| {
+                if path.is_empty() {
+                    message.clone()
+                } else {
+                    format!("{}: {}", path, message)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        cx.throw_type_error(format!("invalid {}: {}", name, errors))
+    }
+}
+
+#[doc(hidden)]
+pub fn field_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+// Extracts a field, recording a missing value as an error unless `T` is optional
+#[doc(hidden)]
+pub fn extract_field<'cx, C, T>(
+    cx: &mut C,
+    v: Handle<'cx, JsValue>,
+    path: &str,
+    errors: &mut FieldErrors,
+) -> NeonResult<Option<T>>
+where
+    C: Context<'cx>,
+    T: TryFromJs<'cx>,
+{
+    if !T::IS_OPTION && v.is_a::<JsUndefined, _>(cx) {
+        errors.push(path, "missing required field");
+        return Ok(None);
+    }
+
+    T::try_from_js_field(cx, v, path, errors)
+}
+
+// Extracts a field as `I` and converts it with the `#[neon(with = "...")]` function
+#[doc(hidden)]
+pub fn extract_field_with<'cx, C, I, T, E, F>(
+    cx: &mut C,
+    v: Handle<'cx, JsValue>,
+    path: &str,
+    errors: &mut FieldErrors,
+    f: F,
+) -> NeonResult<Option<T>>
+where
+    C: Context<'cx>,
+    I: TryFromJs<'cx>,
+    E: fmt::Display,
+    F: FnOnce(I) -> Result<T, E>,
+{
+    let v = match extract_field::<C, I>(cx, v, path, errors)? {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    match f(v) {
+        Ok(v) => Ok(Some(v)),
+        Err(err) => {
+            errors.push(path, err);
+            Ok(None)
+        }
+    }
+}
+
+// Numeric values that may be checked with `#[neon(range(min = ..., max = ...))]`.
+// Optional values are only checked if present.
+#[doc(hidden)]
+pub trait InRange {
+    fn in_range(&self, min: Option<f64>, max: Option<f64>) -> bool;
+}
+
+macro_rules! impl_in_range {
+    ($($ty:ty),*) => {
+        $(
+            impl InRange for $ty {
+                fn in_range(&self, min: Option<f64>, max: Option<f64>) -> bool {
+                    let v = *self as f64;
+
+                    min.map_or(true, |min| v >= min) && max.map_or(true, |max| v <= max)
+                }
+            }
+        )*
+    };
+}
+
+impl_in_range!(f64, f32, i64, u64, i32, u32, i16, u16, i8, u8, isize, usize);
+
+impl<T: InRange> InRange for Option<T> {
+    fn in_range(&self, min: Option<f64>, max: Option<f64>) -> bool {
+        match self {
+            Some(v) => v.in_range(min, max),
+            None => true,
+        }
+    }
+}
+
+impl<T: InRange> InRange for OrUndefined<T> {
+    fn in_range(&self, min: Option<f64>, max: Option<f64>) -> bool {
+        self.0.in_range(min, max)
+    }
+}
+
+impl<T: InRange> InRange for MaybeUndefined<T> {
+    fn in_range(&self, min: Option<f64>, max: Option<f64>) -> bool {
+        match self {
+            MaybeUndefined::Value(v) => v.in_range(min, max),
+            _ => true,
+        }
+    }
+}
+
+// Records an error if `v` is outside of the range
+#[doc(hidden)]
+pub fn check_range<T: InRange>(
+    v: T,
+    min: Option<f64>,
+    max: Option<f64>,
+    path: &str,
+    errors: &mut FieldErrors,
+) -> Option<T> {
+    if v.in_range(min, max) {
+        return Some(v);
+    }
+
+    match (min, max) {
+        (Some(min), Some(max)) => errors.push(path, format!("must be between {} and {}", min, max)),
+        (Some(min), None) => errors.push(path, format!("must be at least {}", min)),
+        (None, Some(max)) => errors.push(path, format!("must be at most {}", max)),
+        (None, None) => unreachable!(),
+    }
+
+    None
+}
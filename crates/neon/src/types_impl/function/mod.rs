@@ -4,14 +4,53 @@ use smallvec::smallvec;
 
 use crate::{
     context::Context,
-    handle::Handle,
+    handle::{Handle, Managed},
     object::Object,
     result::{JsResult, NeonResult},
-    types::{JsFunction, JsObject, JsValue, Value},
+    sys,
+    types::{extract::TryIntoJs, JsFunction, JsObject, JsValue, Value},
 };
 
 pub(crate) mod private;
 
+/// Options for creating a JavaScript function with
+/// [`JsFunction::with_options`](JsFunction::with_options).
+///
+/// ```
+/// # use neon::prelude::*;
+/// # use neon::types::function::FunctionOptions;
+/// # fn add(mut cx: FunctionContext) -> JsResult<JsNumber> { unimplemented!() }
+/// # fn foo(mut cx: FunctionContext) -> JsResult<JsFunction> {
+/// let options = FunctionOptions::new().name("add").length(2);
+/// let add = JsFunction::with_options(&mut cx, options, add)?;
+/// # Ok(add)
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FunctionOptions<'a> {
+    pub(crate) name: Option<&'a str>,
+    pub(crate) length: Option<u32>,
+}
+
+impl<'a> FunctionOptions<'a> {
+    /// Creates options with the default `name` and `length`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `name` property of the function, as seen in stack traces.
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Set the `length` property of the function, i.e., the number of arguments it expects.
+    pub fn length(mut self, length: u32) -> Self {
+        self.length = Some(length);
+        self
+    }
+}
+
 /// A builder for making a JavaScript function call like `parseInt("42")`.
 ///
 /// The builder methods make it convenient to assemble the call from parts:
@@ -53,20 +92,58 @@ impl<'a> CallOptions<'a> {
         self
     }
 
+    /// Add each argument produced by an iterator to the arguments list, converting each
+    /// item with [`TryIntoJs`].
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// # fn foo(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    /// # let math: Handle<JsObject> = cx.global().get(&mut cx, "Math")?;
+    /// # let max: Handle<JsFunction> = math.get(&mut cx, "max")?;
+    /// let n = max
+    ///     .call_with(&cx)
+    ///     .args_from_iter(&mut cx, [1, 2, 3])?
+    ///     .returning::<JsNumber>(&mut cx)?;
+    /// # Ok(n)
+    /// # }
+    /// ```
+    pub fn args_from_iter<'b: 'a, C, T, I>(&mut self, cx: &mut C, args: I) -> NeonResult<&mut Self>
+    where
+        C: Context<'b>,
+        T: TryIntoJs<'b>,
+        I: IntoIterator<Item = T>,
+    {
+        for arg in args {
+            self.args.push(arg.try_into_js(cx)?.upcast());
+        }
+
+        Ok(self)
+    }
+
     /// Make the function call. If the function returns without throwing, the result value
     /// is downcast to the type `V`, throwing a `TypeError` if the downcast fails.
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     pub fn apply<'b: 'a, V: Value, C: Context<'b>>(&self, cx: &mut C) -> JsResult<'b, V> {
         let this = self.this.unwrap_or_else(|| cx.undefined().upcast());
-        let v: Handle<JsValue> = self.callee.call(cx, this, &self.args)?;
-        v.downcast_or_throw(cx)
+        let v: Handle<JsValue> = self.callee.call(cx, this, &self.args[..])?;
+        downcast_result(cx, v)
+    }
+
+    /// Make the function call, downcasting the result to `V`. This is the same as
+    /// [`apply()`](CallOptions::apply), but names only the result type, e.g.,
+    /// `.returning::<JsString>(&mut cx)`.
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
+    pub fn returning<'b: 'a, V: Value>(&self, cx: &mut impl Context<'b>) -> JsResult<'b, V> {
+        self.apply(cx)
     }
 
     /// Make the function call for side effect, discarding the result value. This method is
     /// preferable to [`apply()`](CallOptions::apply) when the result value isn't needed,
     /// since it doesn't require specifying a result type.
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     pub fn exec<'b: 'a, C: Context<'b>>(&self, cx: &mut C) -> NeonResult<()> {
         let this = self.this.unwrap_or_else(|| cx.undefined().upcast());
-        self.callee.call(cx, this, &self.args)?;
+        self.callee.call(cx, this, &self.args[..])?;
         Ok(())
     }
 }
@@ -105,14 +182,102 @@ impl<'a> ConstructOptions<'a> {
         self
     }
 
+    /// Add each argument produced by an iterator to the arguments list, converting each
+    /// item with [`TryIntoJs`].
+    pub fn args_from_iter<'b: 'a, C, T, I>(&mut self, cx: &mut C, args: I) -> NeonResult<&mut Self>
+    where
+        C: Context<'b>,
+        T: TryIntoJs<'b>,
+        I: IntoIterator<Item = T>,
+    {
+        for arg in args {
+            self.args.push(arg.try_into_js(cx)?.upcast());
+        }
+
+        Ok(self)
+    }
+
     /// Make the constructor call. If the function returns without throwing, returns
     /// the resulting object.
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     pub fn apply<'b: 'a, O: Object, C: Context<'b>>(&self, cx: &mut C) -> JsResult<'b, O> {
-        let v: Handle<JsObject> = self.callee.construct(cx, &self.args)?;
+        let v: Handle<JsObject> = self.callee.construct(cx, &self.args[..])?;
         v.downcast_or_throw(cx)
     }
 }
 
+/// The arguments, `this`-binding and `new.target` of a function call, captured by
+/// [`FunctionContext::forward_args`](crate::context::FunctionContext::forward_args)
+/// to be passed on to another function with [`JsFunction::call_with_forwarded`].
+///
+/// The arguments are stored inline for calls with up to eight arguments, so forwarding
+/// them does not allocate in the common case.
+#[derive(Clone, Debug)]
+pub struct ForwardedArgs<'a> {
+    this: Handle<'a, JsValue>,
+    new_target: Option<Handle<'a, JsFunction>>,
+    args: private::ArgsVec<'a>,
+}
+
+impl<'a> ForwardedArgs<'a> {
+    pub(crate) fn new(
+        this: Handle<'a, JsValue>,
+        new_target: Option<Handle<'a, JsFunction>>,
+        args: private::ArgsVec<'a>,
+    ) -> Self {
+        Self {
+            this,
+            new_target,
+            args,
+        }
+    }
+
+    /// The `this`-binding of the original call.
+    pub fn this(&self) -> Handle<'a, JsValue> {
+        self.this
+    }
+
+    /// The value of `new.target` in the original call, or `None` if the function was
+    /// called without `new`.
+    pub fn new_target(&self) -> Option<Handle<'a, JsFunction>> {
+        self.new_target
+    }
+
+    /// The number of arguments.
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Indicates if there are no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// The arguments of the original call.
+    pub fn as_slice(&self) -> &[Handle<'a, JsValue>] {
+        &self.args
+    }
+}
+
+// Downcasts the result of a function call, throwing a `TypeError` that names both the
+// actual and expected types on failure.
+fn downcast_result<'b, V: Value, C: Context<'b>>(
+    cx: &mut C,
+    v: Handle<'b, JsValue>,
+) -> JsResult<'b, V> {
+    if let Ok(v) = v.downcast(cx) {
+        return Ok(v);
+    }
+
+    let actual = unsafe { sys::tag::type_name(cx.env().to_raw(), v.to_raw()) };
+
+    cx.throw_type_error(format!(
+        "function returned {}, expected {}",
+        actual,
+        V::name()
+    ))
+}
+
 /// The trait for specifying arguments for a function call. This trait is sealed and cannot
 /// be implemented by types outside of the Neon crate.
 ///
@@ -217,3 +382,186 @@ impl_arguments! {
         (V32, v32),
     ];
 }
+
+/// The trait for arguments passed to [`JsFunction::call`], [`JsFunction::exec`],
+/// [`JsFunction::construct`] and [`JsFunction::bind`]. This trait is sealed and cannot
+/// be implemented by types outside of the Neon crate.
+///
+/// Arguments may be given as a slice, array or `Vec` of [`Handle<JsValue>`], or as a
+/// tuple of values of different types. Each element of a tuple may be a handle to any
+/// JavaScript type, which is upcast, or any Rust value that implements
+/// [`TryIntoJs`], which is converted when the function is called. Tuples are collected
+/// into an array on the stack, avoiding the allocation and explicit upcasts of a `Vec`.
+///
+/// ```
+/// # use neon::prelude::*;
+/// # fn foo(mut cx: FunctionContext) -> JsResult<JsValue> {
+/// # let callback: Handle<JsFunction> = cx.argument(0)?;
+/// let this = cx.undefined();
+/// let name = cx.string("neon");
+///
+/// // Equivalent to `callback(name, 42, true)`
+/// callback.call(&mut cx, this, (name, 42.0, true))
+/// # }
+/// ```
+///
+/// **Note:** This trait is implemented for tuples of up to 32 values, but for the sake
+/// of brevity, only tuples up to size 8 are shown in this documentation.
+pub trait TryIntoArguments<'cx>: private::TryIntoArgumentsInternal<'cx> {}
+
+impl<'cx> private::TryIntoArgumentsInternal<'cx> for () {
+    type Args = [Handle<'cx, JsValue>; 0];
+
+    fn try_into_args<C: Context<'cx>>(self, _cx: &mut C) -> NeonResult<Self::Args> {
+        Ok([])
+    }
+}
+
+impl<'cx> TryIntoArguments<'cx> for () {}
+
+impl<'cx, 'a, 'b> private::TryIntoArgumentsInternal<'cx> for &'a [Handle<'b, JsValue>] {
+    type Args = Self;
+
+    fn try_into_args<C: Context<'cx>>(self, _cx: &mut C) -> NeonResult<Self::Args> {
+        Ok(self)
+    }
+}
+
+impl<'cx, 'a, 'b> TryIntoArguments<'cx> for &'a [Handle<'b, JsValue>] {}
+
+impl<'cx, 'b, const N: usize> private::TryIntoArgumentsInternal<'cx> for [Handle<'b, JsValue>; N] {
+    type Args = Self;
+
+    fn try_into_args<C: Context<'cx>>(self, _cx: &mut C) -> NeonResult<Self::Args> {
+        Ok(self)
+    }
+}
+
+impl<'cx, 'b, const N: usize> TryIntoArguments<'cx> for [Handle<'b, JsValue>; N] {}
+
+impl<'cx, 'a, 'b, const N: usize> private::TryIntoArgumentsInternal<'cx>
+    for &'a [Handle<'b, JsValue>; N]
+{
+    type Args = &'a [Handle<'b, JsValue>];
+
+    fn try_into_args<C: Context<'cx>>(self, _cx: &mut C) -> NeonResult<Self::Args> {
+        Ok(self)
+    }
+}
+
+impl<'cx, 'a, 'b, const N: usize> TryIntoArguments<'cx> for &'a [Handle<'b, JsValue>; N] {}
+
+impl<'cx, 'b> private::TryIntoArgumentsInternal<'cx> for Vec<Handle<'b, JsValue>> {
+    type Args = Self;
+
+    fn try_into_args<C: Context<'cx>>(self, _cx: &mut C) -> NeonResult<Self::Args> {
+        Ok(self)
+    }
+}
+
+impl<'cx, 'b> TryIntoArguments<'cx> for Vec<Handle<'b, JsValue>> {}
+
+impl<'cx, 'a, 'b> private::TryIntoArgumentsInternal<'cx> for &'a Vec<Handle<'b, JsValue>> {
+    type Args = &'a [Handle<'b, JsValue>];
+
+    fn try_into_args<C: Context<'cx>>(self, _cx: &mut C) -> NeonResult<Self::Args> {
+        Ok(self)
+    }
+}
+
+impl<'cx, 'a, 'b> TryIntoArguments<'cx> for &'a Vec<Handle<'b, JsValue>> {}
+
+macro_rules! impl_try_into_arguments {
+    {
+        [ $(($tprefix:ident, $vprefix:ident), )* ];
+        [];
+    } => {};
+
+    {
+        [ $(($tprefix:ident, $vprefix:ident), )* ];
+        [ $(#[$attr1:meta])? ($tname1:ident, $vname1:ident), $($(#[$attrs:meta])? ($tnames:ident, $vnames:ident), )* ];
+    } => {
+        $(#[$attr1])?
+        impl<'cx, $($tprefix: TryIntoJs<'cx>, )* $tname1: TryIntoJs<'cx>> private::TryIntoArgumentsInternal<'cx> for ($($tprefix, )* $tname1, ) {
+            type Args = [Handle<'cx, JsValue>; 1 $(+ impl_try_into_arguments!(@one $tprefix))*];
+
+            fn try_into_args<C: Context<'cx>>(self, cx: &mut C) -> NeonResult<Self::Args> {
+                let ($($vprefix, )* $vname1, ) = self;
+
+                Ok([$($vprefix.try_into_js(cx)?.upcast(), )* $vname1.try_into_js(cx)?.upcast()])
+            }
+        }
+
+        $(#[$attr1])?
+        impl<'cx, $($tprefix: TryIntoJs<'cx>, )* $tname1: TryIntoJs<'cx>> TryIntoArguments<'cx> for ($($tprefix, )* $tname1, ) {}
+
+        impl_try_into_arguments! {
+            [ $(($tprefix, $vprefix), )* ($tname1, $vname1), ];
+            [ $($(#[$attrs])? ($tnames, $vnames), )* ];
+        }
+    };
+
+    (@one $t:ident) => { 1 };
+}
+
+impl_try_into_arguments! {
+    [];
+    [
+        (V1, v1),
+        (V2, v2),
+        (V3, v3),
+        (V4, v4),
+        (V5, v5),
+        (V6, v6),
+        (V7, v7),
+        (V8, v8),
+        #[doc(hidden)]
+        (V9, v9),
+        #[doc(hidden)]
+        (V10, v10),
+        #[doc(hidden)]
+        (V11, v11),
+        #[doc(hidden)]
+        (V12, v12),
+        #[doc(hidden)]
+        (V13, v13),
+        #[doc(hidden)]
+        (V14, v14),
+        #[doc(hidden)]
+        (V15, v15),
+        #[doc(hidden)]
+        (V16, v16),
+        #[doc(hidden)]
+        (V17, v17),
+        #[doc(hidden)]
+        (V18, v18),
+        #[doc(hidden)]
+        (V19, v19),
+        #[doc(hidden)]
+        (V20, v20),
+        #[doc(hidden)]
+        (V21, v21),
+        #[doc(hidden)]
+        (V22, v22),
+        #[doc(hidden)]
+        (V23, v23),
+        #[doc(hidden)]
+        (V24, v24),
+        #[doc(hidden)]
+        (V25, v25),
+        #[doc(hidden)]
+        (V26, v26),
+        #[doc(hidden)]
+        (V27, v27),
+        #[doc(hidden)]
+        (V28, v28),
+        #[doc(hidden)]
+        (V29, v29),
+        #[doc(hidden)]
+        (V30, v30),
+        #[doc(hidden)]
+        (V31, v31),
+        #[doc(hidden)]
+        (V32, v32),
+    ];
+}
@@ -1,10 +1,103 @@
 use smallvec::SmallVec;
 
-use crate::{handle::Handle, types::JsValue};
+use crate::{context::Context, handle::Handle, result::NeonResult, sys::raw, types::JsValue};
 
-pub type ArgsVec<'a> = SmallVec<[Handle<'a, JsValue>; 8]>;
+// Number of arguments stored inline by `ArgsVec` before spilling to the heap
+pub const ARGS_INLINE: usize = 8;
+
+pub type ArgsVec<'a> = SmallVec<[Handle<'a, JsValue>; ARGS_INLINE]>;
+
+// `Handle<JsValue>` and `JsValue` are `repr(transparent)` wrappers of `raw::Local`
+const _: () = assert!(std::mem::size_of::<Handle<JsValue>>() == std::mem::size_of::<raw::Local>());
+const _: () =
+    assert!(std::mem::align_of::<Handle<JsValue>>() == std::mem::align_of::<raw::Local>());
+
+/// Views arguments as the `napi_value` array expected by Node-API, without copying.
+pub fn raw_args<'s>(args: &'s [Handle<'_, JsValue>]) -> &'s [raw::Local] {
+    // Safety: `Handle<JsValue>` has the same layout as `raw::Local`
+    unsafe { std::slice::from_raw_parts(args.as_ptr().cast(), args.len()) }
+}
 
 /// This type marks the `Arguments` trait as sealed.
 pub trait ArgumentsInternal<'a> {
     fn into_args_vec(self) -> ArgsVec<'a>;
 }
+
+/// This type marks the `TryIntoArguments` trait as sealed.
+pub trait TryIntoArgumentsInternal<'cx> {
+    type Args: ArgsSlice;
+
+    fn try_into_args<C: Context<'cx>>(self, cx: &mut C) -> NeonResult<Self::Args>;
+}
+
+/// Arguments ready to be passed to a function call. Unlike `AsRef`, the lifetime of the
+/// handles is not tied to the lifetime of the context, so that handles from any scope
+/// may be passed.
+pub trait ArgsSlice {
+    fn as_args(&self) -> &[Handle<'_, JsValue>];
+}
+
+impl<'b> ArgsSlice for &[Handle<'b, JsValue>] {
+    fn as_args(&self) -> &[Handle<'_, JsValue>] {
+        self
+    }
+}
+
+impl<'b, const N: usize> ArgsSlice for [Handle<'b, JsValue>; N] {
+    fn as_args(&self) -> &[Handle<'_, JsValue>] {
+        self
+    }
+}
+
+impl<'b> ArgsSlice for Vec<Handle<'b, JsValue>> {
+    fn as_args(&self) -> &[Handle<'_, JsValue>] {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use smallvec::smallvec;
+
+    use super::{raw_args, ArgsVec, ARGS_INLINE};
+    use crate::{handle::Handle, sys::raw, types_impl::JsValue};
+
+    // Handles are never dereferenced, so any distinct pointer values will do
+    fn handle(n: usize) -> Handle<'static, JsValue> {
+        Handle::new_internal(JsValue(n as raw::Local))
+    }
+
+    #[test]
+    fn test_raw_args() {
+        let args = [handle(1), handle(2), handle(3)];
+        let raw = raw_args(&args);
+
+        assert_eq!(raw.len(), 3);
+        assert_eq!(raw.as_ptr(), args.as_ptr().cast());
+        assert_eq!(raw, &[1 as raw::Local, 2 as raw::Local, 3 as raw::Local]);
+        assert!(raw_args(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_args_vec_inline() {
+        let mut args: ArgsVec = smallvec![];
+
+        for i in 0..ARGS_INLINE {
+            args.push(handle(i + 1));
+        }
+
+        assert!(!args.spilled());
+
+        args.push(handle(ARGS_INLINE + 1));
+
+        assert!(args.spilled());
+
+        let raw = raw_args(&args);
+
+        assert_eq!(raw.len(), ARGS_INLINE + 1);
+        assert!(raw
+            .iter()
+            .enumerate()
+            .all(|(i, &local)| local == (i + 1) as raw::Local));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    context::Context,
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{JsFunction, JsNull, JsObject, JsUndefined, JsValue, Value},
+};
+
+/// An iterator over a JavaScript
+/// [iterable](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Iteration_protocols)
+/// value, such as a `Set`, a `Map`, a string or a generator.
+///
+/// A `JsIterator` follows the iteration protocol in the same way as a JavaScript
+/// `for...of` loop: the `next` method is looked up once, when the iterator is created,
+/// and exceptions thrown by the iterator are propagated to the caller.
+///
+/// If iteration is stopped before the iterator is exhausted, [`JsIterator::close`]
+/// should be called so that the iterator can release any resources it holds, as
+/// `break` would in a `for...of` loop. Dropping a `JsIterator` does _not_ close it.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::JsIterator;
+///
+/// // Returns the first element of an iterable or `undefined` if it is empty
+/// fn first(mut cx: FunctionContext) -> JsResult<JsValue> {
+///     let iterable = cx.argument::<JsValue>(0)?;
+///     let mut iter = JsIterator::from_iterable(&mut cx, iterable)?;
+///     let first = iter.next(&mut cx)?;
+///
+///     iter.close(&mut cx)?;
+///
+///     Ok(first.unwrap_or_else(|| cx.undefined().upcast()))
+/// }
+/// ```
+pub struct JsIterator<'a> {
+    iterator: Handle<'a, JsObject>,
+    next: Handle<'a, JsFunction>,
+    done: bool,
+}
+
+impl<'a> JsIterator<'a> {
+    /// Gets an iterator for `iterable` by calling its `Symbol.iterator` method,
+    /// equivalent to the JavaScript expression `iterable[Symbol.iterator]()`.
+    ///
+    /// Throws a `TypeError` if the value is not iterable.
+    pub fn from_iterable<C: Context<'a>, V: Value>(
+        cx: &mut C,
+        iterable: Handle<'a, V>,
+    ) -> NeonResult<Self> {
+        let iterable = iterable.upcast::<JsValue>();
+
+        if iterable.is_a::<JsUndefined, _>(cx) || iterable.is_a::<JsNull, _>(cx) {
+            return cx.throw_type_error("value is not iterable");
+        }
+
+        let symbol = crate::intrinsics::symbol_iterator(cx)?;
+        let method = iterable.coerce_to_object(cx)?.get_value(cx, symbol)?;
+        let method = match method.downcast::<JsFunction, _>(cx) {
+            Ok(method) => method,
+            Err(_) => return cx.throw_type_error("value is not iterable"),
+        };
+
+        let iterator: Handle<JsValue> = method.call_with(cx).this(iterable).apply(cx)?;
+        let iterator = match iterator.downcast::<JsObject, _>(cx) {
+            Ok(iterator) => iterator,
+            Err(_) => return cx.throw_type_error("Symbol.iterator did not return an object"),
+        };
+
+        let next = iterator.get_value(cx, "next")?;
+        let next = match next.downcast::<JsFunction, _>(cx) {
+            Ok(next) => next,
+            Err(_) => return cx.throw_type_error("iterator does not have a next method"),
+        };
+
+        Ok(Self {
+            iterator,
+            next,
+            done: false,
+        })
+    }
+
+    /// Advances the iterator, returning the next value or `None` if the iterator
+    /// is done.
+    ///
+    /// Once the iterator has finished, either by being exhausted or by throwing, `next`
+    /// returns `None` without calling into JavaScript.
+    pub fn next<C: Context<'a>>(&mut self, cx: &mut C) -> NeonResult<Option<Handle<'a, JsValue>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        // The iterator is considered finished if `next` throws, so that it is
+        // not closed afterwards.
+        self.done = true;
+
+        let result: Handle<JsValue> = self.next.call_with(cx).this(self.iterator).apply(cx)?;
+        let result = match result.downcast::<JsObject, _>(cx) {
+            Ok(result) => result,
+            Err(_) => return cx.throw_type_error("iterator result is not an object"),
+        };
+
+        if result.get_value(cx, "done")?.coerce_to_bool(cx)?.value(cx) {
+            return Ok(None);
+        }
+
+        let value = result.get_value(cx, "value")?;
+
+        self.done = false;
+
+        Ok(Some(value))
+    }
+
+    /// Consumes the remaining values of the iterator into a [`Vec`].
+    pub fn collect_vec<C: Context<'a>>(
+        mut self,
+        cx: &mut C,
+    ) -> NeonResult<Vec<Handle<'a, JsValue>>> {
+        let mut values = Vec::new();
+
+        while let Some(value) = self.next(cx)? {
+            values.push(value);
+        }
+
+        Ok(values)
+    }
+
+    /// Closes the iterator before it is exhausted by calling its `return` method, if
+    /// it has one.
+    ///
+    /// Closing an iterator that has already finished does nothing.
+    pub fn close<C: Context<'a>>(self, cx: &mut C) -> NeonResult<()> {
+        if self.done {
+            return Ok(());
+        }
+
+        let method = self.iterator.get_value(cx, "return")?;
+
+        if method.is_a::<JsUndefined, _>(cx) || method.is_a::<JsNull, _>(cx) {
+            return Ok(());
+        }
+
+        let method = match method.downcast::<JsFunction, _>(cx) {
+            Ok(method) => method,
+            Err(_) => return cx.throw_type_error("iterator return method is not a function"),
+        };
+
+        let result: Handle<JsValue> = method.call_with(cx).this(self.iterator).apply(cx)?;
+
+        if !result.is_a::<JsObject, _>(cx) {
+            return cx.throw_type_error("iterator result is not an object");
+        }
+
+        Ok(())
+    }
+
+    /// Indicates whether the iterator has finished, either by being exhausted or
+    /// by throwing.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
@@ -1,23 +1,43 @@
 // See types_docs.rs for top-level module API docs.
 
+#[cfg(feature = "napi-5")]
+pub(crate) mod abort_signal;
 #[cfg(feature = "napi-6")]
 #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
 pub mod bigint;
 pub(crate) mod boxed;
+pub(crate) mod boxed_iterator;
 pub mod buffer;
 #[cfg(feature = "napi-5")]
 pub(crate) mod date;
+pub(crate) mod encoding;
 pub(crate) mod error;
+pub mod extract;
 pub mod function;
+pub(crate) mod iterator;
+pub(crate) mod number;
+pub mod panic;
 pub(crate) mod promise;
+#[cfg(feature = "napi-5")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+pub mod proxy;
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub mod shared;
+
+pub(crate) mod string_builder;
+pub(crate) mod url;
 
 pub(crate) mod private;
 pub(crate) mod utf8;
+pub(crate) mod visitor;
 
 use std::{
     fmt::{self, Debug},
     marker::PhantomData,
+    ops::{Bound, RangeBounds},
     os::raw::c_void,
+    ptr,
 };
 
 use smallvec::smallvec;
@@ -32,25 +52,41 @@ use crate::{
     result::{JsResult, NeonResult, ResultExt, Throw},
     sys::{self, raw},
     types::{
-        function::{CallOptions, ConstructOptions},
+        function::{
+            private::ArgsSlice, CallOptions, ConstructOptions, FunctionOptions, TryIntoArguments,
+        },
         utf8::Utf8,
     },
 };
 
 pub use self::{
-    boxed::{Finalize, JsBox},
+    boxed::{Finalize, JsBox, ReentrantState},
+    boxed_iterator::BoxedIterator,
     buffer::types::{
         JsArrayBuffer, JsBigInt64Array, JsBigUint64Array, JsBuffer, JsFloat32Array, JsFloat64Array,
         JsInt16Array, JsInt32Array, JsInt8Array, JsTypedArray, JsUint16Array, JsUint32Array,
         JsUint8Array,
     },
-    error::JsError,
+    error::{ErrorClass, ErrorSpec, JsError},
+    iterator::JsIterator,
+    number::{NumberResult, PrecisionLoss, TryIntoF64},
     promise::{Deferred, JsPromise},
+    string_builder::JsStringBuilder,
+    url::JsUrl,
+    visitor::ValueVisitor,
 };
 
+#[cfg(feature = "napi-5")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+pub use self::abort_signal::AbortSignal;
+
 #[cfg(feature = "napi-5")]
 pub use self::date::{DateError, DateErrorKind, JsDate};
 
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub use self::shared::{JsRwLock, RwLockHandle, Shared, SharedHandle};
+
 #[cfg(all(feature = "napi-5", feature = "futures"))]
 #[cfg_attr(docsrs, doc(cfg(all(feature = "napi-5", feature = "futures"))))]
 pub use self::promise::JsFuture;
@@ -90,9 +126,147 @@ pub trait Value: private::ValueInternal {
         })
     }
 
+    /// Converts this value to a string with JavaScript semantics, like the template
+    /// literal `` `${v}` ``.
+    ///
+    /// Unlike `String(v)`, converting a `Symbol` throws a `TypeError`. Also throws if
+    /// the conversion throws, e.g., from a custom `toString` method.
+    fn coerce_to_string<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsString> {
+        self.to_string(cx)
+    }
+
+    /// Converts this value to a number with JavaScript semantics, like the unary plus
+    /// expression `+v`.
+    ///
+    /// Unlike `Number(v)`, converting a `BigInt` throws a `TypeError`, as does converting
+    /// a `Symbol`. Also throws if the conversion throws, e.g., from a custom `valueOf`
+    /// method.
+    fn coerce_to_number<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsNumber> {
+        let env = cx.env();
+        build(env, |out| unsafe {
+            sys::convert::to_number(out, env.to_raw(), self.to_raw())
+        })
+    }
+
+    /// Converts this value to a boolean with JavaScript semantics, equivalent to `Boolean(v)`.
+    fn coerce_to_bool<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsBoolean> {
+        let env = cx.env();
+        build(env, |out| unsafe {
+            sys::convert::to_bool(out, env.to_raw(), self.to_raw())
+        })
+    }
+
+    /// Converts this value to an object with JavaScript semantics, equivalent to `Object(v)`.
+    /// Primitives are wrapped in their corresponding wrapper objects.
+    ///
+    /// Throws a `TypeError` if the value is `null` or `undefined`.
+    fn coerce_to_object<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsObject> {
+        let env = cx.env();
+        build(env, |out| unsafe {
+            sys::convert::to_object(out, env.to_raw(), self.to_raw())
+        })
+    }
+
     fn as_value<'a, C: Context<'a>>(&self, _: &mut C) -> Handle<'a, JsValue> {
         JsValue::new_internal(self.to_raw())
     }
+
+    /// Returns the kind of this value, similar to the JavaScript `typeof` operator.
+    ///
+    /// This performs a single type check and is faster than testing a value against
+    /// several types in turn with [`Handle::is_a`](crate::handle::Handle::is_a):
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// use neon::types::ValueKind;
+    ///
+    /// fn describe(mut cx: FunctionContext) -> JsResult<JsString> {
+    ///     let v = cx.argument::<JsValue>(0)?;
+    ///     let description = match v.kind(&mut cx) {
+    ///         ValueKind::Undefined | ValueKind::Null => "nothing",
+    ///         ValueKind::Number | ValueKind::BigInt => "a number",
+    ///         ValueKind::String => "a string",
+    ///         _ => "something else",
+    ///     };
+    ///
+    ///     Ok(cx.string(description))
+    /// }
+    /// ```
+    fn kind<'a, C: Context<'a>>(&self, cx: &mut C) -> ValueKind {
+        match unsafe { sys::tag::value_type(cx.env().to_raw(), self.to_raw()) } {
+            sys::ValueType::Undefined => ValueKind::Undefined,
+            sys::ValueType::Null => ValueKind::Null,
+            sys::ValueType::Boolean => ValueKind::Boolean,
+            sys::ValueType::Number => ValueKind::Number,
+            sys::ValueType::String => ValueKind::String,
+            sys::ValueType::Symbol => ValueKind::Symbol,
+            sys::ValueType::Object => ValueKind::Object,
+            sys::ValueType::Function => ValueKind::Function,
+            sys::ValueType::External => ValueKind::External,
+            sys::ValueType::BigInt => ValueKind::BigInt,
+        }
+    }
+
+    /// Compares this value to another with the JavaScript strict equality operator (`===`).
+    fn strict_equals<'a, C: Context<'a>, U: Value>(&self, cx: &mut C, other: Handle<U>) -> bool {
+        unsafe { sys::mem::strict_equals(cx.env().to_raw(), self.to_raw(), other.to_raw()) }
+    }
+
+    /// Compares this value to another with the
+    /// [SameValue](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Equality_comparisons_and_sameness#same-value_equality_using_object.is)
+    /// algorithm used by `Object.is`. Unlike `===`, `NaN` is equal to itself and
+    /// `+0` is not equal to `-0`.
+    fn same_value<'a, C: Context<'a>, U: Value>(&self, cx: &mut C, other: Handle<U>) -> bool {
+        match numbers(cx, self, &*other) {
+            Some((a, b)) => (a.is_nan() && b.is_nan()) || a.to_bits() == b.to_bits(),
+            None => self.strict_equals(cx, other),
+        }
+    }
+
+    /// Compares this value to another with the
+    /// [SameValueZero](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Equality_comparisons_and_sameness#same-value-zero_equality)
+    /// algorithm used by `Map` and `Set` keys. Unlike `===`, `NaN` is equal to itself.
+    fn same_value_zero<'a, C: Context<'a>, U: Value>(&self, cx: &mut C, other: Handle<U>) -> bool {
+        match numbers(cx, self, &*other) {
+            Some((a, b)) => (a.is_nan() && b.is_nan()) || a == b,
+            None => self.strict_equals(cx, other),
+        }
+    }
+}
+
+// Returns the values of `a` and `b` if both are numbers
+fn numbers<'a, C: Context<'a>, A: Value, B: Value>(cx: &mut C, a: &A, b: &B) -> Option<(f64, f64)> {
+    let env = cx.env().to_raw();
+
+    unsafe {
+        if sys::tag::is_number(env, a.to_raw()) && sys::tag::is_number(env, b.to_raw()) {
+            Some((
+                sys::primitive::number_value(env, a.to_raw()),
+                sys::primitive::number_value(env, b.to_raw()),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// The kind of a JavaScript value, as returned by [`Value::kind`].
+///
+/// The kinds correspond to the results of the JavaScript `typeof` operator, except
+/// that `null` is distinguished from objects and values created with
+/// [`JsBox`](crate::types::JsBox) are reported as `External`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    Undefined,
+    Null,
+    Boolean,
+    Number,
+    String,
+    Symbol,
+    Object,
+    Function,
+    BigInt,
+    External,
 }
 
 /// The type of any JavaScript value, i.e., the root of all types.
@@ -542,15 +716,7 @@ impl JsString {
     /// }
     /// ```
     pub fn value<'a, C: Context<'a>>(&self, cx: &mut C) -> String {
-        let env = cx.env().to_raw();
-
-        unsafe {
-            let capacity = sys::string::utf8_len(env, self.to_raw()) + 1;
-            let mut buffer: Vec<u8> = Vec::with_capacity(capacity);
-            let len = sys::string::data(env, buffer.as_mut_ptr(), capacity, self.to_raw());
-            buffer.set_len(len);
-            String::from_utf8_unchecked(buffer)
-        }
+        unsafe { string_value(cx.env().to_raw(), self.to_raw()) }
     }
 
     /// Convert this JavaScript string into a [`Vec<u16>`] encoded as UTF-16.
@@ -605,6 +771,53 @@ impl JsString {
         }
     }
 
+    /// Decodes the contents of this string as base64 with the standard alphabet,
+    /// equivalent to `Buffer.from(s, "base64")` for valid input.
+    ///
+    /// Padding is optional. Unlike `Buffer`, this method is strict: it throws a
+    /// `TypeError` naming the offset of the first character that is not part of the
+    /// base64 alphabet, including whitespace and URL-safe characters.
+    ///
+    /// **See also:** [`Context::string_base64`]
+    pub fn decode_base64<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Vec<u8>> {
+        let data = self.to_utf16(cx);
+
+        encoding::decode_base64(&data).or_else(|err| cx.throw_type_error(err.to_string()))
+    }
+
+    /// Decodes the contents of this string as hex, equivalent to `Buffer.from(s, "hex")`
+    /// for valid input.
+    ///
+    /// Both lowercase and uppercase digits are accepted. Throws a `TypeError` naming
+    /// the offset of the first character that is not a hex digit or, for strings of odd
+    /// length, the offset of the missing digit.
+    ///
+    /// **See also:** [`Context::string_hex`]
+    pub fn decode_hex<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Vec<u8>> {
+        let data = self.to_utf16(cx);
+
+        encoding::decode_hex(&data).or_else(|err| cx.throw_type_error(err.to_string()))
+    }
+
+    // Creates a string from ASCII bytes, which are passed to the engine as Latin-1
+    // to avoid UTF-8 validation.
+    pub(crate) fn from_ascii<'a, C: Context<'a>>(cx: &mut C, data: &[u8]) -> Handle<'a, JsString> {
+        debug_assert!(data.is_ascii());
+
+        let env = cx.env();
+
+        unsafe {
+            let mut local: raw::Local = std::mem::zeroed();
+
+            assert!(
+                sys::string::new_latin1(&mut local, env.to_raw(), data.as_ptr(), data.len()),
+                "string exceeds the maximum length of a JavaScript string",
+            );
+
+            Handle::new_internal(JsString(local))
+        }
+    }
+
     /// Creates a new `JsString` value from a Rust string by copying its contents.
     ///
     /// This method panics if the string is longer than the maximum string size allowed
@@ -752,6 +965,104 @@ impl private::ValueInternal for JsNumber {
     }
 }
 
+/// The type of JavaScript
+/// [symbol](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Symbol)
+/// primitives.
+///
+/// Symbols are unique values that are most often used as property keys that do not
+/// collide with string keys.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// # use neon::types::JsSymbol;
+/// # fn test(mut cx: FunctionContext) -> JsResult<JsValue> {
+/// let obj = cx.empty_object();
+///
+/// // Create a symbol and use it as a property key:
+/// let key = JsSymbol::with_description(&mut cx, "secret");
+/// let value = cx.number(42);
+///
+/// obj.set(&mut cx, key, value)?;
+/// obj.get_value(&mut cx, key)
+/// # }
+/// ```
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct JsSymbol(raw::Local);
+
+impl JsSymbol {
+    /// Creates a new symbol without a description, equivalent to `Symbol()`.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C) -> Handle<'a, JsSymbol> {
+        JsSymbol::new_internal(cx.env(), None)
+    }
+
+    /// Creates a new symbol with a description, equivalent to `Symbol(description)`.
+    pub fn with_description<'a, C: Context<'a>, S: AsRef<str>>(
+        cx: &mut C,
+        description: S,
+    ) -> Handle<'a, JsSymbol> {
+        let description = cx.string(description);
+
+        JsSymbol::new_internal(cx.env(), Some(description))
+    }
+
+    fn new_internal<'a>(env: Env, description: Option<Handle<JsString>>) -> Handle<'a, JsSymbol> {
+        let description = description.map_or(std::ptr::null_mut(), |s| s.to_raw());
+
+        unsafe {
+            let mut local: raw::Local = std::mem::zeroed();
+            assert!(sys::primitive::symbol(
+                &mut local,
+                env.to_raw(),
+                description
+            ));
+            Handle::new_internal(JsSymbol(local))
+        }
+    }
+
+    /// Returns the description of this symbol, or `None` if it was created without one.
+    pub fn description<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Option<String>> {
+        let description = self.coerce_to_object(cx)?.get_value(cx, "description")?;
+
+        match description.downcast::<JsString, _>(cx) {
+            Ok(s) => Ok(Some(s.value(cx))),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl Value for JsSymbol {}
+
+unsafe impl TransparentNoCopyWrapper for JsSymbol {
+    type Inner = raw::Local;
+
+    fn into_inner(self) -> Self::Inner {
+        self.0
+    }
+}
+
+impl Managed for JsSymbol {
+    fn to_raw(&self) -> raw::Local {
+        self.0
+    }
+
+    fn from_raw(_: Env, h: raw::Local) -> Self {
+        JsSymbol(h)
+    }
+}
+
+impl private::ValueInternal for JsSymbol {
+    fn name() -> String {
+        "symbol".to_string()
+    }
+
+    fn is_typeof<Other: Value>(env: Env, other: &Other) -> bool {
+        unsafe { sys::tag::is_symbol(env.to_raw(), other.to_raw()) }
+    }
+}
+
 /// The type of JavaScript
 /// [objects](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Data_structures#objects),
 /// i.e., the root of all object types.
@@ -909,6 +1220,347 @@ impl JsArray {
         }
     }
 
+    /// Appends the elements of the array at the indices in `range` to `out`.
+    ///
+    /// The length is read once, before any element, and `range` is clamped to it,
+    /// like [`Array.prototype.slice`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/slice).
+    /// Elements removed by shrinking the array while reading, e.g., from a getter, are
+    /// read as `undefined`.
+    ///
+    /// Unlike [`JsArray::to_vec`], this appends to an existing vector, which may be reused
+    /// across calls to avoid allocating. The elements escape into the scope of `cx`, so
+    /// reading very large arrays may be better done in chunks, each in a scope created
+    /// with [`Context::execute_scoped`].
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn first_three(mut cx: FunctionContext) -> JsResult<JsArray> {
+    ///     let array = cx.argument::<JsArray>(0)?;
+    ///     let mut values = Vec::new();
+    ///
+    ///     array.read_into(&mut cx, 0..3, &mut values)?;
+    ///
+    ///     JsArray::from_slice(&mut cx, &values)
+    /// }
+    /// ```
+    pub fn read_into<'a, C: Context<'a>, R: RangeBounds<u32>>(
+        &self,
+        cx: &mut C,
+        range: R,
+        out: &mut Vec<Handle<'a, JsValue>>,
+    ) -> NeonResult<()> {
+        let env = cx.env().to_raw();
+        let array = self.to_raw();
+        let (start, end) = clamp_range(range, self.len_inner(cx.env()));
+
+        out.reserve((end - start) as usize);
+
+        for i in start..end {
+            unsafe {
+                let mut local: raw::Local = std::mem::zeroed();
+
+                if !sys::object::get_index(&mut local, env, array, i) {
+                    return Err(Throw::new());
+                }
+
+                out.push(Handle::new_internal(JsValue(local)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the elements of the array starting at index `start` to `values`, in order,
+    /// extending the array if necessary.
+    ///
+    /// Large slices are written in chunks, each in its own handle scope.
+    pub fn write_from<'a, C: Context<'a>, V: Value>(
+        &self,
+        cx: &mut C,
+        start: u32,
+        values: &[Handle<V>],
+    ) -> NeonResult<()> {
+        let env = cx.env().to_raw();
+        let array = self.to_raw();
+
+        cx.chunked(values.len(), ARRAY_CHUNK_LEN, |_, range| {
+            for i in range {
+                let mut ok = false;
+
+                unsafe {
+                    sys::object::set_index(
+                        &mut ok,
+                        env,
+                        array,
+                        start + i as u32,
+                        values[i].to_raw(),
+                    );
+                }
+
+                if !ok {
+                    return Err(Throw::new());
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Copies the array contents into a new [`Vec`] of numbers, converting each element
+    /// as it is read.
+    ///
+    /// The length is read once. Throws a `TypeError` naming the index if an element is
+    /// not a number. Elements are never coerced, e.g., `"1"` is not a number.
+    ///
+    /// This is faster than calling [`JsArray::to_vec`] and converting each handle, because
+    /// each element is read with a single call into the engine, and no handles outlive
+    /// the chunk of elements being converted.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn mean(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    ///     let values = cx.argument::<JsArray>(0)?.to_vec_f64(&mut cx)?;
+    ///     let sum = values.iter().sum::<f64>();
+    ///
+    ///     Ok(cx.number(sum / values.len() as f64))
+    /// }
+    /// ```
+    pub fn to_vec_f64<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Vec<f64>> {
+        self.convert_elements::<_, _, JsNumber, _>(cx, |env, value| {
+            unsafe { sys::primitive::try_number_value(env, value) }.ok_or(ElementError::Type)
+        })
+    }
+
+    /// Copies the array contents into a new [`Vec`] of `i32`, converting each element
+    /// as it is read.
+    ///
+    /// The length is read once. Throws a `TypeError` naming the index if an element is
+    /// not a number, or a `RangeError` if it is a number that is not an integer in the
+    /// range of `i32`. Unlike the JavaScript `|0` operator, numbers are never truncated.
+    pub fn to_vec_i32<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Vec<i32>> {
+        self.convert_elements::<_, _, JsNumber, _>(cx, |env, value| {
+            let n = unsafe { sys::primitive::try_number_value(env, value) }
+                .ok_or(ElementError::Type)?;
+
+            if n.fract() != 0.0 || n < f64::from(i32::MIN) || n > f64::from(i32::MAX) {
+                return Err(ElementError::Range("i32", n));
+            }
+
+            Ok(n as i32)
+        })
+    }
+
+    /// Copies the array contents into a new [`Vec`] of `u32`, converting each element
+    /// as it is read.
+    ///
+    /// The length is read once. Throws a `TypeError` naming the index if an element is
+    /// not a number, or a `RangeError` if it is a number that is not an integer in the
+    /// range of `u32`. Unlike the JavaScript `>>>0` operator, numbers are never truncated.
+    pub fn to_vec_u32<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Vec<u32>> {
+        self.convert_elements::<_, _, JsNumber, _>(cx, |env, value| {
+            let n = unsafe { sys::primitive::try_number_value(env, value) }
+                .ok_or(ElementError::Type)?;
+
+            if n.fract() != 0.0 || n < 0.0 || n > f64::from(u32::MAX) {
+                return Err(ElementError::Range("u32", n));
+            }
+
+            Ok(n as u32)
+        })
+    }
+
+    /// Copies the array contents into a new [`Vec`] of booleans, converting each element
+    /// as it is read.
+    ///
+    /// The length is read once. Throws a `TypeError` naming the index if an element is
+    /// not a boolean. Elements are never coerced, e.g., `0` is not `false`.
+    pub fn to_vec_bool<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Vec<bool>> {
+        self.convert_elements::<_, _, JsBoolean, _>(cx, |env, value| {
+            unsafe { sys::primitive::try_boolean_value(env, value) }.ok_or(ElementError::Type)
+        })
+    }
+
+    /// Copies the array contents into a new [`Vec`] of strings, converting each element
+    /// as it is read.
+    ///
+    /// The length is read once. Throws a `TypeError` naming the index if an element is
+    /// not a string.
+    pub fn to_vec_string<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Vec<String>> {
+        self.convert_elements::<_, _, JsString, _>(cx, |env, value| {
+            if !unsafe { sys::tag::is_string(env, value) } {
+                return Err(ElementError::Type);
+            }
+
+            Ok(unsafe { string_value(env, value) })
+        })
+    }
+
+    // Reads and converts each element in chunks, so that no handles outlive the chunk
+    // being converted. `U` names the expected type in errors.
+    fn convert_elements<'a, C, T, U, F>(&self, cx: &mut C, mut convert: F) -> NeonResult<Vec<T>>
+    where
+        C: Context<'a>,
+        U: Value,
+        F: FnMut(raw::Env, raw::Local) -> Result<T, ElementError>,
+    {
+        let env = cx.env().to_raw();
+        let array = self.to_raw();
+        let len = self.len_inner(cx.env());
+        let mut result = Vec::with_capacity(len as usize);
+
+        cx.chunked(len as usize, ARRAY_CHUNK_LEN, |cx, range| {
+            for i in range {
+                let local = unsafe {
+                    let mut local: raw::Local = std::mem::zeroed();
+
+                    if !sys::object::get_index(&mut local, env, array, i as u32) {
+                        return Err(Throw::new());
+                    }
+
+                    local
+                };
+
+                match convert(env, local) {
+                    Ok(v) => result.push(v),
+                    Err(ElementError::Type) => {
+                        let value = Handle::new_internal(JsValue(local));
+                        let actual = crate::handle::describe_value(cx, value);
+
+                        return cx.throw_type_error(format!(
+                            "expected {} at index {}, got {}",
+                            U::name(),
+                            i,
+                            actual
+                        ));
+                    }
+                    Err(ElementError::Range(expected, n)) => {
+                        return cx.throw_range_error(format!(
+                            "expected {} at index {}, got {}",
+                            expected, i, n
+                        ));
+                    }
+                }
+            }
+
+            Ok(())
+        })?;
+
+        Ok(result)
+    }
+
+    /// Constructs a new array containing the numbers in `values`, in order.
+    ///
+    /// This is faster than creating a handle for each number and calling
+    /// [`JsArray::from_slice`], because the numbers are created and set in chunks, each
+    /// in its own handle scope.
+    ///
+    /// **See also:** [`Context::array_from_f64_slice`]
+    pub fn from_f64_slice<'a, C: Context<'a>>(cx: &mut C, values: &[f64]) -> JsResult<'a, JsArray> {
+        let env = cx.env().to_raw();
+        let array = JsArray::new(cx, values.len() as u32);
+        let raw_array = array.to_raw();
+
+        cx.chunked(values.len(), ARRAY_CHUNK_LEN, |_, range| {
+            for i in range {
+                let mut ok = false;
+
+                unsafe {
+                    let mut local: raw::Local = std::mem::zeroed();
+
+                    sys::primitive::number(&mut local, env, values[i]);
+                    sys::object::set_index(&mut ok, env, raw_array, i as u32, local);
+                }
+
+                if !ok {
+                    return Err(Throw::new());
+                }
+            }
+
+            Ok(())
+        })?;
+
+        Ok(array)
+    }
+
+    /// Constructs a new array containing the elements of `values`, in order.
+    pub fn from_slice<'a, C: Context<'a>, V: Value>(
+        cx: &mut C,
+        values: &[Handle<V>],
+    ) -> JsResult<'a, JsArray> {
+        let array = JsArray::new(cx, values.len() as u32);
+
+        // N-API may allocate temporary handles for each element that is set, so large
+        // arrays are filled in chunks, each in its own handle scope.
+        for (i, chunk) in values.chunks(ARRAY_CHUNK_LEN).enumerate() {
+            let start = (i * ARRAY_CHUNK_LEN) as u32;
+
+            cx.execute_scoped(|mut cx| {
+                for (j, value) in chunk.iter().enumerate() {
+                    array.set(&mut cx, start + j as u32, *value)?;
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(array)
+    }
+
+    /// Appends `value` to the end of the array, equivalent to the JavaScript expression
+    /// `this.push(value)`.
+    pub fn push<'a, C: Context<'a>, V: Value>(
+        &self,
+        cx: &mut C,
+        value: Handle<V>,
+    ) -> NeonResult<()> {
+        let len = self.len_inner(cx.env());
+
+        self.set(cx, len, value)?;
+
+        Ok(())
+    }
+
+    /// Returns an iterator over the elements of the array.
+    ///
+    /// Like [`Array.prototype.forEach`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/forEach),
+    /// the length is read once, when the iterator is created. Elements appended to
+    /// the array during iteration are not visited, and elements changed ahead of the
+    /// iterator are visited with their new value. Unlike `forEach`, holes are not skipped:
+    /// missing elements, including those removed by shrinking the array during
+    /// iteration, are yielded as `undefined`.
+    ///
+    /// Since getting an element can run arbitrary code (e.g., a getter), each element is
+    /// yielded as a `JsResult`. The iterator borrows the context for its lifetime; use
+    /// [`ArrayIter::cx`] to access it while iterating.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn count_strings(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    ///     let array = cx.argument::<JsArray>(0)?;
+    ///     let mut iter = array.iter(&mut cx);
+    ///     let mut count = 0;
+    ///
+    ///     while let Some(value) = iter.next() {
+    ///         if value?.is_a::<JsString, _>(iter.cx()) {
+    ///             count += 1;
+    ///         }
+    ///     }
+    ///
+    ///     Ok(cx.number(count))
+    /// }
+    /// ```
+    pub fn iter<'a, 'cx, C: Context<'a>>(&self, cx: &'cx mut C) -> ArrayIter<'a, 'cx, C> {
+        let len = self.len_inner(cx.env());
+
+        ArrayIter {
+            array: self.0,
+            cx,
+            index: 0,
+            len,
+            _lifetime: PhantomData,
+        }
+    }
+
     fn len_inner(&self, env: Env) -> u32 {
         unsafe { sys::array::len(env.to_raw(), self.to_raw()) }
     }
@@ -929,6 +1581,89 @@ impl JsArray {
 
 impl Value for JsArray {}
 
+/// Number of elements set per handle scope when filling a large array.
+const ARRAY_CHUNK_LEN: usize = 1024;
+
+// Why an element of an array could not be converted by `JsArray::convert_elements`
+enum ElementError {
+    // The element is not of the expected type
+    Type,
+    // The element is a number outside the range of the named type
+    Range(&'static str, f64),
+}
+
+// Safety: `local` must be a string
+unsafe fn string_value(env: raw::Env, local: raw::Local) -> String {
+    let capacity = sys::string::utf8_len(env, local) + 1;
+    let mut buffer: Vec<u8> = Vec::with_capacity(capacity);
+    let len = sys::string::data(env, buffer.as_mut_ptr(), capacity, local);
+    buffer.set_len(len);
+    String::from_utf8_unchecked(buffer)
+}
+
+// Resolves `range` to `start..end`, clamped to `len`
+fn clamp_range<R: RangeBounds<u32>>(range: R, len: u32) -> (u32, u32) {
+    let start = match range.start_bound() {
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(&i) => i.saturating_add(1),
+        Bound::Excluded(&i) => i,
+        Bound::Unbounded => len,
+    };
+
+    let end = end.min(len);
+
+    (start.min(end), end)
+}
+
+/// An iterator over the elements of a [`JsArray`].
+///
+/// This struct is created by [`JsArray::iter`].
+pub struct ArrayIter<'a, 'cx, C: Context<'a>> {
+    array: raw::Local,
+    cx: &'cx mut C,
+    index: u32,
+    len: u32,
+    _lifetime: PhantomData<&'a ()>,
+}
+
+impl<'a, 'cx, C: Context<'a>> ArrayIter<'a, 'cx, C> {
+    /// Returns the context borrowed by this iterator, e.g., to create values for
+    /// the current element without ending the iteration.
+    pub fn cx(&mut self) -> &mut C {
+        self.cx
+    }
+}
+
+impl<'a, 'cx, C: Context<'a>> Iterator for ArrayIter<'a, 'cx, C> {
+    type Item = JsResult<'a, JsValue>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+
+        let array = Handle::new_internal(JsArray(self.array));
+        let value = array.get(self.cx, self.index);
+
+        self.index += 1;
+
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.len - self.index) as usize;
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, 'cx, C: Context<'a>> ExactSizeIterator for ArrayIter<'a, 'cx, C> {}
+
 unsafe impl TransparentNoCopyWrapper for JsArray {
     type Inner = raw::Local;
 
@@ -1044,19 +1779,16 @@ impl<T: Object> Object for JsFunction<T> {}
 // Maximum number of function arguments in V8.
 const V8_ARGC_LIMIT: usize = 65535;
 
-unsafe fn prepare_call<'a, 'b, C: Context<'a>>(
+pub(crate) unsafe fn prepare_call<'a, 'b, C: Context<'a>>(
     cx: &mut C,
     args: &[Handle<'b, JsValue>],
 ) -> NeonResult<(i32, *const c_void)> {
-    // Note: This cast is only save because `Handle<'_, JsValue>` is
-    // guaranteed to have the same layout as a pointer because `Handle`
-    // and `JsValue` are both `repr(C)` newtypes.
-    let argv = args.as_ptr().cast();
-    let argc = args.len();
+    let argv = function::private::raw_args(args);
+    let argc = argv.len();
     if argc > V8_ARGC_LIMIT {
         return cx.throw_range_error("too many arguments");
     }
-    Ok((argc as i32, argv))
+    Ok((argc as i32, argv.as_ptr().cast()))
 }
 
 impl JsFunction {
@@ -1069,7 +1801,7 @@ impl JsFunction {
         C: Context<'a>,
         U: Value,
     {
-        Self::new_internal(cx, f)
+        Self::new_internal(cx, FunctionOptions::default(), f)
     }
 
     #[cfg(feature = "napi-5")]
@@ -1080,10 +1812,83 @@ impl JsFunction {
         F: Fn(FunctionContext) -> JsResult<V> + 'static,
         V: Value,
     {
-        Self::new_internal(cx, f)
+        Self::new_internal(cx, FunctionOptions::default(), f)
     }
 
-    fn new_internal<'a, C, F, V>(cx: &mut C, f: F) -> JsResult<'a, JsFunction>
+    #[cfg(not(feature = "napi-5"))]
+    /// Returns a new `JsFunction` implemented by `f`, with the `name` and `length`
+    /// given by `options`.
+    pub fn with_options<'a, C, U>(
+        cx: &mut C,
+        options: FunctionOptions,
+        f: fn(FunctionContext) -> JsResult<U>,
+    ) -> JsResult<'a, JsFunction>
+    where
+        C: Context<'a>,
+        U: Value,
+    {
+        Self::new_internal(cx, options, f)
+    }
+
+    #[cfg(feature = "napi-5")]
+    /// Returns a new `JsFunction` implemented by `f`, with the `name` and `length`
+    /// given by `options`.
+    pub fn with_options<'a, C, F, V>(
+        cx: &mut C,
+        options: FunctionOptions,
+        f: F,
+    ) -> JsResult<'a, JsFunction>
+    where
+        C: Context<'a>,
+        F: Fn(FunctionContext) -> JsResult<V> + 'static,
+        V: Value,
+    {
+        Self::new_internal(cx, options, f)
+    }
+
+    #[cfg(feature = "napi-5")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+    /// Returns a new `JsFunction` implemented by a closure that mutates its captured state.
+    ///
+    /// The closure is dropped when the function is garbage collected. Since `f` requires
+    /// exclusive access to its state, calling the function again while it is already
+    /// running (e.g., from a JavaScript callback invoked by `f`) throws an `Error` instead
+    /// of re-entering the closure.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn counter(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    ///     let mut n = 0;
+    ///
+    ///     JsFunction::new_mut(&mut cx, move |mut cx| {
+    ///         n += 1;
+    ///         Ok(cx.number(n))
+    ///     })
+    /// }
+    /// ```
+    pub fn new_mut<'a, C, F, V>(cx: &mut C, f: F) -> JsResult<'a, JsFunction>
+    where
+        C: Context<'a>,
+        F: FnMut(FunctionContext) -> JsResult<V> + 'static,
+        V: Value,
+    {
+        use std::cell::RefCell;
+
+        let f = RefCell::new(f);
+
+        Self::new_internal(cx, FunctionOptions::default(), move |mut cx| {
+            match f.try_borrow_mut() {
+                Ok(mut f) => f(cx).map(|v| v.upcast::<JsValue>()),
+                Err(_) => cx.throw_error("function cannot be called while it is already running"),
+            }
+        })
+    }
+
+    fn new_internal<'a, C, F, V>(
+        cx: &mut C,
+        options: FunctionOptions,
+        f: F,
+    ) -> JsResult<'a, JsFunction>
     where
         C: Context<'a>,
         F: Fn(FunctionContext) -> JsResult<V> + 'static,
@@ -1096,12 +1901,17 @@ impl JsFunction {
         use crate::context::CallbackInfo;
         use crate::types::error::convert_panics;
 
-        let name = any::type_name::<F>();
+        let name = match options.name {
+            Some(name) => name,
+            None => any::type_name::<F>(),
+        };
+        // Only an explicit name is used in error messages; the type name is not helpful
+        let function_name: Option<Box<str>> = options.name.map(Into::into);
         let f = move |env: raw::Env, info| {
             let env = env.into();
             let info = unsafe { CallbackInfo::new(info) };
 
-            FunctionContext::with(env, &info, |cx| {
+            FunctionContext::with(env, &info, function_name.as_deref(), |cx| {
                 convert_panics(env, AssertUnwindSafe(|| f(cx)))
                     .map(|v| v.to_raw())
                     // We do not have a Js Value to return, most likely due to an exception.
@@ -1113,21 +1923,48 @@ impl JsFunction {
             })
         };
 
-        if let Ok(raw) = unsafe { sys::fun::new(cx.env().to_raw(), name, f) } {
-            Ok(Handle::new_internal(JsFunction {
-                raw,
-                marker: PhantomData,
-            }))
-        } else {
-            Err(Throw::new())
+        let env = cx.env().to_raw();
+        let raw = unsafe { sys::fun::new(env, name, f) }.map_err(|_| Throw::new())?;
+
+        if let Some(length) = options.length {
+            let attributes = sys::PropertyAttributes::CONFIGURABLE;
+            let key = cx.string("length");
+            let length = cx.number(length);
+            let defined = unsafe {
+                sys::object::define_value(env, raw, key.to_raw(), length.to_raw(), attributes)
+            };
+
+            if !defined {
+                return Err(Throw::new());
+            }
         }
+
+        Ok(Handle::new_internal(JsFunction {
+            raw,
+            marker: PhantomData,
+        }))
     }
 }
 
 impl<CL: Object> JsFunction<CL> {
+    /// Returns the value of the function's `name` property, or an empty string if
+    /// the property is not a string.
+    pub fn name<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        let name = self.get_value(cx, "name")?;
+
+        Ok(name
+            .downcast::<JsString, _>(cx)
+            .map(|name| name.value(cx))
+            .unwrap_or_default())
+    }
+
     /// Calls this function.
     ///
+    /// The arguments may be a slice or `Vec` of handles, or a tuple of handles and Rust
+    /// values that are converted to JavaScript. See [`TryIntoArguments`].
+    ///
     /// **See also:** [`JsFunction::call_with`].
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     pub fn call<'a, 'b, C: Context<'a>, T, AS>(
         &self,
         cx: &mut C,
@@ -1136,18 +1973,94 @@ impl<CL: Object> JsFunction<CL> {
     ) -> JsResult<'a, JsValue>
     where
         T: Value,
-        AS: AsRef<[Handle<'b, JsValue>]>,
+        AS: TryIntoArguments<'a>,
     {
-        let (argc, argv) = unsafe { prepare_call(cx, args.as_ref()) }?;
+        let args = args.try_into_args(cx)?;
+        let (argc, argv) = unsafe { prepare_call(cx, args.as_args()) }?;
         let env = cx.env().to_raw();
+
+        buffer::debug::assert_no_borrows(env, std::panic::Location::caller());
+
         build(cx.env(), |out| unsafe {
             sys::fun::call(out, env, self.to_raw(), this.to_raw(), argc, argv)
         })
     }
 
+    /// Calls this function with no arguments and an `undefined` `this`-binding,
+    /// equivalent to `f()` in JavaScript.
+    ///
+    /// This is a fast path for notification callbacks; it avoids creating a handle
+    /// for `this` and converting an argument list.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// fn notify(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let callback = cx.argument::<JsFunction>(0)?;
+    ///
+    ///     callback.call0(&mut cx)?;
+    ///
+    ///     Ok(cx.undefined())
+    /// }
+    /// ```
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
+    pub fn call0<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsValue> {
+        self.call_undefined_this(cx, 0, ptr::null())
+    }
+
+    /// Calls this function with a single argument and an `undefined` `this`-binding,
+    /// equivalent to `f(arg)` in JavaScript.
+    ///
+    /// **See also:** [`JsFunction::call0`].
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
+    pub fn call1<'a, 'b, C: Context<'a>, V: Value>(
+        &self,
+        cx: &mut C,
+        arg: Handle<'b, V>,
+    ) -> JsResult<'a, JsValue> {
+        let arg = arg.to_raw();
+
+        self.call_undefined_this(cx, 1, (&arg as *const raw::Local).cast())
+    }
+
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
+    fn call_undefined_this<'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        argc: i32,
+        argv: *const c_void,
+    ) -> JsResult<'a, JsValue> {
+        let env = cx.env().to_raw();
+
+        buffer::debug::assert_no_borrows(env, std::panic::Location::caller());
+
+        build(cx.env(), |out| unsafe {
+            let mut this = ptr::null_mut();
+
+            sys::primitive::undefined(&mut this, env);
+            sys::fun::call(out, env, self.to_raw(), this, argc, argv)
+        })
+    }
+
+    /// Calls this function with arguments captured by
+    /// [`FunctionContext::forward_args`], passing them on unchanged. Any number of
+    /// arguments is supported.
+    ///
+    /// The `this`-binding is given explicitly so that the call may be redirected; use
+    /// [`ForwardedArgs::this`](function::ForwardedArgs::this) to keep the original.
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
+    pub fn call_with_forwarded<'a, 'b, C: Context<'a>, T: Value>(
+        &self,
+        cx: &mut C,
+        this: Handle<'b, T>,
+        forwarded: function::ForwardedArgs<'b>,
+    ) -> JsResult<'a, JsValue> {
+        self.call(cx, this, forwarded.as_slice())
+    }
+
     /// Calls this function for side effect, discarding its result.
     ///
     /// **See also:** [`JsFunction::call_with`].
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
     pub fn exec<'a, 'b, C: Context<'a>, T, AS>(
         &self,
         cx: &mut C,
@@ -1156,21 +2069,30 @@ impl<CL: Object> JsFunction<CL> {
     ) -> NeonResult<()>
     where
         T: Value,
-        AS: AsRef<[Handle<'b, JsValue>]>,
+        AS: TryIntoArguments<'a>,
     {
         self.call(cx, this, args)?;
         Ok(())
     }
 
-    /// Calls this function as a constructor.
+    /// Calls this function as a constructor, equivalent to `new f(...args)` in JavaScript.
+    ///
+    /// If the constructor throws, the exception is propagated as a [`Throw`]. As in
+    /// JavaScript, if the constructor returns an object, that object is the result;
+    /// any other return value is ignored in favor of the newly constructed `this`.
     ///
     /// **See also:** [`JsFunction::construct_with`].
-    pub fn construct<'a, 'b, C: Context<'a>, AS>(&self, cx: &mut C, args: AS) -> JsResult<'a, CL>
+    #[cfg_attr(all(feature = "debug-borrows", debug_assertions), track_caller)]
+    pub fn construct<'a, C: Context<'a>, AS>(&self, cx: &mut C, args: AS) -> JsResult<'a, CL>
     where
-        AS: AsRef<[Handle<'b, JsValue>]>,
+        AS: TryIntoArguments<'a>,
     {
-        let (argc, argv) = unsafe { prepare_call(cx, args.as_ref()) }?;
+        let args = args.try_into_args(cx)?;
+        let (argc, argv) = unsafe { prepare_call(cx, args.as_args()) }?;
         let env = cx.env().to_raw();
+
+        buffer::debug::assert_no_borrows(env, std::panic::Location::caller());
+
         build(cx.env(), |out| unsafe {
             sys::fun::construct(out, env, self.to_raw(), argc, argv)
         })
@@ -1204,6 +2126,37 @@ impl JsFunction {
         }
     }
 
+    /// Creates a new function with `this` and the leading arguments bound to the given
+    /// values, equivalent to
+    /// [`Function.prototype.bind`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Function/bind)
+    /// in JavaScript.
+    ///
+    /// Bound functions are useful for storing a callback with its arguments in a
+    /// [`Root`](crate::handle::Root).
+    pub fn bind<'a, 'b, C: Context<'a>, T, AS>(
+        &self,
+        cx: &mut C,
+        this: Handle<'b, T>,
+        args: AS,
+    ) -> JsResult<'a, JsFunction>
+    where
+        T: Value,
+        AS: TryIntoArguments<'a>,
+    {
+        let args = args.try_into_args(cx)?;
+        let bind = crate::intrinsics::function_bind(cx)?;
+        let mut bind_args: function::private::ArgsVec = smallvec![this.upcast()];
+
+        bind_args.extend_from_slice(args.as_args());
+
+        bind.call(
+            cx,
+            Handle::new_internal(unsafe { self.clone() }),
+            &bind_args[..],
+        )?
+        .downcast_or_throw(cx)
+    }
+
     /// # Safety
     /// The caller must wrap in a `Handle` with an appropriate lifetime.
     unsafe fn clone(&self) -> Self {
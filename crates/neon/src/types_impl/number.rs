@@ -0,0 +1,142 @@
+use std::{error, fmt};
+
+use crate::{
+    context::Context,
+    handle::Handle,
+    result::{JsResult, ResultExt},
+    types::JsNumber,
+};
+
+/// Indicates that a Rust number cannot be represented exactly by a JavaScript
+/// number, i.e., an IEEE 754 double.
+///
+/// Integers with a magnitude greater than `2^53` are exactly representable only if
+/// they are a multiple of a large enough power of two.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PrecisionLoss<T>(T);
+
+impl<T> PrecisionLoss<T> {
+    /// Get the value that could not be converted.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for PrecisionLoss<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} cannot be represented exactly as a JavaScript number",
+            self.0
+        )
+    }
+}
+
+impl<T: fmt::Display + fmt::Debug> error::Error for PrecisionLoss<T> {}
+
+/// The result of constructing a new `JsNumber` with [`Context::number_checked`].
+pub type NumberResult<'a, T> = Result<Handle<'a, JsNumber>, PrecisionLoss<T>>;
+
+impl<'a, T: fmt::Display> ResultExt<Handle<'a, JsNumber>> for NumberResult<'a, T> {
+    fn or_throw<'b, C: Context<'b>>(self, cx: &mut C) -> JsResult<'a, JsNumber> {
+        self.or_else(|err| cx.throw_range_error(err.to_string()))
+    }
+}
+
+/// Lossless conversion of a Rust number to an `f64`.
+///
+/// Unlike `as f64`, conversions fail instead of rounding when the value can't be
+/// represented exactly.
+///
+/// ```
+/// use neon::types::TryIntoF64;
+///
+/// assert_eq!(9_007_199_254_740_992u64.try_into_f64(), Ok(9_007_199_254_740_992.0));
+/// assert!(9_007_199_254_740_993u64.try_into_f64().is_err());
+/// ```
+pub trait TryIntoF64: Sized {
+    /// Convert `self` to an `f64`, failing if precision would be lost.
+    fn try_into_f64(self) -> Result<f64, PrecisionLoss<Self>>;
+}
+
+macro_rules! impl_lossless {
+    ($($ty:ty),*) => {
+        $(
+            impl TryIntoF64 for $ty {
+                fn try_into_f64(self) -> Result<f64, PrecisionLoss<Self>> {
+                    Ok(self.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_lossless!(f64, f32, i32, u32, i16, u16, i8, u8);
+
+macro_rules! impl_checked {
+    ($($ty:ty),*) => {
+        $(
+            impl TryIntoF64 for $ty {
+                fn try_into_f64(self) -> Result<f64, PrecisionLoss<Self>> {
+                    let n = self as f64;
+
+                    // `MAX` is not representable and rounds up to a power of two, which
+                    // saturates to `MAX` when cast back
+                    if n != <$ty>::MAX as f64 && n as $ty == self {
+                        Ok(n)
+                    } else {
+                        Err(PrecisionLoss(self))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_checked!(i64, u64, i128, u128);
+
+// Converted through the 64-bit type, since `MAX` may be representable on 32-bit
+// targets
+impl TryIntoF64 for isize {
+    fn try_into_f64(self) -> Result<f64, PrecisionLoss<Self>> {
+        (self as i64)
+            .try_into_f64()
+            .map_err(|_| PrecisionLoss(self))
+    }
+}
+
+impl TryIntoF64 for usize {
+    fn try_into_f64(self) -> Result<f64, PrecisionLoss<Self>> {
+        (self as u64)
+            .try_into_f64()
+            .map_err(|_| PrecisionLoss(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TryIntoF64;
+
+    #[test]
+    fn test_exact_values() {
+        assert_eq!(0u64.try_into_f64(), Ok(0.0));
+        assert_eq!(i64::MIN.try_into_f64(), Ok(-9_223_372_036_854_775_808.0));
+        assert_eq!((1u64 << 53).try_into_f64(), Ok(9_007_199_254_740_992.0));
+        assert_eq!((1u64 << 63).try_into_f64(), Ok(9_223_372_036_854_775_808.0));
+        assert_eq!(i128::MIN.try_into_f64(), Ok(-(2f64.powi(127))));
+        assert_eq!(((1usize << 31) + 1).try_into_f64(), Ok(2_147_483_649.0));
+    }
+
+    #[test]
+    fn test_precision_loss() {
+        assert_eq!(
+            ((1u64 << 53) + 1).try_into_f64().unwrap_err().into_inner(),
+            (1 << 53) + 1
+        );
+        assert!((-(1i64 << 53) - 1).try_into_f64().is_err());
+        assert!(u64::MAX.try_into_f64().is_err());
+        assert!(i64::MAX.try_into_f64().is_err());
+        assert!(u128::MAX.try_into_f64().is_err());
+        assert!(i128::MAX.try_into_f64().is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//! Recovering Rust panic payloads from JavaScript errors.
+//!
+//! When Neon catches a Rust panic at the boundary with JavaScript, e.g., in a
+//! [`Channel`](crate::event::Channel) callback, a [task](crate::event::TaskBuilder)
+//! or a promise settlement, it is converted to a JavaScript `Error`. The error is
+//! thrown as an uncaught exception or, if the panic occurred while settling a
+//! promise, used to reject the promise.
+//!
+//! The shape of the error is a stable contract:
+//!
+//! * `message` indicates that a panic occurred.
+//! * `panic` is an `Error` describing the panic. If the payload was a `&str` or
+//!   `String`, e.g., from `panic!("...")`, it is the message of this error. Otherwise,
+//!   the message is `"Unknown panic"` and the payload is stored in its `cause`.
+//! * `cause` is the JavaScript exception, if one was also thrown.
+//!
+//! A payload stored in `panic.cause` is an opaque value compatible with
+//! [`JsBox`]: it may be downcast to `JsBox<T>`, where `T` is the type passed to
+//! [`std::panic::panic_any`]. [`downcast_panic`] searches the error for a payload
+//! of a given type.
+//!
+//! ```
+//! # use neon::prelude::*;
+//! use neon::types::panic::downcast_panic;
+//!
+//! #[derive(Debug)]
+//! enum AppError {
+//!     Corrupted(String),
+//! }
+//!
+//! // Called from JavaScript with the rejection reason of a promise
+//! fn describe_failure(mut cx: FunctionContext) -> JsResult<JsString> {
+//!     let err = cx.argument::<JsValue>(0)?;
+//!
+//!     let description = match downcast_panic::<AppError, _, _>(&mut cx, err) {
+//!         Some(AppError::Corrupted(file)) => format!("{} is corrupted", file),
+//!         None => "unknown failure".to_string(),
+//!     };
+//!
+//!     Ok(cx.string(description))
+//! }
+//! ```
+
+use crate::{
+    context::Context,
+    handle::Handle,
+    object::Object,
+    types::{JsBox, JsObject, Value},
+};
+
+// Limits how far `downcast_panic` follows `panic` and `cause`, which may be cyclic
+const MAX_DEPTH: usize = 16;
+
+/// Searches `value` for a panic payload of type `T`, returning a reference to it if
+/// found.
+///
+/// `value` may be the payload itself, an error created from a panic, or any error
+/// whose chain of `panic` and `cause` properties leads to one. For example, if the
+/// rejection reason of a promise is wrapped with `new Error("...", { cause: err })`,
+/// the payload is still found.
+///
+/// Returns `None` if no payload of type `T` is found. Exceptions thrown while reading
+/// properties, e.g., by a getter, are caught and treated as a missing property.
+///
+/// See the [module documentation](self) for the shape of panic errors.
+pub fn downcast_panic<'cx, T, C, V>(cx: &mut C, value: Handle<'cx, V>) -> Option<&'cx T>
+where
+    T: Send + 'static,
+    C: Context<'cx>,
+    V: Value,
+{
+    find(cx, value, MAX_DEPTH)
+}
+
+fn find<'cx, T, C, V>(cx: &mut C, value: Handle<'cx, V>, depth: usize) -> Option<&'cx T>
+where
+    T: Send + 'static,
+    C: Context<'cx>,
+    V: Value,
+{
+    if let Ok(payload) = value.downcast::<JsBox<T>, _>(cx) {
+        // Safety: The data is owned by the `JsBox`, which is kept alive by the handle
+        // for the lifetime `'cx`
+        return Some(unsafe { &*(&**payload as *const T) });
+    }
+
+    if depth == 0 {
+        return None;
+    }
+
+    let obj = value.downcast::<JsObject, _>(cx).ok()?;
+
+    ["panic", "cause"].iter().find_map(|key| {
+        let next = cx.try_catch(|cx| obj.get_value(cx, *key)).ok()?;
+
+        find(cx, next, depth - 1)
+    })
+}
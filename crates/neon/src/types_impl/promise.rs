@@ -1,4 +1,4 @@
-use std::ptr;
+use std::{ffi::c_void, ptr};
 
 use crate::{
     context::{internal::Env, Context},
@@ -16,10 +16,7 @@ use crate::{
 };
 
 #[cfg(feature = "napi-6")]
-use crate::{
-    lifecycle::{DropData, InstanceData},
-    sys::tsfn::ThreadsafeFunction,
-};
+use crate::lifecycle::{DropData, DropQueue, InstanceData};
 
 #[cfg(all(feature = "napi-5", feature = "futures"))]
 use {
@@ -37,10 +34,10 @@ use {
 #[cfg(any(feature = "napi-6", all(feature = "napi-5", feature = "futures")))]
 use std::sync::Arc;
 
-const BOUNDARY: FailureBoundary = FailureBoundary {
-    both: "A panic and exception occurred while resolving a `neon::types::Deferred`",
-    exception: "An exception occurred while resolving a `neon::types::Deferred`",
-    panic: "A panic occurred while resolving a `neon::types::Deferred`",
+#[cfg(feature = "napi-4")]
+const BOUNDARY: FailureBoundary<'static> = FailureBoundary {
+    action: "resolving a `neon::types::Deferred`",
+    detail: None,
 };
 
 #[derive(Debug)]
@@ -287,7 +284,7 @@ impl Object for JsPromise {}
 pub struct Deferred {
     internal: Option<NodeApiDeferred>,
     #[cfg(feature = "napi-6")]
-    drop_queue: Arc<ThreadsafeFunction<DropData>>,
+    drop_queue: Arc<DropQueue>,
 }
 
 impl Deferred {
@@ -342,7 +339,11 @@ impl Deferred {
     /// Settle the [`JsPromise`] by sending a closure across a [`Channel`][crate::event::Channel]
     /// to be executed on the main JavaScript thread.
     ///
-    /// Panics if there is a libuv error.
+    /// The closure is ordered with other closures sent on the same channel; see
+    /// [`Channel`][crate::event::Channel#ordering].
+    ///
+    /// Like [`Channel::send`][crate::event::Channel::send], the closure and the
+    /// `Deferred` are dropped if the environment is shutting down.
     ///
     /// ```
     /// # use neon::prelude::*;
@@ -360,17 +361,80 @@ impl Deferred {
         V: Value,
         F: FnOnce(TaskContext) -> JsResult<V> + Send + 'static,
     {
-        self.try_settle_with(channel, complete).unwrap()
+        channel.send(move |cx| {
+            self.try_catch_settle(cx, complete);
+            Ok(())
+        })
+    }
+
+    #[cfg(feature = "napi-4")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-4")))]
+    /// Settle the [`JsPromise`] immediately if called on the JavaScript thread of
+    /// the [`Channel`][crate::event::Channel], without waiting for a turn of the
+    /// event loop.
+    ///
+    /// The promise is only settled immediately if no closures are queued on the
+    /// channel, so ordering with closures sent before is preserved, and no
+    /// JavaScript exception is pending. Otherwise, the `Deferred` and `complete`
+    /// are returned so that the promise may be settled later, e.g., with
+    /// [`Deferred::settle_with`].
+    ///
+    /// This is useful for code that holds a `Channel` instead of a context and
+    /// usually completes on another thread, but can sometimes complete immediately,
+    /// e.g., with a cached value.
+    ///
+    /// ```
+    /// # use neon::prelude::*;
+    /// # use neon::types::Deferred;
+    /// fn complete(channel: &Channel, deferred: Deferred, n: f64) {
+    ///     let settled = deferred.try_settle_now(channel, move |mut cx| Ok(cx.number(n)));
+    ///
+    ///     if let Err((deferred, complete)) = settled {
+    ///         deferred.settle_with(channel, complete);
+    ///     }
+    /// }
+    /// ```
+    pub fn try_settle_now<V, F>(self, channel: &Channel, complete: F) -> Result<(), (Self, F)>
+    where
+        V: Value,
+        F: FnOnce(TaskContext) -> JsResult<V>,
+    {
+        let env = match channel.idle_env() {
+            Some(env) => env,
+            None => return Err((self, complete)),
+        };
+
+        // The caller may not be in a `HandleScope`, e.g., in a libuv callback
+        let _scope = unsafe { sys::scope::HandleScope::new(env.to_raw()) };
+
+        TaskContext::with_context(env, move |cx| self.try_catch_settle(cx, complete));
+
+        Ok(())
     }
 
+    #[cfg(feature = "napi-4")]
     pub(crate) fn try_catch_settle<'a, C, V, F>(self, cx: C, f: F)
     where
         C: Context<'a>,
         V: Value,
         F: FnOnce(C) -> JsResult<'a, V>,
+    {
+        self.try_catch_settle_within(&BOUNDARY, cx, f)
+    }
+
+    // Like `try_catch_settle`, with failure messages from `boundary`
+    pub(crate) fn try_catch_settle_within<'a, C, V, F>(
+        self,
+        boundary: &FailureBoundary,
+        cx: C,
+        f: F,
+    ) where
+        C: Context<'a>,
+        V: Value,
+        F: FnOnce(C) -> JsResult<'a, V>,
     {
         unsafe {
-            BOUNDARY.catch_failure(
+            boundary.catch_failure(
                 cx.env().to_raw(),
                 Some(self.into_inner()),
                 move |_| match f(cx) {
@@ -381,6 +445,40 @@ impl Deferred {
         }
     }
 
+    /// Consumes the `Deferred`, returning the underlying `napi_deferred` for use with
+    /// another Node-API library.
+    ///
+    /// The caller is responsible for settling the promise, e.g., with
+    /// `napi_resolve_deferred`, or for converting it back with [`Deferred::from_raw`].
+    /// The returned `napi_deferred` is not tracked by Neon; if it is never settled,
+    /// the promise remains pending and is not rejected by the global drop queue.
+    pub fn into_raw(self) -> *mut c_void {
+        self.into_inner().cast()
+    }
+
+    /// Creates a `Deferred` from a `napi_deferred` that was returned by
+    /// [`Deferred::into_raw`] or created by another Node-API library.
+    ///
+    /// The `Deferred` takes ownership of the `napi_deferred` and behaves as if it was
+    /// created with [`Context::promise`], including being rejected if it is dropped
+    /// without being settled.
+    ///
+    /// # Safety
+    ///
+    /// * `raw` must be a valid `napi_deferred` created in the same JavaScript
+    ///   environment as `cx` that has not been settled.
+    /// * Ownership is transferred; the `napi_deferred` must not be settled elsewhere.
+    ///   For example, calling `from_raw` twice with a pointer from a single call to
+    ///   `into_raw` settles the promise twice, which is undefined behavior.
+    #[cfg_attr(not(feature = "napi-6"), allow(unused_variables))]
+    pub unsafe fn from_raw<'a, C: Context<'a>>(cx: &mut C, raw: *mut c_void) -> Self {
+        Deferred {
+            internal: Some(NodeApiDeferred(raw.cast())),
+            #[cfg(feature = "napi-6")]
+            drop_queue: InstanceData::drop_queue(cx),
+        }
+    }
+
     pub(crate) fn into_inner(mut self) -> sys::Deferred {
         self.internal.take().unwrap().0
     }
@@ -427,7 +525,7 @@ impl Drop for Deferred {
     fn drop(&mut self) {
         // If `None`, the `Deferred` has already been settled
         if let Some(internal) = self.internal.take() {
-            let _ = self.drop_queue.call(DropData::Deferred(internal), None);
+            self.drop_queue.push(DropData::Deferred(internal));
         }
     }
 }
@@ -0,0 +1,337 @@
+//! Types for creating JavaScript
+//! [`Proxy`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy)
+//! objects with traps implemented in Rust.
+//!
+//! [`ArrayView`] builds on proxies to expose a Rust `Vec` to JavaScript as an
+//! array-like, without copying the elements.
+
+use crate::{
+    context::{Context, FunctionContext},
+    handle::Handle,
+    object::Object,
+    result::{JsResult, NeonResult},
+    types::{
+        extract::TryIntoJs, function::FunctionOptions, JsArray, JsBox, JsFunction, JsObject,
+        JsString, JsValue,
+    },
+};
+
+/// A proxy trap implemented in Rust.
+///
+/// A trap is called with the same arguments as the corresponding JavaScript
+/// [handler method](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy/Proxy#handler_functions),
+/// and with the handler object as `this`.
+pub type ProxyTrap = Box<dyn Fn(FunctionContext) -> JsResult<JsValue> + 'static>;
+
+/// The traps of a [`Proxy`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy),
+/// used with [`Context::proxy`] and [`Context::revocable_proxy`].
+///
+/// Each trap that is `Some` is converted to a JavaScript function on the handler object.
+/// Operations without a trap are forwarded to the target, as in JavaScript. Traps may
+/// capture Rust state, which is kept alive for as long as the proxy is reachable.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::proxy::ProxyHandler;
+///
+/// // Returns an object where every property is the length of its name
+/// fn name_lengths(mut cx: FunctionContext) -> JsResult<JsObject> {
+///     let target = cx.empty_object();
+///     let handler = ProxyHandler {
+///         get: Some(Box::new(|mut cx| {
+///             let key = cx.argument::<JsValue>(1)?.to_string(&mut cx)?;
+///             let len = key.value(&mut cx).len() as f64;
+///
+///             Ok(cx.number(len).upcast())
+///         })),
+///         ..Default::default()
+///     };
+///
+///     cx.proxy(target, handler)
+/// }
+/// ```
+#[derive(Default)]
+pub struct ProxyHandler {
+    /// Trap for getting a property. Called as `get(target, key, receiver)`.
+    pub get: Option<ProxyTrap>,
+    /// Trap for setting a property. Called as `set(target, key, value, receiver)`.
+    pub set: Option<ProxyTrap>,
+    /// Trap for the `in` operator. Called as `has(target, key)`.
+    pub has: Option<ProxyTrap>,
+    /// Trap for the `delete` operator. Called as `deleteProperty(target, key)`.
+    pub delete_property: Option<ProxyTrap>,
+    /// Trap for listing own property keys, e.g., with `Object.keys`. Called as `ownKeys(target)`.
+    pub own_keys: Option<ProxyTrap>,
+    /// Trap for `Object.getOwnPropertyDescriptor`. Called as
+    /// `getOwnPropertyDescriptor(target, key)`.
+    pub get_own_property_descriptor: Option<ProxyTrap>,
+    /// Trap for `Object.defineProperty`. Called as `defineProperty(target, key, descriptor)`.
+    pub define_property: Option<ProxyTrap>,
+}
+
+impl ProxyHandler {
+    fn into_object<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsObject> {
+        let handler = cx.empty_object();
+        let traps = [
+            ("get", self.get),
+            ("set", self.set),
+            ("has", self.has),
+            ("deleteProperty", self.delete_property),
+            ("ownKeys", self.own_keys),
+            ("getOwnPropertyDescriptor", self.get_own_property_descriptor),
+            ("defineProperty", self.define_property),
+        ];
+
+        for (name, trap) in traps {
+            if let Some(trap) = trap {
+                let options = FunctionOptions::new().name(name);
+                let trap = JsFunction::with_options(cx, options, trap)?;
+
+                handler.set(cx, name, trap)?;
+            }
+        }
+
+        Ok(handler)
+    }
+}
+
+fn proxy_constructor<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsFunction> {
+    crate::intrinsics::proxy(cx)
+}
+
+pub(crate) fn new<'a, C: Context<'a>, T: Object>(
+    cx: &mut C,
+    target: Handle<T>,
+    handler: ProxyHandler,
+) -> JsResult<'a, T> {
+    let handler = handler.into_object(cx)?;
+
+    construct(cx, target, handler)
+}
+
+fn construct<'a, C: Context<'a>, T: Object>(
+    cx: &mut C,
+    target: Handle<T>,
+    handler: Handle<JsObject>,
+) -> JsResult<'a, T> {
+    let proxy = proxy_constructor(cx)?.construct(cx, [target.upcast(), handler.upcast()])?;
+
+    proxy.upcast::<JsValue>().downcast_or_throw(cx)
+}
+
+pub(crate) fn revocable<'a, C: Context<'a>, T: Object>(
+    cx: &mut C,
+    target: Handle<T>,
+    handler: ProxyHandler,
+) -> NeonResult<(Handle<'a, T>, Handle<'a, JsFunction>)> {
+    let handler = handler.into_object(cx)?;
+    let constructor = proxy_constructor(cx)?;
+    let revocable: Handle<JsFunction> = constructor.get(cx, "revocable")?;
+    let result: Handle<JsObject> = revocable
+        .call(cx, constructor, [target.upcast(), handler.upcast()])?
+        .downcast_or_throw(cx)?;
+
+    let proxy = result.get_value(cx, "proxy")?.downcast_or_throw(cx)?;
+    let revoke = result.get(cx, "revoke")?;
+
+    Ok((proxy, revoke))
+}
+
+type ArraySetter<T> =
+    Box<dyn Fn(&mut FunctionContext, &[T], usize, Handle<JsValue>) -> NeonResult<()> + 'static>;
+
+// Key of the boxed `Vec` on the handler object, which is not reachable from JavaScript
+const ARRAY_VIEW_DATA: &str = "data";
+
+/// A live, array-like view of a boxed `Vec`, created with a
+/// [`Proxy`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy)
+/// over an array.
+///
+/// Elements are converted with [`TryIntoJs`] each time they are read, so only the
+/// elements JavaScript accesses are converted. The view has the `length` of the
+/// `Vec`, supports indexing, the `in` operator, `Object.keys` and
+/// `Symbol.iterator`, and since `Array.isArray` is `true` for it, array methods
+/// such as `slice(start, end)` and `map` work as they do for an array,
+/// converting only the elements they visit.
+///
+/// The view is read-only by default; assigning, deleting or defining a property
+/// throws a `TypeError`. Provide a [setter](ArrayView::setter) to handle assignments
+/// to elements, e.g., by updating an element with interior mutability.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::proxy::ArrayView;
+///
+/// fn words(mut cx: FunctionContext) -> JsResult<JsObject> {
+///     let words = (0..10_000).map(|i| format!("word{}", i)).collect::<Vec<_>>();
+///     let words = cx.boxed(words);
+///
+///     // `view[9999]` converts a single string
+///     ArrayView::new(words).build(&mut cx)
+/// }
+/// ```
+pub struct ArrayView<'a, T: Send + 'static> {
+    data: Handle<'a, JsBox<Vec<T>>>,
+    setter: Option<ArraySetter<T>>,
+}
+
+impl<'a, T> ArrayView<'a, T>
+where
+    T: for<'cx> TryIntoJs<'cx> + Clone + Send + 'static,
+{
+    /// Creates a read-only view of the elements of `data`.
+    pub fn new(data: Handle<'a, JsBox<Vec<T>>>) -> Self {
+        Self { data, setter: None }
+    }
+
+    /// Handles assignments to elements of the view with `setter`, which is called
+    /// with the elements, the index and the assigned value.
+    ///
+    /// Assigning to an index out of bounds or to any other property still throws. The
+    /// view reads elements from the `Vec`, so `setter` should update an element for
+    /// the assignment to be observed.
+    pub fn setter<F>(mut self, setter: F) -> Self
+    where
+        F: Fn(&mut FunctionContext, &[T], usize, Handle<JsValue>) -> NeonResult<()> + 'static,
+    {
+        self.setter = Some(Box::new(setter));
+        self
+    }
+
+    /// Creates the view.
+    pub fn build<'b, C: Context<'b>>(self, cx: &mut C) -> JsResult<'b, JsObject> {
+        let target = JsArray::new(cx, self.data.len() as u32);
+        let writable = self.setter.is_some();
+        let setter = self.setter;
+        let handler = ProxyHandler {
+            get: Some(Box::new(|mut cx| {
+                let target = cx.argument::<JsObject>(0)?;
+                let key = cx.argument::<JsValue>(1)?;
+                let data = array_view_data::<T>(&mut cx)?;
+
+                match element_index(&mut cx, key, data.len()) {
+                    Some(i) => Ok(data[i].clone().try_into_js(&mut cx)?.upcast()),
+                    None => target.get_value(&mut cx, key),
+                }
+            })),
+            set: Some(Box::new(move |mut cx| {
+                let key = cx.argument::<JsValue>(1)?;
+                let value = cx.argument::<JsValue>(2)?;
+                let data = array_view_data::<T>(&mut cx)?;
+                let setter = match &setter {
+                    Some(setter) => setter,
+                    None => return cx.throw_type_error("cannot assign to a read-only array view"),
+                };
+
+                match element_index(&mut cx, key, data.len()) {
+                    Some(i) => {
+                        setter(&mut cx, &data, i, value)?;
+
+                        Ok(cx.boolean(true).upcast())
+                    }
+                    None => cx.throw_type_error(format!(
+                        "can only assign to the elements of an array view of length {}",
+                        data.len()
+                    )),
+                }
+            })),
+            has: Some(Box::new(|mut cx| {
+                let target = cx.argument::<JsObject>(0)?;
+                let key = cx.argument::<JsValue>(1)?;
+                let data = array_view_data::<T>(&mut cx)?;
+                let result = match element_index(&mut cx, key, data.len()) {
+                    Some(_) => true,
+                    None => target.has(&mut cx, key)?,
+                };
+
+                Ok(cx.boolean(result).upcast())
+            })),
+            delete_property: Some(Box::new(|mut cx| {
+                cx.throw_type_error("cannot delete properties of an array view")
+            })),
+            own_keys: Some(Box::new(|mut cx| {
+                let data = array_view_data::<T>(&mut cx)?;
+                let keys = JsArray::new(&mut cx, data.len() as u32 + 1);
+
+                for i in 0..data.len() {
+                    let key = cx.string(i.to_string());
+
+                    keys.set(&mut cx, i as u32, key)?;
+                }
+
+                let length = cx.string("length");
+
+                keys.set(&mut cx, data.len() as u32, length)?;
+
+                Ok(keys.upcast())
+            })),
+            get_own_property_descriptor: Some(Box::new(move |mut cx| {
+                let target = cx.argument::<JsObject>(0)?;
+                let key = cx.argument::<JsValue>(1)?;
+                let data = array_view_data::<T>(&mut cx)?;
+
+                let i = match element_index(&mut cx, key, data.len()) {
+                    Some(i) => i,
+                    None => {
+                        let object = crate::intrinsics::object(&mut cx)?;
+                        let describe: Handle<JsFunction> =
+                            object.get(&mut cx, "getOwnPropertyDescriptor")?;
+
+                        return describe.call(&mut cx, object, [target.upcast(), key]);
+                    }
+                };
+
+                let descriptor = cx.empty_object();
+                let value = data[i].clone().try_into_js(&mut cx)?;
+                let writable = cx.boolean(writable);
+                let enumerable = cx.boolean(true);
+                let configurable = cx.boolean(true);
+
+                descriptor.set(&mut cx, "value", value)?;
+                descriptor.set(&mut cx, "writable", writable)?;
+                descriptor.set(&mut cx, "enumerable", enumerable)?;
+                // Elements are not own properties of the target, so they must be
+                // reported as configurable
+                descriptor.set(&mut cx, "configurable", configurable)?;
+
+                Ok(descriptor.upcast())
+            })),
+            define_property: Some(Box::new(|mut cx| {
+                cx.throw_type_error("cannot define properties on an array view")
+            })),
+        };
+
+        let handler = handler.into_object(cx)?;
+
+        handler.set(cx, ARRAY_VIEW_DATA, self.data)?;
+
+        construct(cx, target.upcast::<JsObject>(), handler)
+    }
+}
+
+fn array_view_data<'a, T: Send + 'static>(
+    cx: &mut FunctionContext<'a>,
+) -> JsResult<'a, JsBox<Vec<T>>> {
+    let handler = cx.this::<JsObject>()?;
+
+    handler.get(cx, ARRAY_VIEW_DATA)
+}
+
+// Returns the index of an element if `key` is the canonical string form of an index
+// within `len`
+fn element_index<'a, C: Context<'a>>(
+    cx: &mut C,
+    key: Handle<JsValue>,
+    len: usize,
+) -> Option<usize> {
+    let key = key.downcast::<JsString, _>(cx).ok()?.value(cx);
+    let index = key.parse::<usize>().ok()?;
+
+    if index < len && index.to_string() == key {
+        Some(index)
+    } else {
+        None
+    }
+}
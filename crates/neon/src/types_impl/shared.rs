@@ -0,0 +1,200 @@
+//! Values shared between JavaScript and Rust threads.
+//!
+//! [`Shared`] and [`SharedHandle`] share ownership of a Rust value between JavaScript
+//! objects and Rust code. [`JsRwLock`] and [`RwLockHandle`] additionally guard the
+//! value with a reader-writer lock. [`AtomicI32View`] provides atomic access, including
+//! waiting and notifying, to an `Int32Array` over a `SharedArrayBuffer`.
+
+use std::{fmt, ops::Deref, sync::Arc};
+
+use crate::{
+    context::{Context, FinalizeContext},
+    handle::Handle,
+    lifecycle::{DropData, DropQueue, InstanceData},
+    types::{boxed::Finalize, JsBox},
+};
+
+pub use self::{
+    atomics::{AtomicI32View, WaitResult},
+    rwlock::{JsRwLock, ReadGuard, RwLockHandle, WriteGuard},
+};
+
+#[cfg(debug_assertions)]
+pub(crate) use self::rwlock::assert_no_guards;
+
+mod atomics;
+mod rwlock;
+
+/// The contents of a [`JsBox`] that shares ownership of a value with other boxes and
+/// with [`SharedHandle`]s.
+///
+/// A `Shared<T>` is created with [`Context::shared`] or [`SharedHandle::to_js`] and
+/// dereferences to `T`. The value is finalized with [`Finalize::finalize_gc`] exactly
+/// once, by whichever box or handle is released last.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::{Shared, SharedHandle};
+///
+/// struct Pool {
+///     size: usize,
+/// }
+///
+/// impl Finalize for Pool {}
+///
+/// fn pool_new(mut cx: FunctionContext) -> JsResult<JsBox<Shared<Pool>>> {
+///     let (_, pool) = cx.shared(Pool { size: 4 });
+///
+///     Ok(pool)
+/// }
+///
+/// // Returns a second JavaScript object that shares the same `Pool`
+/// fn pool_share(mut cx: FunctionContext) -> JsResult<JsBox<Shared<Pool>>> {
+///     let pool = cx.argument::<JsBox<Shared<Pool>>>(0)?;
+///     let handle: SharedHandle<Pool> = pool.handle(&mut cx);
+///
+///     Ok(handle.to_js(&mut cx))
+/// }
+///
+/// fn pool_size(mut cx: FunctionContext) -> JsResult<JsNumber> {
+///     let pool = cx.argument::<JsBox<Shared<Pool>>>(0)?;
+///
+///     Ok(cx.number(pool.size as f64))
+/// }
+/// ```
+pub struct Shared<T: Finalize + Send + Sync + 'static>(Arc<T>);
+
+impl<T: Finalize + Send + Sync + 'static> Shared<T> {
+    /// Creates a Rust handle to the shared value.
+    pub fn handle<'a, C: Context<'a>>(&self, cx: &mut C) -> SharedHandle<T> {
+        SharedHandle::new(cx, Arc::clone(&self.0))
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> Finalize for Shared<T> {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        if let Some(value) = Arc::into_inner(self.0) {
+            value.finalize(cx);
+        }
+    }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        if let Some(value) = Arc::into_inner(self.0) {
+            value.finalize_gc(cx);
+        }
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Shared<{}>", std::any::type_name::<T>())
+    }
+}
+
+/// A thread-safe Rust handle to a value shared with one or more JavaScript objects.
+///
+/// Handles are created with [`Context::shared`] or [`Shared::handle`] and may be
+/// cloned, sent across threads, and used to create additional boxes with
+/// [`SharedHandle::to_js`].
+///
+/// The shared value is finalized once the last box has been garbage collected and
+/// the last handle has been dropped. If a handle is dropped last, the value is
+/// finalized on a later tick of the JavaScript event loop. Use the [`Finalize`]
+/// implementation of `SharedHandle` to finalize immediately when a [`Context`] is
+/// available.
+pub struct SharedHandle<T: Finalize + Send + Sync + 'static> {
+    // `Option` is used to skip `Drop` when the handle is finalized explicitly.
+    // It will *always* be `Some` when a user is interacting with `SharedHandle`.
+    value: Option<Arc<T>>,
+    drop_queue: Arc<DropQueue>,
+}
+
+impl<T: Finalize + Send + Sync + 'static> SharedHandle<T> {
+    fn new<'a, C: Context<'a>>(cx: &mut C, value: Arc<T>) -> Self {
+        Self {
+            value: Some(value),
+            drop_queue: InstanceData::drop_queue(cx),
+        }
+    }
+
+    pub(crate) fn pair<'a, C: Context<'a>>(
+        cx: &mut C,
+        value: T,
+    ) -> (Self, Handle<'a, JsBox<Shared<T>>>) {
+        let handle = Self::new(cx, Arc::new(value));
+        let boxed = handle.to_js(cx);
+
+        (handle, boxed)
+    }
+
+    /// Creates a new JavaScript object that shares the value.
+    pub fn to_js<'a, C: Context<'a>>(&self, cx: &mut C) -> Handle<'a, JsBox<Shared<T>>> {
+        cx.boxed(Shared(Arc::clone(self.arc())))
+    }
+
+    fn arc(&self) -> &Arc<T> {
+        self.value
+            .as_ref()
+            .expect("SharedHandle used after it was finalized")
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> Clone for SharedHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Some(Arc::clone(self.arc())),
+            drop_queue: Arc::clone(&self.drop_queue),
+        }
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> Deref for SharedHandle<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.arc()
+    }
+}
+
+// Allows putting a `SharedHandle<T>` directly in a container that implements
+// `Finalize`, e.g., another `JsBox`
+impl<T: Finalize + Send + Sync + 'static> Finalize for SharedHandle<T> {
+    fn finalize<'a, C: Context<'a>>(mut self, cx: &mut C) {
+        if let Some(value) = self.value.take().and_then(Arc::into_inner) {
+            value.finalize(cx);
+        }
+    }
+
+    fn finalize_gc(mut self, cx: &mut FinalizeContext) {
+        if let Some(value) = self.value.take().and_then(Arc::into_inner) {
+            value.finalize_gc(cx);
+        }
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> Drop for SharedHandle<T> {
+    fn drop(&mut self) {
+        let value = match self.value.take().and_then(Arc::into_inner) {
+            Some(value) => value,
+            None => return,
+        };
+
+        self.drop_queue.push(DropData::Shared(Box::new(move |env| {
+            FinalizeContext::with(env.into(), move |mut cx| value.finalize_gc(&mut cx));
+        })));
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> fmt::Debug for SharedHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SharedHandle<{}>", std::any::type_name::<T>())
+    }
+}
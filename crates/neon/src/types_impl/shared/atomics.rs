@@ -0,0 +1,353 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc, Condvar, Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{
+    context::Context,
+    event::Channel,
+    handle::{Handle, Managed, Root},
+    object::Object,
+    result::NeonResult,
+    sys,
+    types::{JsNumber, JsTypedArray},
+};
+
+// How often a waiting thread checks for a change made from JavaScript, which cannot
+// wake it directly
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The result of [`AtomicI32View::wait`], equivalent to the strings returned by
+/// [`Atomics.wait`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics/wait).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WaitResult {
+    /// The thread was woken by a notification or a change of the value (`"ok"`).
+    Ok,
+    /// The value did not equal the expected value (`"not-equal"`).
+    NotEqual,
+    /// The timeout elapsed (`"timed-out"`).
+    TimedOut,
+}
+
+impl WaitResult {
+    /// Returns the string `Atomics.wait` returns for the same outcome.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WaitResult::Ok => "ok",
+            WaitResult::NotEqual => "not-equal",
+            WaitResult::TimedOut => "timed-out",
+        }
+    }
+}
+
+/// Atomic access to an `Int32Array` over a `SharedArrayBuffer`, usable from any
+/// Rust thread.
+///
+/// Operations act directly on the shared memory with sequentially consistent Rust
+/// atomics, the same memory and ordering JavaScript's
+/// [`Atomics`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Atomics)
+/// functions use, so values stored from either side are visible to the other.
+///
+/// JavaScript engines implement `Atomics.wait` and `Atomics.notify` with a wait list
+/// that is not reachable from native code, so waking across the boundary works as
+/// follows:
+///
+/// * [`AtomicI32View::notify`] wakes Rust threads blocked in [`AtomicI32View::wait`]
+///   immediately, and calls `Atomics.notify` on the JavaScript thread of the
+///   environment that created the view, waking JavaScript waiters in any thread
+///   asynchronously.
+/// * A Rust thread blocked in [`AtomicI32View::wait`] cannot be woken directly by
+///   `Atomics.notify`. Instead, it observes a change of the value within about a
+///   millisecond. JavaScript should store a new value before notifying, the usual
+///   protocol for `Atomics.wait` anyway.
+///
+/// The view keeps the shared memory alive with a [`Root`] and a [`Channel`] in the
+/// environment that created it. Create the view in an environment, e.g., the main
+/// thread, that outlives the Rust threads using it; after the environment exits,
+/// JavaScript waiters are no longer notified.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::shared::AtomicI32View;
+///
+/// // Called from JavaScript with an `Int32Array` over a `SharedArrayBuffer`
+/// fn signal_later(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let array = cx.argument::<JsTypedArray<i32>>(0)?;
+///     let view = AtomicI32View::new(&mut cx, array)?;
+///
+///     std::thread::spawn(move || {
+///         // Do some work, then wake a JavaScript `Atomics.wait(array, 0, 0)`
+///         view.store(0, 1);
+///         view.notify(0, u32::MAX);
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct AtomicI32View {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    data: *const AtomicI32,
+    len: usize,
+    array: Root<JsTypedArray<i32>>,
+    channel: Channel,
+}
+
+// Safety: `data` points into shared memory, which is kept alive by `array` and only
+// accessed atomically
+unsafe impl Send for Inner {}
+unsafe impl Sync for Inner {}
+
+impl AtomicI32View {
+    /// Creates a view of `array`.
+    ///
+    /// Throws a `TypeError` if `array` is not backed by a `SharedArrayBuffer`.
+    pub fn new<'a, C: Context<'a>>(
+        cx: &mut C,
+        array: Handle<JsTypedArray<i32>>,
+    ) -> NeonResult<Self> {
+        let env = cx.env().to_raw();
+        let info = unsafe { sys::typedarray::info(env, array.to_raw()) };
+
+        if !unsafe { sys::tag::is_instance_of_global(env, info.buf, "SharedArrayBuffer") } {
+            return cx.throw_type_error("expected an Int32Array backed by a SharedArrayBuffer");
+        }
+
+        let len = array.len(cx);
+        let mut channel = cx.channel();
+
+        // A view must not keep the event loop alive
+        channel.unref(cx);
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                data: info.data.cast(),
+                len,
+                array: array.root(cx),
+                channel,
+            }),
+        })
+    }
+
+    /// Returns the number of elements in the view.
+    pub fn len(&self) -> usize {
+        self.inner.len
+    }
+
+    /// Indicates whether the view has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn cell(&self, index: usize) -> &AtomicI32 {
+        assert!(
+            index < self.inner.len,
+            "index {} out of bounds for AtomicI32View of length {}",
+            index,
+            self.inner.len
+        );
+
+        // Safety: The index is in bounds and the memory is kept alive by `self.inner`
+        unsafe { &*self.inner.data.add(index) }
+    }
+
+    /// Loads the element at `index`, equivalent to `Atomics.load`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn load(&self, index: usize) -> i32 {
+        self.cell(index).load(Ordering::SeqCst)
+    }
+
+    /// Stores `value` at `index`, equivalent to `Atomics.store`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn store(&self, index: usize, value: i32) {
+        self.cell(index).store(value, Ordering::SeqCst)
+    }
+
+    /// Adds `value` to the element at `index`, returning the previous value, equivalent
+    /// to `Atomics.add`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn fetch_add(&self, index: usize, value: i32) -> i32 {
+        self.cell(index).fetch_add(value, Ordering::SeqCst)
+    }
+
+    /// Stores `new` at `index` if the element equals `current`, equivalent to
+    /// `Atomics.compareExchange`. Returns the previous value, as `Ok` if it was
+    /// replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn compare_exchange(&self, index: usize, current: i32, new: i32) -> Result<i32, i32> {
+        self.cell(index)
+            .compare_exchange(current, new, Ordering::SeqCst, Ordering::SeqCst)
+    }
+
+    /// Blocks the current thread while the element at `index` equals `expected`,
+    /// equivalent to `Atomics.wait`.
+    ///
+    /// Returns [`WaitResult::Ok`] when woken by [`AtomicI32View::notify`] or when the
+    /// value changes, [`WaitResult::NotEqual`] without blocking if the value does not
+    /// equal `expected`, and [`WaitResult::TimedOut`] if `timeout` elapses first. Waits
+    /// indefinitely if `timeout` is `None`.
+    ///
+    /// This must not be called on a JavaScript thread, which would block the event loop.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn wait(&self, index: usize, expected: i32, timeout: Option<Duration>) -> WaitResult {
+        let cell = self.cell(index);
+
+        if cell.load(Ordering::SeqCst) != expected {
+            return WaitResult::NotEqual;
+        }
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let addr = cell as *const AtomicI32 as usize;
+        let waiter = Arc::new(Waiter::default());
+
+        lock(&WAITERS)
+            .entry(addr)
+            .or_default()
+            .push_back(waiter.clone());
+
+        let mut woken = lock(&waiter.woken);
+
+        let result = loop {
+            if *woken || cell.load(Ordering::SeqCst) != expected {
+                break WaitResult::Ok;
+            }
+
+            let wait = match deadline {
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(remaining) if !remaining.is_zero() => remaining.min(POLL_INTERVAL),
+                    _ => break WaitResult::TimedOut,
+                },
+                None => POLL_INTERVAL,
+            };
+
+            woken = waiter
+                .condvar
+                .wait_timeout(woken, wait)
+                .unwrap_or_else(|err| err.into_inner())
+                .0;
+        };
+
+        drop(woken);
+
+        // Remove this waiter if it was not removed by `notify`
+        let mut waiters = lock(&WAITERS);
+
+        if let Some(queue) = waiters.get_mut(&addr) {
+            queue.retain(|w| !Arc::ptr_eq(w, &waiter));
+
+            if queue.is_empty() {
+                waiters.remove(&addr);
+            }
+        }
+
+        result
+    }
+
+    /// Wakes up to `count` threads waiting on the element at `index`, equivalent to
+    /// `Atomics.notify`.
+    ///
+    /// Rust threads blocked in [`AtomicI32View::wait`] are woken immediately, in the order
+    /// they started waiting, and their number is returned. JavaScript waiters are woken
+    /// asynchronously, by calling `Atomics.notify` with the remaining count on the
+    /// JavaScript thread of the environment that created the view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn notify(&self, index: usize, count: u32) -> u32 {
+        let addr = self.cell(index) as *const AtomicI32 as usize;
+        let mut woken = 0;
+
+        {
+            let mut waiters = lock(&WAITERS);
+
+            if let Some(queue) = waiters.get_mut(&addr) {
+                while woken < count {
+                    match queue.pop_front() {
+                        Some(waiter) => waiter.wake(),
+                        None => break,
+                    }
+
+                    woken += 1;
+                }
+
+                if queue.is_empty() {
+                    waiters.remove(&addr);
+                }
+            }
+        }
+
+        let remaining = count - woken;
+
+        if remaining > 0 {
+            let inner = self.inner.clone();
+
+            // The environment may have exited, in which case there is nothing to notify
+            let _ = self.inner.channel.try_send(move |mut cx| {
+                let array = inner.array.to_inner(&mut cx);
+                let notify = crate::intrinsics::atomics_notify(&mut cx)?;
+                let index = cx.number(index as f64);
+                // `Atomics.notify` treats counts above the number of waiters as all
+                let count = cx.number(remaining);
+
+                notify
+                    .call_with(&cx)
+                    .arg(array)
+                    .arg(index)
+                    .arg(count)
+                    .apply::<JsNumber, _>(&mut cx)?;
+
+                Ok(())
+            });
+        }
+
+        woken
+    }
+}
+
+// A Rust thread blocked in `AtomicI32View::wait`, parked on its own condition
+// variable so that `notify` only wakes the threads it removes from the wait list
+#[derive(Default)]
+struct Waiter {
+    woken: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl Waiter {
+    fn wake(&self) {
+        *lock(&self.woken) = true;
+        self.condvar.notify_one();
+    }
+}
+
+// Rust threads blocked in `AtomicI32View::wait`, by address of the element
+static WAITERS: Lazy<Mutex<HashMap<usize, VecDeque<Arc<Waiter>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
@@ -0,0 +1,344 @@
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{self, Arc, TryLockError},
+};
+
+#[cfg(debug_assertions)]
+use std::cell::RefCell;
+
+use crate::{
+    context::{Context, FinalizeContext},
+    handle::Handle,
+    types::{boxed::Finalize, JsBox},
+};
+
+use super::{Shared, SharedHandle};
+
+/// The contents of a [`JsBox`] that guards a value with a reader-writer lock, so that
+/// it may be read from other threads, e.g., in the `execute` closure of a
+/// [task](crate::context::Context::task), while methods on the JavaScript thread
+/// occasionally write to it.
+///
+/// A `JsRwLock<T>` is created with [`Context::boxed_rwlock`]. A [`RwLockHandle`]
+/// obtained with [`JsRwLock::to_shared`] shares the lock and may be sent to other
+/// threads. The value is finalized once the last box has been garbage collected and
+/// the last handle has been dropped, like a [`Shared`] value.
+///
+/// Writing on the JavaScript thread blocks the event loop until readers on other
+/// threads release their guards; use [`JsRwLock::try_write`] to avoid waiting.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use std::collections::HashMap;
+///
+/// use neon::types::JsRwLock;
+///
+/// struct Index(HashMap<String, f64>);
+///
+/// impl Finalize for Index {}
+///
+/// type BoxedIndex = JsBox<JsRwLock<Index>>;
+///
+/// fn index_new(mut cx: FunctionContext) -> JsResult<BoxedIndex> {
+///     Ok(cx.boxed_rwlock(Index(HashMap::new())))
+/// }
+///
+/// // Writes on the JavaScript thread
+/// fn index_insert(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let index = cx.argument::<BoxedIndex>(0)?;
+///     let key = cx.argument::<JsString>(1)?.value(&mut cx);
+///     let value = cx.argument::<JsNumber>(2)?.value(&mut cx);
+///
+///     index.write().0.insert(key, value);
+///
+///     Ok(cx.undefined())
+/// }
+///
+/// // Reads on a worker thread
+/// fn index_get(mut cx: FunctionContext) -> JsResult<JsPromise> {
+///     let index = cx.argument::<BoxedIndex>(0)?.to_shared(&mut cx);
+///     let key = cx.argument::<JsString>(1)?.value(&mut cx);
+///
+///     let promise = cx
+///         .task(move || index.read().0.get(&key).copied())
+///         .promise(|mut cx, value| match value {
+///             Some(value) => Ok(cx.number(value).upcast::<JsValue>()),
+///             None => Ok(cx.undefined().upcast()),
+///         });
+///
+///     Ok(promise)
+/// }
+/// ```
+///
+/// # Deadlocks
+///
+/// A guard must not be held while sending a closure with a
+/// [`Channel`](crate::event::Channel) to the JavaScript thread that created the lock:
+/// if the closure takes a conflicting guard, and the sending thread waits for the
+/// closure, neither can make progress. In debug builds, sending on a channel of that
+/// JavaScript thread while holding a guard of one of its locks panics.
+pub struct JsRwLock<T: Finalize + Send + Sync + 'static>(Shared<Lock<T>>);
+
+impl<T: Finalize + Send + Sync + 'static> JsRwLock<T> {
+    pub(crate) fn boxed<'a, C: Context<'a>>(cx: &mut C, value: T) -> Handle<'a, JsBox<Self>> {
+        let lock = Lock::new(cx, value);
+
+        cx.boxed(JsRwLock(Shared(Arc::new(lock))))
+    }
+
+    /// Creates a handle to the lock that may be sent to other threads.
+    pub fn to_shared<'a, C: Context<'a>>(&self, cx: &mut C) -> RwLockHandle<T> {
+        RwLockHandle(SharedHandle::new(cx, Arc::clone(&(self.0).0)))
+    }
+
+    /// Locks the value for reading, blocking until no writer holds the lock.
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        self.0.read()
+    }
+
+    /// Locks the value for reading if no writer holds the lock.
+    pub fn try_read(&self) -> Option<ReadGuard<'_, T>> {
+        self.0.try_read()
+    }
+
+    /// Locks the value for writing, blocking until no reader or writer holds the lock.
+    pub fn write(&self) -> WriteGuard<'_, T> {
+        self.0.write()
+    }
+
+    /// Locks the value for writing if no reader or writer holds the lock.
+    pub fn try_write(&self) -> Option<WriteGuard<'_, T>> {
+        self.0.try_write()
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> Finalize for JsRwLock<T> {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        self.0.finalize(cx);
+    }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        self.0.finalize_gc(cx);
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> fmt::Debug for JsRwLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "JsRwLock<{}>", std::any::type_name::<T>())
+    }
+}
+
+/// A thread-safe handle to the lock of a [`JsRwLock`].
+///
+/// Handles are created with [`JsRwLock::to_shared`] and may be cloned and sent across
+/// threads. If a handle is dropped last, the value is finalized on a later tick of the
+/// JavaScript event loop.
+pub struct RwLockHandle<T: Finalize + Send + Sync + 'static>(SharedHandle<Lock<T>>);
+
+impl<T: Finalize + Send + Sync + 'static> RwLockHandle<T> {
+    /// Creates a new JavaScript object that shares the lock.
+    pub fn to_js<'a, C: Context<'a>>(&self, cx: &mut C) -> Handle<'a, JsBox<JsRwLock<T>>> {
+        cx.boxed(JsRwLock(Shared(Arc::clone(self.0.arc()))))
+    }
+
+    /// Locks the value for reading, blocking until no writer holds the lock.
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        self.0.read()
+    }
+
+    /// Locks the value for reading if no writer holds the lock.
+    pub fn try_read(&self) -> Option<ReadGuard<'_, T>> {
+        self.0.try_read()
+    }
+
+    /// Locks the value for writing, blocking until no reader or writer holds the lock.
+    pub fn write(&self) -> WriteGuard<'_, T> {
+        self.0.write()
+    }
+
+    /// Locks the value for writing if no reader or writer holds the lock.
+    pub fn try_write(&self) -> Option<WriteGuard<'_, T>> {
+        self.0.try_write()
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> Clone for RwLockHandle<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> Finalize for RwLockHandle<T> {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        self.0.finalize(cx);
+    }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        self.0.finalize_gc(cx);
+    }
+}
+
+impl<T: Finalize + Send + Sync + 'static> fmt::Debug for RwLockHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RwLockHandle<{}>", std::any::type_name::<T>())
+    }
+}
+
+struct Lock<T> {
+    lock: sync::RwLock<T>,
+    // Address of the environment that created the lock
+    #[cfg(debug_assertions)]
+    env: usize,
+}
+
+impl<T> Lock<T> {
+    fn new<'a, C: Context<'a>>(cx: &mut C, value: T) -> Self {
+        #[cfg(not(debug_assertions))]
+        let _ = cx;
+
+        Self {
+            lock: sync::RwLock::new(value),
+            #[cfg(debug_assertions)]
+            env: cx.env().to_raw() as usize,
+        }
+    }
+
+    fn read(&self) -> ReadGuard<'_, T> {
+        ReadGuard {
+            guard: self.lock.read().unwrap_or_else(|err| err.into_inner()),
+            _held: self.held(),
+        }
+    }
+
+    fn try_read(&self) -> Option<ReadGuard<'_, T>> {
+        let guard = match self.lock.try_read() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+
+        Some(ReadGuard {
+            guard,
+            _held: self.held(),
+        })
+    }
+
+    fn write(&self) -> WriteGuard<'_, T> {
+        WriteGuard {
+            guard: self.lock.write().unwrap_or_else(|err| err.into_inner()),
+            _held: self.held(),
+        }
+    }
+
+    fn try_write(&self) -> Option<WriteGuard<'_, T>> {
+        let guard = match self.lock.try_write() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+
+        Some(WriteGuard {
+            guard,
+            _held: self.held(),
+        })
+    }
+
+    fn held(&self) -> Held {
+        #[cfg(debug_assertions)]
+        HELD.with(|held| held.borrow_mut().push(self.env));
+
+        Held {
+            #[cfg(debug_assertions)]
+            env: self.env,
+        }
+    }
+
+    fn into_inner(self) -> T {
+        self.lock
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<T: Finalize> Finalize for Lock<T> {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        self.into_inner().finalize(cx);
+    }
+
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
+        self.into_inner().finalize_gc(cx);
+    }
+}
+
+/// A guard that releases the shared read access of a [`JsRwLock`] when dropped.
+pub struct ReadGuard<'a, T> {
+    guard: sync::RwLockReadGuard<'a, T>,
+    _held: Held,
+}
+
+impl<'a, T> Deref for ReadGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+/// A guard that releases the exclusive write access of a [`JsRwLock`] when dropped.
+pub struct WriteGuard<'a, T> {
+    guard: sync::RwLockWriteGuard<'a, T>,
+    _held: Held,
+}
+
+impl<'a, T> Deref for WriteGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<'a, T> DerefMut for WriteGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+#[cfg(debug_assertions)]
+thread_local! {
+    // Addresses of the environments of the locks with guards held by this thread
+    static HELD: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+// Records that the current thread holds a guard until dropped
+struct Held {
+    #[cfg(debug_assertions)]
+    env: usize,
+}
+
+#[cfg(debug_assertions)]
+impl Drop for Held {
+    fn drop(&mut self) {
+        HELD.with(|held| {
+            let mut held = held.borrow_mut();
+
+            if let Some(i) = held.iter().rposition(|env| *env == self.env) {
+                held.swap_remove(i);
+            }
+        });
+    }
+}
+
+// Panics if the current thread holds a guard of a lock created by `env`
+#[cfg(debug_assertions)]
+pub(crate) fn assert_no_guards(env: crate::sys::raw::Env) {
+    let env = env as usize;
+
+    if HELD.with(|held| held.borrow().contains(&env)) {
+        panic!(
+            "Cannot send to the JavaScript thread while holding a guard of a JsRwLock it \
+             created; drop the guard before sending"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use std::mem;
+
+use crate::{
+    context::Context,
+    handle::Handle,
+    intrinsics,
+    result::{JsResult, NeonResult},
+    sys::{self, raw},
+    types_impl::JsString,
+};
+
+// Number of code units buffered in Rust before they are copied, or handed over, to the
+// JavaScript engine
+const CHUNK_LEN: usize = 1 << 18;
+
+/// Builds a large [`JsString`] from many fragments without first concatenating them
+/// into a Rust [`String`].
+///
+/// Fragments are buffered in chunks of a fixed size. Each full chunk is converted to a
+/// JavaScript string and concatenated with the chunks before it, which JavaScript
+/// engines represent as a rope rather than copying. Only one chunk is held in Rust
+/// at a time, however large the result.
+///
+/// When the runtime supports external strings (Node-API 10, or Node.js 20.4 and
+/// later), chunks of ASCII text and of UTF-16 are handed over to the engine instead
+/// of being copied. Other UTF-8 text is copied once per chunk.
+///
+/// The finished string is a rope, which the engine flattens, i.e., copies, the first
+/// time most operations read it. The builder bounds the memory used in Rust rather
+/// than saving time; building a large string takes about as long as copying a
+/// `String` would.
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::JsStringBuilder;
+///
+/// fn to_csv(mut cx: FunctionContext) -> JsResult<JsString> {
+///     let rows = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+///     let mut csv = JsStringBuilder::new(&mut cx);
+///
+///     csv.push_str(&mut cx, "id,square\n")?;
+///
+///     for i in 0..rows {
+///         csv.push_str(&mut cx, &format!("{},{}\n", i, i * i))?;
+///     }
+///
+///     csv.finish(&mut cx)
+/// }
+/// ```
+pub struct JsStringBuilder<'a> {
+    // Concatenation of the chunks handed over so far
+    head: Option<Handle<'a, JsString>>,
+    chunk: Chunk,
+}
+
+enum Chunk {
+    // Also valid Latin-1, which allows creating an external string
+    Ascii(Vec<u8>),
+    Utf8(Vec<u8>),
+    Utf16(Vec<u16>),
+}
+
+impl Chunk {
+    fn len(&self) -> usize {
+        match self {
+            Chunk::Ascii(buf) | Chunk::Utf8(buf) => buf.len(),
+            Chunk::Utf16(buf) => buf.len(),
+        }
+    }
+}
+
+impl<'a> JsStringBuilder<'a> {
+    /// Creates an empty builder. The finished string is returned in the scope of `cx`.
+    pub fn new<C: Context<'a>>(_cx: &mut C) -> Self {
+        Self {
+            head: None,
+            chunk: Chunk::Ascii(Vec::new()),
+        }
+    }
+
+    /// Appends a Rust string.
+    ///
+    /// Throws a `RangeError` if the result would exceed the maximum string length of the
+    /// JavaScript engine.
+    pub fn push_str<C: Context<'a>>(&mut self, cx: &mut C, s: &str) -> NeonResult<()> {
+        let ascii = s.is_ascii();
+
+        if let Chunk::Utf16(_) = self.chunk {
+            self.flush(cx)?;
+            self.chunk = Chunk::Ascii(Vec::new());
+        }
+
+        if self.chunk.len() + s.len() > CHUNK_LEN {
+            self.flush(cx)?;
+
+            // Large fragments are copied directly, skipping the chunk
+            if s.len() >= CHUNK_LEN {
+                let fragment = if ascii {
+                    latin1(cx, s.as_bytes())?
+                } else {
+                    utf8(cx, s)?
+                };
+
+                return self.append(cx, fragment);
+            }
+        }
+
+        match &mut self.chunk {
+            // ASCII is valid UTF-8; the chunk continues as UTF-8
+            Chunk::Ascii(buf) if !ascii => {
+                let mut buf = mem::take(buf);
+
+                buf.extend_from_slice(s.as_bytes());
+                self.chunk = Chunk::Utf8(buf);
+            }
+            Chunk::Ascii(buf) | Chunk::Utf8(buf) => buf.extend_from_slice(s.as_bytes()),
+            Chunk::Utf16(_) => unreachable!("UTF-16 chunks are flushed before appending UTF-8"),
+        }
+
+        Ok(())
+    }
+
+    /// Appends a string of UTF-16 code units, which may include unpaired surrogates,
+    /// like JavaScript strings.
+    ///
+    /// Throws a `RangeError` if the result would exceed the maximum string length of the
+    /// JavaScript engine.
+    pub fn push_utf16<C: Context<'a>>(&mut self, cx: &mut C, s: &[u16]) -> NeonResult<()> {
+        if !matches!(self.chunk, Chunk::Utf16(_)) {
+            self.flush(cx)?;
+            self.chunk = Chunk::Utf16(Vec::new());
+        }
+
+        if self.chunk.len() + s.len() > CHUNK_LEN {
+            self.flush(cx)?;
+
+            if s.len() >= CHUNK_LEN {
+                let fragment = utf16(cx, s)?;
+
+                return self.append(cx, fragment);
+            }
+        }
+
+        if let Chunk::Utf16(buf) = &mut self.chunk {
+            buf.extend_from_slice(s);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the concatenation of all fragments.
+    ///
+    /// Throws a `RangeError` if the result exceeds the maximum string length of the
+    /// JavaScript engine.
+    pub fn finish<C: Context<'a>>(mut self, cx: &mut C) -> JsResult<'a, JsString> {
+        self.flush(cx)?;
+
+        match self.head {
+            Some(head) => Ok(head),
+            None => Ok(cx.string("")),
+        }
+    }
+
+    // Hands the buffered chunk over to the engine, leaving an empty chunk of the same
+    // encoding, or ASCII in place of UTF-8
+    fn flush<C: Context<'a>>(&mut self, cx: &mut C) -> NeonResult<()> {
+        if self.chunk.len() == 0 {
+            return Ok(());
+        }
+
+        let env = cx.env().to_raw();
+        let mut local: raw::Local = std::ptr::null_mut();
+        let fragment = match &mut self.chunk {
+            Chunk::Ascii(buf) => {
+                match unsafe { sys::string::new_external_latin1(&mut local, env, mem::take(buf)) } {
+                    Ok(()) => Handle::new_internal(JsString(local)),
+                    Err(data) => {
+                        let fragment = latin1(cx, &data)?;
+
+                        // Keep the allocation for the next chunk
+                        *buf = data;
+                        buf.clear();
+                        fragment
+                    }
+                }
+            }
+            Chunk::Utf8(buf) => {
+                // Safety: Only whole `str` fragments are appended to UTF-8 chunks
+                let fragment = utf8(cx, unsafe { std::str::from_utf8_unchecked(buf) })?;
+                let mut buf = mem::take(buf);
+
+                buf.clear();
+                self.chunk = Chunk::Ascii(buf);
+                fragment
+            }
+            Chunk::Utf16(buf) => {
+                match unsafe { sys::string::new_external_utf16(&mut local, env, mem::take(buf)) } {
+                    Ok(()) => Handle::new_internal(JsString(local)),
+                    Err(data) => {
+                        let fragment = utf16(cx, &data)?;
+
+                        *buf = data;
+                        buf.clear();
+                        fragment
+                    }
+                }
+            }
+        };
+
+        self.append(cx, fragment)
+    }
+
+    fn append<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        fragment: Handle<'a, JsString>,
+    ) -> NeonResult<()> {
+        let head = match self.head {
+            Some(head) => head,
+            None => {
+                self.head = Some(fragment);
+
+                return Ok(());
+            }
+        };
+
+        let concat = intrinsics::string_concat(cx)?;
+        let head = concat
+            .call_with(cx)
+            .this(head)
+            .arg(fragment)
+            .apply::<JsString, _>(cx)?;
+
+        self.head = Some(head);
+
+        Ok(())
+    }
+}
+
+// The engine fails to create a string that is too long without throwing an exception
+fn too_long<'a, C: Context<'a>, T>(cx: &mut C) -> NeonResult<T> {
+    cx.throw_range_error("Invalid string length")
+}
+
+fn latin1<'a, C: Context<'a>>(cx: &mut C, data: &[u8]) -> JsResult<'a, JsString> {
+    let mut local: raw::Local = std::ptr::null_mut();
+
+    if unsafe { sys::string::new_latin1(&mut local, cx.env().to_raw(), data.as_ptr(), data.len()) }
+    {
+        Ok(Handle::new_internal(JsString(local)))
+    } else {
+        too_long(cx)
+    }
+}
+
+fn utf8<'a, C: Context<'a>>(cx: &mut C, data: &str) -> JsResult<'a, JsString> {
+    match JsString::new_internal(cx.env(), data) {
+        Some(s) => Ok(s),
+        None => too_long(cx),
+    }
+}
+
+fn utf16<'a, C: Context<'a>>(cx: &mut C, data: &[u16]) -> JsResult<'a, JsString> {
+    let mut local: raw::Local = std::ptr::null_mut();
+
+    if unsafe { sys::string::new_utf16(&mut local, cx.env().to_raw(), data.as_ptr(), data.len()) } {
+        Ok(Handle::new_internal(JsString(local)))
+    } else {
+        too_long(cx)
+    }
+}
@@ -0,0 +1,186 @@
+use super::{private::ValueInternal, Value};
+
+use crate::{
+    context::{internal::Env, Context},
+    handle::{internal::TransparentNoCopyWrapper, Managed},
+    object::Object,
+    result::{JsResult, NeonResult},
+    sys::{self, raw},
+    types::{JsFunction, JsObject, JsString, JsValue},
+};
+
+/// The type of JavaScript
+/// [`URL`](https://developer.mozilla.org/en-US/docs/Web/API/URL) objects.
+///
+/// A value downcasts to `JsUrl` if it is `instanceof URL`, using the `URL` class of
+/// the current environment. Accessors read the properties of the underlying object,
+/// so parsing and normalization follow the
+/// [WHATWG URL Standard](https://url.spec.whatwg.org/) as implemented by Node.js.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::JsUrl;
+///
+/// fn page(mut cx: FunctionContext) -> JsResult<JsValue> {
+///     let url = cx.argument::<JsUrl>(0)?;
+///
+///     match url.search_params_get(&mut cx, "page")? {
+///         Some(page) => Ok(cx.string(page).upcast()),
+///         None => Ok(cx.undefined().upcast()),
+///     }
+/// }
+/// ```
+///
+/// # Interop with the `url` crate
+///
+/// With the `url` feature enabled, [`TryIntoJs`](crate::types::extract::TryIntoJs)
+/// and [`TryFromJs`](crate::types::extract::TryFromJs) are implemented for
+/// [`url::Url`](https://docs.rs/url/2/url/struct.Url.html), converting through the
+/// serialized `href`. Both parsers implement the WHATWG URL Standard and agree on
+/// most inputs, including relative path segments, percent-encoding, IPv4 and IPv6
+/// hosts and common internationalized domain names. Known differences are:
+///
+/// * Node.js normalizes a Windows drive letter written as `C|` in `file:` URLs to
+///   `C:`, while `url` keeps the `|`.
+/// * Node.js applies stricter [UTS #46](https://www.unicode.org/reports/tr46/)
+///   checks to hosts containing joiners, e.g., it rejects `https://a\u{200D}b/` and
+///   `http://xn--1ug.de/`, which `url` accepts.
+/// * `url` applies the bidi rule more strictly, e.g., it rejects
+///   `https://\u{661}\u{662}\u{663}.com/`, which Node.js accepts.
+///
+/// Converting a `url::Url` to a `JsUrl` re-parses its serialization with `new URL`,
+/// so a round trip is only lossless for URLs on which both parsers agree.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct JsUrl(raw::Local);
+
+impl Value for JsUrl {}
+
+unsafe impl TransparentNoCopyWrapper for JsUrl {
+    type Inner = raw::Local;
+
+    fn into_inner(self) -> Self::Inner {
+        self.0
+    }
+}
+
+impl Managed for JsUrl {
+    fn to_raw(&self) -> raw::Local {
+        self.0
+    }
+
+    fn from_raw(_: Env, h: raw::Local) -> Self {
+        JsUrl(h)
+    }
+}
+
+impl ValueInternal for JsUrl {
+    fn name() -> String {
+        "URL".to_string()
+    }
+
+    fn is_typeof<Other: Value>(env: Env, other: &Other) -> bool {
+        unsafe { sys::tag::is_instance_of_global(env.to_raw(), other.to_raw(), "URL") }
+    }
+}
+
+impl Object for JsUrl {}
+
+impl JsUrl {
+    /// Parses `input` with `new URL(input, base)`, resolving it against `base` if it is
+    /// relative. Throws a `TypeError` if `input` is not a valid URL.
+    pub fn new<'a, C: Context<'a>>(
+        cx: &mut C,
+        input: &str,
+        base: Option<&str>,
+    ) -> JsResult<'a, JsUrl> {
+        let constructor = crate::intrinsics::url(cx)?;
+        let mut args = vec![cx.string(input).upcast::<JsValue>()];
+
+        if let Some(base) = base {
+            args.push(cx.string(base).upcast());
+        }
+
+        let url = constructor.construct(cx, args)?;
+
+        url.downcast_or_throw(cx)
+    }
+
+    /// Returns the serialized URL, e.g., `https://example.com:8080/a/b?c=d#e`.
+    pub fn href<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        self.string_property(cx, "href")
+    }
+
+    /// Returns the serialized origin, e.g., `https://example.com:8080`. URLs with an
+    /// opaque origin, such as `data:` URLs, return `"null"`.
+    pub fn origin<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        self.string_property(cx, "origin")
+    }
+
+    /// Returns the percent-encoded path, e.g., `/a/b`.
+    pub fn pathname<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        self.string_property(cx, "pathname")
+    }
+
+    /// Returns the first value of the query parameter `name`, decoded as with
+    /// `url.searchParams.get(name)`, or `None` if it is not present.
+    pub fn search_params_get<'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        name: &str,
+    ) -> NeonResult<Option<String>> {
+        let params = self.get::<JsObject, _, _>(cx, "searchParams")?;
+        let get = params.get::<JsFunction, _, _>(cx, "get")?;
+        let name = cx.string(name);
+        let value = get.call(cx, params, [name.upcast()])?;
+
+        match value.downcast::<JsString, _>(cx) {
+            Ok(s) => Ok(Some(s.value(cx))),
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn string_property<'a, C: Context<'a>>(&self, cx: &mut C, key: &str) -> NeonResult<String> {
+        Ok(self.get::<JsString, _, _>(cx, key)?.value(cx))
+    }
+}
+
+#[cfg(feature = "url")]
+mod interop {
+    use super::JsUrl;
+
+    use crate::{
+        context::Context,
+        handle::Handle,
+        result::{JsResult, NeonResult},
+        types::{
+            extract::{TryFromJs, TryIntoJs},
+            JsValue,
+        },
+    };
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
+    impl<'cx> TryIntoJs<'cx> for ::url::Url {
+        type Value = JsUrl;
+
+        fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsUrl> {
+            JsUrl::new(cx, self.as_str(), None)
+        }
+    }
+
+    /// Extracts a `URL` object; throws a `TypeError` if its `href` is rejected by the
+    /// `url` crate.
+    #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
+    impl<'cx> TryFromJs<'cx> for ::url::Url {
+        fn try_from_js<C: Context<'cx>>(cx: &mut C, v: Handle<'cx, JsValue>) -> NeonResult<Self> {
+            let href = v.downcast_or_throw::<JsUrl, _>(cx)?.href(cx)?;
+
+            match ::url::Url::parse(&href) {
+                Ok(url) => Ok(url),
+                Err(err) => cx.throw_type_error(format!("Invalid URL {:?}: {}", href, err)),
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{
+    context::{internal::Env, Context},
+    handle::{Handle, Managed},
+    result::NeonResult,
+    sys::{self, raw},
+    types::{JsArray, JsFunction, JsObject, JsString, JsSymbol, JsValue},
+};
+
+#[cfg(feature = "napi-6")]
+use crate::types::JsBigInt;
+
+/// Dispatches on the type of a JavaScript value, created with [`Handle::visit`].
+///
+/// Visiting a value takes a single `typeof` check, plus an array check for objects,
+/// which is cheaper than a chain of [`Handle::is_a`] or [`Handle::downcast`] calls that
+/// each check the type again. Booleans and numbers are passed by value.
+///
+/// Every method defaults to calling [`ValueVisitor::visit_other`], so a visitor only
+/// implements the types it handles. Values created with [`JsBox`](crate::types::JsBox)
+/// are always passed to `visit_other`, as are `BigInt` values without the `napi-6`
+/// feature.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::types::ValueVisitor;
+///
+/// // Sums numbers and the lengths of strings, ignoring every other value
+/// struct Weight(f64);
+///
+/// impl<'a> ValueVisitor<'a> for Weight {
+///     type Output = ();
+///
+///     fn visit_number<C: Context<'a>>(&mut self, _cx: &mut C, n: f64) -> NeonResult<()> {
+///         self.0 += n;
+///         Ok(())
+///     }
+///
+///     fn visit_string<C: Context<'a>>(
+///         &mut self,
+///         cx: &mut C,
+///         s: Handle<'a, JsString>,
+///     ) -> NeonResult<()> {
+///         self.0 += s.size(cx) as f64;
+///         Ok(())
+///     }
+///
+///     fn visit_other<C: Context<'a>>(
+///         &mut self,
+///         _cx: &mut C,
+///         _value: Handle<'a, JsValue>,
+///     ) -> NeonResult<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// fn weight(mut cx: FunctionContext) -> JsResult<JsNumber> {
+///     let mut weight = Weight(0.0);
+///
+///     for value in cx.argument::<JsArray>(0)?.to_vec(&mut cx)? {
+///         value.visit(&mut cx, &mut weight)?;
+///     }
+///
+///     Ok(cx.number(weight.0))
+/// }
+/// ```
+pub trait ValueVisitor<'a> {
+    /// The result of visiting a value.
+    type Output;
+
+    /// Visits `undefined`.
+    fn visit_undefined<C: Context<'a>>(&mut self, cx: &mut C) -> NeonResult<Self::Output> {
+        let value = cx.undefined().upcast();
+
+        self.visit_other(cx, value)
+    }
+
+    /// Visits `null`.
+    fn visit_null<C: Context<'a>>(&mut self, cx: &mut C) -> NeonResult<Self::Output> {
+        let value = cx.null().upcast();
+
+        self.visit_other(cx, value)
+    }
+
+    /// Visits a boolean.
+    fn visit_boolean<C: Context<'a>>(&mut self, cx: &mut C, b: bool) -> NeonResult<Self::Output> {
+        let value = cx.boolean(b).upcast();
+
+        self.visit_other(cx, value)
+    }
+
+    /// Visits a number.
+    fn visit_number<C: Context<'a>>(&mut self, cx: &mut C, n: f64) -> NeonResult<Self::Output> {
+        let value = cx.number(n).upcast();
+
+        self.visit_other(cx, value)
+    }
+
+    /// Visits a string.
+    fn visit_string<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        s: Handle<'a, JsString>,
+    ) -> NeonResult<Self::Output> {
+        self.visit_other(cx, s.upcast())
+    }
+
+    /// Visits a symbol.
+    fn visit_symbol<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        s: Handle<'a, JsSymbol>,
+    ) -> NeonResult<Self::Output> {
+        self.visit_other(cx, s.upcast())
+    }
+
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+    /// Visits a `BigInt`.
+    fn visit_bigint<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        n: Handle<'a, JsBigInt>,
+    ) -> NeonResult<Self::Output> {
+        self.visit_other(cx, n.upcast())
+    }
+
+    /// Visits an array.
+    fn visit_array<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        array: Handle<'a, JsArray>,
+    ) -> NeonResult<Self::Output> {
+        self.visit_other(cx, array.upcast())
+    }
+
+    /// Visits a function.
+    fn visit_function<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        f: Handle<'a, JsFunction>,
+    ) -> NeonResult<Self::Output> {
+        self.visit_other(cx, f.upcast())
+    }
+
+    /// Visits an object that is not an array or a function.
+    fn visit_object<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        obj: Handle<'a, JsObject>,
+    ) -> NeonResult<Self::Output> {
+        self.visit_other(cx, obj.upcast())
+    }
+
+    /// Visits any value that is not handled by another method.
+    fn visit_other<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        value: Handle<'a, JsValue>,
+    ) -> NeonResult<Self::Output>;
+}
+
+pub(crate) fn visit<'a, C, V>(
+    cx: &mut C,
+    local: raw::Local,
+    visitor: &mut V,
+) -> NeonResult<V::Output>
+where
+    C: Context<'a>,
+    V: ValueVisitor<'a>,
+{
+    let env = cx.env().to_raw();
+
+    // Safety: Each handle is only created for a value of its type
+    match unsafe { sys::tag::value_type(env, local) } {
+        sys::ValueType::Undefined => visitor.visit_undefined(cx),
+        sys::ValueType::Null => visitor.visit_null(cx),
+        sys::ValueType::Boolean => {
+            let b = unsafe { sys::primitive::boolean_value(env, local) };
+
+            visitor.visit_boolean(cx, b)
+        }
+        sys::ValueType::Number => {
+            let n = unsafe { sys::primitive::number_value(env, local) };
+
+            visitor.visit_number(cx, n)
+        }
+        sys::ValueType::String => visitor.visit_string(cx, handle(cx.env(), local)),
+        sys::ValueType::Symbol => visitor.visit_symbol(cx, handle(cx.env(), local)),
+        #[cfg(feature = "napi-6")]
+        sys::ValueType::BigInt => visitor.visit_bigint(cx, handle(cx.env(), local)),
+        sys::ValueType::Function => visitor.visit_function(cx, handle(cx.env(), local)),
+        sys::ValueType::Object if unsafe { sys::tag::is_array(env, local) } => {
+            visitor.visit_array(cx, handle(cx.env(), local))
+        }
+        sys::ValueType::Object => visitor.visit_object(cx, handle(cx.env(), local)),
+        // Without N-API 6, `BigInt` values are also passed to `visit_other`
+        _ => visitor.visit_other(cx, handle(cx.env(), local)),
+    }
+}
+
+fn handle<'a, T: Managed>(env: Env, local: raw::Local) -> Handle<'a, T> {
+    Handle::new_internal(T::from_raw(env, local))
+}
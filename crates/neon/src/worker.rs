@@ -0,0 +1,236 @@
+//! Interoperability with [worker threads](https://nodejs.org/api/worker_threads.html).
+//!
+//! A [`Port`] wraps a [`MessagePort`](https://nodejs.org/api/worker_threads.html#class-messageport),
+//! allowing Rust threads to post messages to the JavaScript environment that owns the
+//! port, e.g., to deliver the results of work requested by a worker directly to it.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate::{
+    context::{Context, FunctionContext, TaskContext},
+    event::{Channel, JoinHandle, SendError},
+    handle::{Handle, Root},
+    object::Object,
+    result::NeonResult,
+    types::{JsArray, JsFunction, JsObject, JsValue},
+};
+
+/// A handle to a JavaScript
+/// [`MessagePort`](https://nodejs.org/api/worker_threads.html#class-messageport) that
+/// may be sent across threads.
+///
+/// Messages are posted on the JavaScript thread that owns the port; to post a
+/// message to a worker, create the `Port` in the worker from one end of a
+/// [`MessageChannel`](https://nodejs.org/api/worker_threads.html#class-messagechannel)
+/// and post to the other end. The port is released once every clone of the handle
+/// has been dropped.
+///
+/// A `Port` does not keep the event loop of the environment that owns it alive;
+/// listening for messages with [`Port::on_message`] does, as in JavaScript.
+///
+/// # Example
+///
+/// ```
+/// # use neon::prelude::*;
+/// use neon::worker::Port;
+///
+/// // Computes a value on a Rust thread and posts it to the port
+/// fn compute(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let port = cx.argument::<JsObject>(0)?;
+///     let port = Port::from_object(&mut cx, port)?;
+///
+///     std::thread::spawn(move || {
+///         let result = 6 * 7;
+///
+///         let _ = port.post(move |cx| Ok(cx.number(result).upcast()));
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Port {
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for Port {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Port")
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl Port {
+    /// Creates a handle to `port`.
+    ///
+    /// Throws a `TypeError` if `port` is not a `MessagePort`.
+    pub fn from_object<'a, C: Context<'a>>(cx: &mut C, port: Handle<JsObject>) -> NeonResult<Self> {
+        port.get::<JsFunction, _, _>(cx, "postMessage")?;
+
+        let closed = Arc::new(AtomicBool::new(false));
+        let on_close = {
+            let closed = Arc::clone(&closed);
+
+            JsFunction::new(cx, move |mut cx| {
+                closed.store(true, Ordering::Release);
+                Ok(cx.undefined())
+            })?
+        };
+
+        add_listener(cx, port, "close", on_close)?;
+
+        let mut channel = cx.channel();
+
+        // Holding a port should not keep the event loop alive
+        channel.unref(cx);
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                port: Some(port.root(cx)),
+                closed,
+                channel,
+            }),
+        })
+    }
+
+    /// Posts the message created by `f` to the port.
+    ///
+    /// `f` is called on the JavaScript thread that owns the port. The returned
+    /// [`JoinHandle`] may be used to wait for the message to be posted. Returns an
+    /// error if the environment that owns the port has shut down.
+    pub fn post<F>(&self, f: F) -> Result<JoinHandle<()>, SendError>
+    where
+        F: for<'a> FnOnce(&mut TaskContext<'a>) -> NeonResult<Handle<'a, JsValue>> + Send + 'static,
+    {
+        self.post_with_transfer(move |cx| Ok((f(cx)?, Vec::new())))
+    }
+
+    /// Posts the message created by `f` to the port, transferring ownership of the
+    /// values in the transfer list, e.g., `ArrayBuffer`s and `MessagePort`s, to the
+    /// receiver.
+    ///
+    /// `f` returns the message and its transfer list. See [`Port::post`].
+    pub fn post_with_transfer<F>(&self, f: F) -> Result<JoinHandle<()>, SendError>
+    where
+        F: for<'a> FnOnce(
+                &mut TaskContext<'a>,
+            )
+                -> NeonResult<(Handle<'a, JsValue>, Vec<Handle<'a, JsValue>>)>
+            + Send
+            + 'static,
+    {
+        let port = self.clone();
+
+        self.inner.channel.try_send(move |mut cx| {
+            let (message, transfer) = f(&mut cx)?;
+            let port = port.to_inner(&mut cx);
+            let post_message = port.get::<JsFunction, _, _>(&mut cx, "postMessage")?;
+            let list = JsArray::new(&mut cx, transfer.len() as u32);
+
+            for (i, value) in transfer.into_iter().enumerate() {
+                list.set(&mut cx, i as u32, value)?;
+            }
+
+            post_message.exec(&mut cx, port, [message, list.upcast()])
+        })
+    }
+
+    /// Calls `f` with each message received by the port.
+    ///
+    /// Like adding a `message` listener in JavaScript, this starts the port and keeps
+    /// the event loop alive until the port is closed. If `f` throws, the exception is
+    /// reported as an uncaught exception.
+    pub fn on_message<'a, C, F>(&self, cx: &mut C, mut f: F) -> NeonResult<()>
+    where
+        C: Context<'a>,
+        F: for<'b> FnMut(&mut FunctionContext<'b>, Handle<'b, JsValue>) -> NeonResult<()> + 'static,
+    {
+        let listener = JsFunction::new_mut(cx, move |mut cx| {
+            let message = cx.argument::<JsValue>(0)?;
+
+            f(&mut cx, message)?;
+
+            Ok(cx.undefined())
+        })?;
+        let port = self.to_inner(cx);
+
+        add_listener(cx, port, "message", listener)
+    }
+
+    /// Calls `f` when the port is closed, either by closing it or the port at the other
+    /// end of the channel, or when the environment at the other end exits.
+    pub fn on_close<'a, C, F>(&self, cx: &mut C, f: F) -> NeonResult<()>
+    where
+        C: Context<'a>,
+        F: for<'b> FnOnce(&mut FunctionContext<'b>) -> NeonResult<()> + 'static,
+    {
+        let mut f = Some(f);
+        let listener = JsFunction::new_mut(cx, move |mut cx| {
+            if let Some(f) = f.take() {
+                f(&mut cx)?;
+            }
+
+            Ok(cx.undefined())
+        })?;
+        let port = self.to_inner(cx);
+
+        add_listener(cx, port, "close", listener)
+    }
+
+    /// Indicates whether the port has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.inner.closed.load(Ordering::Acquire)
+    }
+
+    /// Returns the `MessagePort` object.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is called from a different JavaScript thread than the
+    /// one that owns the port.
+    pub fn to_inner<'a, C: Context<'a>>(&self, cx: &mut C) -> Handle<'a, JsObject> {
+        // `unwrap` will not fail because `port` is only `None` during `Drop`
+        self.inner.port.as_ref().unwrap().to_inner(cx)
+    }
+}
+
+// Adds a listener with `port.on(event, listener)`
+fn add_listener<'a, C: Context<'a>>(
+    cx: &mut C,
+    port: Handle<JsObject>,
+    event: &str,
+    listener: Handle<JsFunction>,
+) -> NeonResult<()> {
+    let on = port.get::<JsFunction, _, _>(cx, "on")?;
+    let event = cx.string(event);
+
+    on.exec(cx, port, [event.upcast(), listener.upcast()])
+}
+
+struct Inner {
+    // `Option` so that the `Root` can be moved to the JavaScript thread on `Drop`.
+    // It will *always* be `Some` until then.
+    port: Option<Root<JsObject>>,
+    closed: Arc<AtomicBool>,
+    channel: Channel,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        if let Some(port) = self.port.take() {
+            // `Channel::try_send` will only fail if the environment has shutdown.
+            // In that case, the teardown will perform clean-up.
+            let _ = self.channel.try_send(move |mut cx| {
+                port.drop(&mut cx);
+                Ok(())
+            });
+        }
+    }
+}
@@ -0,0 +1,30 @@
+// Microbenchmark for reading function arguments from Rust.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 1_000_000;
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  for (let i = 0; i < ITERATIONS / 10; i++) {
+    f();
+  }
+
+  const start = process.hrtime.bigint();
+
+  for (let i = 0; i < ITERATIONS; i++) {
+    f();
+  }
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(elapsed / ITERATIONS).toFixed(1)} ns/call`);
+}
+
+bench("noop()", () => addon.noop());
+bench("noop(1, 2, 3, 4, 5, 6)", () => addon.noop(1, 2, 3, 4, 5, 6));
+bench("sum_six_arguments(1, 2, 3, 4, 5, 6)", () =>
+  addon.sum_six_arguments(1, 2, 3, 4, 5, 6)
+);
@@ -0,0 +1,31 @@
+// Microbenchmark comparing element-by-element reads of a large array against the
+// bulk `JsArray::read_into` and `JsArray::to_vec_f64` accessors.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 100;
+const LEN = 100_000;
+
+const array = Array.from({ length: LEN }, (_, i) => i);
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  f(array);
+
+  const start = process.hrtime.bigint();
+
+  for (let i = 0; i < ITERATIONS; i++) {
+    f(array);
+  }
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(elapsed / ITERATIONS / LEN).toFixed(1)} ns/element`);
+}
+
+bench("array.get_value(cx, i) loop", addon.count_js_array_naive);
+bench("array.read_into(cx, .., &mut vec)", addon.count_js_array_read_into);
+bench("array.get::<JsNumber>(cx, i).value(cx) loop", addon.sum_js_array_naive);
+bench("array.to_vec_f64(cx)", addon.js_array_to_vec_f64);
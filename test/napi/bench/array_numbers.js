@@ -0,0 +1,39 @@
+// Microbenchmark comparing element-by-element conversion of a large array of
+// numbers against the `JsArray::to_vec_f64` and `Context::array_from_f64_slice`
+// fast paths.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 20;
+const LEN = 1_000_000;
+
+const array = Array.from({ length: LEN }, (_, i) => i);
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  f();
+
+  const start = process.hrtime.bigint();
+
+  for (let i = 0; i < ITERATIONS; i++) {
+    f();
+  }
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(elapsed / ITERATIONS / LEN).toFixed(1)} ns/element`);
+}
+
+bench("array.to_vec(cx) + downcast", () =>
+  addon.sum_js_array_to_vec_downcast(array)
+);
+bench("array.to_vec_f64(cx)", () => addon.js_array_to_vec_f64(array));
+bench("array.to_vec_i32(cx)", () => addon.js_array_to_vec_i32(array));
+bench("JsArray::from_slice(cx, &handles)", () =>
+  addon.js_array_from_number_handles(LEN)
+);
+bench("cx.array_from_f64_slice(&values)", () =>
+  addon.js_array_from_f64_slice(LEN)
+);
@@ -0,0 +1,56 @@
+// Microbenchmark comparing `BufferPool::alloc` with `cx.buffer(len)` for small
+// allocations.
+//
+// Allocations are made in batches, yielding to the event loop in between, as a
+// streaming parser would; this gives finalizers a chance to run so that pool slabs
+// can be recycled.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const BATCHES = 200;
+const BATCH_SIZE = 1000;
+const SIZES = [1024, 2048, 3072, 4096];
+
+function tick() {
+  return new Promise((resolve) => setImmediate(resolve));
+}
+
+async function bench(name, f) {
+  // Warm up the JIT before measuring
+  for (let i = 0; i < BATCH_SIZE * 10; i++) {
+    f(SIZES[i % SIZES.length]);
+  }
+
+  await tick();
+
+  let elapsed = 0n;
+
+  for (let batch = 0; batch < BATCHES; batch++) {
+    const start = process.hrtime.bigint();
+
+    for (let i = 0; i < BATCH_SIZE; i++) {
+      f(SIZES[i % SIZES.length]);
+    }
+
+    elapsed += process.hrtime.bigint() - start;
+
+    await tick();
+  }
+
+  const allocs = BATCHES * BATCH_SIZE;
+  const ns = Number(elapsed);
+
+  console.log(
+    `${name}: ${(ns / allocs).toFixed(1)} ns/alloc, ` +
+      `${((allocs / ns) * 1e9).toFixed(0)} allocs/s`
+  );
+}
+
+(async () => {
+  const pool = addon.create_buffer_pool(256 * 1024, 64);
+
+  await bench("alloc_buffer", (len) => addon.alloc_buffer(len));
+  await bench("buffer_pool_alloc", (len) => addon.buffer_pool_alloc(pool, len));
+})();
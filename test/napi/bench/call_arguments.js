@@ -0,0 +1,27 @@
+// Microbenchmark comparing arguments for calling a JavaScript function from Rust
+// collected in a `Vec` with arguments passed as a tuple, which are collected into
+// an array on the stack instead of allocating.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 1_000_000;
+
+function noop() {}
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  f(noop, ITERATIONS / 10);
+
+  const start = process.hrtime.bigint();
+
+  f(noop, ITERATIONS);
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(elapsed / ITERATIONS).toFixed(1)} ns/call`);
+}
+
+bench("f.exec(cx, this, vec![a, b, c])", addon.call_with_vec_args);
+bench("f.exec(cx, this, (a, b, c))", addon.call_with_tuple_args);
@@ -0,0 +1,57 @@
+// Microbenchmark of calls from Rust with increasing numbers of arguments. Argument
+// lists of up to 8 values are stored inline, on the stack, and longer lists spill
+// to the heap.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 1_000_000;
+
+function noop() {}
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  f(ITERATIONS / 10);
+
+  const start = process.hrtime.bigint();
+
+  f(ITERATIONS);
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(elapsed / ITERATIONS).toFixed(1)} ns/call`);
+}
+
+for (const argc of [0, 2, 8, 9]) {
+  bench(`f.call_with(&cx) with ${argc} arguments`, (n) =>
+    addon.call_with_options_args(noop, n, argc)
+  );
+}
+
+const log = console.log;
+
+for (const argc of [0, 2, 8, 9]) {
+  let elapsed;
+
+  // Replace `console.log` while measuring, which `cx.console()` looks up on each call
+  console.log = noop;
+
+  try {
+    addon.console_log_args(ITERATIONS / 10, argc);
+
+    const start = process.hrtime.bigint();
+
+    addon.console_log_args(ITERATIONS, argc);
+
+    elapsed = Number(process.hrtime.bigint() - start);
+  } finally {
+    console.log = log;
+  }
+
+  console.log(
+    `cx.console().log(..) with ${argc} arguments: ${(
+      elapsed / ITERATIONS
+    ).toFixed(1)} ns/call`
+  );
+}
@@ -0,0 +1,27 @@
+// Microbenchmark comparing calls to a notification callback with an explicit
+// `undefined` and an empty argument list against the `JsFunction::call0` fast path,
+// which passes `undefined` directly to Node-API.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 1_000_000;
+
+function noop() {}
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  f(noop, ITERATIONS / 10);
+
+  const start = process.hrtime.bigint();
+
+  f(noop, ITERATIONS);
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(elapsed / ITERATIONS).toFixed(1)} ns/call`);
+}
+
+bench("f.exec(cx, cx.undefined(), [])", addon.call_with_empty_args);
+bench("f.call0(cx)", addon.call0_loop);
@@ -0,0 +1,29 @@
+// Microbenchmark comparing building an object property-by-property from Rust
+// with parsing it from a JSON string.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 1_000;
+const KEYS = 1_000;
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  for (let i = 0; i < ITERATIONS / 10; i++) {
+    f();
+  }
+
+  const start = process.hrtime.bigint();
+
+  for (let i = 0; i < ITERATIONS; i++) {
+    f();
+  }
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(elapsed / ITERATIONS / 1000).toFixed(1)} µs/call`);
+}
+
+bench(`build_object_graph(${KEYS})`, () => addon.build_object_graph(KEYS));
+bench(`build_object_json(${KEYS})`, () => addon.build_object_json(KEYS));
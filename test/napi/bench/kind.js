@@ -0,0 +1,28 @@
+// Microbenchmark comparing `Value::kind` with chained `is_a` checks.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 1_000_000;
+const VALUES = [undefined, null, true, 1, "a", () => {}, {}];
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  for (let i = 0; i < ITERATIONS / 10; i++) {
+    f(VALUES[i % VALUES.length]);
+  }
+
+  const start = process.hrtime.bigint();
+
+  for (let i = 0; i < ITERATIONS; i++) {
+    f(VALUES[i % VALUES.length]);
+  }
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(elapsed / ITERATIONS).toFixed(1)} ns/call`);
+}
+
+bench("value_kind", addon.value_kind);
+bench("value_kind_with_is_a", addon.value_kind_with_is_a);
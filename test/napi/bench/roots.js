@@ -0,0 +1,65 @@
+// Microbenchmark comparing a single `RootSet` with individual `Root`s for
+// retaining many objects.
+//
+// Each variant runs in a separate process so that the heap left behind by one
+// does not affect the other.
+//
+// Run with `npm run bench` after building the addon.
+
+const { execFileSync } = require("child_process");
+
+const COUNT = 50_000;
+const ROUNDS = 20;
+
+const VARIANTS = {
+  Root: "retain_with_roots",
+  RootSet: "retain_with_root_set",
+};
+
+function time(f) {
+  const start = process.hrtime.bigint();
+
+  f();
+
+  return Number(process.hrtime.bigint() - start);
+}
+
+function bench(name) {
+  const addon = require("..");
+  const retain = addon[VARIANTS[name]];
+
+  let retaining = 0;
+  let collecting = 0;
+
+  for (let round = 0; round < ROUNDS; round++) {
+    let retained;
+
+    retaining += time(() => {
+      retained = retain(Array.from({ length: COUNT }, (_, i) => ({ i })));
+    });
+
+    // Time garbage collection while the objects are only retained from Rust
+    collecting += time(() => global.gc());
+
+    retained = null;
+    global.gc();
+  }
+
+  console.log(
+    `${name}: retain ${(retaining / ROUNDS / 1e6).toFixed(2)} ms, ` +
+      `gc ${(collecting / ROUNDS / 1e6).toFixed(2)} ms ` +
+      `(${COUNT} objects)`
+  );
+}
+
+const [variant] = process.argv.slice(2);
+
+if (variant) {
+  bench(variant);
+} else {
+  for (const name of Object.keys(VARIANTS)) {
+    execFileSync(process.execPath, ["--expose-gc", __filename, name], {
+      stdio: "inherit",
+    });
+  }
+}
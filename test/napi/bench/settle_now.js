@@ -0,0 +1,31 @@
+// Microbenchmark measuring the latency of awaiting a promise that is settled from
+// the JavaScript thread immediately with `Deferred::try_settle_now`, compared with
+// settling it on a `Channel`, which waits for a turn of the event loop.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const N = 100_000;
+
+async function bench(name, now) {
+  // Warm up the JIT before measuring
+  for (let i = 0; i < N; i++) {
+    await addon.deferred_settle_module_channel(i, now);
+  }
+
+  const start = process.hrtime.bigint();
+
+  for (let i = 0; i < N; i++) {
+    await addon.deferred_settle_module_channel(i, now);
+  }
+
+  const ns = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(ns / N / 1000).toFixed(2)} µs/await`);
+}
+
+(async () => {
+  await bench("deferred.try_settle_now", true);
+  await bench("deferred.settle_with", false);
+})();
@@ -0,0 +1,46 @@
+// Microbenchmark comparing building a 16 MB string from many fragments with
+// `JsStringBuilder` against concatenating into a Rust `String` and copying it.
+//
+// The builder returns a rope, which the engine flattens, i.e., copies, the first
+// time most operations read it; both timings are reported.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 20;
+const SIZE = 16 * 1024 * 1024;
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  for (let i = 0; i < ITERATIONS / 10; i++) {
+    f();
+  }
+
+  const start = process.hrtime.bigint();
+
+  for (let i = 0; i < ITERATIONS; i++) {
+    f();
+  }
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(elapsed / ITERATIONS / 1e6).toFixed(2)} ms/call`);
+}
+
+for (const [label, fragment] of [
+  ["ASCII", "1234,example,0.5\n"],
+  ["UTF-8", "1234,exämple,0.5\n"],
+]) {
+  const count = Math.floor(SIZE / Buffer.byteLength(fragment));
+
+  bench(`repeat_string_naive(${label})`, () =>
+    addon.repeat_string_naive(fragment, count)
+  );
+  bench(`repeat_string_builder(${label})`, () =>
+    addon.repeat_string_builder(fragment, count)
+  );
+  bench(`repeat_string_builder(${label}) + flatten`, () =>
+    addon.repeat_string_builder(fragment, count).charCodeAt(SIZE / 2)
+  );
+}
@@ -0,0 +1,43 @@
+// Microbenchmark comparing the per-call overhead of `ThreadsafeFunction::call`
+// with `Channel::send` when a background thread calls the same JavaScript function
+// with a small message many times. Both queues are unbounded, so the sending thread
+// never blocks.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const N = 1_000_000;
+
+function run(f) {
+  return new Promise((resolve) => {
+    let received = 0;
+
+    f(N, () => {
+      if (++received === N) {
+        resolve();
+      }
+    });
+  });
+}
+
+async function bench(name, f) {
+  // Warm up the JIT before measuring
+  await run(f);
+
+  const start = process.hrtime.bigint();
+
+  await run(f);
+
+  const ns = Number(process.hrtime.bigint() - start);
+
+  console.log(
+    `${name}: ${(ns / N).toFixed(1)} ns/call, ` +
+      `${((N / ns) * 1e9).toFixed(0)} calls/s`
+  );
+}
+
+(async () => {
+  await bench("channel_send", addon.channel_count);
+  await bench("threadsafe_function_call", addon.tsfn_count);
+})();
@@ -0,0 +1,37 @@
+// Microbenchmark comparing scattered reads of a large typed array with
+// `JsTypedArray::get`, with a slice borrowed for each access, and with a single
+// slice borrowed for all accesses.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 100;
+const LEN = 1_000_000;
+const ACCESSES = 100_000;
+
+const array = Float64Array.from({ length: LEN }, (_, i) => i);
+const indices = Uint32Array.from({ length: ACCESSES }, () =>
+  Math.floor(Math.random() * LEN)
+);
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  f(array, indices);
+
+  const start = process.hrtime.bigint();
+
+  for (let i = 0; i < ITERATIONS; i++) {
+    f(array, indices);
+  }
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(
+    `${name}: ${(elapsed / ITERATIONS / ACCESSES).toFixed(1)} ns/access`
+  );
+}
+
+bench("array.get(cx, i)", addon.sum_scattered_with_get);
+bench("array.as_slice(cx)[i]", addon.sum_scattered_with_slice);
+bench("held array.as_slice(cx)", addon.sum_scattered_with_held_slice);
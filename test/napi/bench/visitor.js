@@ -0,0 +1,39 @@
+// Microbenchmark comparing `ValueVisitor` dispatch with chained `is_a` checks
+// over a large array of mixed values.
+//
+// Run with `npm run bench` after building the addon.
+
+const addon = require("..");
+
+const ITERATIONS = 100;
+const LEN = 100_000;
+
+const SHAPES = [
+  (i) => `s${i}`,
+  (i) => i,
+  (i) => [i],
+  (i) => ({ i }),
+  () => null,
+];
+
+const array = Array.from({ length: LEN }, (_, i) =>
+  SHAPES[i % SHAPES.length](i)
+);
+
+function bench(name, f) {
+  // Warm up the JIT before measuring
+  f(array);
+
+  const start = process.hrtime.bigint();
+
+  for (let i = 0; i < ITERATIONS; i++) {
+    f(array);
+  }
+
+  const elapsed = Number(process.hrtime.bigint() - start);
+
+  console.log(`${name}: ${(elapsed / ITERATIONS / LEN).toFixed(1)} ns/element`);
+}
+
+bench("value.visit(cx, &mut visitor)", addon.count_shapes_with_visitor);
+bench("value.is_a::<T, _>(cx) chain", addon.count_shapes_with_downcast);
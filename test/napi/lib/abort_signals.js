@@ -0,0 +1,65 @@
+const addon = require("..");
+const assert = require("chai").assert;
+const { getEventListeners } = require("events");
+
+describe("AbortSignal", function () {
+  it("observes an abort from a thread", async function () {
+    const controller = new AbortController();
+    const result = addon.abort_signal_count(controller.signal);
+
+    setTimeout(() => controller.abort("stop"), 10);
+
+    const [n, reason] = await result;
+
+    assert.isAbove(n, 0);
+    assert.strictEqual(reason, "stop");
+  });
+
+  it("observes an already aborted signal", async function () {
+    const [n, reason] = await addon.abort_signal_count(
+      AbortSignal.abort("stop")
+    );
+
+    assert.strictEqual(n, 0);
+    assert.strictEqual(reason, "stop");
+  });
+
+  it("calls registered callbacks with the reason", function (cb) {
+    const controller = new AbortController();
+    const reason = new Error("stop");
+
+    addon.abort_signal_register(controller.signal, (actual) => {
+      assert.strictEqual(actual, reason);
+      cb();
+    });
+
+    setTimeout(() => controller.abort(reason), 10);
+  });
+
+  it("calls callbacks registered on an aborted signal", function (cb) {
+    addon.abort_signal_register(AbortSignal.abort(), (reason) => {
+      assert.instanceOf(reason, Error);
+      assert.strictEqual(reason.name, "AbortError");
+      cb();
+    });
+  });
+
+  it("removes the listener when dropped", function (cb) {
+    const controller = new AbortController();
+
+    addon.abort_signal_drop(controller.signal);
+    assert.strictEqual(getEventListeners(controller.signal, "abort").length, 1);
+
+    setTimeout(() => {
+      assert.strictEqual(
+        getEventListeners(controller.signal, "abort").length,
+        0
+      );
+      cb();
+    }, 10);
+  });
+
+  it("throws if the object is not an AbortSignal", function () {
+    assert.throws(() => addon.abort_signal_drop({}), TypeError);
+  });
+});
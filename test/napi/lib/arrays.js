@@ -21,4 +21,266 @@ describe("JsArray", function () {
   it("returns undefined when accessing outside JsArray bounds", function () {
     assert.strictEqual(addon.read_js_array([]), undefined);
   });
+
+  it("can push onto a JsArray", function () {
+    var array = [1, 2];
+
+    assert.strictEqual(addon.push_js_array(array, 3), array);
+    assert.deepEqual(array, [1, 2, 3]);
+    assert.deepEqual(addon.push_js_array([], "a"), ["a"]);
+  });
+
+  it("can construct a JsArray from a slice", function () {
+    assert.deepEqual(addon.js_array_from_arguments(), []);
+    assert.deepEqual(addon.js_array_from_arguments(1, "a", null), [
+      1,
+      "a",
+      null,
+    ]);
+  });
+
+  it("can construct a large JsArray from a slice", function () {
+    var array = addon.js_array_from_range(5000);
+
+    assert.strictEqual(array.length, 5000);
+    assert.strictEqual(array[0], 0);
+    assert.strictEqual(array[1024], 1024);
+    assert.strictEqual(array[4999], 4999);
+  });
+
+  it("can construct a JsArray with capacity", function () {
+    assert.deepEqual(addon.js_array_with_capacity(0), []);
+    assert.deepEqual(addon.js_array_with_capacity(3), ["0", "1", "2"]);
+  });
+
+  it("fills an array created with capacity", function () {
+    const array = addon.js_array_with_capacity(1500);
+
+    assert.strictEqual(array.length, 1500);
+    assert.strictEqual(array[0], "0");
+    assert.strictEqual(array[1499], "1499");
+    assert.strictEqual(Object.keys(array).length, 1500);
+  });
+
+  it("can iterate a JsArray", function () {
+    assert.deepEqual(addon.iter_js_array([]), []);
+    assert.deepEqual(addon.iter_js_array([1, "a", null]), [1, "a", null]);
+  });
+
+  it("yields undefined for holes when iterating a JsArray", function () {
+    // eslint-disable-next-line no-sparse-arrays
+    var array = addon.iter_js_array([1, , 3]);
+
+    assert.strictEqual(array.length, 3);
+    assert.isTrue(1 in array);
+    assert.strictEqual(array[1], undefined);
+  });
+
+  it("propagates exceptions from getters when iterating a JsArray", function () {
+    var array = [1];
+
+    Object.defineProperty(array, 0, {
+      get() {
+        throw new Error("getter failed");
+      },
+    });
+
+    assert.throws(() => addon.iter_js_array(array), /getter failed/);
+  });
+
+  it("reads the length once when iterating a JsArray", function () {
+    var array = [1, 2, 3];
+    var result = addon.iter_js_array_with_callback(array, (x) => {
+      if (x === 1) {
+        array.push(4);
+        array[2] = 30;
+      }
+    });
+
+    assert.deepEqual(result, [1, 2, 30]);
+  });
+
+  it("yields undefined for removed elements when iterating a JsArray", function () {
+    var array = [1, 2, 3];
+    var result = addon.iter_js_array_with_callback(array, () => {
+      array.length = 1;
+    });
+
+    assert.deepEqual(result, [1, undefined, undefined]);
+  });
+
+  it("processes a large array in chunks", function () {
+    const len = 5000000;
+    const array = addon.chunked_range_array(len, 10000);
+
+    assert.strictEqual(array.length, len);
+    assert.strictEqual(array[0], 0);
+    assert.strictEqual(array[len - 1], len - 1);
+    assert.strictEqual(addon.chunked_sum(array, 10000), (len * (len - 1)) / 2);
+  });
+
+  it("calls the chunk callback with each range", function () {
+    const ranges = [];
+
+    addon.chunked_ranges(10, 4, (start, end) => ranges.push([start, end]));
+
+    assert.deepEqual(ranges, [
+      [0, 4],
+      [4, 8],
+      [8, 10],
+    ]);
+  });
+
+  it("treats a chunk size of zero as one", function () {
+    const ranges = [];
+
+    addon.chunked_ranges(2, 0, (start, end) => ranges.push([start, end]));
+
+    assert.deepEqual(ranges, [
+      [0, 1],
+      [1, 2],
+    ]);
+  });
+
+  it("stops processing chunks when a chunk throws", function () {
+    const ranges = [];
+
+    assert.throws(
+      () =>
+        addon.chunked_ranges(10, 4, (start, end) => {
+          ranges.push([start, end]);
+          throw new Error("stop");
+        }),
+      /stop/
+    );
+
+    assert.deepEqual(ranges, [[0, 4]]);
+  });
+
+  it("reads a range of a JsArray into a Vec", function () {
+    assert.deepEqual(addon.read_js_array_into([1, 2, 3, 4], 1, 3), [
+      "prefix",
+      2,
+      3,
+    ]);
+  });
+
+  it("clamps the range when reading a JsArray into a Vec", function () {
+    assert.deepEqual(addon.read_js_array_into([1, 2], 1, 10), ["prefix", 2]);
+    assert.deepEqual(addon.read_js_array_into([1, 2], 5, 10), ["prefix"]);
+  });
+
+  it("propagates exceptions from getters when reading a JsArray", function () {
+    const array = [1, 2];
+
+    Object.defineProperty(array, 1, {
+      get() {
+        throw new Error("getter");
+      },
+    });
+
+    assert.throws(() => addon.read_js_array_into(array, 0, 2), /getter/);
+  });
+
+  it("writes a slice into a JsArray at an offset", function () {
+    assert.deepEqual(
+      addon.write_js_array_from([1, 2, 3], 1, ["a", "b", "c"]),
+      [1, "a", "b", "c"]
+    );
+  });
+
+  it("writes a large slice into a JsArray", function () {
+    const values = Array.from({ length: 5000 }, (_, i) => i);
+    const array = addon.write_js_array_from([], 0, values);
+
+    assert.deepEqual(array, values);
+  });
+
+  it("converts a JsArray to a Vec of numbers", function () {
+    const values = Array.from({ length: 5000 }, (_, i) => i);
+
+    assert.strictEqual(addon.js_array_to_vec_f64(values), 12497500);
+    assert.strictEqual(addon.js_array_to_vec_f64([]), 0);
+  });
+
+  it("converts a JsArray to a Vec of strings", function () {
+    assert.strictEqual(addon.js_array_to_vec_string(["a", "b", "c"]), "a,b,c");
+  });
+
+  it("converts a JsArray to a Vec of integers", function () {
+    assert.strictEqual(
+      addon.js_array_to_vec_i32([-2147483648, 1, -0]),
+      -2147483647
+    );
+    assert.strictEqual(addon.js_array_to_vec_u32([4294967295, 1]), 4294967296);
+  });
+
+  it("converts a JsArray to a Vec of booleans", function () {
+    assert.strictEqual(addon.js_array_count_true([true, false, true]), 2);
+    assert.throws(
+      () => addon.js_array_count_true([true, 0]),
+      TypeError,
+      /^expected boolean at index 1, got number$/
+    );
+  });
+
+  it("throws on numbers outside the range of an integer type", function () {
+    assert.throws(
+      () => addon.js_array_to_vec_i32([1, 2.5]),
+      RangeError,
+      /^expected i32 at index 1, got 2.5$/
+    );
+    assert.throws(
+      () => addon.js_array_to_vec_i32([2147483648]),
+      RangeError,
+      /^expected i32 at index 0, got 2147483648$/
+    );
+    assert.throws(
+      () => addon.js_array_to_vec_u32([0, -1]),
+      RangeError,
+      /^expected u32 at index 1, got -1$/
+    );
+    assert.throws(
+      () => addon.js_array_to_vec_u32([NaN]),
+      RangeError,
+      /^expected u32 at index 0, got NaN$/
+    );
+  });
+
+  it("does not coerce elements", function () {
+    assert.throws(
+      () => addon.js_array_to_vec_f64([1, "2"]),
+      TypeError,
+      /^expected number at index 1, got string$/
+    );
+    assert.throws(
+      () => addon.js_array_to_vec_f64([new Number(1)]),
+      TypeError,
+      /^expected number at index 0, got object \(instance of Number\)$/
+    );
+  });
+
+  it("creates a JsArray from a slice of numbers", function () {
+    assert.deepEqual(addon.js_array_from_f64_slice(5), [0, 0.5, 1, 1.5, 2]);
+    assert.deepEqual(addon.js_array_from_f64_slice(0), []);
+
+    const array = addon.js_array_from_f64_slice(5000);
+
+    assert.strictEqual(array.length, 5000);
+    assert.strictEqual(array[4999], 2499.5);
+  });
+
+  it("names the index of an element with the wrong type", function () {
+    assert.throws(
+      () => addon.js_array_to_vec_f64([1, 2, "three"]),
+      TypeError,
+      /^expected number at index 2, got string$/
+    );
+
+    assert.throws(
+      () => addon.js_array_to_vec_string(["a", new Map()]),
+      TypeError,
+      /^expected string at index 1, got object \(instance of Map\)$/
+    );
+  });
 });
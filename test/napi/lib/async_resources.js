@@ -0,0 +1,133 @@
+const addon = require("..");
+const assert = require("chai").assert;
+const async_hooks = require("async_hooks");
+
+describe("AsyncResource", function () {
+  let hook;
+  let events;
+
+  beforeEach(function () {
+    events = [];
+    hook = async_hooks.createHook({
+      init(id, type, _triggerId, resource) {
+        if (type.startsWith("neon:")) {
+          events.push({ event: "init", id, type, resource });
+        }
+      },
+      before(id) {
+        events.push({ event: "before", id });
+      },
+      after(id) {
+        events.push({ event: "after", id });
+      },
+      destroy(id) {
+        events.push({ event: "destroy", id });
+      },
+    });
+    hook.enable();
+  });
+
+  afterEach(function () {
+    hook.disable();
+  });
+
+  function assertCallback(type, expected, done) {
+    const triggerId = async_hooks.executionAsyncId();
+
+    return function (arg) {
+      try {
+        assert.strictEqual(arg, expected);
+
+        const init = events.find((e) => e.event === "init" && e.type === type);
+
+        assert.ok(init, "expected an init hook");
+        assert.strictEqual(async_hooks.executionAsyncId(), init.id);
+        assert.strictEqual(async_hooks.triggerAsyncId(), triggerId);
+        assert.ok(
+          events.some((e) => e.event === "before" && e.id === init.id),
+          "expected a before hook"
+        );
+
+        setImmediate(() => {
+          try {
+            assert.ok(
+              events.some((e) => e.event === "after" && e.id === init.id),
+              "expected an after hook"
+            );
+            done();
+          } catch (err) {
+            done(err);
+          }
+        });
+      } catch (err) {
+        done(err);
+      }
+    };
+  }
+
+  it("should call a function in the async context from a channel", function (done) {
+    const type = "neon:channel";
+
+    addon.async_resource_channel(type, assertCallback(type, "channel", done));
+  });
+
+  it("should call a function in the async context from a task", function (done) {
+    const type = "neon:task";
+
+    addon.async_resource_task(type, assertCallback(type, "task", done));
+  });
+
+  it("should call a function in a callback scope", function (done) {
+    const type = "neon:scope";
+    const resource = {};
+    const callback = assertCallback(type, "scope", done);
+
+    addon.async_resource_scope(
+      type,
+      (arg) => {
+        assert.strictEqual(async_hooks.executionAsyncResource(), resource);
+        callback(arg);
+      },
+      resource
+    );
+  });
+
+  it("should propagate AsyncLocalStorage to callbacks", function (done) {
+    const storage = new async_hooks.AsyncLocalStorage();
+    const store = {};
+
+    storage.run(store, () => {
+      addon.async_resource_channel("neon:storage", () => {
+        try {
+          assert.strictEqual(storage.getStore(), store);
+          done();
+        } catch (err) {
+          done(err);
+        }
+      });
+    });
+  });
+
+  it("should destroy a dropped resource", function (done) {
+    const type = "neon:drop";
+
+    addon.async_resource_drop(type);
+
+    const init = events.find((e) => e.event === "init" && e.type === type);
+
+    assert.ok(init, "expected an init hook");
+
+    // `destroy` hooks are emitted asynchronously
+    setTimeout(() => {
+      try {
+        assert.ok(
+          events.some((e) => e.event === "destroy" && e.id === init.id),
+          "expected a destroy hook"
+        );
+        done();
+      } catch (err) {
+        done(err);
+      }
+    }, 50);
+  });
+});
@@ -0,0 +1,139 @@
+const assert = require("assert");
+const { Worker } = require("worker_threads");
+
+const addon = require("..");
+
+// Blocks a worker in `Atomics.wait` and reports the result
+function waitInWorker(array, index, expected, timeout) {
+  const worker = new Worker(
+    `
+    const { parentPort, workerData } = require("worker_threads");
+    const { array, index, expected, timeout } = workerData;
+
+    parentPort.postMessage("waiting");
+    parentPort.postMessage(Atomics.wait(array, index, expected, timeout));
+    `,
+    { eval: true, workerData: { array, index, expected, timeout } }
+  );
+
+  const waiting = new Promise((resolve) => worker.once("message", resolve));
+  const result = waiting.then(
+    () =>
+      new Promise((resolve, reject) => {
+        worker.once("message", resolve);
+        worker.once("error", reject);
+      })
+  );
+
+  return { waiting, result };
+}
+
+function int32Array(length) {
+  return new Int32Array(new SharedArrayBuffer(length * 4));
+}
+
+describe("AtomicI32View", function () {
+  it("rejects arrays that are not backed by shared memory", function () {
+    assert.throws(
+      () => addon.atomics_view_len(new Int32Array(4)),
+      /^TypeError: expected an Int32Array backed by a SharedArrayBuffer$/
+    );
+  });
+
+  it("views the shared memory of an Int32Array", function () {
+    const array = int32Array(4);
+
+    assert.strictEqual(addon.atomics_view_len(array), 4);
+    assert.strictEqual(addon.atomics_view_len(array.subarray(1, 3)), 2);
+  });
+
+  it("operates on the same memory as Atomics", function () {
+    const array = int32Array(4);
+
+    Atomics.store(array, 2, 40);
+
+    assert.strictEqual(addon.atomics_fetch_add(array, 2, 2), 40);
+    assert.strictEqual(Atomics.load(array, 2), 42);
+  });
+
+  it("wakes a JavaScript Atomics.wait in a worker from a Rust thread", async function () {
+    const array = int32Array(1);
+    const { waiting, result } = waitInWorker(array, 0, 0, 10_000);
+
+    await waiting;
+    addon.atomics_notify_from_thread(array, 0, 1, 50);
+
+    assert.strictEqual(await result, "ok");
+    assert.strictEqual(Atomics.load(array, 0), 1);
+  });
+
+  it("wakes a Rust thread from JavaScript Atomics.notify", function (cb) {
+    const array = int32Array(1);
+
+    addon.atomics_wait_on_thread(array, 0, 0, -1, (result) => {
+      try {
+        assert.strictEqual(result, "ok");
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+
+    setTimeout(() => {
+      Atomics.store(array, 0, 1);
+      Atomics.notify(array, 0);
+    }, 50);
+  });
+
+  it("returns not-equal without blocking", function (cb) {
+    const array = int32Array(1);
+
+    Atomics.store(array, 0, 1);
+
+    addon.atomics_wait_on_thread(array, 0, 0, -1, (result) => {
+      try {
+        assert.strictEqual(result, "not-equal");
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it("times out", function (cb) {
+    const array = int32Array(1);
+
+    addon.atomics_wait_on_thread(array, 0, 0, 20, (result) => {
+      try {
+        assert.strictEqual(result, "timed-out");
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it("wakes a Rust thread from another Rust thread", function (cb) {
+    addon.atomics_wait_and_notify(int32Array(1), (result, woken) => {
+      try {
+        assert.strictEqual(result, "ok");
+        assert.strictEqual(woken, 1);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it("only wakes Rust threads waiting on the notified element", function (cb) {
+    addon.atomics_notify_one_element(int32Array(2), (other, notified) => {
+      try {
+        assert.strictEqual(other, "timed-out");
+        assert.strictEqual(notified, "ok");
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+});
@@ -30,6 +30,10 @@ class RefPerson {
   fail() {
     addon.ref_person_fail(this._person);
   }
+
+  greetReentrant(callback) {
+    return addon.ref_person_greet_reentrant(this._person, callback);
+  }
 }
 
 describe("boxed", function () {
@@ -58,18 +62,219 @@ describe("boxed", function () {
     assert.throws(() => new RefPerson("World").fail(), /BorrowMutError/);
   });
 
+  it("should release the borrow while calling back into JavaScript", function () {
+    const person = new RefPerson("World");
+    const greetings = [];
+
+    const greeting = person.greetReentrant((greeting) => {
+      greetings.push(greeting);
+      greetings.push(person.setName("Universe").greet());
+      greetings.push(
+        person.greetReentrant((nested) => {
+          greetings.push(nested);
+          person.setName("Neon");
+        })
+      );
+    });
+
+    assert.deepEqual(greetings, [
+      "Hello, World!",
+      "Hello, Universe!",
+      "Hello, Universe!",
+      "Hello, Neon!",
+    ]);
+    assert.strictEqual(greeting, "Hello, Neon!");
+  });
+
   it("should type check externals", function () {
     // `any::type_name` does not guarantee exact format
-    // failed downcast to neon::types::boxed::JsBox<napi::js::boxed::Person>
+    // expected neon::types::boxed::JsBox<napi::js::boxed::Person> at argument 0, ...
     assert.throws(
       () => addon.person_greet({}),
-      /failed to downcast.*JsBox.*Person/
+      TypeError,
+      /expected .*JsBox.*Person.* at argument 0, got object \(instance of Object\)$/
     );
   });
 
   it("should type check dynamic type", function () {
     const unit = addon.external_unit();
 
-    assert.throws(() => addon.person_greet(unit), /failed to downcast/);
+    assert.throws(
+      () => addon.person_greet(unit),
+      TypeError,
+      /expected .*JsBox.*Person.* at argument 0, got object$/
+    );
+  });
+
+  describe("shared", function () {
+    function collect() {
+      // Finalizers and the drop queue run on later ticks of the event loop
+      return (async () => {
+        for (let i = 0; i < 10; i++) {
+          global.gc();
+          await new Promise((resolve) => setImmediate(resolve));
+        }
+      })();
+    }
+
+    function sharedNew() {
+      const finalized = [];
+      const [handle, resource] = addon.shared_new((name) =>
+        finalized.push(name)
+      );
+
+      return { finalized, handle, resource };
+    }
+
+    it("shares a value between boxes", function () {
+      const { handle, resource } = sharedNew();
+      const other = addon.shared_to_js(handle);
+
+      assert.notStrictEqual(other, resource);
+      assert.strictEqual(addon.shared_name(resource), "resource");
+      assert.strictEqual(addon.shared_name(other), "resource");
+    });
+
+    (global.gc ? it : it.skip)(
+      "finalizes immediately when the last handle is finalized",
+      async function () {
+        let { finalized, handle, resource } = sharedNew();
+
+        resource = null;
+        await collect();
+        assert.deepEqual(finalized, []);
+
+        addon.shared_handle_finalize(handle);
+        assert.deepEqual(finalized, ["resource"]);
+      }
+    );
+
+    (global.gc ? it : it.skip)(
+      "finalizes once after the last box when handles are dropped first",
+      async function () {
+        let { finalized, handle, resource: a } = sharedNew();
+        let b = addon.shared_to_js(handle);
+        let other = addon.shared_handle(b);
+
+        addon.shared_handle_drop(handle);
+        addon.shared_handle_drop_on_thread(other);
+        handle = other = null;
+
+        await collect();
+        assert.deepEqual(finalized, []);
+        assert.strictEqual(addon.shared_name(a), "resource");
+
+        a = null;
+        await collect();
+        assert.deepEqual(finalized, []);
+        assert.strictEqual(addon.shared_name(b), "resource");
+
+        b = null;
+        await collect();
+        assert.deepEqual(finalized, ["resource"]);
+
+        await collect();
+        assert.deepEqual(finalized, ["resource"]);
+      }
+    );
+
+    (global.gc ? it : it.skip)(
+      "finalizes once after the last handle when boxes are collected first",
+      async function () {
+        let { finalized, handle, resource: a } = sharedNew();
+        let b = addon.shared_to_js(handle);
+        const other = addon.shared_handle(b);
+
+        a = b = null;
+        await collect();
+        assert.deepEqual(finalized, []);
+
+        addon.shared_handle_drop(handle);
+        await collect();
+        assert.deepEqual(finalized, []);
+
+        addon.shared_handle_drop_on_thread(other);
+        await collect();
+        assert.deepEqual(finalized, ["resource"]);
+
+        await collect();
+        assert.deepEqual(finalized, ["resource"]);
+      }
+    );
+
+    (global.gc ? it : it.skip)(
+      "finalizes when a box holding the last handle is collected",
+      async function () {
+        let { finalized, handle, resource } = sharedNew();
+
+        resource = null;
+        await collect();
+        assert.deepEqual(finalized, []);
+
+        handle = null;
+        await collect();
+        assert.deepEqual(finalized, ["resource"]);
+      }
+    );
+  });
+
+  describe("rwlock", function () {
+    // Resolves once a task holds a read guard; wrapped to avoid awaiting the sum
+    async function whileReading(index) {
+      const sum = addon.rwlock_sum(index, 200);
+
+      while (!addon.rwlock_is_reading(index)) {
+        await new Promise((resolve) => setImmediate(resolve));
+      }
+
+      return { sum };
+    }
+
+    it("reads in tasks and writes on the JavaScript thread", async function () {
+      const index = addon.rwlock_new([1, 2, 3]);
+
+      assert.strictEqual(await addon.rwlock_sum(index, 0), 6);
+
+      addon.rwlock_push(index, 4);
+
+      const sums = await Promise.all([
+        addon.rwlock_sum(index, 10),
+        addon.rwlock_sum(index, 10),
+        addon.rwlock_sum(index, 10),
+      ]);
+
+      assert.deepEqual(sums, [10, 10, 10]);
+    });
+
+    it("reads concurrently from the JavaScript thread and tasks", async function () {
+      const index = addon.rwlock_new([1, 2, 3]);
+      const { sum } = await whileReading(index);
+
+      assert.strictEqual(await sum, 6);
+    });
+
+    it("does not write while a task is reading", async function () {
+      const index = addon.rwlock_new([1, 2, 3]);
+      const { sum } = await whileReading(index);
+
+      assert.strictEqual(addon.rwlock_try_push(index, 4), false);
+      assert.strictEqual(await sum, 6);
+      assert.strictEqual(addon.rwlock_try_push(index, 4), true);
+      assert.strictEqual(await addon.rwlock_sum(index, 0), 10);
+    });
+
+    it("panics when sending to the JavaScript thread while holding a guard", async function () {
+      const index = addon.rwlock_new([1, 2, 3]);
+
+      try {
+        await addon.rwlock_send_while_reading(index);
+
+        throw new Error("Did not throw");
+      } catch (err) {
+        assert.instanceOf(err, Error);
+        assert.match(err.message, /panic/i);
+        assert.match(err.panic.message, /holding a guard of a JsRwLock/);
+      }
+    });
   });
 });
@@ -7,4 +7,71 @@ describe("coercions", function () {
     assert.strictEqual(addon.to_string(new Map()), "[object Map]");
     assert.strictEqual(addon.to_string({ a: "b" }), "[object Object]");
   });
+
+  it("can coerce to string with custom toString", function () {
+    var obj = {
+      toString: function () {
+        return "custom";
+      },
+    };
+
+    assert.strictEqual(addon.coerce_to_string(obj), "custom");
+    assert.strictEqual(addon.coerce_to_string(42), "42");
+    assert.strictEqual(addon.coerce_to_string(null), "null");
+  });
+
+  it("propagates exceptions when coercing to string", function () {
+    var obj = {
+      toString: function () {
+        throw new Error("toString failed");
+      },
+    };
+
+    assert.throws(() => addon.coerce_to_string(obj), /toString failed/);
+    assert.throws(() => addon.coerce_to_string(Symbol("s")), TypeError);
+  });
+
+  it("can coerce to number with custom valueOf", function () {
+    var obj = {
+      valueOf: function () {
+        return 7;
+      },
+    };
+
+    assert.strictEqual(addon.coerce_to_number(obj), 7);
+    assert.strictEqual(addon.coerce_to_number("12.5"), 12.5);
+    assert.strictEqual(addon.coerce_to_number(true), 1);
+    assert.isNaN(addon.coerce_to_number("not a number"));
+  });
+
+  it("propagates exceptions when coercing to number", function () {
+    var obj = {
+      valueOf: function () {
+        throw new Error("valueOf failed");
+      },
+    };
+
+    assert.throws(() => addon.coerce_to_number(obj), /valueOf failed/);
+    assert.throws(() => addon.coerce_to_number(Symbol("s")), TypeError);
+    assert.throws(() => addon.coerce_to_number(10n), TypeError);
+  });
+
+  it("can coerce to boolean", function () {
+    assert.strictEqual(addon.coerce_to_bool(""), false);
+    assert.strictEqual(addon.coerce_to_bool(0), false);
+    assert.strictEqual(addon.coerce_to_bool(null), false);
+    assert.strictEqual(addon.coerce_to_bool("false"), true);
+    assert.strictEqual(addon.coerce_to_bool({}), true);
+  });
+
+  it("can coerce to object", function () {
+    var obj = {};
+    var wrapped = addon.coerce_to_object("abc");
+
+    assert.strictEqual(addon.coerce_to_object(obj), obj);
+    assert.instanceOf(wrapped, String);
+    assert.strictEqual(wrapped.valueOf(), "abc");
+    assert.throws(() => addon.coerce_to_object(null), TypeError);
+    assert.throws(() => addon.coerce_to_object(undefined), TypeError);
+  });
 });
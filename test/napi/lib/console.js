@@ -0,0 +1,86 @@
+const addon = require("..");
+const assert = require("chai").assert;
+const util = require("util");
+
+describe("console", function () {
+  const methods = ["log", "warn", "error"];
+  let originals;
+  let calls;
+
+  beforeEach(function () {
+    originals = {};
+    calls = [];
+
+    for (const method of methods) {
+      originals[method] = console[method];
+      console[method] = (...args) => calls.push([method, ...args]);
+    }
+  });
+
+  afterEach(function () {
+    Object.assign(console, originals);
+  });
+
+  it("should log values with console.log", function () {
+    const obj = {};
+
+    addon.console_log("hello", 1, obj);
+
+    assert.deepEqual(calls, [["log", "hello", 1, obj]]);
+    assert.strictEqual(calls[0][3], obj);
+  });
+
+  it("should log Rust values with console.warn and console.error", function () {
+    addon.console_warn_error();
+
+    assert.deepEqual(calls, [
+      ["warn", "warning", "from Rust"],
+      ["error", 42],
+    ]);
+  });
+
+  it("should log from a channel closure", async function () {
+    await addon.console_log_channel();
+
+    assert.deepEqual(calls, [["log", "from a channel"]]);
+  });
+
+  it("should log from a task completion", async function () {
+    await addon.console_log_task();
+
+    assert.deepEqual(calls, [["log", "from a task"]]);
+  });
+
+  it("should throw when console.log is not a function", function () {
+    console.log = 42;
+
+    assert.throws(() => addon.console_log("hello"), TypeError);
+  });
+
+  it("should propagate exceptions thrown by console.log", function () {
+    console.log = () => {
+      throw new Error("monkey-patched");
+    };
+
+    assert.throws(() => addon.console_log("hello"), /monkey-patched/);
+  });
+});
+
+describe("inspect", function () {
+  it("should format values with util.inspect", function () {
+    const value = { a: { b: { c: { d: [1, "two"] } } } };
+
+    assert.strictEqual(addon.inspect(value, 2), util.inspect(value));
+    assert.strictEqual(
+      addon.inspect(value),
+      util.inspect(value, { depth: Infinity })
+    );
+  });
+
+  it("should format values with colors", function () {
+    assert.strictEqual(
+      addon.inspect("hello", 2, true),
+      util.inspect("hello", { colors: true })
+    );
+  });
+});
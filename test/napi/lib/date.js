@@ -39,4 +39,77 @@ describe("JsDate", function () {
     const dateValue = addon.get_date_value();
     assert.equal(dateValue, 31415);
   });
+
+  describe("chrono", function () {
+    it("should round trip a DateTime", function () {
+      const date = new Date("2020-01-02T03:04:05.678Z");
+      assert.equal(addon.chrono_round_trip(date).getTime(), date.getTime());
+    });
+
+    it("should truncate sub-millisecond precision towards the past", function () {
+      assert.equal(addon.chrono_from_nanos(1, 999999).getTime(), 1000);
+      assert.equal(addon.chrono_from_nanos(-1, 999999999).getTime(), -1);
+    });
+
+    it("should convert the largest DateTime", function () {
+      assert.equal(addon.chrono_max().getUTCFullYear(), 262142);
+    });
+
+    it("should throw a RangeError for dates outside the chrono range", function () {
+      assert.throws(() => addon.chrono_round_trip(new Date(8.64e15)), RangeError);
+      assert.throws(() => addon.chrono_round_trip(new Date(NaN)), RangeError);
+    });
+
+    it("should throw a TypeError for values that are not dates", function () {
+      assert.throws(
+        () => addon.chrono_round_trip("2020-01-02T03:04:05.678Z"),
+        TypeError
+      );
+    });
+
+    it("should convert a NaiveDate", function () {
+      const date = new Date("2020-01-02T23:59:59.999Z");
+      assert.equal(addon.chrono_naive_date(date), "2020-01-02");
+      assert.equal(
+        addon.chrono_naive_date_to_js().toISOString(),
+        "2020-01-02T00:00:00.000Z"
+      );
+    });
+
+    it("should accept ISO 8601 strings when opted in", function () {
+      const date = new Date("2020-01-02T03:04:05.678Z");
+      assert.equal(addon.chrono_iso_8601(date).getTime(), date.getTime());
+      assert.equal(
+        addon.chrono_iso_8601("2020-01-02T04:04:05.678+01:00").getTime(),
+        date.getTime()
+      );
+      assert.equal(addon.chrono_iso_8601_naive_date("2020-01-02"), "2020-01-02");
+      assert.throws(() => addon.chrono_iso_8601("yesterday"), TypeError);
+    });
+  });
+
+  describe("time", function () {
+    it("should round trip an OffsetDateTime", function () {
+      const date = new Date("2020-01-02T03:04:05.678Z");
+      assert.equal(addon.time_round_trip(date).getTime(), date.getTime());
+    });
+
+    it("should truncate sub-millisecond precision towards the past", function () {
+      assert.equal(addon.time_from_nanos(1999999).getTime(), 1);
+      assert.equal(addon.time_from_nanos(-1).getTime(), -1);
+    });
+
+    it("should throw a RangeError for dates outside the time range", function () {
+      assert.throws(() => addon.time_round_trip(new Date(8.64e15)), RangeError);
+    });
+
+    it("should accept ISO 8601 strings when opted in", function () {
+      const date = new Date("2020-01-02T03:04:05.678Z");
+      assert.equal(
+        addon.time_iso_8601("2020-01-02T04:04:05.678+01:00").getTime(),
+        date.getTime()
+      );
+      assert.throws(() => addon.time_iso_8601("yesterday"), TypeError);
+    });
+  });
 });
@@ -0,0 +1,58 @@
+const addon = require("..");
+const assert = require("chai").assert;
+const { spawnSync } = require("child_process");
+const path = require("path");
+
+function sleep(ms) {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+function spin(ms) {
+  const end = Date.now() + ms;
+
+  while (Date.now() < end) {}
+}
+
+describe("LoopMonitor", function () {
+  it("should record event loop latency", async function () {
+    const monitor = addon.loop_monitor_new(5);
+
+    await sleep(100);
+
+    const idle = addon.loop_monitor_snapshot(monitor);
+
+    assert.isAbove(idle.count, 0);
+    assert.isAtMost(idle.p50, idle.p95);
+    assert.isAtMost(idle.p95, idle.p99);
+    assert.isAtMost(idle.p99, idle.max);
+
+    spin(300);
+    await sleep(50);
+
+    const busy = addon.loop_monitor_snapshot(monitor);
+
+    assert.isAbove(busy.count, idle.count);
+    assert.isAtLeast(busy.max, 250);
+    assert.isAbove(busy.max, idle.max);
+
+    addon.loop_monitor_reset(monitor);
+
+    assert.strictEqual(addon.loop_monitor_snapshot(monitor).count, 0);
+  });
+
+  it("should not keep the process alive", function () {
+    const script = `
+      const addon = require(${JSON.stringify(path.join(__dirname, ".."))});
+
+      globalThis.monitor = addon.loop_monitor_new(5);
+    `;
+
+    const { status, error } = spawnSync(process.execPath, ["-e", script], {
+      encoding: "utf8",
+      timeout: 10000,
+    });
+
+    assert.isUndefined(error);
+    assert.strictEqual(status, 0);
+  });
+});
@@ -1,3 +1,6 @@
+const path = require("path");
+const { Worker } = require("worker_threads");
+
 const addon = require("..");
 const assert = require("chai").assert;
 
@@ -39,3 +42,428 @@ describe("errors", function () {
     assert.strictEqual(msg, "failed to downcast string to number");
   });
 });
+
+describe("Error stacks", function () {
+  it("inserts a note as the innermost frame", function () {
+    const err = addon.error_with_stack_note("parser::parse (parser.rs:123)");
+    const lines = err.stack.split("\n");
+
+    assert.strictEqual(lines[0], "Error: failed to parse");
+    assert.strictEqual(lines[1], "    at rust: parser::parse (parser.rs:123)");
+    assert.match(lines[2], /^    at /);
+  });
+
+  it("captures the stack above skipped frames", function () {
+    function wrapper() {
+      return addon.error_captured_above(1);
+    }
+
+    function caller() {
+      return wrapper();
+    }
+
+    const unskipped = (function wrapper() {
+      return addon.error_captured_above(0);
+    })();
+
+    assert.match(unskipped.stack.split("\n")[1], /at wrapper/);
+
+    const lines = caller().stack.split("\n");
+
+    assert.strictEqual(lines[0], "Error: failed to parse");
+    assert.match(lines[1], /at caller/);
+    assert.notInclude(caller().stack, "at wrapper");
+  });
+
+  it("notes the Rust location of thrown errors", function () {
+    try {
+      addon.throw_error("failed");
+    } catch (err) {
+      assert.match(
+        err.stack.split("\n")[1],
+        /^    at rust: throw_error \(.*errors\.rs:\d+:\d+\)$/
+      );
+      return;
+    }
+
+    assert.fail("expected an exception");
+  });
+
+  it("notes the Rust location of tasks that reject", async function () {
+    try {
+      await addon.task_throw_error();
+    } catch (err) {
+      const lines = err.stack.split("\n");
+
+      assert.strictEqual(err.message, "task failed");
+      assert.match(lines[1], /^    at rust: task \(.*errors\.rs:\d+:\d+\)$/);
+      assert.match(lines[2], /^    at rust: throw_error \(.*errors\.rs:\d+:\d+\)$/);
+      return;
+    }
+
+    assert.fail("expected a rejection");
+  });
+});
+
+describe("Downcast errors", function () {
+  function message(f) {
+    try {
+      f();
+    } catch (err) {
+      assert.instanceOf(err, TypeError);
+
+      return err.message;
+    }
+
+    throw new Error("Did not throw");
+  }
+
+  it("names the argument index and the expected and actual types", function () {
+    assert.strictEqual(
+      message(() => addon.expect_function_argument(null, new Map())),
+      "expect_function_argument: expected function at argument 1, got object (instance of Map)"
+    );
+    assert.strictEqual(
+      message(() => addon.expect_function_argument(null, "f")),
+      "expect_function_argument: expected function at argument 1, got string"
+    );
+    assert.strictEqual(
+      message(() => addon.expect_function_argument(null, null)),
+      "expect_function_argument: expected function at argument 1, got null"
+    );
+  });
+
+  it("names the constructor of class instances", function () {
+    class Point {}
+
+    assert.strictEqual(
+      message(() => addon.expect_function_argument(null, new Point())),
+      "expect_function_argument: expected function at argument 1, got object (instance of Point)"
+    );
+  });
+
+  it("omits the constructor if it cannot be found", function () {
+    const throwing = new Proxy(
+      {},
+      {
+        get() {
+          throw new Error("trap");
+        },
+      }
+    );
+
+    assert.strictEqual(
+      message(() =>
+        addon.expect_function_argument(null, Object.create(null))
+      ),
+      "expect_function_argument: expected function at argument 1, got object"
+    );
+    assert.strictEqual(
+      message(() => addon.expect_function_argument(null, throwing)),
+      "expect_function_argument: expected function at argument 1, got object"
+    );
+  });
+
+  it("omits the argument index in downcast_or_throw", function () {
+    assert.strictEqual(
+      message(() => addon.downcast_or_throw_array({})),
+      "expected Array, got object (instance of Object)"
+    );
+    assert.strictEqual(
+      message(() => addon.downcast_or_throw_array(() => {})),
+      "expected Array, got function"
+    );
+  });
+
+  it("names the exported function", function () {
+    assert.strictEqual(addon.own_function_name(), "own_function_name");
+    assert.strictEqual(addon.own_function_name.name, "own_function_name");
+    assert.strictEqual(
+      message(() => addon.expect_function_argument(null)),
+      "expect_function_argument: not enough arguments"
+    );
+  });
+
+  it("names functions created with a name", function () {
+    const parseConfig = addon.named_function_expecting_string();
+
+    assert.strictEqual(
+      message(() => parseConfig(42)),
+      "parseConfig: expected string at argument 0, got number"
+    );
+  });
+
+  it("omits the name of functions created without one", function () {
+    const f = addon.unnamed_function_expecting_string();
+
+    assert.strictEqual(
+      message(() => f(42)),
+      "expected string at argument 0, got number"
+    );
+  });
+});
+
+describe("Result-returning functions", function () {
+  it("should return the value of Ok", function () {
+    assert.strictEqual(addon.parse_positive("1.5"), 1.5);
+  });
+
+  it("should throw a TypeError with a code", function () {
+    try {
+      addon.parse_positive("");
+      assert.fail("expected an exception");
+    } catch (err) {
+      assert.instanceOf(err, TypeError);
+      assert.strictEqual(err.message, "input is empty");
+      assert.strictEqual(err.code, "ERR_EMPTY");
+    }
+  });
+
+  it("should throw an Error without a code", function () {
+    try {
+      addon.parse_positive("abc");
+      assert.fail("expected an exception");
+    } catch (err) {
+      assert.strictEqual(Object.getPrototypeOf(err), Error.prototype);
+      assert.strictEqual(err.message, "invalid number: abc");
+      assert.isFalse("code" in err);
+    }
+  });
+
+  it("should throw a RangeError with a code", function () {
+    try {
+      addon.parse_positive("-3");
+      assert.fail("expected an exception");
+    } catch (err) {
+      assert.instanceOf(err, RangeError);
+      assert.strictEqual(err.message, "-3 is negative");
+      assert.strictEqual(err.code, "ERR_NEGATIVE");
+    }
+  });
+
+  it("should convert panics", function () {
+    assert.throws(
+      () => addon.parse_positive("42"),
+      /internal error in Neon module: 42 is not allowed/
+    );
+  });
+
+  it("should propagate a pending exception", function () {
+    assert.throws(() => addon.result_after_throw(), TypeError, "thrown first");
+  });
+});
+
+describe("Error translation", function () {
+  class DatabaseError extends Error {
+    constructor(message, code) {
+      super(message);
+      this.name = "DatabaseError";
+      this.code = code;
+    }
+  }
+
+  before(function () {
+    addon.init_database_error(DatabaseError);
+  });
+
+  function assertDatabaseError(err) {
+    assert.instanceOf(err, DatabaseError);
+    assert.strictEqual(err.message, "no such table: nothing");
+    assert.strictEqual(err.code, "EDB");
+  }
+
+  it("should translate errors thrown with or_throw", function () {
+    assert.strictEqual(addon.query_or_throw("SELECT 1"), 1);
+
+    try {
+      addon.query_or_throw("SELECT nothing");
+      assert.fail("expected an exception");
+    } catch (err) {
+      assertDatabaseError(err);
+    }
+  });
+
+  it("should translate errors returned from exported functions", function () {
+    assert.strictEqual(addon.query_result("SELECT 1"), 1);
+
+    try {
+      addon.query_result("SELECT nothing");
+      assert.fail("expected an exception");
+    } catch (err) {
+      assertDatabaseError(err);
+    }
+  });
+
+  it("should translate errors rejecting task promises", async function () {
+    assert.strictEqual(await addon.query_task("SELECT 1"), 1);
+
+    try {
+      await addon.query_task("SELECT nothing");
+      assert.fail("expected a rejection");
+    } catch (err) {
+      assertDatabaseError(err);
+    }
+  });
+
+  it("should throw an Error for errors without a translation", function () {
+    for (const f of [addon.query_or_throw, addon.query_result]) {
+      try {
+        f("DROP TABLE users");
+        assert.fail("expected an exception");
+      } catch (err) {
+        assert.strictEqual(Object.getPrototypeOf(err), Error.prototype);
+        assert.strictEqual(err.message, "invalid float literal");
+      }
+    }
+  });
+
+  it("should use translations registered after first use", function () {
+    function lateError() {
+      try {
+        addon.throw_late_error();
+      } catch (err) {
+        return err;
+      }
+
+      assert.fail("expected an exception");
+    }
+
+    const before = lateError();
+
+    assert.strictEqual(Object.getPrototypeOf(before), Error.prototype);
+    assert.strictEqual(before.message, "late error");
+
+    addon.register_late_error("ELATE");
+
+    const after = lateError();
+
+    assert.instanceOf(after, TypeError);
+    assert.strictEqual(after.message, "late error");
+    assert.strictEqual(after.code, "ELATE");
+
+    addon.register_late_error("ELATER");
+
+    assert.strictEqual(lateError().code, "ELATER");
+  });
+});
+
+describe("Custom error classes", function () {
+  class AppError extends Error {
+    constructor(message) {
+      super(message);
+      this.name = "AppError";
+    }
+  }
+
+  before(function () {
+    addon.register_error_class("AppError", AppError);
+  });
+
+  function thrown(f) {
+    try {
+      f();
+    } catch (err) {
+      return err;
+    }
+
+    assert.fail("expected an exception");
+  }
+
+  it("should throw an instance of a registered class", function () {
+    const err = thrown(() =>
+      addon.throw_custom_error("AppError", "failed", { status: 404 })
+    );
+
+    assert.instanceOf(err, AppError);
+    assert.strictEqual(err.name, "AppError");
+    assert.strictEqual(err.message, "failed");
+    assert.strictEqual(err.status, 404);
+    assert.notProperty(err, "warning");
+  });
+
+  it("should build an instance of a registered class", function () {
+    const err = addon.custom_error("AppError", "failed", "EAPP");
+
+    assert.instanceOf(err, AppError);
+    assert.strictEqual(err.message, "failed");
+    assert.strictEqual(err.code, "EAPP");
+  });
+
+  it("should fall back to Error for an unknown class", function () {
+    const err = thrown(() => addon.throw_custom_error("NoSuchError", "failed"));
+
+    assert.strictEqual(Object.getPrototypeOf(err), Error.prototype);
+    assert.strictEqual(err.message, "failed");
+    assert.include(err.warning, "NoSuchError");
+  });
+
+  it("should replace a registered class", function () {
+    class OtherError extends Error {}
+
+    addon.register_error_class("ReplacedError", AppError);
+    addon.register_error_class("ReplacedError", OtherError);
+
+    assert.instanceOf(addon.custom_error("ReplacedError", "", ""), OtherError);
+  });
+
+  it("should throw when the constructor throws", function () {
+    addon.register_error_class("BrokenError", function () {
+      throw new RangeError("constructor failed");
+    });
+
+    const err = thrown(() => addon.throw_custom_error("BrokenError", "failed"));
+
+    assert.instanceOf(err, RangeError);
+    assert.strictEqual(err.message, "constructor failed");
+  });
+
+  it("should register classes separately in each worker", async function () {
+    const worker = new Worker(
+      `
+        const { parentPort } = require("worker_threads");
+        const addon = require(${JSON.stringify(path.join(__dirname, ".."))});
+
+        function thrown(f) {
+          try {
+            f();
+          } catch (err) {
+            return err;
+          }
+        }
+
+        // Registered in the main thread only
+        const before = thrown(() => addon.throw_custom_error("AppError", "failed"));
+
+        class AppError extends Error {}
+
+        addon.register_error_class("AppError", AppError);
+
+        const after = thrown(() => addon.throw_custom_error("AppError", "failed"));
+
+        parentPort.postMessage({
+          beforeIsError: Object.getPrototypeOf(before) === Error.prototype,
+          beforeWarning: before.warning,
+          afterIsWorkerClass: after instanceof AppError,
+        });
+      `,
+      { eval: true }
+    );
+
+    const result = await new Promise((resolve, reject) => {
+      worker.once("message", resolve);
+      worker.once("error", reject);
+    });
+
+    await worker.terminate();
+
+    assert.isTrue(result.beforeIsError);
+    assert.include(result.beforeWarning, "AppError");
+    assert.isTrue(result.afterIsWorkerClass);
+
+    // Registering in the worker does not affect the main thread
+    assert.instanceOf(
+      thrown(() => addon.throw_custom_error("AppError", "failed")),
+      AppError
+    );
+  });
+});
@@ -10,6 +10,26 @@ describe("JsFunction", function () {
     assert.equal(addon.return_js_function()(41), 42);
   });
 
+  it("return a JsFunction with a name and length", function () {
+    const f = addon.return_js_function_with_options();
+
+    assert.strictEqual(f.name, "add1");
+    assert.strictEqual(f.length, 1);
+    assert.strictEqual(f(41), 42);
+    assert.isFalse(Object.getOwnPropertyDescriptor(f, "length").writable);
+  });
+
+  it("reads the name of a JsFunction", function () {
+    function hello() {}
+
+    assert.strictEqual(addon.get_js_function_name(hello), "hello");
+    assert.strictEqual(addon.get_js_function_name(() => {}), "");
+    assert.strictEqual(
+      addon.get_js_function_name(addon.return_js_function_with_options()),
+      "add1"
+    );
+  });
+
   it("call a JsFunction built in JS that implements x => x + 1", function () {
     assert.equal(
       addon.call_js_function(function (x) {
@@ -79,6 +99,61 @@ describe("JsFunction", function () {
     ]);
   });
 
+  it("call a JsFunction with a tuple of handles and Rust values", function () {
+    const obj = {};
+    const result = addon.call_js_function_with_tuple_args(function (...args) {
+      return [this, ...args];
+    }, obj);
+
+    assert.deepStrictEqual(result, [obj, "neon", 42, true, "hello", null]);
+    assert.strictEqual(result[0], obj);
+  });
+
+  it("construct a JsFunction with a tuple of arguments", function () {
+    assert.deepStrictEqual(
+      addon.construct_js_function_with_tuple_args(),
+      [1, 2, 3]
+    );
+  });
+
+  it("bind a JsFunction with a tuple of arguments", function () {
+    const obj = { base: "zero" };
+    const f = addon.bind_js_function_with_tuple_args(function (a, b, c) {
+      return [this.base, a, b, c];
+    }, obj);
+
+    assert.deepStrictEqual(f(3), ["zero", 1, "two", 3]);
+  });
+
+  it("call a JsFunction with args from an iterator", function () {
+    assert.equal(addon.call_js_function_with_args_from_iter(), 4.0);
+  });
+
+  it("names the actual and expected types of a bad return value", function () {
+    assert.strictEqual(
+      addon.call_js_function_expecting_string(() => "hello"),
+      "hello"
+    );
+    assert.throws(
+      () => addon.call_js_function_expecting_string(() => 42),
+      TypeError,
+      /^function returned number, expected string$/
+    );
+  });
+
+  it("bind a JsFunction to this and partial args", function () {
+    const obj = { base: 10 };
+    const f = addon.bind_js_function(
+      function (a, b) {
+        return this.base + a + b;
+      },
+      obj,
+      [1]
+    );
+
+    assert.strictEqual(f(2), 13);
+  });
+
   it("new a JsFunction", function () {
     assert.equal(addon.construct_js_function(Date), 1970);
   });
@@ -94,6 +169,63 @@ describe("JsFunction", function () {
     );
   });
 
+  it("new a user-defined class passed from JS", function () {
+    class Point {
+      constructor(x) {
+        this.x = x;
+      }
+    }
+
+    for (const construct of [
+      addon.construct_js_class,
+      addon.construct_js_class_idiomatically,
+    ]) {
+      const p = construct(Point, 42);
+
+      assert.instanceOf(p, Point);
+      assert.strictEqual(p.x, 42);
+    }
+  });
+
+  it("propagates an exception thrown by a constructor", function () {
+    class Fails {
+      constructor(msg) {
+        throw new RangeError(msg);
+      }
+    }
+
+    assert.throws(
+      () => addon.construct_js_class(Fails, "nope"),
+      RangeError,
+      /^nope$/
+    );
+    assert.throws(
+      () => addon.construct_js_class_idiomatically(Fails, "nope"),
+      RangeError,
+      /^nope$/
+    );
+  });
+
+  it("follows JS semantics for constructors that return values", function () {
+    const obj = {};
+
+    function ReturnsObject() {
+      return obj;
+    }
+
+    function ReturnsPrimitive() {
+      this.constructed = true;
+      return 42;
+    }
+
+    assert.strictEqual(addon.construct_js_class(ReturnsObject, null), obj);
+
+    const p = addon.construct_js_class(ReturnsPrimitive, null);
+
+    assert.instanceOf(p, ReturnsPrimitive);
+    assert.isTrue(p.constructed);
+  });
+
   it("got two parameters, a string and a number", function () {
     addon.check_string_and_number("string", 42);
   });
@@ -126,6 +258,45 @@ describe("JsFunction", function () {
     assert.equal(addon.num_arguments("a", "b", "c", "d"), 4);
   });
 
+  it("collects rest arguments", function () {
+    assert.strictEqual(addon.sum_rest_arguments(1), 0);
+    assert.strictEqual(addon.sum_rest_arguments(1, 1, 2, 3), 6);
+    assert.strictEqual(addon.sum_rest_arguments(2, 1, 2, 3), 5);
+    assert.strictEqual(addon.sum_rest_arguments(10, 1, 2, 3), 0);
+    assert.strictEqual(
+      addon.sum_rest_arguments(1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10),
+      55
+    );
+  });
+
+  it("reads many arguments", function () {
+    assert.strictEqual(addon.sum_six_arguments(1, 2, 3, 4, 5, 6), 21);
+    assert.throws(() => addon.sum_six_arguments(1, 2, 3), TypeError);
+  });
+
+  it("iterates over arguments", function () {
+    assert.strictEqual(addon.count_arguments_iter(), 0);
+    assert.strictEqual(addon.count_arguments_iter("a", "b", "c"), 3);
+    assert.strictEqual(addon.count_arguments_iter(1, 2, 3, 4, 5, 6), 6);
+  });
+
+  it("reads arguments and this from ArgsInfo", function () {
+    const o = {};
+
+    assert.deepEqual(addon.describe_args_info.call(o), [0, o, undefined]);
+    assert.deepEqual(
+      addon.describe_args_info.call(o, 1, 2, 3, 4, 5, 6, 7, 8, 9),
+      [9, o, 9]
+    );
+  });
+
+  it("uses a default for missing or undefined arguments", function () {
+    assert.strictEqual(addon.argument_or_default(), "default");
+    assert.strictEqual(addon.argument_or_default(undefined), "default");
+    assert.strictEqual(addon.argument_or_default("given"), "given");
+    assert.throws(() => addon.argument_or_default(null), TypeError);
+  });
+
   it("gets the right `this`-value", function () {
     var o = { iamobject: "i am object" };
     assert.equal(addon.return_this.call(o), o);
@@ -222,15 +393,201 @@ describe("JsFunction", function () {
     assert.strictEqual(addon.get_number_or_default(), 0);
   });
 
+  it("catches a pending exception with cx.catch_pending", function () {
+    const error = new Error("Something bad happened");
+
+    assert.deepEqual(
+      addon.call_and_catch_pending(() => {
+        throw error;
+      }),
+      [true, error, false]
+    );
+    assert.deepEqual(addon.call_and_catch_pending(() => 42), [false, 42]);
+  });
+
+  it("treats cx.catch_pending as a no-op when not throwing", function () {
+    assert.isTrue(addon.catch_pending_when_not_throwing());
+  });
+
+  it("composes cx.catch_pending with cx.try_catch", function () {
+    const error = new Error("not a string");
+
+    assert.strictEqual(
+      addon.catch_strings(() => {
+        throw "recovered";
+      }),
+      "recovered"
+    );
+    assert.strictEqual(addon.catch_strings(() => "value"), "value");
+
+    try {
+      addon.catch_strings(() => {
+        throw error;
+      });
+      assert.fail("should have thrown");
+    } catch (err) {
+      assert.strictEqual(err.rethrown, error);
+    }
+  });
+
+  it("exports a closure capturing state from module load", function () {
+    assert.strictEqual(addon.exported_closure(), "Hello, World!");
+  });
+
+  it("exports a bound function", function () {
+    assert.strictEqual(addon.exported_bound_function(6), 21);
+  });
+
   it("distinguishes calls from constructs", function () {
     assert.equal(addon.is_construct.call({}).wasConstructed, false);
     assert.equal(new addon.is_construct().wasConstructed, true);
   });
 
+  it("throws when a constructor is called without new", function () {
+    assert.throws(
+      () => addon.construct_only(),
+      TypeError,
+      /must be called with new/
+    );
+    assert.throws(() => addon.construct_only.call({}), TypeError);
+  });
+
+  it("exposes new.target to a constructor", function () {
+    const obj = new addon.construct_only();
+
+    assert.strictEqual(obj.target, addon.construct_only);
+    assert.ok(obj instanceof addon.construct_only);
+  });
+
+  it("exposes the subclass as new.target", function () {
+    class Sub extends addon.construct_only {}
+
+    const obj = new Sub();
+
+    assert.strictEqual(obj.target, Sub);
+    assert.ok(obj instanceof Sub);
+  });
+
   it("should be able to call a function from a closure", function () {
     assert.strictEqual(addon.count_called() + 1, addon.count_called());
   });
 
+  it("should be able to mutate state captured by a closure", function () {
+    const counter = addon.return_mut_counter();
+
+    assert.strictEqual(counter(), 1);
+    assert.strictEqual(counter(), 2);
+    assert.strictEqual(addon.return_mut_counter()(), 1);
+  });
+
+  it("throws when a mutable closure is re-entered", function () {
+    const f = addon.return_mut_reentrant();
+
+    assert.strictEqual(f((n) => n * 2), 2);
+    assert.throws(() => f(() => f(() => {})), Error, /already running/);
+    assert.strictEqual(f((n) => n), 3);
+  });
+
+  it("calls a function with no arguments with call0", function () {
+    let seen;
+
+    const result = addon.call0_js_function(function () {
+      "use strict";
+
+      const args = Array.from(arguments);
+
+      seen = { self: this, args };
+
+      return "called";
+    });
+
+    assert.strictEqual(result, "called");
+    assert.strictEqual(seen.self, undefined);
+    assert.deepStrictEqual(seen.args, []);
+    assert.throws(
+      () =>
+        addon.call0_js_function(() => {
+          throw new Error("call0");
+        }),
+      /call0/
+    );
+  });
+
+  it("calls a function with one argument with call1", function () {
+    const obj = {};
+    let seen;
+
+    const result = addon.call1_js_function(function () {
+      "use strict";
+
+      const args = Array.from(arguments);
+
+      seen = { self: this, args };
+
+      return args[0];
+    }, obj);
+
+    assert.strictEqual(result, obj);
+    assert.strictEqual(seen.self, undefined);
+    assert.strictEqual(seen.args.length, 1);
+    assert.strictEqual(seen.args[0], obj);
+  });
+
+  it("calls a function repeatedly with call0", function () {
+    let count = 0;
+
+    addon.call0_loop(() => count++, 1000);
+
+    assert.strictEqual(count, 1000);
+  });
+
+  it("forwards this and arguments unchanged with forward_args", function () {
+    const seen = [];
+    const wrapped = addon.forwarding_wrapper(function (...args) {
+      seen.push({ self: this, args });
+      return args.length;
+    });
+
+    const obj = { wrapped };
+    const many = [1, "two", null, undefined, {}, [], true, 8n, 9, "ten", 11];
+
+    assert.strictEqual(obj.wrapped(), 0);
+    assert.strictEqual(obj.wrapped(...many.slice(0, 3)), 3);
+    assert.strictEqual(obj.wrapped(...many), many.length);
+
+    assert.strictEqual(seen.length, 3);
+    seen.forEach(({ self }) => assert.strictEqual(self, obj));
+    assert.deepStrictEqual(seen[0].args, []);
+    assert.deepStrictEqual(seen[1].args, many.slice(0, 3));
+    assert.strictEqual(seen[2].args.length, many.length);
+    seen[2].args.forEach((arg, i) => assert.strictEqual(arg, many[i]));
+  });
+
+  it("propagates exceptions from a forwarded call", function () {
+    const wrapped = addon.forwarding_wrapper(() => {
+      throw new Error("forwarded");
+    });
+
+    assert.throws(() => wrapped(1, 2), /forwarded/);
+  });
+
+  it("exposes new.target of a forwarded call", function () {
+    assert.strictEqual(addon.forwarded_new_target(), undefined);
+    assert.strictEqual(
+      new addon.forwarded_new_target(),
+      addon.forwarded_new_target
+    );
+  });
+
+  it("creates a function from a closure with cx.function", function () {
+    const f = addon.return_function_from_context("hi");
+
+    assert.strictEqual(f(), "hi 1");
+    assert.strictEqual(f(), "hi 2");
+    assert.throws(() => f(() => f()), Error, /already running/);
+    assert.strictEqual(f(), "hi 4");
+  });
+
   (global.gc ? it : it.skip)(
     "should drop function when going out of scope",
     function (cb) {
@@ -31,4 +31,73 @@ describe("hello", function () {
   it("should export a Rust function", function () {
     assert.strictEqual(addon.add1(2), 3.0);
   });
+
+  describe("duplicate exports", function () {
+    it("should throw naming both exports", function () {
+      assert.instanceOf(addon.duplicateExport, Error);
+      assert.match(
+        addon.duplicateExport.message,
+        /`greeting` is exported more than once, at \S+lib\.rs:\d+:\d+ and at \S+lib\.rs:\d+:\d+/
+      );
+      assert.strictEqual(addon.greeting, "Hello, World!");
+    });
+
+    it("should throw for duplicates in a namespace", function () {
+      assert.match(
+        addon.duplicateBuilderExport.message,
+        /`duplicates\.version` is exported more than once/
+      );
+      assert.notProperty(addon, "duplicates");
+    });
+
+    it("should overwrite an export explicitly", function () {
+      assert.strictEqual(addon.overwritten(), "second");
+    });
+
+    it("should list the exported names", function () {
+      assert.deepEqual(
+        addon.exportedNames,
+        Object.keys(addon).filter((name) => name !== "exportedNames")
+      );
+    });
+  });
+
+  describe("ModuleBuilder", function () {
+    it("should export namespaces", function () {
+      assert.strictEqual(addon.builder.add1(2), 3);
+      assert.strictEqual(addon.builder.frozen.version, "1.0.0");
+    });
+
+    it("should export non-enumerable values", function () {
+      assert.strictEqual(addon.builder.secret, 42);
+      assert.ok(!Object.keys(addon.builder).includes("secret"));
+    });
+
+    it("should export read-only values", function () {
+      "use strict";
+
+      assert.throws(() => {
+        addon.builder.version = "2.0.0";
+      }, TypeError);
+      assert.strictEqual(addon.builder.version, "1.0.0");
+      assert.ok(Object.isFrozen(addon.builder.frozen));
+    });
+
+    it("should initialize lazy exports once", function () {
+      const descriptor = Object.getOwnPropertyDescriptor(addon.builder, "lazy");
+
+      assert.strictEqual(typeof descriptor.get, "function");
+      assert.strictEqual(addon.builder.lazy, 1);
+      assert.strictEqual(addon.builder.lazy, 1);
+      assert.strictEqual(
+        Object.getOwnPropertyDescriptor(addon.builder, "lazy").value,
+        1
+      );
+    });
+
+    it("should throw from lazy exports that fail to initialize", function () {
+      assert.throws(() => addon.builder.lazyThrows, /lazy failed/);
+      assert.throws(() => addon.builder.lazyThrows, /failed to initialize/);
+    });
+  });
 });
@@ -0,0 +1,248 @@
+var addon = require("..");
+var assert = require("chai").assert;
+
+describe("JsIterator", function () {
+  it("can collect built-in iterables", function () {
+    assert.deepEqual(addon.collect_iterable([1, 2, 3]), [1, 2, 3]);
+    assert.deepEqual(addon.collect_iterable(new Set(["a", "b"])), ["a", "b"]);
+    assert.deepEqual(addon.collect_iterable(new Map([["a", 1]])), [["a", 1]]);
+    assert.deepEqual(addon.collect_iterable("hi"), ["h", "i"]);
+  });
+
+  it("can consume a generator", function () {
+    function* range(n) {
+      for (var i = 0; i < n; i++) {
+        yield i;
+      }
+    }
+
+    assert.deepEqual(addon.collect_iterable(range(4)), [0, 1, 2, 3]);
+  });
+
+  it("can consume a custom iterable", function () {
+    var iterable = {
+      [Symbol.iterator]() {
+        var i = 0;
+
+        return {
+          next() {
+            i += 1;
+            return i > 2 ? { done: true } : { value: i, done: false };
+          },
+        };
+      },
+    };
+
+    assert.deepEqual(addon.collect_iterable(iterable), [1, 2]);
+  });
+
+  it("throws when a value is not iterable", function () {
+    assert.throws(() => addon.collect_iterable({}), TypeError, /not iterable/);
+    assert.throws(() => addon.collect_iterable(null), TypeError, /not iterable/);
+    assert.throws(() => addon.collect_iterable(1), TypeError, /not iterable/);
+  });
+
+  it("propagates exceptions thrown by next", function () {
+    function* failing() {
+      yield 1;
+      throw new Error("next failed");
+    }
+
+    assert.throws(() => addon.collect_iterable(failing()), /next failed/);
+  });
+
+  it("calls return when stopping early", function () {
+    var closed = false;
+
+    function* range() {
+      try {
+        yield 1;
+        yield 2;
+        yield 3;
+      } finally {
+        closed = true;
+      }
+    }
+
+    assert.deepEqual(addon.take_from_iterable(range(), 2), [1, 2]);
+    assert.isTrue(closed);
+  });
+
+  it("does not call return when exhausted", function () {
+    var returned = false;
+    var iterable = {
+      [Symbol.iterator]() {
+        return {
+          next() {
+            return { done: true };
+          },
+          return() {
+            returned = true;
+            return {};
+          },
+        };
+      },
+    };
+
+    assert.deepEqual(addon.take_from_iterable(iterable, 2), []);
+    assert.isFalse(returned);
+  });
+
+  it("propagates exceptions thrown by return", function () {
+    var iterable = {
+      [Symbol.iterator]() {
+        return {
+          next() {
+            return { value: 1, done: false };
+          },
+          return() {
+            throw new Error("return failed");
+          },
+        };
+      },
+    };
+
+    assert.throws(() => addon.take_from_iterable(iterable, 1), /return failed/);
+  });
+});
+
+describe("boxed iterators", function () {
+  function dropsAfter(f) {
+    const before = addon.boxed_range_dropped();
+
+    f();
+
+    return addon.boxed_range_dropped() - before;
+  }
+
+  it("can be iterated with for...of", function () {
+    const values = [];
+
+    for (const value of addon.boxed_range(4)) {
+      values.push(value);
+    }
+
+    assert.deepEqual(values, [0, 1, 2, 3]);
+    assert.deepEqual([...addon.boxed_range(0)], []);
+  });
+
+  it("follows the iterator protocol", function () {
+    const it = addon.boxed_range(1);
+
+    assert.strictEqual(it[Symbol.iterator](), it);
+    assert.deepEqual(it.next(), { value: 0, done: false });
+    assert.deepEqual(it.next(), { value: undefined, done: true });
+    assert.deepEqual(it.next(), { value: undefined, done: true });
+  });
+
+  it("can be consumed in chunks", function () {
+    const it = addon.boxed_range(10);
+
+    assert.deepEqual(it.nextChunk(4), [0, 1, 2, 3]);
+    assert.deepEqual(it.next(), { value: 4, done: false });
+    assert.deepEqual(it.nextChunk(0), []);
+    assert.deepEqual(it.nextChunk(100), [5, 6, 7, 8, 9]);
+    assert.deepEqual(it.nextChunk(100), []);
+    assert.throws(() => it.nextChunk(-1), RangeError);
+  });
+
+  it("drops the Rust iterator when exhausted", function () {
+    const it = addon.boxed_range(2);
+
+    assert.strictEqual(
+      dropsAfter(() => it.nextChunk(2)),
+      0
+    );
+    assert.strictEqual(
+      dropsAfter(() => it.next()),
+      1
+    );
+  });
+
+  it("drops the Rust iterator when closed", function () {
+    const it = addon.boxed_range(10);
+
+    assert.strictEqual(
+      dropsAfter(() => {
+        for (const value of it) {
+          if (value === 2) {
+            break;
+          }
+        }
+      }),
+      1
+    );
+    assert.deepEqual(it.next(), { value: undefined, done: true });
+  });
+
+  it("keeps throwing the error of a failed item", function () {
+    const it = addon.boxed_range(10, 2);
+
+    assert.deepEqual(it.next(), { value: 0, done: false });
+
+    let first;
+
+    assert.strictEqual(
+      dropsAfter(() => {
+        assert.deepEqual(it.nextChunk(5), [1]);
+
+        try {
+          it.next();
+        } catch (err) {
+          first = err;
+        }
+      }),
+      1
+    );
+
+    assert.instanceOf(first, Error);
+    assert.strictEqual(first.message, "failed at 2");
+
+    for (const f of [() => it.next(), () => it.nextChunk(1), () => [...it]]) {
+      try {
+        f();
+        assert.fail("expected an exception");
+      } catch (err) {
+        assert.strictEqual(err, first);
+      }
+    }
+
+    it.return();
+    assert.throws(() => it.next(), /failed at 2/);
+  });
+
+  it("can call detached methods", function () {
+    const { next, nextChunk } = addon.boxed_range(3);
+
+    assert.deepEqual(next(), { value: 0, done: false });
+    assert.deepEqual(nextChunk(5), [1, 2]);
+  });
+
+  (global.gc ? it : it.skip)(
+    "drops the Rust iterator when garbage collected",
+    async function () {
+      // Finalizers run on later ticks of the event loop
+      async function collect() {
+        for (let i = 0; i < 10; i++) {
+          global.gc();
+          await new Promise((resolve) => setImmediate(resolve));
+        }
+      }
+
+      // Iterators left by other tests are collected first
+      await collect();
+
+      const before = addon.boxed_range_dropped();
+
+      (() => {
+        const it = addon.boxed_range(10);
+
+        it.next();
+      })();
+
+      await collect();
+
+      assert.strictEqual(addon.boxed_range_dropped(), before + 1);
+    }
+  );
+});
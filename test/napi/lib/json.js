@@ -0,0 +1,46 @@
+const addon = require("..");
+const { assert } = require("chai");
+
+describe("JSON", function () {
+  it("parses JSON", function () {
+    assert.deepEqual(addon.json_parse('{"a":[1,true,null,"b"]}'), {
+      a: [1, true, null, "b"],
+    });
+    assert.strictEqual(addon.json_parse("42"), 42);
+  });
+
+  it("throws on invalid JSON", function () {
+    assert.throws(() => addon.json_parse("{"), SyntaxError);
+  });
+
+  it("stringifies values", function () {
+    assert.strictEqual(addon.json_stringify({ a: [1, "b"] }), '{"a":[1,"b"]}');
+    assert.strictEqual(addon.json_stringify("hi"), '"hi"');
+  });
+
+  it("stringifies values with an indent", function () {
+    assert.strictEqual(
+      addon.json_stringify({ a: 1 }, 2),
+      JSON.stringify({ a: 1 }, null, 2)
+    );
+  });
+
+  it("throws on circular structures", function () {
+    const obj = {};
+    obj.self = obj;
+
+    assert.throws(() => addon.json_stringify(obj), TypeError);
+  });
+
+  it("throws on values that cannot be serialized", function () {
+    assert.throws(
+      () => addon.json_stringify(undefined),
+      TypeError,
+      /cannot be serialized/
+    );
+  });
+
+  it("builds the same object from JSON and from properties", function () {
+    assert.deepEqual(addon.build_object_json(10), addon.build_object_graph(10));
+  });
+});
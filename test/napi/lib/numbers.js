@@ -56,4 +56,60 @@ describe("JsNumber", function () {
       assert.equal(addon.accept_and_return_negative_js_number(-55), -55);
     });
   });
+
+  describe("checked conversions", function () {
+    it("converts integers that are represented exactly", function () {
+      assert.strictEqual(addon.number_checked_u64("9007199254740992"), 2 ** 53);
+      assert.strictEqual(
+        addon.number_checked_u64("1152921504606846976"),
+        2 ** 60
+      );
+      assert.strictEqual(
+        addon.number_checked_i64("-9007199254740991"),
+        -(2 ** 53 - 1)
+      );
+      assert.strictEqual(addon.usize_into_js("4294967296"), 2 ** 32);
+    });
+
+    it("throws a RangeError on precision loss", function () {
+      assert.throws(
+        () => addon.number_checked_u64("9007199254740993"),
+        RangeError,
+        /9007199254740993 cannot be represented exactly/
+      );
+      assert.throws(
+        () => addon.number_checked_u64("18446744073709551615"),
+        RangeError
+      );
+      assert.throws(
+        () => addon.number_checked_i64("-9007199254740993"),
+        RangeError
+      );
+      assert.throws(() => addon.usize_into_js("9007199254740993"), RangeError);
+    });
+
+    it("rounds when lossy conversion is requested", function () {
+      assert.strictEqual(
+        addon.u64_into_js_lossy("9007199254740993"),
+        9007199254740992
+      );
+      assert.strictEqual(
+        addon.u64_into_js_lossy("18446744073709551615"),
+        2 ** 64
+      );
+    });
+
+    it("returns a number or a BigInt", function () {
+      assert.strictEqual(addon.int_or_bigint("42"), 42);
+      assert.strictEqual(
+        addon.int_or_bigint("9007199254740991"),
+        Number.MAX_SAFE_INTEGER
+      );
+      assert.strictEqual(addon.int_or_bigint("9007199254740992"), 2n ** 53n);
+      assert.strictEqual(
+        addon.int_or_bigint("18446744073709551615"),
+        2n ** 64n - 1n
+      );
+    });
+  });
 });
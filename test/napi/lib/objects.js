@@ -1,5 +1,7 @@
 var addon = require("..");
 var assert = require("chai").assert;
+var { Worker } = require("worker_threads");
+var { spawnSync } = require("child_process");
 
 describe("JsObject", function () {
   it("return the v8::Global object", function () {
@@ -124,4 +126,240 @@ describe("JsObject", function () {
 
     assert.strictEqual(addon.call_symbol_method(obj, sym), "hello");
   });
+
+  it("sets and gets a symbol-keyed property", function () {
+    const [obj, sym, value, has, description] =
+      addon.symbol_property_round_trip();
+
+    assert.typeOf(sym, "symbol");
+    assert.strictEqual(obj[sym], "hidden");
+    assert.strictEqual(value, "hidden");
+    assert.isTrue(has);
+    assert.strictEqual(description, "neon");
+    assert.deepEqual(Object.keys(obj), []);
+  });
+
+  it("sets and gets element 2^32 on a plain object", function () {
+    const obj = {};
+    const index = 2 ** 32;
+
+    assert.strictEqual(addon.set_large_index(obj, "big", index), "big");
+    assert.strictEqual(obj[index], "big");
+    assert.deepEqual(Object.keys(obj), ["4294967296"]);
+  });
+
+  it("sets and gets u32 indices on a plain object", function () {
+    const obj = {};
+
+    assert.strictEqual(addon.set_large_index(obj, "max", 2 ** 32 - 1), "max");
+    assert.strictEqual(addon.set_large_index(obj, "zero", 0), "zero");
+    assert.deepEqual(obj, { 0: "zero", 4294967295: "max" });
+  });
+
+  it("throws a RangeError for indices beyond Number.MAX_SAFE_INTEGER", function () {
+    assert.throws(
+      () => addon.set_large_index({}, 1, 2 ** 54),
+      RangeError,
+      /MAX_SAFE_INTEGER/
+    );
+  });
+
+  it("checks for properties with has", function () {
+    const sym = Symbol("key");
+    const obj = Object.create({ inherited: true });
+
+    obj[sym] = 1;
+    obj.own = 2;
+
+    assert.isTrue(addon.has_property(obj, "own"));
+    assert.isTrue(addon.has_property(obj, "inherited"));
+    assert.isTrue(addon.has_property(obj, sym));
+    assert.isFalse(addon.has_property(obj, "missing"));
+    assert.isFalse(addon.has_property(obj, Symbol("key")));
+    assert.isTrue(addon.has_index([1, 2, 3], 2));
+    assert.isFalse(addon.has_index([1, 2, 3], 3));
+  });
+
+  it("deletes properties with delete", function () {
+    const sym = Symbol("key");
+    const arr = [1, 2, 3];
+    const obj = { [sym]: 1, a: 2 };
+
+    Object.defineProperty(obj, "fixed", { value: 3 });
+
+    assert.isTrue(addon.delete_property(obj, sym));
+    assert.isTrue(addon.delete_property(obj, "a"));
+    assert.isFalse(addon.delete_property(obj, "fixed"));
+    assert.isTrue(addon.delete_index(arr, 1));
+    assert.deepEqual(Object.keys(obj), []);
+    assert.strictEqual(obj.fixed, 3);
+    assert.isFalse(1 in arr);
+  });
+
+  it("defines properties with define_property", function () {
+    const sym = Symbol("key");
+    const obj = {};
+
+    addon.define_property(obj, "a", 1, {
+      writable: false,
+      enumerable: true,
+      configurable: false,
+    });
+    addon.define_property(obj, sym, 2, {
+      writable: true,
+      enumerable: false,
+      configurable: true,
+    });
+    addon.define_index(obj, 7, 3);
+
+    assert.deepEqual(Object.getOwnPropertyDescriptor(obj, "a"), {
+      value: 1,
+      writable: false,
+      enumerable: true,
+      configurable: false,
+    });
+    assert.deepEqual(Object.getOwnPropertyDescriptor(obj, sym), {
+      value: 2,
+      writable: true,
+      enumerable: false,
+      configurable: true,
+    });
+    assert.deepEqual(Object.getOwnPropertyDescriptor(obj, 7), {
+      value: 3,
+      writable: false,
+      enumerable: false,
+      configurable: false,
+    });
+    assert.throws(
+      () =>
+        addon.define_property(obj, "a", 4, {
+          writable: true,
+          enumerable: true,
+          configurable: true,
+        }),
+      TypeError
+    );
+  });
+
+  it("deeply clones a value with structured_clone", function () {
+    const date = new Date();
+    const obj = { a: [1, { b: "c" }], date, map: new Map([[1, 2]]) };
+    const clone = addon.structured_clone(obj);
+
+    assert.notStrictEqual(clone, obj);
+    assert.notStrictEqual(clone.a, obj.a);
+    assert.deepEqual(clone, obj);
+  });
+
+  it("detaches transferred values with structured_clone", function () {
+    const buf = new ArrayBuffer(8);
+    const clone = addon.structured_clone({ buf }, [buf]);
+
+    assert.strictEqual(buf.byteLength, 0);
+    assert.strictEqual(clone.buf.byteLength, 8);
+  });
+
+  it("throws a DataCloneError for values that cannot be cloned", function () {
+    assert.throws(
+      () => addon.structured_clone(() => {}),
+      DOMException,
+      /could not be cloned/
+    );
+    assert.throws(() => addon.structured_clone(Symbol("nope")), DOMException);
+  });
+
+  function collect() {
+    // Finalizers may be deferred until after garbage collection
+    return (async () => {
+      for (var i = 0; i < 10; i++) {
+        global.gc();
+        await new Promise((resolve) => setImmediate(resolve));
+      }
+    })();
+  }
+
+  (global.gc ? it : it.skip)(
+    "runs a finalizer when an object is collected",
+    async function () {
+      var before = addon.finalized_count();
+      var guard = (function () {
+        return addon.add_counting_finalizer({});
+      })();
+
+      assert.strictEqual(addon.finalizer_is_pending(guard), true);
+
+      await collect();
+
+      assert.strictEqual(addon.finalized_count(), before + 1);
+      assert.strictEqual(addon.finalizer_is_pending(guard), false);
+      assert.strictEqual(addon.cancel_finalizer(guard), false);
+    }
+  );
+
+  (global.gc ? it : it.skip)("cancels a finalizer", async function () {
+    var before = addon.finalized_count();
+    var guard = (function () {
+      return addon.add_counting_finalizer({});
+    })();
+
+    assert.strictEqual(addon.cancel_finalizer(guard), true);
+
+    await collect();
+
+    assert.strictEqual(addon.finalized_count(), before);
+  });
+
+  (global.gc ? it : it.skip)(
+    "runs a finalizer with a context on a later tick",
+    async function () {
+      var received = [];
+
+      (function () {
+        addon.add_context_finalizer({}, (arg) => received.push(arg));
+      })();
+
+      await collect();
+
+      assert.deepEqual(received, ["finalized"]);
+    }
+  );
+
+  it("runs finalizers for live objects when a worker exits", function (done) {
+    var before = addon.finalized_count();
+    var worker = new Worker(
+      `
+        const addon = require(${JSON.stringify(require.resolve(".."))});
+        globalThis.kept = {};
+        addon.add_counting_finalizer(globalThis.kept);
+      `,
+      { eval: true }
+    );
+
+    worker.once("error", done);
+    worker.once("exit", () => {
+      try {
+        assert.strictEqual(addon.finalized_count(), before + 1);
+        done();
+      } catch (err) {
+        done(err);
+      }
+    });
+  });
+
+  it("exits with a pending context finalizer", function () {
+    this.timeout(30000);
+
+    var script = `
+      const addon = require(${JSON.stringify(require.resolve(".."))});
+      globalThis.kept = {};
+      addon.add_context_finalizer(globalThis.kept, () => {});
+    `;
+    var { status, signal, stderr } = spawnSync(process.execPath, ["-e", script], {
+      encoding: "utf8",
+      timeout: 10000,
+    });
+
+    assert.strictEqual(signal, null, "timed out");
+    assert.strictEqual(status, 0, stderr);
+  });
 });
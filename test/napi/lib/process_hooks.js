@@ -0,0 +1,212 @@
+const addon = require("..");
+const assert = require("chai").assert;
+const { spawnSync } = require("child_process");
+const path = require("path");
+
+// Resolves once `process` has `n` listeners for `event`
+async function listenerCount(event, n) {
+  for (let i = 0; i < 100 && process.listenerCount(event) !== n; i++) {
+    await new Promise((resolve) => setTimeout(resolve, 10));
+  }
+
+  assert.strictEqual(process.listenerCount(event), n);
+}
+
+describe("process error hooks", function () {
+  let unhandledRejectionListeners = [];
+
+  beforeEach(() => {
+    unhandledRejectionListeners = process.listeners("unhandledRejection");
+    process.removeAllListeners("unhandledRejection");
+  });
+
+  afterEach(() => {
+    // Restore listeners
+    process.removeAllListeners("unhandledRejection");
+    unhandledRejectionListeners.forEach((listener) =>
+      process.on("unhandledRejection", listener)
+    );
+  });
+
+  it("should call the hook for unhandled rejections", function (cb) {
+    const err = new Error("Unhandled");
+    const guard = addon.on_unhandled_rejection((reason, promise) => {
+      try {
+        assert.strictEqual(reason, err);
+        assert.instanceOf(promise, Promise);
+
+        addon.remove_listener_guard(guard);
+        assert.strictEqual(process.listenerCount("unhandledRejection"), 0);
+
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+
+    assert.strictEqual(process.listenerCount("unhandledRejection"), 1);
+    Promise.reject(err);
+  });
+
+  it("should remove the listener when the guard is dropped", async function () {
+    const guard = addon.on_unhandled_rejection(() => {});
+
+    await listenerCount("unhandledRejection", 1);
+    addon.drop_listener_guard(guard);
+    await listenerCount("unhandledRejection", 0);
+  });
+
+  it("should remove the listener when the guard is dropped on another thread", async function () {
+    const guard = addon.on_unhandled_rejection(() => {});
+
+    await listenerCount("unhandledRejection", 1);
+    addon.drop_listener_guard_on_thread(guard);
+    await listenerCount("unhandledRejection", 0);
+  });
+
+  it("should not call hooks with the failures of hooks", function (cb) {
+    const err = new Error("Unhandled");
+    const calls = [];
+    const rejections = [];
+    const guard = addon.on_unhandled_rejection_panic((reason) =>
+      calls.push(reason)
+    );
+
+    process.on("unhandledRejection", (reason) => rejections.push(reason));
+    Promise.reject(err);
+
+    setTimeout(() => {
+      try {
+        addon.remove_listener_guard(guard);
+
+        assert.deepStrictEqual(calls, [err]);
+        assert.strictEqual(rejections.length, 2);
+        assert.strictEqual(rejections[0], err);
+        assert.match(rejections[1].message, /panic occurred/);
+        assert.strictEqual(rejections[1].panic.message, "Hello, Panic!");
+
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    }, 100);
+  });
+
+  it("should observe unhandled rejections without handling them", function () {
+    const script = `
+      const addon = require(${JSON.stringify(path.join(__dirname, ".."))});
+
+      globalThis.guards = [1, 2].map((n) =>
+        addon.on_unhandled_rejection((reason) => {
+          console.log("observed " + n + ": " + reason.message);
+        })
+      );
+
+      Promise.reject(new Error("Unhandled"));
+    `;
+
+    const { status, stdout, stderr } = spawnSync(
+      process.execPath,
+      ["-e", script],
+      { encoding: "utf8" }
+    );
+
+    assert.strictEqual(status, 1);
+    assert.include(stdout, "observed 1: Unhandled");
+    assert.include(stdout, "observed 2: Unhandled");
+    assert.include(stderr, "Error: Unhandled");
+  });
+
+  it("should keep the configured unhandled rejection mode", function () {
+    const script = `
+      const addon = require(${JSON.stringify(path.join(__dirname, ".."))});
+
+      globalThis.guard = addon.on_unhandled_rejection((reason) => {
+        console.log("observed: " + reason.message);
+      });
+
+      Promise.reject(new Error("Unhandled"));
+    `;
+
+    function run(mode, env = process.env) {
+      const args = mode ? [`--unhandled-rejections=${mode}`] : [];
+
+      return spawnSync(process.execPath, [...args, "-e", script], {
+        encoding: "utf8",
+        env,
+      });
+    }
+
+    for (const mode of ["warn", "none"]) {
+      const { status, stdout, stderr } = run(mode);
+
+      assert.strictEqual(status, 0, stderr);
+      assert.include(stdout, "observed: Unhandled");
+    }
+
+    const warned = run("warn-with-error-code");
+
+    assert.strictEqual(warned.status, 1);
+    assert.include(warned.stdout, "observed: Unhandled");
+    assert.include(warned.stderr, "UnhandledPromiseRejectionWarning");
+
+    const fromEnv = run(undefined, {
+      ...process.env,
+      NODE_OPTIONS: "--unhandled-rejections=none",
+    });
+
+    assert.strictEqual(fromEnv.status, 0, fromEnv.stderr);
+
+    const overridden = run("throw", {
+      ...process.env,
+      NODE_OPTIONS: "--unhandled-rejections=none",
+    });
+
+    assert.strictEqual(overridden.status, 1);
+    assert.include(overridden.stderr, "Error: Unhandled");
+  });
+
+  it("should not raise unhandled rejections handled by other listeners", function () {
+    const script = `
+      const addon = require(${JSON.stringify(path.join(__dirname, ".."))});
+
+      globalThis.guard = addon.on_unhandled_rejection(() => {});
+      process.on("unhandledRejection", (reason) => {
+        console.log("handled: " + reason.message);
+      });
+
+      Promise.reject(new Error("Unhandled"));
+    `;
+
+    const { status, stdout } = spawnSync(process.execPath, ["-e", script], {
+      encoding: "utf8",
+    });
+
+    assert.strictEqual(status, 0);
+    assert.include(stdout, "handled: Unhandled");
+  });
+
+  it("should observe uncaught exceptions without handling them", function () {
+    const script = `
+      const addon = require(${JSON.stringify(path.join(__dirname, ".."))});
+
+      globalThis.guard = addon.on_uncaught_exception((err) => {
+        console.log("observed: " + err.message);
+      });
+
+      setImmediate(() => {
+        throw new Error("Uncaught");
+      });
+    `;
+
+    const { status, stdout, stderr } = spawnSync(
+      process.execPath,
+      ["-e", script],
+      { encoding: "utf8" }
+    );
+
+    assert.strictEqual(status, 1);
+    assert.include(stdout, "observed: Uncaught");
+    assert.include(stderr, "Error: Uncaught");
+  });
+});
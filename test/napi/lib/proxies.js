@@ -0,0 +1,116 @@
+var addon = require("..");
+var assert = require("chai").assert;
+
+describe("Proxy", function () {
+  it("calls Rust traps for property access", function () {
+    var ns = addon.lazy_namespace();
+
+    assert.strictEqual(ns.loads, 0);
+    assert.strictEqual(ns.foo, "plugin:foo");
+    assert.strictEqual(ns.foo, "plugin:foo");
+    assert.strictEqual(ns.bar, "plugin:bar");
+    assert.strictEqual(ns.loads, 2);
+  });
+
+  it("calls Rust traps for has and ownKeys", function () {
+    var ns = addon.lazy_namespace();
+
+    assert.isTrue("pluginA" in ns);
+    assert.isFalse("other" in ns);
+    assert.deepEqual(Reflect.ownKeys(ns), ["a", "b"]);
+  });
+
+  it("propagates exceptions thrown by traps", function () {
+    var ns = addon.lazy_namespace();
+
+    assert.throws(() => ns.broken, /failed to load broken/);
+  });
+
+  it("forwards operations without a trap to the target", function () {
+    var ns = addon.lazy_namespace();
+
+    ns.x = 1;
+
+    assert.strictEqual(ns.x, 1);
+    assert.isTrue(delete ns.x);
+  });
+
+  it("can proxy a function", function () {
+    var f = addon.proxy_function(function () {
+      return "called";
+    });
+
+    assert.strictEqual(typeof f, "function");
+    assert.strictEqual(f(), "called");
+    assert.strictEqual(f.name, "proxied");
+  });
+
+  it("can create a revocable proxy", function () {
+    var [proxy, revoke] = addon.revocable_proxy();
+
+    assert.strictEqual(proxy.anything, 42);
+    revoke();
+    assert.throws(() => proxy.anything, TypeError);
+  });
+});
+
+describe("ArrayView", function () {
+  it("exposes the length and elements of a Vec", function () {
+    var view = addon.array_view(3);
+
+    assert.isTrue(Array.isArray(view));
+    assert.strictEqual(view.length, 3);
+    assert.strictEqual(view[0], "word0");
+    assert.strictEqual(view[2], "word2");
+    assert.strictEqual(view[3], undefined);
+    assert.isTrue(1 in view);
+    assert.isFalse(3 in view);
+    assert.deepEqual(Object.keys(view), ["0", "1", "2"]);
+  });
+
+  it("supports iteration and array methods", function () {
+    var view = addon.array_view(4);
+
+    assert.deepEqual([...view], ["word0", "word1", "word2", "word3"]);
+    assert.deepEqual(view.slice(1, 3), ["word1", "word2"]);
+    assert.deepEqual(
+      view.map((s) => s.length),
+      [5, 5, 5, 5]
+    );
+    assert.strictEqual(JSON.stringify(view.slice(-1)), '["word3"]');
+  });
+
+  it("is read-only without a setter", function () {
+    var view = addon.array_view(2);
+
+    assert.throws(() => {
+      view[0] = "other";
+    }, /read-only array view/);
+    assert.throws(() => view.push("other"), TypeError);
+    assert.throws(() => delete view[0], TypeError);
+    assert.strictEqual(view[0], "word0");
+  });
+
+  it("converts elements lazily", function () {
+    var [view, conversions] = addon.writable_array_view(1000);
+
+    assert.strictEqual(view[500], 500);
+    assert.deepEqual(view.slice(10, 12), [10, 11]);
+    assert.strictEqual(conversions(), 3);
+  });
+
+  it("calls the setter for assigned elements", function () {
+    var [view] = addon.writable_array_view(3);
+
+    view[1] = 10;
+
+    assert.deepEqual([...view], [0, 10, 2]);
+    assert.isTrue(Object.getOwnPropertyDescriptor(view, 1).writable);
+    assert.throws(() => {
+      view[3] = 1;
+    }, /array view of length 3/);
+    assert.throws(() => {
+      view[1] = "ten";
+    }, TypeError);
+  });
+});
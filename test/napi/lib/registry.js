@@ -0,0 +1,36 @@
+const addon = require("..");
+const assert = require("chai").assert;
+
+describe("registry", function () {
+  it("should export registered functions", function () {
+    assert.strictEqual(addon.registry_sum(new Float64Array([1, 2, 3.5])), 6.5);
+    assert.deepEqual(addon.registry_origin(), { x: 0 });
+    assert.strictEqual(addon.registry_greet("Neon"), "Hello, Neon!");
+  });
+
+  it("should declare registered functions and interfaces", function () {
+    assert.strictEqual(
+      addon.type_declarations(),
+      [
+        "export interface Point {",
+        "  x: number;",
+        "  label?: string;",
+        "}",
+        "",
+        "export function registry_sum(nums: Float64Array): number;",
+        "export function registry_origin(): Point;",
+        "export function registry_greet(name: string): string;",
+        "",
+      ].join("\n")
+    );
+  });
+
+  it("should declare every registered function once when loaded again", function () {
+    const declarations = addon.type_declarations();
+
+    delete require.cache[require.resolve("..")];
+    require("..");
+
+    assert.strictEqual(addon.type_declarations(), declarations);
+  });
+});
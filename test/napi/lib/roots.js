@@ -0,0 +1,109 @@
+const addon = require("..");
+const assert = require("chai").assert;
+
+describe("RootSet", function () {
+  it("should get inserted values", function () {
+    const set = addon.root_set_new();
+    const a = {};
+    const b = [1, 2, 3];
+
+    const ka = addon.root_set_insert(set, a);
+    const kb = addon.root_set_insert(set, b);
+    const kc = addon.root_set_insert(set, "primitive");
+
+    assert.strictEqual(addon.root_set_len(set), 3);
+    assert.strictEqual(addon.root_set_get(set, ka), a);
+    assert.strictEqual(addon.root_set_get(set, kb), b);
+    assert.strictEqual(addon.root_set_get(set, kc), "primitive");
+  });
+
+  it("should keep keys valid when other values are removed", function () {
+    const set = addon.root_set_new();
+    const a = {};
+    const b = {};
+
+    const ka = addon.root_set_insert(set, a);
+    const kb = addon.root_set_insert(set, b);
+
+    assert.strictEqual(addon.root_set_remove(set, ka), a);
+    assert.strictEqual(addon.root_set_len(set), 1);
+    assert.isFalse(addon.root_set_contains(set, ka));
+    assert.isTrue(addon.root_set_contains(set, kb));
+    assert.strictEqual(addon.root_set_get(set, kb), b);
+  });
+
+  it("should not reuse keys of removed values", function () {
+    const set = addon.root_set_new();
+    const a = {};
+    const b = {};
+
+    const ka = addon.root_set_insert(set, a);
+
+    addon.root_set_remove(set, ka);
+
+    const kb = addon.root_set_insert(set, b);
+
+    assert.strictEqual(addon.root_set_get(set, ka), null);
+    assert.strictEqual(addon.root_set_remove(set, ka), null);
+    assert.strictEqual(addon.root_set_get(set, kb), b);
+    assert.strictEqual(addon.root_set_len(set), 1);
+  });
+
+  it("should keep values alive until removed", async function () {
+    const set = addon.root_set_new();
+    const keys = [];
+    let collected = 0;
+
+    const registry = new FinalizationRegistry(() => collected++);
+
+    for (let i = 0; i < 10; i++) {
+      const obj = { i };
+
+      registry.register(obj, i);
+      keys.push(addon.root_set_insert(set, obj));
+    }
+
+    for (let i = 0; i < 3; i++) {
+      await new Promise((resolve) => setImmediate(resolve));
+      global.gc();
+    }
+
+    assert.strictEqual(collected, 0);
+    assert.strictEqual(addon.root_set_get(set, keys[9]).i, 9);
+
+    for (const key of keys.slice(0, 5)) {
+      addon.root_set_remove(set, key);
+    }
+
+    for (let i = 0; i < 10 && collected < 5; i++) {
+      await new Promise((resolve) => setImmediate(resolve));
+      global.gc();
+    }
+
+    assert.strictEqual(collected, 5);
+    assert.strictEqual(addon.root_set_len(set), 5);
+  });
+});
+
+describe("Root raw parts", function () {
+  it("should round-trip a Root through a napi_ref", function (cb) {
+    const obj = { value: 42 };
+
+    addon.root_napi_ref_round_trip(obj, (value) => {
+      try {
+        assert.strictEqual(value, obj);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it("should share a napi_ref between clones", function () {
+    const obj = {};
+    const [a, b] = addon.root_napi_ref_clone(obj);
+
+    assert.strictEqual(a, obj);
+    assert.strictEqual(b, obj);
+  });
+});
@@ -0,0 +1,213 @@
+const addon = require("..");
+const assert = require("chai").assert;
+const fs = require("fs");
+const path = require("path");
+const { Worker } = require("worker_threads");
+
+describe("runtime", function () {
+  it("should report the Node-API version", function () {
+    const features = addon.runtime_features();
+
+    assert.strictEqual(features.napiVersion, Number(process.versions.napi));
+  });
+
+  it("should detect the Node-API functions available in Node", function () {
+    assert.deepEqual(addon.runtime_features(), {
+      napiVersion: Number(process.versions.napi),
+      externalBuffers: true,
+      threadsafeFunctions: true,
+      date: true,
+      bigint: true,
+      instanceData: true,
+      detachArraybuffer: true,
+      typeTags: true,
+      objectFreeze: true,
+    });
+  });
+
+  it("should return the path of the loaded addon", function () {
+    const filename = addon.module_filename();
+
+    assert.strictEqual(
+      fs.realpathSync(filename),
+      fs.realpathSync(require.resolve(".."))
+    );
+  });
+
+  it("should return the directory of the loaded addon", function () {
+    assert.strictEqual(
+      addon.module_dir(),
+      path.dirname(addon.module_filename())
+    );
+  });
+
+  it("should require a built-in module from Rust", function () {
+    assert.strictEqual(
+      addon.require_path_join("a", "b"),
+      path.join("a", "b")
+    );
+  });
+
+  it("should require a module that exports a function", function () {
+    assert.strictEqual(addon.require_event_emitter(), require("events"));
+    assert.strictEqual(addon.require_module("node:events"), require("events"));
+  });
+
+  it("should resolve relative requires against the addon", function () {
+    assert.strictEqual(addon.require_module("./package.json").name, "napi-tests");
+  });
+
+  it("should throw the original error when a module cannot be found", function () {
+    assert.throws(
+      () => addon.require_module("./does-not-exist.js"),
+      /Cannot find module '\.\/does-not-exist\.js'/
+    );
+  });
+
+  it("should import an ES module from Rust", async function () {
+    const ns = await addon.import_module("node:path");
+
+    assert.strictEqual(ns.join, path.join);
+  });
+
+  it("should reject when an ES module cannot be found", async function () {
+    try {
+      await addon.import_module("./does-not-exist.mjs");
+    } catch (err) {
+      assert.match(err.message, /does-not-exist\.mjs/);
+      return;
+    }
+
+    assert.fail("expected import to reject");
+  });
+
+  it("should read environment variables set after startup", function () {
+    process.env.NEON_TEST_ENV = "hello";
+
+    try {
+      assert.strictEqual(addon.process_env("NEON_TEST_ENV"), "hello");
+      assert.strictEqual(addon.process_env_all().NEON_TEST_ENV, "hello");
+    } finally {
+      delete process.env.NEON_TEST_ENV;
+    }
+
+    assert.strictEqual(addon.process_env("NEON_TEST_ENV"), undefined);
+  });
+
+  it("should coerce environment variables to strings", function () {
+    process.env.NEON_TEST_ENV = 42;
+
+    try {
+      assert.strictEqual(addon.process_env("NEON_TEST_ENV"), "42");
+    } finally {
+      delete process.env.NEON_TEST_ENV;
+    }
+  });
+
+  it("should return the process id and arguments", function () {
+    assert.deepEqual(addon.process_info(), {
+      pid: process.pid,
+      argv: process.argv,
+    });
+  });
+
+  it("should refuse to load in a Node older than the addon requires", function (cb) {
+    // The addon requires Node 18; the worker reports an older version
+    const worker = new Worker(
+      `
+      const { parentPort, workerData } = require("worker_threads");
+
+      Object.defineProperty(process, "versions", {
+        value: { ...process.versions, node: "14.17.0" },
+      });
+
+      try {
+        require(workerData);
+        parentPort.postMessage(null);
+      } catch (err) {
+        parentPort.postMessage(err.message);
+      }
+      `,
+      { eval: true, workerData: require.resolve("..") }
+    );
+
+    worker.once("message", (message) => {
+      try {
+        assert.strictEqual(
+          message,
+          "napi-tests requires Node >= 18.0 (N-API 8); you are running " +
+            `Node 14.17.0 (N-API ${process.versions.napi})`
+        );
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+    worker.once("error", cb);
+  });
+
+  describe("performance", function () {
+    afterEach(function () {
+      addon.set_performance_calibration_interval(60_000);
+    });
+
+    it("should read performance.now()", function () {
+      const before = performance.now();
+      const now = addon.performance_now();
+      const after = performance.now();
+
+      assert.isAtLeast(now, before);
+      assert.isAtMost(now, after);
+    });
+
+    it("should read performance.timeOrigin", function () {
+      assert.strictEqual(addon.time_origin(), performance.timeOrigin);
+    });
+
+    it("should convert an Instant to a performance timestamp", function () {
+      const before = performance.now();
+      const timestamp = addon.performance_timestamp(0);
+      const after = performance.now();
+
+      // Allow for rounding in the calibration
+      assert.isAtLeast(timestamp, before - 1);
+      assert.isAtMost(timestamp, after + 1);
+    });
+
+    it("should convert an Instant in the past", function () {
+      const before = performance.now();
+      const timestamp = addon.performance_timestamp(100);
+      const after = performance.now();
+
+      assert.isAtLeast(timestamp, before - 101);
+      assert.isAtMost(timestamp, after - 99);
+    });
+
+    it("should convert an Instant captured on another thread", function (cb) {
+      const start = performance.now();
+
+      addon.performance_timestamp_from_thread((timestamp) => {
+        try {
+          assert.isAtLeast(timestamp, start + 19);
+          assert.isAtMost(timestamp, performance.now() + 1);
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      });
+    });
+
+    it("should recalibrate with a zero interval", function () {
+      addon.set_performance_calibration_interval(0);
+
+      for (let i = 0; i < 10; i++) {
+        const before = performance.now();
+        const timestamp = addon.performance_timestamp(0);
+        const after = performance.now();
+
+        assert.isAtLeast(timestamp, before - 1);
+        assert.isAtMost(timestamp, after + 1);
+      }
+    });
+  });
+});
@@ -0,0 +1,81 @@
+const addon = require("..");
+const assert = require("chai").assert;
+
+describe("Schema", function () {
+  it("should extract valid fields", function () {
+    const tags = ["a", "b"];
+    const options = addon.schema_extract_options({
+      retries: 5,
+      mode: "safe",
+      verbose: true,
+      tags,
+      name: "test",
+      extra: "ignored",
+    });
+
+    assert.deepEqual(options, {
+      retries: 5,
+      mode: "safe",
+      verbose: true,
+      name: "test",
+      tags,
+    });
+    assert.strictEqual(options.tags, tags);
+  });
+
+  it("should treat null and undefined optional fields as missing", function () {
+    const options = addon.schema_extract_options({
+      retries: null,
+      mode: "fast",
+      verbose: undefined,
+      name: "test",
+    });
+
+    assert.deepEqual(options, {
+      retries: 3,
+      mode: "fast",
+      verbose: false,
+      name: "test",
+      tags: [],
+    });
+  });
+
+  it("should report every invalid field", function () {
+    assert.throws(
+      () =>
+        addon.schema_extract_options({
+          retries: "3",
+          mode: "slow",
+          tags: {},
+        }),
+      TypeError,
+      '`retries`: expected number or undefined, found string; `mode`: expected one of "fast", "safe", found "slow"; `tags`: expected array or undefined, found object; `name`: expected string, found undefined'
+    );
+  });
+
+  it("should report the type of non-string enum values", function () {
+    assert.throws(
+      () => addon.schema_extract_options({ mode: 1, name: "test" }),
+      TypeError,
+      '`mode`: expected one of "fast", "safe", found number'
+    );
+  });
+
+  it("should throw if the value is not an object", function () {
+    assert.throws(
+      () => addon.schema_extract_options("options"),
+      TypeError,
+      "expected object, found string"
+    );
+  });
+
+  it("should propagate exceptions from getters", function () {
+    const options = {
+      get retries() {
+        throw new Error("getter failed");
+      },
+    };
+
+    assert.throws(() => addon.schema_extract_options(options), "getter failed");
+  });
+});
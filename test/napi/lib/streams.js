@@ -0,0 +1,201 @@
+const addon = require("..");
+const assert = require("chai").assert;
+const { Readable, Writable } = require("stream");
+const { pipeline } = require("stream/promises");
+
+function collector(chunks, options) {
+  return new Writable({
+    ...options,
+    write(chunk, _encoding, callback) {
+      chunks.push(chunk);
+      setImmediate(callback);
+    },
+  });
+}
+
+describe("streams", function () {
+  describe("readable", function () {
+    it("creates a Readable stream", function () {
+      const { stream } = addon.stream_readable(0, 1);
+
+      assert.instanceOf(stream, Readable);
+    });
+
+    it("creates a Readable stream without process.getBuiltinModule", function () {
+      const { getBuiltinModule } = process;
+
+      // Emulates versions of Node before 20.16 and 22.3
+      process.getBuiltinModule = undefined;
+
+      try {
+        const { stream } = addon.stream_readable(0, 1);
+
+        assert.instanceOf(stream, Readable);
+      } finally {
+        process.getBuiltinModule = getBuiltinModule;
+      }
+    });
+
+    it("pipes the data to a writable stream", async function () {
+      const total = 1024 * 1024;
+      const { stream } = addon.stream_readable(total, 1000);
+      const chunks = [];
+
+      await pipeline(stream, collector(chunks, { highWaterMark: 1024 }));
+
+      const data = Buffer.concat(chunks);
+
+      assert.strictEqual(data.length, total);
+
+      for (let i = 0; i < total; i++) {
+        if (data[i] !== i % 256) {
+          assert.fail(`unexpected byte ${data[i]} at ${i}`);
+        }
+      }
+    });
+
+    it("stops pulling when the buffer is full", function (cb) {
+      const { stream, pulls } = addon.stream_readable(1024 * 1024, 1024);
+
+      // Pause to fill the internal buffer without consuming it
+      stream.pause();
+      stream.read(0);
+
+      setTimeout(() => {
+        const n = pulls();
+
+        assert.isAbove(n, 0);
+        // The default `highWaterMark` is 16KiB
+        assert.isBelow(n, 32);
+
+        stream.destroy();
+        cb();
+      }, 20);
+    });
+
+    it("destroys the stream with the pull error", async function () {
+      const stream = addon.stream_readable_error("Pull failed");
+      const chunks = [];
+
+      try {
+        await pipeline(stream, collector(chunks));
+        assert.fail("expected the pipeline to fail");
+      } catch (err) {
+        assert.instanceOf(err, Error);
+        assert.strictEqual(err.message, "Pull failed");
+      }
+
+      assert.isTrue(stream.destroyed);
+    });
+  });
+
+  describe("writable", function () {
+    const size = 10 * 1024 * 1024;
+    const chunkSize = 64 * 1024;
+    const data = Buffer.alloc(size);
+
+    for (let i = 0; i < size; i++) {
+      data[i] = (i * 7) % 251;
+    }
+
+    const expectedSum = data.reduce((sum, b) => sum + b, 0);
+
+    function* chunks() {
+      for (let i = 0; i < size; i += chunkSize) {
+        yield data.subarray(i, i + chunkSize);
+      }
+    }
+
+    function finished() {
+      let resolve;
+      const promise = new Promise((r) => (resolve = r));
+
+      return [promise, (...args) => resolve(args)];
+    }
+
+    it("creates a Writable stream", function () {
+      assert.instanceOf(
+        addon.stream_writable(0, () => {}),
+        Writable
+      );
+    });
+
+    it("creates a Writable stream without process.getBuiltinModule", function () {
+      const { getBuiltinModule } = process;
+
+      // Emulates versions of Node before 20.16 and 22.3
+      process.getBuiltinModule = undefined;
+
+      try {
+        assert.instanceOf(
+          addon.stream_writable(0, () => {}),
+          Writable
+        );
+      } finally {
+        process.getBuiltinModule = getBuiltinModule;
+      }
+    });
+
+    it("receives piped chunks", async function () {
+      const [done, callback] = finished();
+      const sink = addon.stream_writable(size, callback);
+
+      await pipeline(Readable.from(chunks()), sink);
+
+      assert.deepEqual(await done, [null, size, expectedSum]);
+    });
+
+    it("receives piped chunks acknowledged from a thread", async function () {
+      const [done, callback] = finished();
+      const sink = addon.stream_writable_with_ack(chunkSize, callback);
+
+      await pipeline(Readable.from(chunks()), sink);
+
+      assert.deepEqual(await done, [null, size, expectedSum]);
+    });
+
+    it("applies backpressure until chunks are acknowledged", async function () {
+      const [done, callback] = finished();
+      const sink = addon.stream_writable_with_ack(chunkSize, callback);
+      const chunk = Buffer.alloc(1024, 1);
+      const results = [];
+
+      // Acks are delivered asynchronously, so the chunks remain buffered
+      for (let i = 0; i < 128; i++) {
+        results.push(sink.write(chunk));
+      }
+
+      assert.isTrue(results[0]);
+      assert.isFalse(results[127]);
+
+      sink.end();
+
+      assert.deepEqual(await done, [null, 128 * 1024, 128 * 1024]);
+    });
+
+    it("destroys the stream with the chunk error", async function () {
+      const [done, callback] = finished();
+      const sink = addon.stream_writable(chunkSize, callback);
+
+      try {
+        await pipeline(Readable.from(chunks()), sink);
+        assert.fail("expected the pipeline to fail");
+      } catch (err) {
+        assert.instanceOf(err, Error);
+        assert.strictEqual(err.message, "Too much data");
+      }
+
+      assert.deepEqual(await done, ["Too much data"]);
+    });
+
+    it("reports a stream destroyed before finishing", async function () {
+      const [done, callback] = finished();
+      const sink = addon.stream_writable(size, callback);
+
+      sink.write(Buffer.alloc(1));
+      sink.destroy();
+
+      assert.deepEqual(await done, ["stream was destroyed before finishing"]);
+    });
+  });
+});
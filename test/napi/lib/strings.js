@@ -32,4 +32,157 @@ describe("JsString", function () {
       );
     });
   });
+
+  describe("base64 and hex", function () {
+    // Deterministic pseudo-random bytes so that failures are reproducible
+    function randomBytes(seed, len) {
+      var buf = Buffer.alloc(len);
+      var state = seed;
+
+      for (var i = 0; i < len; i++) {
+        state = (state * 1103515245 + 12345) & 0x7fffffff;
+        buf[i] = state >> 16;
+      }
+
+      return buf;
+    }
+
+    it("encodes like Buffer", function () {
+      for (var len = 0; len < 200; len++) {
+        var buf = randomBytes(len, len);
+
+        assert.strictEqual(addon.encode_base64(buf), buf.toString("base64"));
+        assert.strictEqual(addon.encode_hex(buf), buf.toString("hex"));
+      }
+    });
+
+    it("decodes like Buffer", function () {
+      for (var len = 0; len < 200; len++) {
+        var buf = randomBytes(len + 1000, len);
+        var base64 = buf.toString("base64");
+        var unpadded = base64.replace(/=+$/, "");
+        var hex = buf.toString("hex");
+
+        assert.deepEqual(addon.decode_base64(base64), buf);
+        assert.deepEqual(addon.decode_base64(unpadded), buf);
+        assert.deepEqual(addon.decode_hex(hex), buf);
+        assert.deepEqual(addon.decode_hex(hex.toUpperCase()), buf);
+      }
+    });
+
+    it("reports the offset of invalid base64 characters", function () {
+      for (var len = 1; len < 100; len++) {
+        var base64 = randomBytes(len, len).toString("base64");
+        var unpadded = base64.replace(/=+$/, "");
+        var offset = len % unpadded.length;
+        var chars = ["!", " ", "-", "_", "\u0141"];
+
+        // Padding is only invalid if it is followed by data
+        if (offset + 2 < unpadded.length) {
+          chars.push("=");
+        }
+
+        chars.forEach((c) => {
+          var invalid =
+            base64.slice(0, offset) + c + base64.slice(offset + 1);
+
+          assert.throws(
+            () => addon.decode_base64(invalid),
+            TypeError,
+            "invalid base64 character at offset " + offset
+          );
+        });
+      }
+    });
+
+    it("rejects base64 with an invalid length", function () {
+      assert.throws(
+        () => addon.decode_base64("QUJDR"),
+        TypeError,
+        "unexpected end of base64 string at offset 5"
+      );
+      assert.throws(
+        () => addon.decode_base64("QQ="),
+        TypeError,
+        "unexpected end of base64 string at offset 3"
+      );
+    });
+
+    it("reports the offset of invalid hex characters", function () {
+      for (var len = 1; len < 100; len++) {
+        var hex = randomBytes(len, len).toString("hex");
+        var offset = (len * 7) % hex.length;
+
+        ["g", "G", " ", "\u0141"].forEach((c) => {
+          var invalid = hex.slice(0, offset) + c + hex.slice(offset + 1);
+
+          assert.throws(
+            () => addon.decode_hex(invalid),
+            TypeError,
+            "invalid hex character at offset " + offset
+          );
+        });
+      }
+    });
+
+    it("rejects hex with an odd length", function () {
+      assert.throws(
+        () => addon.decode_hex("abc"),
+        TypeError,
+        "unexpected end of hex string at offset 3"
+      );
+      assert.throws(
+        () => addon.decode_hex("abz"),
+        TypeError,
+        "invalid hex character at offset 2"
+      );
+    });
+  });
+
+  describe("JsStringBuilder", function () {
+    it("concatenates UTF-8 and UTF-16 fragments", function () {
+      assert.strictEqual(addon.build_string([]), "");
+      assert.strictEqual(
+        addon.build_string(["hello", " ", "wörld", [0x20, 0xd83e, 0xdd79], "!"]),
+        "hello wörld 🥹!"
+      );
+    });
+
+    it("preserves unpaired surrogates", function () {
+      assert.strictEqual(
+        addon.build_string(["a", [0xd800], "b", [0xdc00]]),
+        "a\ud800b\udc00"
+      );
+    });
+
+    it("builds strings larger than a chunk", function () {
+      const ascii = "0123456789abcdef".repeat(4096);
+      const fragments = [ascii, "ü".repeat(100_000), ascii, ascii.slice(0, 7)];
+      const units = Array.from({ length: 300_000 }, (_, i) => 0x4e00 + (i % 64));
+
+      const expected =
+        fragments.join("") +
+        units.map((u) => String.fromCharCode(u)).join("") +
+        ascii;
+
+      assert.strictEqual(
+        addon.build_string([...fragments, units, ascii]),
+        expected
+      );
+    });
+
+    it("matches a string built in Rust", function () {
+      const fragment = "id,name,value\n1,🥹,0.5\n";
+      const count = 100_000;
+
+      assert.strictEqual(
+        addon.repeat_string_builder(fragment, count),
+        addon.repeat_string_naive(fragment, count)
+      );
+      assert.strictEqual(
+        addon.repeat_string_builder("x", count),
+        "x".repeat(count)
+      );
+    });
+  });
 });
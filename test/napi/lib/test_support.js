@@ -0,0 +1,124 @@
+const addon = require("..");
+const { assert } = require("chai");
+
+describe("test support", function () {
+  it("uses every helper from Rust", function () {
+    addon.test_support_self_test();
+  });
+
+  describe("diff", function () {
+    it("matches equal values", function () {
+      assert.isNull(addon.js_value_diff(null, "null"));
+      assert.isNull(addon.js_value_diff(true, "true"));
+      assert.isNull(addon.js_value_diff(1.5, "1.5"));
+      assert.isNull(addon.js_value_diff("a", '"a"'));
+      assert.isNull(
+        addon.js_value_diff(
+          { a: [1, { b: "c" }], d: null },
+          '{"d":null,"a":[1,{"b":"c"}]}'
+        )
+      );
+    });
+
+    it("ignores undefined properties", function () {
+      assert.isNull(addon.js_value_diff({ a: 1, b: undefined }, '{"a":1}'));
+    });
+
+    it("reports the path of the first difference", function () {
+      assert.strictEqual(
+        addon.js_value_diff({ a: [1, { b: "c" }] }, '{"a":[1,{"b":"d"}]}'),
+        '$.a[1].b: expected "d", found "c"'
+      );
+      assert.strictEqual(
+        addon.js_value_diff([1, 2], "[1, 2, 3]"),
+        "$: expected an array of length 3, found length 2"
+      );
+    });
+
+    it("reports missing and unexpected properties", function () {
+      assert.strictEqual(
+        addon.js_value_diff({}, '{"a":1}'),
+        "$.a: expected 1, found undefined"
+      );
+      assert.strictEqual(
+        addon.js_value_diff({ a: 1, b: 2 }, '{"a":1}'),
+        "$: unexpected property `b`"
+      );
+    });
+
+    it("does not match values that JSON cannot represent", function () {
+      assert.strictEqual(
+        addon.js_value_diff(undefined, "null"),
+        "$: expected null, found undefined"
+      );
+      assert.strictEqual(
+        addon.js_value_diff(() => {}, "{}"),
+        "$: expected {}, found a function"
+      );
+      assert.strictEqual(
+        addon.js_value_diff([], "{}"),
+        "$: expected {}, found an array of length 0"
+      );
+    });
+  });
+
+  it("asserts that values are equal", function () {
+    addon.assert_js_eq_json([1, "a"], '[1,"a"]');
+    assert.throws(
+      () => addon.assert_js_eq_json([1, "a"], '[1,"b"]'),
+      /JavaScript value does not match: \$\[1\]: expected "b", found "a"/
+    );
+  });
+
+  it("returns caught exceptions", function () {
+    assert.strictEqual(
+      addon.expect_throw_message(() => {
+        throw new TypeError("bad");
+      }),
+      "bad"
+    );
+    assert.strictEqual(
+      addon.expect_throw_message(() => {
+        throw "oops";
+      }),
+      "oops"
+    );
+  });
+
+  it("fails when no exception is thrown", function () {
+    assert.throws(
+      () => addon.expect_throw_message(() => {}),
+      /expected an exception to be thrown/
+    );
+  });
+
+  it("records calls", function () {
+    const receiver = {};
+    const calls = addon.recorded_calls((f) => {
+      f(1, "a");
+      f.call(receiver);
+      f.apply(null, [[2], { b: 3 }]);
+    });
+
+    assert.strictEqual(calls.length, 3);
+    assert.deepEqual(calls[0].args, [1, "a"]);
+    assert.strictEqual(calls[1].this, receiver);
+    assert.deepEqual(calls[1].args, []);
+    assert.deepEqual(calls[2].args, [[2], { b: 3 }]);
+  });
+
+  it("keeps recorded arguments alive", function () {
+    const calls = addon.recorded_calls((f) => {
+      for (let i = 0; i < 10; i++) {
+        f({ i });
+      }
+
+      global.gc();
+    });
+
+    assert.deepEqual(
+      calls.map((call) => call.args[0].i),
+      [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+    );
+  });
+});
@@ -1,5 +1,9 @@
 const addon = require("..");
 const assert = require("chai").assert;
+const { spawnSync } = require("child_process");
+const fs = require("fs");
+const os = require("os");
+const path = require("path");
 
 (function () {
   // These tests require GC exposed to shutdown properly; skip if it is not
@@ -61,19 +65,23 @@ const assert = require("chai").assert;
   });
 
   it("should run callback on drop", function (cb) {
+    let collected = false;
+
     // IIFE to allow GC
     (function () {
       addon.greeter_new(
         "Hello, World!",
         function () {},
         function () {
-          // No assert needed; test will timeout
+          // Deferred until after garbage collection
+          assert.strictEqual(collected, true);
           cb();
         }
       );
     })();
 
     global.gc();
+    collected = true;
   });
 
   it("should be able to unref channel", function () {
@@ -81,6 +89,356 @@ const assert = require("chai").assert;
     addon.leak_channel();
   });
 
+  describe("shared channel", function () {
+    // Each threadsafe function is backed by a libuv async handle
+    function asyncHandles() {
+      return process.report
+        .getReport()
+        .libuv.filter((handle) => handle.type === "async").length;
+    }
+
+    it("should share a threadsafe function between objects", function () {
+      const before = asyncHandles();
+      const objects = addon.channel_objects(10000, false);
+
+      assert.strictEqual(objects.length, 10000);
+      // The shared channel is created on first use
+      assert.isAtMost(asyncHandles() - before, 1);
+    });
+
+    it("should create a threadsafe function for each new channel", function () {
+      const before = asyncHandles();
+      const objects = addon.channel_objects(10, true);
+
+      assert.strictEqual(objects.length, 10);
+      assert.strictEqual(asyncHandles() - before, 10);
+    });
+
+    it("should keep the event loop alive while any clone is referenced", function () {
+      this.timeout(30000);
+
+      const script = `
+        const addon = require(${JSON.stringify(path.join(__dirname, ".."))});
+
+        addon.channel_unref_clone(() => console.log("called"));
+      `;
+
+      const { status, stdout, stderr } = spawnSync(
+        process.execPath,
+        ["-e", script],
+        { encoding: "utf8", timeout: 30000 }
+      );
+
+      assert.strictEqual(status, 0, stderr);
+      assert.include(stdout, "called");
+    });
+  });
+
+  it("should deliver threadsafe function messages in order", function (cb) {
+    const n = 1000;
+    let expected = 0;
+
+    addon.tsfn_count(n, function (i) {
+      assert.strictEqual(i, expected++);
+
+      if (expected === n) {
+        cb();
+      }
+    });
+  });
+
+  it("should fail to call a threadsafe function with a full queue", function (cb) {
+    const received = [];
+    const results = addon.tsfn_try_call_full((i) => received.push(i));
+
+    assert.deepEqual(results, [true, false, true]);
+
+    setTimeout(() => {
+      assert.deepEqual(received, [0]);
+      cb();
+    }, 10);
+  });
+
+  it("should not deliver messages after aborting a threadsafe function", function (cb) {
+    let called = false;
+
+    assert.strictEqual(addon.tsfn_abort(() => (called = true)), true);
+
+    setTimeout(() => {
+      assert.strictEqual(called, false);
+      cb();
+    }, 10);
+  });
+
+  it("should unref a threadsafe function while a producer is blocked", function (cb) {
+    const received = [];
+
+    addon.tsfn_blocked_producer((i) => {
+      received.push(i);
+
+      if (received.length === 3) {
+        assert.deepEqual(received, [0, 1, 2]);
+        cb();
+      }
+    }, "unref");
+  });
+
+  it("should abort a threadsafe function while a producer is blocked", function () {
+    assert.strictEqual(addon.tsfn_blocked_producer(() => {}, "abort"), true);
+  });
+
+  it("should be able to unref a threadsafe function", function () {
+    // If the threadsafe function is not unreferenced, the test runner will not cleanly exit
+    addon.leak_tsfn(() => {});
+  });
+
+  [false, true].forEach((batch) => {
+    it(`should emit events in order from a thread (batch: ${batch})`, function (cb) {
+      const EventEmitter = require("events");
+      const emitter = new EventEmitter();
+      const received = [];
+
+      emitter.on("data", (i) => received.push(i));
+      emitter.on("end", ({ count }) => {
+        try {
+          assert.strictEqual(count, 100);
+          assert.deepEqual(
+            received,
+            Array.from({ length: count }, (_, i) => i)
+          );
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      });
+
+      addon.emitter_count(emitter, 100, batch);
+    });
+
+    it(`should report listener exceptions and keep emitting (batch: ${batch})`, function (cb) {
+      const EventEmitter = require("events");
+      const emitter = new EventEmitter();
+      const received = [];
+      let rejection;
+
+      process.removeAllListeners("unhandledRejection");
+      process.once("unhandledRejection", (err) => (rejection = err));
+
+      emitter.on("data", (i) => {
+        received.push(i);
+
+        if (i === 1) {
+          throw new Error("Listener failed");
+        }
+      });
+
+      emitter.on("end", () => {
+        // Unhandled rejections are reported after the current microtasks
+        setTimeout(() => {
+          try {
+            assert.deepEqual(received, [0, 1, 2, 3]);
+            assert.instanceOf(rejection, Error);
+            assert.match(rejection.message, /exception/i);
+            assert.strictEqual(rejection.cause.message, "Listener failed");
+            cb();
+          } catch (err) {
+            cb(err);
+          }
+        }, 10);
+      });
+
+      addon.emitter_count(emitter, 4, batch);
+    });
+  });
+
+  it("should throw if the emitter has no emit method", function () {
+    assert.throws(() => addon.emitter_count({}, 0, false), TypeError);
+  });
+
+  it("should call a callback from multiple threads", function (cb) {
+    const calls = [];
+
+    addon.callback_sum(
+      (i) => {
+        calls.push(i);
+
+        return i * 2;
+      },
+      8,
+      (sum) => {
+        try {
+          assert.strictEqual(sum, 56);
+          assert.sameMembers(calls, [0, 1, 2, 3, 4, 5, 6, 7]);
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }
+    );
+  });
+
+  it("should call a callback with arguments created by a closure", function (cb) {
+    addon.callback_call_with(
+      (o, n) => `${o.name}:${n}`,
+      (result) => {
+        try {
+          assert.strictEqual(result, "neon:1");
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }
+    );
+  });
+
+  it("should return an error if a callback throws", function (cb) {
+    let rejection;
+
+    process.removeAllListeners("unhandledRejection");
+    process.once("unhandledRejection", (err) => (rejection = err));
+
+    addon.callback_call_error(
+      () => {
+        throw new Error("Callback failed");
+      },
+      (message, closed) => {
+        try {
+          assert.match(message, /exception/i);
+          assert.strictEqual(closed, false);
+          assert.strictEqual(rejection.cause.message, "Callback failed");
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }
+    );
+  });
+
+  it("should call a rooted function exactly once from a thread", function (cb) {
+    const calls = [];
+
+    addon.root_call_threaded(
+      (i) => calls.push(i),
+      1,
+      (ok) => {
+        try {
+          assert.strictEqual(ok, true);
+          assert.deepEqual(calls, [0]);
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }
+    );
+  });
+
+  it("should call clones of a rooted function from threads", function (cb) {
+    const calls = [];
+
+    addon.root_call_threaded(
+      (i) => calls.push(i),
+      8,
+      (ok) => {
+        try {
+          assert.strictEqual(ok, true);
+          assert.sameMembers(calls, [0, 1, 2, 3, 4, 5, 6, 7]);
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }
+    );
+  });
+
+  it("should report exceptions from a rooted function called from a thread", function (cb) {
+    let rejection;
+
+    process.removeAllListeners("unhandledRejection");
+    process.once("unhandledRejection", (err) => (rejection = err));
+
+    addon.root_call_threaded(
+      () => {
+        throw new Error("Rooted function failed");
+      },
+      1,
+      (ok) => {
+        try {
+          assert.strictEqual(ok, false);
+          assert.strictEqual(rejection.cause.message, "Rooted function failed");
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }
+    );
+  });
+
+  it("should convert a rooted function into a callback", function (cb) {
+    addon.root_into_callback(
+      (i) => i * 2,
+      8,
+      (sum) => {
+        try {
+          assert.strictEqual(sum, 56);
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }
+    );
+  });
+
+  it("should return an error if a callback returns the wrong type", function (cb) {
+    process.removeAllListeners("unhandledRejection");
+    process.once("unhandledRejection", () => {});
+
+    addon.callback_call_error(
+      () => "not a number",
+      (message, closed) => {
+        try {
+          assert.match(message, /exception/i);
+          assert.strictEqual(closed, false);
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }
+    );
+  });
+
+  it("should return an error when calling a callback after its environment exits", function () {
+    this.timeout(30000);
+
+    const script = `
+      const { Worker } = require("worker_threads");
+      const addon = ${JSON.stringify(path.join(__dirname, ".."))};
+      const worker = new Worker(\`
+        const { parentPort } = require("worker_threads");
+        const addon = require(\${JSON.stringify(addon)});
+
+        addon.callback_stash(() => 42);
+        parentPort.postMessage("stashed");
+      \`, { eval: true });
+
+      worker.once("message", async () => {
+        await worker.terminate();
+
+        const { callback_call_stashed } = require(addon);
+
+        console.log(callback_call_stashed() ? "closed" : "called");
+      });
+    `;
+
+    const { status, stdout, stderr } = spawnSync(
+      process.execPath,
+      ["-e", script],
+      { encoding: "utf8", timeout: 30000 }
+    );
+
+    assert.strictEqual(status, 0, stderr);
+    assert.include(stdout, "closed");
+  });
+
   it("should drop leaked Root from the global queue", function (cb) {
     addon.drop_global_queue(cb);
 
@@ -88,6 +446,428 @@ const assert = require("chai").assert;
     setTimeout(() => global.gc(), 10);
   });
 
+  it("should cancel guarded roots dropped without being consumed", function (cb) {
+    const count = 100;
+    let aborted = 0;
+
+    addon.drop_guarded_roots_from_threads(count, 4, (err) => {
+      try {
+        assert.instanceOf(err, Error);
+        assert.strictEqual(err.message, "aborted");
+      } catch (err) {
+        return cb(err);
+      }
+
+      if (++aborted === count) {
+        cb();
+      }
+    });
+  });
+
+  it("should not cancel a consumed guarded root", function (cb) {
+    const calls = [];
+
+    addon.guarded_root_consume((...args) => calls.push(args));
+
+    setTimeout(() => {
+      try {
+        assert.deepStrictEqual(calls, [[]]);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    }, 100);
+  });
+
+  it("should cancel a guarded root immediately", function () {
+    const calls = [];
+
+    addon.guarded_root_cancel((err) => calls.push(err.message));
+
+    assert.deepStrictEqual(calls, ["aborted"]);
+    assert.throws(
+      () =>
+        addon.guarded_root_cancel(() => {
+          throw new Error("cancel failed");
+        }),
+      /cancel failed/
+    );
+  });
+
+  it("should not cancel a disarmed guarded root", function (cb) {
+    let called = false;
+
+    addon.guarded_root_disarm(() => {
+      called = true;
+    });
+
+    setTimeout(() => {
+      try {
+        assert.strictEqual(called, false);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    }, 100);
+  });
+
+  describe("drop queue at environment shutdown", function () {
+    // Drops thousands of roots from multiple threads while the worker that created
+    // them is terminated
+    function dropDuringTermination(
+      policy,
+      fatalHookPath,
+      drop = "addon.drop_roots_from_threads(10000, 4)"
+    ) {
+      const script = `
+        const { Worker } = require("worker_threads");
+        const addon = ${JSON.stringify(path.join(__dirname, ".."))};
+        const worker = new Worker(\`
+          const { parentPort } = require("worker_threads");
+          const addon = require(\${JSON.stringify(addon)});
+
+          ${
+            fatalHookPath
+              ? `addon.set_fatal_hook_file(${JSON.stringify(fatalHookPath)});`
+              : ""
+          }
+          addon.set_drop_policy(${JSON.stringify(policy)});
+          ${drop};
+          parentPort.postMessage("dropping");
+        \`, { eval: true });
+
+        worker.once("message", async () => {
+          await worker.terminate();
+          setTimeout(() => console.log("exited"), 500);
+        });
+      `;
+
+      return spawnSync(process.execPath, ["-e", script], {
+        encoding: "utf8",
+        timeout: 30000,
+      });
+    }
+
+    it("should leak roots dropped after termination", function () {
+      this.timeout(30000);
+
+      const { status, stdout, stderr } = dropDuringTermination("leak");
+
+      assert.strictEqual(status, 0, stderr);
+      assert.include(stdout, "exited");
+      assert.notInclude(stderr, "leaked");
+    });
+
+    it("should log roots dropped after termination", function () {
+      this.timeout(30000);
+
+      const { status, stdout, stderr } = dropDuringTermination("log");
+
+      assert.strictEqual(status, 0, stderr);
+      assert.include(stdout, "exited");
+      assert.include(
+        stderr,
+        "Warning: neon::handle::Root leaked because the JavaScript environment is shutting down"
+      );
+    });
+
+    it("should abort on roots dropped after termination", function () {
+      this.timeout(30000);
+
+      const { status, stdout, stderr } = dropDuringTermination("fatal");
+
+      assert.notStrictEqual(status, 0);
+      assert.notInclude(stdout, "exited");
+      assert.include(
+        stderr,
+        "neon::handle::Root dropped while the JavaScript environment is shutting down"
+      );
+    });
+
+    // Runs `code` in a worker that exits on its own, with the `fatal` policy
+    function exitWorker(code) {
+      const script = `
+        const { Worker } = require("worker_threads");
+        const addon = ${JSON.stringify(path.join(__dirname, ".."))};
+        const worker = new Worker(\`
+          const addon = require(\${JSON.stringify(addon)});
+
+          addon.set_drop_policy("fatal");
+          ${code};
+        \`, { eval: true });
+
+        worker.once("exit", (code) => console.log("exited", code));
+      `;
+
+      return spawnSync(process.execPath, ["-e", script], {
+        encoding: "utf8",
+        timeout: 30000,
+      });
+    }
+
+    it("should release roots owned by the module when a worker exits", function () {
+      this.timeout(30000);
+
+      const { status, stdout, stderr } = exitWorker(
+        "addon.stash_global_object()"
+      );
+
+      assert.strictEqual(status, 0, stderr);
+      assert.include(stdout, "exited 0");
+      assert.notInclude(stderr, "shutting down");
+    });
+
+    it("should release cached intrinsics when a worker exits", function () {
+      this.timeout(30000);
+
+      const { status, stdout, stderr } = exitWorker("addon.intrinsics()");
+
+      assert.strictEqual(status, 0, stderr);
+      assert.include(stdout, "exited 0");
+      assert.notInclude(stderr, "shutting down");
+    });
+
+    it("should leak guarded roots dropped after termination", function () {
+      this.timeout(30000);
+
+      // Cancellations that run before termination call into JavaScript; the rest
+      // are leaked along with the root they hold
+      const { status, stdout, stderr } = dropDuringTermination(
+        "log",
+        undefined,
+        "addon.drop_guarded_roots_from_threads(10000, 4, () => {})"
+      );
+
+      assert.strictEqual(status, 0, stderr);
+      assert.include(stdout, "exited");
+      assert.include(
+        stderr,
+        "Warning: neon::handle::GuardedRoot leaked because the JavaScript environment is shutting down"
+      );
+
+      // A few roots held by the addon itself may also leak, but not one for each
+      // guarded root
+      const rootLeaks = stderr
+        .split("\n")
+        .filter((line) => line.includes("neon::handle::Root leaked"));
+
+      assert.isBelow(rootLeaks.length, 100);
+    });
+
+    it("should drop closures sent from Drop after termination", function () {
+      this.timeout(30000);
+
+      // Half of the values are dropped by threads and half by finalizers during
+      // teardown
+      const { status, stdout, stderr } = dropDuringTermination(
+        "leak",
+        undefined,
+        'globalThis.boxes = addon.send_on_drop(1000, 4, "send")'
+      );
+
+      assert.strictEqual(status, 0, stderr);
+      assert.include(stdout, "exited");
+    });
+
+    it("should fail to try_send from Drop after termination", function () {
+      this.timeout(30000);
+
+      const { status, stdout, stderr } = dropDuringTermination(
+        "leak",
+        undefined,
+        'globalThis.boxes = addon.send_on_drop(1000, 4, "try_send")'
+      );
+
+      assert.strictEqual(status, 0, stderr);
+      assert.include(stdout, "exited");
+      assert.include(stderr, "SendOnDrop failed to send");
+    });
+
+    it("should call the fatal hook before aborting", function () {
+      this.timeout(30000);
+
+      const dir = fs.mkdtempSync(path.join(os.tmpdir(), "neon-fatal-hook-"));
+      const hookPath = path.join(dir, "fatal.log");
+
+      try {
+        const { status, stdout } = dropDuringTermination("fatal", hookPath);
+
+        assert.notStrictEqual(status, 0);
+        assert.notInclude(stdout, "exited");
+
+        const [location, message] = fs
+          .readFileSync(hookPath, "utf8")
+          .split("\n");
+
+        assert.include(location, "drop_queue.rs");
+        assert.strictEqual(
+          message,
+          "neon::handle::Root dropped while the JavaScript environment is shutting down"
+        );
+      } finally {
+        fs.rmSync(dir, { recursive: true, force: true });
+      }
+    });
+  });
+
+  it("should return a Rust value from a channel", function (cb) {
+    addon.channel_send_map({ n: 21 }, (n) => {
+      try {
+        assert.strictEqual(n, 42);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it("should return a rooted object from a channel", function (cb) {
+    addon.channel_send_value((obj) => {
+      try {
+        assert.deepEqual(obj, { answer: 42 });
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it("should release unjoined roots from a channel", async function () {
+    process.removeAllListeners("unhandledRejection");
+
+    const rejection = new Promise((resolve) =>
+      process.once("unhandledRejection", resolve)
+    );
+
+    assert.strictEqual(await addon.channel_send_value_unjoined(), true);
+
+    const err = await rejection;
+
+    assert.instanceOf(err, Error);
+    assert.match(err.message, /exception/i);
+    assert.strictEqual(err.cause.message, "send_value failed");
+  });
+
+  it("should abort a queued closure", async function () {
+    const [aborted, abortedAgain, joinAborted, ran] =
+      await addon.channel_abort_queued();
+
+    assert.strictEqual(aborted, true);
+    assert.strictEqual(abortedAgain, false);
+    assert.strictEqual(joinAborted, true);
+    assert.strictEqual(ran, false);
+  });
+
+  it("should run a closure after its handle is dropped", function (cb) {
+    addon.channel_detach(cb);
+  });
+
+  it("should abort only closures that have not executed", async function () {
+    this.timeout(30000);
+
+    const count = 10000;
+
+    for (let round = 0; round < 5; round++) {
+      const [aborted, mismatches, ran] = await addon.channel_abort_race(count);
+
+      assert.strictEqual(mismatches, 0);
+      assert.strictEqual(aborted + ran, count);
+    }
+  });
+
+  // Records whether `promise` settles before a microtask queued right after it,
+  // i.e., whether it was already settled without waiting for the event loop
+  async function settlesImmediately(promise) {
+    const order = [];
+
+    const settled = promise.then(
+      () => order.push("settled"),
+      () => order.push("settled")
+    );
+
+    queueMicrotask(() => order.push("microtask"));
+
+    await settled;
+
+    return order[0] === "settled";
+  }
+
+  it("should settle immediately on the JavaScript thread", async function () {
+    const promise = addon.deferred_settle_now(42, false);
+
+    assert.isTrue(await settlesImmediately(promise));
+    assert.strictEqual(await promise, 42);
+  });
+
+  it("should settle immediately repeatedly in the same tick", async function () {
+    const promises = [1, 2, 3].map((n) => addon.deferred_settle_now(n, false));
+
+    for (const promise of promises) {
+      assert.isTrue(await settlesImmediately(promise));
+    }
+
+    assert.deepEqual(await Promise.all(promises), [1, 2, 3]);
+  });
+
+  it("should not settle immediately behind queued closures", async function () {
+    const promise = addon.deferred_settle_now(42, true);
+
+    assert.isFalse(await settlesImmediately(promise));
+    assert.strictEqual(await promise, 42);
+  });
+
+  it("should not settle immediately from another thread", async function () {
+    const promise = addon.deferred_settle_now_from_thread(42);
+
+    assert.isFalse(await settlesImmediately(promise));
+    assert.strictEqual(await promise, 42);
+  });
+
+  it("should reject immediately when the settle closure throws", async function () {
+    const promise = addon.deferred_settle_now_throw("cache poisoned");
+
+    assert.isTrue(await settlesImmediately(promise));
+
+    try {
+      await promise;
+      assert.fail("expected a rejection");
+    } catch (err) {
+      assert.strictEqual(err.message, "cache poisoned");
+    }
+  });
+
+  it("should preserve the order of sends and settlements", async function () {
+    this.timeout(30000);
+
+    const count = 10000;
+    const events = [];
+    const resolved = [];
+    const promises = addon.channel_interleave_settle(count, (kind, i) => {
+      events.push(`${kind} ${i}`);
+    });
+
+    const values = await Promise.all(
+      promises.map((promise, i) =>
+        promise.then((value) => {
+          // The send enqueued before settling must already have executed
+          assert.strictEqual(events[2 * i], `send ${i}`);
+          resolved.push(value);
+          return value;
+        })
+      )
+    );
+
+    const expected = [];
+
+    for (let i = 0; i < count; i++) {
+      expected.push(`send ${i}`, `settle ${i}`);
+    }
+
+    assert.deepEqual(events, expected);
+    assert.deepEqual(values, [...Array(count).keys()]);
+    assert.deepEqual(resolved, values);
+  });
+
   it("should be able to join on the result of a channel", function (cb) {
     // `msg` is closed over by multiple functions. A function that returns the
     // current value is passed to the Neon function `addon.channel_join`. Additionally,
@@ -123,6 +903,93 @@ const assert = require("chai").assert;
     assert.strictEqual(expected, actual);
   });
 
+  it("should chain async work after a task", async function () {
+    const nums = new Float64Array([1, 2, 3]);
+    const actual = await addon.sum_then(nums, async (n) => {
+      await new Promise((resolve) => setTimeout(resolve, 10));
+
+      return n * 2;
+    });
+
+    assert.strictEqual(actual, 12);
+  });
+
+  it("should reject when chained async work rejects", async function () {
+    const nums = new Float64Array([1, 2, 3]);
+
+    try {
+      await addon.sum_then(nums, async (n) => {
+        await new Promise((resolve) => setTimeout(resolve, 10));
+
+        throw new Error(`failed after ${n}`);
+      });
+
+      throw new Error("Did not throw");
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.strictEqual(err.message, "failed after 6");
+    }
+  });
+
+  it("should adopt a thenable returned after a task", async function () {
+    const nums = new Float64Array([1, 2, 3]);
+    const actual = await addon.sum_then(nums, (n) => ({
+      then(resolve) {
+        resolve(n + 1);
+      },
+    }));
+
+    assert.strictEqual(actual, 7);
+  });
+
+  it("should resolve a task scope with results in order", async function () {
+    const actual = await addon.task_scope_squares(10);
+
+    assert.deepStrictEqual(
+      actual,
+      [...new Array(10)].map((_, i) => i * i)
+    );
+  });
+
+  it("should resolve a task scope without tickets", async function () {
+    assert.deepStrictEqual(await addon.task_scope_squares(0), []);
+  });
+
+  it("should reject a task scope when a ticket panics", async function () {
+    let called = false;
+
+    try {
+      await addon.task_scope_fail("panic", () => {
+        called = true;
+      });
+
+      throw new Error("Did not throw");
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.instanceOf(err.panic, Error);
+      assert.strictEqual(err.panic.message, "ticket panicked");
+    }
+
+    assert.strictEqual(called, false);
+  });
+
+  it("should reject a task scope when a ticket throws", async function () {
+    let called = false;
+
+    try {
+      await addon.task_scope_fail("throw", () => {
+        called = true;
+      });
+
+      throw new Error("Did not throw");
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.strictEqual(err.message, "ticket threw");
+    }
+
+    assert.strictEqual(called, false);
+  });
+
   it("should be able to resolve a promise manually", async function () {
     const nums = new Float64Array(
       [...new Array(10000)].map(() => Math.random())
@@ -143,6 +1010,24 @@ const assert = require("chai").assert;
     assert.strictEqual(expected, actual);
   });
 
+  it("should round-trip a Deferred through a napi_deferred", async function () {
+    const obj = {};
+
+    assert.strictEqual(await addon.deferred_raw_round_trip(obj), obj);
+  });
+
+  it("should reject a Deferred from a napi_deferred if leaked", async function () {
+    try {
+      await addon.deferred_raw_leak();
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.match(err.message, /Deferred/);
+      return;
+    }
+
+    assert.fail("Expected promise to reject");
+  });
+
   it("should reject promise if leaked", async function () {
     try {
       await addon.leak_promise();
@@ -181,6 +1066,35 @@ const assert = require("chai").assert;
     addon.channel_panic(msg);
   });
 
+  it("should run microtasks between closures sent from a thread", function (cb) {
+    const n = 100;
+    const events = [];
+
+    addon.channel_count(n, (i) => {
+      events.push(`closure ${i}`);
+      Promise.resolve().then(() => events.push(`microtask ${i}`));
+
+      if (i !== n - 1) {
+        return;
+      }
+
+      setTimeout(() => {
+        try {
+          assert.deepEqual(
+            events,
+            [...new Array(n)].flatMap((_, i) => [
+              `closure ${i}`,
+              `microtask ${i}`,
+            ])
+          );
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }, 10);
+    });
+  });
+
   it("should throw an unhandledRejection when throwing in a channel", function (cb) {
     const msg = "Hello, Throw!";
 
@@ -288,6 +1202,28 @@ const assert = require("chai").assert;
     addon.task_panic_execute(msg);
   });
 
+  it("should name the task and where it was scheduled when panicking in a task", function (cb) {
+    const msg = "Hello, Panic!";
+
+    process.removeAllListeners("unhandledRejection");
+    process.once("unhandledRejection", (err) => {
+      try {
+        assert.instanceOf(err, Error);
+        assert.match(
+          err.message,
+          /^A panic occurred while executing a `neon::event::TaskBuilder` task \("decode-image", scheduled at \S*threads\.rs:\d+:\d+\)$/
+        );
+        assert.strictEqual(err.panic.message, msg);
+
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+
+    addon.task_named_panic_execute(msg, "decode-image");
+  });
+
   it("should throw an unhandledRejection when panicking in a task complete", function (cb) {
     const msg = "Hello, Panic!";
 
@@ -422,6 +1358,33 @@ const assert = require("chai").assert;
     }
   });
 
+  it("panic in a task should reject the promise with where it was scheduled", async function () {
+    const msg = "Rejected!";
+
+    try {
+      await addon.task_named_panic_execute_promise(msg);
+
+      throw new Error("Did not throw");
+    } catch (err) {
+      assert.match(
+        err.message,
+        /^A panic occurred while executing a `neon::event::TaskBuilder` task \(scheduled at \S*threads\.rs:\d+:\d+\)$/
+      );
+      assert.strictEqual(err.panic.message, msg);
+    }
+
+    try {
+      await addon.task_named_panic_execute_promise(msg, 'say "hi"');
+
+      throw new Error("Did not throw");
+    } catch (err) {
+      assert.match(
+        err.message,
+        /\("say \\"hi\\"", scheduled at \S*threads\.rs:\d+:\d+\)$/
+      );
+    }
+  });
+
   it("panic in a task should reject the promise", async function () {
     const msg = "Rejected!";
 
@@ -436,6 +1399,50 @@ const assert = require("chai").assert;
     }
   });
 
+  it("recovers a custom panic payload from a promise rejection", async function () {
+    const msg = "Custom payload";
+
+    try {
+      await addon.task_custom_panic_promise(msg);
+
+      throw new Error("Did not throw");
+    } catch (err) {
+      assert.instanceOf(err.panic, Error);
+      assert.strictEqual(err.panic.message, "Unknown panic");
+      assert.strictEqual(addon.custom_panic_payload(err), msg);
+      assert.strictEqual(addon.custom_panic_payload(err.panic), msg);
+      assert.strictEqual(addon.custom_panic_payload(err.panic.cause), msg);
+
+      const wrapped = new Error("Wrapped", { cause: err });
+
+      assert.strictEqual(addon.custom_panic_payload(wrapped), msg);
+    }
+  });
+
+  it("does not find a custom panic payload in other errors", async function () {
+    try {
+      await addon.task_panic_execute_promise("Rejected!");
+
+      throw new Error("Did not throw");
+    } catch (err) {
+      assert.strictEqual(addon.custom_panic_payload(err), undefined);
+    }
+
+    const cyclic = new Error("Cyclic");
+    cyclic.cause = cyclic;
+
+    assert.strictEqual(addon.custom_panic_payload(cyclic), undefined);
+    assert.strictEqual(addon.custom_panic_payload(42), undefined);
+    assert.strictEqual(
+      addon.custom_panic_payload({
+        get panic() {
+          throw new Error("getter");
+        },
+      }),
+      undefined
+    );
+  });
+
   it("panic and exception in a task should reject the promise", async function () {
     const msg = "Rejected!";
 
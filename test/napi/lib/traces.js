@@ -0,0 +1,60 @@
+const addon = require("..");
+const assert = require("chai").assert;
+
+function tick() {
+  return new Promise((resolve) => setImmediate(resolve));
+}
+
+function find(traces, name) {
+  const trace = traces.find((trace) => trace.name === name);
+
+  assert.isDefined(trace, `expected a \`${name}\` trace`);
+
+  return trace;
+}
+
+function assertCaller(trace, line) {
+  assert.match(trace["caller.file"], /traces\.rs$/);
+  assert.strictEqual(Number(trace["caller.line"]), line);
+  assert.isAbove(Number(trace["caller.column"]), 0);
+}
+
+describe("tracing", function () {
+  beforeEach(async function () {
+    // Installs the subscriber and discards unrelated traces, including callbacks
+    // still queued by earlier tests
+    addon.traces_take();
+    await tick();
+    addon.traces_take();
+  });
+
+  it("should trace channel sends and callbacks", async function () {
+    const line = addon.traces_channel_send();
+    const sent = addon.traces_take();
+
+    assertCaller(find(sent, "neon.channel.send"), line);
+
+    await tick();
+
+    const traces = addon.traces_take();
+    const callback = find(traces, "neon.channel.callback");
+
+    assert.strictEqual(callback.kind, "span");
+    assertCaller(callback, line);
+    assert.isAtLeast(Number(callback.duration_us), 0);
+  });
+
+  it("should trace task execution and completion", async function () {
+    const line = await addon.traces_task();
+    const traces = addon.traces_take();
+    const execute = find(traces, "neon.task.execute");
+    const complete = find(traces, "neon.task.complete");
+
+    assert.strictEqual(execute.kind, "span");
+    assert.strictEqual(complete.kind, "span");
+    assertCaller(execute, line);
+    assertCaller(complete, line);
+    assert.isAtLeast(Number(execute.duration_us), 5000);
+    assert.isBelow(traces.indexOf(execute), traces.indexOf(complete));
+  });
+});
@@ -83,6 +83,129 @@ describe("Typed arrays", function () {
     assert.throws(() => addon.copy_typed_array(a, b));
   });
 
+  it("reads single elements of a typed array", function () {
+    const buf = new ArrayBuffer(16);
+    const arr = new Int32Array(buf, 4, 2);
+
+    new Int32Array(buf).set([1, 2, -3, 4]);
+
+    assert.strictEqual(addon.try_get_typed_array_element(arr, 0), 2);
+    assert.strictEqual(addon.try_get_typed_array_element(arr, 1), -3);
+    assert.strictEqual(addon.try_get_typed_array_element(arr, 2), undefined);
+    assert.strictEqual(addon.get_typed_array_element(arr, 1), -3);
+    assert.throws(
+      () => addon.get_typed_array_element(arr, 2),
+      RangeError,
+      "index 2 is out of bounds for typed array of length 2"
+    );
+  });
+
+  it("writes single elements of a typed array", function () {
+    const buf = new ArrayBuffer(16);
+    const arr = new Int32Array(buf, 4, 2);
+
+    addon.set_typed_array_element(arr, 1, -7);
+
+    assert.deepEqual([...new Int32Array(buf)], [0, 0, -7, 0]);
+    assert.throws(
+      () => addon.set_typed_array_element(arr, 2, 1),
+      RangeError,
+      "index 2 is out of bounds for typed array of length 2"
+    );
+    assert.deepEqual([...new Int32Array(buf)], [0, 0, -7, 0]);
+  });
+
+  it("accesses single elements while JavaScript runs in between", function () {
+    const arr = new Float64Array([0, 1, 2, 3]);
+    const pairs = new Uint32Array([0, 3, 1, 2]);
+    let calls = 0;
+
+    addon.swap_typed_array_elements_with_callback(arr, pairs, (arr) => {
+      calls++;
+      arr[3] += 10;
+    });
+
+    assert.strictEqual(calls, 2);
+    assert.deepEqual([...arr], [3, 2, 1, 10]);
+  });
+
+  it("fails to access single elements of a detached typed array", function () {
+    const arr = new Float64Array([0, 1, 2, 3]);
+    const pairs = new Uint32Array([0, 3, 1, 2]);
+
+    assert.throws(
+      () =>
+        addon.swap_typed_array_elements_with_callback(arr, pairs, (arr) =>
+          detach(arr.buffer)
+        ),
+      RangeError,
+      "index 0 is out of bounds for typed array of length 0"
+    );
+    assert.strictEqual(arr.length, 0);
+  });
+
+  it("reads an exact length into a buffer from a reader", function () {
+    const bytes = Buffer.from("Hello, World!");
+
+    const buf = addon.buffer_from_reader(bytes, bytes.length);
+    assert.instanceOf(buf, Buffer);
+    assert.deepEqual(buf, bytes);
+
+    const arrayBuf = addon.array_buffer_from_reader(bytes, 5);
+    assert.instanceOf(arrayBuf, ArrayBuffer);
+    assert.deepEqual(Buffer.from(arrayBuf), bytes.subarray(0, 5));
+
+    assert.strictEqual(addon.buffer_from_reader(bytes, 0).length, 0);
+  });
+
+  it("fails to read past the end of a reader into a buffer", function () {
+    const bytes = Buffer.from("Hello, World!");
+
+    assert.throws(
+      () => addon.buffer_from_reader(bytes, 20),
+      Error,
+      "reader ended after 13 of 20 bytes"
+    );
+    assert.throws(
+      () => addon.array_buffer_from_reader(Buffer.alloc(0), 1),
+      Error,
+      "reader ended after 0 of 1 bytes"
+    );
+  });
+
+  it("throws I/O errors when reading into a buffer", function () {
+    const bytes = Buffer.from("Hello, World!");
+
+    assert.throws(
+      () => addon.array_buffer_from_reader(bytes, 10, 6),
+      Error,
+      "disk on fire"
+    );
+    assert.deepEqual(
+      Buffer.from(addon.array_buffer_from_reader(bytes, 5, 6)),
+      bytes.subarray(0, 5)
+    );
+  });
+
+  it("collects an iterator into a buffer", function () {
+    const expected = Buffer.from([0, 1, 2, 3, 4, 5]);
+
+    assert.deepEqual(Buffer.from(addon.array_buffer_from_iter(6)), expected);
+    assert.deepEqual(
+      addon.buffer_from_iter_filtered(6),
+      Buffer.from([0, 2, 4])
+    );
+    assert.strictEqual(addon.array_buffer_from_iter(0).byteLength, 0);
+  });
+
+  it("collects an iterator with a wrong size hint into a buffer", function () {
+    const expected = Buffer.from([0, 1, 2, 3, 4, 5]);
+
+    assert.deepEqual(Buffer.from(addon.array_buffer_from_iter(6, 6)), expected);
+    assert.deepEqual(Buffer.from(addon.array_buffer_from_iter(6, 2)), expected);
+    assert.deepEqual(Buffer.from(addon.array_buffer_from_iter(6, 10)), expected);
+  });
+
   it("gets a 16-byte, zeroed ArrayBuffer", function () {
     var b = addon.return_array_buffer();
     assert.equal(b.byteLength, 16);
@@ -174,6 +297,44 @@ describe("Typed arrays", function () {
     assert.strictEqual(Buffer.from(buf).toString(), expected);
   });
 
+  it("reports whether an external Buffer was externalized", function () {
+    var expected = "String to copy";
+    var policies = {
+      require: true,
+      "prefer-copy-fallback": true,
+      "always-copy": false,
+    };
+
+    Object.keys(policies).forEach(function (policy) {
+      var [buf, externalized] = addon.return_external_buffer_with_policy(
+        expected,
+        policy
+      );
+      assert.instanceOf(buf, Buffer);
+      assert.strictEqual(buf.toString(), expected);
+      assert.strictEqual(externalized, policies[policy], policy);
+    });
+  });
+
+  it("reports whether an external ArrayBuffer was externalized", function () {
+    var expected = "String to copy";
+    var policies = {
+      require: true,
+      "prefer-copy-fallback": true,
+      "always-copy": false,
+    };
+
+    Object.keys(policies).forEach(function (policy) {
+      var [buf, externalized] = addon.return_external_array_buffer_with_policy(
+        expected,
+        policy
+      );
+      assert.instanceOf(buf, ArrayBuffer);
+      assert.strictEqual(Buffer.from(buf).toString(), expected);
+      assert.strictEqual(externalized, policies[policy], policy);
+    });
+  });
+
   it("gets a typed array constructed from an ArrayBuffer", function () {
     var b = new ArrayBuffer(64);
     var i8 = addon.return_int8array_from_arraybuffer(b);
@@ -551,4 +712,305 @@ describe("Typed arrays", function () {
       assert.fail("region overrun should be validated when instantiating");
     } catch (expected) {}
   });
+
+  it("allocates consecutive views from a buffer pool", function () {
+    var pool = addon.create_buffer_pool(64, 2);
+    var a = addon.buffer_pool_alloc(pool, 16);
+    var b = addon.buffer_pool_alloc(pool, 8);
+
+    assert.instanceOf(a, Uint8Array);
+    assert.strictEqual(a.length, 16);
+    assert.strictEqual(b.length, 8);
+    assert.strictEqual(a.buffer, b.buffer);
+    assert.strictEqual(a.buffer.byteLength, 64);
+    assert.strictEqual(a.byteOffset, 0);
+    assert.strictEqual(b.byteOffset, 16);
+    assert.deepEqual(Array.from(b), [0, 0, 0, 0, 0, 0, 0, 0]);
+  });
+
+  it("does not alias views from a buffer pool", function () {
+    var pool = addon.create_buffer_pool(64, 1);
+    var a = addon.buffer_pool_alloc(pool, 4);
+    var b = addon.buffer_pool_alloc(pool, 4);
+
+    a.fill(1);
+
+    assert.deepEqual(Array.from(a), [1, 1, 1, 1]);
+    assert.deepEqual(Array.from(b), [0, 0, 0, 0]);
+  });
+
+  it("falls back to unpooled allocation", function () {
+    var pool = addon.create_buffer_pool(16, 1);
+    var large = addon.buffer_pool_alloc(pool, 32);
+    var a = addon.buffer_pool_alloc(pool, 12);
+    var b = addon.buffer_pool_alloc(pool, 12);
+
+    assert.strictEqual(large.buffer.byteLength, 32);
+    assert.strictEqual(a.buffer.byteLength, 16);
+    assert.strictEqual(b.buffer.byteLength, 12);
+    assert.notStrictEqual(a.buffer, b.buffer);
+    assert.strictEqual(addon.buffer_pool_slab_count(pool), 1);
+  });
+
+  it("grows a buffer pool up to the maximum number of slabs", function () {
+    var pool = addon.create_buffer_pool(16, 2);
+    var a = addon.buffer_pool_alloc(pool, 12);
+    var b = addon.buffer_pool_alloc(pool, 12);
+
+    assert.notStrictEqual(a.buffer, b.buffer);
+    assert.strictEqual(b.buffer.byteLength, 16);
+    assert.strictEqual(addon.buffer_pool_slab_count(pool), 2);
+  });
+
+  it("discards buffer pool slabs detached by JavaScript", function () {
+    var pool = addon.create_buffer_pool(16, 1);
+    var a = addon.buffer_pool_alloc(pool, 12);
+
+    structuredClone(a.buffer, { transfer: [a.buffer] });
+
+    var b = addon.buffer_pool_alloc(pool, 4);
+
+    assert.strictEqual(a.byteLength, 0);
+    assert.strictEqual(b.buffer.byteLength, 16);
+    assert.strictEqual(b.byteOffset, 0);
+    assert.strictEqual(addon.buffer_pool_slab_count(pool), 1);
+  });
+
+  (global.gc ? it : it.skip)(
+    "detaching a collected buffer pool slab does not prevent allocation",
+    async function () {
+      var pool = addon.create_buffer_pool(16, 1);
+
+      (function () {
+        var view = addon.buffer_pool_alloc(pool, 16);
+
+        structuredClone(view.buffer, { transfer: [view.buffer] });
+      })();
+
+      for (var i = 0; i < 10; i++) {
+        global.gc();
+        await new Promise((resolve) => setImmediate(resolve));
+      }
+
+      var view = addon.buffer_pool_alloc(pool, 12);
+
+      assert.strictEqual(view.length, 12);
+      assert.strictEqual(view.buffer.byteLength, 16);
+    }
+  );
+
+  (global.gc ? it : it.skip)(
+    "recycles buffer pool slabs after views are collected",
+    async function () {
+      var pool = addon.create_buffer_pool(16, 1);
+
+      (function () {
+        addon.buffer_pool_alloc(pool, 12).fill(7);
+      })();
+
+      // Finalizers may be deferred until after garbage collection
+      for (var i = 0; i < 10; i++) {
+        global.gc();
+        await new Promise((resolve) => setImmediate(resolve));
+      }
+
+      var view = addon.buffer_pool_alloc(pool, 12);
+
+      assert.strictEqual(view.buffer.byteLength, 16);
+      assert.strictEqual(view.byteOffset, 0);
+      assert.deepEqual(Array.from(view), new Array(12).fill(0));
+    }
+  );
+
+  describe("bytes", function () {
+    it("shares the memory of uniquely owned Bytes", function () {
+      var [buf, addr] = addon.buffer_from_unique_bytes(256);
+
+      assert.instanceOf(buf, Buffer);
+      assert.strictEqual(addon.buffer_address(buf), addr);
+      assert.deepEqual(
+        Array.from(buf),
+        Array.from({ length: 256 }, (_, i) => i)
+      );
+    });
+
+    it("copies shared Bytes", function () {
+      var [buf, addr] = addon.buffer_from_shared_bytes(16);
+
+      assert.notStrictEqual(addon.buffer_address(buf), addr);
+      assert.deepEqual(
+        Array.from(buf),
+        Array.from({ length: 16 }, (_, i) => i)
+      );
+    });
+
+    it("converts a Buffer to Bytes and back", function () {
+      var buf = Buffer.from("hello, bytes");
+
+      assert.deepEqual(addon.bytes_round_trip(buf), buf);
+      assert.deepEqual(addon.bytes_round_trip(Buffer.alloc(0)), Buffer.alloc(0));
+      assert.throws(() => addon.bytes_round_trip("hello"), TypeError);
+    });
+
+    it("copies a Buffer extracted as Bytes", function () {
+      var buf = Buffer.alloc(4096, 7);
+
+      var copy = addon.bytes_after_detach(buf, (buf) => {
+        detach(buf.buffer);
+        assert.strictEqual(buf.length, 0);
+      });
+
+      assert.deepEqual(copy, Buffer.alloc(4096, 7));
+    });
+
+    (global.gc ? it : it.skip)(
+      "keeps buffers alive while Bytes exist",
+      async function () {
+        var refs = [];
+        var expected = 0;
+
+        (function () {
+          for (var i = 0; i < 100; i++) {
+            var buf = Buffer.alloc(4096 + i, i);
+
+            refs.push(new WeakRef(buf));
+            addon.stash_bytes(buf);
+
+            // The whole buffer and every byte after the first
+            expected += (4096 + i) * i + (4095 + i) * i;
+          }
+
+          // Small buffers share a pool
+          for (var i = 0; i < 100; i++) {
+            addon.stash_bytes(Buffer.from([1, 2, 3]));
+            expected += 6 + 5;
+          }
+        })();
+
+        async function collect() {
+          for (var i = 0; i < 10; i++) {
+            global.gc();
+            new Array(1e5).fill({});
+            await new Promise((resolve) => setImmediate(resolve));
+          }
+        }
+
+        await collect();
+
+        assert.ok(refs.every((ref) => ref.deref() !== undefined));
+        assert.strictEqual(await addon.sum_stashed_bytes(), expected);
+
+        addon.clear_stashed_bytes();
+        await collect();
+
+        assert.ok(refs.every((ref) => ref.deref() === undefined));
+      }
+    );
+  });
+
+  describe("text encodings", function () {
+    const text = "héllo, wörld \u{1F600}";
+
+    it("should decode valid text", function () {
+      for (const encoding of ["utf8", "utf16le", "latin1"]) {
+        const input = encoding === "latin1" ? "héllo" : text;
+        const data = Buffer.from(input, encoding);
+        const expected = data.toString(encoding);
+
+        assert.strictEqual(addon.decode_text(data, encoding, false), expected);
+        assert.strictEqual(addon.decode_text(data, encoding, true), expected);
+      }
+    });
+
+    it("should encode text", function () {
+      for (const encoding of ["utf8", "utf16le"]) {
+        const data = addon.encode_text(text, encoding);
+
+        assert.instanceOf(data, Uint8Array);
+        assert.deepEqual(Buffer.from(data), Buffer.from(text, encoding));
+      }
+
+      assert.deepEqual(
+        Buffer.from(addon.encode_text("héllo", "latin1")),
+        Buffer.from("héllo", "latin1")
+      );
+    });
+
+    it("should report the byte offset of invalid sequences", function () {
+      const utf8 = Buffer.from([0x61, 0x62, 0xc3, 0x28]);
+
+      assert.throws(
+        () => addon.decode_text(utf8, "utf8", false),
+        TypeError,
+        "invalid utf-8 sequence at byte offset 2"
+      );
+      assert.strictEqual(
+        addon.decode_text(utf8, "utf8", true),
+        new TextDecoder().decode(utf8)
+      );
+
+      // An unpaired surrogate, followed by a trailing byte
+      const utf16 = Buffer.from([0x61, 0x00, 0x00, 0xd8, 0x62, 0x00, 0x63]);
+
+      assert.throws(
+        () => addon.decode_text(utf16, "utf16le", false),
+        TypeError,
+        "invalid utf-16le sequence at byte offset 2"
+      );
+      assert.throws(
+        () => addon.decode_text(utf16.subarray(4), "utf16le", false),
+        TypeError,
+        "invalid utf-16le sequence at byte offset 2"
+      );
+      assert.strictEqual(
+        addon.decode_text(utf16, "utf16le", true),
+        new TextDecoder("utf-16le").decode(utf16)
+      );
+    });
+
+    it("should report the byte offset of unmappable characters", function () {
+      assert.throws(
+        () => addon.encode_text("hé\u0100", "latin1"),
+        TypeError,
+        "character 'Ā' at byte offset 3 cannot be encoded as latin1"
+      );
+      assert.throws(
+        () => addon.encode_text("a\u{1F600}", "windows-1252"),
+        TypeError,
+        "at byte offset 1 cannot be encoded as windows-1252"
+      );
+    });
+
+    it("should support WHATWG encodings", function () {
+      const cp1252 = Buffer.from([0x80, 0x93, 0x68, 0xe9, 0x94]);
+      const expected = "€“hé”";
+
+      assert.strictEqual(addon.encoding_name("latin1"), "latin1");
+      assert.strictEqual(addon.encoding_name(" ISO-8859-1 "), "windows-1252");
+      assert.strictEqual(addon.encoding_name("unicode-1-1-utf-8"), "utf-8");
+      assert.throws(() => addon.encoding_name("nope"), RangeError);
+
+      assert.strictEqual(addon.decode_text(cp1252, "cp1252", false), expected);
+      assert.deepEqual(
+        Buffer.from(addon.encode_text(expected, "cp1252")),
+        cp1252
+      );
+
+      const sjis = Buffer.from([0x82, 0xa0, 0x82]);
+
+      assert.throws(
+        () => addon.decode_text(sjis, "shift_jis", false),
+        TypeError,
+        "invalid Shift_JIS sequence at byte offset 2"
+      );
+      assert.strictEqual(
+        addon.decode_text(sjis, "shift_jis", true),
+        "\u3042\ufffd"
+      );
+      assert.deepEqual(
+        Buffer.from(addon.encode_text("\u3042", "utf-16be")),
+        Buffer.from([0x30, 0x42])
+      );
+    });
+  });
 });
@@ -90,4 +90,269 @@ describe("type checks", function () {
     assert(!addon.strict_equals(o1, o2));
     assert(!addon.strict_equals(o1, 17));
   });
+
+  it("same_value", function () {
+    assert(addon.same_value(17, 17));
+    assert(addon.same_value(NaN, NaN));
+    assert(!addon.same_value(0, -0));
+    assert(!addon.same_value(17, "17"));
+    let o = {};
+    assert(addon.same_value(o, o));
+    assert(!addon.same_value(o, {}));
+  });
+
+  it("same_value_zero", function () {
+    assert(addon.same_value_zero(17, 17));
+    assert(addon.same_value_zero(NaN, NaN));
+    assert(addon.same_value_zero(0, -0));
+    assert(!addon.same_value_zero(17, "17"));
+  });
+
+  it("value_kind", function () {
+    assert.strictEqual(addon.value_kind(undefined), "undefined");
+    assert.strictEqual(addon.value_kind(null), "null");
+    assert.strictEqual(addon.value_kind(true), "boolean");
+    assert.strictEqual(addon.value_kind(1.5), "number");
+    assert.strictEqual(addon.value_kind("hi"), "string");
+    assert.strictEqual(addon.value_kind(Symbol()), "symbol");
+    assert.strictEqual(addon.value_kind({}), "object");
+    assert.strictEqual(addon.value_kind([]), "object");
+    assert.strictEqual(addon.value_kind(() => {}), "function");
+    assert.strictEqual(addon.value_kind(10n), "bigint");
+    assert.strictEqual(addon.value_kind(addon.external_unit()), "external");
+  });
+
+  it("visit_value", function () {
+    assert.strictEqual(addon.visit_value(undefined), "undefined");
+    assert.strictEqual(addon.visit_value(null), "null");
+    assert.strictEqual(addon.visit_value(false), "boolean false");
+    assert.strictEqual(addon.visit_value(1.5), "number 1.5");
+    assert.strictEqual(addon.visit_value("hi"), "string hi");
+    assert.strictEqual(addon.visit_value(Symbol()), "other symbol");
+    assert.strictEqual(addon.visit_value(10n), "other bigint");
+    assert.strictEqual(addon.visit_value({}), "object");
+    assert.strictEqual(addon.visit_value(new Map()), "object");
+    assert.strictEqual(addon.visit_value(() => {}), "function");
+    assert.strictEqual(
+      addon.visit_value([1, "a", [null]]),
+      "array [number 1, string a, array [null]]"
+    );
+    assert.strictEqual(
+      addon.visit_value(addon.external_unit()),
+      "other external"
+    );
+  });
+
+  it("dispatches like chained downcasts", function () {
+    const values = ["a", 1, [], {}, null, () => {}, new Date(), "b", 2.5];
+    const expected = [2, 2, 1, 2, 2];
+
+    assert.deepEqual(addon.count_shapes_with_visitor(values), expected);
+    assert.deepEqual(addon.count_shapes_with_downcast(values), expected);
+  });
+});
+
+describe("optional values", function () {
+  it("converts None to null by default", function () {
+    assert.strictEqual(addon.option_or_null("b"), 1);
+    assert.strictEqual(addon.option_or_null("z"), null);
+  });
+
+  it("converts None to undefined with OrUndefined", function () {
+    assert.strictEqual(addon.option_or_undefined("c"), 2);
+    assert.strictEqual(addon.option_or_undefined("z"), undefined);
+  });
+
+  it("resolves task promises with optional values", async function () {
+    assert.strictEqual(await addon.option_in_task(9), 3);
+    assert.strictEqual(await addon.option_in_task(-1), null);
+  });
+
+  it("extracts null and undefined as None", function () {
+    assert.strictEqual(addon.option_from_js("hi"), "hi");
+    assert.strictEqual(addon.option_from_js(null), "none");
+    assert.strictEqual(addon.option_from_js(undefined), "none");
+    assert.throws(() => addon.option_from_js(1), TypeError);
+
+    assert.strictEqual(addon.or_undefined_from_js(true), true);
+    assert.strictEqual(addon.or_undefined_from_js(null), false);
+    assert.strictEqual(addon.or_undefined_from_js(undefined), false);
+    assert.throws(() => addon.or_undefined_from_js("true"), TypeError);
+  });
+
+  it("distinguishes null from undefined with MaybeUndefined", function () {
+    assert.strictEqual(addon.maybe_undefined_argument(), "missing");
+    assert.strictEqual(addon.maybe_undefined_argument(undefined), "missing");
+    assert.strictEqual(addon.maybe_undefined_argument(null), "null");
+    assert.strictEqual(addon.maybe_undefined_argument(5), "value 5");
+    assert.throws(() => addon.maybe_undefined_argument("5"), TypeError);
+  });
+
+  it("converts MaybeUndefined back to null or undefined", function () {
+    assert.strictEqual(addon.maybe_undefined_round_trip(), undefined);
+    assert.strictEqual(addon.maybe_undefined_round_trip(null), null);
+    assert.strictEqual(addon.maybe_undefined_round_trip("hi"), "hi");
+  });
+
+  it("keeps a default when an option is omitted", function () {
+    assert.strictEqual(addon.request_timeout({}), 30000);
+    assert.strictEqual(addon.request_timeout({ timeout: undefined }), 30000);
+    assert.strictEqual(addon.request_timeout({ timeout: 500 }), 500);
+  });
+
+  it("disables a default when an option is null", function () {
+    assert.strictEqual(addon.request_timeout({ timeout: null }), null);
+    assert.throws(() => addon.request_timeout({ timeout: "1s" }), TypeError);
+  });
+});
+
+describe("derived extractors", function () {
+  it("extracts an object with defaults", function () {
+    assert.deepEqual(addon.parse_request_options({}), {
+      timeout: null,
+      max_retries: 3,
+      mode: "fast",
+      cache_options: null,
+    });
+  });
+
+  it("extracts renamed, converted and nested fields", function () {
+    assert.deepEqual(
+      addon.parse_request_options({
+        timeout: 500,
+        maxRetries: 5,
+        mode: "safe",
+        cache: { size: 10, name: "lru" },
+      }),
+      {
+        timeout: 500,
+        max_retries: 5,
+        mode: "safe",
+        cache_options: { size: 10, name: "lru" },
+      }
+    );
+  });
+
+  it("throws a single TypeError listing every invalid field", function () {
+    assert.throws(
+      () =>
+        addon.parse_request_options({
+          timeout: "1s",
+          maxRetries: 11,
+          mode: "slow",
+          cache: { size: 0 },
+          extra: 1,
+          other: 2,
+        }),
+      TypeError,
+      "invalid RequestOptions: " +
+        "timeout: expected number, got string; " +
+        "maxRetries: must be between 0 and 10; " +
+        'mode: expected "fast" or "safe", found "slow"; ' +
+        "cache.size: must be at least 1; " +
+        "cache.name: missing required field; " +
+        "extra: unknown field; " +
+        "other: unknown field"
+    );
+  });
+
+  it("finds unknown fields when Object.keys is replaced", function () {
+    const keys = Object.keys;
+
+    Object.keys = () => [];
+
+    try {
+      assert.throws(
+        () => addon.parse_request_options({ extra: 1 }),
+        TypeError,
+        "invalid RequestOptions: extra: unknown field"
+      );
+    } finally {
+      Object.keys = keys;
+    }
+  });
+
+  it("only uses defaults for undefined values", function () {
+    assert.throws(
+      () => addon.parse_request_options({ maxRetries: null }),
+      TypeError,
+      "invalid RequestOptions: maxRetries: expected number, got null"
+    );
+  });
+
+  it("rejects values that are not objects", function () {
+    assert.throws(
+      () => addon.parse_request_options(),
+      TypeError,
+      "invalid RequestOptions: expected an object"
+    );
+    assert.throws(
+      () => addon.parse_request_options({ cache: 5 }),
+      TypeError,
+      "invalid RequestOptions: cache: expected an object"
+    );
+  });
+});
+
+describe("multiple values", function () {
+  it("converts tuples to arrays", function () {
+    assert.deepEqual(addon.tuple_to_array("neon"), ["neon", 4, null]);
+  });
+
+  it("converts named tuples to objects", function () {
+    assert.deepEqual(addon.tuple_to_object("neon"), {
+      name: "neon",
+      length: 4,
+    });
+  });
+
+  it("resolves task promises with tuples", async function () {
+    const [written, checksum] = await addon.tuple_in_task("abc");
+
+    assert.strictEqual(written, 3);
+    assert.strictEqual(checksum, 294);
+  });
+
+  it("resolves task promises with named tuples", async function () {
+    const { bytesWritten, checksum } = await addon.named_tuple_in_task("abc");
+
+    assert.strictEqual(bytesWritten, 3);
+    assert.strictEqual(checksum, 294);
+  });
+});
+
+describe("conversions in other contexts", function () {
+  it("converts in a function", function () {
+    assert.deepEqual(addon.convert_in_function([3, "neon", true]), [
+      6,
+      "NEON",
+      false,
+    ]);
+    assert.throws(() => addon.convert_in_function([3, 4, true]), TypeError);
+  });
+
+  it("converts in a channel callback", async function () {
+    const result = await new Promise((resolve) =>
+      addon.convert_in_channel([3, "neon", null], resolve)
+    );
+
+    assert.deepEqual(result, [6, "NEON", null]);
+  });
+
+  (global.gc ? it : it.skip)("converts in a finalizer", async function () {
+    let result;
+    let boxed = addon.convert_in_finalize([3, "neon", false], (value) => {
+      result = value;
+    });
+
+    boxed = null;
+
+    // Finalizers run on later ticks of the event loop
+    for (let i = 0; i < 10 && !result; i++) {
+      global.gc();
+      await new Promise((resolve) => setImmediate(resolve));
+    }
+
+    assert.deepEqual(result, [6, "NEON", true]);
+  });
 });
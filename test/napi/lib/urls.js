@@ -0,0 +1,149 @@
+const addon = require("..");
+const assert = require("chai").assert;
+
+describe("JsUrl", function () {
+  it("should expose the parts of a URL", function () {
+    const url = new URL(
+      "https://user@Example.com:8080/a/./b/../c?page=2&page=3#d"
+    );
+
+    assert.deepEqual(addon.url_parts(url), {
+      href: "https://user@example.com:8080/a/c?page=2&page=3#d",
+      origin: "https://example.com:8080",
+      pathname: "/a/c",
+      page: "2",
+    });
+
+    const opaque = addon.url_parts(new URL("data:,hi"));
+
+    assert.equal(opaque.origin, "null");
+    assert.isUndefined(opaque.page);
+  });
+
+  it("should parse a URL", function () {
+    const url = addon.parse_url("../d?page=1", "https://example.com/a/b/c");
+
+    assert.instanceOf(url, URL);
+    assert.equal(url.href, "https://example.com/a/d?page=1");
+    assert.equal(
+      addon.parse_url("https://example.com").href,
+      "https://example.com/"
+    );
+  });
+
+  it("should throw a TypeError for an invalid URL", function () {
+    assert.throws(() => addon.parse_url("not a url"), TypeError);
+    assert.throws(() => addon.parse_url("/a", "not a base"), TypeError);
+  });
+
+  it("should only downcast URL objects", function () {
+    class MyUrl extends URL {}
+
+    assert.isTrue(addon.is_url(new URL("https://example.com")));
+    assert.isTrue(addon.is_url(new MyUrl("https://example.com")));
+    assert.isFalse(addon.is_url("https://example.com"));
+    assert.isFalse(addon.is_url({ href: "https://example.com" }));
+    assert.isFalse(addon.is_url(null));
+    assert.throws(() => addon.url_parts("https://example.com"), TypeError);
+  });
+
+  it("should not leave an exception from `instanceof` pending", function () {
+    const value = {};
+
+    Object.defineProperty(URL, Symbol.hasInstance, {
+      configurable: true,
+      value() {
+        throw new Error("hasInstance");
+      },
+    });
+
+    try {
+      assert.isFalse(addon.is_url(value));
+    } finally {
+      delete URL[Symbol.hasInstance];
+    }
+  });
+
+  it("should leave an exception that was already pending", function () {
+    const [isUrl, err] = addon.is_url_while_throwing(
+      new URL("https://example.com")
+    );
+
+    assert.isFalse(isUrl);
+    assert.instanceOf(err, Error);
+    assert.strictEqual(err.message, "pending");
+  });
+
+  describe("url crate", function () {
+    it("should round trip a URL", function () {
+      const url = new URL("https://example.com/a%20b?c=%C3%A4#e");
+      const copy = addon.url_crate_round_trip(url);
+
+      assert.instanceOf(copy, URL);
+      assert.notStrictEqual(copy, url);
+      assert.equal(copy.href, url.href);
+    });
+
+    it("should agree with the WHATWG parser on tricky inputs", function () {
+      const inputs = [
+        "HTTP://EXAMPLE.com:80/a/./b/../c?x=1#y",
+        "http://[::1]:8080/",
+        "http://[::127.0.0.1]/",
+        "http://0x7f.1/",
+        "http://1.2.3/",
+        "http://1.2.3.4.5/",
+        "file:///C:/path/../x",
+        "file://localhost/etc",
+        "http://example.com/%7Efoo/ bar",
+        "http://example.com/foo/%2e%2E/bar",
+        "https://ñ.example/",
+        "https://Faß.ExAmPlE/",
+        "https://a%C2%ADb/",
+        "  http://example.com/ \t",
+        "http://user:p@ss@host/",
+        "non-special://host/path/../x",
+        "sc://ñ/",
+        "http://example.com\\a\\b",
+        "http://ex ample.com/",
+        "http://example.com/?q=a b&c=ä",
+        "data:text/plain,hi there",
+        "blob:https://example.com/uuid",
+        "http:example.com/",
+        "http://example.com:65536/",
+      ];
+
+      for (const input of inputs) {
+        let expected = null;
+
+        try {
+          expected = new URL(input).href;
+        } catch (_err) {}
+
+        assert.strictEqual(addon.url_crate_href(input), expected, input);
+      }
+    });
+
+    it("should differ from the WHATWG parser as documented", function () {
+      assert.equal(new URL("file:///C|/foo").href, "file:///C:/foo");
+      assert.equal(addon.url_crate_href("file:///C|/foo"), "file:///C|/foo");
+
+      assert.throws(() => new URL("https://a\u200Db/"), TypeError);
+      assert.equal(
+        addon.url_crate_href("https://a\u200Db/"),
+        "https://xn--ab-m1t/"
+      );
+
+      assert.throws(() => new URL("http://xn--1ug.de/"), TypeError);
+      assert.equal(
+        addon.url_crate_href("http://xn--1ug.de/"),
+        "http://xn--1ug.de/"
+      );
+
+      assert.equal(
+        new URL("https://\u0661\u0662\u0663.com/").href,
+        "https://xn--9hbcd.com/"
+      );
+      assert.isNull(addon.url_crate_href("https://\u0661\u0662\u0663.com/"));
+    });
+  });
+});
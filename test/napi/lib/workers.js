@@ -22,6 +22,11 @@ if (!isMainThread) {
   addon.get_or_init_thread_id(threadId);
 
   parentPort.once("message", (message) => {
+    if (message && message.port) {
+      addon.port_double(message.port);
+      return;
+    }
+
     try {
       switch (message) {
         case "get_and_replace":
@@ -39,6 +44,39 @@ if (!isMainThread) {
             parentPort.postMessage(id);
           }
           break;
+        case "get_env_info":
+          parentPort.postMessage(addon.env_info());
+          break;
+        case "get_env_info_without_builtin_module":
+          // Emulates versions of Node before 20.16 and 22.3
+          process.getBuiltinModule = undefined;
+          parentPort.postMessage(addon.env_info());
+          break;
+        case "get_process_init_info":
+          parentPort.postMessage({
+            ...addon.process_init_info(),
+            processInitCountAtMain: addon.processInitCountAtMain,
+          });
+          break;
+        case "check_intrinsics":
+          {
+            const intrinsics = addon.intrinsics();
+
+            parentPort.postMessage(
+              intrinsics.promise === Promise &&
+                intrinsics.objectFreeze === Object.freeze &&
+                intrinsics.symbolIterator === Symbol.iterator
+            );
+          }
+          break;
+        case "unstash_serialized":
+          parentPort.postMessage(addon.unstash_serialized());
+          break;
+        case "unstash_serialized_without_builtin_module":
+          // Emulates versions of Node before 20.16 and 22.3
+          process.getBuiltinModule = undefined;
+          parentPort.postMessage(addon.unstash_serialized());
+          break;
         default:
           throw new Error(`Unexpected message: ${message}`);
       }
@@ -213,3 +251,265 @@ describe("Instance-local storage", () => {
     });
   });
 });
+
+describe("Environment identification", () => {
+  it("should identify the main thread", () => {
+    assert.strictEqual(addon.env_info().isMainThread, true);
+  });
+
+  it("should have a stable environment id", () => {
+    assert.strictEqual(addon.env_info().envId, addon.env_info().envId);
+  });
+
+  it("should have a different environment id in a worker", (cb) => {
+    const worker = new Worker(__filename);
+    const { envId } = addon.env_info();
+
+    worker.once("message", (message) => {
+      try {
+        assert.strictEqual(message.isMainThread, false);
+        assert.strictEqual(typeof message.envId, "number");
+        assert.notStrictEqual(message.envId, envId);
+        assert.strictEqual(addon.env_info().envId, envId);
+        cb();
+      } catch (err) {
+        cb(err);
+      } finally {
+        worker.terminate();
+      }
+    });
+
+    worker.postMessage("get_env_info");
+  });
+
+  it("should identify a worker without process.getBuiltinModule", (cb) => {
+    const worker = new Worker(__filename);
+
+    worker.once("message", (message) => {
+      try {
+        assert.strictEqual(message.isMainThread, false);
+        cb();
+      } catch (err) {
+        cb(err);
+      } finally {
+        worker.terminate();
+      }
+    });
+
+    worker.postMessage("get_env_info_without_builtin_module");
+  });
+});
+
+describe("Process initialization", () => {
+  it("should run before main in the main thread", () => {
+    assert.strictEqual(addon.processInitCountAtMain, 1);
+    assert.strictEqual(addon.process_init_info().processInitCount, 1);
+  });
+
+  it("should run once regardless of how many workers load the module", async () => {
+    const { mainCount } = addon.process_init_info();
+    const workers = Array.from({ length: 4 }, () => new Worker(__filename));
+
+    try {
+      const infos = await Promise.all(
+        workers.map(
+          (worker) =>
+            new Promise((resolve, reject) => {
+              worker.once("message", resolve);
+              worker.once("error", reject);
+              worker.postMessage("get_process_init_info");
+            })
+        )
+      );
+
+      for (const info of infos) {
+        assert.strictEqual(info.processInitCountAtMain, 1);
+        assert.strictEqual(info.processInitCount, 1);
+      }
+
+      assert.strictEqual(addon.process_init_info().processInitCount, 1);
+      assert(addon.process_init_info().mainCount >= mainCount + workers.length);
+    } finally {
+      await Promise.all(workers.map((worker) => worker.terminate()));
+    }
+  });
+});
+
+describe("Transfer serialization", () => {
+  const value = {
+    number: 42,
+    string: "hello",
+    date: new Date(0),
+    map: new Map([["key", [1, 2, 3]]]),
+    bytes: new Uint8Array([1, 2, 3]),
+  };
+
+  it("should round-trip a value", () => {
+    addon.stash_serialized(value);
+    assert.deepStrictEqual(addon.unstash_serialized(), value);
+  });
+
+  it("should deserialize a value in a worker", (cb) => {
+    const worker = new Worker(__filename);
+
+    addon.stash_serialized(value);
+
+    worker.once("message", (message) => {
+      try {
+        assert.deepStrictEqual(message, value);
+        cb();
+      } catch (err) {
+        cb(err);
+      } finally {
+        worker.terminate();
+      }
+    });
+
+    worker.postMessage("unstash_serialized");
+  });
+
+  it("should deserialize a value without process.getBuiltinModule", (cb) => {
+    const worker = new Worker(__filename);
+
+    addon.stash_serialized(value);
+
+    worker.once("message", (message) => {
+      try {
+        assert.deepStrictEqual(message, value);
+        cb();
+      } catch (err) {
+        cb(err);
+      } finally {
+        worker.terminate();
+      }
+    });
+
+    worker.postMessage("unstash_serialized_without_builtin_module");
+  });
+
+  it("should throw the engine's error for unsupported values", () => {
+    assert.throws(
+      () => addon.stash_serialized({ f() {} }),
+      /could not be cloned/
+    );
+  });
+});
+
+describe("MessagePort bridging", () => {
+  const { MessageChannel } = require("worker_threads");
+
+  it("should post messages in order from a Rust thread", (cb) => {
+    const { port1, port2 } = new MessageChannel();
+    const received = [];
+
+    port2.on("message", (message) => {
+      received.push(message);
+
+      if (received.length === 10) {
+        port2.close();
+        assert.deepStrictEqual(received, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        cb();
+      }
+    });
+
+    addon.port_post(port1, 10);
+  });
+
+  it("should transfer values in the transfer list", (cb) => {
+    const { port1, port2 } = new MessageChannel();
+
+    port2.once("message", (message) => {
+      port2.close();
+      assert(message instanceof ArrayBuffer);
+      assert.strictEqual(message.byteLength, 16);
+      cb();
+    });
+
+    addon.port_post_transfer(port1, 16);
+  });
+
+  it("should receive and reply to messages in a worker", (cb) => {
+    const worker = new Worker(__filename);
+    const { port1, port2 } = new MessageChannel();
+
+    port1.once("message", (message) => {
+      try {
+        assert.strictEqual(message, 42);
+        cb();
+      } catch (err) {
+        cb(err);
+      } finally {
+        port1.close();
+        worker.terminate();
+      }
+    });
+
+    worker.postMessage({ port: port2 }, [port2]);
+    port1.postMessage(21);
+  });
+
+  it("should notify when the other side is closed", (cb) => {
+    const { port1, port2 } = new MessageChannel();
+
+    addon.port_on_close(port1, (isClosed) => {
+      assert.strictEqual(isClosed, true);
+      cb();
+    });
+
+    port2.close();
+  });
+
+  it("should throw for objects that are not ports", () => {
+    assert.throws(() => addon.port_post({}, 1), TypeError);
+  });
+});
+
+describe("Intrinsics", () => {
+  it("should return the built-ins of the environment", () => {
+    assert.deepStrictEqual(addon.intrinsics(), {
+      object: Object,
+      objectFreeze: Object.freeze,
+      objectDefineProperty: Object.defineProperty,
+      functionBind: Function.prototype.bind,
+      array: Array,
+      promise: Promise,
+      proxy: Proxy,
+      jsonParse: JSON.parse,
+      jsonStringify: JSON.stringify,
+      symbolIterator: Symbol.iterator,
+      symbolAsyncIterator: Symbol.asyncIterator,
+    });
+  });
+
+  it("should not observe replaced globals after caching", () => {
+    const original = globalThis.Promise;
+
+    addon.intrinsics();
+    globalThis.Promise = function NotAPromise() {};
+
+    try {
+      assert.strictEqual(addon.intrinsics().promise, original);
+    } finally {
+      globalThis.Promise = original;
+    }
+  });
+
+  it("should cache built-ins separately for each addon instance", (cb) => {
+    const worker = new Worker(__filename);
+
+    addon.intrinsics();
+
+    worker.once("message", (message) => {
+      try {
+        assert.strictEqual(message, true);
+        cb();
+      } catch (err) {
+        cb(err);
+      } finally {
+        worker.terminate();
+      }
+    });
+
+    worker.postMessage("check_intrinsics");
+  });
+});
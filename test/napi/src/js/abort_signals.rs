@@ -0,0 +1,59 @@
+use neon::{prelude::*, types::AbortSignal};
+
+pub fn abort_signal_count(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let signal = cx.argument::<JsObject>(0)?;
+    let signal = AbortSignal::from_object(&mut cx, signal)?;
+    let (deferred, promise) = cx.promise();
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        let mut n = 0u32;
+
+        while !signal.is_aborted() {
+            n = n.wrapping_add(1);
+            std::thread::yield_now();
+        }
+
+        deferred.settle_with(&channel, move |mut cx| {
+            let reason = signal.reason(&mut cx)?;
+            let n = cx.number(n);
+            let result = cx.empty_array();
+
+            result.set(&mut cx, 0, n)?;
+            result.set(&mut cx, 1, reason)?;
+
+            Ok(result)
+        });
+    });
+
+    Ok(promise)
+}
+
+pub fn abort_signal_register(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let signal = cx.argument::<JsObject>(0)?;
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let signal = AbortSignal::from_object(&mut cx, signal)?;
+
+    // Register from another thread, holding the token until the signal is aborted
+    std::thread::spawn(move || {
+        signal.register(move |mut cx, reason| {
+            let callback = callback.into_inner(&mut cx);
+
+            callback.call_with(&cx).arg(reason).exec(&mut cx)
+        });
+
+        while !signal.is_aborted() {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn abort_signal_drop(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let signal = cx.argument::<JsObject>(0)?;
+
+    AbortSignal::from_object(&mut cx, signal)?;
+
+    Ok(cx.undefined())
+}
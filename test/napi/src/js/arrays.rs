@@ -24,3 +24,245 @@ pub fn read_js_array(mut cx: FunctionContext) -> JsResult<JsValue> {
 
     Ok(first_element)
 }
+
+pub fn push_js_array(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let array: Handle<JsArray> = cx.argument(0)?;
+    let value: Handle<JsValue> = cx.argument(1)?;
+    array.push(&mut cx, value)?;
+    Ok(array)
+}
+
+pub fn js_array_from_arguments(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let args = cx.rest_arguments(0);
+    JsArray::from_slice(&mut cx, &args)
+}
+
+pub fn js_array_from_range(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let len = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let values = (0..len).map(|i| cx.number(i)).collect::<Vec<_>>();
+    JsArray::from_slice(&mut cx, &values)
+}
+
+pub fn js_array_with_capacity(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let len = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let array = cx.array_with_capacity(len);
+
+    for i in 0..len {
+        let s = cx.string(i.to_string());
+        array.set(&mut cx, i, s)?;
+    }
+
+    Ok(array)
+}
+
+pub fn iter_js_array(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let array: Handle<JsArray> = cx.argument(0)?;
+    let values = array
+        .iter(&mut cx)
+        .collect::<NeonResult<Vec<Handle<JsValue>>>>()?;
+    JsArray::from_slice(&mut cx, &values)
+}
+
+pub fn iter_js_array_with_callback(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let array: Handle<JsArray> = cx.argument(0)?;
+    let f: Handle<JsFunction> = cx.argument(1)?;
+    let result = cx.empty_array();
+    let mut iter = array.iter(&mut cx);
+
+    while let Some(value) = iter.next() {
+        let value = value?;
+        let cx = iter.cx();
+        let this = cx.undefined();
+        f.call(cx, this, [value])?;
+        result.push(cx, value)?;
+    }
+
+    Ok(result)
+}
+
+pub fn chunked_range_array(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let len = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let chunk_size = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let array = JsArray::new(&mut cx, len as u32);
+
+    cx.chunked(len, chunk_size, |cx, range| {
+        for i in range {
+            let n = cx.number(i as f64);
+            array.set(cx, i as u32, n)?;
+        }
+
+        Ok(())
+    })?;
+
+    Ok(array)
+}
+
+pub fn chunked_sum(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsArray>(0)?;
+    let chunk_size = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let len = array.len(&mut cx) as usize;
+    let mut sum = 0.0;
+
+    cx.chunked(len, chunk_size, |cx, range| {
+        for i in range {
+            sum += array.get::<JsNumber, _, _>(cx, i as u32)?.value(cx);
+        }
+
+        Ok(())
+    })?;
+
+    Ok(cx.number(sum))
+}
+
+pub fn chunked_ranges(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let len = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let chunk_size = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let f = cx.argument::<JsFunction>(2)?;
+
+    cx.chunked(len, chunk_size, |cx, range| {
+        let start = cx.number(range.start as f64);
+        let end = cx.number(range.end as f64);
+        let this = cx.undefined();
+
+        f.call(cx, this, [start.upcast(), end.upcast()])?;
+
+        Ok(())
+    })?;
+
+    Ok(cx.undefined())
+}
+
+pub fn read_js_array_into(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let array = cx.argument::<JsArray>(0)?;
+    let start = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let end = cx.argument::<JsNumber>(2)?.value(&mut cx) as u32;
+    let mut values = vec![cx.string("prefix").upcast()];
+
+    array.read_into(&mut cx, start..end, &mut values)?;
+
+    JsArray::from_slice(&mut cx, &values)
+}
+
+pub fn write_js_array_from(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let array = cx.argument::<JsArray>(0)?;
+    let start = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let values = cx.argument::<JsArray>(2)?.to_vec(&mut cx)?;
+
+    array.write_from(&mut cx, start, &values)?;
+
+    Ok(array)
+}
+
+pub fn js_array_to_vec_f64(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsArray>(0)?;
+    let sum = array
+        .to_vec_f64(&mut cx)?
+        .into_iter()
+        .fold(0.0, |a, b| a + b);
+
+    Ok(cx.number(sum))
+}
+
+pub fn js_array_to_vec_string(mut cx: FunctionContext) -> JsResult<JsString> {
+    let array = cx.argument::<JsArray>(0)?;
+    let joined = array.to_vec_string(&mut cx)?.join(",");
+
+    Ok(cx.string(joined))
+}
+
+pub fn js_array_to_vec_i32(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsArray>(0)?;
+    let sum = array
+        .to_vec_i32(&mut cx)?
+        .into_iter()
+        .map(f64::from)
+        .sum::<f64>();
+
+    Ok(cx.number(sum))
+}
+
+pub fn js_array_to_vec_u32(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsArray>(0)?;
+    let sum = array
+        .to_vec_u32(&mut cx)?
+        .into_iter()
+        .map(f64::from)
+        .sum::<f64>();
+
+    Ok(cx.number(sum))
+}
+
+pub fn js_array_count_true(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsArray>(0)?;
+    let count = array
+        .to_vec_bool(&mut cx)?
+        .into_iter()
+        .filter(|b| *b)
+        .count();
+
+    Ok(cx.number(count as f64))
+}
+
+// Returns `[0, 0.5, 1, ...]` of length `n`
+pub fn js_array_from_f64_slice(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let values = (0..n).map(|i| i as f64 / 2.0).collect::<Vec<_>>();
+
+    cx.array_from_f64_slice(&values)
+}
+
+// Converts each handle of `to_vec`, for comparison with `to_vec_f64`
+pub fn sum_js_array_to_vec_downcast(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsArray>(0)?;
+    let mut sum = 0.0;
+
+    for value in array.to_vec(&mut cx)? {
+        sum += value
+            .downcast_or_throw::<JsNumber, _>(&mut cx)?
+            .value(&mut cx);
+    }
+
+    Ok(cx.number(sum))
+}
+
+// Creates a handle for each number, for comparison with `array_from_f64_slice`
+pub fn js_array_from_number_handles(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let values = (0..n)
+        .map(|i| cx.number(i as f64 / 2.0))
+        .collect::<Vec<_>>();
+
+    JsArray::from_slice(&mut cx, &values)
+}
+
+pub fn sum_js_array_naive(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsArray>(0)?;
+    let mut sum = 0.0;
+
+    for i in 0..array.len(&mut cx) {
+        sum += array.get::<JsNumber, _, _>(&mut cx, i)?.value(&mut cx);
+    }
+
+    Ok(cx.number(sum))
+}
+
+pub fn count_js_array_naive(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsArray>(0)?;
+    let len = array.len(&mut cx);
+    let mut values = Vec::with_capacity(len as usize);
+
+    for i in 0..len {
+        values.push(array.get_value(&mut cx, i)?);
+    }
+
+    Ok(cx.number(values.len() as f64))
+}
+
+pub fn count_js_array_read_into(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsArray>(0)?;
+    let mut values = Vec::new();
+
+    array.read_into(&mut cx, .., &mut values)?;
+
+    Ok(cx.number(values.len() as f64))
+}
@@ -0,0 +1,75 @@
+use neon::{event::AsyncResource, prelude::*};
+
+pub fn async_resource_channel(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let resource = AsyncResource::new(&mut cx, &name)?;
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        channel.send(move |mut cx| {
+            let callback = callback.into_inner(&mut cx);
+            let this = cx.global();
+            let arg = cx.string("channel");
+
+            resource.make_callback(&mut cx, this, callback, [arg.upcast()])?;
+            resource.destroy(&mut cx);
+
+            Ok(())
+        })
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn async_resource_task(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let resource = AsyncResource::new(&mut cx, &name)?;
+
+    cx.task(|| "task").and_then(move |mut cx, arg| {
+        let callback = callback.into_inner(&mut cx);
+        let this = cx.global();
+        let arg = cx.string(arg);
+
+        resource.make_callback(&mut cx, this, callback, [arg.upcast()])?;
+        resource.destroy(&mut cx);
+
+        Ok(())
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn async_resource_scope(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let resource = cx.argument::<JsObject>(2)?;
+    let resource = AsyncResource::with_resource(&mut cx, &name, resource)?;
+    let channel = cx.channel();
+
+    channel.send(move |mut cx| {
+        let callback = callback.into_inner(&mut cx);
+
+        resource.in_scope(&mut cx, |cx| {
+            let this = cx.undefined();
+            let arg = cx.string("scope");
+
+            callback.call(cx, this, [arg.upcast()])
+        })?;
+
+        resource.destroy(&mut cx);
+
+        Ok(())
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn async_resource_drop(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    drop(AsyncResource::new(&mut cx, &name)?);
+
+    Ok(cx.undefined())
+}
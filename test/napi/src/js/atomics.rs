@@ -0,0 +1,144 @@
+use std::{thread, time::Duration};
+
+use neon::{prelude::*, types::shared::AtomicI32View};
+
+fn view(cx: &mut FunctionContext) -> NeonResult<AtomicI32View> {
+    let array = cx.argument::<JsTypedArray<i32>>(0)?;
+
+    AtomicI32View::new(cx, array)
+}
+
+pub fn atomics_view_len(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let view = view(&mut cx)?;
+
+    Ok(cx.number(view.len() as f64))
+}
+
+pub fn atomics_fetch_add(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let view = view(&mut cx)?;
+    let index = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let value = cx.argument::<JsNumber>(2)?.value(&mut cx) as i32;
+
+    Ok(cx.number(view.fetch_add(index, value)))
+}
+
+// Stores `value` at `index` and notifies all waiters from a Rust thread after `delay` ms
+pub fn atomics_notify_from_thread(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let view = view(&mut cx)?;
+    let index = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let value = cx.argument::<JsNumber>(2)?.value(&mut cx) as i32;
+    let delay = cx.argument::<JsNumber>(3)?.value(&mut cx) as u64;
+
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(delay));
+        view.store(index, value);
+        view.notify(index, u32::MAX);
+    });
+
+    Ok(cx.undefined())
+}
+
+// Waits on a Rust thread and calls back with the result of `AtomicI32View::wait`
+pub fn atomics_wait_on_thread(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let view = view(&mut cx)?;
+    let index = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let expected = cx.argument::<JsNumber>(2)?.value(&mut cx) as i32;
+    let timeout = cx.argument::<JsNumber>(3)?.value(&mut cx);
+    let timeout = (timeout >= 0.0).then(|| Duration::from_secs_f64(timeout / 1000.0));
+    let callback = cx.argument::<JsFunction>(4)?.root(&mut cx);
+    let channel = cx.channel();
+
+    thread::spawn(move || {
+        let result = view.wait(index, expected, timeout);
+
+        channel.send(move |mut cx| {
+            let result = cx.string(result.as_str());
+
+            callback
+                .into_inner(&mut cx)
+                .call_with(&cx)
+                .arg(result)
+                .exec(&mut cx)
+        });
+    });
+
+    Ok(cx.undefined())
+}
+
+// Wakes a waiting Rust thread from another Rust thread without changing the value
+pub fn atomics_wait_and_notify(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let view = view(&mut cx)?;
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let channel = cx.channel();
+
+    thread::spawn(move || {
+        let waiter = {
+            let view = view.clone();
+
+            thread::spawn(move || view.wait(0, 0, Some(Duration::from_secs(10))))
+        };
+
+        // Notify until the waiter has started waiting
+        let mut woken = 0;
+
+        while woken == 0 {
+            thread::sleep(Duration::from_millis(5));
+            woken = view.notify(0, 1);
+        }
+
+        let result = waiter.join().unwrap();
+
+        channel.send(move |mut cx| {
+            let result = cx.string(result.as_str());
+            let woken = cx.number(woken);
+
+            callback
+                .into_inner(&mut cx)
+                .call_with(&cx)
+                .arg(result)
+                .arg(woken)
+                .exec(&mut cx)
+        });
+    });
+
+    Ok(cx.undefined())
+}
+
+// Notifies a Rust thread waiting on element `1` while another waits on element `0`,
+// returning the result of both waits
+pub fn atomics_notify_one_element(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let view = view(&mut cx)?;
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let channel = cx.channel();
+
+    thread::spawn(move || {
+        let waiters = [(0, Duration::from_millis(50)), (1, Duration::from_secs(10))].map(
+            |(index, timeout)| {
+                let view = view.clone();
+
+                thread::spawn(move || view.wait(index, 0, Some(timeout)))
+            },
+        );
+
+        // Notify until the waiter has started waiting
+        while view.notify(1, u32::MAX) == 0 {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        let [other, notified] = waiters.map(|waiter| waiter.join().unwrap());
+
+        channel.send(move |mut cx| {
+            let other = cx.string(other.as_str());
+            let notified = cx.string(notified.as_str());
+
+            callback
+                .into_inner(&mut cx)
+                .call_with(&cx)
+                .arg(other)
+                .arg(notified)
+                .exec(&mut cx)
+        });
+    });
+
+    Ok(cx.undefined())
+}
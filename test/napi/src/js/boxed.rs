@@ -1,6 +1,14 @@
-use std::cell::RefCell;
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
 
-use neon::prelude::*;
+use neon::{
+    prelude::*,
+    types::{JsRwLock, Shared, SharedHandle},
+};
 
 pub struct Person {
     name: String,
@@ -69,6 +77,200 @@ pub fn ref_person_fail(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     Ok(cx.undefined())
 }
 
+// Calls `callback` with the greeting, allowing it to call back into other methods
+// on the same person, and returns the greeting afterward
+pub fn ref_person_greet_reentrant(mut cx: FunctionContext) -> JsResult<JsString> {
+    let person = cx.argument::<JsBox<RefCell<Person>>>(0)?;
+    let callback = cx.argument::<JsFunction>(1)?;
+
+    person.with_state_reentrant(&mut cx, |s| {
+        let greeting = s.state().greet();
+        let cx = s.cx();
+        let greeting = cx.string(greeting);
+
+        callback.call_with(cx).arg(greeting).exec(cx)?;
+
+        let greeting = s.state().greet();
+
+        Ok(s.cx().string(greeting))
+    })
+}
+
 pub fn external_unit(mut cx: FunctionContext) -> JsResult<JsBox<()>> {
     Ok(cx.boxed(()))
 }
+
+pub struct SharedResource {
+    name: String,
+    on_finalize: Root<JsFunction>,
+}
+
+impl Finalize for SharedResource {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        let on_finalize = self.on_finalize.into_inner(cx);
+        let this = cx.undefined();
+
+        let _ = on_finalize.call(cx, this, (self.name,));
+    }
+}
+
+type BoxedResource = JsBox<Shared<SharedResource>>;
+type BoxedSharedHandle = JsBox<RefCell<Option<SharedHandle<SharedResource>>>>;
+
+fn take_shared_handle(cx: &mut FunctionContext) -> NeonResult<SharedHandle<SharedResource>> {
+    let handle = cx.argument::<BoxedSharedHandle>(0)?;
+    let handle = handle.borrow_mut().take();
+
+    match handle {
+        Some(handle) => Ok(handle),
+        None => cx.throw_error("handle was already taken"),
+    }
+}
+
+pub fn shared_new(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let on_finalize = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let (handle, resource) = cx.shared(SharedResource {
+        name: "resource".to_string(),
+        on_finalize,
+    });
+    let handle = cx.boxed(RefCell::new(Some(handle)));
+    let result = cx.empty_array();
+
+    result.set(&mut cx, 0, handle)?;
+    result.set(&mut cx, 1, resource)?;
+
+    Ok(result)
+}
+
+pub fn shared_name(mut cx: FunctionContext) -> JsResult<JsString> {
+    let resource = cx.argument::<BoxedResource>(0)?;
+
+    Ok(cx.string(&resource.name))
+}
+
+pub fn shared_to_js(mut cx: FunctionContext) -> JsResult<BoxedResource> {
+    let handle = cx.argument::<BoxedSharedHandle>(0)?;
+    let handle = handle.borrow();
+
+    match handle.as_ref() {
+        Some(handle) => Ok(handle.to_js(&mut cx)),
+        None => cx.throw_error("handle was already taken"),
+    }
+}
+
+pub fn shared_handle(mut cx: FunctionContext) -> JsResult<BoxedSharedHandle> {
+    let resource = cx.argument::<BoxedResource>(0)?;
+    let handle = resource.handle(&mut cx);
+
+    Ok(cx.boxed(RefCell::new(Some(handle))))
+}
+
+pub fn shared_handle_drop(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    drop(take_shared_handle(&mut cx)?);
+
+    Ok(cx.undefined())
+}
+
+pub fn shared_handle_drop_on_thread(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let handle = take_shared_handle(&mut cx)?;
+
+    thread::spawn(move || drop(handle)).join().unwrap();
+
+    Ok(cx.undefined())
+}
+
+pub fn shared_handle_finalize(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    take_shared_handle(&mut cx)?.finalize(&mut cx);
+
+    Ok(cx.undefined())
+}
+
+pub struct Index {
+    values: Vec<f64>,
+    reading: AtomicBool,
+}
+
+impl Finalize for Index {}
+
+type BoxedIndex = JsBox<JsRwLock<Index>>;
+
+pub fn rwlock_new(mut cx: FunctionContext) -> JsResult<BoxedIndex> {
+    let values = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+    let values = values
+        .into_iter()
+        .map(|v| Ok(v.downcast_or_throw::<JsNumber, _>(&mut cx)?.value(&mut cx)))
+        .collect::<NeonResult<_>>()?;
+
+    Ok(cx.boxed_rwlock(Index {
+        values,
+        reading: AtomicBool::new(false),
+    }))
+}
+
+// Sums the values in a task, holding the read guard for `ms` milliseconds
+pub fn rwlock_sum(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let index = cx.argument::<BoxedIndex>(0)?.to_shared(&mut cx);
+    let ms = cx.argument::<JsNumber>(1)?.value(&mut cx) as u64;
+
+    let promise = cx
+        .task(move || {
+            let index = index.read();
+
+            index.reading.store(true, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(ms));
+            index.reading.store(false, Ordering::SeqCst);
+
+            index.values.iter().sum::<f64>()
+        })
+        .promise(|mut cx, sum| Ok(cx.number(sum)));
+
+    Ok(promise)
+}
+
+pub fn rwlock_is_reading(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let index = cx.argument::<BoxedIndex>(0)?;
+    let reading = index.read().reading.load(Ordering::SeqCst);
+
+    Ok(cx.boolean(reading))
+}
+
+pub fn rwlock_push(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let index = cx.argument::<BoxedIndex>(0)?;
+    let value = cx.argument::<JsNumber>(1)?.value(&mut cx);
+
+    index.write().values.push(value);
+
+    Ok(cx.undefined())
+}
+
+// Pushes `value` if no task is reading, returning `false` otherwise
+pub fn rwlock_try_push(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let index = cx.argument::<BoxedIndex>(0)?;
+    let value = cx.argument::<JsNumber>(1)?.value(&mut cx);
+    let pushed = match index.try_write() {
+        Some(mut index) => {
+            index.values.push(value);
+            true
+        }
+        None => false,
+    };
+
+    Ok(cx.boolean(pushed))
+}
+
+// Sends to the JavaScript thread from a task while holding a read guard
+pub fn rwlock_send_while_reading(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let index = cx.argument::<BoxedIndex>(0)?.to_shared(&mut cx);
+    let channel = cx.channel();
+
+    let promise = cx
+        .task(move || {
+            let index = index.read();
+            let len = index.values.len();
+
+            channel.send(move |_| Ok(len));
+        })
+        .promise(|mut cx, _| Ok(cx.undefined()));
+
+    Ok(promise)
+}
@@ -4,3 +4,23 @@ pub fn to_string(mut cx: FunctionContext) -> JsResult<JsString> {
     let arg: Handle<JsValue> = cx.argument(0)?;
     arg.to_string(&mut cx)
 }
+
+pub fn coerce_to_string(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg: Handle<JsValue> = cx.argument(0)?;
+    arg.coerce_to_string(&mut cx)
+}
+
+pub fn coerce_to_number(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arg: Handle<JsValue> = cx.argument(0)?;
+    arg.coerce_to_number(&mut cx)
+}
+
+pub fn coerce_to_bool(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let arg: Handle<JsValue> = cx.argument(0)?;
+    arg.coerce_to_bool(&mut cx)
+}
+
+pub fn coerce_to_object(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let arg: Handle<JsValue> = cx.argument(0)?;
+    arg.coerce_to_object(&mut cx)
+}
@@ -0,0 +1,59 @@
+use neon::{console::InspectOptions, prelude::*};
+
+pub fn console_log(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let args = cx.arguments_iter().collect::<Vec<_>>();
+
+    cx.console().log(args)?;
+
+    Ok(cx.undefined())
+}
+
+pub fn console_warn_error(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    cx.console().warn(["warning", "from Rust"])?;
+    cx.console().error([42])?;
+
+    Ok(cx.undefined())
+}
+
+pub fn console_log_channel(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let (deferred, promise) = cx.promise();
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        deferred.settle_with(&channel, |mut cx| {
+            cx.console().log(["from a channel"])?;
+
+            Ok(cx.undefined())
+        });
+    });
+
+    Ok(promise)
+}
+
+pub fn console_log_task(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let promise = cx
+        .task(|| String::from("from a task"))
+        .promise(|mut cx, message| {
+            cx.console().log([message])?;
+
+            Ok(cx.undefined())
+        });
+
+    Ok(promise)
+}
+
+pub fn inspect(mut cx: FunctionContext) -> JsResult<JsString> {
+    let value = cx.argument::<JsValue>(0)?;
+    let depth = cx
+        .argument_opt(1)
+        .and_then(|v| v.downcast::<JsNumber, _>(&mut cx).ok())
+        .map(|n| n.value(&mut cx) as u32);
+    let colors = cx
+        .argument_opt(2)
+        .and_then(|v| v.downcast::<JsBoolean, _>(&mut cx).ok())
+        .map(|b| b.value(&mut cx))
+        .unwrap_or(false);
+    let s = cx.inspect(value, InspectOptions { depth, colors })?;
+
+    Ok(cx.string(s))
+}
@@ -1,4 +1,10 @@
-use neon::{prelude::*, types::JsDate};
+use neon::{
+    prelude::*,
+    types::{
+        extract::{Iso8601, TryFromJs, TryIntoJs},
+        JsDate,
+    },
+};
 
 pub fn create_date(mut cx: FunctionContext) -> JsResult<JsDate> {
     let date = JsDate::new_lossy(&mut cx, 31415);
@@ -91,3 +97,70 @@ pub fn get_date_value(mut cx: FunctionContext) -> JsResult<JsNumber> {
     let value = date.value(&mut cx);
     Ok(cx.number(value))
 }
+
+pub fn chrono_round_trip(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let date_time = chrono::DateTime::<chrono::Utc>::try_from_js(&mut cx, arg)?;
+
+    date_time.try_into_js(&mut cx)
+}
+
+pub fn chrono_from_nanos(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let secs = cx.argument::<JsNumber>(0)?.value(&mut cx) as i64;
+    let nanos = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let date_time = chrono::DateTime::from_timestamp(secs, nanos).unwrap();
+
+    date_time.try_into_js(&mut cx)
+}
+
+pub fn chrono_max(mut cx: FunctionContext) -> JsResult<JsDate> {
+    chrono::DateTime::<chrono::Utc>::MAX_UTC.try_into_js(&mut cx)
+}
+
+pub fn chrono_naive_date(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let date = chrono::NaiveDate::try_from_js(&mut cx, arg)?;
+
+    Ok(cx.string(date.to_string()))
+}
+
+pub fn chrono_naive_date_to_js(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let date = chrono::NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+
+    date.try_into_js(&mut cx)
+}
+
+pub fn chrono_iso_8601(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let Iso8601(date_time) = Iso8601::<chrono::DateTime<chrono::Utc>>::try_from_js(&mut cx, arg)?;
+
+    date_time.try_into_js(&mut cx)
+}
+
+pub fn chrono_iso_8601_naive_date(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let Iso8601(date) = Iso8601::<chrono::NaiveDate>::try_from_js(&mut cx, arg)?;
+
+    Ok(cx.string(date.to_string()))
+}
+
+pub fn time_round_trip(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let date_time = time::OffsetDateTime::try_from_js(&mut cx, arg)?;
+
+    date_time.try_into_js(&mut cx)
+}
+
+pub fn time_from_nanos(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let nanos = cx.argument::<JsNumber>(0)?.value(&mut cx) as i128;
+    let date_time = time::OffsetDateTime::from_unix_timestamp_nanos(nanos).unwrap();
+
+    date_time.try_into_js(&mut cx)
+}
+
+pub fn time_iso_8601(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let Iso8601(date_time) = Iso8601::<time::OffsetDateTime>::try_from_js(&mut cx, arg)?;
+
+    date_time.try_into_js(&mut cx)
+}
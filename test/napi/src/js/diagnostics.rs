@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use neon::{diagnostics::LoopMonitor, prelude::*};
+
+pub fn loop_monitor_new(mut cx: FunctionContext) -> JsResult<JsBox<LoopMonitor>> {
+    let interval = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let monitor = LoopMonitor::with_interval(&mut cx, Duration::from_secs_f64(interval / 1000.0));
+
+    Ok(cx.boxed(monitor))
+}
+
+pub fn loop_monitor_snapshot(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let stats = cx.argument::<JsBox<LoopMonitor>>(0)?.snapshot();
+    let obj = cx.empty_object();
+
+    for (key, value) in [
+        ("p50", stats.p50),
+        ("p95", stats.p95),
+        ("p99", stats.p99),
+        ("max", stats.max),
+    ] {
+        let ms = cx.number(value.as_secs_f64() * 1000.0);
+
+        obj.set(&mut cx, key, ms)?;
+    }
+
+    let count = cx.number(stats.count as f64);
+
+    obj.set(&mut cx, "count", count)?;
+
+    Ok(obj)
+}
+
+pub fn loop_monitor_reset(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    cx.argument::<JsBox<LoopMonitor>>(0)?.reset();
+
+    Ok(cx.undefined())
+}
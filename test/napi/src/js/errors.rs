@@ -1,4 +1,11 @@
-use neon::prelude::*;
+use std::fmt;
+
+use neon::{
+    error::{self, Error, ErrorBuilder},
+    prelude::*,
+    thread::LocalKey,
+    types::{function::FunctionOptions, ErrorSpec},
+};
 
 pub fn new_error(mut cx: FunctionContext) -> JsResult<JsError> {
     let msg = cx.argument::<JsString>(0)?.value(&mut cx);
@@ -24,6 +31,64 @@ pub fn throw_error(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     cx.throw_error(msg)
 }
 
+pub fn error_with_stack_note(mut cx: FunctionContext) -> JsResult<JsError> {
+    let note = cx.argument::<JsString>(0)?.value(&mut cx);
+    let err = cx.error("failed to parse")?;
+
+    err.prepend_stack_note(&mut cx, &note)?;
+
+    Ok(err)
+}
+
+pub fn error_captured_above(mut cx: FunctionContext) -> JsResult<JsError> {
+    let skip_frames = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let err = cx.error("failed to parse")?;
+
+    err.capture_stack_above(&mut cx, skip_frames)?;
+
+    Ok(err)
+}
+
+pub fn task_throw_error(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let promise = cx
+        .task(|| ())
+        .promise(|mut cx, _| cx.throw_error::<_, Handle<JsUndefined>>("task failed"));
+
+    Ok(promise)
+}
+
+// `expect_function_argument(value, callback)`
+pub fn expect_function_argument(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    cx.argument::<JsFunction>(1)?;
+
+    Ok(cx.undefined())
+}
+
+pub fn downcast_or_throw_array(mut cx: FunctionContext) -> JsResult<JsArray> {
+    cx.argument::<JsValue>(0)?.downcast_or_throw(&mut cx)
+}
+
+pub fn own_function_name(mut cx: FunctionContext) -> JsResult<JsValue> {
+    match cx.function_name() {
+        Some(name) => Ok(cx.string(name).upcast()),
+        None => Ok(cx.null().upcast()),
+    }
+}
+
+fn expect_string_argument(mut cx: FunctionContext) -> JsResult<JsString> {
+    cx.argument::<JsString>(0)
+}
+
+pub fn named_function_expecting_string(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let options = FunctionOptions::new().name("parseConfig");
+
+    JsFunction::with_options(&mut cx, options, expect_string_argument)
+}
+
+pub fn unnamed_function_expecting_string(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    JsFunction::new(&mut cx, expect_string_argument)
+}
+
 pub fn downcast_error(mut cx: FunctionContext) -> JsResult<JsString> {
     let s = cx.string("hi");
     if let Err(e) = s.downcast::<JsNumber, _>(&mut cx) {
@@ -32,3 +97,194 @@ pub fn downcast_error(mut cx: FunctionContext) -> JsResult<JsString> {
         panic!()
     }
 }
+
+pub enum ParseError {
+    Empty,
+    Invalid(String),
+    Negative(f64),
+}
+
+impl From<ParseError> for ErrorSpec {
+    fn from(err: ParseError) -> Self {
+        match err {
+            ParseError::Empty => ErrorSpec::type_error("input is empty").with_code("ERR_EMPTY"),
+            ParseError::Invalid(s) => ErrorSpec::error(format!("invalid number: {}", s)),
+            ParseError::Negative(n) => {
+                ErrorSpec::range_error(format!("{} is negative", n)).with_code("ERR_NEGATIVE")
+            }
+        }
+    }
+}
+
+pub fn parse_positive(mut cx: FunctionContext) -> Result<Handle<JsNumber>, ParseError> {
+    let s = match cx
+        .argument_opt(0)
+        .and_then(|v| v.downcast::<JsString, _>(&mut cx).ok())
+    {
+        Some(s) => s.value(&mut cx),
+        None => return Err(ParseError::Empty),
+    };
+
+    if s.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let n = s.parse::<f64>().map_err(|_| ParseError::Invalid(s))?;
+
+    if n < 0.0 {
+        return Err(ParseError::Negative(n));
+    }
+
+    if n == 42.0 {
+        panic!("{} is not allowed", n);
+    }
+
+    Ok(cx.number(n))
+}
+
+pub fn result_after_throw(mut cx: FunctionContext) -> Result<Handle<JsUndefined>, &'static str> {
+    let _ = cx.throw_type_error::<_, ()>("thrown first");
+
+    Err("returned second")
+}
+
+#[derive(Debug)]
+pub struct DatabaseError {
+    message: String,
+    code: &'static str,
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+#[derive(Debug)]
+pub struct LateError;
+
+impl fmt::Display for LateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("late error")
+    }
+}
+
+impl std::error::Error for LateError {}
+
+static DATABASE_ERROR: LocalKey<Root<JsFunction>> = LocalKey::new();
+
+fn query(sql: &str) -> Result<f64, Error> {
+    match sql {
+        "SELECT 1" => Ok(1.0),
+        "SELECT nothing" => Err(DatabaseError {
+            message: "no such table: nothing".to_string(),
+            code: "EDB",
+        }
+        .into()),
+        _ => Err(sql.parse::<f64>().unwrap_err().into()),
+    }
+}
+
+pub fn init_database_error(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let class = cx.argument::<JsFunction>(0)?;
+
+    DATABASE_ERROR.get_or_try_init(&mut cx, |cx| NeonResult::Ok(class.root(cx)))?;
+
+    error::register::<DatabaseError, _>(|cx, err| {
+        let class = match DATABASE_ERROR.get(cx) {
+            Some(class) => class.to_inner(cx),
+            None => return Ok(cx.error(err.to_string())?.upcast()),
+        };
+
+        let message = cx.string(&err.message).upcast();
+        let code = cx.string(err.code).upcast();
+
+        class.construct(cx, [message, code])
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn query_or_throw(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let sql = cx.argument::<JsString>(0)?.value(&mut cx);
+    let n = query(&sql).or_throw(&mut cx)?;
+
+    Ok(cx.number(n))
+}
+
+pub fn query_result(mut cx: FunctionContext) -> Result<Handle<JsNumber>, Error> {
+    let sql = match cx
+        .argument_opt(0)
+        .and_then(|v| v.downcast::<JsString, _>(&mut cx).ok())
+    {
+        Some(sql) => sql.value(&mut cx),
+        None => String::new(),
+    };
+
+    query(&sql).map(|n| cx.number(n))
+}
+
+pub fn query_task(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let sql = cx.argument::<JsString>(0)?.value(&mut cx);
+    let promise = cx.task(move || query(&sql)).into_promise();
+
+    Ok(promise)
+}
+
+pub fn throw_late_error(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    error::throw(&mut cx, &LateError)
+}
+
+pub fn register_late_error(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let code = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    error::register::<LateError, _>(move |cx, err| {
+        let js_err = cx.type_error(err.to_string())?;
+        let code = cx.string(&code);
+
+        js_err.set(cx, "code", code)?;
+
+        Ok(js_err.upcast())
+    });
+
+    Ok(cx.undefined())
+}
+
+// `register_error_class(name, constructor)`
+pub fn register_error_class(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+    let constructor = cx.argument::<JsFunction>(1)?;
+
+    error::register_class(&mut cx, &name, constructor);
+
+    Ok(cx.undefined())
+}
+
+// `throw_custom_error(class, message, props)`, where `props` may be `undefined`
+pub fn throw_custom_error(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let class = cx.argument::<JsString>(0)?.value(&mut cx);
+    let message = cx.argument::<JsString>(1)?.value(&mut cx);
+    let props = cx.argument_opt(2);
+    let props = match props {
+        Some(props) if !props.is_a::<JsUndefined, _>(&mut cx) => {
+            Some(props.downcast_or_throw::<JsObject, _>(&mut cx)?)
+        }
+        _ => None,
+    };
+
+    cx.throw_custom(&class, message, props)
+}
+
+// `custom_error(class, message, code)`
+pub fn custom_error(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let class = cx.argument::<JsString>(0)?.value(&mut cx);
+    let message = cx.argument::<JsString>(1)?.value(&mut cx);
+    let code = cx.argument::<JsString>(2)?.value(&mut cx);
+
+    ErrorBuilder::class(class)
+        .message(message)
+        .code(code)
+        .build(&mut cx)
+}
@@ -1,4 +1,4 @@
-use neon::prelude::*;
+use neon::{prelude::*, types::function::FunctionOptions};
 
 fn add1(mut cx: FunctionContext) -> JsResult<JsNumber> {
     let x = cx.argument::<JsNumber>(0)?.value(&mut cx);
@@ -103,6 +103,30 @@ pub fn call_js_function_with_heterogeneous_tuple(mut cx: FunctionContext) -> JsR
         .apply(&mut cx)
 }
 
+pub fn call_js_function_with_tuple_args(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let this = cx.argument::<JsValue>(1)?;
+    let name = cx.string("neon");
+
+    f.call(&mut cx, this, (name, 42.0, true, "hello", None::<f64>))
+}
+
+pub fn construct_js_function_with_tuple_args(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let array: Handle<JsFunction> = cx.global().get(&mut cx, "Array")?;
+    let one = cx.number(1);
+
+    array
+        .construct(&mut cx, (one, 2.0, 3))?
+        .downcast_or_throw(&mut cx)
+}
+
+pub fn bind_js_function_with_tuple_args(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let this = cx.argument::<JsValue>(1)?;
+
+    f.bind(&mut cx, this, (1, "two"))
+}
+
 pub fn construct_js_function(mut cx: FunctionContext) -> JsResult<JsNumber> {
     let f = cx.argument::<JsFunction>(0)?;
     let zero = cx.number(0.0);
@@ -229,6 +253,73 @@ pub fn call_and_catch(mut cx: FunctionContext) -> JsResult<JsValue> {
         .unwrap_or_else(|err| err))
 }
 
+pub fn call_and_catch_pending(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let f: Handle<JsFunction> = cx.argument(0)?;
+    let this = cx.undefined();
+    let value = match f.call(&mut cx, this, ()) {
+        Ok(v) => v,
+        Err(_) => {
+            let was_throwing = cx.is_throwing();
+            let err = cx.catch_pending().expect("exception is pending");
+            let result = cx.empty_array();
+            let was_throwing = cx.boolean(was_throwing);
+            let is_throwing = cx.boolean(cx.is_throwing());
+
+            result.set(&mut cx, 0, was_throwing)?;
+            result.set(&mut cx, 1, err)?;
+            result.set(&mut cx, 2, is_throwing)?;
+
+            return Ok(result);
+        }
+    };
+
+    let result = cx.empty_array();
+    let is_throwing = cx.boolean(cx.is_throwing());
+
+    result.set(&mut cx, 0, is_throwing)?;
+    result.set(&mut cx, 1, value)?;
+
+    Ok(result)
+}
+
+pub fn catch_pending_when_not_throwing(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    assert!(!cx.is_throwing());
+    assert!(cx.catch_pending().is_none());
+    assert!(cx.catch_pending().is_none());
+
+    Ok(cx.boolean(!cx.is_throwing()))
+}
+
+// Recovers from strings thrown by `f` and rethrows everything else through `try_catch`
+pub fn catch_strings(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let f: Handle<JsFunction> = cx.argument(0)?;
+    let result = cx.try_catch(|cx| {
+        let this = cx.undefined();
+
+        if let Ok(v) = f.call(cx, this, ()) {
+            return Ok(v);
+        }
+
+        let err = cx.catch_pending().expect("exception is pending");
+
+        if err.is_a::<JsString, _>(cx) {
+            Ok(err)
+        } else {
+            cx.throw(err)
+        }
+    });
+
+    match result {
+        Ok(v) => Ok(v),
+        Err(err) => {
+            let wrapped = cx.empty_object();
+
+            wrapped.set(&mut cx, "rethrown", err)?;
+            cx.throw(wrapped)
+        }
+    }
+}
+
 pub fn get_number_or_default(mut cx: FunctionContext) -> JsResult<JsNumber> {
     let n = cx
         .try_catch(|cx| Ok(cx.argument::<JsNumber>(0)?.value(cx)))
@@ -245,6 +336,18 @@ pub fn is_construct(mut cx: FunctionContext) -> JsResult<JsObject> {
     Ok(this)
 }
 
+// Behaves like a class constructor, recording `new.target` on the new object
+pub fn construct_only(mut cx: FunctionContext) -> JsResult<JsObject> {
+    if !cx.is_construct_call() {
+        return cx.throw_type_error("construct_only must be called with new");
+    }
+
+    let this = cx.this::<JsObject>()?;
+    let target = cx.new_target().unwrap();
+    this.set(&mut cx, "target", target)?;
+    Ok(this)
+}
+
 // `function caller_with_drop_callback(wrappedCallback, dropCallback)`
 //
 // `wrappedCallback` will be called each time the returned function is
@@ -294,3 +397,261 @@ pub fn caller_with_drop_callback(mut cx: FunctionContext) -> JsResult<JsFunction
         callback.f.to_inner(&mut cx).call(&mut cx, this, args)
     })
 }
+
+pub fn return_mut_counter(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let mut n = 0;
+
+    JsFunction::new_mut(&mut cx, move |mut cx| {
+        n += 1;
+        Ok(cx.number(n))
+    })
+}
+
+// Returns a function that calls its argument; the argument may attempt to call the
+// returned function again to verify re-entrancy is rejected.
+pub fn return_mut_reentrant(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let mut calls = 0;
+
+    JsFunction::new_mut(&mut cx, move |mut cx| {
+        calls += 1;
+        cx.argument::<JsFunction>(0)?
+            .call_with(&cx)
+            .arg(cx.number(calls))
+            .apply::<JsValue, _>(&mut cx)
+    })
+}
+
+// Like `return_mut_reentrant`, an optional callback argument may call the function again
+pub fn return_function_from_context(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let greeting = cx.argument::<JsString>(0)?.value(&mut cx);
+    let mut calls = 0;
+
+    cx.function(move |mut cx| {
+        calls += 1;
+
+        if let Some(f) = cx.argument_opt(0) {
+            f.downcast_or_throw::<JsFunction, _>(&mut cx)?
+                .call_with(&cx)
+                .exec(&mut cx)?;
+        }
+
+        Ok(cx.string(format!("{greeting} {calls}")))
+    })
+}
+
+pub fn return_js_function_with_options(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let options = FunctionOptions::new().name("add1").length(1);
+
+    JsFunction::with_options(&mut cx, options, add1)
+}
+
+pub fn get_js_function_name(mut cx: FunctionContext) -> JsResult<JsString> {
+    let name = cx.argument::<JsFunction>(0)?.name(&mut cx)?;
+
+    Ok(cx.string(name))
+}
+
+pub fn construct_js_class(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let class = cx.argument::<JsFunction>(0)?;
+    let arg = cx.argument::<JsValue>(1)?;
+
+    class.construct(&mut cx, [arg])
+}
+
+pub fn construct_js_class_idiomatically(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let arg = cx.argument::<JsValue>(1)?;
+
+    cx.argument::<JsFunction>(0)?
+        .construct_with(&cx)
+        .arg(arg)
+        .apply(&mut cx)
+}
+
+pub fn call_js_function_with_args_from_iter(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    get_math_max(&mut cx)?
+        .call_with(&cx)
+        .args_from_iter(&mut cx, 1..=4)?
+        .apply(&mut cx)
+}
+
+pub fn call_js_function_expecting_string(mut cx: FunctionContext) -> JsResult<JsString> {
+    cx.argument::<JsFunction>(0)?
+        .call_with(&cx)
+        .returning::<JsString>(&mut cx)
+}
+
+pub fn bind_js_function(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let this = cx.argument::<JsValue>(1)?;
+    let args = cx.argument::<JsArray>(2)?.to_vec(&mut cx)?;
+
+    f.bind(&mut cx, this, args)
+}
+
+pub fn sum_rest_arguments(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let from = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let mut sum = 0.0;
+
+    for n in cx.rest_arguments(from) {
+        sum += n.downcast_or_throw::<JsNumber, _>(&mut cx)?.value(&mut cx);
+    }
+
+    Ok(cx.number(sum))
+}
+
+pub fn count_arguments_iter(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let n = cx.arguments_iter().count();
+
+    Ok(cx.number(n as u32))
+}
+
+pub fn describe_args_info(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let info = cx.args_info();
+    let len = info.len();
+    let this = info.this();
+    let last = info.get(len.saturating_sub(1));
+    let out = cx.empty_array();
+    let len = cx.number(len as u32);
+    let last = match last {
+        Some(v) => v,
+        None => cx.undefined().upcast(),
+    };
+
+    out.set(&mut cx, 0, len)?;
+    out.set(&mut cx, 1, this)?;
+    out.set(&mut cx, 2, last)?;
+
+    Ok(out)
+}
+
+pub fn argument_or_default(mut cx: FunctionContext) -> JsResult<JsString> {
+    let default = cx.string("default");
+
+    cx.argument_or(0, default)
+}
+
+pub fn noop(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    Ok(cx.undefined())
+}
+
+pub fn sum_six_arguments(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let mut sum = 0.0;
+
+    for i in 0..6 {
+        sum += cx.argument::<JsNumber>(i)?.value(&mut cx);
+    }
+
+    Ok(cx.number(sum))
+}
+
+// Calls `f(a, b, c)` `n` times, collecting the arguments in a `Vec`
+pub fn call_with_vec_args(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let this = cx.undefined();
+    let (a, b, c) = (cx.number(1), cx.string("b"), cx.boolean(true));
+
+    for _ in 0..n {
+        f.exec(&mut cx, this, vec![a.upcast(), b.upcast(), c.upcast()])?;
+    }
+
+    Ok(cx.undefined())
+}
+
+// Calls `f(a, b, c)` `n` times, passing the arguments as a tuple
+pub fn call_with_tuple_args(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let this = cx.undefined();
+    let (a, b, c) = (cx.number(1), cx.string("b"), cx.boolean(true));
+
+    for _ in 0..n {
+        f.exec(&mut cx, this, (a, b, c))?;
+    }
+
+    Ok(cx.undefined())
+}
+
+pub fn call_with_options_args(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let argc = cx.argument::<JsNumber>(2)?.value(&mut cx) as usize;
+    let arg = cx.number(1);
+
+    for _ in 0..n {
+        f.call_with(&cx)
+            .args_from_iter(&mut cx, std::iter::repeat_n(arg, argc))?
+            .exec(&mut cx)?;
+    }
+
+    Ok(cx.undefined())
+}
+
+pub fn console_log_args(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let argc = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+
+    for _ in 0..n {
+        cx.console().log(std::iter::repeat_n(1.0, argc))?;
+    }
+
+    Ok(cx.undefined())
+}
+
+// Returns a function that forwards its `this` and arguments unchanged to `inner`
+pub fn forwarding_wrapper(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let inner = cx.argument::<JsFunction>(0)?.root(&mut cx);
+
+    cx.function(move |mut cx| {
+        let inner = inner.to_inner(&mut cx);
+        let forwarded = cx.forward_args();
+        let this = forwarded.this();
+
+        inner.call_with_forwarded(&mut cx, this, forwarded)
+    })
+}
+
+// Returns `new.target` as seen by `forward_args`, or `undefined` for a plain call
+pub fn forwarded_new_target(mut cx: FunctionContext) -> JsResult<JsValue> {
+    match cx.forward_args().new_target() {
+        Some(target) => Ok(target.upcast()),
+        None => Ok(cx.undefined().upcast()),
+    }
+}
+
+// Calls `f()` `n` times with an explicit `undefined` and an empty argument list
+pub fn call_with_empty_args(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+
+    for _ in 0..n {
+        let this = cx.undefined();
+
+        f.exec(&mut cx, this, [])?;
+    }
+
+    Ok(cx.undefined())
+}
+
+// Calls `f()` `n` times with the `call0` fast path
+pub fn call0_loop(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+
+    for _ in 0..n {
+        f.call0(&mut cx)?;
+    }
+
+    Ok(cx.undefined())
+}
+
+pub fn call0_js_function(mut cx: FunctionContext) -> JsResult<JsValue> {
+    cx.argument::<JsFunction>(0)?.call0(&mut cx)
+}
+
+pub fn call1_js_function(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let arg = cx.argument::<JsValue>(1)?;
+
+    f.call1(&mut cx, arg)
+}
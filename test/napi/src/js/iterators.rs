@@ -0,0 +1,85 @@
+use std::{
+    io,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use neon::{error::Error, prelude::*, types::JsIterator};
+
+pub fn collect_iterable(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let iterable: Handle<JsValue> = cx.argument(0)?;
+    let values = JsIterator::from_iterable(&mut cx, iterable)?.collect_vec(&mut cx)?;
+    JsArray::from_slice(&mut cx, &values)
+}
+
+pub fn take_from_iterable(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let iterable: Handle<JsValue> = cx.argument(0)?;
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let mut iter = JsIterator::from_iterable(&mut cx, iterable)?;
+    let mut values = Vec::with_capacity(n);
+
+    while values.len() < n {
+        match iter.next(&mut cx)? {
+            Some(value) => values.push(value),
+            None => break,
+        }
+    }
+
+    iter.close(&mut cx)?;
+
+    JsArray::from_slice(&mut cx, &values)
+}
+
+// Number of `Tracked` iterators that have been dropped
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+// Yields `0..n`, failing at `fail_at`, if any, and records when it is dropped
+struct Tracked {
+    next: u32,
+    n: u32,
+    fail_at: Option<u32>,
+}
+
+impl Iterator for Tracked {
+    type Item = Result<f64, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.n {
+            return None;
+        }
+
+        let i = self.next;
+
+        self.next += 1;
+
+        if Some(i) == self.fail_at {
+            return Some(Err(io::Error::other(format!("failed at {}", i)).into()));
+        }
+
+        Some(Ok(i.into()))
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+// `boxed_range(n, failAt)`
+pub fn boxed_range(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let fail_at = cx
+        .argument_opt(1)
+        .and_then(|v| v.downcast::<JsNumber, _>(&mut cx).ok())
+        .map(|v| v.value(&mut cx) as u32);
+
+    cx.boxed_iterator(Tracked {
+        next: 0,
+        n,
+        fail_at,
+    })
+}
+
+pub fn boxed_range_dropped(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    Ok(cx.number(DROPPED.load(Ordering::SeqCst) as f64))
+}
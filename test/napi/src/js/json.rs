@@ -0,0 +1,44 @@
+use neon::prelude::*;
+
+pub fn json_parse(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let s = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    cx.json_parse(&s)
+}
+
+pub fn json_stringify(mut cx: FunctionContext) -> JsResult<JsString> {
+    let value = cx.argument::<JsValue>(0)?;
+    let json = match cx.argument_opt(1) {
+        Some(indent) => {
+            let indent = indent.downcast_or_throw::<JsNumber, _>(&mut cx)?;
+            let indent = indent.value(&mut cx) as u32;
+
+            cx.json_stringify_pretty(value, indent)?
+        }
+        None => cx.json_stringify(value)?,
+    };
+
+    Ok(cx.string(json))
+}
+
+pub fn build_object_graph(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let obj = cx.empty_object();
+
+    for i in 0..n {
+        let value = cx.number(i);
+        obj.set(&mut cx, format!("key{}", i).as_str(), value)?;
+    }
+
+    Ok(obj)
+}
+
+pub fn build_object_json(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let fields = (0..n)
+        .map(|i| format!("\"key{}\":{}", i, i))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    cx.json_parse(&format!("{{{}}}", fields))
+}
@@ -1,4 +1,7 @@
-use neon::prelude::*;
+use neon::{
+    prelude::*,
+    types::extract::{Lossy, TryIntoJs},
+};
 
 pub fn return_js_number(mut cx: FunctionContext) -> JsResult<JsNumber> {
     Ok(cx.number(9000_f64))
@@ -39,3 +42,43 @@ pub fn accept_and_return_negative_js_number(mut cx: FunctionContext) -> JsResult
     let number: Handle<JsNumber> = cx.argument(0)?;
     Ok(number)
 }
+
+// 64-bit integers are passed as strings so that they reach Rust without rounding
+fn parse_argument<T: std::str::FromStr>(cx: &mut FunctionContext, i: usize) -> NeonResult<T> {
+    let s = cx.argument::<JsString>(i)?.value(cx);
+
+    match s.parse() {
+        Ok(n) => Ok(n),
+        Err(_) => cx.throw_type_error(format!("invalid integer: {}", s)),
+    }
+}
+
+pub fn number_checked_u64(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let n = parse_argument::<u64>(&mut cx, 0)?;
+
+    cx.number_checked(n).or_throw(&mut cx)
+}
+
+pub fn number_checked_i64(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let n = parse_argument::<i64>(&mut cx, 0)?;
+
+    cx.number_checked(n).or_throw(&mut cx)
+}
+
+pub fn usize_into_js(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let n = parse_argument::<usize>(&mut cx, 0)?;
+
+    n.try_into_js(&mut cx)
+}
+
+pub fn u64_into_js_lossy(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let n = parse_argument::<u64>(&mut cx, 0)?;
+
+    Lossy(n).try_into_js(&mut cx)
+}
+
+pub fn int_or_bigint(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let n = parse_argument::<u64>(&mut cx, 0)?;
+
+    Ok(cx.int_or_bigint(n))
+}
@@ -1,6 +1,14 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
-use neon::{prelude::*, types::buffer::TypedArray};
+use neon::{
+    object::{FinalizerGuard, PropertyAttributes},
+    prelude::*,
+    types::buffer::TypedArray,
+};
 
 pub fn return_js_global_object(mut cx: FunctionContext) -> JsResult<JsObject> {
     Ok(cx.global())
@@ -95,3 +103,170 @@ pub fn call_symbol_method(mut cx: FunctionContext) -> JsResult<JsString> {
     let sym: Handle<JsValue> = cx.argument::<JsValue>(1)?;
     obj.call_method_with(&mut cx, sym)?.apply(&mut cx)
 }
+
+pub fn structured_clone(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let value = cx.argument::<JsValue>(0)?;
+    let transfer = match cx.argument_opt(1) {
+        Some(list) => Some(
+            list.downcast_or_throw::<JsArray, _>(&mut cx)?
+                .to_vec(&mut cx)?,
+        ),
+        None => None,
+    };
+
+    cx.structured_clone(value, transfer.as_deref())
+}
+
+pub fn symbol_property_round_trip(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let obj = cx.empty_object();
+    let key = JsSymbol::with_description(&mut cx, "neon");
+    let value = cx.string("hidden");
+
+    obj.set(&mut cx, key, value)?;
+
+    let read: Handle<JsString> = obj.get(&mut cx, key)?;
+    let has = obj.has(&mut cx, key)?;
+    let has = cx.boolean(has);
+    let description = match key.description(&mut cx)? {
+        Some(description) => cx.string(description).upcast::<JsValue>(),
+        None => cx.undefined().upcast(),
+    };
+    let result = cx.empty_array();
+
+    result.set(&mut cx, 0, obj)?;
+    result.set(&mut cx, 1, key)?;
+    result.set(&mut cx, 2, read)?;
+    result.set(&mut cx, 3, has)?;
+    result.set(&mut cx, 4, description)?;
+
+    Ok(result)
+}
+
+pub fn set_large_index(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let value = cx.argument::<JsValue>(1)?;
+    let index = cx.argument::<JsNumber>(2)?.value(&mut cx) as usize;
+
+    obj.set(&mut cx, index, value)?;
+    obj.get_value(&mut cx, index)
+}
+
+pub fn has_index(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let index = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let has = obj.has(&mut cx, index)?;
+
+    Ok(cx.boolean(has))
+}
+
+pub fn has_property(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let key = cx.argument::<JsValue>(1)?;
+    let has = obj.has(&mut cx, key)?;
+
+    Ok(cx.boolean(has))
+}
+
+pub fn delete_property(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let key = cx.argument::<JsValue>(1)?;
+    let deleted = obj.delete(&mut cx, key)?;
+
+    Ok(cx.boolean(deleted))
+}
+
+pub fn delete_index(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let index = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let deleted = obj.delete(&mut cx, index)?;
+
+    Ok(cx.boolean(deleted))
+}
+
+pub fn define_property(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let key = cx.argument::<JsValue>(1)?;
+    let value = cx.argument::<JsValue>(2)?;
+    let attributes = cx.argument::<JsObject>(3)?;
+    let attributes = PropertyAttributes {
+        writable: attributes
+            .get::<JsBoolean, _, _>(&mut cx, "writable")?
+            .value(&mut cx),
+        enumerable: attributes
+            .get::<JsBoolean, _, _>(&mut cx, "enumerable")?
+            .value(&mut cx),
+        configurable: attributes
+            .get::<JsBoolean, _, _>(&mut cx, "configurable")?
+            .value(&mut cx),
+    };
+
+    obj.define_property(&mut cx, key, value, attributes)?;
+
+    Ok(cx.undefined())
+}
+
+pub fn define_index(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let index = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let value = cx.argument::<JsValue>(2)?;
+
+    obj.define_property(&mut cx, index, value, PropertyAttributes::default())?;
+
+    Ok(cx.undefined())
+}
+
+static FINALIZED: AtomicUsize = AtomicUsize::new(0);
+
+type BoxedFinalizerGuard = JsBox<RefCell<Option<FinalizerGuard>>>;
+
+pub fn finalized_count(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    Ok(cx.number(FINALIZED.load(Ordering::SeqCst) as f64))
+}
+
+pub fn add_counting_finalizer(mut cx: FunctionContext) -> JsResult<BoxedFinalizerGuard> {
+    let object = cx.argument::<JsObject>(0)?;
+    let guard = object.add_finalizer(&mut cx, 1, |n| {
+        FINALIZED.fetch_add(n, Ordering::SeqCst);
+    });
+
+    Ok(cx.boxed(RefCell::new(Some(guard))))
+}
+
+pub fn cancel_finalizer(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let guard = cx.argument::<BoxedFinalizerGuard>(0)?;
+    let guard = guard.borrow_mut().take();
+    let cancelled = match guard {
+        Some(guard) => guard.cancel(&mut cx),
+        None => false,
+    };
+
+    Ok(cx.boolean(cancelled))
+}
+
+pub fn finalizer_is_pending(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let guard = cx.argument::<BoxedFinalizerGuard>(0)?;
+    let pending = guard
+        .borrow()
+        .as_ref()
+        .map(|guard| guard.is_pending())
+        .unwrap_or(false);
+
+    Ok(cx.boolean(pending))
+}
+
+pub fn add_context_finalizer(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let object = cx.argument::<JsObject>(0)?;
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+
+    object.add_finalizer_with_context(&mut cx, callback, |mut cx, callback| {
+        let callback = callback.into_inner(&mut cx);
+        let this = cx.undefined();
+        let arg = cx.string("finalized");
+
+        callback.call(&mut cx, this, [arg.upcast()])?;
+
+        Ok(())
+    });
+
+    Ok(cx.undefined())
+}
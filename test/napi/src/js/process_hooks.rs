@@ -0,0 +1,73 @@
+use std::cell::RefCell;
+
+use neon::{event::ListenerGuard, prelude::*};
+
+type BoxedGuard = JsBox<RefCell<Option<ListenerGuard>>>;
+
+pub fn on_uncaught_exception(mut cx: FunctionContext) -> JsResult<BoxedGuard> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let guard = cx.on_uncaught_exception(move |mut cx, err| {
+        callback
+            .to_inner(&mut cx)
+            .call_with(&cx)
+            .arg(err)
+            .exec(&mut cx)
+    })?;
+
+    Ok(cx.boxed(RefCell::new(Some(guard))))
+}
+
+pub fn on_unhandled_rejection(mut cx: FunctionContext) -> JsResult<BoxedGuard> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let guard = cx.on_unhandled_rejection(move |mut cx, reason, promise| {
+        callback
+            .to_inner(&mut cx)
+            .call_with(&cx)
+            .arg(reason)
+            .arg(promise)
+            .exec(&mut cx)
+    })?;
+
+    Ok(cx.boxed(RefCell::new(Some(guard))))
+}
+
+pub fn on_unhandled_rejection_panic(mut cx: FunctionContext) -> JsResult<BoxedGuard> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let guard = cx.on_unhandled_rejection(move |mut cx, reason, _promise| {
+        callback
+            .to_inner(&mut cx)
+            .call_with(&cx)
+            .arg(reason)
+            .exec(&mut cx)?;
+
+        panic!("Hello, Panic!")
+    })?;
+
+    Ok(cx.boxed(RefCell::new(Some(guard))))
+}
+
+pub fn remove_listener_guard(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let guard = cx.argument::<BoxedGuard>(0)?.borrow_mut().take();
+
+    if let Some(guard) = guard {
+        guard.remove(&mut cx)?;
+    }
+
+    Ok(cx.undefined())
+}
+
+pub fn drop_listener_guard(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let guard = cx.argument::<BoxedGuard>(0)?.borrow_mut().take();
+
+    drop(guard);
+
+    Ok(cx.undefined())
+}
+
+pub fn drop_listener_guard_on_thread(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let guard = cx.argument::<BoxedGuard>(0)?.borrow_mut().take();
+
+    std::thread::spawn(move || drop(guard)).join().unwrap();
+
+    Ok(cx.undefined())
+}
@@ -0,0 +1,141 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use neon::{
+    prelude::*,
+    types::{
+        extract::TryIntoJs,
+        proxy::{ArrayView, ProxyHandler},
+    },
+};
+
+// A namespace whose properties are "loaded" on first access and then cached on the target
+pub fn lazy_namespace(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let target = cx.empty_object();
+    let loads = Rc::new(Cell::new(0));
+    let handler = ProxyHandler {
+        get: Some(Box::new(move |mut cx| {
+            let target = cx.argument::<JsObject>(0)?;
+            let key = cx.argument::<JsValue>(1)?;
+
+            if !key.is_a::<JsString, _>(&mut cx) {
+                return Ok(cx.undefined().upcast());
+            }
+
+            let name = key
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+
+            match name.as_str() {
+                "loads" => return Ok(cx.number(loads.get()).upcast()),
+                "broken" => return cx.throw_error("failed to load broken"),
+                _ => {}
+            }
+
+            if let Some(value) = target.get_opt::<JsValue, _, _>(&mut cx, key)? {
+                return Ok(value);
+            }
+
+            loads.set(loads.get() + 1);
+
+            let value = cx.string(format!("plugin:{}", name));
+            target.set(&mut cx, key, value)?;
+
+            Ok(value.upcast())
+        })),
+        has: Some(Box::new(|mut cx| {
+            let key = cx.argument::<JsValue>(1)?.to_string(&mut cx)?;
+            let result = key.value(&mut cx).starts_with("plugin");
+
+            Ok(cx.boolean(result).upcast())
+        })),
+        own_keys: Some(Box::new(|mut cx| {
+            let a = cx.string("a");
+            let b = cx.string("b");
+
+            Ok(JsArray::from_slice(&mut cx, &[a, b])?.upcast())
+        })),
+        ..Default::default()
+    };
+
+    cx.proxy(target, handler)
+}
+
+pub fn proxy_function(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let target: Handle<JsFunction> = cx.argument(0)?;
+    let handler = ProxyHandler {
+        get: Some(Box::new(|mut cx| Ok(cx.string("proxied").upcast()))),
+        ..Default::default()
+    };
+
+    cx.proxy(target, handler)
+}
+
+pub fn revocable_proxy(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let target = cx.empty_object();
+    let handler = ProxyHandler {
+        get: Some(Box::new(|mut cx| Ok(cx.number(42).upcast()))),
+        ..Default::default()
+    };
+
+    let (proxy, revoke) = cx.revocable_proxy(target, handler)?;
+
+    JsArray::from_slice(&mut cx, &[proxy.upcast::<JsValue>(), revoke.upcast()])
+}
+
+pub fn array_view(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let len = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let words = (0..len).map(|i| format!("word{}", i)).collect::<Vec<_>>();
+    let words = cx.boxed(words);
+
+    ArrayView::new(words).build(&mut cx)
+}
+
+// A number that counts how many times it is converted
+#[derive(Clone)]
+pub struct Counted(Arc<Mutex<f64>>, Arc<AtomicU32>);
+
+impl Finalize for Counted {}
+
+impl<'cx> TryIntoJs<'cx> for Counted {
+    type Value = JsNumber;
+
+    fn try_into_js<C: Context<'cx>>(self, cx: &mut C) -> JsResult<'cx, JsNumber> {
+        self.1.fetch_add(1, Ordering::SeqCst);
+
+        let n = *self.0.lock().unwrap();
+
+        Ok(cx.number(n))
+    }
+}
+
+// Returns `[view, conversions]`, where `conversions()` counts converted elements
+pub fn writable_array_view(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let len = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let conversions = Arc::new(AtomicU32::new(0));
+    let numbers = (0..len)
+        .map(|i| Counted(Arc::new(Mutex::new(i as f64)), conversions.clone()))
+        .collect::<Vec<_>>();
+    let numbers = cx.boxed(numbers);
+    let view = ArrayView::new(numbers)
+        .setter(|cx, numbers, i, value| {
+            let value = value.downcast_or_throw::<JsNumber, _>(cx)?.value(cx);
+
+            *numbers[i].0.lock().unwrap() = value;
+
+            Ok(())
+        })
+        .build(&mut cx)?;
+
+    let conversions = JsFunction::new(&mut cx, move |mut cx| {
+        Ok(cx.number(conversions.load(Ordering::SeqCst)))
+    })?;
+
+    JsArray::from_slice(&mut cx, &[view.upcast::<JsValue>(), conversions.upcast()])
+}
@@ -0,0 +1,54 @@
+use neon::{
+    prelude::*,
+    registry::{self, Function, Interface},
+    types::{buffer::TypedArray, JsFloat64Array},
+};
+
+pub fn export_registered(cx: &mut ModuleContext) -> NeonResult<()> {
+    Interface::new("Point")
+        .field_of::<f64>("x")
+        .field_of::<Option<String>>("label")
+        .register();
+
+    Function::new("registry_sum")
+        .param_of::<Handle<JsFloat64Array>>("nums")
+        .returns_of::<f64>()
+        .export(cx, registry_sum)?;
+
+    Function::new("registry_origin")
+        .returns("Point")
+        .export(cx, registry_origin)?;
+
+    // Declared as returning the `JsString` returned by the function
+    Function::new("registry_greet")
+        .param_of::<&str>("name")
+        .export(cx, registry_greet)?;
+
+    cx.export_function("type_declarations", type_declarations)
+}
+
+fn registry_sum(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let nums = cx.argument::<JsFloat64Array>(0)?;
+    let sum = nums.as_slice(&cx).iter().sum::<f64>();
+
+    Ok(cx.number(sum))
+}
+
+fn registry_origin(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let point = cx.empty_object();
+    let x = cx.number(0);
+
+    point.set(&mut cx, "x", x)?;
+
+    Ok(point)
+}
+
+fn registry_greet(mut cx: FunctionContext) -> JsResult<JsString> {
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    Ok(cx.string(format!("Hello, {}!", name)))
+}
+
+fn type_declarations(mut cx: FunctionContext) -> JsResult<JsString> {
+    Ok(cx.string(registry::type_declarations()))
+}
@@ -0,0 +1,123 @@
+use std::cell::RefCell;
+
+use neon::{
+    handle::{RootKey, RootSet},
+    prelude::*,
+};
+
+type BoxedRootSet = JsBox<RefCell<RootSet>>;
+
+pub struct Key(RootKey);
+
+impl Finalize for Key {}
+
+pub fn root_set_new(mut cx: FunctionContext) -> JsResult<BoxedRootSet> {
+    let set = RootSet::new(&mut cx);
+
+    Ok(cx.boxed(RefCell::new(set)))
+}
+
+pub fn root_set_insert(mut cx: FunctionContext) -> JsResult<JsBox<Key>> {
+    let set = cx.argument::<BoxedRootSet>(0)?;
+    let value = cx.argument::<JsValue>(1)?;
+    let key = set.borrow_mut().insert(&mut cx, value)?;
+
+    Ok(cx.boxed(Key(key)))
+}
+
+pub fn root_set_get(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let set = cx.argument::<BoxedRootSet>(0)?;
+    let key = cx.argument::<JsBox<Key>>(1)?.0;
+    let value = set.borrow().get(&mut cx, key)?;
+
+    Ok(value.unwrap_or_else(|| cx.null().upcast()))
+}
+
+pub fn root_set_remove(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let set = cx.argument::<BoxedRootSet>(0)?;
+    let key = cx.argument::<JsBox<Key>>(1)?.0;
+    let value = set.borrow_mut().remove(&mut cx, key)?;
+
+    Ok(value.unwrap_or_else(|| cx.null().upcast()))
+}
+
+pub fn root_set_contains(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let set = cx.argument::<BoxedRootSet>(0)?;
+    let key = cx.argument::<JsBox<Key>>(1)?.0;
+    let contains = set.borrow().contains(key);
+
+    Ok(cx.boolean(contains))
+}
+
+pub fn root_set_len(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let set = cx.argument::<BoxedRootSet>(0)?;
+    let len = set.borrow().len();
+
+    Ok(cx.number(len as f64))
+}
+
+// Retains every element of an array with an individual `Root`
+pub fn retain_with_roots(mut cx: FunctionContext) -> JsResult<JsBox<Vec<Root<JsObject>>>> {
+    let values = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+    let roots = values
+        .into_iter()
+        .map(|v| Ok(v.downcast_or_throw::<JsObject, _>(&mut cx)?.root(&mut cx)))
+        .collect::<NeonResult<Vec<_>>>()?;
+
+    Ok(cx.boxed(roots))
+}
+
+// Retains every element of an array in a single `RootSet`
+pub fn retain_with_root_set(mut cx: FunctionContext) -> JsResult<BoxedRootSet> {
+    let values = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+    let mut set = RootSet::new(&mut cx);
+
+    for v in values {
+        set.insert(&mut cx, v)?;
+    }
+
+    Ok(cx.boxed(RefCell::new(set)))
+}
+
+// Round-trips a `Root` through a raw `napi_ref` on another thread, as if it were
+// handed to another Node-API library
+pub fn root_napi_ref_round_trip(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let raw = cx
+        .argument::<JsObject>(0)?
+        .root(&mut cx)
+        .into_napi_ref(&mut cx) as usize;
+
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        channel.send(move |mut cx| {
+            let root = unsafe { Root::<JsObject>::from_napi_ref(&mut cx, raw as *mut _) };
+            let value = root.into_inner(&mut cx);
+
+            callback
+                .into_inner(&mut cx)
+                .call_with(&cx)
+                .arg(value)
+                .exec(&mut cx)
+        })
+    });
+
+    Ok(cx.undefined())
+}
+
+// Converts a clone of a `Root` to a raw `napi_ref` and back. Both roots share the
+// reference and must each release one count.
+pub fn root_napi_ref_clone(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let root = cx.argument::<JsObject>(0)?.root(&mut cx);
+    let raw = root.clone(&mut cx).into_napi_ref(&mut cx);
+    let clone = unsafe { Root::<JsObject>::from_napi_ref(&mut cx, raw) };
+    let values = cx.empty_array();
+    let a = clone.into_inner(&mut cx);
+    let b = root.into_inner(&mut cx);
+
+    values.set(&mut cx, 0, a)?;
+    values.set(&mut cx, 1, b)?;
+
+    Ok(values)
+}
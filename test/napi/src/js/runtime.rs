@@ -0,0 +1,162 @@
+use std::time::{Duration, Instant};
+
+use neon::prelude::*;
+
+pub fn runtime_features(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let features = neon::runtime::features(&mut cx);
+    let obj = cx.empty_object();
+    let flags = [
+        ("externalBuffers", features.external_buffers),
+        ("threadsafeFunctions", features.threadsafe_functions),
+        ("date", features.date),
+        ("bigint", features.bigint),
+        ("instanceData", features.instance_data),
+        ("detachArraybuffer", features.detach_arraybuffer),
+        ("typeTags", features.type_tags),
+        ("objectFreeze", features.object_freeze),
+    ];
+
+    let napi_version = cx.number(features.napi_version);
+    obj.set(&mut cx, "napiVersion", napi_version)?;
+
+    for (key, flag) in flags {
+        let flag = cx.boolean(flag);
+        obj.set(&mut cx, key, flag)?;
+    }
+
+    Ok(obj)
+}
+
+pub fn module_filename(mut cx: FunctionContext) -> JsResult<JsValue> {
+    match cx.module_filename() {
+        Some(filename) => Ok(cx.string(filename).upcast()),
+        None => Ok(cx.undefined().upcast()),
+    }
+}
+
+pub fn module_dir(mut cx: FunctionContext) -> JsResult<JsValue> {
+    match cx.module_dir() {
+        Some(dir) => Ok(cx.string(dir.to_string_lossy()).upcast()),
+        None => Ok(cx.undefined().upcast()),
+    }
+}
+
+pub fn require_path_join(mut cx: FunctionContext) -> JsResult<JsString> {
+    let path = cx.require::<JsObject>("node:path")?;
+    let join = path.get::<JsFunction, _, _>(&mut cx, "join")?;
+    let a = cx.argument::<JsString>(0)?;
+    let b = cx.argument::<JsString>(1)?;
+
+    join.call_with(&cx).this(path).arg(a).arg(b).apply(&mut cx)
+}
+
+pub fn require_module(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let specifier = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    cx.require(&specifier)
+}
+
+pub fn require_event_emitter(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    cx.require("node:events")
+}
+
+pub fn import_module(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let specifier = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    cx.import(&specifier)
+}
+
+pub fn process_env(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    match cx.process_env(&name)? {
+        Some(value) => Ok(cx.string(value).upcast()),
+        None => Ok(cx.undefined().upcast()),
+    }
+}
+
+pub fn process_env_all(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let vars = cx.process_env_all()?;
+    let obj = cx.empty_object();
+
+    for (name, value) in vars {
+        let value = cx.string(value);
+        obj.set(&mut cx, name.as_str(), value)?;
+    }
+
+    Ok(obj)
+}
+
+pub fn process_info(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let pid = cx.process_pid()?;
+    let argv = cx.process_argv()?;
+    let obj = cx.empty_object();
+    let pid = cx.number(pid);
+    let args = cx.empty_array();
+
+    for (i, arg) in argv.into_iter().enumerate() {
+        let arg = cx.string(arg);
+        args.set(&mut cx, i as u32, arg)?;
+    }
+
+    obj.set(&mut cx, "pid", pid)?;
+    obj.set(&mut cx, "argv", args)?;
+
+    Ok(obj)
+}
+
+pub fn performance_now(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let now = cx.performance_now()?;
+
+    Ok(cx.number(now))
+}
+
+pub fn time_origin(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let origin = cx.time_origin()?;
+
+    Ok(cx.number(origin))
+}
+
+// Converts an `Instant` from `ago` milliseconds in the past
+pub fn performance_timestamp(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let ago = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let instant = Instant::now() - Duration::from_secs_f64(ago / 1000.0);
+    let timestamp = cx.to_performance_timestamp(instant)?;
+
+    Ok(cx.number(timestamp))
+}
+
+pub fn performance_timestamp_from_thread(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+
+        let instant = Instant::now();
+
+        channel.send(move |mut cx| {
+            let timestamp = cx.to_performance_timestamp(instant)?;
+            let timestamp = cx.number(timestamp);
+
+            callback
+                .into_inner(&mut cx)
+                .call_with(&cx)
+                .arg(timestamp)
+                .exec(&mut cx)
+        });
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn set_performance_calibration_interval(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let interval = cx.argument::<JsNumber>(0)?.value(&mut cx);
+
+    neon::performance::set_calibration_interval(
+        &mut cx,
+        Duration::from_secs_f64(interval / 1000.0),
+    )?;
+
+    Ok(cx.undefined())
+}
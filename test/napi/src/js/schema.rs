@@ -0,0 +1,43 @@
+use std::sync::OnceLock;
+
+use neon::{
+    prelude::*,
+    schema::{Kind, Schema},
+};
+
+fn options_schema() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+
+    SCHEMA.get_or_init(|| {
+        Schema::object()
+            .field("retries", Kind::Number.optional())
+            .field("mode", Kind::StringEnum(&["fast", "safe"]))
+            .field("verbose", Kind::Boolean.optional())
+            .field("tags", Kind::Array.optional())
+            .field("name", Kind::String)
+    })
+}
+
+pub fn schema_extract_options(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let options = cx.argument::<JsValue>(0)?;
+    let options = options_schema().extract(&mut cx, options)?;
+    let result = cx.empty_object();
+
+    let retries = options.number("retries").unwrap_or(3.0);
+    let retries = cx.number(retries);
+    let mode = cx.string(options.string("mode").unwrap());
+    let verbose = cx.boolean(options.boolean("verbose").unwrap_or(false));
+    let name = cx.string(options.string("name").unwrap());
+    let tags = match options.value("tags") {
+        Some(tags) => tags,
+        None => cx.empty_array().upcast(),
+    };
+
+    result.set(&mut cx, "retries", retries)?;
+    result.set(&mut cx, "mode", mode)?;
+    result.set(&mut cx, "verbose", verbose)?;
+    result.set(&mut cx, "name", name)?;
+    result.set(&mut cx, "tags", tags)?;
+
+    Ok(result)
+}
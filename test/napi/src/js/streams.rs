@@ -0,0 +1,147 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{mpsc, Arc, Mutex},
+};
+
+use neon::{prelude::*, stream::WritableOptions};
+
+// Creates a readable stream of `total` bytes, counting each byte, in chunks of
+// `chunk_size`. Returns the stream and a function returning the number of pulls.
+pub fn stream_readable(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let total = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let chunk_size = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let pulls = Rc::new(Cell::new(0));
+    let mut offset = 0;
+
+    let stream = {
+        let pulls = pulls.clone();
+
+        neon::stream::readable(&mut cx, move |_size| {
+            pulls.set(pulls.get() + 1);
+
+            if offset >= total {
+                return Ok::<_, String>(None);
+            }
+
+            let end = (offset + chunk_size).min(total);
+            let chunk = (offset..end).map(|i| i as u8).collect();
+
+            offset = end;
+
+            Ok(Some(chunk))
+        })?
+    };
+
+    let pulls = cx.function(move |mut cx| Ok(cx.number(pulls.get())))?;
+    let result = cx.empty_object();
+
+    result.set(&mut cx, "stream", stream)?;
+    result.set(&mut cx, "pulls", pulls)?;
+
+    Ok(result)
+}
+
+pub fn stream_readable_error(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let msg = cx.argument::<JsString>(0)?.value(&mut cx);
+    let mut chunks = 0;
+
+    neon::stream::readable(&mut cx, move |_size| {
+        chunks += 1;
+
+        if chunks > 1 {
+            return Err(msg.clone());
+        }
+
+        Ok(Some(vec![1, 2, 3]))
+    })
+}
+
+// Calls `callback` with an error message, or the number of bytes written and their sum
+fn finish_with(
+    callback: Root<JsFunction>,
+    summary: impl Fn() -> (usize, u32) + 'static,
+) -> impl for<'b> FnOnce(&mut FunctionContext<'b>, Result<(), String>) -> NeonResult<()> {
+    move |cx, result| {
+        let callback = callback.into_inner(cx);
+        let this = cx.undefined();
+        let args = match result {
+            Ok(()) => {
+                let (len, sum) = summary();
+                let err = cx.null().upcast::<JsValue>();
+                let len = cx.number(len as f64).upcast();
+                let sum = cx.number(sum).upcast();
+
+                vec![err, len, sum]
+            }
+            Err(msg) => vec![cx.string(msg).upcast()],
+        };
+
+        callback.exec(cx, this, args)
+    }
+}
+
+pub fn stream_writable(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let fail_after = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let summary = Rc::new(Cell::new((0, 0u32)));
+    let on_finish = {
+        let summary = summary.clone();
+
+        finish_with(callback, move || summary.get())
+    };
+
+    neon::stream::writable(
+        &mut cx,
+        WritableOptions::new(),
+        move |chunk| {
+            let (len, sum) = summary.get();
+            let len = len + chunk.len();
+
+            if len > fail_after {
+                return Err("Too much data");
+            }
+
+            let sum = chunk
+                .iter()
+                .fold(sum, |sum, &b| sum.wrapping_add(u32::from(b)));
+
+            summary.set((len, sum));
+
+            Ok(())
+        },
+        on_finish,
+    )
+}
+
+pub fn stream_writable_with_ack(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let high_water_mark = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let summary = Arc::new(Mutex::new((0, 0u32)));
+    let (tx, rx) = mpsc::channel::<(Vec<u8>, neon::stream::WriteAck)>();
+
+    // Consume chunks on another thread
+    {
+        let summary = summary.clone();
+
+        std::thread::spawn(move || {
+            for (chunk, ack) in rx {
+                let mut summary = summary.lock().unwrap();
+
+                summary.0 += chunk.len();
+                summary.1 = chunk
+                    .iter()
+                    .fold(summary.1, |sum, &b| sum.wrapping_add(u32::from(b)));
+
+                ack.ack(Ok::<_, String>(()));
+            }
+        });
+    }
+
+    neon::stream::writable_with_ack(
+        &mut cx,
+        WritableOptions::new().high_water_mark(high_water_mark),
+        move |chunk, ack| tx.send((chunk, ack)).unwrap(),
+        finish_with(callback, move || *summary.lock().unwrap()),
+    )
+}
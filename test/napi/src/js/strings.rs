@@ -1,4 +1,8 @@
-use neon::{prelude::*, reflect::eval};
+use neon::{
+    prelude::*,
+    reflect::eval,
+    types::{buffer::TypedArray, JsStringBuilder},
+};
 
 pub fn return_js_string(mut cx: FunctionContext) -> JsResult<JsString> {
     Ok(cx.string("hello node"))
@@ -23,3 +27,78 @@ pub fn run_string_as_script(mut cx: FunctionContext) -> JsResult<JsValue> {
     let string_script = cx.argument::<JsString>(0)?;
     eval(&mut cx, string_script)
 }
+
+pub fn encode_base64(mut cx: FunctionContext) -> JsResult<JsString> {
+    let buf = cx.argument::<JsBuffer>(0)?;
+    let data = buf.as_slice(&cx).to_vec();
+
+    Ok(cx.string_base64(data))
+}
+
+pub fn encode_hex(mut cx: FunctionContext) -> JsResult<JsString> {
+    let buf = cx.argument::<JsBuffer>(0)?;
+    let data = buf.as_slice(&cx).to_vec();
+
+    Ok(cx.string_hex(data))
+}
+
+pub fn decode_base64(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let data = cx.argument::<JsString>(0)?.decode_base64(&mut cx)?;
+
+    JsBuffer::from_slice(&mut cx, &data)
+}
+
+pub fn decode_hex(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let data = cx.argument::<JsString>(0)?.decode_hex(&mut cx)?;
+
+    JsBuffer::from_slice(&mut cx, &data)
+}
+
+// Each part is either a string, appended as UTF-8, or an array of UTF-16 code units
+pub fn build_string(mut cx: FunctionContext) -> JsResult<JsString> {
+    let parts = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+    let mut builder = JsStringBuilder::new(&mut cx);
+
+    for part in parts {
+        if let Ok(s) = part.downcast::<JsString, _>(&mut cx) {
+            let s = s.value(&mut cx);
+
+            builder.push_str(&mut cx, &s)?;
+        } else {
+            let units = part
+                .downcast_or_throw::<JsArray, _>(&mut cx)?
+                .to_vec_f64(&mut cx)?
+                .into_iter()
+                .map(|unit| unit as u16)
+                .collect::<Vec<_>>();
+
+            builder.push_utf16(&mut cx, &units)?;
+        }
+    }
+
+    builder.finish(&mut cx)
+}
+
+pub fn repeat_string_builder(mut cx: FunctionContext) -> JsResult<JsString> {
+    let fragment = cx.argument::<JsString>(0)?.value(&mut cx);
+    let count = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let mut builder = JsStringBuilder::new(&mut cx);
+
+    for _ in 0..count {
+        builder.push_str(&mut cx, &fragment)?;
+    }
+
+    builder.finish(&mut cx)
+}
+
+pub fn repeat_string_naive(mut cx: FunctionContext) -> JsResult<JsString> {
+    let fragment = cx.argument::<JsString>(0)?.value(&mut cx);
+    let count = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let mut s = String::new();
+
+    for _ in 0..count {
+        s.push_str(&fragment);
+    }
+
+    Ok(cx.string(s))
+}
@@ -0,0 +1,111 @@
+use neon::{
+    prelude::*,
+    test::{assert_js_eq, diff, expect_throw, record_calls},
+    types::extract::TryIntoJs,
+};
+use serde_json::json;
+
+pub fn js_value_diff(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let value = cx.argument::<JsValue>(0)?;
+    let expected = cx.argument::<JsString>(1)?.value(&mut cx);
+    let expected =
+        serde_json::from_str(&expected).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    match diff(&mut cx, value, &expected)? {
+        Some(diff) => Ok(cx.string(diff).upcast()),
+        None => Ok(cx.null().upcast()),
+    }
+}
+
+pub fn assert_js_eq_json(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let value = cx.argument::<JsValue>(0)?;
+    let expected = cx.argument::<JsString>(1)?.value(&mut cx);
+    let expected =
+        serde_json::from_str(&expected).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    assert_js_eq(&mut cx, value, &expected)?;
+
+    Ok(cx.undefined())
+}
+
+// Returns the message of the exception thrown by `f`
+pub fn expect_throw_message(mut cx: FunctionContext) -> JsResult<JsString> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let err = expect_throw(&mut cx, |cx| f.call_with(cx).exec(cx));
+    let message = err.message(&mut cx)?;
+
+    Ok(cx.string(message))
+}
+
+// Passes a recording function to `f` and returns the recorded calls as
+// `{ this, args }` objects
+pub fn recorded_calls(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let (callback, calls) = record_calls(&mut cx)?;
+
+    f.call_with(&cx).arg(callback).exec(&mut cx)?;
+
+    let result = cx.empty_array();
+
+    for i in 0..calls.len() {
+        let call = cx.empty_object();
+        let this = calls.this(&mut cx, i);
+        let args = calls.args(&mut cx, i);
+
+        call.set(&mut cx, "this", this)?;
+        call.set(&mut cx, "args", args)?;
+        result.set(&mut cx, i as u32, call)?;
+    }
+
+    calls.clear(&mut cx);
+
+    Ok(result)
+}
+
+// Uses every helper from Rust, as an addon's own test functions would
+pub fn test_support_self_test(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let point = (1.0, "a", true).try_into_js(&mut cx)?;
+
+    assert_js_eq(&mut cx, point, &json!([1.0, "a", true]))?;
+
+    let err = expect_throw(&mut cx, |cx| cx.throw_range_error::<_, ()>("out of range"));
+
+    assert!(err.is_a::<JsError, _>(&mut cx));
+    assert_eq!(err.message(&mut cx)?, "out of range");
+
+    let err = expect_throw(&mut cx, |cx| {
+        let value = cx.number(42);
+
+        cx.throw::<_, ()>(value)
+    });
+
+    assert!(!err.is_a::<JsError, _>(&mut cx));
+    assert_eq!(err.message(&mut cx)?, "42");
+
+    let (callback, calls) = record_calls(&mut cx)?;
+
+    assert!(calls.is_empty());
+
+    callback.call_with(&cx).arg(point).exec(&mut cx)?;
+    let (a, b) = (cx.number(1), cx.number(2));
+
+    callback.call_with(&cx).args((a, b)).exec(&mut cx)?;
+
+    assert_eq!(calls.len(), 2);
+
+    let args = calls.args(&mut cx, 0);
+
+    assert_js_eq(&mut cx, args, &json!([[1.0, "a", true]]))?;
+
+    let arg = calls.arg(&mut cx, 1, 1);
+    let missing = calls.arg(&mut cx, 1, 2);
+
+    assert_js_eq(&mut cx, arg, &json!(2))?;
+    assert!(missing.is_a::<JsUndefined, _>(&mut cx));
+
+    calls.clear(&mut cx);
+
+    assert!(calls.is_empty());
+
+    Ok(cx.undefined())
+}
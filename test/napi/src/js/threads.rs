@@ -1,6 +1,22 @@
-use std::{cell::RefCell, sync::Arc, time::Duration};
-
-use neon::{prelude::*, types::buffer::TypedArray};
+use std::{
+    cell::RefCell,
+    ffi::CStr,
+    os::raw::c_char,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::Duration,
+};
+
+use neon::{
+    context::FinalizeContext,
+    event::{Callback, Emitter, TaskScope, ThreadsafeFunction},
+    handle::{set_drop_queue_policy, DropQueuePolicy, GuardedRoot},
+    prelude::*,
+    types::panic::downcast_panic,
+    types::{buffer::TypedArray, Deferred},
+};
 
 pub fn useless_root(mut cx: FunctionContext) -> JsResult<JsObject> {
     let object = cx.argument::<JsObject>(0)?;
@@ -76,16 +92,17 @@ impl AsyncGreeter {
 }
 
 impl Finalize for AsyncGreeter {
-    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+    fn finalize_gc(self, cx: &mut FinalizeContext) {
         let Self {
             callback, shutdown, ..
         } = self;
 
+        // JavaScript cannot be called during garbage collection
         if let Some(shutdown) = shutdown {
-            let _ = shutdown.into_inner(cx).call_with(cx).exec(cx);
+            cx.defer(move |mut cx| shutdown.into_inner(&mut cx).call_with(&cx).exec(&mut cx));
         }
 
-        callback.drop(cx);
+        cx.drop_root(callback);
     }
 }
 
@@ -129,6 +146,52 @@ pub fn leak_channel(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     Ok(cx.undefined())
 }
 
+struct BoxedChannel {
+    _channel: Channel,
+}
+
+impl Finalize for BoxedChannel {}
+
+// Creates `n` objects that each hold a channel, with its own queue if `isolated`
+pub fn channel_objects(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let isolated = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+    let objects = cx.empty_array();
+
+    for i in 0..n {
+        let channel = if isolated {
+            cx.new_channel()
+        } else {
+            cx.channel()
+        };
+
+        let object = cx.boxed(BoxedChannel { _channel: channel });
+
+        objects.set(&mut cx, i, object)?;
+    }
+
+    Ok(objects)
+}
+
+// Unreferences one clone of the shared channel and calls `callback` on another
+// clone after a delay
+pub fn channel_unref_clone(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let mut unreferenced = cx.channel();
+    let channel = cx.channel();
+
+    unreferenced.unref(&mut cx);
+
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+
+        channel.send(move |mut cx| callback.into_inner(&mut cx).call_with(&cx).exec(&mut cx));
+        drop(unreferenced);
+    });
+
+    Ok(cx.undefined())
+}
+
 pub fn drop_global_queue(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     struct Wrapper {
         callback: Option<Root<JsFunction>>,
@@ -168,6 +231,213 @@ pub fn drop_global_queue(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     Ok(cx.undefined())
 }
 
+pub fn set_drop_policy(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let policy = match cx.argument::<JsString>(0)?.value(&mut cx).as_str() {
+        "leak" => DropQueuePolicy::Leak,
+        "log" => DropQueuePolicy::Log,
+        "fatal" => DropQueuePolicy::FatalError,
+        policy => return cx.throw_type_error(format!("Unknown policy: {}", policy)),
+    };
+
+    set_drop_queue_policy(policy);
+
+    Ok(cx.undefined())
+}
+
+static FATAL_HOOK_PATH: OnceLock<String> = OnceLock::new();
+
+extern "C" fn write_fatal_error(location: *const c_char, message: *const c_char) {
+    let (location, message) = unsafe { (CStr::from_ptr(location), CStr::from_ptr(message)) };
+    let contents = format!(
+        "{}\n{}",
+        location.to_string_lossy(),
+        message.to_string_lossy()
+    );
+
+    if let Some(path) = FATAL_HOOK_PATH.get() {
+        let _ = std::fs::write(path, contents);
+    }
+}
+
+// Registers a fatal error hook that writes the location and message to `path`
+pub fn set_fatal_hook_file(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let path = cx.argument::<JsString>(0)?.value(&mut cx);
+    let _ = FATAL_HOOK_PATH.set(path);
+
+    Ok(cx.boolean(neon::set_fatal_hook(write_fatal_error)))
+}
+
+// Drops `count` roots from `threads` threads, slowly enough to race with the
+// environment shutting down
+pub fn drop_roots_from_threads(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let count = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let threads = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let mut roots = (0..count)
+        .map(|_| cx.empty_object().root(&mut cx))
+        .collect::<Vec<_>>();
+
+    for i in 0..threads {
+        let chunk = roots.split_off(roots.len() - roots.len() / (threads - i));
+
+        std::thread::spawn(move || {
+            for root in chunk {
+                drop(root);
+                std::thread::sleep(Duration::from_micros(50));
+            }
+        });
+    }
+
+    Ok(cx.undefined())
+}
+
+// Sends a closure that releases a root when dropped; with `try_send`, the first
+// failure to send is reported to stderr
+struct SendOnDrop {
+    channel: Channel,
+    root: Option<Root<JsObject>>,
+    try_send: bool,
+}
+
+static SEND_ON_DROP_FAILED: AtomicBool = AtomicBool::new(false);
+
+impl Finalize for SendOnDrop {}
+
+impl Drop for SendOnDrop {
+    fn drop(&mut self) {
+        let root = self.root.take();
+        let f = move |mut cx: TaskContext| {
+            if let Some(root) = root {
+                root.drop(&mut cx);
+            }
+
+            Ok(())
+        };
+
+        if !self.try_send {
+            self.channel.send(f);
+        } else if self.channel.try_send(f).is_err()
+            && !SEND_ON_DROP_FAILED.swap(true, Ordering::Relaxed)
+        {
+            eprintln!("SendOnDrop failed to send");
+        }
+    }
+}
+
+// Returns `count` boxes that send when finalized, and drops as many from `threads`
+// threads; `mode` is either "send" or "try_send"
+pub fn send_on_drop(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let count = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let threads = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let try_send = cx.argument::<JsString>(2)?.value(&mut cx) == "try_send";
+    let mut channel = cx.channel();
+
+    channel.unref(&mut cx);
+
+    let mut values = (0..count * 2)
+        .map(|_| SendOnDrop {
+            channel: channel.clone(),
+            root: Some(cx.empty_object().root(&mut cx)),
+            try_send,
+        })
+        .collect::<Vec<_>>();
+
+    let boxes = cx.empty_array();
+
+    for (i, value) in values.split_off(count).into_iter().enumerate() {
+        let value = cx.boxed(value);
+
+        boxes.set(&mut cx, i as u32, value)?;
+    }
+
+    for i in 0..threads {
+        let chunk = values.split_off(values.len() - values.len() / (threads - i));
+
+        std::thread::spawn(move || {
+            for value in chunk {
+                drop(value);
+                std::thread::sleep(Duration::from_micros(50));
+            }
+        });
+    }
+
+    Ok(boxes)
+}
+
+// Roots `callback` with a cancellation that calls it with an "aborted" error
+fn guarded_callback(
+    cx: &mut FunctionContext,
+    callback: Handle<JsFunction>,
+) -> GuardedRoot<JsFunction> {
+    callback.root(cx).on_unconsumed(|cx, callback| {
+        let err = cx.error("aborted")?;
+
+        callback.call1(cx, err)?;
+
+        Ok(())
+    })
+}
+
+// Drops `count` guarded roots of `callback` from `threads` threads without consuming
+// them, slowly enough to race with the environment shutting down
+pub fn drop_guarded_roots_from_threads(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let count = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let threads = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let callback = cx.argument::<JsFunction>(2)?;
+    let mut roots = (0..count)
+        .map(|_| guarded_callback(&mut cx, callback))
+        .collect::<Vec<_>>();
+
+    for i in 0..threads {
+        let chunk = roots.split_off(roots.len() - roots.len() / (threads - i));
+
+        std::thread::spawn(move || {
+            for root in chunk {
+                drop(root);
+                std::thread::sleep(Duration::from_micros(50));
+            }
+        });
+    }
+
+    Ok(cx.undefined())
+}
+
+// Calls `callback()` from a channel, consuming the guarded root
+pub fn guarded_root_consume(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?;
+    let callback = guarded_callback(&mut cx, callback);
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        channel.send(move |mut cx| {
+            callback.into_inner(&mut cx).call0(&mut cx)?;
+
+            Ok(())
+        })
+    });
+
+    Ok(cx.undefined())
+}
+
+// Cancels the guarded root immediately, calling `callback(err)` before returning
+pub fn guarded_root_cancel(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?;
+
+    guarded_callback(&mut cx, callback).cancel(&mut cx)?;
+
+    Ok(cx.undefined())
+}
+
+// Disarms the guarded root and drops the plain root
+pub fn guarded_root_disarm(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?;
+
+    guarded_callback(&mut cx, callback)
+        .into_root()
+        .drop(&mut cx);
+
+    Ok(cx.undefined())
+}
+
 pub fn channel_join(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     // Function to fetch a message for processing
     let get_message = cx.argument::<JsFunction>(0)?.root(&mut cx);
@@ -221,6 +491,84 @@ pub fn sum(mut cx: FunctionContext) -> JsResult<JsPromise> {
     Ok(promise)
 }
 
+// Sums on the worker pool, then resolves with the result of calling `then` with the sum
+pub fn sum_then(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let nums = cx.argument::<JsTypedArray<f64>>(0)?.as_slice(&cx).to_vec();
+    let then = cx.argument::<JsFunction>(1)?.root(&mut cx);
+
+    let promise = cx
+        .task(move || nums.into_iter().sum())
+        .promise(move |mut cx, n: f64| {
+            let then = then.into_inner(&mut cx);
+            let n = cx.number(n);
+
+            then.call_with(&cx).arg(n).apply::<JsValue, _>(&mut cx)
+        });
+
+    Ok(promise)
+}
+
+// Resolves with the squares of `0..n`, alternating between worker threads and the
+// JavaScript thread; later tickets complete first
+pub fn task_scope_squares(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+    let scope = TaskScope::new(channel, deferred, |mut cx, squares: Vec<u32>| {
+        let arr = cx.empty_array();
+
+        for (i, square) in squares.into_iter().enumerate() {
+            let square = cx.number(square);
+
+            arr.set(&mut cx, i as u32, square)?;
+        }
+
+        Ok(arr)
+    });
+
+    for i in 0..n {
+        if i % 2 == 0 {
+            scope.spawn(move || {
+                std::thread::sleep(Duration::from_millis(u64::from(n - i) * 5));
+                i * i
+            });
+        } else {
+            scope.send(move |_| Ok(i * i));
+        }
+    }
+
+    Ok(promise)
+}
+
+// Rejects after the first ticket fails by panicking or throwing, depending on `kind`;
+// tickets started after the failure would call `callback`
+pub fn task_scope_fail(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let kind = cx.argument::<JsString>(0)?.value(&mut cx);
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+    let scope = TaskScope::new(channel, deferred, |mut cx, _: Vec<()>| Ok(cx.undefined()));
+
+    match kind.as_str() {
+        "panic" => scope.spawn(|| panic!("ticket panicked")),
+        _ => scope.send(|mut cx| cx.throw_error("ticket threw")),
+    }
+
+    std::thread::spawn(move || {
+        while !scope.is_failed() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        scope.spawn(|| panic!("cancelled ticket was called"));
+        scope.send(move |mut cx| {
+            callback.into_inner(&mut cx).call_with(&cx).exec(&mut cx)?;
+            Ok(())
+        });
+    });
+
+    Ok(promise)
+}
+
 pub fn sum_manual_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
     let nums = cx.argument::<JsTypedArray<f64>>(0)?.as_slice(&cx).to_vec();
 
@@ -251,6 +599,359 @@ pub fn sum_rust_thread(mut cx: FunctionContext) -> JsResult<JsPromise> {
     Ok(promise)
 }
 
+// Settles a promise with `n`, immediately if possible, falling back to the channel.
+// With `queue_first`, a closure is sent on the channel before settling.
+pub fn deferred_settle_now(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let queue_first = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+    // A new queue, so that closures sent by other tests are not queued ahead
+    let channel = Channel::new(&mut cx);
+
+    // Dropping a referenced clone does not queue a closure to `unref` the channel
+    drop(channel.clone());
+    let (deferred, promise) = cx.promise();
+
+    if queue_first {
+        channel.send(|_| Ok(()));
+    }
+
+    settle_now_or_with(&channel, deferred, n);
+
+    Ok(promise)
+}
+
+// Settles a promise with `n` on the module's channel, immediately if `now` and
+// possible; for comparing the latency of both paths
+pub fn deferred_settle_module_channel(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let now = cx.argument::<JsBoolean>(1)?.value(&mut cx);
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    if now {
+        settle_now_or_with(&channel, deferred, n);
+    } else {
+        deferred.settle_with(&channel, move |mut cx| Ok(cx.number(n)));
+    }
+
+    Ok(promise)
+}
+
+// Like `deferred_settle_now`, but from another thread
+pub fn deferred_settle_now_from_thread(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    std::thread::spawn(move || settle_now_or_with(&channel, deferred, n));
+
+    Ok(promise)
+}
+
+fn settle_now_or_with(channel: &Channel, deferred: Deferred, n: f64) {
+    if let Err((deferred, complete)) =
+        deferred.try_settle_now(channel, move |mut cx| Ok(cx.number(n)))
+    {
+        deferred.settle_with(channel, complete);
+    }
+}
+
+// Settles a promise immediately by throwing from the settle closure
+pub fn deferred_settle_now_throw(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let msg = cx.argument::<JsString>(0)?.value(&mut cx);
+    let channel = Channel::new(&mut cx);
+    let (deferred, promise) = cx.promise();
+
+    if deferred
+        .try_settle_now::<JsValue, _>(&channel, move |mut cx| cx.throw_error(msg))
+        .is_err()
+    {
+        return cx.throw_error("expected to settle immediately");
+    }
+
+    Ok(promise)
+}
+
+// From another thread, interleaves `count` closures that call `on_event` with
+// settling `count` promises on a clone of the same channel. Each settle closure
+// also calls `on_event` before resolving.
+pub fn channel_interleave_settle(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let count = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let on_event = Arc::new(cx.argument::<JsFunction>(1)?.root(&mut cx));
+    let channel = cx.channel();
+    let settle_channel = channel.clone();
+    let promises = cx.empty_array();
+    let mut deferreds = Vec::with_capacity(count as usize);
+
+    for i in 0..count {
+        let (deferred, promise) = cx.promise();
+
+        promises.set(&mut cx, i, promise)?;
+        deferreds.push(deferred);
+    }
+
+    std::thread::spawn(move || {
+        for (i, deferred) in deferreds.into_iter().enumerate() {
+            let on_event_send = on_event.clone();
+            let on_event_settle = on_event.clone();
+
+            channel.send(move |mut cx| {
+                let kind = cx.string("send");
+                let n = cx.number(i as f64);
+
+                on_event_send
+                    .to_inner(&mut cx)
+                    .call_with(&cx)
+                    .arg(kind)
+                    .arg(n)
+                    .exec(&mut cx)
+            });
+
+            deferred.settle_with(&settle_channel, move |mut cx| {
+                let kind = cx.string("settle");
+                let n = cx.number(i as f64);
+
+                on_event_settle
+                    .to_inner(&mut cx)
+                    .call_with(&cx)
+                    .arg(kind)
+                    .arg(n)
+                    .exec(&mut cx)?;
+
+                Ok(cx.number(i as f64))
+            });
+        }
+    });
+
+    Ok(promises)
+}
+
+// Computes a value on the JavaScript thread from another thread and passes it
+// back to `callback`
+pub fn channel_send_map(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let obj = cx.argument::<JsObject>(0)?.root(&mut cx);
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        let n = channel
+            .send_map(move |mut cx| {
+                let obj = obj.into_inner(&mut cx);
+
+                obj.get::<JsNumber, _, _>(&mut cx, "n")
+                    .map(|n| n.value(&mut cx))
+            })
+            .join()
+            .unwrap();
+
+        channel.send(move |mut cx| {
+            let n = cx.number(n * 2.0);
+
+            callback
+                .into_inner(&mut cx)
+                .call_with(&cx)
+                .arg(n)
+                .exec(&mut cx)
+        });
+    });
+
+    Ok(cx.undefined())
+}
+
+// Creates an object on the JavaScript thread from another thread, holds the
+// `Root` on that thread, and then passes the object to `callback`
+pub fn channel_send_value(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        let obj = channel
+            .send_value(|mut cx| {
+                let obj = cx.empty_object();
+                let answer = cx.number(42);
+
+                obj.set(&mut cx, "answer", answer)?;
+
+                Ok(obj)
+            })
+            .join()
+            .unwrap();
+
+        channel.send(move |mut cx| {
+            let obj = obj.into_inner(&mut cx);
+
+            callback
+                .into_inner(&mut cx)
+                .call_with(&cx)
+                .arg(obj)
+                .exec(&mut cx)
+        });
+    });
+
+    Ok(cx.undefined())
+}
+
+// Returns a promise for whether `send_value` threw, after dropping the
+// `JoinHandle` for an object without joining
+pub fn channel_send_value_unjoined(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    std::thread::spawn(move || {
+        drop(channel.send_value(|mut cx| Ok(cx.empty_object())));
+
+        let thrown = channel
+            .send_value(|mut cx| cx.throw_error::<_, Handle<JsObject>>("send_value failed"))
+            .join()
+            .is_err();
+
+        deferred.settle_with(&channel, move |mut cx| Ok(cx.boolean(thrown)));
+    });
+
+    Ok(promise)
+}
+
+// Aborts a closure from the JavaScript thread, which cannot drain the queue until the
+// function returns
+pub fn channel_abort_queued(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+    let ran = Arc::new(AtomicBool::new(false));
+    let root = cx.empty_object().root(&mut cx);
+    let handle = {
+        let ran = ran.clone();
+
+        channel.send(move |mut cx| {
+            root.drop(&mut cx);
+            ran.store(true, Ordering::SeqCst);
+            Ok(())
+        })
+    };
+
+    let aborted = handle.abort();
+    let aborted_again = handle.abort();
+
+    std::thread::spawn(move || {
+        let join_aborted = matches!(handle.join(), Err(err) if err.is_aborted());
+
+        // Settled after the aborted closure would have executed
+        deferred.settle_with(&channel, move |mut cx| {
+            let ran = ran.load(Ordering::SeqCst);
+
+            cx.try_into_js((aborted, aborted_again, join_aborted, ran))
+        });
+    });
+
+    Ok(promise)
+}
+
+pub fn channel_detach(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        drop(channel.send(move |mut cx| callback.into_inner(&mut cx).call_with(&cx).exec(&mut cx)));
+    });
+
+    Ok(cx.undefined())
+}
+
+// Aborts every closure while the JavaScript thread drains the queue; resolves with the
+// number aborted and the number whose abort result disagreed with what happened
+pub fn channel_abort_race(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    const ABORT_LAG: usize = 16;
+
+    let count = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let roots = (0..count)
+        .map(|_| cx.empty_object().root(&mut cx))
+        .collect::<Vec<_>>();
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    std::thread::spawn(move || {
+        let ran = Arc::new(
+            (0..count)
+                .map(|_| AtomicBool::new(false))
+                .collect::<Vec<_>>(),
+        );
+        let mut handles = Vec::with_capacity(count);
+        let mut aborted = Vec::with_capacity(count);
+
+        for (i, root) in roots.into_iter().enumerate() {
+            let ran = ran.clone();
+
+            handles.push(channel.send(move |mut cx| {
+                root.drop(&mut cx);
+                ran[i].store(true, Ordering::SeqCst);
+                Ok(i)
+            }));
+
+            // Abort a recent closure, which may or may not have been drained yet
+            if i >= ABORT_LAG {
+                aborted.push(handles[i - ABORT_LAG].abort());
+            }
+        }
+
+        for handle in &handles[aborted.len()..] {
+            aborted.push(handle.abort());
+        }
+
+        let mut mismatches = 0u32;
+
+        for (i, (handle, aborted)) in handles.into_iter().zip(aborted.iter()).enumerate() {
+            let joined = match handle.join() {
+                Ok(j) => !aborted && j == i,
+                Err(err) => *aborted && err.is_aborted(),
+            };
+
+            if !joined || ran[i].load(Ordering::SeqCst) == *aborted {
+                mismatches += 1;
+            }
+        }
+
+        let aborted = aborted.iter().filter(|aborted| **aborted).count() as u32;
+
+        deferred.settle_with(&channel, move |mut cx| {
+            // Aborted closures never execute, even later
+            let late = ran.iter().filter(|ran| ran.load(Ordering::SeqCst)).count() as u32;
+
+            cx.try_into_js((aborted, mismatches, late))
+        });
+    });
+
+    Ok(promise)
+}
+
+// Round-trips a `Deferred` through a raw `napi_deferred` on another thread, as if it
+// were handed to another Node-API library
+pub fn deferred_raw_round_trip(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let value = cx.argument::<JsObject>(0)?.root(&mut cx);
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+    let raw = deferred.into_raw() as usize;
+
+    std::thread::spawn(move || {
+        channel.send(move |mut cx| {
+            let deferred = unsafe { Deferred::from_raw(&mut cx, raw as *mut _) };
+            let value = value.into_inner(&mut cx);
+
+            deferred.resolve(&mut cx, value);
+
+            Ok(())
+        })
+    });
+
+    Ok(promise)
+}
+
+pub fn deferred_raw_leak(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let (deferred, promise) = cx.promise();
+    let raw = deferred.into_raw();
+    let _ = unsafe { Deferred::from_raw(&mut cx, raw) };
+
+    Ok(promise)
+}
+
 pub fn leak_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
     let (_, promise) = cx.promise();
 
@@ -392,6 +1093,54 @@ pub fn task_panic_execute_promise(mut cx: FunctionContext) -> JsResult<JsPromise
     Ok(promise)
 }
 
+pub fn task_custom_panic_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let msg = cx.argument::<JsString>(0)?.value(&mut cx);
+    let promise = cx
+        .task(move || std::panic::panic_any(CustomPanic(msg)))
+        .promise(|mut cx, _| Ok(cx.undefined()));
+
+    Ok(promise)
+}
+
+pub fn task_named_panic_execute(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let msg = cx.argument::<JsString>(0)?.value(&mut cx);
+    let name = cx.argument::<JsString>(1)?.value(&mut cx);
+
+    cx.task(move || panic!("{}", msg))
+        .name(&name)
+        .and_then(|_, _| Ok(()));
+
+    Ok(cx.undefined())
+}
+
+pub fn task_named_panic_execute_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let msg = cx.argument::<JsString>(0)?.value(&mut cx);
+    let name = match cx.argument_opt(1) {
+        Some(name) => Some(
+            name.downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx),
+        ),
+        None => None,
+    };
+    let mut task = cx.task(move || panic!("{}", msg));
+
+    if let Some(name) = &name {
+        task = task.name(name);
+    }
+
+    Ok(task.promise(|mut cx, _| Ok(cx.undefined())))
+}
+
+// Recovers the message of a `CustomPanic` anywhere in the chain of an error
+pub fn custom_panic_payload(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let err = cx.argument::<JsValue>(0)?;
+
+    match downcast_panic::<CustomPanic, _, _>(&mut cx, err) {
+        Some(panic) => Ok(cx.string(&panic.0).upcast()),
+        None => Ok(cx.undefined().upcast()),
+    }
+}
+
 pub fn task_panic_complete_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
     let msg = cx.argument::<JsString>(0)?.value(&mut cx);
     let promise = cx
@@ -463,3 +1212,330 @@ pub fn deferred_settle_with_panic_throw(mut cx: FunctionContext) -> JsResult<JsP
 
     Ok(promise)
 }
+
+fn count_tsfn(
+    cx: &mut FunctionContext,
+    callback: Handle<JsFunction>,
+    max_queue_size: usize,
+) -> NeonResult<ThreadsafeFunction<u32>> {
+    ThreadsafeFunction::new(cx, callback, max_queue_size, |cx, callback, i: u32| {
+        let this = cx.undefined();
+        let i = cx.number(i);
+
+        callback.call(cx, this, [i.upcast()])?;
+
+        Ok(())
+    })
+}
+
+pub fn tsfn_count(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let callback = cx.argument::<JsFunction>(1)?;
+    let tsfn = count_tsfn(&mut cx, callback, 0)?;
+
+    std::thread::spawn(move || {
+        for i in 0..n {
+            tsfn.call(i).unwrap();
+        }
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn channel_count(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let callback = Arc::new(cx.argument::<JsFunction>(1)?.root(&mut cx));
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        for i in 0..n {
+            let callback = Arc::clone(&callback);
+
+            channel.send(move |mut cx| {
+                let this = cx.undefined();
+                let i = cx.number(i);
+
+                callback
+                    .to_inner(&mut cx)
+                    .call(&mut cx, this, [i.upcast()])?;
+
+                Ok(())
+            });
+        }
+    });
+
+    Ok(cx.undefined())
+}
+
+// Returns whether each of the first two calls to a tsfn with a queue size of `1`
+// succeeded, followed by whether the failed call reported a full queue
+pub fn tsfn_try_call_full(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let callback = cx.argument::<JsFunction>(0)?;
+    let tsfn = count_tsfn(&mut cx, callback, 1)?;
+    let first = tsfn.try_call(0);
+    let second = tsfn.try_call(1);
+    let is_full = second.as_ref().map(|_| false).unwrap_or_else(|err| {
+        assert_eq!(err.to_string(), "ThreadsafeFunction queue is full");
+        err.is_full()
+    });
+
+    let results = [first.is_ok(), second.is_ok(), is_full].map(|b| cx.boolean(b));
+
+    JsArray::from_slice(&mut cx, &results)
+}
+
+// Aborts a tsfn with pending messages and returns whether a later call failed
+// without reporting a full queue
+pub fn tsfn_abort(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let callback = cx.argument::<JsFunction>(0)?;
+    let tsfn = count_tsfn(&mut cx, callback, 0)?;
+
+    for i in 0..4 {
+        tsfn.call(i).unwrap();
+    }
+
+    tsfn.clone().abort();
+
+    let closed = match tsfn.call(4) {
+        Ok(()) => false,
+        Err(err) => !err.is_full() && err.into_inner() == 4,
+    };
+
+    Ok(cx.boolean(closed))
+}
+
+// Calls `unref` and `reference`, or `abort`, on a tsfn with a queue size of `1` while
+// a producer thread is blocked on the full queue. After aborting, returns whether
+// the blocked call failed without reporting a full queue.
+pub fn tsfn_blocked_producer(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let callback = cx.argument::<JsFunction>(0)?;
+    let action = cx.argument::<JsString>(1)?.value(&mut cx);
+    let tsfn = count_tsfn(&mut cx, callback, 1)?;
+    let (tx, rx) = std::sync::mpsc::channel();
+    let producer = {
+        let tsfn = tsfn.clone();
+
+        std::thread::spawn(move || {
+            tsfn.call(0).unwrap();
+            tx.send(()).unwrap();
+
+            // Blocks until the JavaScript thread drains the queue or aborts
+            (1..3).try_for_each(|i| tsfn.call(i))
+        })
+    };
+
+    rx.recv().unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+
+    if action == "abort" {
+        tsfn.abort();
+
+        let closed = match producer.join().unwrap() {
+            Ok(()) => false,
+            Err(err) => !err.is_full(),
+        };
+
+        return Ok(cx.boolean(closed).upcast());
+    }
+
+    tsfn.unref(&mut cx).reference(&mut cx);
+
+    Ok(cx.undefined().upcast())
+}
+
+pub fn leak_tsfn(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?;
+    let tsfn = count_tsfn(&mut cx, callback, 0)?;
+
+    tsfn.unref(&mut cx);
+    std::mem::forget(tsfn);
+
+    Ok(cx.undefined())
+}
+
+pub fn emitter_count(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let target = cx.argument::<JsObject>(0)?;
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let batch = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+    let mut emitter = Emitter::new(&mut cx, target)?;
+
+    emitter.batch(batch);
+
+    std::thread::spawn(move || {
+        for i in 0..n {
+            emitter.emit("data", i);
+        }
+
+        emitter.emit_with("end", move |cx| {
+            let o = cx.empty_object();
+            let count = cx.number(n);
+
+            o.set(cx, "count", count)?;
+
+            Ok(o.upcast())
+        });
+    });
+
+    Ok(cx.undefined())
+}
+
+// Calls `f` with each index from `n` threads, blocking on each call, and then calls
+// `done` with the sum of the results
+pub fn callback_sum(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let f = Callback::<(f64,)>::new(&mut cx, f);
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let done = cx.argument::<JsFunction>(2)?;
+    let done = Callback::<(f64,)>::new(&mut cx, done);
+
+    std::thread::spawn(move || {
+        let threads = (0..n)
+            .map(|i| {
+                let f = f.clone();
+
+                std::thread::spawn(move || f.call_blocking::<f64>((i as f64,)).unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        // The last clone of `f` is dropped on this thread
+        drop(f);
+
+        let sum = threads.into_iter().map(|t| t.join().unwrap()).sum::<f64>();
+
+        done.call_and_forget((sum,)).unwrap();
+    });
+
+    Ok(cx.undefined())
+}
+
+// Calls `f` with arguments created on the JavaScript thread and then calls `done`
+// with the result
+pub fn callback_call_with(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let f = Callback::<()>::new(&mut cx, f);
+    let done = cx.argument::<JsFunction>(1)?;
+    let done = Callback::<(String,)>::new(&mut cx, done);
+
+    std::thread::spawn(move || {
+        let result = f
+            .call_with::<String, _>(|cx| {
+                let o = cx.empty_object();
+                let name = cx.string("neon");
+
+                o.set(cx, "name", name)?;
+
+                Ok(vec![o.upcast(), cx.number(1).upcast()])
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+
+        done.call_and_forget((result,)).unwrap();
+    });
+
+    Ok(cx.undefined())
+}
+
+// Calls `f` expecting a number and then calls `done` with the error and whether the
+// environment was closed
+pub fn callback_call_error(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let f = Callback::<()>::new(&mut cx, f);
+    let done = cx.argument::<JsFunction>(1)?;
+    let done = Callback::<(String, bool)>::new(&mut cx, done);
+
+    std::thread::spawn(move || {
+        let err = f.call_blocking::<f64>(()).unwrap_err();
+
+        done.call_and_forget((err.to_string(), err.is_closed()))
+            .unwrap();
+    });
+
+    Ok(cx.undefined())
+}
+
+// Calls `f` with each index, from a thread per clone of its root, and then calls `done`
+// with whether every call succeeded
+pub fn root_call_threaded(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let done = cx.argument::<JsFunction>(2)?;
+    let done = Callback::<(bool,)>::new(&mut cx, done);
+    let roots = (0..n).map(|_| f.clone(&mut cx)).collect::<Vec<_>>();
+    let channel = cx.channel();
+
+    f.drop(&mut cx);
+
+    std::thread::spawn(move || {
+        let threads = roots
+            .into_iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let channel = channel.clone();
+
+                std::thread::spawn(move || {
+                    f.call_threaded(&channel, move |cx| Ok(vec![cx.number(i as f64).upcast()]))
+                        .join()
+                        .is_ok()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let ok = threads.into_iter().all(|t| t.join().unwrap());
+
+        done.call_and_forget((ok,)).unwrap();
+    });
+
+    Ok(cx.undefined())
+}
+
+// Calls `f` with each index from `n` threads through a `Callback` converted from its
+// root, and then calls `done` with the sum of the results
+pub fn root_into_callback(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let done = cx.argument::<JsFunction>(2)?.root(&mut cx);
+    let channel = cx.channel();
+    let f = f.into_callback::<(f64,)>(channel.clone());
+
+    std::thread::spawn(move || {
+        let threads = (0..n)
+            .map(|i| {
+                let f = f.clone();
+
+                std::thread::spawn(move || f.call_blocking::<f64>((i as f64,)).unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        let sum = threads.into_iter().map(|t| t.join().unwrap()).sum::<f64>();
+
+        done.call_threaded(&channel, move |cx| Ok(vec![cx.number(sum).upcast()]));
+    });
+
+    Ok(cx.undefined())
+}
+
+static STASHED_CALLBACK: Mutex<Option<Callback>> = Mutex::new(None);
+
+// Keeps `f` after the environment that created it exits
+pub fn callback_stash(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let f = Callback::new(&mut cx, f);
+
+    *STASHED_CALLBACK.lock().unwrap() = Some(f);
+
+    Ok(cx.undefined())
+}
+
+// Calls and drops the stashed callback, returning `true` if every call failed
+// because its environment is closed
+pub fn callback_call_stashed(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let f = STASHED_CALLBACK.lock().unwrap().take().unwrap();
+    let forgotten = f.call_and_forget(()).is_err();
+    let blocking = matches!(f.call_blocking::<f64>(()), Err(err) if err.is_closed());
+
+    drop(f);
+
+    Ok(cx.boolean(forgotten && blocking))
+}
+
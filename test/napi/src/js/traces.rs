@@ -0,0 +1,151 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, Once},
+};
+
+use neon::prelude::*;
+use once_cell::sync::Lazy;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Subscriber,
+};
+use tracing_subscriber::{
+    layer::{Context as LayerContext, SubscriberExt},
+    registry::LookupSpan,
+    Layer,
+};
+
+#[derive(Clone, Debug, Default)]
+struct Trace {
+    kind: &'static str,
+    name: String,
+    fields: HashMap<String, String>,
+}
+
+// Traces emitted by Neon, with spans in the order they were closed
+static TRACES: Lazy<Mutex<Vec<Trace>>> = Lazy::new(Default::default);
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+
+impl<'a> Visit for Fields<'a> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+// Records spans and events with the `neon::event` target
+struct Recorder;
+
+impl<S> Layer<S> for Recorder
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, cx: LayerContext<'_, S>) {
+        if attrs.metadata().target() != "neon::event" {
+            return;
+        }
+
+        let mut trace = Trace {
+            kind: "span",
+            name: attrs.metadata().name().to_string(),
+            fields: HashMap::new(),
+        };
+
+        attrs.record(&mut Fields(&mut trace.fields));
+        cx.span(id).unwrap().extensions_mut().insert(trace);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, cx: LayerContext<'_, S>) {
+        let span = cx.span(id).unwrap();
+        let mut extensions = span.extensions_mut();
+
+        if let Some(trace) = extensions.get_mut::<Trace>() {
+            values.record(&mut Fields(&mut trace.fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _cx: LayerContext<'_, S>) {
+        if event.metadata().target() != "neon::event" {
+            return;
+        }
+
+        let mut trace = Trace {
+            kind: "event",
+            name: event.metadata().name().to_string(),
+            fields: HashMap::new(),
+        };
+
+        event.record(&mut Fields(&mut trace.fields));
+        TRACES.lock().unwrap().push(trace);
+    }
+
+    fn on_close(&self, id: Id, cx: LayerContext<'_, S>) {
+        let span = cx.span(&id).unwrap();
+        let trace = span.extensions_mut().remove::<Trace>();
+
+        if let Some(trace) = trace {
+            TRACES.lock().unwrap().push(trace);
+        }
+    }
+}
+
+fn install() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let subscriber = tracing_subscriber::registry().with(Recorder);
+
+        tracing::subscriber::set_global_default(subscriber).unwrap();
+    });
+}
+
+pub fn traces_take(mut cx: FunctionContext) -> JsResult<JsArray> {
+    install();
+
+    let traces = std::mem::take(&mut *TRACES.lock().unwrap());
+    let arr = cx.empty_array();
+
+    for (i, trace) in traces.into_iter().enumerate() {
+        let obj = cx.empty_object();
+        let kind = cx.string(trace.kind);
+        let name = cx.string(trace.name);
+
+        obj.set(&mut cx, "kind", kind)?;
+        obj.set(&mut cx, "name", name)?;
+
+        for (key, value) in trace.fields {
+            let value = cx.string(value);
+
+            obj.set(&mut cx, key.as_str(), value)?;
+        }
+
+        arr.set(&mut cx, i as u32, obj)?;
+    }
+
+    Ok(arr)
+}
+
+// Sends a closure on a channel, returning the line of the call
+pub fn traces_channel_send(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let channel = cx.channel();
+    let line = line!() + 1;
+    channel.send(|_| Ok(()));
+
+    Ok(cx.number(line))
+}
+
+// Schedules a task, resolving with the line of the call
+pub fn traces_task(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let line = line!() + 3;
+    let promise = cx
+        .task(|| std::thread::sleep(std::time::Duration::from_millis(5)))
+        .promise(move |mut cx, _| Ok(cx.number(line)));
+
+    Ok(promise)
+}
@@ -1,6 +1,15 @@
+use std::{
+    io::{self, Read},
+    sync::Mutex,
+};
+
+use bytes::{Bytes, BytesMut};
 use neon::{
     prelude::*,
-    types::buffer::{Binary, BorrowError, TypedArray},
+    types::{
+        buffer::{self, Binary, BorrowError, BufferPool, Encoding, ExternalPolicy, TypedArray},
+        extract::{TryFromJs, TryIntoJs},
+    },
 };
 
 pub fn return_array_buffer(mut cx: FunctionContext) -> JsResult<JsArrayBuffer> {
@@ -101,6 +110,201 @@ pub fn copy_typed_array(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     Ok(cx.undefined())
 }
 
+pub fn try_get_typed_array_element(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let arr = cx.argument::<JsTypedArray<i32>>(0)?;
+    let i = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+
+    match arr.try_get(&cx, i) {
+        Some(n) => Ok(cx.number(n).upcast()),
+        None => Ok(cx.undefined().upcast()),
+    }
+}
+
+pub fn get_typed_array_element(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arr = cx.argument::<JsTypedArray<i32>>(0)?;
+    let i = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let n = arr.get(&mut cx, i)?;
+
+    Ok(cx.number(n))
+}
+
+pub fn set_typed_array_element(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let arr = cx.argument::<JsTypedArray<i32>>(0)?;
+    let i = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let n = cx.argument::<JsNumber>(2)?.value(&mut cx) as i32;
+
+    arr.set(&mut cx, i, n)?;
+
+    Ok(cx.undefined())
+}
+
+// Swaps pairs of elements, calling `f` between the read and the write of each pair
+pub fn swap_typed_array_elements_with_callback(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let arr = cx.argument::<JsTypedArray<f64>>(0)?;
+    let pairs = cx.argument::<JsUint32Array>(1)?;
+    let f = cx.argument::<JsFunction>(2)?;
+    let len = pairs.len(&mut cx);
+
+    for p in (0..len).step_by(2) {
+        let i = pairs.get(&mut cx, p)? as usize;
+        let j = pairs.get(&mut cx, p + 1)? as usize;
+        let a = arr.get(&mut cx, i)?;
+        let b = arr.get(&mut cx, j)?;
+
+        f.call_with(&cx).arg(arr).exec(&mut cx)?;
+
+        arr.set(&mut cx, i, b)?;
+        arr.set(&mut cx, j, a)?;
+    }
+
+    Ok(cx.undefined())
+}
+
+pub fn sum_scattered_with_get(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arr = cx.argument::<JsFloat64Array>(0)?;
+    let indices = cx.argument::<JsUint32Array>(1)?;
+    let indices = indices.as_slice(&cx).to_vec();
+    let mut sum = 0.0;
+
+    for i in indices {
+        sum += arr.get(&mut cx, i as usize)?;
+    }
+
+    Ok(cx.number(sum))
+}
+
+pub fn sum_scattered_with_slice(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arr = cx.argument::<JsFloat64Array>(0)?;
+    let indices = cx.argument::<JsUint32Array>(1)?;
+    let indices = indices.as_slice(&cx).to_vec();
+    let mut sum = 0.0;
+
+    // Borrow the slice again for each access, as when JavaScript runs in between
+    for i in indices {
+        sum += arr.as_slice(&cx)[i as usize];
+    }
+
+    Ok(cx.number(sum))
+}
+
+pub fn sum_scattered_with_held_slice(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arr = cx.argument::<JsFloat64Array>(0)?;
+    let indices = cx.argument::<JsUint32Array>(1)?;
+    let indices = indices.as_slice(&cx);
+    let data = arr.as_slice(&cx);
+    let sum = indices.iter().map(|&i| data[i as usize]).sum::<f64>();
+
+    Ok(cx.number(sum))
+}
+
+// Reads `bytes` in chunks of at most `chunk` bytes, interrupted before every read
+// and failing after `fail_after` bytes, if set
+struct ChunkedReader {
+    bytes: Vec<u8>,
+    pos: usize,
+    chunk: usize,
+    interrupt: bool,
+    fail_after: Option<usize>,
+}
+
+impl Read for ChunkedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+
+        if self.interrupt {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+
+        if self.fail_after.is_some_and(|n| self.pos >= n) {
+            return Err(io::Error::other("disk on fire"));
+        }
+
+        let n = buf.len().min(self.chunk).min(self.bytes.len() - self.pos);
+
+        buf[..n].copy_from_slice(&self.bytes[self.pos..self.pos + n]);
+        self.pos += n;
+
+        Ok(n)
+    }
+}
+
+fn chunked_reader(cx: &mut FunctionContext) -> NeonResult<ChunkedReader> {
+    let bytes = cx.argument::<JsBuffer>(0)?.as_slice(cx).to_vec();
+    let fail_after = match cx.argument_opt(2) {
+        Some(n) => Some(n.downcast_or_throw::<JsNumber, _>(cx)?.value(cx) as usize),
+        None => None,
+    };
+
+    Ok(ChunkedReader {
+        bytes,
+        pos: 0,
+        chunk: 3,
+        interrupt: false,
+        fail_after,
+    })
+}
+
+pub fn array_buffer_from_reader(mut cx: FunctionContext) -> JsResult<JsArrayBuffer> {
+    let reader = chunked_reader(&mut cx)?;
+    let len = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+
+    JsArrayBuffer::from_reader(&mut cx, reader, len)
+}
+
+pub fn buffer_from_reader(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let reader = chunked_reader(&mut cx)?;
+    let len = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+
+    JsBuffer::from_reader(&mut cx, reader, len)
+}
+
+// Yields `len` bytes, reporting an exact size hint of `hint`
+struct HintedBytes {
+    next: u8,
+    len: u8,
+    hint: usize,
+}
+
+impl Iterator for HintedBytes {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.next == self.len {
+            return None;
+        }
+
+        self.next += 1;
+        self.hint = self.hint.saturating_sub(1);
+
+        Some(self.next - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.hint, Some(self.hint))
+    }
+}
+
+pub fn array_buffer_from_iter(mut cx: FunctionContext) -> JsResult<JsArrayBuffer> {
+    let len = cx.argument::<JsNumber>(0)?.value(&mut cx) as u8;
+
+    match cx.argument_opt(1) {
+        Some(hint) => {
+            let hint = hint
+                .downcast_or_throw::<JsNumber, _>(&mut cx)?
+                .value(&mut cx) as usize;
+
+            JsArrayBuffer::from_iter(&mut cx, HintedBytes { next: 0, len, hint })
+        }
+        None => JsArrayBuffer::from_iter(&mut cx, 0..len),
+    }
+}
+
+pub fn buffer_from_iter_filtered(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let len = cx.argument::<JsNumber>(0)?.value(&mut cx) as u8;
+
+    JsBuffer::from_iter(&mut cx, (0..len).filter(|b| b % 2 == 0))
+}
+
 pub fn return_uninitialized_buffer(mut cx: FunctionContext) -> JsResult<JsBuffer> {
     let b: Handle<JsBuffer> = unsafe { JsBuffer::uninitialized(&mut cx, 16)? };
     Ok(b)
@@ -125,6 +329,41 @@ pub fn return_external_array_buffer(mut cx: FunctionContext) -> JsResult<JsArray
     Ok(buf)
 }
 
+fn external_policy(cx: &mut FunctionContext, i: usize) -> NeonResult<ExternalPolicy> {
+    match cx.argument::<JsString>(i)?.value(cx).as_str() {
+        "require" => Ok(ExternalPolicy::Require),
+        "prefer-copy-fallback" => Ok(ExternalPolicy::PreferCopyFallback),
+        "always-copy" => Ok(ExternalPolicy::AlwaysCopy),
+        policy => cx.throw_error(format!("unknown policy: {}", policy)),
+    }
+}
+
+pub fn return_external_buffer_with_policy(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let data = cx.argument::<JsString>(0)?.value(&mut cx);
+    let policy = external_policy(&mut cx, 1)?;
+    let buf = JsBuffer::external_with_policy(&mut cx, data.into_bytes(), policy)?;
+    let is_externalized = cx.boolean(buf.is_externalized());
+    let result = cx.empty_array();
+
+    result.set(&mut cx, 0, buf.into_inner())?;
+    result.set(&mut cx, 1, is_externalized)?;
+
+    Ok(result)
+}
+
+pub fn return_external_array_buffer_with_policy(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let data = cx.argument::<JsString>(0)?.value(&mut cx);
+    let policy = external_policy(&mut cx, 1)?;
+    let buf = JsArrayBuffer::external_with_policy(&mut cx, data.into_bytes(), policy)?;
+    let is_externalized = cx.boolean(buf.is_externalized());
+    let result = cx.empty_array();
+
+    result.set(&mut cx, 0, buf.into_inner())?;
+    result.set(&mut cx, 1, is_externalized)?;
+
+    Ok(result)
+}
+
 pub fn return_int8array_from_arraybuffer(mut cx: FunctionContext) -> JsResult<JsInt8Array> {
     let buf = cx.argument::<JsArrayBuffer>(0)?;
     JsInt8Array::from_buffer(&mut cx, buf)
@@ -379,3 +618,179 @@ pub fn write_buffer_with_borrow_mut(mut cx: FunctionContext) -> JsResult<JsUndef
 
     Ok(cx.undefined())
 }
+
+pub fn create_buffer_pool(mut cx: FunctionContext) -> JsResult<JsBox<BufferPool>> {
+    let slab_size = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let max_slabs = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+    let pool = BufferPool::with_max_slabs(&mut cx, slab_size, max_slabs)?;
+
+    Ok(cx.boxed(pool))
+}
+
+pub fn buffer_pool_alloc(mut cx: FunctionContext) -> JsResult<JsUint8Array> {
+    let pool = cx.argument::<JsBox<BufferPool>>(0)?;
+    let len = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+
+    pool.alloc(&mut cx, len)
+}
+
+pub fn buffer_pool_slab_count(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let pool = cx.argument::<JsBox<BufferPool>>(0)?;
+    let count = pool.slab_count() as f64;
+
+    Ok(cx.number(count))
+}
+
+pub fn alloc_buffer(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let len = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+
+    cx.buffer(len)
+}
+
+fn text_encoding(cx: &mut FunctionContext, i: usize) -> NeonResult<Encoding> {
+    let label = cx.argument::<JsString>(i)?.value(cx);
+
+    match label.as_str() {
+        "utf8" => Ok(Encoding::Utf8),
+        "utf16le" => Ok(Encoding::Utf16Le),
+        "latin1" => Ok(Encoding::Latin1),
+        _ => match Encoding::for_label(&label) {
+            Some(encoding) => Ok(encoding),
+            None => cx.throw_range_error(format!("unknown encoding {:?}", label)),
+        },
+    }
+}
+
+pub fn decode_text(mut cx: FunctionContext) -> JsResult<JsString> {
+    let data = cx.argument::<JsTypedArray<u8>>(0)?;
+    let encoding = text_encoding(&mut cx, 1)?;
+    let lossy = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+    let bytes = data.as_slice(&cx).to_vec();
+    let s = if lossy {
+        buffer::decode_lossy(&bytes, encoding)
+    } else {
+        buffer::decode(&mut cx, &bytes, encoding)?
+    };
+
+    Ok(cx.string(s))
+}
+
+pub fn encode_text(mut cx: FunctionContext) -> JsResult<JsTypedArray<u8>> {
+    let s = cx.argument::<JsString>(0)?.value(&mut cx);
+    let encoding = text_encoding(&mut cx, 1)?;
+
+    buffer::encode(&mut cx, &s, encoding)
+}
+
+pub fn encoding_name(mut cx: FunctionContext) -> JsResult<JsString> {
+    let encoding = text_encoding(&mut cx, 0)?;
+
+    Ok(cx.string(encoding.name()))
+}
+
+// Creates `Bytes` counting up from zero, returning them and the address of their memory
+fn counting_bytes(cx: &mut FunctionContext) -> NeonResult<(Bytes, f64)> {
+    let len = cx.argument::<JsNumber>(0)?.value(cx) as usize;
+    let bytes = (0..len).map(|i| i as u8).collect::<BytesMut>().freeze();
+    let addr = bytes.as_ptr() as usize as f64;
+
+    Ok((bytes, addr))
+}
+
+fn buffer_and_address<'a>(
+    cx: &mut FunctionContext<'a>,
+    buf: Handle<JsBuffer>,
+    addr: f64,
+) -> JsResult<'a, JsArray> {
+    let result = cx.empty_array();
+    let addr = cx.number(addr);
+
+    result.set(cx, 0, buf)?;
+    result.set(cx, 1, addr)?;
+
+    Ok(result)
+}
+
+pub fn buffer_from_unique_bytes(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let (bytes, addr) = counting_bytes(&mut cx)?;
+    let buf = JsBuffer::from_bytes(&mut cx, bytes)?;
+
+    buffer_and_address(&mut cx, buf, addr)
+}
+
+pub fn buffer_from_shared_bytes(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let (bytes, addr) = counting_bytes(&mut cx)?;
+    let buf = JsBuffer::from_bytes(&mut cx, bytes.clone())?;
+
+    // Writes to the buffer must not be visible through the other clone
+    assert_eq!(bytes.as_ptr() as usize as f64, addr);
+
+    buffer_and_address(&mut cx, buf, addr)
+}
+
+pub fn buffer_address(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let buf = cx.argument::<JsBuffer>(0)?;
+    let addr = buf.as_slice(&cx).as_ptr() as usize;
+
+    Ok(cx.number(addr as f64))
+}
+
+pub fn bytes_round_trip(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let v = cx.argument::<JsValue>(0)?;
+    let bytes = Bytes::try_from_js(&mut cx, v)?;
+
+    bytes.try_into_js(&mut cx)
+}
+
+// Extracts `Bytes` from a buffer, then calls `detach` with the buffer before
+// converting the `Bytes` back to a buffer
+pub fn bytes_after_detach(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let v = cx.argument::<JsValue>(0)?;
+    let detach = cx.argument::<JsFunction>(1)?;
+    let bytes = Bytes::try_from_js(&mut cx, v)?;
+
+    detach.call_with(&cx).arg(v).exec(&mut cx)?;
+
+    bytes.try_into_js(&mut cx)
+}
+
+// `Bytes` that outlive the buffers they were created from
+static STASHED_BYTES: Mutex<Vec<Bytes>> = Mutex::new(Vec::new());
+
+// JavaScript must not modify or detach the buffer until `clear_stashed_bytes`
+pub fn stash_bytes(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let buf = cx.argument::<JsBuffer>(0)?;
+    // Safety: The tests only stash buffers that they never use again
+    let bytes = unsafe { buf.to_bytes(&mut cx) };
+    let mut stash = STASHED_BYTES.lock().unwrap();
+
+    stash.push(bytes.slice(1..));
+    stash.push(bytes);
+
+    Ok(cx.undefined())
+}
+
+pub fn sum_stashed_bytes(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let stash = STASHED_BYTES.lock().unwrap().clone();
+
+    let promise = cx
+        .task(move || {
+            stash
+                .iter()
+                .flat_map(|bytes| bytes.iter())
+                .map(|b| *b as f64)
+                .sum::<f64>()
+        })
+        .promise(|mut cx, sum| Ok(cx.number(sum)));
+
+    Ok(promise)
+}
+
+pub fn clear_stashed_bytes(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let stash = std::mem::take(&mut *STASHED_BYTES.lock().unwrap());
+
+    // Releases the buffers from another thread
+    std::thread::spawn(move || drop(stash)).join().unwrap();
+
+    Ok(cx.undefined())
+}
@@ -1,4 +1,10 @@
-use neon::prelude::*;
+use neon::{
+    prelude::*,
+    types::{
+        extract::{MaybeUndefined, Named, OrUndefined, TryFromJs, TryIntoJs},
+        ValueKind, ValueVisitor,
+    },
+};
 
 pub fn is_string(mut cx: FunctionContext) -> JsResult<JsBoolean> {
     let val: Handle<JsValue> = cx.argument(0)?;
@@ -72,3 +78,454 @@ pub fn strict_equals(mut cx: FunctionContext) -> JsResult<JsBoolean> {
     let eq = v1.strict_equals(&mut cx, v2);
     Ok(cx.boolean(eq))
 }
+
+pub fn same_value(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let v1: Handle<JsValue> = cx.argument(0)?;
+    let v2: Handle<JsValue> = cx.argument(1)?;
+    let eq = v1.same_value(&mut cx, v2);
+    Ok(cx.boolean(eq))
+}
+
+pub fn same_value_zero(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let v1: Handle<JsValue> = cx.argument(0)?;
+    let v2: Handle<JsValue> = cx.argument(1)?;
+    let eq = v1.same_value_zero(&mut cx, v2);
+    Ok(cx.boolean(eq))
+}
+
+fn kind_name(kind: ValueKind) -> &'static str {
+    match kind {
+        ValueKind::Undefined => "undefined",
+        ValueKind::Null => "null",
+        ValueKind::Boolean => "boolean",
+        ValueKind::Number => "number",
+        ValueKind::String => "string",
+        ValueKind::Symbol => "symbol",
+        ValueKind::Object => "object",
+        ValueKind::Function => "function",
+        ValueKind::BigInt => "bigint",
+        ValueKind::External => "external",
+    }
+}
+
+pub fn value_kind(mut cx: FunctionContext) -> JsResult<JsString> {
+    let val: Handle<JsValue> = cx.argument(0)?;
+    let kind = val.kind(&mut cx);
+    Ok(cx.string(kind_name(kind)))
+}
+
+pub fn value_kind_with_is_a(mut cx: FunctionContext) -> JsResult<JsString> {
+    let val: Handle<JsValue> = cx.argument(0)?;
+    let name = if val.is_a::<JsUndefined, _>(&mut cx) {
+        "undefined"
+    } else if val.is_a::<JsNull, _>(&mut cx) {
+        "null"
+    } else if val.is_a::<JsBoolean, _>(&mut cx) {
+        "boolean"
+    } else if val.is_a::<JsNumber, _>(&mut cx) {
+        "number"
+    } else if val.is_a::<JsString, _>(&mut cx) {
+        "string"
+    } else if val.is_a::<JsFunction, _>(&mut cx) {
+        "function"
+    } else if val.is_a::<JsObject, _>(&mut cx) {
+        "object"
+    } else {
+        "other"
+    };
+    Ok(cx.string(name))
+}
+
+// Describes a value, including its contents if it is a primitive or an array
+struct Describe;
+
+impl<'a> ValueVisitor<'a> for Describe {
+    type Output = String;
+
+    fn visit_undefined<C: Context<'a>>(&mut self, _cx: &mut C) -> NeonResult<String> {
+        Ok("undefined".to_string())
+    }
+
+    fn visit_null<C: Context<'a>>(&mut self, _cx: &mut C) -> NeonResult<String> {
+        Ok("null".to_string())
+    }
+
+    fn visit_boolean<C: Context<'a>>(&mut self, _cx: &mut C, b: bool) -> NeonResult<String> {
+        Ok(format!("boolean {}", b))
+    }
+
+    fn visit_number<C: Context<'a>>(&mut self, _cx: &mut C, n: f64) -> NeonResult<String> {
+        Ok(format!("number {}", n))
+    }
+
+    fn visit_string<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        s: Handle<'a, JsString>,
+    ) -> NeonResult<String> {
+        Ok(format!("string {}", s.value(cx)))
+    }
+
+    fn visit_array<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        array: Handle<'a, JsArray>,
+    ) -> NeonResult<String> {
+        let mut elements = Vec::new();
+
+        for value in array.to_vec(cx)? {
+            elements.push(value.visit(cx, self)?);
+        }
+
+        Ok(format!("array [{}]", elements.join(", ")))
+    }
+
+    fn visit_function<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        _f: Handle<'a, JsFunction>,
+    ) -> NeonResult<String> {
+        Ok("function".to_string())
+    }
+
+    fn visit_object<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        _obj: Handle<'a, JsObject>,
+    ) -> NeonResult<String> {
+        Ok("object".to_string())
+    }
+
+    fn visit_other<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        value: Handle<'a, JsValue>,
+    ) -> NeonResult<String> {
+        Ok(format!("other {}", kind_name(value.kind(cx))))
+    }
+}
+
+pub fn visit_value(mut cx: FunctionContext) -> JsResult<JsString> {
+    let val: Handle<JsValue> = cx.argument(0)?;
+    let description = val.visit(&mut cx, &mut Describe)?;
+
+    Ok(cx.string(description))
+}
+
+// Counts the strings, numbers, arrays, plain objects and other values of an array
+struct Shapes([u32; 5]);
+
+impl<'a> ValueVisitor<'a> for Shapes {
+    type Output = ();
+
+    fn visit_string<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        _s: Handle<'a, JsString>,
+    ) -> NeonResult<()> {
+        self.0[0] += 1;
+        Ok(())
+    }
+
+    fn visit_number<C: Context<'a>>(&mut self, _cx: &mut C, _n: f64) -> NeonResult<()> {
+        self.0[1] += 1;
+        Ok(())
+    }
+
+    fn visit_array<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        _array: Handle<'a, JsArray>,
+    ) -> NeonResult<()> {
+        self.0[2] += 1;
+        Ok(())
+    }
+
+    fn visit_object<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        _obj: Handle<'a, JsObject>,
+    ) -> NeonResult<()> {
+        self.0[3] += 1;
+        Ok(())
+    }
+
+    fn visit_other<C: Context<'a>>(
+        &mut self,
+        _cx: &mut C,
+        _value: Handle<'a, JsValue>,
+    ) -> NeonResult<()> {
+        self.0[4] += 1;
+        Ok(())
+    }
+}
+
+fn shape_counts<'a>(cx: &mut FunctionContext<'a>, counts: [u32; 5]) -> JsResult<'a, JsArray> {
+    let counts = counts.map(f64::from);
+
+    cx.array_from_f64_slice(&counts)
+}
+
+pub fn count_shapes_with_visitor(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let mut shapes = Shapes([0; 5]);
+
+    for value in cx.argument::<JsArray>(0)?.to_vec(&mut cx)? {
+        value.visit(&mut cx, &mut shapes)?;
+    }
+
+    shape_counts(&mut cx, shapes.0)
+}
+
+pub fn count_shapes_with_downcast(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let mut counts = [0; 5];
+
+    for value in cx.argument::<JsArray>(0)?.to_vec(&mut cx)? {
+        let i = if value.is_a::<JsString, _>(&mut cx) {
+            0
+        } else if value.is_a::<JsNumber, _>(&mut cx) {
+            1
+        } else if value.is_a::<JsArray, _>(&mut cx) {
+            2
+        } else if value.is_a::<JsFunction, _>(&mut cx) {
+            4
+        } else if value.is_a::<JsObject, _>(&mut cx) {
+            3
+        } else {
+            4
+        };
+
+        counts[i] += 1;
+    }
+
+    shape_counts(&mut cx, counts)
+}
+
+fn lookup_index(cx: &mut FunctionContext) -> NeonResult<Option<u32>> {
+    let needle = cx.argument::<JsString>(0)?.value(cx);
+    let index = ["a", "b", "c"].iter().position(|s| *s == needle);
+
+    Ok(index.map(|i| i as u32))
+}
+
+pub fn option_or_null(mut cx: FunctionContext) -> JsResult<JsValue> {
+    lookup_index(&mut cx)?.try_into_js(&mut cx)
+}
+
+pub fn option_or_undefined(mut cx: FunctionContext) -> JsResult<JsValue> {
+    OrUndefined(lookup_index(&mut cx)?).try_into_js(&mut cx)
+}
+
+pub fn option_in_task(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let promise = cx
+        .task(move || if n >= 0.0 { Some(n.sqrt()) } else { None })
+        .promise(|mut cx, value| value.try_into_js(&mut cx));
+
+    Ok(promise)
+}
+
+pub fn option_from_js(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let s = Option::<String>::try_from_js(&mut cx, arg)?;
+
+    Ok(cx.string(s.unwrap_or_else(|| "none".to_string())))
+}
+
+pub fn or_undefined_from_js(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let OrUndefined(b) = OrUndefined::<bool>::try_from_js(&mut cx, arg)?;
+
+    Ok(cx.boolean(b.unwrap_or(false)))
+}
+
+pub fn maybe_undefined_argument(mut cx: FunctionContext) -> JsResult<JsString> {
+    let description = match cx.extract_argument::<MaybeUndefined<f64>>(0)? {
+        MaybeUndefined::Missing => "missing".to_string(),
+        MaybeUndefined::Null => "null".to_string(),
+        MaybeUndefined::Value(n) => format!("value {}", n),
+    };
+
+    Ok(cx.string(description))
+}
+
+pub fn maybe_undefined_round_trip(mut cx: FunctionContext) -> JsResult<JsValue> {
+    cx.extract_argument::<MaybeUndefined<String>>(0)?
+        .try_into_js(&mut cx)
+}
+
+pub fn request_timeout(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let options = cx.argument::<JsObject>(0)?;
+    let timeout = options
+        .extract_property::<MaybeUndefined<f64>, _, _>(&mut cx, "timeout")?
+        .with_default(30_000.0);
+
+    timeout.try_into_js(&mut cx)
+}
+
+#[derive(TryFromJs)]
+#[neon(deny_unknown_fields, rename_all = "camelCase")]
+pub struct RequestOptions {
+    timeout: Option<f64>,
+    #[neon(default = 3, range(min = 0, max = 10))]
+    max_retries: f64,
+    #[neon(default, with = "parse_mode")]
+    mode: Mode,
+    #[neon(rename = "cache")]
+    cache_options: Option<CacheOptions>,
+}
+
+#[derive(Default)]
+pub enum Mode {
+    #[default]
+    Fast,
+    Safe,
+}
+
+fn parse_mode(mode: String) -> Result<Mode, String> {
+    match mode.as_str() {
+        "fast" => Ok(Mode::Fast),
+        "safe" => Ok(Mode::Safe),
+        _ => Err(format!("expected \"fast\" or \"safe\", found {:?}", mode)),
+    }
+}
+
+#[derive(TryFromJs)]
+pub struct CacheOptions {
+    #[neon(range(min = 1))]
+    size: f64,
+    name: String,
+}
+
+// Returns the extracted options as an object, with the field names of `RequestOptions`
+pub fn parse_request_options(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let options = cx.extract_argument::<RequestOptions>(0)?;
+    let obj = cx.empty_object();
+    let timeout = options.timeout.try_into_js(&mut cx)?;
+    let max_retries = cx.number(options.max_retries);
+    let mode = cx.string(match options.mode {
+        Mode::Fast => "fast",
+        Mode::Safe => "safe",
+    });
+    let cache_options: Handle<JsValue> = match options.cache_options {
+        Some(cache) => {
+            let cache_obj = cx.empty_object();
+            let size = cx.number(cache.size);
+            let name = cx.string(cache.name);
+
+            cache_obj.set(&mut cx, "size", size)?;
+            cache_obj.set(&mut cx, "name", name)?;
+            cache_obj.upcast()
+        }
+        None => cx.null().upcast(),
+    };
+
+    obj.set(&mut cx, "timeout", timeout)?;
+    obj.set(&mut cx, "max_retries", max_retries)?;
+    obj.set(&mut cx, "mode", mode)?;
+    obj.set(&mut cx, "cache_options", cache_options)?;
+
+    Ok(obj)
+}
+
+pub fn tuple_to_array(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+    let len = name.len() as u32;
+
+    (name, len, None::<bool>).try_into_js(&mut cx)
+}
+
+pub fn tuple_to_object(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+    let len = name.len() as u32;
+
+    Named(["name", "length"], (name, len)).try_into_js(&mut cx)
+}
+
+pub fn tuple_in_task(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let data = cx.argument::<JsString>(0)?.value(&mut cx);
+    let promise = cx
+        .task(move || {
+            let checksum = data.bytes().fold(0u32, |sum, b| sum + b as u32);
+
+            (data.len() as u32, checksum)
+        })
+        .into_promise();
+
+    Ok(promise)
+}
+
+pub fn named_tuple_in_task(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let data = cx.argument::<JsString>(0)?.value(&mut cx);
+    let promise = cx
+        .task(move || {
+            let checksum = data.bytes().fold(0u32, |sum, b| sum + b as u32);
+
+            Named(["bytesWritten", "checksum"], (data.len() as u32, checksum))
+        })
+        .into_promise();
+
+    Ok(promise)
+}
+
+// Converts `[number, string, boolean | null]` to Rust and back, doubling the number,
+// upper-casing the string and negating the boolean; generic over the context
+fn convert_values<'a, C: Context<'a>>(
+    cx: &mut C,
+    values: Handle<'a, JsArray>,
+) -> JsResult<'a, JsArray> {
+    let n = values.get_value(cx, 0)?;
+    let n: f64 = cx.try_from_js(n)?;
+    let s = values.get_value(cx, 1)?;
+    let s: String = cx.try_from_js(s)?;
+    let flag = values.get_value(cx, 2)?;
+    let flag: Option<bool> = cx.try_from_js(flag)?;
+
+    cx.try_into_js((n * 2.0, s.to_uppercase(), flag.map(|b| !b)))
+}
+
+fn convert_and_call<'a, C: Context<'a>>(
+    cx: &mut C,
+    values: Root<JsArray>,
+    callback: Root<JsFunction>,
+) -> NeonResult<()> {
+    let values = values.into_inner(cx);
+    let result = convert_values(cx, values)?;
+
+    callback.into_inner(cx).call_with(cx).arg(result).exec(cx)
+}
+
+pub fn convert_in_function(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let values = cx.argument::<JsArray>(0)?;
+
+    convert_values(&mut cx, values)
+}
+
+pub fn convert_in_channel(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let values = cx.argument::<JsArray>(0)?.root(&mut cx);
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        channel.send(move |mut cx| convert_and_call(&mut cx, values, callback))
+    });
+
+    Ok(cx.undefined())
+}
+
+pub struct ConvertOnFinalize {
+    values: Root<JsArray>,
+    callback: Root<JsFunction>,
+}
+
+impl Finalize for ConvertOnFinalize {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        let _ = convert_and_call(cx, self.values, self.callback);
+    }
+}
+
+pub fn convert_in_finalize(mut cx: FunctionContext) -> JsResult<JsBox<ConvertOnFinalize>> {
+    let values = cx.argument::<JsArray>(0)?.root(&mut cx);
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+
+    Ok(cx.boxed(ConvertOnFinalize { values, callback }))
+}
@@ -0,0 +1,88 @@
+use neon::{
+    prelude::*,
+    types::{
+        extract::{TryFromJs, TryIntoJs},
+        JsUrl,
+    },
+};
+
+pub fn url_parts(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let url = cx.argument::<JsUrl>(0)?;
+    let obj = cx.empty_object();
+    let href = url.href(&mut cx)?;
+    let href = cx.string(href);
+    let origin = url.origin(&mut cx)?;
+    let origin = cx.string(origin);
+    let pathname = url.pathname(&mut cx)?;
+    let pathname = cx.string(pathname);
+
+    obj.set(&mut cx, "href", href)?;
+    obj.set(&mut cx, "origin", origin)?;
+    obj.set(&mut cx, "pathname", pathname)?;
+
+    if let Some(page) = url.search_params_get(&mut cx, "page")? {
+        let page = cx.string(page);
+
+        obj.set(&mut cx, "page", page)?;
+    }
+
+    Ok(obj)
+}
+
+pub fn parse_url(mut cx: FunctionContext) -> JsResult<JsUrl> {
+    let input = cx.argument::<JsString>(0)?.value(&mut cx);
+    let base = match cx.argument_opt(1) {
+        Some(base) => Some(
+            base.downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx),
+        ),
+        None => None,
+    };
+
+    cx.url(&input, base.as_deref())
+}
+
+pub fn is_url(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let value = cx.argument::<JsValue>(0)?;
+    let is_url = value.is_a::<JsUrl, _>(&mut cx);
+
+    Ok(cx.boolean(is_url))
+}
+
+// Checks whether the argument is a URL while an exception is pending, returning the
+// result of the check and the exception
+pub fn is_url_while_throwing(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let value = cx.argument::<JsValue>(0)?;
+    let mut is_url = true;
+    let err = cx
+        .try_catch(|cx| {
+            let thrown = cx.throw_error::<_, ()>("pending");
+
+            is_url = value.is_a::<JsUrl, _>(cx);
+            thrown
+        })
+        .unwrap_err();
+    let is_url = cx.boolean(is_url);
+    let result = cx.empty_array();
+
+    result.set(&mut cx, 0, is_url)?;
+    result.set(&mut cx, 1, err)?;
+
+    Ok(result)
+}
+
+pub fn url_crate_href(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let input = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    match url::Url::parse(&input) {
+        Ok(url) => Ok(cx.string(url.as_str()).upcast()),
+        Err(_) => Ok(cx.null().upcast()),
+    }
+}
+
+pub fn url_crate_round_trip(mut cx: FunctionContext) -> JsResult<JsUrl> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let url = url::Url::try_from_js(&mut cx, arg)?;
+
+    url.try_into_js(&mut cx)
+}
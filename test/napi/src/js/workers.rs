@@ -1,9 +1,18 @@
-use std::{convert::TryFrom, sync::Mutex, thread, time::Duration};
+use std::{
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
 
 use once_cell::sync::{Lazy, OnceCell};
 
 use neon::prelude::*;
 use neon::thread::LocalKey;
+use neon::worker::Port;
 
 pub fn get_and_replace(mut cx: FunctionContext) -> JsResult<JsValue> {
     static OBJECT: Lazy<Mutex<Option<Root<JsObject>>>> = Lazy::new(Default::default);
@@ -123,3 +132,168 @@ pub fn box_channels(mut cx: FunctionContext) -> JsResult<JsBox<Channels>> {
 
     Ok(cx.boxed(channels))
 }
+
+pub fn env_info(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let env_id = cx.env_id();
+    let is_main_thread = cx.is_main_thread();
+    let info = cx.empty_object();
+    let env_id = cx.number(env_id as f64);
+    let is_main_thread = cx.boolean(is_main_thread);
+
+    info.set(&mut cx, "envId", env_id)?;
+    info.set(&mut cx, "isMainThread", is_main_thread)?;
+
+    Ok(info)
+}
+
+static SERIALIZED: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+pub fn stash_serialized(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let value = cx.argument::<JsValue>(0)?;
+    let data = cx.serialize_for_transfer(value)?;
+
+    *SERIALIZED.lock().unwrap() = data;
+
+    Ok(cx.undefined())
+}
+
+pub fn unstash_serialized(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let data = SERIALIZED.lock().unwrap().clone();
+
+    cx.deserialize_from_transfer(&data)
+}
+
+pub fn port_post(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let port = cx.argument::<JsObject>(0)?;
+    let port = Port::from_object(&mut cx, port)?;
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+
+    thread::spawn(move || {
+        for i in 0..n {
+            let _ = port.post(move |cx| Ok(cx.number(i).upcast()));
+        }
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn port_post_transfer(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let port = cx.argument::<JsObject>(0)?;
+    let port = Port::from_object(&mut cx, port)?;
+    let len = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+
+    thread::spawn(move || {
+        let _ = port.post_with_transfer(move |cx| {
+            let buf = JsArrayBuffer::new(cx, len)?.upcast();
+
+            Ok((buf, vec![buf]))
+        });
+    });
+
+    Ok(cx.undefined())
+}
+
+// Doubles each number received on a Rust thread and posts it back
+pub fn port_double(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let port = cx.argument::<JsObject>(0)?;
+    let port = Port::from_object(&mut cx, port)?;
+    let reply = port.clone();
+
+    port.on_message(&mut cx, move |cx, message| {
+        let n = message.downcast_or_throw::<JsNumber, _>(cx)?.value(cx);
+        let reply = reply.clone();
+
+        thread::spawn(move || {
+            let _ = reply.post(move |cx| Ok(cx.number(n * 2.0).upcast()));
+        });
+
+        Ok(())
+    })?;
+
+    Ok(cx.undefined())
+}
+
+pub fn port_on_close(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let port = cx.argument::<JsObject>(0)?;
+    let port = Port::from_object(&mut cx, port)?;
+    let cb = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let closed = port.clone();
+
+    port.on_close(&mut cx, move |cx| {
+        let cb = cb.into_inner(cx);
+        let is_closed = cx.boolean(closed.is_closed());
+
+        cb.call_with(cx).arg(is_closed).exec(cx)
+    })?;
+
+    Ok(cx.undefined())
+}
+
+pub fn intrinsics(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let intrinsics = cx.intrinsics();
+    let obj = cx.empty_object();
+    let functions = [
+        ("object", intrinsics.object(&mut cx)?),
+        ("objectFreeze", intrinsics.object_freeze(&mut cx)?),
+        (
+            "objectDefineProperty",
+            intrinsics.object_define_property(&mut cx)?,
+        ),
+        ("functionBind", intrinsics.function_bind(&mut cx)?),
+        ("array", intrinsics.array(&mut cx)?),
+        ("promise", intrinsics.promise(&mut cx)?),
+        ("proxy", intrinsics.proxy(&mut cx)?),
+        ("jsonParse", intrinsics.json_parse(&mut cx)?),
+        ("jsonStringify", intrinsics.json_stringify(&mut cx)?),
+    ];
+
+    for (key, f) in functions {
+        obj.set(&mut cx, key, f)?;
+    }
+
+    let symbol_iterator = intrinsics.symbol_iterator(&mut cx)?;
+    let symbol_async_iterator = intrinsics.symbol_async_iterator(&mut cx)?;
+
+    obj.set(&mut cx, "symbolIterator", symbol_iterator)?;
+    obj.set(&mut cx, "symbolAsyncIterator", symbol_async_iterator)?;
+
+    Ok(obj)
+}
+
+static PROCESS_INIT_COUNT: AtomicUsize = AtomicUsize::new(0);
+static MAIN_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// Called once per process by `#[neon::main(process_init = "...")]`
+pub fn process_init() {
+    assert_eq!(
+        MAIN_COUNT.load(Ordering::SeqCst),
+        0,
+        "main ran before process_init"
+    );
+
+    // Widen the window for a concurrently loading environment to observe a partially
+    // completed initialization
+    thread::sleep(Duration::from_millis(50));
+    PROCESS_INIT_COUNT.fetch_add(1, Ordering::SeqCst);
+}
+
+pub fn export_process_init_info(cx: &mut ModuleContext) -> NeonResult<()> {
+    // Captured while `main` runs, to check that `process_init` already completed
+    let count = PROCESS_INIT_COUNT.load(Ordering::SeqCst);
+    let count = cx.number(count as f64);
+
+    MAIN_COUNT.fetch_add(1, Ordering::SeqCst);
+    cx.export_value("processInitCountAtMain", count)?;
+    cx.export_function("process_init_info", process_init_info)
+}
+
+pub fn process_init_info(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let info = cx.empty_object();
+    let process_init_count = cx.number(PROCESS_INIT_COUNT.load(Ordering::SeqCst) as f64);
+    let main_count = cx.number(MAIN_COUNT.load(Ordering::SeqCst) as f64);
+
+    info.set(&mut cx, "processInitCount", process_init_count)?;
+    info.set(&mut cx, "mainCount", main_count)?;
+
+    Ok(info)
+}
@@ -1,29 +1,55 @@
-use neon::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use neon::{context::ModuleBuilder, prelude::*};
 
 use crate::js::{
-    arrays::*, boxed::*, coercions::*, date::*, errors::*, functions::*, numbers::*, objects::*,
-    strings::*, threads::*, typedarrays::*, types::*,
+    abort_signals::*, arrays::*, async_resources::*, atomics::*, boxed::*, coercions::*,
+    console::*, date::*, diagnostics::*, errors::*, functions::*, iterators::*, json::*,
+    numbers::*, objects::*, process_hooks::*, proxies::*, roots::*, runtime::*, schema::*,
+    streams::*, strings::*, test_support::*, threads::*, traces::*, typedarrays::*, types::*,
+    urls::*,
 };
 
 mod js {
+    pub mod abort_signals;
     pub mod arrays;
+    pub mod async_resources;
+    pub mod atomics;
     pub mod bigint;
     pub mod boxed;
     pub mod coercions;
+    pub mod console;
     pub mod date;
+    pub mod diagnostics;
     pub mod errors;
     pub mod functions;
     pub mod futures;
+    pub mod iterators;
+    pub mod json;
     pub mod numbers;
     pub mod objects;
+    pub mod process_hooks;
+    pub mod proxies;
+    pub mod registry;
+    pub mod roots;
+    pub mod runtime;
+    pub mod schema;
+    pub mod streams;
     pub mod strings;
+    pub mod test_support;
     pub mod threads;
+    pub mod traces;
     pub mod typedarrays;
     pub mod types;
+    pub mod urls;
     pub mod workers;
 }
 
-#[neon::main]
+#[neon::main(
+    min_napi = 8,
+    min_node = "18.0",
+    process_init = "js::workers::process_init"
+)]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
     let greeting = cx.string("Hello, World!");
     let greeting_copy = greeting.value(&mut cx);
@@ -108,11 +134,44 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
 
     cx.export_function("add1", add1)?;
 
+    // Module builder
+    static LAZY_INITS: AtomicU32 = AtomicU32::new(0);
+
+    let version = cx.string("1.0.0");
+    let secret = cx.number(42);
+
+    ModuleBuilder::new(&mut cx)
+        .namespace("builder", |ns| {
+            ns.function("add1", add1);
+            ns.value("secret", secret).enumerable(false);
+            ns.value("version", version).read_only();
+            ns.namespace("frozen", |ns| {
+                ns.value("version", version);
+            })
+            .read_only();
+            ns.lazy("lazy", |cx| {
+                let inits = LAZY_INITS.fetch_add(1, Ordering::SeqCst) + 1;
+
+                Ok(cx.number(inits))
+            });
+            ns.lazy("lazyThrows", |cx| {
+                cx.throw_error::<_, Handle<JsValue>>("lazy failed")
+            });
+        })
+        .finish()?;
+
     cx.export_function("return_js_string", return_js_string)?;
     cx.export_function("return_js_string_utf16", return_js_string_utf16)?;
+    cx.export_function("encode_base64", encode_base64)?;
+    cx.export_function("encode_hex", encode_hex)?;
+    cx.export_function("decode_base64", decode_base64)?;
+    cx.export_function("decode_hex", decode_hex)?;
     cx.export_function("return_length_utf8", return_length_utf8)?;
     cx.export_function("return_length_utf16", return_length_utf16)?;
     cx.export_function("run_string_as_script", run_string_as_script)?;
+    cx.export_function("build_string", build_string)?;
+    cx.export_function("repeat_string_builder", repeat_string_builder)?;
+    cx.export_function("repeat_string_naive", repeat_string_naive)?;
 
     cx.export_function("return_js_number", return_js_number)?;
     cx.export_function("return_large_js_number", return_large_js_number)?;
@@ -135,6 +194,11 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
         "accept_and_return_negative_js_number",
         accept_and_return_negative_js_number,
     )?;
+    cx.export_function("number_checked_u64", number_checked_u64)?;
+    cx.export_function("number_checked_i64", number_checked_i64)?;
+    cx.export_function("usize_into_js", usize_into_js)?;
+    cx.export_function("u64_into_js_lossy", u64_into_js_lossy)?;
+    cx.export_function("int_or_bigint", int_or_bigint)?;
 
     cx.export_function("return_js_function", return_js_function)?;
     cx.export_function("call_js_function", call_js_function)?;
@@ -178,6 +242,27 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
         "call_js_function_with_heterogeneous_tuple",
         call_js_function_with_heterogeneous_tuple,
     )?;
+    cx.export_function(
+        "call_js_function_with_tuple_args",
+        call_js_function_with_tuple_args,
+    )?;
+    cx.export_function(
+        "construct_js_function_with_tuple_args",
+        construct_js_function_with_tuple_args,
+    )?;
+    cx.export_function(
+        "bind_js_function_with_tuple_args",
+        bind_js_function_with_tuple_args,
+    )?;
+    cx.export_function(
+        "call_js_function_with_args_from_iter",
+        call_js_function_with_args_from_iter,
+    )?;
+    cx.export_function(
+        "call_js_function_expecting_string",
+        call_js_function_expecting_string,
+    )?;
+    cx.export_function("bind_js_function", bind_js_function)?;
     cx.export_function("construct_js_function", construct_js_function)?;
     cx.export_function(
         "construct_js_function_idiomatically",
@@ -187,7 +272,28 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
         "construct_js_function_with_overloaded_result",
         construct_js_function_with_overloaded_result,
     )?;
+    cx.export_function("construct_js_class", construct_js_class)?;
+    cx.export_function(
+        "construct_js_class_idiomatically",
+        construct_js_class_idiomatically,
+    )?;
     cx.export_function("num_arguments", num_arguments)?;
+    cx.export_function("sum_rest_arguments", sum_rest_arguments)?;
+    cx.export_function("count_arguments_iter", count_arguments_iter)?;
+    cx.export_function("argument_or_default", argument_or_default)?;
+    cx.export_function("describe_args_info", describe_args_info)?;
+    cx.export_function("noop", noop)?;
+    cx.export_function("sum_six_arguments", sum_six_arguments)?;
+    cx.export_function("call_with_vec_args", call_with_vec_args)?;
+    cx.export_function("call_with_tuple_args", call_with_tuple_args)?;
+    cx.export_function("call_with_options_args", call_with_options_args)?;
+    cx.export_function("console_log_args", console_log_args)?;
+    cx.export_function("call_with_empty_args", call_with_empty_args)?;
+    cx.export_function("call0_loop", call0_loop)?;
+    cx.export_function("call0_js_function", call0_js_function)?;
+    cx.export_function("call1_js_function", call1_js_function)?;
+    cx.export_function("forwarding_wrapper", forwarding_wrapper)?;
+    cx.export_function("forwarded_new_target", forwarded_new_target)?;
     cx.export_function("return_this", return_this)?;
     cx.export_function("require_object_this", require_object_this)?;
     cx.export_function("is_argument_zero_some", is_argument_zero_some)?;
@@ -201,8 +307,63 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("return_js_array_with_number", return_js_array_with_number)?;
     cx.export_function("return_js_array_with_string", return_js_array_with_string)?;
     cx.export_function("read_js_array", read_js_array)?;
+    cx.export_function("push_js_array", push_js_array)?;
+    cx.export_function("js_array_from_arguments", js_array_from_arguments)?;
+    cx.export_function("js_array_from_range", js_array_from_range)?;
+    cx.export_function("js_array_with_capacity", js_array_with_capacity)?;
+    cx.export_function("iter_js_array", iter_js_array)?;
+    cx.export_function("iter_js_array_with_callback", iter_js_array_with_callback)?;
+    cx.export_function("chunked_range_array", chunked_range_array)?;
+    cx.export_function("chunked_sum", chunked_sum)?;
+    cx.export_function("chunked_ranges", chunked_ranges)?;
+    cx.export_function("read_js_array_into", read_js_array_into)?;
+    cx.export_function("write_js_array_from", write_js_array_from)?;
+    cx.export_function("js_array_to_vec_f64", js_array_to_vec_f64)?;
+    cx.export_function("js_array_to_vec_string", js_array_to_vec_string)?;
+    cx.export_function("js_array_to_vec_i32", js_array_to_vec_i32)?;
+    cx.export_function("js_array_to_vec_u32", js_array_to_vec_u32)?;
+    cx.export_function("js_array_count_true", js_array_count_true)?;
+    cx.export_function("js_array_from_f64_slice", js_array_from_f64_slice)?;
+    cx.export_function("sum_js_array_to_vec_downcast", sum_js_array_to_vec_downcast)?;
+    cx.export_function("js_array_from_number_handles", js_array_from_number_handles)?;
+    cx.export_function("sum_js_array_naive", sum_js_array_naive)?;
+    cx.export_function("count_js_array_naive", count_js_array_naive)?;
+    cx.export_function("count_js_array_read_into", count_js_array_read_into)?;
+
+    cx.export_function("atomics_view_len", atomics_view_len)?;
+    cx.export_function("atomics_fetch_add", atomics_fetch_add)?;
+    cx.export_function("atomics_notify_from_thread", atomics_notify_from_thread)?;
+    cx.export_function("atomics_wait_on_thread", atomics_wait_on_thread)?;
+    cx.export_function("atomics_wait_and_notify", atomics_wait_and_notify)?;
+    cx.export_function("atomics_notify_one_element", atomics_notify_one_element)?;
 
     cx.export_function("to_string", to_string)?;
+    cx.export_function("coerce_to_string", coerce_to_string)?;
+    cx.export_function("coerce_to_number", coerce_to_number)?;
+    cx.export_function("coerce_to_bool", coerce_to_bool)?;
+    cx.export_function("coerce_to_object", coerce_to_object)?;
+
+    cx.export_function("collect_iterable", collect_iterable)?;
+    cx.export_function("take_from_iterable", take_from_iterable)?;
+    cx.export_function("boxed_range", boxed_range)?;
+    cx.export_function("boxed_range_dropped", boxed_range_dropped)?;
+
+    cx.export_function("json_parse", json_parse)?;
+    cx.export_function("json_stringify", json_stringify)?;
+    cx.export_function("build_object_graph", build_object_graph)?;
+    cx.export_function("build_object_json", build_object_json)?;
+
+    cx.export_function("js_value_diff", js_value_diff)?;
+    cx.export_function("assert_js_eq_json", assert_js_eq_json)?;
+    cx.export_function("expect_throw_message", expect_throw_message)?;
+    cx.export_function("recorded_calls", recorded_calls)?;
+    cx.export_function("test_support_self_test", test_support_self_test)?;
+
+    cx.export_function("lazy_namespace", lazy_namespace)?;
+    cx.export_function("proxy_function", proxy_function)?;
+    cx.export_function("revocable_proxy", revocable_proxy)?;
+    cx.export_function("array_view", array_view)?;
+    cx.export_function("writable_array_view", writable_array_view)?;
 
     cx.export_function("return_js_global_object", return_js_global_object)?;
     cx.export_function("return_js_object", return_js_object)?;
@@ -216,6 +377,18 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("seal_js_object", seal_js_object)?;
 
     cx.export_function("return_array_buffer", return_array_buffer)?;
+    cx.export_function("create_buffer_pool", create_buffer_pool)?;
+    cx.export_function("buffer_pool_alloc", buffer_pool_alloc)?;
+    cx.export_function("buffer_pool_slab_count", buffer_pool_slab_count)?;
+    cx.export_function("buffer_from_unique_bytes", buffer_from_unique_bytes)?;
+    cx.export_function("buffer_from_shared_bytes", buffer_from_shared_bytes)?;
+    cx.export_function("buffer_address", buffer_address)?;
+    cx.export_function("bytes_round_trip", bytes_round_trip)?;
+    cx.export_function("bytes_after_detach", bytes_after_detach)?;
+    cx.export_function("stash_bytes", stash_bytes)?;
+    cx.export_function("sum_stashed_bytes", sum_stashed_bytes)?;
+    cx.export_function("clear_stashed_bytes", clear_stashed_bytes)?;
+    cx.export_function("alloc_buffer", alloc_buffer)?;
     cx.export_function(
         "return_array_buffer_from_slice",
         return_array_buffer_from_slice,
@@ -237,10 +410,35 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     )?;
     cx.export_function("read_u8_typed_array", read_u8_typed_array)?;
     cx.export_function("copy_typed_array", copy_typed_array)?;
+    cx.export_function("try_get_typed_array_element", try_get_typed_array_element)?;
+    cx.export_function("get_typed_array_element", get_typed_array_element)?;
+    cx.export_function("set_typed_array_element", set_typed_array_element)?;
+    cx.export_function(
+        "swap_typed_array_elements_with_callback",
+        swap_typed_array_elements_with_callback,
+    )?;
+    cx.export_function("sum_scattered_with_get", sum_scattered_with_get)?;
+    cx.export_function("sum_scattered_with_slice", sum_scattered_with_slice)?;
+    cx.export_function(
+        "sum_scattered_with_held_slice",
+        sum_scattered_with_held_slice,
+    )?;
+    cx.export_function("array_buffer_from_reader", array_buffer_from_reader)?;
+    cx.export_function("buffer_from_reader", buffer_from_reader)?;
+    cx.export_function("array_buffer_from_iter", array_buffer_from_iter)?;
+    cx.export_function("buffer_from_iter_filtered", buffer_from_iter_filtered)?;
     cx.export_function("return_uninitialized_buffer", return_uninitialized_buffer)?;
     cx.export_function("return_buffer", return_buffer)?;
     cx.export_function("return_external_buffer", return_external_buffer)?;
     cx.export_function("return_external_array_buffer", return_external_array_buffer)?;
+    cx.export_function(
+        "return_external_buffer_with_policy",
+        return_external_buffer_with_policy,
+    )?;
+    cx.export_function(
+        "return_external_array_buffer_with_policy",
+        return_external_array_buffer_with_policy,
+    )?;
     cx.export_function(
         "return_int8array_from_arraybuffer",
         return_int8array_from_arraybuffer,
@@ -277,12 +475,29 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("build_f64_region", build_f64_region)?;
     cx.export_function("read_buffer_with_lock", read_buffer_with_lock)?;
     cx.export_function("read_buffer_with_borrow", read_buffer_with_borrow)?;
+    cx.export_function("decode_text", decode_text)?;
+    cx.export_function("encode_text", encode_text)?;
+    cx.export_function("encoding_name", encoding_name)?;
     cx.export_function("write_buffer_with_lock", write_buffer_with_lock)?;
     cx.export_function("write_buffer_with_borrow_mut", write_buffer_with_borrow_mut)?;
     cx.export_function("byte_length", byte_length)?;
     cx.export_function("call_nullary_method", call_nullary_method)?;
     cx.export_function("call_unary_method", call_unary_method)?;
     cx.export_function("call_symbol_method", call_symbol_method)?;
+    cx.export_function("structured_clone", structured_clone)?;
+    cx.export_function("symbol_property_round_trip", symbol_property_round_trip)?;
+    cx.export_function("set_large_index", set_large_index)?;
+    cx.export_function("has_index", has_index)?;
+    cx.export_function("has_property", has_property)?;
+    cx.export_function("delete_property", delete_property)?;
+    cx.export_function("delete_index", delete_index)?;
+    cx.export_function("define_property", define_property)?;
+    cx.export_function("define_index", define_index)?;
+    cx.export_function("finalized_count", finalized_count)?;
+    cx.export_function("add_counting_finalizer", add_counting_finalizer)?;
+    cx.export_function("cancel_finalizer", cancel_finalizer)?;
+    cx.export_function("finalizer_is_pending", finalizer_is_pending)?;
+    cx.export_function("add_context_finalizer", add_context_finalizer)?;
 
     cx.export_function("create_date", create_date)?;
     cx.export_function("get_date_value", get_date_value)?;
@@ -293,6 +508,23 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("nan_dates", nan_dates)?;
     cx.export_function("create_date_from_value", create_date_from_value)?;
     cx.export_function("create_and_get_invalid_date", create_and_get_invalid_date)?;
+    cx.export_function("chrono_round_trip", chrono_round_trip)?;
+    cx.export_function("chrono_from_nanos", chrono_from_nanos)?;
+    cx.export_function("chrono_max", chrono_max)?;
+    cx.export_function("chrono_naive_date", chrono_naive_date)?;
+    cx.export_function("chrono_naive_date_to_js", chrono_naive_date_to_js)?;
+    cx.export_function("chrono_iso_8601", chrono_iso_8601)?;
+    cx.export_function("chrono_iso_8601_naive_date", chrono_iso_8601_naive_date)?;
+    cx.export_function("time_round_trip", time_round_trip)?;
+    cx.export_function("time_from_nanos", time_from_nanos)?;
+    cx.export_function("time_iso_8601", time_iso_8601)?;
+
+    cx.export_function("url_parts", url_parts)?;
+    cx.export_function("parse_url", parse_url)?;
+    cx.export_function("is_url", is_url)?;
+    cx.export_function("is_url_while_throwing", is_url_while_throwing)?;
+    cx.export_function("url_crate_href", url_crate_href)?;
+    cx.export_function("url_crate_round_trip", url_crate_round_trip)?;
 
     cx.export_function("is_array", is_array)?;
     cx.export_function("is_array_buffer", is_array_buffer)?;
@@ -306,21 +538,118 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("is_string", is_string)?;
     cx.export_function("is_undefined", is_undefined)?;
     cx.export_function("strict_equals", strict_equals)?;
+    cx.export_function("same_value", same_value)?;
+    cx.export_function("same_value_zero", same_value_zero)?;
+    cx.export_function("value_kind", value_kind)?;
+    cx.export_function("value_kind_with_is_a", value_kind_with_is_a)?;
+    cx.export_function("visit_value", visit_value)?;
+    cx.export_function("count_shapes_with_visitor", count_shapes_with_visitor)?;
+    cx.export_function("count_shapes_with_downcast", count_shapes_with_downcast)?;
+    cx.export_function("traces_take", traces_take)?;
+    cx.export_function("traces_channel_send", traces_channel_send)?;
+    cx.export_function("traces_task", traces_task)?;
+
+    cx.export_function("loop_monitor_new", loop_monitor_new)?;
+    cx.export_function("loop_monitor_snapshot", loop_monitor_snapshot)?;
+    cx.export_function("loop_monitor_reset", loop_monitor_reset)?;
+
+    cx.export_function("root_set_new", root_set_new)?;
+    cx.export_function("root_set_insert", root_set_insert)?;
+    cx.export_function("root_set_get", root_set_get)?;
+    cx.export_function("root_set_remove", root_set_remove)?;
+    cx.export_function("root_set_contains", root_set_contains)?;
+    cx.export_function("root_set_len", root_set_len)?;
+    cx.export_function("retain_with_roots", retain_with_roots)?;
+    cx.export_function("retain_with_root_set", retain_with_root_set)?;
+    cx.export_function("root_napi_ref_round_trip", root_napi_ref_round_trip)?;
+    cx.export_function("root_napi_ref_clone", root_napi_ref_clone)?;
+
+    cx.export_function("option_or_null", option_or_null)?;
+    cx.export_function("option_or_undefined", option_or_undefined)?;
+    cx.export_function("option_in_task", option_in_task)?;
+    cx.export_function("option_from_js", option_from_js)?;
+    cx.export_function("or_undefined_from_js", or_undefined_from_js)?;
+    cx.export_function("maybe_undefined_argument", maybe_undefined_argument)?;
+    cx.export_function("maybe_undefined_round_trip", maybe_undefined_round_trip)?;
+    cx.export_function("request_timeout", request_timeout)?;
+    cx.export_function("parse_request_options", parse_request_options)?;
+
+    cx.export_function("tuple_to_array", tuple_to_array)?;
+    cx.export_function("tuple_to_object", tuple_to_object)?;
+    cx.export_function("tuple_in_task", tuple_in_task)?;
+    cx.export_function("named_tuple_in_task", named_tuple_in_task)?;
+    cx.export_function("convert_in_function", convert_in_function)?;
+    cx.export_function("convert_in_channel", convert_in_channel)?;
+    cx.export_function("convert_in_finalize", convert_in_finalize)?;
+
+    cx.export_function("schema_extract_options", schema_extract_options)?;
 
     cx.export_function("new_error", new_error)?;
     cx.export_function("new_type_error", new_type_error)?;
     cx.export_function("new_range_error", new_range_error)?;
     cx.export_function("throw_error", throw_error)?;
+    cx.export_function("error_with_stack_note", error_with_stack_note)?;
+    cx.export_function("error_captured_above", error_captured_above)?;
+    cx.export_function("task_throw_error", task_throw_error)?;
     cx.export_function("downcast_error", downcast_error)?;
+    cx.export_function("expect_function_argument", expect_function_argument)?;
+    cx.export_function("own_function_name", own_function_name)?;
+    cx.export_function(
+        "named_function_expecting_string",
+        named_function_expecting_string,
+    )?;
+    cx.export_function(
+        "unnamed_function_expecting_string",
+        unnamed_function_expecting_string,
+    )?;
+    cx.export_function("downcast_or_throw_array", downcast_or_throw_array)?;
+    cx.export_result_function("parse_positive", parse_positive)?;
+    cx.export_result_function("result_after_throw", result_after_throw)?;
+    cx.export_function("init_database_error", init_database_error)?;
+    cx.export_function("query_or_throw", query_or_throw)?;
+    cx.export_result_function("query_result", query_result)?;
+    cx.export_function("query_task", query_task)?;
+    cx.export_function("throw_late_error", throw_late_error)?;
+    cx.export_function("register_late_error", register_late_error)?;
+    cx.export_function("register_error_class", register_error_class)?;
+    cx.export_function("throw_custom_error", throw_custom_error)?;
+    cx.export_function("custom_error", custom_error)?;
 
     cx.export_function("panic", panic)?;
     cx.export_function("panic_after_throw", panic_after_throw)?;
 
     cx.export_function("throw_and_catch", throw_and_catch)?;
     cx.export_function("call_and_catch", call_and_catch)?;
+    cx.export_function("call_and_catch_pending", call_and_catch_pending)?;
+    cx.export_function(
+        "catch_pending_when_not_throwing",
+        catch_pending_when_not_throwing,
+    )?;
+    cx.export_function("catch_strings", catch_strings)?;
     cx.export_function("get_number_or_default", get_number_or_default)?;
     cx.export_function("is_construct", is_construct)?;
+
+    // Closures capturing state computed at load time, and functions created with `bind`
+    let load_time_greeting = greeting_copy.value(&mut cx);
+    cx.export_function("exported_closure", move |mut cx| {
+        Ok(cx.string(&load_time_greeting))
+    })?;
+
+    let sum_six = JsFunction::new(&mut cx, sum_six_arguments)?;
+    let this = cx.undefined();
+    let sum_bound = sum_six.bind(&mut cx, this, (1.0, 2.0, 3.0, 4.0, 5.0))?;
+    cx.export_value("exported_bound_function", sum_bound)?;
+
+    cx.export_function("construct_only", construct_only)?;
     cx.export_function("caller_with_drop_callback", caller_with_drop_callback)?;
+    cx.export_function("return_mut_counter", return_mut_counter)?;
+    cx.export_function("return_mut_reentrant", return_mut_reentrant)?;
+    cx.export_function("return_function_from_context", return_function_from_context)?;
+    cx.export_function(
+        "return_js_function_with_options",
+        return_js_function_with_options,
+    )?;
+    cx.export_function("get_js_function_name", get_js_function_name)?;
 
     cx.export_function("count_called", {
         let n = std::cell::RefCell::new(0);
@@ -345,7 +674,21 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("ref_person_greet", ref_person_greet)?;
     cx.export_function("ref_person_set_name", ref_person_set_name)?;
     cx.export_function("ref_person_fail", ref_person_fail)?;
+    cx.export_function("ref_person_greet_reentrant", ref_person_greet_reentrant)?;
     cx.export_function("external_unit", external_unit)?;
+    cx.export_function("shared_new", shared_new)?;
+    cx.export_function("shared_name", shared_name)?;
+    cx.export_function("shared_to_js", shared_to_js)?;
+    cx.export_function("shared_handle", shared_handle)?;
+    cx.export_function("shared_handle_drop", shared_handle_drop)?;
+    cx.export_function("shared_handle_drop_on_thread", shared_handle_drop_on_thread)?;
+    cx.export_function("shared_handle_finalize", shared_handle_finalize)?;
+    cx.export_function("rwlock_new", rwlock_new)?;
+    cx.export_function("rwlock_sum", rwlock_sum)?;
+    cx.export_function("rwlock_is_reading", rwlock_is_reading)?;
+    cx.export_function("rwlock_push", rwlock_push)?;
+    cx.export_function("rwlock_try_push", rwlock_try_push)?;
+    cx.export_function("rwlock_send_while_reading", rwlock_send_while_reading)?;
 
     cx.export_function("useless_root", useless_root)?;
     cx.export_function("thread_callback", thread_callback)?;
@@ -353,9 +696,110 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("greeter_new", greeter_new)?;
     cx.export_function("greeter_greet", greeter_greet)?;
     cx.export_function("leak_channel", leak_channel)?;
+    cx.export_function("channel_objects", channel_objects)?;
+    cx.export_function("channel_unref_clone", channel_unref_clone)?;
+    cx.export_function("tsfn_count", tsfn_count)?;
+    cx.export_function("channel_count", channel_count)?;
+    cx.export_function("tsfn_try_call_full", tsfn_try_call_full)?;
+    cx.export_function("tsfn_abort", tsfn_abort)?;
+    cx.export_function("tsfn_blocked_producer", tsfn_blocked_producer)?;
+    cx.export_function("leak_tsfn", leak_tsfn)?;
+    cx.export_function("emitter_count", emitter_count)?;
+    cx.export_function("callback_sum", callback_sum)?;
+    cx.export_function("callback_call_with", callback_call_with)?;
+    cx.export_function("callback_call_error", callback_call_error)?;
+    cx.export_function("root_call_threaded", root_call_threaded)?;
+    cx.export_function("root_into_callback", root_into_callback)?;
+    cx.export_function("callback_stash", callback_stash)?;
+    cx.export_function("callback_call_stashed", callback_call_stashed)?;
+
+    cx.export_function("async_resource_channel", async_resource_channel)?;
+    cx.export_function("async_resource_task", async_resource_task)?;
+    cx.export_function("async_resource_scope", async_resource_scope)?;
+    cx.export_function("async_resource_drop", async_resource_drop)?;
+
+    cx.export_function("abort_signal_count", abort_signal_count)?;
+    cx.export_function("abort_signal_register", abort_signal_register)?;
+    cx.export_function("abort_signal_drop", abort_signal_drop)?;
+
+    cx.export_function("stream_readable", stream_readable)?;
+    cx.export_function("stream_readable_error", stream_readable_error)?;
+    cx.export_function("stream_writable", stream_writable)?;
+    cx.export_function("stream_writable_with_ack", stream_writable_with_ack)?;
+
+    cx.export_function("console_log", console_log)?;
+    cx.export_function("console_warn_error", console_warn_error)?;
+    cx.export_function("console_log_channel", console_log_channel)?;
+    cx.export_function("console_log_task", console_log_task)?;
+
+    cx.export_function("on_uncaught_exception", on_uncaught_exception)?;
+    cx.export_function("on_unhandled_rejection", on_unhandled_rejection)?;
+    cx.export_function("on_unhandled_rejection_panic", on_unhandled_rejection_panic)?;
+    cx.export_function("remove_listener_guard", remove_listener_guard)?;
+    cx.export_function("drop_listener_guard", drop_listener_guard)?;
+    cx.export_function(
+        "drop_listener_guard_on_thread",
+        drop_listener_guard_on_thread,
+    )?;
+    cx.export_function("inspect", inspect)?;
+
+    cx.export_function("runtime_features", runtime_features)?;
+    cx.export_function("module_filename", module_filename)?;
+    cx.export_function("performance_now", performance_now)?;
+    cx.export_function("time_origin", time_origin)?;
+    cx.export_function("performance_timestamp", performance_timestamp)?;
+    cx.export_function(
+        "performance_timestamp_from_thread",
+        performance_timestamp_from_thread,
+    )?;
+    cx.export_function(
+        "set_performance_calibration_interval",
+        set_performance_calibration_interval,
+    )?;
+    cx.export_function("module_dir", module_dir)?;
+    cx.export_function("require_path_join", require_path_join)?;
+    cx.export_function("require_module", require_module)?;
+    cx.export_function("require_event_emitter", require_event_emitter)?;
+    cx.export_function("import_module", import_module)?;
+    cx.export_function("process_env", process_env)?;
+    cx.export_function("process_env_all", process_env_all)?;
+    cx.export_function("process_info", process_info)?;
     cx.export_function("drop_global_queue", drop_global_queue)?;
+    cx.export_function("deferred_raw_round_trip", deferred_raw_round_trip)?;
+    cx.export_function("deferred_raw_leak", deferred_raw_leak)?;
+    cx.export_function("set_drop_policy", set_drop_policy)?;
+    cx.export_function("set_fatal_hook_file", set_fatal_hook_file)?;
+    cx.export_function("drop_roots_from_threads", drop_roots_from_threads)?;
+    cx.export_function("send_on_drop", send_on_drop)?;
+    cx.export_function(
+        "drop_guarded_roots_from_threads",
+        drop_guarded_roots_from_threads,
+    )?;
+    cx.export_function("guarded_root_consume", guarded_root_consume)?;
+    cx.export_function("guarded_root_cancel", guarded_root_cancel)?;
+    cx.export_function("guarded_root_disarm", guarded_root_disarm)?;
     cx.export_function("channel_join", channel_join)?;
+    cx.export_function("deferred_settle_now", deferred_settle_now)?;
+    cx.export_function(
+        "deferred_settle_module_channel",
+        deferred_settle_module_channel,
+    )?;
+    cx.export_function(
+        "deferred_settle_now_from_thread",
+        deferred_settle_now_from_thread,
+    )?;
+    cx.export_function("deferred_settle_now_throw", deferred_settle_now_throw)?;
+    cx.export_function("channel_interleave_settle", channel_interleave_settle)?;
+    cx.export_function("channel_send_map", channel_send_map)?;
+    cx.export_function("channel_send_value", channel_send_value)?;
+    cx.export_function("channel_send_value_unjoined", channel_send_value_unjoined)?;
+    cx.export_function("channel_abort_queued", channel_abort_queued)?;
+    cx.export_function("channel_detach", channel_detach)?;
+    cx.export_function("channel_abort_race", channel_abort_race)?;
     cx.export_function("sum", sum)?;
+    cx.export_function("sum_then", sum_then)?;
+    cx.export_function("task_scope_squares", task_scope_squares)?;
+    cx.export_function("task_scope_fail", task_scope_fail)?;
     cx.export_function("sum_manual_promise", sum_manual_promise)?;
     cx.export_function("sum_rust_thread", sum_rust_thread)?;
     cx.export_function("leak_promise", leak_promise)?;
@@ -371,6 +815,13 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("task_custom_panic", task_custom_panic)?;
     cx.export_function("task_reject_promise", task_reject_promise)?;
     cx.export_function("task_panic_execute_promise", task_panic_execute_promise)?;
+    cx.export_function("task_custom_panic_promise", task_custom_panic_promise)?;
+    cx.export_function("task_named_panic_execute", task_named_panic_execute)?;
+    cx.export_function(
+        "task_named_panic_execute_promise",
+        task_named_panic_execute_promise,
+    )?;
+    cx.export_function("custom_panic_payload", custom_panic_payload)?;
     cx.export_function("task_panic_complete_promise", task_panic_complete_promise)?;
     cx.export_function("task_panic_throw_promise", task_panic_throw_promise)?;
     cx.export_function("deferred_settle_with_throw", deferred_settle_with_throw)?;
@@ -389,6 +840,18 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("unstash_global_object", js::workers::unstash_global_object)?;
     cx.export_function("reject_after", js::workers::reject_after)?;
     cx.export_function("box_channels", js::workers::box_channels)?;
+    cx.export_function("env_info", js::workers::env_info)?;
+    cx.export_function("stash_serialized", js::workers::stash_serialized)?;
+    cx.export_function("unstash_serialized", js::workers::unstash_serialized)?;
+    cx.export_function("port_post", js::workers::port_post)?;
+    cx.export_function("port_post_transfer", js::workers::port_post_transfer)?;
+    cx.export_function("port_double", js::workers::port_double)?;
+    cx.export_function("port_on_close", js::workers::port_on_close)?;
+    cx.export_function("intrinsics", js::workers::intrinsics)?;
+    js::workers::export_process_init_info(&mut cx)?;
+
+    // Type declarations
+    js::registry::export_registered(&mut cx)?;
 
     // Futures
     cx.export_function("lazy_async_add", js::futures::lazy_async_add)?;
@@ -397,5 +860,37 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     // JsBigInt test suite
     cx.export_function("bigint_suite", js::bigint::bigint_suite)?;
 
+    // Duplicate exports
+    cx.export_function("overwritten", |mut cx| Ok(cx.string("first")))?;
+    cx.export_function_overwrite("overwritten", |mut cx| Ok(cx.string("second")))?;
+
+    let duplicate_export = cx
+        .try_catch(|cx| cx.export_value("greeting", greeting))
+        .unwrap_err();
+
+    let duplicate_builder_export = cx
+        .try_catch(|cx| {
+            ModuleBuilder::new(cx)
+                .namespace("duplicates", |ns| {
+                    ns.value("version", version);
+                    ns.value("version", version);
+                })
+                .finish()
+        })
+        .unwrap_err();
+
+    cx.export_value("duplicateExport", duplicate_export)?;
+    cx.export_value("duplicateBuilderExport", duplicate_builder_export)?;
+
+    let exported_names = cx.empty_array();
+
+    for (i, name) in cx.exported_names().into_iter().enumerate() {
+        let name = cx.string(name);
+
+        exported_names.set(&mut cx, i as u32, name)?;
+    }
+
+    cx.export_value("exportedNames", exported_names)?;
+
     Ok(())
 }